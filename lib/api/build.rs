@@ -88,6 +88,8 @@ fn configure_validation(builder: Builder) -> Builder {
             ("UpdateCollection.collection_name", "length(min = 1, max = 255)"),
             ("UpdateCollection.optimizers_config", ""),
             ("UpdateCollection.params", ""),
+            ("UpdateCollection.vectors", ""),
+            ("UpdateCollection.wal_config", ""),
             ("UpdateCollection.timeout", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
             ("DeleteCollection.collection_name", "length(min = 1, max = 255)"),
             ("DeleteCollection.timeout", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
@@ -99,6 +101,8 @@ fn configure_validation(builder: Builder) -> Builder {
             ("OptimizersConfigDiff.vacuum_min_vector_number", "custom = \"crate::grpc::validate::validate_u64_range_min_100\""),
             ("OptimizersConfigDiff.memmap_threshold", "custom = \"crate::grpc::validate::validate_u64_range_min_1000\""),
             ("OptimizersConfigDiff.indexing_threshold", "custom = \"crate::grpc::validate::validate_u64_range_min_1000\""),
+            ("OptimizersConfigDiff.target_segment_count", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
+            ("OptimizersConfigDiff.max_segment_size_mb", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
             ("VectorsConfig.config", ""),
             ("VectorParams.size", "range(min = 1)"),
             ("VectorParamsMap.map", ""),
@@ -135,6 +139,12 @@ fn configure_validation(builder: Builder) -> Builder {
             ("RecommendBatchPoints.collection_name", "length(min = 1, max = 255)"),
             ("RecommendBatchPoints.recommend_points", ""),
             ("CountPoints.collection_name", "length(min = 1, max = 255)"),
+            ("QueryPoints.collection_name", "length(min = 1, max = 255)"),
+            ("QueryPoints.limit", "range(min = 1)"),
+            ("DiscoverPoints.collection_name", "length(min = 1, max = 255)"),
+            ("DiscoverPoints.limit", "range(min = 1)"),
+            ("SearchArithmeticPoints.collection_name", "length(min = 1, max = 255)"),
+            ("SearchArithmeticPoints.limit", "range(min = 1)"),
         ], &[])
         // Service: points_internal_service.proto
         .validates(&[
@@ -152,8 +162,15 @@ fn configure_validation(builder: Builder) -> Builder {
             ("ScrollPointsInternal.scroll_points", ""),
             ("GetPointsInternal.get_points", ""),
             ("CountPointsInternal.count_points", ""),
+            ("IdChunksInternal.collection_name", "length(min = 1, max = 255)"),
+            ("IdChunksInternal.chunks", "range(min = 1)"),
+            ("PointVersionsInternal.collection_name", "length(min = 1, max = 255)"),
+            ("WaitAppliedInternal.collection_name", "length(min = 1, max = 255)"),
+            ("WaitAppliedInternal.timeout", "range(min = 1)"),
             ("SyncPointsInternal.sync_points", ""),
             ("SyncPoints.collection_name", "length(min = 1, max = 255)"),
+            ("UpdateVectorsInternal.update_vectors", ""),
+            ("UpdatePointVectors.collection_name", "length(min = 1, max = 255)"),
         ], &[])
         // Service: raft_service.proto
         .validates(&[
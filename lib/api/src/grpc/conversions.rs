@@ -18,13 +18,16 @@ use crate::grpc::qdrant::value::Kind;
 use crate::grpc::qdrant::vectors::VectorsOptions;
 use crate::grpc::qdrant::with_payload_selector::SelectorOptions;
 use crate::grpc::qdrant::{
-    with_vectors_selector, CollectionDescription, CollectionOperationResponse, Condition, Distance,
-    FieldCondition, Filter, GeoBoundingBox, GeoPoint, GeoRadius, HasIdCondition, HealthCheckReply,
-    HnswConfigDiff, IsEmptyCondition, IsNullCondition, ListCollectionsResponse, ListValue, Match,
-    NamedVectors, PayloadExcludeSelector, PayloadIncludeSelector, PayloadIndexParams,
-    PayloadSchemaInfo, PayloadSchemaType, PointId, QuantizationConfig, QuantizationSearchParams,
-    Range, ScalarQuantization, ScoredPoint, SearchParams, Struct, TextIndexParams, TokenizerType,
-    Value, ValuesCount, Vector, Vectors, VectorsSelector, WithPayloadSelector, WithVectorsSelector,
+    with_vectors_selector, CollectionDescription, CollectionOperationResponse, CompressionRatio,
+    Condition, Distance, DistinctValues, FieldCondition, Filter, GeoBoundingBox, GeoPoint,
+    GeoRadius, HasIdCondition, HealthCheckReply, Highlight, HnswConfigDiff, IsEmptyCondition,
+    IsNullCondition, ListCollectionsResponse, ListValue, Match, NamedVectors,
+    PayloadExcludeSelector, PayloadIncludeSelector, PayloadIndexParams, PayloadIndexStatus,
+    PayloadSchemaInfo, PayloadSchemaType, PlannerHint, PointId, ProductQuantization,
+    QuantizationConfig, QuantizationSearchParams, Range, ScalarQuantization, ScoredPoint,
+    SearchParams, Struct, TextIndexParams, TextIndexSynonyms, TokenizerType, Value, ValuesCount,
+    Vector, VectorSlice, Vectors, VectorsSelector, VectorsSlices, WithPayloadSelector,
+    WithVectorsSelector,
 };
 
 pub fn payload_to_proto(payload: segment::types::Payload) -> HashMap<String, Value> {
@@ -151,6 +154,20 @@ impl From<segment::data_types::text_index::TextIndexParams> for PayloadIndexPara
                 lowercase: params.lowercase,
                 min_token_len: params.min_token_len.map(|x| x as u64),
                 max_token_len: params.max_token_len.map(|x| x as u64),
+                stopwords: params.stopwords.into_iter().flatten().collect(),
+                synonyms: params
+                    .synonyms
+                    .into_iter()
+                    .flatten()
+                    .map(|(token, synonyms)| {
+                        (
+                            token,
+                            TextIndexSynonyms {
+                                synonyms: synonyms.into_iter().collect(),
+                            },
+                        )
+                    })
+                    .collect(),
             })),
         }
     }
@@ -165,6 +182,7 @@ impl From<segment::types::PayloadIndexInfo> for PayloadSchemaInfo {
                 segment::types::PayloadSchemaType::Float => PayloadSchemaType::Float,
                 segment::types::PayloadSchemaType::Geo => PayloadSchemaType::Geo,
                 segment::types::PayloadSchemaType::Text => PayloadSchemaType::Text,
+                segment::types::PayloadSchemaType::Bool => PayloadSchemaType::Bool,
             }
             .into(),
             params: schema.params.map(|params| match params {
@@ -173,6 +191,18 @@ impl From<segment::types::PayloadIndexInfo> for PayloadSchemaInfo {
                 }
             }),
             points: Some(schema.points as u64),
+            status: Some(PayloadIndexStatus::from(schema.status) as i32),
+            progress_percent: schema.progress_percent.map(u32::from),
+        }
+    }
+}
+
+impl From<segment::types::PayloadIndexStatus> for PayloadIndexStatus {
+    fn from(status: segment::types::PayloadIndexStatus) -> Self {
+        match status {
+            segment::types::PayloadIndexStatus::Ready => PayloadIndexStatus::IndexReady,
+            segment::types::PayloadIndexStatus::Building => PayloadIndexStatus::IndexBuilding,
+            segment::types::PayloadIndexStatus::Failed => PayloadIndexStatus::IndexFailed,
         }
     }
 }
@@ -202,6 +232,15 @@ impl TryFrom<TextIndexParams> for segment::data_types::text_index::TextIndexPara
             lowercase: params.lowercase,
             min_token_len: params.min_token_len.map(|x| x as usize),
             max_token_len: params.max_token_len.map(|x| x as usize),
+            stopwords: (!params.stopwords.is_empty())
+                .then(|| params.stopwords.into_iter().collect()),
+            synonyms: (!params.synonyms.is_empty()).then(|| {
+                params
+                    .synonyms
+                    .into_iter()
+                    .map(|(token, synonyms)| (token, synonyms.synonyms.into_iter().collect()))
+                    .collect()
+            }),
         })
     }
 }
@@ -246,6 +285,7 @@ impl TryFrom<PayloadSchemaInfo> for segment::types::PayloadIndexInfo {
                 PayloadSchemaType::Float => segment::types::PayloadSchemaType::Float,
                 PayloadSchemaType::Geo => segment::types::PayloadSchemaType::Geo,
                 PayloadSchemaType::Text => segment::types::PayloadSchemaType::Text,
+                PayloadSchemaType::Bool => segment::types::PayloadSchemaType::Bool,
                 PayloadSchemaType::UnknownType => {
                     return Err(Status::invalid_argument(
                         "Malformed payload schema".to_string(),
@@ -261,10 +301,30 @@ impl TryFrom<PayloadSchemaInfo> for segment::types::PayloadIndexInfo {
             }) => Some(index_params.try_into()?),
         };
 
+        let status = match schema.status.map(PayloadIndexStatus::from_i32) {
+            None => segment::types::PayloadIndexStatus::Ready,
+            Some(Some(PayloadIndexStatus::IndexReady)) => segment::types::PayloadIndexStatus::Ready,
+            Some(Some(PayloadIndexStatus::IndexBuilding)) => {
+                segment::types::PayloadIndexStatus::Building
+            }
+            Some(Some(PayloadIndexStatus::IndexFailed)) => {
+                segment::types::PayloadIndexStatus::Failed
+            }
+            Some(None | Some(PayloadIndexStatus::UnknownPayloadIndexStatus)) => {
+                return Err(Status::invalid_argument(
+                    "Malformed payload index status".to_string(),
+                ))
+            }
+        };
+
         Ok(segment::types::PayloadIndexInfo {
             data_type,
             params,
             points: schema.points.unwrap_or(0) as usize,
+            status,
+            progress_percent: schema
+                .progress_percent
+                .map(|percent| percent.min(100) as u8),
         })
     }
 }
@@ -301,6 +361,10 @@ impl TryFrom<WithPayloadSelector> for segment::types::WithPayloadInterface {
                 SelectorOptions::Include(s) => {
                     segment::types::PayloadSelectorInclude::new(s.fields).into()
                 }
+                SelectorOptions::Count(true) => {
+                    segment::types::WithPayloadInterface::Mode(segment::types::PayloadMode::Count)
+                }
+                SelectorOptions::Count(false) => segment::types::WithPayloadInterface::Bool(true),
             }),
             _ => Err(Status::invalid_argument("No PayloadSelector".to_string())),
         }
@@ -322,6 +386,9 @@ impl From<segment::types::WithPayloadInterface> for WithPayloadSelector {
                     SelectorOptions::Exclude(PayloadExcludeSelector { fields: s.exclude })
                 }
             },
+            segment::types::WithPayloadInterface::Mode(segment::types::PayloadMode::Count) => {
+                SelectorOptions::Count(true)
+            }
         };
         WithPayloadSelector {
             selector_options: Some(selector_options),
@@ -336,6 +403,7 @@ impl From<QuantizationSearchParams> for segment::types::QuantizationSearchParams
             rescore: params
                 .rescore
                 .unwrap_or(default_quantization_rescore_value()),
+            rescore_factor: params.rescore_factor,
         }
     }
 }
@@ -345,6 +413,7 @@ impl From<segment::types::QuantizationSearchParams> for QuantizationSearchParams
         Self {
             ignore: Some(params.ignore),
             rescore: Some(params.rescore),
+            rescore_factor: params.rescore_factor,
         }
     }
 }
@@ -355,6 +424,10 @@ impl From<SearchParams> for segment::types::SearchParams {
             hnsw_ef: params.hnsw_ef.map(|x| x as usize),
             exact: params.exact.unwrap_or(false),
             quantization: params.quantization.map(|q| q.into()),
+            planner_hint: params
+                .planner_hint
+                .and_then(PlannerHint::from_i32)
+                .map(|hint| hint.into()),
         }
     }
 }
@@ -365,6 +438,29 @@ impl From<segment::types::SearchParams> for SearchParams {
             hnsw_ef: params.hnsw_ef.map(|x| x as u64),
             exact: Some(params.exact),
             quantization: params.quantization.map(|q| q.into()),
+            planner_hint: params
+                .planner_hint
+                .map(|hint| PlannerHint::from(hint) as i32),
+        }
+    }
+}
+
+impl From<PlannerHint> for segment::types::PlannerHint {
+    fn from(hint: PlannerHint) -> Self {
+        match hint {
+            PlannerHint::IndexScan => segment::types::PlannerHint::IndexScan,
+            PlannerHint::StreamScan => segment::types::PlannerHint::StreamScan,
+            PlannerHint::PlainSearch => segment::types::PlannerHint::PlainSearch,
+        }
+    }
+}
+
+impl From<segment::types::PlannerHint> for PlannerHint {
+    fn from(hint: segment::types::PlannerHint) -> Self {
+        match hint {
+            segment::types::PlannerHint::IndexScan => PlannerHint::IndexScan,
+            segment::types::PlannerHint::StreamScan => PlannerHint::StreamScan,
+            segment::types::PlannerHint::PlainSearch => PlannerHint::PlainSearch,
         }
     }
 }
@@ -418,6 +514,35 @@ impl From<segment::types::ScoredPoint> for ScoredPoint {
             score: point.score,
             version: point.version,
             vectors: point.vector.map(|v| v.into()),
+            highlights: point
+                .highlights
+                .unwrap_or_default()
+                .into_iter()
+                .map(|highlight| highlight.into())
+                .collect(),
+            geo_distance: point.geo_distance,
+        }
+    }
+}
+
+impl From<segment::types::Highlight> for Highlight {
+    fn from(highlight: segment::types::Highlight) -> Self {
+        Self {
+            key: highlight.key,
+            index: highlight.index as u64,
+            start: highlight.start as u64,
+            end: highlight.end as u64,
+        }
+    }
+}
+
+impl From<Highlight> for segment::types::Highlight {
+    fn from(highlight: Highlight) -> Self {
+        Self {
+            key: highlight.key,
+            index: highlight.index as usize,
+            start: highlight.start as usize,
+            end: highlight.end as usize,
         }
     }
 }
@@ -459,6 +584,18 @@ impl From<segment::types::WithVector> for WithVectorsSelector {
             segment::types::WithVector::Selector(include) => {
                 with_vectors_selector::SelectorOptions::Include(VectorsSelector { names: include })
             }
+            segment::types::WithVector::Slices(slices) => {
+                with_vectors_selector::SelectorOptions::Slices(VectorsSlices {
+                    slices: slices
+                        .into_iter()
+                        .map(|slice| VectorSlice {
+                            name: slice.name,
+                            offset: slice.offset as u64,
+                            limit: slice.limit.map(|limit| limit as u64),
+                        })
+                        .collect(),
+                })
+            }
         };
         Self {
             selector_options: Some(selector_options),
@@ -474,6 +611,17 @@ impl From<WithVectorsSelector> for segment::types::WithVector {
             Some(with_vectors_selector::SelectorOptions::Include(include)) => {
                 Self::Selector(include.names)
             }
+            Some(with_vectors_selector::SelectorOptions::Slices(slices)) => Self::Slices(
+                slices
+                    .slices
+                    .into_iter()
+                    .map(|slice| segment::types::VectorSlice {
+                        name: slice.name,
+                        offset: slice.offset as usize,
+                        limit: slice.limit.map(|limit| limit as usize),
+                    })
+                    .collect(),
+            ),
         }
     }
 }
@@ -511,6 +659,23 @@ impl From<segment::types::QuantizationConfig> for QuantizationConfig {
                         },
                         quantile: config.quantile,
                         always_ram: config.always_ram,
+                        quantized_only: config.quantized_only,
+                    },
+                )),
+            },
+            segment::types::QuantizationConfig::Product(segment::types::ProductQuantization {
+                product: config,
+            }) => Self {
+                quantization: Some(super::qdrant::quantization_config::Quantization::Product(
+                    ProductQuantization {
+                        compression: match config.compression {
+                            segment::types::CompressionRatio::X4 => CompressionRatio::X4 as i32,
+                            segment::types::CompressionRatio::X8 => CompressionRatio::X8 as i32,
+                            segment::types::CompressionRatio::X16 => CompressionRatio::X16 as i32,
+                            segment::types::CompressionRatio::X32 => CompressionRatio::X32 as i32,
+                            segment::types::CompressionRatio::X64 => CompressionRatio::X64 as i32,
+                        },
+                        always_ram: config.always_ram,
                     },
                 )),
             },
@@ -545,6 +710,25 @@ impl TryFrom<QuantizationConfig> for segment::types::QuantizationConfig {
                     },
                     quantile: config.quantile,
                     always_ram: config.always_ram,
+                    quantized_only: config.quantized_only,
+                }
+                .into())
+            }
+            super::qdrant::quantization_config::Quantization::Product(config) => {
+                Ok(segment::types::ProductQuantizationConfig {
+                    compression: match CompressionRatio::from_i32(config.compression) {
+                        None => {
+                            return Err(Status::invalid_argument(
+                                "Error converting compression ratio: None",
+                            ));
+                        }
+                        Some(CompressionRatio::X4) => segment::types::CompressionRatio::X4,
+                        Some(CompressionRatio::X8) => segment::types::CompressionRatio::X8,
+                        Some(CompressionRatio::X16) => segment::types::CompressionRatio::X16,
+                        Some(CompressionRatio::X32) => segment::types::CompressionRatio::X32,
+                        Some(CompressionRatio::X64) => segment::types::CompressionRatio::X64,
+                    },
+                    always_ram: config.always_ram,
                 }
                 .into())
             }
@@ -907,6 +1091,7 @@ impl From<HnswConfigDiff> for segment::types::HnswConfig {
             max_indexing_threads: hnsw_config.max_indexing_threads.unwrap_or_default() as usize,
             on_disk: hnsw_config.on_disk,
             payload_m: hnsw_config.payload_m.map(|x| x as usize),
+            payload_fields: hnsw_config.payload_fields.map(|x| x.fields),
         }
     }
 }
@@ -943,3 +1128,32 @@ pub fn from_grpc_dist(dist: i32) -> Result<segment::types::Distance, Status> {
         Some(grpc_distance) => Ok(grpc_distance.try_into()?),
     }
 }
+
+impl TryFrom<DistinctValues> for segment::data_types::distinct::DistinctValues {
+    type Error = Status;
+
+    fn try_from(value: DistinctValues) -> Result<Self, Self::Error> {
+        let DistinctValues { hashes, registers } = value;
+        if registers.is_empty() {
+            return Ok(Self::Exact(hashes.into_iter().collect()));
+        }
+        segment::data_types::distinct::HyperLogLog::from_registers(registers)
+            .map(Self::Approximate)
+            .ok_or_else(|| Status::invalid_argument("Unexpected number of HyperLogLog registers"))
+    }
+}
+
+impl From<segment::data_types::distinct::DistinctValues> for DistinctValues {
+    fn from(value: segment::data_types::distinct::DistinctValues) -> Self {
+        match value {
+            segment::data_types::distinct::DistinctValues::Exact(hashes) => Self {
+                hashes: hashes.into_iter().collect(),
+                registers: vec![],
+            },
+            segment::data_types::distinct::DistinctValues::Approximate(sketch) => Self {
+                hashes: vec![],
+                registers: sketch.into_registers(),
+            },
+        }
+    }
+}
@@ -15,6 +15,25 @@ pub struct VectorParams {
     /// Configuration of vector quantization config. If omitted - the collection configuration will be used
     #[prost(message, optional, tag = "4")]
     pub quantization_config: ::core::option::Option<QuantizationConfig>,
+    /// Linear projection applied to vectors on upsert and to query vectors on search. If set - `size` is the size of projected vectors
+    #[prost(message, optional, tag = "5")]
+    pub projection: ::core::option::Option<VectorProjection>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct VectorProjectionRow {
+    #[prost(float, repeated, tag = "1")]
+    pub values: ::prost::alloc::vec::Vec<f32>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct VectorProjection {
+    /// Projection matrix, one row per dimension of the projected vector
+    #[prost(message, repeated, tag = "1")]
+    pub matrix: ::prost::alloc::vec::Vec<VectorProjectionRow>,
+    /// Mean of the original vectors, subtracted before the projection. If empty - vectors are not centered
+    #[prost(float, repeated, tag = "2")]
+    pub mean: ::prost::alloc::vec::Vec<f32>,
 }
 #[derive(validator::Validate)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -24,6 +43,27 @@ pub struct VectorParamsMap {
     #[validate]
     pub map: ::std::collections::HashMap<::prost::alloc::string::String, VectorParams>,
 }
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct VectorAliases {
+    #[prost(map = "string, string", tag = "1")]
+    pub map: ::std::collections::HashMap<
+        ::prost::alloc::string::String,
+        ::prost::alloc::string::String,
+    >,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SparseVectorParams {}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SparseVectorConfig {
+    #[prost(map = "string, message", tag = "1")]
+    pub map: ::std::collections::HashMap<
+        ::prost::alloc::string::String,
+        SparseVectorParams,
+    >,
+}
 #[derive(validator::Validate)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -121,6 +161,16 @@ pub struct HnswConfigDiff {
     /// Number of additional payload-aware links per node in the index graph. If not set - regular M parameter will be used.
     #[prost(uint64, optional, tag = "6")]
     pub payload_m: ::core::option::Option<u64>,
+    ///
+    /// Payload fields, for which additional payload-aware links are built. If not set - links are built for all indexed payload fields.
+    #[prost(message, optional, tag = "7")]
+    pub payload_fields: ::core::option::Option<HnswPayloadFields>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct HnswPayloadFields {
+    #[prost(string, repeated, tag = "1")]
+    pub fields: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
 }
 #[derive(validator::Validate)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -192,6 +242,18 @@ pub struct OptimizersConfigDiff {
     /// Max number of threads, which can be used for optimization. If 0 - `NUM_CPU - 1` will be used
     #[prost(uint64, optional, tag = "8")]
     pub max_optimization_threads: ::core::option::Option<u64>,
+    ///
+    /// Exact number of segments the optimizer will try to keep.
+    /// If set, takes precedence over `default_segment_number`.
+    #[prost(uint64, optional, tag = "9")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
+    pub target_segment_count: ::core::option::Option<u64>,
+    ///
+    /// Do not create segments larger this size (in MegaBytes).
+    /// If set, takes precedence over `max_segment_size`.
+    #[prost(uint64, optional, tag = "10")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
+    pub max_segment_size_mb: ::core::option::Option<u64>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -205,11 +267,24 @@ pub struct ScalarQuantization {
     /// If true - quantized vectors always will be stored in RAM, ignoring the config of main storage
     #[prost(bool, optional, tag = "3")]
     pub always_ram: ::core::option::Option<bool>,
+    /// If true - original vectors are removed from optimized segments, only quantized vectors are kept
+    #[prost(bool, optional, tag = "4")]
+    pub quantized_only: ::core::option::Option<bool>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ProductQuantization {
+    /// Compression ratio
+    #[prost(enumeration = "CompressionRatio", tag = "1")]
+    pub compression: i32,
+    /// If true - quantized vectors always will be stored in RAM, ignoring the config of main storage
+    #[prost(bool, optional, tag = "2")]
+    pub always_ram: ::core::option::Option<bool>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct QuantizationConfig {
-    #[prost(oneof = "quantization_config::Quantization", tags = "1")]
+    #[prost(oneof = "quantization_config::Quantization", tags = "1, 2")]
     pub quantization: ::core::option::Option<quantization_config::Quantization>,
 }
 /// Nested message and enum types in `QuantizationConfig`.
@@ -219,6 +294,8 @@ pub mod quantization_config {
     pub enum Quantization {
         #[prost(message, tag = "1")]
         Scalar(super::ScalarQuantization),
+        #[prost(message, tag = "2")]
+        Product(super::ProductQuantization),
     }
 }
 #[derive(validator::Validate)]
@@ -266,6 +343,9 @@ pub struct CreateCollection {
     /// Quantization configuration of vector
     #[prost(message, optional, tag = "14")]
     pub quantization_config: ::core::option::Option<QuantizationConfig>,
+    /// Configuration for sparse vectors
+    #[prost(message, optional, tag = "15")]
+    pub sparse_vectors_config: ::core::option::Option<SparseVectorConfig>,
 }
 #[derive(validator::Validate)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -287,6 +367,14 @@ pub struct UpdateCollection {
     #[prost(message, optional, tag = "4")]
     #[validate]
     pub params: ::core::option::Option<CollectionParamsDiff>,
+    /// New named vectors to add to the collection
+    #[prost(message, optional, tag = "5")]
+    #[validate]
+    pub vectors: ::core::option::Option<VectorParamsMap>,
+    /// New configuration parameters for the collection WAL
+    #[prost(message, optional, tag = "6")]
+    #[validate]
+    pub wal_config: ::core::option::Option<WalConfigDiff>,
 }
 #[derive(validator::Validate)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -313,6 +401,32 @@ pub struct CollectionOperationResponse {
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct WriteOrdering {
+    /// Write ordering guarantees
+    #[prost(enumeration = "WriteOrderingType", tag = "1")]
+    pub r#type: i32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ReadConsistency {
+    #[prost(oneof = "read_consistency::Value", tags = "1, 2")]
+    pub value: ::core::option::Option<read_consistency::Value>,
+}
+/// Nested message and enum types in `ReadConsistency`.
+pub mod read_consistency {
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Value {
+        /// Common read consistency configurations
+        #[prost(enumeration = "super::ReadConsistencyType", tag = "1")]
+        Type(i32),
+        /// Send request to a specified number of nodes, and return points which are present on all of them
+        #[prost(uint64, tag = "2")]
+        Factor(u64),
+    }
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct CollectionParams {
     /// Number of shards in collection
     #[prost(uint32, tag = "3")]
@@ -329,6 +443,33 @@ pub struct CollectionParams {
     /// How many replicas should apply the operation for us to consider it successful
     #[prost(uint32, optional, tag = "7")]
     pub write_consistency_factor: ::core::option::Option<u32>,
+    /// Alternative names of vectors
+    #[prost(message, optional, tag = "8")]
+    pub vector_aliases: ::core::option::Option<VectorAliases>,
+    /// Name of the vector to use if request does not specify any vector name
+    #[prost(string, optional, tag = "9")]
+    pub default_vector: ::core::option::Option<::prost::alloc::string::String>,
+    /// Payload key to group points by, when optimizers rebuild segments
+    #[prost(string, optional, tag = "10")]
+    pub defrag_key: ::core::option::Option<::prost::alloc::string::String>,
+    /// Skip upserts of points, which content hash matches the stored one
+    #[prost(bool, optional, tag = "11")]
+    pub deduplicate_upserts: ::core::option::Option<bool>,
+    /// Max number of concurrently executed searches in the collection, 0 - unlimited
+    #[prost(uint64, optional, tag = "12")]
+    pub max_concurrent_searches: ::core::option::Option<u64>,
+    /// Payload key with the list of identity claims, allowed to read the point
+    #[prost(string, optional, tag = "13")]
+    pub acl_key: ::core::option::Option<::prost::alloc::string::String>,
+    /// Configuration for sparse vectors
+    #[prost(message, optional, tag = "14")]
+    pub sparse_vectors_config: ::core::option::Option<SparseVectorConfig>,
+    /// Write ordering of updates, which don't specify it
+    #[prost(message, optional, tag = "15")]
+    pub default_write_ordering: ::core::option::Option<WriteOrdering>,
+    /// Read consistency of requests, which don't specify it
+    #[prost(message, optional, tag = "16")]
+    pub default_read_consistency: ::core::option::Option<ReadConsistency>,
 }
 #[derive(validator::Validate)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -340,6 +481,30 @@ pub struct CollectionParamsDiff {
     /// How many replicas should apply the operation for us to consider it successful
     #[prost(uint32, optional, tag = "2")]
     pub write_consistency_factor: ::core::option::Option<u32>,
+    /// Alternative names of vectors, replaces existing aliases
+    #[prost(message, optional, tag = "3")]
+    pub vector_aliases: ::core::option::Option<VectorAliases>,
+    /// Name of the vector to use if request does not specify any vector name
+    #[prost(string, optional, tag = "4")]
+    pub default_vector: ::core::option::Option<::prost::alloc::string::String>,
+    /// Payload key to group points by, when optimizers rebuild segments
+    #[prost(string, optional, tag = "5")]
+    pub defrag_key: ::core::option::Option<::prost::alloc::string::String>,
+    /// Skip upserts of points, which content hash matches the stored one
+    #[prost(bool, optional, tag = "6")]
+    pub deduplicate_upserts: ::core::option::Option<bool>,
+    /// Max number of concurrently executed searches in the collection, 0 - unlimited
+    #[prost(uint64, optional, tag = "7")]
+    pub max_concurrent_searches: ::core::option::Option<u64>,
+    /// Payload key with the list of identity claims, allowed to read the point, empty string - disable
+    #[prost(string, optional, tag = "8")]
+    pub acl_key: ::core::option::Option<::prost::alloc::string::String>,
+    /// Write ordering of updates, which don't specify it
+    #[prost(message, optional, tag = "9")]
+    pub default_write_ordering: ::core::option::Option<WriteOrdering>,
+    /// Read consistency of requests, which don't specify it
+    #[prost(message, optional, tag = "10")]
+    pub default_read_consistency: ::core::option::Option<ReadConsistency>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -362,6 +527,13 @@ pub struct CollectionConfig {
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TextIndexSynonyms {
+    /// Tokens, which also match the query token
+    #[prost(string, repeated, tag = "1")]
+    pub synonyms: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct TextIndexParams {
     /// Tokenizer type
     #[prost(enumeration = "TokenizerType", tag = "1")]
@@ -375,6 +547,15 @@ pub struct TextIndexParams {
     /// Maximal token length
     #[prost(uint64, optional, tag = "4")]
     pub max_token_len: ::core::option::Option<u64>,
+    /// Tokens to exclude from both documents and queries
+    #[prost(string, repeated, tag = "5")]
+    pub stopwords: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// Query-time expansion of tokens into their synonyms
+    #[prost(map = "string, message", tag = "6")]
+    pub synonyms: ::std::collections::HashMap<
+        ::prost::alloc::string::String,
+        TextIndexSynonyms,
+    >,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -404,6 +585,12 @@ pub struct PayloadSchemaInfo {
     /// Number of points indexed within this field indexed
     #[prost(uint64, optional, tag = "3")]
     pub points: ::core::option::Option<u64>,
+    /// State of the index build
+    #[prost(enumeration = "PayloadIndexStatus", optional, tag = "4")]
+    pub status: ::core::option::Option<i32>,
+    /// Percent of points processed by the index build, while the index is being built
+    #[prost(uint32, optional, tag = "5")]
+    pub progress_percent: ::core::option::Option<u32>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -602,6 +789,7 @@ pub enum PayloadSchemaType {
     Float = 3,
     Geo = 4,
     Text = 5,
+    Bool = 6,
 }
 impl PayloadSchemaType {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -616,6 +804,7 @@ impl PayloadSchemaType {
             PayloadSchemaType::Float => "Float",
             PayloadSchemaType::Geo => "Geo",
             PayloadSchemaType::Text => "Text",
+            PayloadSchemaType::Bool => "Bool",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -627,6 +816,42 @@ impl PayloadSchemaType {
             "Float" => Some(Self::Float),
             "Geo" => Some(Self::Geo),
             "Text" => Some(Self::Text),
+            "Bool" => Some(Self::Bool),
+            _ => None,
+        }
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum PayloadIndexStatus {
+    UnknownPayloadIndexStatus = 0,
+    /// Index is built and used for filtering
+    IndexReady = 1,
+    /// Index is being built
+    IndexBuilding = 2,
+    /// Index build failed
+    IndexFailed = 3,
+}
+impl PayloadIndexStatus {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            PayloadIndexStatus::UnknownPayloadIndexStatus => "UnknownPayloadIndexStatus",
+            PayloadIndexStatus::IndexReady => "IndexReady",
+            PayloadIndexStatus::IndexBuilding => "IndexBuilding",
+            PayloadIndexStatus::IndexFailed => "IndexFailed",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "UnknownPayloadIndexStatus" => Some(Self::UnknownPayloadIndexStatus),
+            "IndexReady" => Some(Self::IndexReady),
+            "IndexBuilding" => Some(Self::IndexBuilding),
+            "IndexFailed" => Some(Self::IndexFailed),
             _ => None,
         }
     }
@@ -659,6 +884,105 @@ impl QuantizationType {
 }
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
 #[repr(i32)]
+pub enum CompressionRatio {
+    X4 = 0,
+    X8 = 1,
+    X16 = 2,
+    X32 = 3,
+    X64 = 4,
+}
+impl CompressionRatio {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            CompressionRatio::X4 => "x4",
+            CompressionRatio::X8 => "x8",
+            CompressionRatio::X16 => "x16",
+            CompressionRatio::X32 => "x32",
+            CompressionRatio::X64 => "x64",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "x4" => Some(Self::X4),
+            "x8" => Some(Self::X8),
+            "x16" => Some(Self::X16),
+            "x32" => Some(Self::X32),
+            "x64" => Some(Self::X64),
+            _ => None,
+        }
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum WriteOrderingType {
+    /// Write operations may be reordered, works faster, default
+    Weak = 0,
+    /// Write operations go through dynamically selected leader, may be inconsistent for a short period of time in case of leader change
+    Medium = 1,
+    /// Write operations go through the permanent leader, consistent, but may be unavailable if leader is down
+    Strong = 2,
+}
+impl WriteOrderingType {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            WriteOrderingType::Weak => "Weak",
+            WriteOrderingType::Medium => "Medium",
+            WriteOrderingType::Strong => "Strong",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "Weak" => Some(Self::Weak),
+            "Medium" => Some(Self::Medium),
+            "Strong" => Some(Self::Strong),
+            _ => None,
+        }
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum ReadConsistencyType {
+    /// Send request to all nodes and return points which are present on all of them
+    All = 0,
+    /// Send requests to all nodes and return points which are present on majority of them
+    Majority = 1,
+    /// Send requests to half + 1 nodes, return points which are present on all of them
+    Quorum = 2,
+}
+impl ReadConsistencyType {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            ReadConsistencyType::All => "All",
+            ReadConsistencyType::Majority => "Majority",
+            ReadConsistencyType::Quorum => "Quorum",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "All" => Some(Self::All),
+            "Majority" => Some(Self::Majority),
+            "Quorum" => Some(Self::Quorum),
+            _ => None,
+        }
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
 pub enum TokenizerType {
     Unknown = 0,
     Prefix = 1,
@@ -2038,32 +2362,6 @@ impl NullValue {
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct WriteOrdering {
-    /// Write ordering guarantees
-    #[prost(enumeration = "WriteOrderingType", tag = "1")]
-    pub r#type: i32,
-}
-#[allow(clippy::derive_partial_eq_without_eq)]
-#[derive(Clone, PartialEq, ::prost::Message)]
-pub struct ReadConsistency {
-    #[prost(oneof = "read_consistency::Value", tags = "1, 2")]
-    pub value: ::core::option::Option<read_consistency::Value>,
-}
-/// Nested message and enum types in `ReadConsistency`.
-pub mod read_consistency {
-    #[allow(clippy::derive_partial_eq_without_eq)]
-    #[derive(Clone, PartialEq, ::prost::Oneof)]
-    pub enum Value {
-        /// Common read consistency configurations
-        #[prost(enumeration = "super::ReadConsistencyType", tag = "1")]
-        Type(i32),
-        /// Send request to a specified number of nodes, and return points which are present on all of them
-        #[prost(uint64, tag = "2")]
-        Factor(u64),
-    }
-}
-#[allow(clippy::derive_partial_eq_without_eq)]
-#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct PointId {
     #[prost(oneof = "point_id::PointIdOptions", tags = "1, 2")]
     pub point_id_options: ::core::option::Option<point_id::PointIdOptions>,
@@ -2103,6 +2401,9 @@ pub struct UpsertPoints {
     /// Write ordering guarantees
     #[prost(message, optional, tag = "4")]
     pub ordering: ::core::option::Option<WriteOrdering>,
+    /// Shard, which is expected to store all affected points
+    #[prost(uint32, optional, tag = "5")]
+    pub shard_key: ::core::option::Option<u32>,
 }
 #[derive(validator::Validate)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -2121,6 +2422,9 @@ pub struct DeletePoints {
     /// Write ordering guarantees
     #[prost(message, optional, tag = "4")]
     pub ordering: ::core::option::Option<WriteOrdering>,
+    /// Shard, which is expected to store all affected points
+    #[prost(uint32, optional, tag = "5")]
+    pub shard_key: ::core::option::Option<u32>,
 }
 #[derive(validator::Validate)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -2264,7 +2568,7 @@ pub struct PayloadExcludeSelector {
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct WithPayloadSelector {
-    #[prost(oneof = "with_payload_selector::SelectorOptions", tags = "1, 2, 3")]
+    #[prost(oneof = "with_payload_selector::SelectorOptions", tags = "1, 2, 3, 4")]
     pub selector_options: ::core::option::Option<with_payload_selector::SelectorOptions>,
 }
 /// Nested message and enum types in `WithPayloadSelector`.
@@ -2279,6 +2583,9 @@ pub mod with_payload_selector {
         Include(super::PayloadIncludeSelector),
         #[prost(message, tag = "3")]
         Exclude(super::PayloadExcludeSelector),
+        /// If `true` - return only number of payload keys and payload size in bytes
+        #[prost(bool, tag = "4")]
+        Count(bool),
     }
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -2313,8 +2620,28 @@ pub struct VectorsSelector {
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct VectorSlice {
+    /// Name of the vector, default vector if empty
+    #[prost(string, tag = "1")]
+    pub name: ::prost::alloc::string::String,
+    /// Index of the first dimension to return
+    #[prost(uint64, tag = "2")]
+    pub offset: u64,
+    /// Number of dimensions to return, all remaining if not specified
+    #[prost(uint64, optional, tag = "3")]
+    pub limit: ::core::option::Option<u64>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct VectorsSlices {
+    /// List of vector dimension ranges to include into result
+    #[prost(message, repeated, tag = "1")]
+    pub slices: ::prost::alloc::vec::Vec<VectorSlice>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct WithVectorsSelector {
-    #[prost(oneof = "with_vectors_selector::SelectorOptions", tags = "1, 2")]
+    #[prost(oneof = "with_vectors_selector::SelectorOptions", tags = "1, 2, 3")]
     pub selector_options: ::core::option::Option<with_vectors_selector::SelectorOptions>,
 }
 /// Nested message and enum types in `WithVectorsSelector`.
@@ -2328,6 +2655,9 @@ pub mod with_vectors_selector {
         /// List of payload keys to include into result
         #[prost(message, tag = "2")]
         Include(super::VectorsSelector),
+        /// List of vector dimension ranges to include into result
+        #[prost(message, tag = "3")]
+        Slices(super::VectorsSlices),
     }
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -2341,6 +2671,10 @@ pub struct QuantizationSearchParams {
     /// If true, use original vectors to re-score top-k results. Default is true.
     #[prost(bool, optional, tag = "2")]
     pub rescore: ::core::option::Option<bool>,
+    ///
+    /// Oversampling factor of the re-scoring. If set, `limit * rescore_factor` candidates are re-scored with original vectors in each segment.
+    #[prost(double, optional, tag = "3")]
+    pub rescore_factor: ::core::option::Option<f64>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -2358,6 +2692,10 @@ pub struct SearchParams {
     /// If set to true, search will ignore quantized vector data
     #[prost(message, optional, tag = "3")]
     pub quantization: ::core::option::Option<QuantizationSearchParams>,
+    ///
+    /// Override the strategy, selected by the query planner from the filter cardinality
+    #[prost(enumeration = "PlannerHint", optional, tag = "4")]
+    pub planner_hint: ::core::option::Option<i32>,
 }
 #[derive(validator::Validate)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -2399,6 +2737,18 @@ pub struct SearchPoints {
     /// Options for specifying read consistency guarantees
     #[prost(message, optional, tag = "12")]
     pub read_consistency: ::core::option::Option<ReadConsistency>,
+    /// Priority of the request in the search queue, default is normal
+    #[prost(enumeration = "SearchPriority", optional, tag = "13")]
+    pub priority: ::core::option::Option<i32>,
+    /// If true - return positions of the words, matched by full-text conditions of the filter
+    #[prost(bool, optional, tag = "14")]
+    pub with_highlights: ::core::option::Option<bool>,
+    /// If true - return distance from the center of the geo condition of the filter
+    #[prost(bool, optional, tag = "15")]
+    pub with_geo_distance: ::core::option::Option<bool>,
+    /// Max duration of the request in seconds, no timeout if not specified
+    #[prost(uint64, optional, tag = "16")]
+    pub timeout: ::core::option::Option<u64>,
 }
 #[derive(validator::Validate)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -2414,6 +2764,12 @@ pub struct SearchBatchPoints {
     /// Options for specifying read consistency guarantees
     #[prost(message, optional, tag = "3")]
     pub read_consistency: ::core::option::Option<ReadConsistency>,
+    /// Priority of the request in the search queue, default is normal
+    #[prost(enumeration = "SearchPriority", optional, tag = "4")]
+    pub priority: ::core::option::Option<i32>,
+    /// Max duration of the request in seconds, no timeout if not specified
+    #[prost(uint64, optional, tag = "5")]
+    pub timeout: ::core::option::Option<u64>,
 }
 #[derive(validator::Validate)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -2441,6 +2797,47 @@ pub struct ScrollPoints {
     /// Options for specifying read consistency guarantees
     #[prost(message, optional, tag = "8")]
     pub read_consistency: ::core::option::Option<ReadConsistency>,
+    /// Order of returned points, default is by ID
+    #[prost(enumeration = "ScrollOrder", optional, tag = "9")]
+    pub order: ::core::option::Option<i32>,
+    /// Seed of the random order, random if not specified
+    #[prost(uint64, optional, tag = "10")]
+    pub seed: ::core::option::Option<u64>,
+    /// Override the strategy of reading filtered points
+    #[prost(enumeration = "PlannerHint", optional, tag = "11")]
+    pub planner_hint: ::core::option::Option<i32>,
+    /// Reference point for the GeoDistance order, required for it
+    #[prost(message, optional, tag = "12")]
+    pub geo_order: ::core::option::Option<GeoDistanceOrder>,
+    /// Order points by values of the payload field instead of IDs
+    #[prost(message, optional, tag = "13")]
+    pub order_by: ::core::option::Option<OrderBy>,
+    /// Max duration of the request in seconds, no timeout if not specified
+    #[prost(uint64, optional, tag = "14")]
+    pub timeout: ::core::option::Option<u64>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GeoDistanceOrder {
+    /// Payload field with geo points, must have a geo index
+    #[prost(string, tag = "1")]
+    pub key: ::prost::alloc::string::String,
+    /// Distances are measured from this point to the closest geo point of the field
+    #[prost(message, optional, tag = "2")]
+    pub origin: ::core::option::Option<GeoPoint>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct OrderBy {
+    /// Payload field to order by, must have an integer or float index with range support
+    #[prost(string, tag = "1")]
+    pub key: ::prost::alloc::string::String,
+    /// Direction of the order, default is ascending
+    #[prost(enumeration = "Direction", optional, tag = "2")]
+    pub direction: ::core::option::Option<i32>,
+    /// Value of the field to start reading points from, inclusive
+    #[prost(double, optional, tag = "3")]
+    pub start_from: ::core::option::Option<f64>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -2495,6 +2892,27 @@ pub struct RecommendPoints {
     /// Options for specifying read consistency guarantees
     #[prost(message, optional, tag = "14")]
     pub read_consistency: ::core::option::Option<ReadConsistency>,
+    /// Priority of the request in the search queue, default is normal
+    #[prost(enumeration = "SearchPriority", optional, tag = "15")]
+    pub priority: ::core::option::Option<i32>,
+    /// Look for vectors closest to those, given as raw vectors
+    #[prost(message, repeated, tag = "16")]
+    pub positive_vectors: ::prost::alloc::vec::Vec<Vector>,
+    /// Try to avoid vectors like this, given as raw vectors
+    #[prost(message, repeated, tag = "17")]
+    pub negative_vectors: ::prost::alloc::vec::Vec<Vector>,
+    /// How to use the examples, default is AverageVector
+    #[prost(enumeration = "RecommendStrategy", optional, tag = "18")]
+    pub strategy: ::core::option::Option<i32>,
+    /// Max duration of the request in seconds, no timeout if not specified
+    #[prost(uint64, optional, tag = "19")]
+    pub timeout: ::core::option::Option<u64>,
+    /// Weights of positive examples: ids first, then raw vectors. Each weight is 1 if empty
+    #[prost(float, repeated, tag = "20")]
+    pub positive_weights: ::prost::alloc::vec::Vec<f32>,
+    /// Weights of negative examples: ids first, then raw vectors. Each weight is 1 if empty
+    #[prost(float, repeated, tag = "21")]
+    pub negative_weights: ::prost::alloc::vec::Vec<f32>,
 }
 #[derive(validator::Validate)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -2510,21 +2928,229 @@ pub struct RecommendBatchPoints {
     /// Options for specifying read consistency guarantees
     #[prost(message, optional, tag = "3")]
     pub read_consistency: ::core::option::Option<ReadConsistency>,
+    /// Priority of the request in the search queue, default is normal
+    #[prost(enumeration = "SearchPriority", optional, tag = "4")]
+    pub priority: ::core::option::Option<i32>,
+    /// Max duration of the request in seconds, no timeout if not specified
+    #[prost(uint64, optional, tag = "5")]
+    pub timeout: ::core::option::Option<u64>,
 }
-#[derive(validator::Validate)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct CountPoints {
-    /// name of the collection
-    #[prost(string, tag = "1")]
-    #[validate(length(min = 1, max = 255))]
-    pub collection_name: ::prost::alloc::string::String,
-    /// Filter conditions - return only those points that satisfy the specified conditions
+pub struct PrefetchQuery {
+    /// vector
+    #[prost(float, repeated, tag = "1")]
+    pub vector: ::prost::alloc::vec::Vec<f32>,
+    /// Which vector to use for search, if not specified - use default vector
+    #[prost(string, optional, tag = "2")]
+    pub vector_name: ::core::option::Option<::prost::alloc::string::String>,
+    /// Filter conditions, in addition to the filter of the query
+    #[prost(message, optional, tag = "3")]
+    pub filter: ::core::option::Option<Filter>,
+    /// Search config
+    #[prost(message, optional, tag = "4")]
+    pub params: ::core::option::Option<SearchParams>,
+    /// Max number of candidates, default is limit + offset of the query
+    #[prost(uint64, optional, tag = "5")]
+    pub limit: ::core::option::Option<u64>,
+    /// Multiplier of the prefetch contribution to the fused score, default is 1.0
+    #[prost(float, optional, tag = "6")]
+    pub weight: ::core::option::Option<f32>,
+    /// If provided - cut off candidates with worse scores
+    #[prost(float, optional, tag = "7")]
+    pub score_threshold: ::core::option::Option<f32>,
+}
+#[derive(validator::Validate)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct QueryPoints {
+    /// name of the collection
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1, max = 255))]
+    pub collection_name: ::prost::alloc::string::String,
+    /// Sub-queries, which results are fused
+    #[prost(message, repeated, tag = "2")]
+    pub prefetch: ::prost::alloc::vec::Vec<PrefetchQuery>,
+    /// Method of fusion of the prefetch results, default is RRF
+    #[prost(enumeration = "Fusion", optional, tag = "3")]
+    pub fusion: ::core::option::Option<i32>,
+    /// Filter conditions, applied to all prefetches
+    #[prost(message, optional, tag = "4")]
+    pub filter: ::core::option::Option<Filter>,
+    /// Max number of result
+    #[prost(uint64, tag = "5")]
+    #[validate(range(min = 1))]
+    pub limit: u64,
+    /// Offset of the result
+    #[prost(uint64, optional, tag = "6")]
+    pub offset: ::core::option::Option<u64>,
+    /// Options for specifying which payload to include or not
+    #[prost(message, optional, tag = "7")]
+    pub with_payload: ::core::option::Option<WithPayloadSelector>,
+    /// Options for specifying which vectors to include into response
+    #[prost(message, optional, tag = "8")]
+    pub with_vectors: ::core::option::Option<WithVectorsSelector>,
+    /// If provided - cut off results with worse fused scores
+    #[prost(float, optional, tag = "9")]
+    pub score_threshold: ::core::option::Option<f32>,
+    /// Options for specifying read consistency guarantees
+    #[prost(message, optional, tag = "10")]
+    pub read_consistency: ::core::option::Option<ReadConsistency>,
+    /// Priority of the request in the search queue, default is normal
+    #[prost(enumeration = "SearchPriority", optional, tag = "11")]
+    pub priority: ::core::option::Option<i32>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct VectorExample {
+    #[prost(oneof = "vector_example::Example", tags = "1, 2")]
+    pub example: ::core::option::Option<vector_example::Example>,
+}
+/// Nested message and enum types in `VectorExample`.
+pub mod vector_example {
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Example {
+        /// Id of the point, which vector is looked up
+        #[prost(message, tag = "1")]
+        Id(super::PointId),
+        /// Raw vector
+        #[prost(message, tag = "2")]
+        Vector(super::Vector),
+    }
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ContextExamplePair {
+    /// Found points should be closer to this example
+    #[prost(message, optional, tag = "1")]
+    pub positive: ::core::option::Option<VectorExample>,
+    /// than to this one
+    #[prost(message, optional, tag = "2")]
+    pub negative: ::core::option::Option<VectorExample>,
+}
+#[derive(validator::Validate)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DiscoverPoints {
+    /// name of the collection
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1, max = 255))]
+    pub collection_name: ::prost::alloc::string::String,
+    /// Look for vectors closest to this
+    #[prost(message, optional, tag = "2")]
+    pub target: ::core::option::Option<VectorExample>,
+    /// Pairs of examples, which constrain the search space
+    #[prost(message, repeated, tag = "3")]
+    pub context: ::prost::alloc::vec::Vec<ContextExamplePair>,
+    /// Filter conditions - return only those points that satisfy the specified conditions
+    #[prost(message, optional, tag = "4")]
+    pub filter: ::core::option::Option<Filter>,
+    /// Max number of result
+    #[prost(uint64, tag = "5")]
+    #[validate(range(min = 1))]
+    pub limit: u64,
+    /// Offset of the result
+    #[prost(uint64, optional, tag = "6")]
+    pub offset: ::core::option::Option<u64>,
+    /// Options for specifying which payload to include or not
+    #[prost(message, optional, tag = "7")]
+    pub with_payload: ::core::option::Option<WithPayloadSelector>,
+    /// Options for specifying which vectors to include into response
+    #[prost(message, optional, tag = "8")]
+    pub with_vectors: ::core::option::Option<WithVectorsSelector>,
+    /// Search config
+    #[prost(message, optional, tag = "9")]
+    pub params: ::core::option::Option<SearchParams>,
+    /// Define which vector to use for discovery, if not specified - default vector
+    #[prost(string, optional, tag = "10")]
+    pub using: ::core::option::Option<::prost::alloc::string::String>,
+    /// Name of the collection to use for points lookup, if not specified - use current collection
+    #[prost(message, optional, tag = "11")]
+    pub lookup_from: ::core::option::Option<LookupLocation>,
+    /// Options for specifying read consistency guarantees
+    #[prost(message, optional, tag = "12")]
+    pub read_consistency: ::core::option::Option<ReadConsistency>,
+    /// Priority of the request in the search queue, default is normal
+    #[prost(enumeration = "SearchPriority", optional, tag = "13")]
+    pub priority: ::core::option::Option<i32>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct WeightedVectorExample {
+    /// Id of the point or raw vector
+    #[prost(message, optional, tag = "1")]
+    pub example: ::core::option::Option<VectorExample>,
+    /// Weight of the example vector in the query, might be negative
+    #[prost(float, tag = "2")]
+    pub weight: f32,
+}
+#[derive(validator::Validate)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SearchArithmeticPoints {
+    /// name of the collection
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1, max = 255))]
+    pub collection_name: ::prost::alloc::string::String,
+    /// Weighted examples, which sum up to the query vector
+    #[prost(message, repeated, tag = "2")]
+    pub terms: ::prost::alloc::vec::Vec<WeightedVectorExample>,
+    /// Filter conditions - return only those points that satisfy the specified conditions
+    #[prost(message, optional, tag = "3")]
+    pub filter: ::core::option::Option<Filter>,
+    /// Max number of result
+    #[prost(uint64, tag = "4")]
+    #[validate(range(min = 1))]
+    pub limit: u64,
+    /// Offset of the result
+    #[prost(uint64, optional, tag = "5")]
+    pub offset: ::core::option::Option<u64>,
+    /// Options for specifying which payload to include or not
+    #[prost(message, optional, tag = "6")]
+    pub with_payload: ::core::option::Option<WithPayloadSelector>,
+    /// Options for specifying which vectors to include into response
+    #[prost(message, optional, tag = "7")]
+    pub with_vectors: ::core::option::Option<WithVectorsSelector>,
+    /// Search config
+    #[prost(message, optional, tag = "8")]
+    pub params: ::core::option::Option<SearchParams>,
+    /// If provided - cut off results with worse scores
+    #[prost(float, optional, tag = "9")]
+    pub score_threshold: ::core::option::Option<f32>,
+    /// Define which vector to search by, if not specified - default vector
+    #[prost(string, optional, tag = "10")]
+    pub using: ::core::option::Option<::prost::alloc::string::String>,
+    /// Name of the collection to use for points lookup, if not specified - use current collection
+    #[prost(message, optional, tag = "11")]
+    pub lookup_from: ::core::option::Option<LookupLocation>,
+    /// Options for specifying read consistency guarantees
+    #[prost(message, optional, tag = "12")]
+    pub read_consistency: ::core::option::Option<ReadConsistency>,
+    /// Priority of the request in the search queue, default is normal
+    #[prost(enumeration = "SearchPriority", optional, tag = "13")]
+    pub priority: ::core::option::Option<i32>,
+}
+#[derive(validator::Validate)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CountPoints {
+    /// name of the collection
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1, max = 255))]
+    pub collection_name: ::prost::alloc::string::String,
+    /// Filter conditions - return only those points that satisfy the specified conditions
     #[prost(message, optional, tag = "2")]
     pub filter: ::core::option::Option<Filter>,
     /// If `true` - return exact count, if `false` - return approximate count
     #[prost(bool, optional, tag = "3")]
     pub exact: ::core::option::Option<bool>,
+    /// Override the strategy of reading filtered points for exact count
+    #[prost(enumeration = "PlannerHint", optional, tag = "4")]
+    pub planner_hint: ::core::option::Option<i32>,
+    /// Also count distinct values of this payload field, which has keyword or integer index
+    #[prost(string, optional, tag = "5")]
+    pub distinct: ::core::option::Option<::prost::alloc::string::String>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -2563,6 +3189,28 @@ pub struct ScoredPoint {
     /// Vectors to search
     #[prost(message, optional, tag = "6")]
     pub vectors: ::core::option::Option<Vectors>,
+    /// Words of the payload text, matched by full-text conditions of the filter
+    #[prost(message, repeated, tag = "7")]
+    pub highlights: ::prost::alloc::vec::Vec<Highlight>,
+    /// Distance in meters from the center of the geo condition of the filter
+    #[prost(double, optional, tag = "8")]
+    pub geo_distance: ::core::option::Option<f64>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Highlight {
+    /// Payload field, which contains the text
+    #[prost(string, tag = "1")]
+    pub key: ::prost::alloc::string::String,
+    /// Position of the text among the values of the field
+    #[prost(uint64, tag = "2")]
+    pub index: u64,
+    /// Position of the first character of the word
+    #[prost(uint64, tag = "3")]
+    pub start: u64,
+    /// Position after the last character of the word
+    #[prost(uint64, tag = "4")]
+    pub end: u64,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -2608,12 +3256,31 @@ pub struct ScrollResponse {
     /// Time spent to process
     #[prost(double, tag = "3")]
     pub time: f64,
+    /// Use this value as start_from of order_by for the next query
+    #[prost(double, optional, tag = "4")]
+    pub next_page_order_value: ::core::option::Option<f64>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct CountResult {
     #[prost(uint64, tag = "1")]
     pub count: u64,
+    /// Number of distinct values of the requested field. Exact up to 10000 values, approximate above
+    #[prost(uint64, optional, tag = "2")]
+    pub distinct: ::core::option::Option<u64>,
+    /// Distinct values of the requested field, used to merge counts of shards
+    #[prost(message, optional, tag = "3")]
+    pub distinct_values: ::core::option::Option<DistinctValues>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DistinctValues {
+    /// Hashes of the values, if they are counted exactly
+    #[prost(uint64, repeated, tag = "1")]
+    pub hashes: ::prost::alloc::vec::Vec<u64>,
+    /// Registers of HyperLogLog sketch, if values are counted approximately
+    #[prost(bytes = "vec", tag = "2")]
+    pub registers: ::prost::alloc::vec::Vec<u8>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -2624,6 +3291,9 @@ pub struct RetrievedPoint {
     pub payload: ::std::collections::HashMap<::prost::alloc::string::String, Value>,
     #[prost(message, optional, tag = "4")]
     pub vectors: ::core::option::Option<Vectors>,
+    /// Distance in meters to the origin of the GeoDistance order of scroll
+    #[prost(double, optional, tag = "5")]
+    pub geo_distance: ::core::option::Option<f64>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -2654,6 +3324,33 @@ pub struct RecommendBatchResponse {
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct QueryResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub result: ::prost::alloc::vec::Vec<ScoredPoint>,
+    /// Time spent to process
+    #[prost(double, tag = "2")]
+    pub time: f64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DiscoverResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub result: ::prost::alloc::vec::Vec<ScoredPoint>,
+    /// Time spent to process
+    #[prost(double, tag = "2")]
+    pub time: f64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SearchArithmeticResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub result: ::prost::alloc::vec::Vec<ScoredPoint>,
+    /// Time spent to process
+    #[prost(double, tag = "2")]
+    pub time: f64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct Filter {
     /// At least one of those conditions should match
     #[prost(message, repeated, tag = "1")]
@@ -2848,6 +3545,12 @@ pub struct PointStruct {
     pub payload: ::std::collections::HashMap<::prost::alloc::string::String, Value>,
     #[prost(message, optional, tag = "4")]
     pub vectors: ::core::option::Option<Vectors>,
+    /// External sequence number, stale writes are skipped
+    #[prost(uint64, optional, tag = "5")]
+    pub sequence: ::core::option::Option<u64>,
+    /// Hash of the point content, unchanged writes are skipped
+    #[prost(string, optional, tag = "6")]
+    pub content_hash: ::core::option::Option<::prost::alloc::string::String>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -2859,64 +3562,184 @@ pub struct GeoPoint {
 }
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
 #[repr(i32)]
-pub enum WriteOrderingType {
-    /// Write operations may be reordered, works faster, default
-    Weak = 0,
-    /// Write operations go through dynamically selected leader, may be inconsistent for a short period of time in case of leader change
-    Medium = 1,
-    /// Write operations go through the permanent leader, consistent, but may be unavailable if leader is down
-    Strong = 2,
+pub enum ScrollOrder {
+    /// Ascending order of point IDs, supports pagination with offset
+    Id = 0,
+    /// Pseudo-random sample of matching points, reproducible with the same seed
+    Random = 1,
+    /// Nearest to the origin of geo_order points first, requires geo index of the field
+    GeoDistance = 2,
+}
+impl ScrollOrder {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            ScrollOrder::Id => "Id",
+            ScrollOrder::Random => "Random",
+            ScrollOrder::GeoDistance => "GeoDistance",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "Id" => Some(Self::Id),
+            "Random" => Some(Self::Random),
+            "GeoDistance" => Some(Self::GeoDistance),
+            _ => None,
+        }
+    }
 }
-impl WriteOrderingType {
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum PlannerHint {
+    /// Read filtered points using the payload index
+    IndexScan = 0,
+    /// Read points in order of their IDs and check each of them against the filter
+    StreamScan = 1,
+    /// Search without the HNSW graph, score all points which satisfy the filter
+    PlainSearch = 2,
+}
+impl PlannerHint {
     /// String value of the enum field names used in the ProtoBuf definition.
     ///
     /// The values are not transformed in any way and thus are considered stable
     /// (if the ProtoBuf definition does not change) and safe for programmatic use.
     pub fn as_str_name(&self) -> &'static str {
         match self {
-            WriteOrderingType::Weak => "Weak",
-            WriteOrderingType::Medium => "Medium",
-            WriteOrderingType::Strong => "Strong",
+            PlannerHint::IndexScan => "IndexScan",
+            PlannerHint::StreamScan => "StreamScan",
+            PlannerHint::PlainSearch => "PlainSearch",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
     pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
         match value {
-            "Weak" => Some(Self::Weak),
-            "Medium" => Some(Self::Medium),
-            "Strong" => Some(Self::Strong),
+            "IndexScan" => Some(Self::IndexScan),
+            "StreamScan" => Some(Self::StreamScan),
+            "PlainSearch" => Some(Self::PlainSearch),
             _ => None,
         }
     }
 }
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
 #[repr(i32)]
-pub enum ReadConsistencyType {
-    /// Send request to all nodes and return points which are present on all of them
-    All = 0,
-    /// Send requests to all nodes and return points which are present on majority of them
-    Majority = 1,
-    /// Send requests to half + 1 nodes, return points which are present on all of them
-    Quorum = 2,
+pub enum Direction {
+    /// Smallest values first
+    Asc = 0,
+    /// Largest values first
+    Desc = 1,
 }
-impl ReadConsistencyType {
+impl Direction {
     /// String value of the enum field names used in the ProtoBuf definition.
     ///
     /// The values are not transformed in any way and thus are considered stable
     /// (if the ProtoBuf definition does not change) and safe for programmatic use.
     pub fn as_str_name(&self) -> &'static str {
         match self {
-            ReadConsistencyType::All => "All",
-            ReadConsistencyType::Majority => "Majority",
-            ReadConsistencyType::Quorum => "Quorum",
+            Direction::Asc => "Asc",
+            Direction::Desc => "Desc",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
     pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
         match value {
-            "All" => Some(Self::All),
-            "Majority" => Some(Self::Majority),
-            "Quorum" => Some(Self::Quorum),
+            "Asc" => Some(Self::Asc),
+            "Desc" => Some(Self::Desc),
+            _ => None,
+        }
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum SearchPriority {
+    /// Rejected, if the search queue is more than 80% full
+    Normal = 0,
+    /// Rejected, if the search queue is more than half full
+    Low = 1,
+    /// Rejected only if the search queue is full
+    High = 2,
+}
+impl SearchPriority {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            SearchPriority::Normal => "Normal",
+            SearchPriority::Low => "Low",
+            SearchPriority::High => "High",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "Normal" => Some(Self::Normal),
+            "Low" => Some(Self::Low),
+            "High" => Some(Self::High),
+            _ => None,
+        }
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum Fusion {
+    /// Reciprocal rank fusion, does not depend on the scale of scores
+    Rrf = 0,
+    /// Sum of scores of the prefetches, multiplied by their weights
+    WeightedSum = 1,
+    /// Sum of scores of the prefetches, scaled into range \[0, 1\] and multiplied by their weights
+    NormalizedSum = 2,
+}
+impl Fusion {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Fusion::Rrf => "RRF",
+            Fusion::WeightedSum => "WeightedSum",
+            Fusion::NormalizedSum => "NormalizedSum",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "RRF" => Some(Self::Rrf),
+            "WeightedSum" => Some(Self::WeightedSum),
+            "NormalizedSum" => Some(Self::NormalizedSum),
+            _ => None,
+        }
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum RecommendStrategy {
+    /// Search by the weighted average of positive examples, shifted away from negative ones
+    AverageVector = 0,
+    /// Score candidates by the most similar example, scaled by its weight, negative if it is a negative example
+    BestScore = 1,
+}
+impl RecommendStrategy {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            RecommendStrategy::AverageVector => "AverageVector",
+            RecommendStrategy::BestScore => "BestScore",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "AverageVector" => Some(Self::AverageVector),
+            "BestScore" => Some(Self::BestScore),
             _ => None,
         }
     }
@@ -2929,6 +3752,7 @@ pub enum FieldType {
     Float = 2,
     Geo = 3,
     Text = 4,
+    Bool = 5,
 }
 impl FieldType {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -2942,6 +3766,7 @@ impl FieldType {
             FieldType::Float => "FieldTypeFloat",
             FieldType::Geo => "FieldTypeGeo",
             FieldType::Text => "FieldTypeText",
+            FieldType::Bool => "FieldTypeBool",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -2952,6 +3777,7 @@ impl FieldType {
             "FieldTypeFloat" => Some(Self::Float),
             "FieldTypeGeo" => Some(Self::Geo),
             "FieldTypeText" => Some(Self::Text),
+            "FieldTypeBool" => Some(Self::Bool),
             _ => None,
         }
     }
@@ -3363,20 +4189,89 @@ pub mod points_client {
                     )
                 })?;
             let codec = tonic::codec::ProstCodec::default();
-            let path = http::uri::PathAndQuery::from_static("/qdrant.Points/Scroll");
+            let path = http::uri::PathAndQuery::from_static("/qdrant.Points/Scroll");
+            let mut req = request.into_request();
+            req.extensions_mut().insert(GrpcMethod::new("qdrant.Points", "Scroll"));
+            self.inner.unary(req, path, codec).await
+        }
+        ///
+        /// Look for the points which are closer to stored positive examples and at the same time further to negative examples.
+        pub async fn recommend(
+            &mut self,
+            request: impl tonic::IntoRequest<super::RecommendPoints>,
+        ) -> std::result::Result<
+            tonic::Response<super::RecommendResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/qdrant.Points/Recommend");
+            let mut req = request.into_request();
+            req.extensions_mut().insert(GrpcMethod::new("qdrant.Points", "Recommend"));
+            self.inner.unary(req, path, codec).await
+        }
+        ///
+        /// Look for the points which are closer to stored positive examples and at the same time further to negative examples.
+        pub async fn recommend_batch(
+            &mut self,
+            request: impl tonic::IntoRequest<super::RecommendBatchPoints>,
+        ) -> std::result::Result<
+            tonic::Response<super::RecommendBatchResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.Points/RecommendBatch",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("qdrant.Points", "RecommendBatch"));
+            self.inner.unary(req, path, codec).await
+        }
+        ///
+        /// Count points in collection with given filtering conditions
+        pub async fn count(
+            &mut self,
+            request: impl tonic::IntoRequest<super::CountPoints>,
+        ) -> std::result::Result<tonic::Response<super::CountResponse>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/qdrant.Points/Count");
             let mut req = request.into_request();
-            req.extensions_mut().insert(GrpcMethod::new("qdrant.Points", "Scroll"));
+            req.extensions_mut().insert(GrpcMethod::new("qdrant.Points", "Count"));
             self.inner.unary(req, path, codec).await
         }
         ///
-        /// Look for the points which are closer to stored positive examples and at the same time further to negative examples.
-        pub async fn recommend(
+        /// Search points by several prefetch queries over different vectors and fuse their results into a single ranking.
+        pub async fn query(
             &mut self,
-            request: impl tonic::IntoRequest<super::RecommendPoints>,
-        ) -> std::result::Result<
-            tonic::Response<super::RecommendResponse>,
-            tonic::Status,
-        > {
+            request: impl tonic::IntoRequest<super::QueryPoints>,
+        ) -> std::result::Result<tonic::Response<super::QueryResponse>, tonic::Status> {
             self.inner
                 .ready()
                 .await
@@ -3387,18 +4282,18 @@ pub mod points_client {
                     )
                 })?;
             let codec = tonic::codec::ProstCodec::default();
-            let path = http::uri::PathAndQuery::from_static("/qdrant.Points/Recommend");
+            let path = http::uri::PathAndQuery::from_static("/qdrant.Points/Query");
             let mut req = request.into_request();
-            req.extensions_mut().insert(GrpcMethod::new("qdrant.Points", "Recommend"));
+            req.extensions_mut().insert(GrpcMethod::new("qdrant.Points", "Query"));
             self.inner.unary(req, path, codec).await
         }
         ///
-        /// Look for the points which are closer to stored positive examples and at the same time further to negative examples.
-        pub async fn recommend_batch(
+        /// Look for the points close to the target example, which are closer to positive examples than to negative ones of the context pairs.
+        pub async fn discover(
             &mut self,
-            request: impl tonic::IntoRequest<super::RecommendBatchPoints>,
+            request: impl tonic::IntoRequest<super::DiscoverPoints>,
         ) -> std::result::Result<
-            tonic::Response<super::RecommendBatchResponse>,
+            tonic::Response<super::DiscoverResponse>,
             tonic::Status,
         > {
             self.inner
@@ -3411,20 +4306,20 @@ pub mod points_client {
                     )
                 })?;
             let codec = tonic::codec::ProstCodec::default();
-            let path = http::uri::PathAndQuery::from_static(
-                "/qdrant.Points/RecommendBatch",
-            );
+            let path = http::uri::PathAndQuery::from_static("/qdrant.Points/Discover");
             let mut req = request.into_request();
-            req.extensions_mut()
-                .insert(GrpcMethod::new("qdrant.Points", "RecommendBatch"));
+            req.extensions_mut().insert(GrpcMethod::new("qdrant.Points", "Discover"));
             self.inner.unary(req, path, codec).await
         }
         ///
-        /// Count points in collection with given filtering conditions
-        pub async fn count(
+        /// Look for the points closest to the weighted sum of the vectors of the examples.
+        pub async fn search_arithmetic(
             &mut self,
-            request: impl tonic::IntoRequest<super::CountPoints>,
-        ) -> std::result::Result<tonic::Response<super::CountResponse>, tonic::Status> {
+            request: impl tonic::IntoRequest<super::SearchArithmeticPoints>,
+        ) -> std::result::Result<
+            tonic::Response<super::SearchArithmeticResponse>,
+            tonic::Status,
+        > {
             self.inner
                 .ready()
                 .await
@@ -3435,9 +4330,12 @@ pub mod points_client {
                     )
                 })?;
             let codec = tonic::codec::ProstCodec::default();
-            let path = http::uri::PathAndQuery::from_static("/qdrant.Points/Count");
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.Points/SearchArithmetic",
+            );
             let mut req = request.into_request();
-            req.extensions_mut().insert(GrpcMethod::new("qdrant.Points", "Count"));
+            req.extensions_mut()
+                .insert(GrpcMethod::new("qdrant.Points", "SearchArithmetic"));
             self.inner.unary(req, path, codec).await
         }
     }
@@ -3572,6 +4470,30 @@ pub mod points_server {
             &self,
             request: tonic::Request<super::CountPoints>,
         ) -> std::result::Result<tonic::Response<super::CountResponse>, tonic::Status>;
+        ///
+        /// Search points by several prefetch queries over different vectors and fuse their results into a single ranking.
+        async fn query(
+            &self,
+            request: tonic::Request<super::QueryPoints>,
+        ) -> std::result::Result<tonic::Response<super::QueryResponse>, tonic::Status>;
+        ///
+        /// Look for the points close to the target example, which are closer to positive examples than to negative ones of the context pairs.
+        async fn discover(
+            &self,
+            request: tonic::Request<super::DiscoverPoints>,
+        ) -> std::result::Result<
+            tonic::Response<super::DiscoverResponse>,
+            tonic::Status,
+        >;
+        ///
+        /// Look for the points closest to the weighted sum of the vectors of the examples.
+        async fn search_arithmetic(
+            &self,
+            request: tonic::Request<super::SearchArithmeticPoints>,
+        ) -> std::result::Result<
+            tonic::Response<super::SearchArithmeticResponse>,
+            tonic::Status,
+        >;
     }
     #[derive(Debug)]
     pub struct PointsServer<T: Points> {
@@ -4306,6 +5228,136 @@ pub mod points_server {
                     };
                     Box::pin(fut)
                 }
+                "/qdrant.Points/Query" => {
+                    #[allow(non_camel_case_types)]
+                    struct QuerySvc<T: Points>(pub Arc<T>);
+                    impl<T: Points> tonic::server::UnaryService<super::QueryPoints>
+                    for QuerySvc<T> {
+                        type Response = super::QueryResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::QueryPoints>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move { (*inner).query(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = QuerySvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/qdrant.Points/Discover" => {
+                    #[allow(non_camel_case_types)]
+                    struct DiscoverSvc<T: Points>(pub Arc<T>);
+                    impl<T: Points> tonic::server::UnaryService<super::DiscoverPoints>
+                    for DiscoverSvc<T> {
+                        type Response = super::DiscoverResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::DiscoverPoints>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move { (*inner).discover(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = DiscoverSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/qdrant.Points/SearchArithmetic" => {
+                    #[allow(non_camel_case_types)]
+                    struct SearchArithmeticSvc<T: Points>(pub Arc<T>);
+                    impl<
+                        T: Points,
+                    > tonic::server::UnaryService<super::SearchArithmeticPoints>
+                    for SearchArithmeticSvc<T> {
+                        type Response = super::SearchArithmeticResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::SearchArithmeticPoints>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                (*inner).search_arithmetic(request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = SearchArithmeticSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 _ => {
                     Box::pin(async move {
                         Ok(
@@ -4496,23 +5548,154 @@ pub struct RecommendPointsInternal {
     #[prost(uint32, optional, tag = "2")]
     pub shard_id: ::core::option::Option<u32>,
 }
-#[derive(validator::Validate)]
+#[derive(validator::Validate)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetPointsInternal {
+    #[prost(message, optional, tag = "1")]
+    #[validate]
+    pub get_points: ::core::option::Option<GetPoints>,
+    #[prost(uint32, optional, tag = "2")]
+    pub shard_id: ::core::option::Option<u32>,
+}
+#[derive(validator::Validate)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CountPointsInternal {
+    #[prost(message, optional, tag = "1")]
+    #[validate]
+    pub count_points: ::core::option::Option<CountPoints>,
+    #[prost(uint32, optional, tag = "2")]
+    pub shard_id: ::core::option::Option<u32>,
+}
+#[derive(validator::Validate)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct IdChunksInternal {
+    /// name of the collection
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1, max = 255))]
+    pub collection_name: ::prost::alloc::string::String,
+    /// Number of chunks to split the points of the shard into
+    #[prost(uint32, tag = "2")]
+    #[validate(range(min = 1))]
+    pub chunks: u32,
+    #[prost(uint32, optional, tag = "3")]
+    pub shard_id: ::core::option::Option<u32>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct IdChunk {
+    /// First point id of the chunk
+    #[prost(message, optional, tag = "1")]
+    pub start: ::core::option::Option<PointId>,
+    /// Number of points in the chunk
+    #[prost(uint64, tag = "2")]
+    pub count: u64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct IdChunksResponse {
+    /// Chunks of consecutive point ids, ordered by id
+    #[prost(message, repeated, tag = "1")]
+    pub result: ::prost::alloc::vec::Vec<IdChunk>,
+    /// Time spent to process
+    #[prost(double, tag = "2")]
+    pub time: f64,
+}
+#[derive(validator::Validate)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PointVersionsInternal {
+    /// name of the collection
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1, max = 255))]
+    pub collection_name: ::prost::alloc::string::String,
+    /// Ids of the points to look up
+    #[prost(message, repeated, tag = "2")]
+    pub ids: ::prost::alloc::vec::Vec<PointId>,
+    #[prost(uint32, optional, tag = "3")]
+    pub shard_id: ::core::option::Option<u32>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PointVersion {
+    /// Id of the existing point
+    #[prost(message, optional, tag = "1")]
+    pub id: ::core::option::Option<PointId>,
+    /// Version of the last update of the point
+    #[prost(uint64, tag = "2")]
+    pub version: u64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PointVersionsResponse {
+    /// Versions of the points, which exist in the shard
+    #[prost(message, repeated, tag = "1")]
+    pub result: ::prost::alloc::vec::Vec<PointVersion>,
+    /// Time spent to process
+    #[prost(double, tag = "2")]
+    pub time: f64,
+}
+#[derive(validator::Validate)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct WaitAppliedInternal {
+    /// name of the collection
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1, max = 255))]
+    pub collection_name: ::prost::alloc::string::String,
+    /// Number of the operation in the WAL of the replica
+    #[prost(uint64, tag = "2")]
+    pub version: u64,
+    /// Wait timeout in seconds
+    #[prost(uint64, tag = "3")]
+    #[validate(range(min = 1))]
+    pub timeout: u64,
+    #[prost(uint32, optional, tag = "4")]
+    pub shard_id: ::core::option::Option<u32>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct WaitAppliedResponse {
+    /// Time spent to process
+    #[prost(double, tag = "1")]
+    pub time: f64,
+}
+#[derive(validator::Validate)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UpdatePointVectors {
+    /// name of the collection
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1, max = 255))]
+    pub collection_name: ::prost::alloc::string::String,
+    /// Wait until the changes have been applied?
+    #[prost(bool, optional, tag = "2")]
+    pub wait: ::core::option::Option<bool>,
+    /// Vectors to set, other vectors of the points are kept
+    #[prost(message, repeated, tag = "3")]
+    pub points: ::prost::alloc::vec::Vec<PointVectors>,
+    #[prost(message, optional, tag = "4")]
+    pub ordering: ::core::option::Option<WriteOrdering>,
+}
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct GetPointsInternal {
+pub struct PointVectors {
+    /// Id of the existing point
     #[prost(message, optional, tag = "1")]
-    #[validate]
-    pub get_points: ::core::option::Option<GetPoints>,
-    #[prost(uint32, optional, tag = "2")]
-    pub shard_id: ::core::option::Option<u32>,
+    pub id: ::core::option::Option<PointId>,
+    /// Vectors to set
+    #[prost(message, optional, tag = "2")]
+    pub vectors: ::core::option::Option<Vectors>,
 }
 #[derive(validator::Validate)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct CountPointsInternal {
+pub struct UpdateVectorsInternal {
     #[prost(message, optional, tag = "1")]
     #[validate]
-    pub count_points: ::core::option::Option<CountPoints>,
+    pub update_vectors: ::core::option::Option<UpdatePointVectors>,
     #[prost(uint32, optional, tag = "2")]
     pub shard_id: ::core::option::Option<u32>,
 }
@@ -4963,6 +6146,106 @@ pub mod points_internal_client {
             req.extensions_mut().insert(GrpcMethod::new("qdrant.PointsInternal", "Get"));
             self.inner.unary(req, path, codec).await
         }
+        pub async fn id_chunks(
+            &mut self,
+            request: impl tonic::IntoRequest<super::IdChunksInternal>,
+        ) -> std::result::Result<
+            tonic::Response<super::IdChunksResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.PointsInternal/IdChunks",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("qdrant.PointsInternal", "IdChunks"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn point_versions(
+            &mut self,
+            request: impl tonic::IntoRequest<super::PointVersionsInternal>,
+        ) -> std::result::Result<
+            tonic::Response<super::PointVersionsResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.PointsInternal/PointVersions",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("qdrant.PointsInternal", "PointVersions"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn wait_applied(
+            &mut self,
+            request: impl tonic::IntoRequest<super::WaitAppliedInternal>,
+        ) -> std::result::Result<
+            tonic::Response<super::WaitAppliedResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.PointsInternal/WaitApplied",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("qdrant.PointsInternal", "WaitApplied"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn update_vectors(
+            &mut self,
+            request: impl tonic::IntoRequest<super::UpdateVectorsInternal>,
+        ) -> std::result::Result<
+            tonic::Response<super::PointsOperationResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.PointsInternal/UpdateVectors",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("qdrant.PointsInternal", "UpdateVectors"));
+            self.inner.unary(req, path, codec).await
+        }
     }
 }
 /// Generated server implementations.
@@ -5065,6 +6348,34 @@ pub mod points_internal_server {
             &self,
             request: tonic::Request<super::GetPointsInternal>,
         ) -> std::result::Result<tonic::Response<super::GetResponse>, tonic::Status>;
+        async fn id_chunks(
+            &self,
+            request: tonic::Request<super::IdChunksInternal>,
+        ) -> std::result::Result<
+            tonic::Response<super::IdChunksResponse>,
+            tonic::Status,
+        >;
+        async fn point_versions(
+            &self,
+            request: tonic::Request<super::PointVersionsInternal>,
+        ) -> std::result::Result<
+            tonic::Response<super::PointVersionsResponse>,
+            tonic::Status,
+        >;
+        async fn wait_applied(
+            &self,
+            request: tonic::Request<super::WaitAppliedInternal>,
+        ) -> std::result::Result<
+            tonic::Response<super::WaitAppliedResponse>,
+            tonic::Status,
+        >;
+        async fn update_vectors(
+            &self,
+            request: tonic::Request<super::UpdateVectorsInternal>,
+        ) -> std::result::Result<
+            tonic::Response<super::PointsOperationResponse>,
+            tonic::Status,
+        >;
     }
     #[derive(Debug)]
     pub struct PointsInternalServer<T: PointsInternal> {
@@ -5823,6 +7134,182 @@ pub mod points_internal_server {
                     };
                     Box::pin(fut)
                 }
+                "/qdrant.PointsInternal/IdChunks" => {
+                    #[allow(non_camel_case_types)]
+                    struct IdChunksSvc<T: PointsInternal>(pub Arc<T>);
+                    impl<
+                        T: PointsInternal,
+                    > tonic::server::UnaryService<super::IdChunksInternal>
+                    for IdChunksSvc<T> {
+                        type Response = super::IdChunksResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::IdChunksInternal>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move { (*inner).id_chunks(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = IdChunksSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/qdrant.PointsInternal/PointVersions" => {
+                    #[allow(non_camel_case_types)]
+                    struct PointVersionsSvc<T: PointsInternal>(pub Arc<T>);
+                    impl<
+                        T: PointsInternal,
+                    > tonic::server::UnaryService<super::PointVersionsInternal>
+                    for PointVersionsSvc<T> {
+                        type Response = super::PointVersionsResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::PointVersionsInternal>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move { (*inner).point_versions(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = PointVersionsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/qdrant.PointsInternal/WaitApplied" => {
+                    #[allow(non_camel_case_types)]
+                    struct WaitAppliedSvc<T: PointsInternal>(pub Arc<T>);
+                    impl<
+                        T: PointsInternal,
+                    > tonic::server::UnaryService<super::WaitAppliedInternal>
+                    for WaitAppliedSvc<T> {
+                        type Response = super::WaitAppliedResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::WaitAppliedInternal>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move { (*inner).wait_applied(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = WaitAppliedSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/qdrant.PointsInternal/UpdateVectors" => {
+                    #[allow(non_camel_case_types)]
+                    struct UpdateVectorsSvc<T: PointsInternal>(pub Arc<T>);
+                    impl<
+                        T: PointsInternal,
+                    > tonic::server::UnaryService<super::UpdateVectorsInternal>
+                    for UpdateVectorsSvc<T> {
+                        type Response = super::PointsOperationResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::UpdateVectorsInternal>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move { (*inner).update_vectors(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = UpdateVectorsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 _ => {
                     Box::pin(async move {
                         Ok(
@@ -37,6 +37,8 @@ fn create_rnd_batch() -> CollectionUpdateOperations {
             id: i.into(),
             vector: vectors.into(),
             payload: Some(Payload(payload_map)),
+            sequence: None,
+            content_hash: None,
         };
         points.push(point);
     }
@@ -64,12 +66,22 @@ fn batch_search_bench(c: &mut Criterion) {
             distance: Distance::Dot,
             hnsw_config: None,
             quantization_config: None,
+            projection: None,
         }
         .into(),
         shard_number: NonZeroU32::new(1).expect("Shard number can not be zero"),
         replication_factor: NonZeroU32::new(1).unwrap(),
         write_consistency_factor: NonZeroU32::new(1).unwrap(),
         on_disk_payload: false,
+        vector_aliases: Default::default(),
+        default_vector: None,
+        defrag_key: None,
+        deduplicate_upserts: false,
+        max_concurrent_searches: 0,
+        acl_key: None,
+        default_write_ordering: None,
+        default_read_consistency: None,
+        sparse_vectors: Default::default(),
     };
 
     let collection_config = CollectionConfig {
@@ -83,10 +95,14 @@ fn batch_search_bench(c: &mut Criterion) {
             indexing_threshold: 50_000,
             flush_interval_sec: 30,
             max_optimization_threads: 2,
+            target_segment_count: None,
+            max_segment_size_mb: None,
         },
         wal_config,
         hnsw_config: Default::default(),
         quantization_config: Default::default(),
+        lifecycle_config: None,
+        created_at: None,
     };
 
     let shared_config = Arc::new(RwLock::new(collection_config));
@@ -144,6 +160,8 @@ fn batch_search_bench(c: &mut Criterion) {
                             with_payload: None,
                             with_vector: None,
                             score_threshold: None,
+                            with_highlights: None,
+                            with_geo_distance: None,
                         };
                         let result = shard
                             .search(
@@ -176,6 +194,8 @@ fn batch_search_bench(c: &mut Criterion) {
                             with_payload: None,
                             with_vector: None,
                             score_threshold: None,
+                            with_highlights: None,
+                            with_geo_distance: None,
                         };
                         searches.push(search_query);
                     }
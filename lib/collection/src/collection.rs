@@ -1,44 +1,96 @@
 use std::cmp::max;
 use std::collections::hash_map::Entry;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::future::Future;
 use std::ops::Deref;
-use std::path::{Path, PathBuf};
+use std::path::{Component, Path, PathBuf};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 use futures::future::{join_all, try_join_all};
 use itertools::Itertools;
+use segment::common::utils::sampling_key;
 use segment::common::version::StorageVersion;
+use segment::data_types::distinct::DistinctValues;
+use segment::data_types::histogram::{HistogramBuckets, ValueBounds};
+use segment::data_types::vectors::{NamedVector, VectorStruct, DEFAULT_VECTOR_NAME};
+use segment::entry::entry_point::SegmentEntry;
+use segment::segment_constructor::{
+    check_segment_compatibility, is_segment_committed, load_segment, migrate_segment,
+};
 use segment::spaces::tools::{peek_top_largest_iterable, peek_top_smallest_iterable};
 use segment::types::{
-    ExtendedPointId, Order, ScoredPoint, WithPayload, WithPayloadInterface, WithVector,
+    Condition, ExtendedPointId, Filter, IsEmptyCondition, MemoryUsage, Order, PayloadField,
+    PayloadFieldSchema, PayloadKeyType, PayloadKeyTypeRef, PayloadSchemaType, PointIdType,
+    QuantizationConfig, QuantizationSearchParams, RecordPayload, ScoredPoint, SearchParams,
+    SeqNumberType, WithPayload, WithPayloadInterface, WithVector,
 };
 use semver::Version;
 use tar::Builder as TarBuilder;
 use tokio::fs::{copy, create_dir_all, remove_dir_all, remove_file, rename};
 use tokio::runtime::Handle;
-use tokio::sync::{Mutex, RwLock, RwLockWriteGuard};
+use tokio::sync::{Mutex, OwnedSemaphorePermit, RwLock, RwLockWriteGuard, Semaphore};
 use validator::Validate;
 
+use crate::collection_manager::segments_updater::rebuild_field_index;
 use crate::collection_state::{ShardInfo, State};
 use crate::common::is_ready::IsReady;
 use crate::config::CollectionConfig;
+use crate::geo_distance::{add_geo_distances, scroll_by_geo_distance};
 use crate::hash_ring::HashRing;
-use crate::operations::config_diff::{CollectionParamsDiff, DiffConfig, OptimizersConfigDiff};
-use crate::operations::consistency_params::ReadConsistency;
-use crate::operations::point_ops::WriteOrdering;
+use crate::highlight::add_highlights;
+use crate::operations::backfill_ops::{
+    BackfillBatch, BackfillJobId, BackfillJobInfo, BackfillJobs, BackfillPointsRequest,
+    BackfillPointsResult, CreateBackfillJob,
+};
+use crate::operations::cardinality_ops::{
+    CardinalityEstimationRequest, CardinalityEstimationResult, ShardCardinalityEstimation,
+};
+use crate::operations::config_diff::{
+    CollectionParamsDiff, DiffConfig, OptimizersConfigDiff, WalConfigDiff,
+};
+use crate::operations::config_history_ops::{
+    config_diff, ConfigChange, CONFIG_HISTORY_FILE, CONFIG_HISTORY_SIZE,
+};
+use crate::operations::consistency_params::{
+    ConsistencyToken, ReadConsistency, CONSISTENCY_TOKEN_WAIT_TIMEOUT,
+};
+use crate::operations::facet_ops::{top_values, FacetRequest, FacetResponse};
+use crate::operations::filter_validation::{check_filter_fields, FilterCheck, FilterValidation};
+use crate::operations::histogram_ops::{histogram_buckets, HistogramRequest, HistogramResult};
+use crate::operations::id_range_ops::{
+    partition_id_ranges, IdChunk, IdRange, CHUNKS_PER_PARTITION,
+};
+use crate::operations::index_rebuild_ops::{IndexRebuildInfo, IndexRebuildStatus};
+use crate::operations::lifecycle_ops::LifecycleConfig;
+use crate::operations::orphan_files_ops::{remove_orphaned_path, OrphanFilesResult, OrphanedFiles};
+use crate::operations::payload_schema_ops::{
+    infer_payload_schema, PayloadSchemaInferenceRequest, PayloadSchemaInferenceResult,
+};
+use crate::operations::point_access_ops::{HotPointsReport, HotPointsRequest, PointAccessStats};
+use crate::operations::point_ops::{PointOperations, PointVectors, WriteOrdering};
+use crate::operations::recall_ops::{RecallEstimationRequest, RecallEstimationResult};
+use crate::operations::scoring_benchmark_ops::{
+    aggregate_measurements, ScoringBenchmarkRequest, ScoringBenchmarkResult,
+};
 use crate::operations::shared_storage_config::SharedStorageConfig;
 use crate::operations::snapshot_ops::{
-    get_snapshot_description, list_snapshots_in_directory, SnapshotDescription,
+    get_snapshot_description, list_snapshots_in_directory, SegmentSnapshotInspection,
+    ShardSnapshotInspection, SnapshotDescription, SnapshotInspection, SnapshotVerification,
 };
+use crate::operations::terms_ops::{top_terms, TermsRequest, TermsResult};
 use crate::operations::types::{
     CollectionClusterInfo, CollectionError, CollectionInfo, CollectionResult, CountRequest,
-    CountResult, LocalShardInfo, NodeType, PointRequest, Record, RemoteShardInfo, ScrollRequest,
-    ScrollResult, SearchRequest, SearchRequestBatch, UpdateResult,
+    CountResult, FlushResult, GeoDistanceOrder, LocalShardInfo, NodeType, OrderBy, PeerRoute,
+    PointRequest, Record, RemoteShardInfo, ScrollOrder, ScrollRequest, ScrollResult, SearchRequest,
+    SearchRequestBatch, ShardRoute, UpdateResult, VectorParams, VectorsConfig, WalTruncateResult,
 };
+use crate::operations::vacuum_ops::{VacuumRequest, VacuumResult};
+use crate::operations::wal_replay_ops::{ShardWalReplayResult, WalReplayRequest, WalReplayResult};
 use crate::operations::CollectionUpdateOperations;
 use crate::optimizers_builder::OptimizersConfig;
+use crate::order_by::scroll_by_order;
+use crate::save_on_disk::SaveOnDisk;
 use crate::shards::channel_service::ChannelService;
 use crate::shards::collection_shard_distribution::CollectionShardDistribution;
 use crate::shards::local_shard::LocalShard;
@@ -58,13 +110,17 @@ use crate::shards::transfer::shard_transfer::{
 };
 use crate::shards::transfer::transfer_tasks_pool::{TaskResult, TransferTasksPool};
 use crate::shards::{replica_set, CollectionId, HASH_RING_SHARD_SCALE};
-use crate::telemetry::CollectionTelemetry;
+use crate::telemetry::{CollectionTelemetry, SearchRecallAggregator};
+use crate::wal_archive::{archived_files, read_archived_file};
 
 pub type VectorLookupFuture<'a> = Box<dyn Future<Output = CollectionResult<Vec<Record>>> + 'a>;
 pub type OnTransferFailure = Arc<dyn Fn(ShardTransfer, CollectionId, &str) + Send + Sync>;
 pub type OnTransferSuccess = Arc<dyn Fn(ShardTransfer, CollectionId) + Send + Sync>;
 pub type RequestShardTransfer = Arc<dyn Fn(ShardTransfer) + Send + Sync>;
 
+/// Number of vectors, read from each segment to verify the restored snapshot
+const RESTORE_VERIFICATION_SAMPLES: usize = 100;
+
 struct CollectionVersion;
 
 impl StorageVersion for CollectionVersion {
@@ -73,6 +129,10 @@ impl StorageVersion for CollectionVersion {
     }
 }
 
+fn new_search_limiter(max_concurrent_searches: usize) -> Option<Arc<Semaphore>> {
+    (max_concurrent_searches > 0).then(|| Arc::new(Semaphore::new(max_concurrent_searches)))
+}
+
 /// Collection's data is split into several shards.
 pub struct Collection {
     pub(crate) id: CollectionId,
@@ -86,6 +146,18 @@ pub struct Collection {
     snapshots_path: PathBuf,
     channel_service: ChannelService,
     transfer_tasks: Mutex<TransferTasksPool>,
+    backfill_jobs: Mutex<BackfillJobs>,
+    // Progress of payload index rebuilds by field, not persisted
+    index_rebuilds: Arc<parking_lot::Mutex<HashMap<PayloadKeyType, IndexRebuildInfo>>>,
+    search_recall: parking_lot::Mutex<SearchRecallAggregator>,
+    // Access counters of points, accumulated from sampled client requests, not persisted
+    point_access: parking_lot::Mutex<PointAccessStats>,
+    // Orphaned files of local shards, found by the last check, not persisted
+    orphaned_files: parking_lot::Mutex<OrphanedFiles>,
+    // Dead replicas of this peer by shard, with the time of the next recovery attempt, not persisted
+    dead_replica_recovery: parking_lot::Mutex<HashMap<ShardId, Instant>>,
+    // Log of the recent config changes, latest last
+    config_history: SaveOnDisk<Vec<ConfigChange>>,
     request_shard_transfer_cb: RequestShardTransfer,
     #[allow(dead_code)] //Might be useful in case of repartition implementation
     notify_peer_failure_cb: ChangePeerState,
@@ -97,6 +169,9 @@ pub struct Collection {
     // Lock is acquired for read on update operation and can be acquired for write externally,
     // which will block all update operations until the lock is released.
     updates_lock: RwLock<()>,
+    // Limits concurrent searches in the collection, if `max_concurrent_searches` is configured.
+    // Replaced on config update, permits of already running searches are released to the old one.
+    search_limiter: parking_lot::RwLock<Option<Arc<Semaphore>>>,
     // Search runtime handle.
     search_runtime: Handle,
     // Update runtime handle.
@@ -163,6 +238,9 @@ impl Collection {
         CollectionVersion::save(path)?;
         collection_config.save(path)?;
 
+        let search_limiter = new_search_limiter(collection_config.params.max_concurrent_searches);
+        let config_history = SaveOnDisk::load_or_init(path.join(CONFIG_HISTORY_FILE))?;
+
         Ok(Self {
             id: name.clone(),
             shards_holder: locked_shard_holder,
@@ -174,11 +252,19 @@ impl Collection {
             snapshots_path: snapshots_path.to_owned(),
             channel_service,
             transfer_tasks: Mutex::new(TransferTasksPool::new(name.clone())),
+            backfill_jobs: Default::default(),
+            index_rebuilds: Default::default(),
+            search_recall: Default::default(),
+            point_access: Default::default(),
+            orphaned_files: Default::default(),
+            dead_replica_recovery: Default::default(),
+            config_history,
             request_shard_transfer_cb: request_shard_transfer.clone(),
             notify_peer_failure_cb: on_replica_failure.clone(),
             init_time: start_time.elapsed(),
             is_initialized: Arc::new(Default::default()),
             updates_lock: RwLock::new(()),
+            search_limiter: parking_lot::RwLock::new(search_limiter),
             search_runtime: search_runtime.unwrap_or_else(Handle::current),
             update_runtime: update_runtime.unwrap_or_else(Handle::current),
         })
@@ -207,6 +293,163 @@ impl Collection {
         true
     }
 
+    /// Check if the collection storage of `stored` version can be converted to `app` version
+    /// with [`Collection::migrate_storage`].
+    /// Collection config, shard configs and WAL keep their format within the same minor version,
+    /// so only segments need to be converted, which is supported for any patch difference.
+    ///
+    /// Example:
+    ///   0.4.0 -> 0.4.2 = true
+    ///   0.4.2 -> 0.5.0 = false
+    ///   0.4.2 -> 1.4.2 = false
+    pub fn can_migrate_storage(stored: &Version, app: &Version) -> bool {
+        stored.major == app.major && stored.minor == app.minor && stored <= app
+    }
+
+    /// Upgrade on-disk format of the collection at `path` to the current version.
+    /// Unlike [`Collection::load`], allows to upgrade over several patch versions at once,
+    /// so the collection must not be used by a running instance.
+    ///
+    /// Versions, not supported by [`Collection::can_migrate_storage`], are refused.
+    /// Whole collection is validated before any file is modified, and the collection version
+    /// is only updated once all segments are converted.
+    ///
+    /// This method performs blocking IO.
+    pub fn migrate_storage(path: &Path) -> CollectionResult<()> {
+        let stored_version: Version = CollectionVersion::load(path)?.parse().map_err(|err| {
+            CollectionError::service_error(format!(
+                "Failed to parse stored collection version as semver: {err}"
+            ))
+        })?;
+        let app_version: Version = CollectionVersion::current()
+            .parse()
+            .expect("Failed to parse current collection version as semver");
+
+        if stored_version > app_version {
+            return Err(CollectionError::service_error(format!(
+                "Collection version {stored_version} is newer than application version {app_version}"
+            )));
+        }
+
+        if !Self::can_migrate_storage(&stored_version, &app_version) {
+            return Err(CollectionError::service_error(format!(
+                "Migration of collection from {stored_version} to {app_version} is not supported, \
+                upgrade through intermediate versions of Qdrant first"
+            )));
+        }
+
+        // Make sure that nothing is modified if any part of the collection can't be read
+        CollectionConfig::load(path).map_err(|err| {
+            CollectionError::service_error(format!("Can't read collection config: {err}"))
+        })?;
+        for shard_entry in std::fs::read_dir(path)? {
+            let shard_path = shard_entry?.path();
+            if shard_path.is_dir() {
+                ShardConfig::load(&shard_path)?;
+            }
+        }
+        let segment_paths = Self::stored_segment_paths(path)?;
+        for segment_path in &segment_paths {
+            check_segment_compatibility(segment_path).map_err(|err| {
+                CollectionError::service_error(format!("Segment {}: {err}", segment_path.display()))
+            })?;
+        }
+
+        if stored_version == app_version {
+            return Ok(());
+        }
+
+        log::info!(
+            "Migrating collection at {} from {stored_version} to {app_version}",
+            path.display()
+        );
+
+        for segment_path in &segment_paths {
+            migrate_segment(segment_path)?;
+        }
+
+        CollectionVersion::save(path)?;
+        Ok(())
+    }
+
+    /// Check that the collection at `path` can be loaded by the current version,
+    /// without modifying it.
+    ///
+    /// Returns descriptions of all found incompatibilities.
+    /// This method performs blocking IO.
+    pub fn check_storage_compatibility(path: &Path) -> Vec<String> {
+        let mut problems = Vec::new();
+        let app_version: Version = CollectionVersion::current()
+            .parse()
+            .expect("Failed to parse current collection version as semver");
+
+        match CollectionVersion::load(path).map(|version| version.parse::<Version>()) {
+            Ok(Ok(stored_version)) if stored_version > app_version => problems.push(format!(
+                "Collection version {stored_version} is newer than application version {app_version}"
+            )),
+            Ok(Ok(stored_version))
+                if stored_version != app_version
+                    && !Self::can_upgrade_storage(&stored_version, &app_version) =>
+            {
+                if Self::can_migrate_storage(&stored_version, &app_version) {
+                    problems.push(format!(
+                        "Collection version {stored_version} can't be upgraded to {app_version} on load, \
+                        use `qdrant migrate` to upgrade the storage"
+                    ))
+                } else {
+                    problems.push(format!(
+                        "Collection version {stored_version} can't be upgraded to {app_version}, \
+                        upgrade through intermediate versions of Qdrant first"
+                    ))
+                }
+            }
+            Ok(Ok(_)) => {}
+            Ok(Err(err)) => problems.push(format!(
+                "Failed to parse stored collection version as semver: {err}"
+            )),
+            Err(err) => problems.push(format!("Can't read collection version: {err}")),
+        }
+
+        if let Err(err) = CollectionConfig::load(path) {
+            problems.push(format!("Can't read collection config: {err}"));
+        }
+
+        match Self::stored_segment_paths(path) {
+            Ok(segment_paths) => {
+                for segment_path in segment_paths {
+                    if let Err(err) = check_segment_compatibility(&segment_path) {
+                        problems.push(format!("Segment {}: {err}", segment_path.display()));
+                    }
+                }
+            }
+            Err(err) => problems.push(format!("Can't list segments: {err}")),
+        }
+
+        problems
+    }
+
+    /// Paths of all segments, stored in the collection directory
+    fn stored_segment_paths(path: &Path) -> CollectionResult<Vec<PathBuf>> {
+        let mut segment_paths = Vec::new();
+        // Every shard version directory with local data has segments inside
+        for shard_entry in std::fs::read_dir(path)? {
+            let segments_path = LocalShard::segments_path(&shard_entry?.path());
+            if !segments_path.is_dir() {
+                continue;
+            }
+            for segment_entry in std::fs::read_dir(&segments_path)? {
+                let segment_path = segment_entry?.path();
+                if segment_path.is_dir()
+                    && !segment_path.ends_with("deleted")
+                    && is_segment_committed(&segment_path)
+                {
+                    segment_paths.push(segment_path);
+                }
+            }
+        }
+        Ok(segment_paths)
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub async fn load(
         collection_id: CollectionId,
@@ -274,6 +517,10 @@ impl Collection {
 
         let locked_shard_holder = Arc::new(LockedShardHolder::new(shard_holder));
 
+        let search_limiter = new_search_limiter(collection_config.params.max_concurrent_searches);
+        let config_history = SaveOnDisk::load_or_init(path.join(CONFIG_HISTORY_FILE))
+            .expect("Can't read collection config history");
+
         Self {
             id: collection_id.clone(),
             shards_holder: locked_shard_holder,
@@ -285,11 +532,19 @@ impl Collection {
             snapshots_path: snapshots_path.to_owned(),
             channel_service,
             transfer_tasks: Mutex::new(TransferTasksPool::new(collection_id.clone())),
+            backfill_jobs: Default::default(),
+            index_rebuilds: Default::default(),
+            search_recall: Default::default(),
+            point_access: Default::default(),
+            orphaned_files: Default::default(),
+            dead_replica_recovery: Default::default(),
+            config_history,
             request_shard_transfer_cb: request_shard_transfer.clone(),
             notify_peer_failure_cb: on_replica_failure,
             init_time: start_time.elapsed(),
             is_initialized: Arc::new(Default::default()),
             updates_lock: RwLock::new(()),
+            search_limiter: parking_lot::RwLock::new(search_limiter),
             search_runtime: search_runtime.unwrap_or_else(Handle::current),
             update_runtime: update_runtime.unwrap_or_else(Handle::current),
         }
@@ -401,23 +656,12 @@ impl Collection {
             }
         }
 
-        // Try to request shard transfer if replicas on the current peer are dead
+        // Replicas on the current peer are recovered by `sync_local_state` after the cool-down
         if state == ReplicaState::Dead && self.this_peer_id == peer_id {
-            let transfer_from = replica_set
-                .peers()
-                .into_iter()
-                .find(|(_, state)| state == &ReplicaState::Active)
-                .map(|(peer_id, _)| peer_id);
-            if let Some(transfer_from) = transfer_from {
-                self.request_shard_transfer(ShardTransfer {
-                    shard_id,
-                    from: transfer_from,
-                    to: self.this_peer_id,
-                    sync: true,
-                })
-            } else {
-                log::warn!("No alive replicas to recover shard {shard_id}");
-            }
+            let cooldown = self.shared_storage_config.dead_replica_recovery.cooldown();
+            self.dead_replica_recovery
+                .lock()
+                .insert(shard_id, Instant::now() + cooldown);
         }
 
         Ok(())
@@ -515,6 +759,10 @@ impl Collection {
         T: Future<Output = ()> + Send + 'static,
         F: Future<Output = ()> + Send + 'static,
     {
+        self.collection_config
+            .read()
+            .await
+            .check_quantized_only_transfer()?;
         let shard_id = shard_transfer.shard_id;
         let do_transfer = {
             let shards_holder = self.shards_holder.read().await;
@@ -728,12 +976,58 @@ impl Collection {
         &self,
         operation: CollectionUpdateOperations,
         wait: bool,
-        ordering: WriteOrdering,
+        ordering: Option<WriteOrdering>,
+    ) -> CollectionResult<UpdateResult> {
+        self.update_from_client_with_shard_key(operation, None, wait, ordering)
+            .await
+    }
+
+    /// Update from client, which expects all points of the operation to be stored in `shard_key`.
+    /// Clients use routing table of the collection to send requests directly to the peers,
+    /// which store the shard. Operation is rejected if the routing table of the client is outdated.
+    /// If `ordering` is not specified, the default ordering of the collection is used.
+    pub async fn update_from_client_with_shard_key(
+        &self,
+        operation: CollectionUpdateOperations,
+        shard_key: Option<ShardId>,
+        wait: bool,
+        ordering: Option<WriteOrdering>,
     ) -> CollectionResult<UpdateResult> {
         operation.validate()?;
+        let ordering = self
+            .collection_config
+            .read()
+            .await
+            .params
+            .resolve_write_ordering(ordering);
+        let operation = match operation {
+            CollectionUpdateOperations::PointOperation(point_operation) => {
+                let collection_params = &self.collection_config.read().await.params;
+                CollectionUpdateOperations::PointOperation(
+                    point_operation
+                        .resolve_vector_names(collection_params)
+                        .project_vectors(collection_params)?
+                        .compute_content_hashes(collection_params),
+                )
+            }
+            operation => operation,
+        };
+        self.update_shards(operation, shard_key, wait, ordering)
+            .await
+    }
+
+    /// Send the operation to the shards. Vectors of the operation are expected to be in the
+    /// form stored in the collection, i.e. with resolved names and projected.
+    async fn update_shards(
+        &self,
+        operation: CollectionUpdateOperations,
+        shard_key: Option<ShardId>,
+        wait: bool,
+        ordering: WriteOrdering,
+    ) -> CollectionResult<UpdateResult> {
         let _update_lock = self.updates_lock.read().await;
 
-        let mut results = {
+        let results = {
             let shards_holder = self.shards_holder.read().await;
             let shard_to_op = shards_holder.split_by_shard(operation);
 
@@ -743,6 +1037,17 @@ impl Collection {
                 ));
             }
 
+            if let Some(shard_key) = shard_key {
+                if shard_to_op
+                    .iter()
+                    .any(|(replica_set, _)| replica_set.shard_id != shard_key)
+                {
+                    return Err(CollectionError::bad_request(format!(
+                        "Operation affects points outside of shard {shard_key}, routing table is outdated"
+                    )));
+                }
+            }
+
             let shard_requests = shard_to_op
                 .into_iter()
                 .map(move |(replica_set, operation)| {
@@ -780,8 +1085,41 @@ impl Collection {
                 first_err
             }
         } else {
+            let mut results: Vec<_> = results.into_iter().collect::<CollectionResult<_>>()?;
+            // report the replication of all shards
+            let replication = results
+                .iter_mut()
+                .filter_map(|result| result.replication.take())
+                .flatten()
+                .collect();
+            // reads observe the update only if every shard provides a token
+            let consistency_token =
+                results
+                    .iter_mut()
+                    .try_fold(ConsistencyToken::default(), |mut token, result| {
+                        token.merge(result.consistency_token.take()?);
+                        Some(token)
+                    });
             // At least one result is always present.
-            results.pop().unwrap()
+            let mut result = results.pop().unwrap();
+            result.replication = Some(replication);
+            result.consistency_token = consistency_token;
+            Ok(result)
+        }
+    }
+
+    /// Wait until the search is allowed by the `max_concurrent_searches` limit of the collection.
+    /// The permit should be held for the whole duration of the search.
+    /// Returns `None` if the collection has no limit.
+    pub async fn acquire_search_permit(&self) -> CollectionResult<Option<OwnedSemaphorePermit>> {
+        let search_limiter = self.search_limiter.read().clone();
+        match search_limiter {
+            None => Ok(None),
+            Some(search_limiter) => search_limiter
+                .acquire_owned()
+                .await
+                .map(Some)
+                .map_err(|_| CollectionError::service_error("Search limiter is closed".into())),
         }
     }
 
@@ -790,6 +1128,195 @@ impl Collection {
         request: SearchRequestBatch,
         read_consistency: Option<ReadConsistency>,
         shard_selection: Option<ShardId>,
+    ) -> CollectionResult<Vec<Vec<ScoredPoint>>> {
+        self.search_batch_in_shards(request, read_consistency, shard_selection, None)
+            .await
+    }
+
+    /// Search of a client, which expects results only from the points stored in `shard_key`
+    pub async fn search_batch_with_shard_key(
+        &self,
+        request: SearchRequestBatch,
+        read_consistency: Option<ReadConsistency>,
+        shard_key: Option<ShardId>,
+    ) -> CollectionResult<Vec<Vec<ScoredPoint>>> {
+        self.search_batch_in_shards(request, read_consistency, None, shard_key)
+            .await
+    }
+
+    /// Search in the shards, selected either by another peer or by the client.
+    ///
+    /// * `shard_selection` - local shard, selected by the peer, which coordinates the request
+    /// * `shard_key` - shard, selected by the client
+    async fn search_batch_in_shards(
+        &self,
+        request: SearchRequestBatch,
+        read_consistency: Option<ReadConsistency>,
+        shard_selection: Option<ShardId>,
+        shard_key: Option<ShardId>,
+    ) -> CollectionResult<Vec<Vec<ScoredPoint>>> {
+        // Requests from other peers carry query vectors, which are already projected
+        let request = match shard_selection {
+            None => self.project_search_vectors(request).await?,
+            Some(_) => request,
+        };
+        if shard_selection.is_none() {
+            self.check_quantized_only_searches(&request).await?;
+        }
+
+        // Recall is measured once per client request, peers only serve the exact search
+        let sample_rate = self.shared_storage_config.search_recall_sample_rate;
+        let results = if shard_selection.is_none()
+            && sample_rate > 0.0
+            && rand::random::<f64>() < sample_rate
+        {
+            self.search_batch_with_recall(request, read_consistency, shard_key)
+                .await?
+        } else {
+            self.search_batch_by_stored_vectors(
+                request,
+                read_consistency,
+                shard_selection,
+                shard_key,
+            )
+            .await?
+        };
+
+        if shard_selection.is_none() && self.sample_point_access() {
+            let mut point_access = self.point_access.lock();
+            for result in &results {
+                point_access.add_hits(result.iter().map(|point| point.id));
+            }
+        }
+
+        Ok(results)
+    }
+
+    /// Read consistency of the request: the requested one, or the default of the collection.
+    /// Requests of other peers never specify it, so they are served by the local replica,
+    /// the default is only applied to requests of clients.
+    async fn resolve_read_consistency(
+        &self,
+        read_consistency: Option<ReadConsistency>,
+        shard_selection: Option<ShardId>,
+    ) -> Option<ReadConsistency> {
+        match shard_selection {
+            Some(_) => read_consistency,
+            None => read_consistency.or(self
+                .collection_config
+                .read()
+                .await
+                .params
+                .default_read_consistency),
+        }
+    }
+
+    /// Whether the client request should be accounted in point access counters
+    fn sample_point_access(&self) -> bool {
+        let sample_rate = self.shared_storage_config.point_access_sample_rate;
+        sample_rate > 0.0 && rand::random::<f64>() < sample_rate
+    }
+
+    /// Most frequently accessed points, according to the sampled retrieve and search requests
+    pub fn hot_points(&self, request: HotPointsRequest) -> HotPointsReport {
+        self.point_access.lock().report(
+            self.shared_storage_config.point_access_sample_rate,
+            request.limit(),
+        )
+    }
+
+    /// Run the search along with its exact counterpart and record recall of approximate searches
+    async fn search_batch_with_recall(
+        &self,
+        request: SearchRequestBatch,
+        read_consistency: Option<ReadConsistency>,
+        shard_key: Option<ShardId>,
+    ) -> CollectionResult<Vec<Vec<ScoredPoint>>> {
+        let approximate: Vec<_> = {
+            let config = self.collection_config.read().await;
+            request
+                .searches
+                .iter()
+                .map(|search| {
+                    let exact = search.params.map(|params| params.exact).unwrap_or(false);
+                    // There is no exact counterpart for quantized-only vectors
+                    let vector_name = config.params.resolve_vector_name(search.vector.get_name());
+                    !exact && !config.is_quantized_only(&vector_name)
+                })
+                .collect()
+        };
+        let exact_request = SearchRequestBatch {
+            searches: request
+                .searches
+                .iter()
+                .zip(&approximate)
+                .filter(|(_, is_approximate)| **is_approximate)
+                .map(|(search, _)| {
+                    let mut exact_search = search.clone();
+                    exact_search.with_payload = None;
+                    exact_search.with_vector = None;
+                    exact_search.with_highlights = None;
+                    exact_search.with_geo_distance = None;
+                    exact_search.params = Some(SearchParams {
+                        hnsw_ef: None,
+                        exact: true,
+                        quantization: Some(QuantizationSearchParams {
+                            ignore: true,
+                            rescore: false,
+                            rescore_factor: None,
+                        }),
+                        planner_hint: None,
+                    });
+                    exact_search
+                })
+                .collect(),
+        };
+
+        if exact_request.searches.is_empty() {
+            return self
+                .search_batch_by_stored_vectors(request, read_consistency, None, shard_key)
+                .await;
+        }
+
+        let (results, exact_results) = tokio::join!(
+            self.search_batch_by_stored_vectors(request, read_consistency, None, shard_key),
+            self.search_batch_by_stored_vectors(exact_request, read_consistency, None, shard_key),
+        );
+        let results = results?;
+
+        match exact_results {
+            Ok(exact_results) => {
+                let approximate_results = results
+                    .iter()
+                    .zip(approximate)
+                    .filter(|(_, is_approximate)| *is_approximate)
+                    .map(|(result, _)| result);
+                let mut search_recall = self.search_recall.lock();
+                for (result, exact_result) in approximate_results.zip(exact_results) {
+                    if exact_result.is_empty() {
+                        continue;
+                    }
+                    let found: HashSet<_> = result.iter().map(|point| point.id).collect();
+                    let matched = exact_result
+                        .iter()
+                        .filter(|point| found.contains(&point.id))
+                        .count();
+                    search_recall.add(matched as f64 / exact_result.len() as f64);
+                }
+            }
+            Err(err) => log::warn!("Failed to run exact search for recall estimation: {err}"),
+        }
+
+        Ok(results)
+    }
+
+    /// Search with query vectors in the space of stored vectors, i.e. already projected
+    pub(crate) async fn search_batch_by_stored_vectors(
+        &self,
+        request: SearchRequestBatch,
+        read_consistency: Option<ReadConsistency>,
+        shard_selection: Option<ShardId>,
+        shard_key: Option<ShardId>,
     ) -> CollectionResult<Vec<Vec<ScoredPoint>>> {
         // shortcuts batch if all requests with limit=0
         if request.searches.iter().all(|s| s.limit == 0) {
@@ -841,7 +1368,12 @@ impl Collection {
                 searches: without_payload_requests,
             };
             let without_payload_results = self
-                ._search_batch(without_payload_batch, read_consistency, shard_selection)
+                ._search_batch(
+                    without_payload_batch,
+                    read_consistency,
+                    shard_selection,
+                    shard_key,
+                )
                 .await?;
             let filled_results = without_payload_results
                 .into_iter()
@@ -858,7 +1390,7 @@ impl Collection {
             try_join_all(filled_results).await
         } else {
             let result = self
-                ._search_batch(request, read_consistency, shard_selection)
+                ._search_batch(request, read_consistency, shard_selection, shard_key)
                 .await?;
             Ok(result)
         }
@@ -869,14 +1401,18 @@ impl Collection {
         request: SearchRequestBatch,
         read_consistency: Option<ReadConsistency>,
         shard_selection: Option<ShardId>,
+        shard_key: Option<ShardId>,
     ) -> CollectionResult<Vec<Vec<ScoredPoint>>> {
         let batch_size = request.searches.len();
-        let request = Arc::new(request);
+        let request = Arc::new(self.resolve_search_vector_names(request).await);
+        let read_consistency = self
+            .resolve_read_consistency(read_consistency, shard_selection)
+            .await;
 
         // query all shards concurrently
         let mut all_searches_res = {
             let shard_holder = self.shards_holder.read().await;
-            let target_shards = shard_holder.target_shard(shard_selection)?;
+            let target_shards = shard_holder.target_shard(shard_selection.or(shard_key))?;
             let all_searches = target_shards
                 .iter()
                 .map(|shard| shard.search(request.clone(), read_consistency, &self.search_runtime));
@@ -891,7 +1427,7 @@ impl Collection {
             }
         }
         let collection_params = self.collection_config.read().await.params.clone();
-        let top_results: Vec<_> = merged_results
+        let mut top_results: Vec<_> = merged_results
             .into_iter()
             .zip(request.searches.iter())
             .map(|(res, request)| {
@@ -920,9 +1456,68 @@ impl Collection {
             })
             .collect::<CollectionResult<Vec<_>>>()?;
 
+        // Highlights and distances are made once for the client request,
+        // when results of all shards are merged
+        if shard_selection.is_none() {
+            add_highlights(self, &request.searches, &mut top_results, read_consistency).await?;
+            add_geo_distances(self, &request.searches, &mut top_results, read_consistency).await?;
+        }
+
         Ok(top_results)
     }
 
+    /// Replace vector aliases and default vector names in search requests with real names
+    async fn resolve_search_vector_names(&self, request: SearchRequestBatch) -> SearchRequestBatch {
+        let collection_params = &self.collection_config.read().await.params;
+        let searches = request
+            .searches
+            .into_iter()
+            .map(|mut search| {
+                let mut vector = NamedVector::from(search.vector);
+                vector.name = collection_params.resolve_vector_name(&vector.name);
+                search.vector = vector.into();
+                search.with_vector = search
+                    .with_vector
+                    .map(|with_vector| collection_params.resolve_with_vector(with_vector));
+                search
+            })
+            .collect();
+        SearchRequestBatch { searches }
+    }
+
+    /// Reject searches, which require original values of quantized-only vectors
+    async fn check_quantized_only_searches(
+        &self,
+        request: &SearchRequestBatch,
+    ) -> CollectionResult<()> {
+        let config = self.collection_config.read().await;
+        for search in &request.searches {
+            let vector_name = config.params.resolve_vector_name(search.vector.get_name());
+            config.check_quantized_only_search(&vector_name, search.params.as_ref())?;
+        }
+        Ok(())
+    }
+
+    /// Apply configured projections to query vectors
+    async fn project_search_vectors(
+        &self,
+        request: SearchRequestBatch,
+    ) -> CollectionResult<SearchRequestBatch> {
+        let collection_params = &self.collection_config.read().await.params;
+        let searches = request
+            .searches
+            .into_iter()
+            .map(|mut search| {
+                let mut vector = NamedVector::from(search.vector);
+                let vector_name = collection_params.resolve_vector_name(&vector.name);
+                vector.vector = collection_params.project_vector(&vector_name, vector.vector)?;
+                search.vector = vector.into();
+                Ok(search)
+            })
+            .collect::<CollectionResult<_>>()?;
+        Ok(SearchRequestBatch { searches })
+    }
+
     async fn fill_search_result_with_payload(
         &self,
         search_result: Vec<ScoredPoint>,
@@ -949,13 +1544,18 @@ impl Collection {
                 // Points might get deleted between search and retrieve.
                 // But it's not a problem, because we don't want to return deleted points.
                 // So we just filter out them.
-                records_map.remove(&scored_point.id).map(|record| {
-                    scored_point.payload = record.payload;
-                    scored_point.vector = record.vector;
-                    scored_point
-                })
+                records_map
+                    .remove(&scored_point.id)
+                    .map(|record| -> CollectionResult<_> {
+                        scored_point.payload = record
+                            .payload
+                            .map(RecordPayload::into_payload)
+                            .transpose()?;
+                        scored_point.vector = record.vector;
+                        Ok(scored_point)
+                    })
             })
-            .collect();
+            .collect::<CollectionResult<_>>()?;
         Ok(enriched_result)
     }
 
@@ -964,6 +1564,28 @@ impl Collection {
         request: SearchRequest,
         read_consistency: Option<ReadConsistency>,
         shard_selection: Option<ShardId>,
+    ) -> CollectionResult<Vec<ScoredPoint>> {
+        self.search_in_shards(request, read_consistency, shard_selection, None)
+            .await
+    }
+
+    /// Search of a client, which expects results only from the points stored in `shard_key`
+    pub async fn search_with_shard_key(
+        &self,
+        request: SearchRequest,
+        read_consistency: Option<ReadConsistency>,
+        shard_key: Option<ShardId>,
+    ) -> CollectionResult<Vec<ScoredPoint>> {
+        self.search_in_shards(request, read_consistency, None, shard_key)
+            .await
+    }
+
+    async fn search_in_shards(
+        &self,
+        request: SearchRequest,
+        read_consistency: Option<ReadConsistency>,
+        shard_selection: Option<ShardId>,
+        shard_key: Option<ShardId>,
     ) -> CollectionResult<Vec<ScoredPoint>> {
         if request.limit == 0 {
             return Ok(vec![]);
@@ -972,8 +1594,16 @@ impl Collection {
         let request_batch = SearchRequestBatch {
             searches: vec![request],
         };
+        let request_batch = match shard_selection {
+            None => {
+                let request_batch = self.project_search_vectors(request_batch).await?;
+                self.check_quantized_only_searches(&request_batch).await?;
+                request_batch
+            }
+            Some(_) => request_batch,
+        };
         let results = self
-            ._search_batch(request_batch, read_consistency, shard_selection)
+            ._search_batch(request_batch, read_consistency, shard_selection, shard_key)
             .await?;
         Ok(results.into_iter().next().unwrap())
     }
@@ -983,6 +1613,28 @@ impl Collection {
         request: ScrollRequest,
         read_consistency: Option<ReadConsistency>,
         shard_selection: Option<ShardId>,
+    ) -> CollectionResult<ScrollResult> {
+        self.scroll_in_shards(request, read_consistency, shard_selection, None)
+            .await
+    }
+
+    /// Scroll of a client, which expects only the points stored in `shard_key`
+    pub async fn scroll_by_with_shard_key(
+        &self,
+        request: ScrollRequest,
+        read_consistency: Option<ReadConsistency>,
+        shard_key: Option<ShardId>,
+    ) -> CollectionResult<ScrollResult> {
+        self.scroll_in_shards(request, read_consistency, None, shard_key)
+            .await
+    }
+
+    async fn scroll_in_shards(
+        &self,
+        request: ScrollRequest,
+        read_consistency: Option<ReadConsistency>,
+        shard_selection: Option<ShardId>,
+        shard_key: Option<ShardId>,
     ) -> CollectionResult<ScrollResult> {
         let default_request = ScrollRequest::default();
 
@@ -994,7 +1646,12 @@ impl Collection {
             .with_payload
             .clone()
             .unwrap_or_else(|| default_request.with_payload.clone().unwrap());
-        let with_vector = request.with_vector;
+        let with_vector = self
+            .collection_config
+            .read()
+            .await
+            .params
+            .resolve_with_vector(request.with_vector);
 
         if limit == 0 {
             return Err(CollectionError::BadRequest {
@@ -1002,24 +1659,124 @@ impl Collection {
             });
         }
 
-        // Needed to return next page offset.
-        let limit = limit + 1;
-        let retrieved_points: Vec<_> = {
-            let shards_holder = self.shards_holder.read().await;
-            let target_shards = shards_holder.target_shard(shard_selection)?;
-            let scroll_futures = target_shards.into_iter().map(|shard| {
-                shard.scroll_by(
-                    offset,
-                    limit,
-                    &with_payload_interface,
-                    &with_vector,
-                    request.filter.as_ref(),
-                    read_consistency,
-                )
-            });
-
+        if let Some(order_by) = &request.order_by {
+            if request.order != ScrollOrder::Id {
+                return Err(CollectionError::BadRequest {
+                    description: "Order by payload field can only be used with the default order"
+                        .to_string(),
+                });
+            }
+            if offset.is_some() && order_by.start_from.is_none() {
+                return Err(CollectionError::BadRequest {
+                    description: "Offset requires `start_from` value of the order by payload field"
+                        .to_string(),
+                });
+            }
+            return scroll_by_order(
+                self,
+                order_by,
+                offset,
+                limit,
+                request.filter.as_ref(),
+                with_payload_interface,
+                with_vector,
+                read_consistency,
+                shard_selection,
+                shard_key,
+            )
+            .await;
+        }
+
+        if request.order == ScrollOrder::GeoDistance {
+            if offset.is_some() {
+                return Err(CollectionError::BadRequest {
+                    description: "Offset is not supported for geo distance order".to_string(),
+                });
+            }
+            let geo_order =
+                request
+                    .geo_order
+                    .as_ref()
+                    .ok_or_else(|| CollectionError::BadRequest {
+                        description: "Geo order is required for geo distance order".to_string(),
+                    })?;
+            let points = scroll_by_geo_distance(
+                self,
+                geo_order,
+                limit,
+                request.filter.as_ref(),
+                with_payload_interface,
+                with_vector,
+                read_consistency,
+                shard_selection,
+                shard_key,
+            )
+            .await?;
+
+            // Points ordered by distance are not paginated
+            return Ok(ScrollResult {
+                points,
+                next_page_offset: None,
+                next_page_order_value: None,
+            });
+        }
+
+        // All shards must use the same seed, so the sample could be merged
+        let sample_seed = match request.order {
+            ScrollOrder::Id | ScrollOrder::GeoDistance => None,
+            ScrollOrder::Random => Some(request.seed.unwrap_or_else(rand::random)),
+        };
+
+        if sample_seed.is_some() && offset.is_some() {
+            return Err(CollectionError::BadRequest {
+                description: "Offset is not supported for random order".to_string(),
+            });
+        }
+
+        let read_consistency = self
+            .resolve_read_consistency(read_consistency, shard_selection)
+            .await;
+        // Needed to return next page offset.
+        let limit = if sample_seed.is_some() {
+            limit
+        } else {
+            limit + 1
+        };
+        let retrieved_points: Vec<_> = {
+            let shards_holder = self.shards_holder.read().await;
+            let target_shards = shards_holder.target_shard(shard_selection.or(shard_key))?;
+            let scroll_futures = target_shards.into_iter().map(|shard| {
+                shard.scroll_by(
+                    offset,
+                    limit,
+                    &with_payload_interface,
+                    &with_vector,
+                    request.filter.as_ref(),
+                    sample_seed,
+                    request.planner_hint,
+                    read_consistency,
+                )
+            });
+
             try_join_all(scroll_futures).await?
         };
+
+        if let Some(seed) = sample_seed {
+            let points = retrieved_points
+                .into_iter()
+                .flatten()
+                .sorted_by_key(|point| (sampling_key(seed, point.id), point.id))
+                .take(limit)
+                .collect();
+
+            // Random sample is not paginated
+            return Ok(ScrollResult {
+                points,
+                next_page_offset: None,
+                next_page_order_value: None,
+            });
+        }
+
         let mut points: Vec<_> = retrieved_points
             .into_iter()
             .flatten()
@@ -1037,6 +1794,7 @@ impl Collection {
         Ok(ScrollResult {
             points,
             next_page_offset,
+            next_page_order_value: None,
         })
     }
 
@@ -1044,12 +1802,30 @@ impl Collection {
         &self,
         request: CountRequest,
         shard_selection: Option<ShardId>,
+    ) -> CollectionResult<CountResult> {
+        self.count_in_shards(request, shard_selection, None).await
+    }
+
+    /// Count of a client, which expects only the points stored in `shard_key`
+    pub async fn count_with_shard_key(
+        &self,
+        request: CountRequest,
+        shard_key: Option<ShardId>,
+    ) -> CollectionResult<CountResult> {
+        self.count_in_shards(request, None, shard_key).await
+    }
+
+    async fn count_in_shards(
+        &self,
+        request: CountRequest,
+        shard_selection: Option<ShardId>,
+        shard_key: Option<ShardId>,
     ) -> CollectionResult<CountResult> {
         let request = Arc::new(request);
 
-        let counts: Vec<_> = {
+        let counts: Vec<CountResult> = {
             let shards_holder = self.shards_holder.read().await;
-            let target_shards = shards_holder.target_shard(shard_selection)?;
+            let target_shards = shards_holder.target_shard(shard_selection.or(shard_key))?;
             let count_futures = target_shards
                 .into_iter()
                 .map(|shard| shard.count(request.clone()));
@@ -1057,7 +1833,18 @@ impl Collection {
         };
 
         let total_count = counts.iter().map(|x| x.count).sum::<usize>();
-        let aggregated_count = CountResult { count: total_count };
+        let distinct_values = counts.into_iter().filter_map(|x| x.distinct_values).reduce(
+            |mut distinct_values, shard_values| {
+                distinct_values.merge(shard_values);
+                distinct_values
+            },
+        );
+        let aggregated_count = CountResult {
+            count: total_count,
+            distinct: distinct_values.as_ref().map(DistinctValues::count),
+            // Values are only needed to merge them with other shards
+            distinct_values: distinct_values.filter(|_| shard_selection.is_some()),
+        };
         Ok(aggregated_count)
     }
 
@@ -1066,16 +1853,47 @@ impl Collection {
         request: PointRequest,
         read_consistency: Option<ReadConsistency>,
         shard_selection: Option<ShardId>,
+    ) -> CollectionResult<Vec<Record>> {
+        self.retrieve_in_shards(request, read_consistency, shard_selection, None)
+            .await
+    }
+
+    /// Retrieve of a client, which expects only the points stored in `shard_key`
+    pub async fn retrieve_with_shard_key(
+        &self,
+        request: PointRequest,
+        read_consistency: Option<ReadConsistency>,
+        shard_key: Option<ShardId>,
+    ) -> CollectionResult<Vec<Record>> {
+        self.retrieve_in_shards(request, read_consistency, None, shard_key)
+            .await
+    }
+
+    async fn retrieve_in_shards(
+        &self,
+        mut request: PointRequest,
+        read_consistency: Option<ReadConsistency>,
+        shard_selection: Option<ShardId>,
+        shard_key: Option<ShardId>,
     ) -> CollectionResult<Vec<Record>> {
         let with_payload_interface = request
             .with_payload
             .as_ref()
             .unwrap_or(&WithPayloadInterface::Bool(false));
         let with_payload = WithPayload::from(with_payload_interface);
+        request.with_vector = self
+            .collection_config
+            .read()
+            .await
+            .params
+            .resolve_with_vector(request.with_vector);
+        let read_consistency = self
+            .resolve_read_consistency(read_consistency, shard_selection)
+            .await;
         let request = Arc::new(request);
         let all_shard_collection_results = {
             let shard_holder = self.shards_holder.read().await;
-            let target_shards = shard_holder.target_shard(shard_selection)?;
+            let target_shards = shard_holder.target_shard(shard_selection.or(shard_key))?;
             let retrieve_futures = target_shards.into_iter().map(|shard| {
                 shard.retrieve(
                     request.clone(),
@@ -1086,22 +1904,265 @@ impl Collection {
             });
             try_join_all(retrieve_futures).await?
         };
-        let points = all_shard_collection_results.into_iter().flatten().collect();
+        let points: Vec<Record> = all_shard_collection_results.into_iter().flatten().collect();
+
+        if shard_selection.is_none() && self.sample_point_access() {
+            self.point_access
+                .lock()
+                .add_reads(points.iter().map(|point| point.id));
+        }
+
         Ok(points)
     }
 
     pub async fn update_params_from_diff(
         &self,
         params_diff: CollectionParamsDiff,
+    ) -> CollectionResult<()> {
+        let defrag_key_changed = {
+            let mut config = self.collection_config.write().await;
+            let new_params = params_diff.update(&config.params)?;
+            new_params.check_vector_aliases()?;
+            let defrag_key_changed = new_params.defrag_key != config.params.defrag_key;
+            let new_config = CollectionConfig {
+                params: new_params,
+                ..config.clone()
+            };
+            new_config.check_quantized_only_replication()?;
+            if new_config.params.max_concurrent_searches != config.params.max_concurrent_searches {
+                *self.search_limiter.write() =
+                    new_search_limiter(new_config.params.max_concurrent_searches);
+            }
+            *config = new_config;
+            defrag_key_changed
+        };
+        if defrag_key_changed {
+            // Optimizers keep their own copy of collection params
+            let shard_holder = self.shards_holder.read().await;
+            for replica_set in shard_holder.all_shards() {
+                replica_set.on_optimizer_config_update().await?;
+            }
+        }
+        self.collection_config.read().await.save(&self.path)?;
+        Ok(())
+    }
+
+    /// Adds new named vectors to the collection:
+    /// - Saves new params on disk
+    /// - Creates a new appendable segment with all vectors in each local shard
+    /// - Restarts optimizers with new params
+    ///
+    /// Existing segments are not changed, so points stored before don't have the new vectors
+    /// until they are upserted with all vectors of the collection.
+    pub async fn add_vectors(
+        &self,
+        vectors: BTreeMap<String, VectorParams>,
     ) -> CollectionResult<()> {
         {
             let mut config = self.collection_config.write().await;
-            config.params = params_diff.update(&config.params)?;
+            let mut new_params = config.params.clone();
+            let mut added_vectors = 0;
+            match &mut new_params.vectors {
+                VectorsConfig::Single(_) => {
+                    return Err(CollectionError::bad_input(
+                        "Can't add named vectors to a collection with a single unnamed vector"
+                            .to_string(),
+                    ))
+                }
+                VectorsConfig::Multi(vectors_params) => {
+                    for (vector_name, vector_params) in vectors {
+                        vector_params.validate()?;
+                        match vectors_params.get(&vector_name) {
+                            // Already added, e.g. the operation is re-applied
+                            Some(existing_params) if *existing_params == vector_params => {}
+                            Some(_) => {
+                                return Err(CollectionError::bad_input(format!(
+                                    "Vector {vector_name} already exists and can't be changed"
+                                )))
+                            }
+                            None => {
+                                vectors_params.insert(vector_name, vector_params);
+                                added_vectors += 1;
+                            }
+                        }
+                    }
+                }
+            }
+            if added_vectors == 0 {
+                return Ok(());
+            }
+            new_params.check_vector_aliases()?;
+            let new_config = CollectionConfig {
+                params: new_params,
+                ..config.clone()
+            };
+            new_config.check_quantized_only_replication()?;
+            *config = new_config;
+        }
+        {
+            let shard_holder = self.shards_holder.read().await;
+            for replica_set in shard_holder.all_shards() {
+                replica_set.on_vectors_added().await?;
+            }
         }
         self.collection_config.read().await.save(&self.path)?;
         Ok(())
     }
 
+    /// Starts a job, which tracks filling of the vector for points stored before it was added
+    pub async fn create_backfill_job(
+        &self,
+        request: CreateBackfillJob,
+    ) -> CollectionResult<BackfillJobInfo> {
+        let vector_name = {
+            let params = &self.collection_config.read().await.params;
+            let vector_name = params.resolve_vector_name(&request.vector);
+            params.get_vector_params(&vector_name)?;
+            vector_name
+        };
+        let shard_ids = self
+            .shards_holder
+            .read()
+            .await
+            .get_shards()
+            .map(|(shard_id, _)| *shard_id)
+            .sorted()
+            .collect_vec();
+        Ok(self
+            .backfill_jobs
+            .lock()
+            .await
+            .create(vector_name, shard_ids))
+    }
+
+    pub async fn backfill_job_info(
+        &self,
+        job_id: BackfillJobId,
+    ) -> CollectionResult<BackfillJobInfo> {
+        self.backfill_jobs.lock().await.get(job_id).cloned()
+    }
+
+    /// Returns next points, which don't have the vector of the backfill job.
+    ///
+    /// Shards are scanned one by one, the scan position of each shard is stored in the job.
+    pub async fn backfill_points(
+        &self,
+        job_id: BackfillJobId,
+        request: BackfillPointsRequest,
+    ) -> CollectionResult<BackfillPointsResult> {
+        let limit = request
+            .limit
+            .unwrap_or_else(|| ScrollRequest::default().limit.unwrap());
+
+        // Lock is held for the whole scan, so concurrent requests don't return the same points
+        let mut backfill_jobs = self.backfill_jobs.lock().await;
+        let job = backfill_jobs.get_mut(job_id)?;
+
+        let mut points = Vec::new();
+        for shard in job.shards.iter_mut() {
+            while !shard.scanned && points.len() < limit {
+                let scroll_request = ScrollRequest {
+                    offset: shard.offset,
+                    limit: Some(limit - points.len()),
+                    with_payload: request.with_payload.clone(),
+                    with_vector: true.into(),
+                    ..Default::default()
+                };
+                let result = self
+                    .scroll_by(scroll_request, None, Some(shard.shard_id))
+                    .await?;
+                shard.offset = result.next_page_offset;
+                shard.scanned = result.next_page_offset.is_none();
+
+                let missing_points = result.points.into_iter().filter(|point| {
+                    let vector = point.vector.as_ref();
+                    vector.and_then(|vector| vector.get(&job.vector)).is_none()
+                });
+                let points_before = points.len();
+                points.extend(missing_points);
+                shard.missing += points.len() - points_before;
+            }
+        }
+
+        Ok(BackfillPointsResult {
+            points,
+            completed: job.shards.iter().all(|shard| shard.scanned),
+        })
+    }
+
+    /// Sets the vector of the backfill job for existing points, other vectors and payload are kept
+    pub async fn backfill_update(
+        &self,
+        job_id: BackfillJobId,
+        batch: BackfillBatch,
+        wait: bool,
+        ordering: Option<WriteOrdering>,
+    ) -> CollectionResult<UpdateResult> {
+        batch.validate()?;
+        let vector_name = self.backfill_jobs.lock().await.get(job_id)?.vector.clone();
+        let (vector_params, ordering) = {
+            let collection_params = &self.collection_config.read().await.params;
+            (
+                collection_params.get_vector_params(&vector_name)?,
+                collection_params.resolve_write_ordering(ordering),
+            )
+        };
+
+        let request = PointRequest {
+            ids: batch.points.iter().map(|point| point.id).collect(),
+            with_payload: Some(WithPayloadInterface::Bool(false)),
+            with_vector: false.into(),
+        };
+        let existing_ids: HashSet<_> = self
+            .retrieve(request, None, None)
+            .await?
+            .into_iter()
+            .map(|record| record.id)
+            .collect();
+        if let Some(point) = batch
+            .points
+            .iter()
+            .find(|point| !existing_ids.contains(&point.id))
+        {
+            return Err(CollectionError::PointNotFound {
+                missed_point_id: point.id,
+            });
+        }
+
+        // Only the backfilled vector is sent, other vectors are read by the shard while applying
+        // the update, so concurrent writes to the points are not overwritten
+        let points: Vec<_> = batch
+            .points
+            .into_iter()
+            .map(|point| {
+                let vector = vector_params.project_vector(point.vector)?;
+                Ok(PointVectors {
+                    id: point.id,
+                    vector: VectorStruct::Multi(HashMap::from([(vector_name.clone(), vector)])),
+                })
+            })
+            .collect::<CollectionResult<_>>()?;
+
+        let shard_counts = {
+            let shards_holder = self.shards_holder.read().await;
+            points
+                .iter()
+                .map(|point| shards_holder.point_shard(point.id))
+                .counts()
+        };
+
+        let operation =
+            CollectionUpdateOperations::PointOperation(PointOperations::UpdateVectors { points });
+        let result = self.update_shards(operation, None, wait, ordering).await?;
+
+        if let Ok(job) = self.backfill_jobs.lock().await.get_mut(job_id) {
+            for shard in job.shards.iter_mut() {
+                shard.backfilled += shard_counts.get(&shard.shard_id).copied().unwrap_or(0);
+            }
+        }
+        Ok(result)
+    }
+
     pub fn request_shard_transfer(&self, shard_transfer: ShardTransfer) {
         self.request_shard_transfer_cb.deref()(shard_transfer)
     }
@@ -1176,6 +2237,138 @@ impl Collection {
         Ok(())
     }
 
+    /// Updates WAL params:
+    /// - Saves new params on disk
+    /// - Reopens WAL of each local shard with new params
+    pub async fn update_wal_params_from_diff(
+        &self,
+        wal_config_diff: WalConfigDiff,
+    ) -> CollectionResult<()> {
+        {
+            let mut config = self.collection_config.write().await;
+            config.wal_config = DiffConfig::update(wal_config_diff, &config.wal_config)?;
+        }
+        {
+            let shard_holder = self.shards_holder.read().await;
+            for replica_set in shard_holder.all_shards() {
+                replica_set.on_optimizer_config_update().await?;
+            }
+        }
+        self.collection_config.read().await.save(&self.path)?;
+        Ok(())
+    }
+
+    /// Updates quantization params:
+    /// - Saves new params on disk
+    /// - Runs new optimizers, which rebuild indexed segments without quantized vectors
+    pub async fn update_quantization_config(
+        &self,
+        quantization_config: QuantizationConfig,
+    ) -> CollectionResult<()> {
+        {
+            let mut config = self.collection_config.write().await;
+            let new_config = CollectionConfig {
+                quantization_config: Some(quantization_config),
+                ..config.clone()
+            };
+            new_config.check_quantized_only_replication()?;
+            *config = new_config;
+        }
+        {
+            let shard_holder = self.shards_holder.read().await;
+            for replica_set in shard_holder.all_shards() {
+                replica_set.on_optimizer_config_update().await?;
+            }
+        }
+        self.collection_config.read().await.save(&self.path)?;
+        Ok(())
+    }
+
+    /// Replaces lifecycle rules of the collection, empty list of rules removes them
+    pub async fn update_lifecycle_config(
+        &self,
+        lifecycle_config: LifecycleConfig,
+    ) -> CollectionResult<()> {
+        let mut config = self.collection_config.write().await;
+        config.lifecycle_config = if lifecycle_config.rules.is_empty() {
+            None
+        } else {
+            Some(lifecycle_config)
+        };
+        config.save(&self.path)?;
+        Ok(())
+    }
+
+    pub async fn config(&self) -> CollectionConfig {
+        self.collection_config.read().await.clone()
+    }
+
+    /// Appends the change from `previous` to the current config to the config history.
+    ///
+    /// Nothing is recorded if the config is not changed.
+    pub async fn record_config_change(
+        &self,
+        previous: CollectionConfig,
+        author: Option<String>,
+    ) -> CollectionResult<()> {
+        let diff = config_diff(&previous, &*self.collection_config.read().await);
+        if diff.is_empty() {
+            return Ok(());
+        }
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        self.config_history.write(|history| {
+            let id = history.last().map_or(0, |change| change.id + 1);
+            history.push(ConfigChange {
+                id,
+                timestamp,
+                author,
+                diff,
+                previous,
+            });
+            if history.len() > CONFIG_HISTORY_SIZE {
+                history.drain(..history.len() - CONFIG_HISTORY_SIZE);
+            }
+        })?;
+        Ok(())
+    }
+
+    /// Recent config changes, latest last
+    pub fn config_history(&self) -> Vec<ConfigChange> {
+        self.config_history.read().clone()
+    }
+
+    pub fn config_change(&self, id: u64) -> CollectionResult<ConfigChange> {
+        self.config_history
+            .read()
+            .iter()
+            .find(|change| change.id == id)
+            .cloned()
+            .ok_or_else(|| CollectionError::NotFound {
+                what: format!("Config change {id}"),
+            })
+    }
+
+    /// Time since the collection was created.
+    ///
+    /// Collections, created before the creation time was recorded in the config, fall back to
+    /// the creation time of the collection directory.
+    pub async fn age(&self) -> CollectionResult<Duration> {
+        let created_at = self.collection_config.read().await.created_at;
+        let created_at = match created_at {
+            Some(created_at) => UNIX_EPOCH + Duration::from_secs(created_at),
+            None => {
+                let metadata = std::fs::metadata(&self.path)?;
+                metadata.created().or_else(|_| metadata.modified())?
+            }
+        };
+        Ok(SystemTime::now()
+            .duration_since(created_at)
+            .unwrap_or_default())
+    }
+
     /// Updates shard optimization params:
     /// - Saves new params on disk
     /// - Stops existing optimization loop
@@ -1229,7 +2422,7 @@ impl Collection {
                 for (key, schema) in shard_info.payload_schema {
                     match info.payload_schema.entry(key) {
                         Entry::Occupied(o) => {
-                            o.into_mut().points += schema.points;
+                            o.into_mut().merge(&schema);
                         }
                         Entry::Vacant(v) => {
                             v.insert(schema);
@@ -1240,7 +2433,11 @@ impl Collection {
         Ok(info)
     }
 
-    pub async fn cluster_info(&self, peer_id: PeerId) -> CollectionResult<CollectionClusterInfo> {
+    pub async fn cluster_info(
+        &self,
+        peer_id: PeerId,
+        with_routing: bool,
+    ) -> CollectionResult<CollectionClusterInfo> {
         let shards_holder = self.shards_holder.read().await;
         let shard_count = shards_holder.len();
         let mut local_shards = Vec::new();
@@ -1248,6 +2445,8 @@ impl Collection {
         let count_request = Arc::new(CountRequest {
             filter: None,
             exact: false, // Don't need exact count of unique ids here, only size estimation
+            planner_hint: None,
+            distinct: None,
         });
         // extract shards info
         for (shard_id, replica_set) in shards_holder.get_shards() {
@@ -1284,16 +2483,45 @@ impl Collection {
         local_shards.sort_by_key(|k| k.shard_id);
         remote_shards.sort_by_key(|k| k.shard_id);
 
+        let routing = with_routing.then(|| self.routing_table(&shards_holder));
+
         let info = CollectionClusterInfo {
             peer_id,
             shard_count,
             local_shards,
             remote_shards,
             shard_transfers,
+            routing,
         };
         Ok(info)
     }
 
+    /// Active replicas of each shard with addresses of their peers
+    fn routing_table(&self, shards_holder: &ShardHolder) -> Vec<ShardRoute> {
+        let id_to_address = self.channel_service.id_to_address.read();
+        let mut routing: Vec<_> = shards_holder
+            .get_shards()
+            .map(|(shard_id, replica_set)| {
+                let mut peers: Vec<_> = replica_set
+                    .peers()
+                    .into_iter()
+                    .filter(|(_, state)| *state == ReplicaState::Active)
+                    .map(|(peer_id, _)| PeerRoute {
+                        peer_id,
+                        uri: id_to_address.get(&peer_id).map(|uri| uri.to_string()),
+                    })
+                    .collect();
+                peers.sort_by_key(|peer| peer.peer_id);
+                ShardRoute {
+                    shard_id: *shard_id,
+                    peers,
+                }
+            })
+            .collect();
+        routing.sort_by_key(|route| route.shard_id);
+        routing
+    }
+
     pub async fn before_drop(&mut self) {
         self.shards_holder.write().await.before_drop().await;
         self.before_drop_called = true
@@ -1342,26 +2570,817 @@ impl Collection {
             config: self.collection_config.read().await.clone(),
             shards: shards_telemetry,
             transfers,
+            search_recall: self.search_recall.lock().get_statistics(),
         }
     }
 
-    pub async fn list_snapshots(&self) -> CollectionResult<Vec<SnapshotDescription>> {
-        list_snapshots_in_directory(&self.snapshots_path).await
+    /// Flush WAL and segments of all local shards to disk.
+    /// After this call, all acknowledged operations are persisted in the storage.
+    pub async fn flush(&self) -> CollectionResult<FlushResult> {
+        let mut shards = Vec::new();
+        let shard_holder = self.shards_holder.read().await;
+        for replica_set in shard_holder.all_shards() {
+            shards.extend(replica_set.flush().await?);
+        }
+        Ok(FlushResult { shards })
     }
 
-    pub async fn get_snapshot_path(&self, snapshot_name: &str) -> CollectionResult<PathBuf> {
-        let snapshot_path = self.snapshots_path.join(snapshot_name);
-        if !snapshot_path.exists() {
-            return Err(CollectionError::NotFound {
-                what: format!("Snapshot {snapshot_name}"),
-            });
+    /// Estimate memory, occupied by all local shards of the collection
+    pub async fn memory_usage(&self) -> CollectionResult<MemoryUsage> {
+        let mut usage = MemoryUsage::default();
+        let shard_holder = self.shards_holder.read().await;
+        for replica_set in shard_holder.all_shards() {
+            if let Some(shard_usage) = replica_set.memory_usage().await? {
+                usage += shard_usage;
+            }
         }
-        Ok(snapshot_path)
+        Ok(usage)
     }
 
-    pub async fn create_snapshot(
-        &self,
-        temp_dir: &Path,
+    /// Truncate WAL of all local shards up to operations, persisted in all segments
+    pub async fn truncate_wal(&self) -> CollectionResult<WalTruncateResult> {
+        let mut shards = Vec::new();
+        let shard_holder = self.shards_holder.read().await;
+        for replica_set in shard_holder.all_shards() {
+            shards.extend(replica_set.truncate_wal().await?);
+        }
+        Ok(WalTruncateResult { shards })
+    }
+
+    /// Report fraction of deleted points in segments of local shards and start rebuilding
+    /// segments with the fraction above the threshold to reclaim disk space
+    pub async fn vacuum(&self, request: VacuumRequest) -> CollectionResult<VacuumResult> {
+        let deleted_threshold = match request.deleted_threshold {
+            Some(deleted_threshold) => deleted_threshold,
+            None => {
+                self.collection_config
+                    .read()
+                    .await
+                    .optimizer_config
+                    .deleted_threshold
+            }
+        };
+
+        let mut segments = Vec::new();
+        let mut optimizations_started = 0;
+        let shard_holder = self.shards_holder.read().await;
+        for replica_set in shard_holder.all_shards() {
+            let (shard_segments, shard_optimizations) =
+                replica_set.vacuum(deleted_threshold).await?;
+            segments.extend(shard_segments);
+            optimizations_started += shard_optimizations;
+        }
+
+        Ok(VacuumResult {
+            deleted_threshold,
+            segments,
+            optimizations_started,
+        })
+    }
+
+    /// Remove files in segment directories of local shards, which are not used by any segment
+    /// for longer than the grace period. Shards with running optimizations are skipped.
+    pub async fn remove_orphaned_files(
+        &self,
+        grace_period: Duration,
+    ) -> CollectionResult<OrphanFilesResult> {
+        let shard_holder = self.shards_holder.read().await;
+        let mut orphaned = Vec::new();
+        for replica_set in shard_holder.all_shards() {
+            if let Some(shard_orphaned) = replica_set.orphaned_files().await? {
+                orphaned.extend(shard_orphaned);
+            }
+        }
+
+        let (expired, pending) = self.orphaned_files.lock().update(orphaned, grace_period);
+
+        let removal = tokio::task::spawn_blocking(move || {
+            let mut removed = Vec::new();
+            let mut reclaimed_bytes = 0;
+            for path in expired {
+                match remove_orphaned_path(&path) {
+                    Ok(size) => {
+                        reclaimed_bytes += size;
+                        removed.push(path.display().to_string());
+                    }
+                    // Removal is retried on the next check
+                    Err(err) => log::warn!("Can't remove orphaned {}: {err}", path.display()),
+                }
+            }
+            (removed, reclaimed_bytes)
+        });
+        let (removed, reclaimed_bytes) = removal.await?;
+
+        Ok(OrphanFilesResult {
+            removed,
+            reclaimed_bytes,
+            pending: pending
+                .iter()
+                .map(|path| path.display().to_string())
+                .collect(),
+        })
+    }
+
+    /// Replay operations from the WAL archive of the collection in order of their sequence numbers.
+    /// Operations are applied to the shards they were archived from, as new updates.
+    /// The archive must be located in the `wal_archive.path` directory of the node.
+    pub async fn replay_wal_archive(
+        &self,
+        request: WalReplayRequest,
+    ) -> CollectionResult<WalReplayResult> {
+        let archive_config = self
+            .shared_storage_config
+            .wal_archive
+            .as_ref()
+            .ok_or_else(|| CollectionError::BadRequest {
+                description: "WAL archive is not configured on this node".to_string(),
+            })?;
+        // Only archives inside the configured directory can be replayed
+        let archive_dir = Path::new(request.path.as_deref().unwrap_or(&self.id));
+        if !archive_dir
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)))
+        {
+            return Err(CollectionError::BadRequest {
+                description: format!(
+                    "WAL archive path {} must be relative to the archive directory of the node",
+                    archive_dir.display(),
+                ),
+            });
+        }
+        let archive_dir = Path::new(&archive_config.path).join(archive_dir);
+
+        let shard_holder = self.shards_holder.read().await;
+        let mut shards = Vec::new();
+        for (shard_id, replica_set) in shard_holder.get_shards() {
+            let shard_dir = archive_dir.join(shard_id.to_string());
+            if !shard_dir.is_dir() {
+                continue;
+            }
+
+            let mut result = ShardWalReplayResult {
+                shard_id: *shard_id,
+                operations: 0,
+                last_operation: None,
+            };
+            for file_path in archived_files(&shard_dir)? {
+                let records: Vec<(u64, CollectionUpdateOperations)> =
+                    read_archived_file(&file_path)?;
+                for (op_num, operation) in records {
+                    // Files may overlap, if records were archived again after restart
+                    let is_replayed = result.last_operation.map_or(false, |last| op_num <= last);
+                    let is_skipped = request.after.map_or(false, |after| op_num <= after)
+                        || request.until.map_or(false, |until| op_num > until);
+                    if is_replayed || is_skipped {
+                        continue;
+                    }
+                    replica_set
+                        .update_with_consistency(operation, true, WriteOrdering::default())
+                        .await?;
+                    result.operations += 1;
+                    result.last_operation = Some(op_num);
+                }
+            }
+            shards.push(result);
+        }
+        shards.sort_by_key(|shard| shard.shard_id);
+
+        Ok(WalReplayResult { shards })
+    }
+
+    /// Start rebuilding the index of the payload field in all segments of local shards.
+    /// Segments are re-indexed one by one in background, progress is reported by
+    /// [`Collection::field_index_rebuild_info`].
+    ///
+    /// Segments under optimization are skipped, as the optimized segments get new indexes anyway.
+    pub async fn rebuild_field_index(
+        &self,
+        field_name: PayloadKeyType,
+    ) -> CollectionResult<IndexRebuildInfo> {
+        let mut segments = Vec::new();
+        {
+            let shard_holder = self.shards_holder.read().await;
+            for replica_set in shard_holder.all_shards() {
+                segments.extend(replica_set.local_original_segments().await);
+            }
+        }
+        segments.retain(|segment| {
+            segment
+                .get()
+                .read()
+                .get_indexed_fields()
+                .contains_key(&field_name)
+        });
+
+        if segments.is_empty() {
+            return Err(CollectionError::NotFound {
+                what: format!("Index of field {field_name} in local shards"),
+            });
+        }
+
+        let info = {
+            let mut index_rebuilds = self.index_rebuilds.lock();
+            if let Some(running) = index_rebuilds
+                .get(&field_name)
+                .filter(|info| info.status == IndexRebuildStatus::Running)
+            {
+                return Err(CollectionError::BadRequest {
+                    description: format!(
+                        "Index of field {field_name} is already being rebuilt, {} of {} segments done",
+                        running.segments_rebuilt, running.segments_total,
+                    ),
+                });
+            }
+            let info = IndexRebuildInfo {
+                field_name: field_name.clone(),
+                status: IndexRebuildStatus::Running,
+                segments_total: segments.len(),
+                segments_rebuilt: 0,
+                error: None,
+            };
+            index_rebuilds.insert(field_name.clone(), info.clone());
+            info
+        };
+
+        let index_rebuilds = self.index_rebuilds.clone();
+        self.update_runtime.spawn_blocking(move || {
+            for segment in segments {
+                let result = rebuild_field_index(&segment, &field_name);
+
+                let mut index_rebuilds = index_rebuilds.lock();
+                let info = match index_rebuilds.get_mut(&field_name) {
+                    Some(info) => info,
+                    None => return,
+                };
+                if let Err(err) = result {
+                    log::error!("Failed to rebuild index of field {field_name}: {err}");
+                    info.status = IndexRebuildStatus::Failed;
+                    info.error = Some(err.to_string());
+                    return;
+                }
+                info.segments_rebuilt += 1;
+            }
+            if let Some(info) = index_rebuilds.lock().get_mut(&field_name) {
+                info.status = IndexRebuildStatus::Completed;
+            }
+        });
+
+        Ok(info)
+    }
+
+    /// Progress of the last rebuild of the payload field index
+    pub fn field_index_rebuild_info(
+        &self,
+        field_name: &PayloadKeyType,
+    ) -> CollectionResult<IndexRebuildInfo> {
+        self.index_rebuilds
+            .lock()
+            .get(field_name)
+            .cloned()
+            .ok_or_else(|| CollectionError::NotFound {
+                what: format!("Rebuild of field {field_name} index"),
+            })
+    }
+
+    /// Payload key with identity claims, allowed to read the point, if point-level ACL is enabled
+    pub async fn acl_key(&self) -> Option<PayloadKeyType> {
+        self.collection_config
+            .read()
+            .await
+            .params
+            .enabled_acl_key()
+            .map(str::to_owned)
+    }
+
+    /// Check the filter of a read request against payload keys of the collection.
+    /// Conditions on indexed fields are checked against the schema of local indexes,
+    /// existence of other keys is checked by looking for any point, which has the key.
+    ///
+    /// `validation` - overrides the default mode of the node
+    pub async fn check_filter(
+        &self,
+        filter: Option<&Filter>,
+        validation: Option<FilterValidation>,
+    ) -> CollectionResult<()> {
+        let validation = validation.unwrap_or(self.shared_storage_config.filter_validation);
+        let filter = match (validation, filter) {
+            (FilterValidation::Disabled, _) | (_, None) => return Ok(()),
+            (_, Some(filter)) => filter,
+        };
+
+        let mut indexed_fields = HashMap::new();
+        for replica_set in self.shards_holder.read().await.all_shards() {
+            indexed_fields.extend(replica_set.local_indexed_fields().await);
+        }
+
+        let FilterCheck {
+            mut problems,
+            unindexed_keys,
+        } = check_filter_fields(filter, &indexed_fields);
+        for key in unindexed_keys {
+            if !self.has_payload_key(&key).await? {
+                problems.push(format!("No point has payload key `{key}`"));
+            }
+        }
+
+        if problems.is_empty() {
+            return Ok(());
+        }
+        let description = format!("Filter can't match any point: {}", problems.join("; "));
+        match validation {
+            FilterValidation::Strict => Err(CollectionError::BadRequest { description }),
+            _ => {
+                log::warn!("Collection {}: {description}", self.id);
+                Ok(())
+            }
+        }
+    }
+
+    /// Whether any point of the collection has a non-empty value of the payload key
+    async fn has_payload_key(&self, key: &PayloadKeyType) -> CollectionResult<bool> {
+        let has_key = Filter {
+            should: None,
+            must: None,
+            must_not: Some(vec![Condition::IsEmpty(IsEmptyCondition {
+                is_empty: PayloadField { key: key.clone() },
+            })]),
+        };
+        let request = ScrollRequest {
+            offset: None,
+            limit: Some(1),
+            filter: Some(has_key),
+            with_payload: Some(WithPayloadInterface::Bool(false)),
+            with_vector: WithVector::Bool(false),
+            order: ScrollOrder::Id,
+            seed: None,
+            planner_hint: None,
+            geo_order: None,
+            order_by: None,
+        };
+        let result = self.scroll_by(request, None, None).await?;
+        Ok(!result.points.is_empty())
+    }
+
+    /// Estimate the number of points, which satisfy the filter, in each segment of local shards.
+    /// Shows how the query planner would select points in each segment.
+    pub async fn estimate_cardinality(
+        &self,
+        request: CardinalityEstimationRequest,
+    ) -> CollectionResult<CardinalityEstimationResult> {
+        let mut shards = Vec::new();
+        let shard_holder = self.shards_holder.read().await;
+        for (&shard_id, replica_set) in shard_holder.get_shards() {
+            if let Some(segments) = replica_set
+                .local_cardinality_estimation(request.filter.as_ref())
+                .await
+            {
+                shards.push(ShardCardinalityEstimation { shard_id, segments });
+            }
+        }
+        shards.sort_by_key(|shard| shard.shard_id);
+        Ok(CardinalityEstimationResult::new(shards))
+    }
+
+    /// Chunks of consecutive point ids of the local replica of the shard
+    pub async fn local_id_chunks(
+        &self,
+        shard_id: ShardId,
+        chunks: usize,
+    ) -> CollectionResult<Vec<IdChunk>> {
+        let shard_holder = self.shards_holder.read().await;
+        let replica_set =
+            shard_holder
+                .get_shard(&shard_id)
+                .ok_or_else(|| CollectionError::NotFound {
+                    what: format!("Shard {shard_id}"),
+                })?;
+        replica_set.local_id_chunks(chunks).await.ok_or_else(|| {
+            CollectionError::service_error(format!(
+                "Shard {shard_id} has no local replica on peer {}",
+                self.this_peer_id
+            ))
+        })
+    }
+
+    /// Versions of the points of the local replica of the shard, which exist among the given ids
+    pub async fn local_point_versions(
+        &self,
+        shard_id: ShardId,
+        ids: &[PointIdType],
+    ) -> CollectionResult<HashMap<PointIdType, SeqNumberType>> {
+        let shard_holder = self.shards_holder.read().await;
+        let replica_set =
+            shard_holder
+                .get_shard(&shard_id)
+                .ok_or_else(|| CollectionError::NotFound {
+                    what: format!("Shard {shard_id}"),
+                })?;
+        replica_set.local_point_versions(ids).await.ok_or_else(|| {
+            CollectionError::service_error(format!(
+                "Shard {shard_id} has no local replica on peer {}",
+                self.this_peer_id
+            ))
+        })
+    }
+
+    /// Versions of the points, which exist in the collection among the given ids.
+    /// Neither payload nor vectors of the points are read.
+    pub async fn point_versions(
+        &self,
+        ids: &[PointIdType],
+        shard_selection: Option<ShardId>,
+    ) -> CollectionResult<HashMap<PointIdType, SeqNumberType>> {
+        let shard_holder = self.shards_holder.read().await;
+        let target_shards = shard_holder.target_shard(shard_selection)?;
+        let shard_versions = target_shards
+            .into_iter()
+            .map(|replica_set| replica_set.point_versions(ids));
+        let mut versions: HashMap<PointIdType, SeqNumberType> = HashMap::new();
+        for (id, version) in try_join_all(shard_versions).await?.into_iter().flatten() {
+            let latest = versions.entry(id).or_insert(version);
+            *latest = (*latest).max(version);
+        }
+        Ok(versions)
+    }
+
+    /// Wait until the local replica of the shard applies the operation with the given number
+    pub async fn local_wait_applied(
+        &self,
+        shard_id: ShardId,
+        version: SeqNumberType,
+        timeout: Duration,
+    ) -> CollectionResult<()> {
+        let shard_holder = self.shards_holder.read().await;
+        let replica_set =
+            shard_holder
+                .get_shard(&shard_id)
+                .ok_or_else(|| CollectionError::NotFound {
+                    what: format!("Shard {shard_id}"),
+                })?;
+        replica_set.local_wait_applied(version, timeout).await
+    }
+
+    /// Wait until the replicas apply the writes of the consistency token, so the following reads
+    /// observe them. Shards, which are not present anymore, are skipped.
+    pub async fn wait_for_writes(&self, token: &ConsistencyToken) -> CollectionResult<()> {
+        let shard_holder = self.shards_holder.read().await;
+        let waits = token.shards().filter_map(|(shard_id, peer_versions)| {
+            shard_holder.get_shard(&shard_id).map(|replica_set| {
+                replica_set.wait_for_writes(peer_versions, CONSISTENCY_TOKEN_WAIT_TIMEOUT)
+            })
+        });
+        try_join_all(waits).await?;
+        Ok(())
+    }
+
+    /// Split the collection into at most `partitions` non-overlapping ranges of point ids with
+    /// about the same number of points, so they can be scrolled concurrently.
+    pub async fn id_range_partitions(&self, partitions: usize) -> CollectionResult<Vec<IdRange>> {
+        let shard_holder = self.shards_holder.read().await;
+        let shard_chunks = shard_holder
+            .all_shards()
+            .map(|replica_set| replica_set.id_chunks(partitions * CHUNKS_PER_PARTITION));
+        let chunks = try_join_all(shard_chunks)
+            .await?
+            .into_iter()
+            .flatten()
+            .collect();
+        Ok(partition_id_ranges(chunks, partitions))
+    }
+
+    /// Points nearest to the `origin` according to the geo index of the field in local replicas,
+    /// with distances in meters, ordered from the nearest.
+    pub async fn nearest_points(
+        &self,
+        geo_order: &GeoDistanceOrder,
+        limit: usize,
+        filter: Option<&Filter>,
+        shard_selection: Option<ShardId>,
+    ) -> CollectionResult<Vec<(PointIdType, f64)>> {
+        let mut nearest: Option<Vec<(PointIdType, f64)>> = None;
+        let shard_holder = self.shards_holder.read().await;
+        for replica_set in shard_holder.target_shard(shard_selection)? {
+            let shard_nearest = match replica_set
+                .local_nearest_points(&geo_order.key, &geo_order.origin, limit, filter)
+                .await?
+            {
+                Some(shard_nearest) => shard_nearest,
+                None => continue,
+            };
+            nearest.get_or_insert_with(Vec::new).extend(shard_nearest);
+        }
+
+        match nearest {
+            Some(nearest) => Ok(nearest
+                .into_iter()
+                .sorted_by(|(id_a, distance_a), (id_b, distance_b)| {
+                    distance_a.total_cmp(distance_b).then(id_a.cmp(id_b))
+                })
+                .take(limit)
+                .collect()),
+            None => Err(CollectionError::BadRequest {
+                description: format!(
+                    "Geo index of field {} is required to order points by geo distance",
+                    geo_order.key
+                ),
+            }),
+        }
+    }
+
+    /// Points ordered by values of the integer or float index of the field in local replicas,
+    /// starting from the `start_from` value of the order and the `offset` id.
+    pub async fn ordered_points(
+        &self,
+        order_by: &OrderBy,
+        offset: Option<PointIdType>,
+        limit: usize,
+        filter: Option<&Filter>,
+        shard_selection: Option<ShardId>,
+    ) -> CollectionResult<Vec<(PointIdType, f64)>> {
+        let direction = order_by.direction;
+        let mut ordered: Option<Vec<(PointIdType, f64)>> = None;
+        let shard_holder = self.shards_holder.read().await;
+        for replica_set in shard_holder.target_shard(shard_selection)? {
+            let shard_ordered = match replica_set
+                .local_ordered_points(
+                    &order_by.key,
+                    direction,
+                    order_by.start_from,
+                    offset,
+                    limit,
+                    filter,
+                )
+                .await?
+            {
+                Some(shard_ordered) => shard_ordered,
+                None => continue,
+            };
+            ordered.get_or_insert_with(Vec::new).extend(shard_ordered);
+        }
+
+        match ordered {
+            Some(ordered) => Ok(ordered
+                .into_iter()
+                .sorted_by(|(id_a, value_a), (id_b, value_b)| {
+                    direction.cmp(*value_a, *value_b).then(id_a.cmp(id_b))
+                })
+                .take(limit)
+                .collect()),
+            None => Err(CollectionError::BadRequest {
+                description: format!(
+                    "Integer or float index of field {} is required to order points by it",
+                    order_by.key
+                ),
+            }),
+        }
+    }
+
+    /// Term counts of the keyword or full-text index of the field, summed over all shards.
+    /// Returns `None` if no shard has the index of the field.
+    ///
+    /// Terms are counted in the indexes of local replicas, so all shards of the collection must
+    /// have an active replica on this peer.
+    async fn term_counts(
+        &self,
+        field_name: PayloadKeyTypeRef<'_>,
+        filter: Option<&Filter>,
+    ) -> CollectionResult<Option<HashMap<String, usize>>> {
+        let mut term_counts: Option<HashMap<String, usize>> = None;
+        let shard_holder = self.shards_holder.read().await;
+        for replica_set in shard_holder.all_shards() {
+            let shard_counts = match replica_set.local_term_counts(field_name, filter).await? {
+                Some(shard_counts) => shard_counts,
+                None => continue,
+            };
+            let term_counts = term_counts.get_or_insert_with(HashMap::new);
+            for (term, count) in shard_counts {
+                *term_counts.entry(term).or_default() += count;
+            }
+        }
+        Ok(term_counts)
+    }
+
+    /// Most frequent terms of the keyword or full-text index of the field.
+    /// Each term is counted once per point, which has it and satisfies the filter of the request.
+    pub async fn field_terms(
+        &self,
+        field_name: PayloadKeyType,
+        request: TermsRequest,
+    ) -> CollectionResult<TermsResult> {
+        let term_counts = self
+            .term_counts(&field_name, request.filter.as_ref())
+            .await?;
+        match term_counts {
+            Some(term_counts) => Ok(TermsResult {
+                field_name,
+                terms: top_terms(term_counts, request.limit()),
+            }),
+            None => Err(CollectionError::NotFound {
+                what: format!("Keyword or text index of field {field_name}"),
+            }),
+        }
+    }
+
+    /// Number of points for each value of the keyword-indexed field.
+    /// Values are read from the keyword index, points are not scrolled.
+    pub async fn facet(&self, request: FacetRequest) -> CollectionResult<FacetResponse> {
+        // Terms of full-text index are tokens, not the values of the field
+        for replica_set in self.shards_holder.read().await.all_shards() {
+            match replica_set.local_indexed_fields().await.get(&request.key) {
+                Some(PayloadFieldSchema::FieldType(PayloadSchemaType::Keyword)) | None => {}
+                Some(_) => {
+                    return Err(CollectionError::BadRequest {
+                        description: format!(
+                            "Keyword index of field {} is required for facet counting",
+                            request.key
+                        ),
+                    })
+                }
+            }
+        }
+
+        let value_counts = self
+            .term_counts(&request.key, request.filter.as_ref())
+            .await?
+            .ok_or_else(|| CollectionError::NotFound {
+                what: format!("Keyword index of field {}", request.key),
+            })?;
+
+        Ok(FacetResponse {
+            hits: top_values(value_counts, request.limit()),
+        })
+    }
+
+    /// Distribution of values of the integer or float index of the field.
+    ///
+    /// Bounds of the values are collected first, so all shards count points in the same buckets.
+    /// Values are read from the indexes of local replicas, so all shards of the collection must
+    /// have an active replica on this peer.
+    pub async fn field_histogram(
+        &self,
+        field_name: PayloadKeyType,
+        request: HistogramRequest,
+    ) -> CollectionResult<HistogramResult> {
+        let shard_holder = self.shards_holder.read().await;
+        let mut bounds: Option<Option<ValueBounds>> = None;
+        for replica_set in shard_holder.all_shards() {
+            let shard_bounds = match replica_set
+                .local_numeric_bounds(&field_name, request.filter.as_ref())
+                .await?
+            {
+                Some(shard_bounds) => shard_bounds,
+                None => continue,
+            };
+            let bounds = bounds.get_or_insert(None);
+            *bounds = ValueBounds::merge_opt(*bounds, shard_bounds);
+        }
+
+        let bounds = match bounds {
+            Some(Some(bounds)) => bounds,
+            Some(None) => {
+                return Ok(HistogramResult {
+                    field_name,
+                    buckets: vec![],
+                })
+            }
+            None => {
+                return Err(CollectionError::NotFound {
+                    what: format!("Integer or float index of field {field_name}"),
+                })
+            }
+        };
+        let buckets = HistogramBuckets {
+            bounds,
+            count: request.buckets(),
+        };
+
+        let mut counts = vec![0; buckets.count];
+        for replica_set in shard_holder.all_shards() {
+            let shard_counts = replica_set
+                .local_numeric_histogram(&field_name, &buckets, request.filter.as_ref())
+                .await?
+                .unwrap_or_default();
+            for (count, shard_count) in counts.iter_mut().zip(shard_counts) {
+                *count += shard_count;
+            }
+        }
+
+        Ok(HistogramResult {
+            field_name,
+            buckets: histogram_buckets(&buckets, counts),
+        })
+    }
+
+    /// Infer types of payload fields from a random sample of points and suggest index schemas.
+    /// Useful to create indexes for payloads without a consistent structure.
+    pub async fn infer_payload_schema(
+        &self,
+        request: PayloadSchemaInferenceRequest,
+    ) -> CollectionResult<PayloadSchemaInferenceResult> {
+        let scroll_request = ScrollRequest {
+            offset: None,
+            limit: Some(request.samples()),
+            filter: request.filter,
+            with_payload: Some(WithPayloadInterface::Bool(true)),
+            with_vector: WithVector::Bool(false),
+            order: ScrollOrder::Random,
+            seed: request.seed,
+            planner_hint: None,
+            geo_order: None,
+            order_by: None,
+        };
+        let sample = self.scroll_by(scroll_request, None, None).await?;
+        let payloads: Vec<_> = sample
+            .points
+            .into_iter()
+            .filter_map(|point| point.payload.map(RecordPayload::into_payload))
+            .collect::<Result<_, _>>()?;
+        Ok(infer_payload_schema(&payloads))
+    }
+
+    /// Estimate recall of approximate search in segments of local shards.
+    ///
+    /// Sampled stored points are used as queries, so the estimation doesn't require any input
+    /// from the user and may be used to check the index after bulk updates.
+    pub async fn estimate_recall(
+        &self,
+        request: RecallEstimationRequest,
+    ) -> CollectionResult<RecallEstimationResult> {
+        let vector_name = {
+            let collection_params = &self.collection_config.read().await.params;
+            let vector_name = collection_params
+                .resolve_vector_name(request.vector.as_deref().unwrap_or(DEFAULT_VECTOR_NAME));
+            // Check that the vector exists
+            collection_params.get_vector_params(&vector_name)?;
+            vector_name
+        };
+
+        let mut segments = Vec::new();
+        let shard_holder = self.shards_holder.read().await;
+        for replica_set in shard_holder.all_shards() {
+            segments.extend(
+                replica_set
+                    .estimate_recall(&vector_name, &request, &self.search_runtime)
+                    .await?,
+            );
+        }
+
+        Ok(RecallEstimationResult {
+            vector: vector_name,
+            limit: request.limit(),
+            segments,
+        })
+    }
+
+    /// Measure raw scoring throughput of the vector in segments of local shards.
+    ///
+    /// Helps to check, that SIMD instructions and storage IO perform as expected on the hardware.
+    pub async fn benchmark_scoring(
+        &self,
+        request: ScoringBenchmarkRequest,
+    ) -> CollectionResult<ScoringBenchmarkResult> {
+        let vector_name = {
+            let collection_params = &self.collection_config.read().await.params;
+            let vector_name = collection_params
+                .resolve_vector_name(request.vector.as_deref().unwrap_or(DEFAULT_VECTOR_NAME));
+            // Check that the vector exists
+            collection_params.get_vector_params(&vector_name)?;
+            vector_name
+        };
+
+        let mut measurements = Vec::new();
+        let shard_holder = self.shards_holder.read().await;
+        for replica_set in shard_holder.all_shards() {
+            measurements.extend(
+                replica_set
+                    .benchmark_scoring(&vector_name, request.points())
+                    .await?,
+            );
+        }
+
+        Ok(ScoringBenchmarkResult {
+            vector: vector_name,
+            measurements: aggregate_measurements(measurements),
+        })
+    }
+
+    pub async fn list_snapshots(&self) -> CollectionResult<Vec<SnapshotDescription>> {
+        list_snapshots_in_directory(&self.snapshots_path).await
+    }
+
+    pub async fn get_snapshot_path(&self, snapshot_name: &str) -> CollectionResult<PathBuf> {
+        let snapshot_path = self.snapshots_path.join(snapshot_name);
+        if !snapshot_path.exists() {
+            return Err(CollectionError::NotFound {
+                what: format!("Snapshot {snapshot_name}"),
+            });
+        }
+        Ok(snapshot_path)
+    }
+
+    pub async fn create_snapshot(
+        &self,
+        temp_dir: &Path,
         this_peer_id: PeerId,
     ) -> CollectionResult<SnapshotDescription> {
         let snapshot_name = format!(
@@ -1457,13 +3476,15 @@ impl Collection {
 
     /// Restore collection from snapshot
     ///
+    /// Restored segments are verified afterwards, found inconsistencies are logged and reported
+    /// in the returned summary.
     /// This method performs blocking IO.
     pub fn restore_snapshot(
         snapshot_path: &Path,
         target_dir: &Path,
         this_peer_id: PeerId,
         is_distributed: bool,
-    ) -> CollectionResult<()> {
+    ) -> CollectionResult<SnapshotVerification> {
         // decompress archive
         let archive_file = std::fs::File::open(snapshot_path)?;
         let mut ar = tar::Archive::new(archive_file);
@@ -1499,7 +3520,105 @@ impl Collection {
             }
         }
 
-        Ok(())
+        let verification = Self::verify_restored_shards(target_dir, configured_shards)?;
+        if verification.is_ok() {
+            log::info!(
+                "Verified {} segments with {} points, restored from snapshot {}",
+                verification.segments,
+                verification.points_count,
+                snapshot_path.display(),
+            );
+        } else {
+            for problem in &verification.problems {
+                log::error!(
+                    "Snapshot {} is inconsistent: {problem}",
+                    snapshot_path.display()
+                );
+            }
+        }
+        Ok(verification)
+    }
+
+    /// Load segments of restored local shards and cross-check their components
+    fn verify_restored_shards(
+        target_dir: &Path,
+        shard_number: u32,
+    ) -> CollectionResult<SnapshotVerification> {
+        let mut verification = SnapshotVerification::default();
+        for shard_id in 0..shard_number {
+            let segments_path =
+                LocalShard::segments_path(&versioned_shard_path(target_dir, shard_id, 0));
+            if !segments_path.is_dir() {
+                continue;
+            }
+            for entry in std::fs::read_dir(&segments_path)? {
+                let segment_path = entry?.path();
+                if !segment_path.is_dir() || segment_path.ends_with("deleted") {
+                    continue;
+                }
+                match load_segment(&segment_path) {
+                    Ok(Some(segment)) => {
+                        let segment_verification = segment.verify(RESTORE_VERIFICATION_SAMPLES)?;
+                        verification.add_segment(&segment_path, &segment_verification);
+                    }
+                    Ok(None) => {}
+                    Err(err) => verification.problems.push(format!(
+                        "Segment {}: can't be loaded: {err}",
+                        segment_path.display()
+                    )),
+                }
+            }
+        }
+        Ok(verification)
+    }
+
+    /// Describe content of the snapshot: version, config, shards and segments.
+    ///
+    /// Snapshot is unpacked into `target_dir` and segments are loaded from there,
+    /// so it doesn't affect the collection storage.
+    /// This method performs blocking IO.
+    pub fn inspect_snapshot(
+        snapshot_path: &Path,
+        target_dir: &Path,
+    ) -> CollectionResult<SnapshotInspection> {
+        let archive_file = std::fs::File::open(snapshot_path)?;
+        let mut ar = tar::Archive::new(archive_file);
+        ar.unpack(target_dir)?;
+
+        let version = CollectionVersion::load(target_dir)?;
+        let config = CollectionConfig::load(target_dir)?;
+
+        let mut shards = Vec::new();
+        for shard_id in 0..config.params.shard_number.get() {
+            let shard_path = versioned_shard_path(target_dir, shard_id, 0);
+            let segments_path = LocalShard::segments_path(&shard_path);
+            if !segments_path.is_dir() {
+                shards.push(ShardSnapshotInspection::new(shard_id, false, vec![]));
+                continue;
+            }
+
+            LocalShard::restore_snapshot(&shard_path)?;
+            let mut segments = Vec::new();
+            for entry in std::fs::read_dir(&segments_path)? {
+                let segment_path = entry?.path();
+                if !segment_path.is_dir() || segment_path.ends_with("deleted") {
+                    continue;
+                }
+                if let Some(segment) = load_segment(&segment_path)? {
+                    segments.push(SegmentSnapshotInspection {
+                        segment_id: segment_path
+                            .file_name()
+                            .map(|name| name.to_string_lossy().into_owned())
+                            .unwrap_or_default(),
+                        info: segment.info(),
+                    });
+                }
+            }
+            segments.sort_by(|a, b| a.segment_id.cmp(&b.segment_id));
+            shards.push(ShardSnapshotInspection::new(shard_id, true, segments));
+        }
+
+        Ok(SnapshotInspection::new(version, config, shards))
     }
 
     pub async fn remove_shards_at_peer(&self, peer_id: PeerId) -> CollectionResult<()> {
@@ -1585,13 +3704,19 @@ impl Collection {
             }
 
             if this_peer_state != Some(Dead) {
+                self.dead_replica_recovery.lock().remove(&shard_id);
                 continue; // All good
             }
 
+            if !self.dead_replica_recovery_due(shard_id) {
+                continue;
+            }
+
             // Try to find dead replicas with no active transfers
             let transfers = self.get_transfers(|_| true).await;
 
-            // Try to find a replica to transfer from
+            // Try to find a healthy replica to transfer from
+            let mut recovery_requested = false;
             for replica_id in replica_set.active_remote_shards().await {
                 let transfer = ShardTransfer {
                     from: replica_id,
@@ -1602,6 +3727,16 @@ impl Collection {
                 if check_transfer_conflicts(&transfer, transfers.iter()).is_some() {
                     continue; // this transfer won't work
                 }
+                if let Err(err) = replica_set.check_remote_replica(replica_id).await {
+                    log::debug!(
+                        "Can't recover shard {}:{} from peer {}, health check failed: {}",
+                        self.name(),
+                        shard_id,
+                        replica_id,
+                        err
+                    );
+                    continue;
+                }
                 log::debug!(
                     "Recovering shard {}:{} on peer {} by requesting it from {}",
                     self.name(),
@@ -1610,13 +3745,40 @@ impl Collection {
                     replica_id
                 );
                 self.request_shard_transfer(transfer);
+                recovery_requested = true;
                 break;
             }
+            if !recovery_requested {
+                log::warn!(
+                    "No healthy replicas to recover shard {}:{}, retrying after cool-down",
+                    self.name(),
+                    shard_id
+                );
+            }
         }
 
         Ok(())
     }
 
+    /// Check if recovery of the dead replica of this peer should be attempted now, according to
+    /// the dead replica recovery policy. Schedules the next attempt after the cool-down.
+    fn dead_replica_recovery_due(&self, shard_id: ShardId) -> bool {
+        let policy = self.shared_storage_config.dead_replica_recovery;
+        if !policy.enabled {
+            return false;
+        }
+        let now = Instant::now();
+        let mut next_attempts = self.dead_replica_recovery.lock();
+        let next_attempt = *next_attempts
+            .entry(shard_id)
+            .or_insert_with(|| now + policy.cooldown());
+        if next_attempt > now {
+            return false;
+        }
+        next_attempts.insert(shard_id, now + policy.cooldown());
+        true
+    }
+
     pub fn wait_collection_initiated(&self, timeout: Duration) -> bool {
         self.is_initialized.await_ready_for_timeout(timeout)
     }
@@ -70,13 +70,18 @@ impl CollectionUpdater {
 
 #[cfg(test)]
 mod tests {
-    use segment::data_types::vectors::{only_default_vector, DEFAULT_VECTOR_NAME};
-    use segment::types::{Payload, WithPayload};
+    use std::collections::HashMap;
+
+    use segment::data_types::vectors::{only_default_vector, VectorStruct, DEFAULT_VECTOR_NAME};
+    use segment::segment_constructor::build_segment;
+    use segment::types::{
+        Distance, Indexes, Payload, SegmentConfig, VectorDataConfig, WithPayload,
+    };
     use serde_json::json;
     use tempfile::Builder;
 
     use super::*;
-    use crate::collection_manager::fixtures::build_test_holder;
+    use crate::collection_manager::fixtures::{build_segment_1, build_test_holder};
     use crate::collection_manager::segments_searcher::SegmentsSearcher;
     use crate::collection_manager::segments_updater::upsert_points;
     use crate::operations::payload_ops::{DeletePayload, PayloadOps, SetPayload};
@@ -97,26 +102,36 @@ mod tests {
                 id: 11.into(),
                 vector: vec11.into(),
                 payload: None,
+                sequence: None,
+                content_hash: None,
             },
             PointStruct {
                 id: 12.into(),
                 vector: vec12.into(),
                 payload: None,
+                sequence: None,
+                content_hash: None,
             },
             PointStruct {
                 id: 13.into(),
                 vector: vec13.into(),
                 payload: Some(json!({ "color": "red" }).into()),
+                sequence: None,
+                content_hash: None,
             },
             PointStruct {
                 id: 14.into(),
                 vector: vec![0., 0., 0., 0.].into(),
                 payload: None,
+                sequence: None,
+                content_hash: None,
             },
             PointStruct {
                 id: 500.into(),
                 vector: vec![2., 0., 2., 0.].into(),
                 payload: None,
+                sequence: None,
+                content_hash: None,
             },
         ];
 
@@ -139,11 +154,15 @@ mod tests {
                 id: 1.into(),
                 vector: vec![2., 2., 2., 2.].into(),
                 payload: None,
+                sequence: None,
+                content_hash: None,
             },
             PointStruct {
                 id: 500.into(),
                 vector: vec![2., 0., 2., 0.].into(),
                 payload: None,
+                sequence: None,
+                content_hash: None,
             },
         ];
 
@@ -228,7 +247,7 @@ mod tests {
             Some(r) => match &r.payload {
                 None => panic!("No payload assigned"),
                 Some(payload) => {
-                    assert!(payload.contains_key("color"))
+                    assert!(payload.to_payload().unwrap().contains_key("color"))
                 }
             },
         };
@@ -254,7 +273,13 @@ mod tests {
         .await
         .unwrap();
         assert_eq!(res.len(), 1);
-        assert!(!res[0].payload.as_ref().unwrap().contains_key("color"));
+        assert!(!res[0]
+            .payload
+            .as_ref()
+            .unwrap()
+            .to_payload()
+            .unwrap()
+            .contains_key("color"));
 
         // Test clear payload
 
@@ -267,7 +292,13 @@ mod tests {
         .await
         .unwrap();
         assert_eq!(res.len(), 1);
-        assert!(res[0].payload.as_ref().unwrap().contains_key("color"));
+        assert!(res[0]
+            .payload
+            .as_ref()
+            .unwrap()
+            .to_payload()
+            .unwrap()
+            .contains_key("color"));
 
         process_payload_operation(
             &segments,
@@ -286,6 +317,279 @@ mod tests {
         .await
         .unwrap();
         assert_eq!(res.len(), 1);
-        assert!(!res[0].payload.as_ref().unwrap().contains_key("color"));
+        assert!(!res[0]
+            .payload
+            .as_ref()
+            .unwrap()
+            .to_payload()
+            .unwrap()
+            .contains_key("color"));
+    }
+
+    #[tokio::test]
+    async fn test_upsert_with_sequence() {
+        let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+        let segments = build_test_holder(dir.path());
+
+        let point = |vector: Vec<f32>, sequence: u64| PointStruct {
+            id: 1.into(),
+            vector: vector.into(),
+            payload: None,
+            sequence: Some(sequence),
+            content_hash: None,
+        };
+
+        upsert_points(&segments.read(), 100, &[point(vec![1., 1., 1., 1.], 10)]).unwrap();
+
+        // Stale write must be skipped
+        let res = upsert_points(&segments.read(), 101, &[point(vec![2., 2., 2., 2.], 5)]);
+        assert!(matches!(res, Ok(0)));
+
+        // Repeated write is skipped too
+        let res = upsert_points(&segments.read(), 102, &[point(vec![3., 3., 3., 3.], 10)]);
+        assert!(matches!(res, Ok(0)));
+
+        let records = SegmentsSearcher::retrieve(
+            &segments,
+            &[1.into()],
+            &WithPayload::from(true),
+            &true.into(),
+        )
+        .await
+        .unwrap();
+        let vector = records[0].vector.as_ref().unwrap();
+        assert_eq!(vector.get(DEFAULT_VECTOR_NAME), Some(&vec![1., 1., 1., 1.]));
+
+        // Newer write is applied
+        upsert_points(&segments.read(), 103, &[point(vec![4., 4., 4., 4.], 11)]).unwrap();
+
+        let records = SegmentsSearcher::retrieve(
+            &segments,
+            &[1.into()],
+            &WithPayload::from(true),
+            &true.into(),
+        )
+        .await
+        .unwrap();
+        let vector = records[0].vector.as_ref().unwrap();
+        assert_eq!(vector.get(DEFAULT_VECTOR_NAME), Some(&vec![4., 4., 4., 4.]));
+        // Sequence is stored apart from the payload
+        let payload = records[0].payload.as_ref().unwrap().to_payload().unwrap();
+        assert!(payload.0.is_empty());
+
+        // Payload operations and upserts without sequence keep the stored one
+        process_payload_operation(
+            &segments,
+            104,
+            PayloadOps::OverwritePayload(SetPayload {
+                payload: json!({ "_sequence": 0 }).into(),
+                points: Some(vec![1.into()]),
+                filter: None,
+            }),
+        )
+        .unwrap();
+        process_payload_operation(
+            &segments,
+            105,
+            PayloadOps::ClearPayload {
+                points: vec![1.into()],
+            },
+        )
+        .unwrap();
+        upsert_points(
+            &segments.read(),
+            106,
+            &[PointStruct {
+                sequence: None,
+                ..point(vec![5., 5., 5., 5.], 0)
+            }],
+        )
+        .unwrap();
+
+        let res = upsert_points(&segments.read(), 107, &[point(vec![6., 6., 6., 6.], 11)]);
+        assert!(matches!(res, Ok(0)));
+        assert_eq!(stored_default_vector(&segments).await, vec![5., 5., 5., 5.]);
+    }
+
+    async fn stored_default_vector(segments: &RwLock<SegmentHolder>) -> Vec<f32> {
+        let records = SegmentsSearcher::retrieve(
+            segments,
+            &[1.into()],
+            &WithPayload::from(true),
+            &true.into(),
+        )
+        .await
+        .unwrap();
+        let vector = records[0].vector.as_ref().unwrap();
+        vector.get(DEFAULT_VECTOR_NAME).cloned().unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_upsert_with_content_hash() {
+        let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+        let segments = build_test_holder(dir.path());
+
+        let point = |vector: Vec<f32>, content_hash: Option<&str>| PointStruct {
+            id: 1.into(),
+            vector: vector.into(),
+            payload: None,
+            sequence: None,
+            content_hash: content_hash.map(str::to_string),
+        };
+
+        upsert_points(
+            &segments.read(),
+            100,
+            &[point(vec![1., 1., 1., 1.], Some("a"))],
+        )
+        .unwrap();
+
+        // Write with the same hash must be skipped
+        let res = upsert_points(
+            &segments.read(),
+            101,
+            &[point(vec![2., 2., 2., 2.], Some("a"))],
+        );
+        assert!(matches!(res, Ok(0)));
+        assert_eq!(stored_default_vector(&segments).await, vec![1., 1., 1., 1.]);
+
+        // Write without hash is applied and invalidates the stored hash
+        upsert_points(&segments.read(), 102, &[point(vec![3., 3., 3., 3.], None)]).unwrap();
+        assert_eq!(stored_default_vector(&segments).await, vec![3., 3., 3., 3.]);
+
+        upsert_points(
+            &segments.read(),
+            103,
+            &[point(vec![4., 4., 4., 4.], Some("a"))],
+        )
+        .unwrap();
+        assert_eq!(stored_default_vector(&segments).await, vec![4., 4., 4., 4.]);
+
+        // Hash is stored apart from the payload, so it can't be read or forged through it
+        let records = SegmentsSearcher::retrieve(
+            &segments,
+            &[1.into()],
+            &WithPayload::from(true),
+            &false.into(),
+        )
+        .await
+        .unwrap();
+        let payload = records[0].payload.as_ref().unwrap().to_payload().unwrap();
+        assert!(payload.0.is_empty());
+
+        process_payload_operation(
+            &segments,
+            104,
+            PayloadOps::SetPayload(SetPayload {
+                payload: json!({ "_content_hash": "b" }).into(),
+                points: Some(vec![1.into()]),
+                filter: None,
+            }),
+        )
+        .unwrap();
+        upsert_points(
+            &segments.read(),
+            105,
+            &[point(vec![5., 5., 5., 5.], Some("b"))],
+        )
+        .unwrap();
+        assert_eq!(stored_default_vector(&segments).await, vec![5., 5., 5., 5.]);
+
+        // Payload update invalidates the stored hash
+        process_payload_operation(
+            &segments,
+            106,
+            PayloadOps::SetPayload(SetPayload {
+                payload: json!({ "color": "red" }).into(),
+                points: Some(vec![1.into()]),
+                filter: None,
+            }),
+        )
+        .unwrap();
+        upsert_points(
+            &segments.read(),
+            107,
+            &[point(vec![6., 6., 6., 6.], Some("b"))],
+        )
+        .unwrap();
+        assert_eq!(stored_default_vector(&segments).await, vec![6., 6., 6., 6.]);
+    }
+
+    #[tokio::test]
+    async fn test_upsert_with_added_vector() {
+        let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+
+        let vector_config = VectorDataConfig {
+            size: 4,
+            distance: Distance::Dot,
+            hnsw_config: None,
+            quantization_config: None,
+        };
+        let extended_segment = build_segment(
+            dir.path(),
+            &SegmentConfig {
+                vector_data: HashMap::from([
+                    (DEFAULT_VECTOR_NAME.to_owned(), vector_config.clone()),
+                    ("extra".to_owned(), vector_config),
+                ]),
+                index: Indexes::Plain {},
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let mut holder = SegmentHolder::default();
+        let old_sid = holder.add(build_segment_1(dir.path()));
+        let new_sid = holder.add(extended_segment);
+        let segments = RwLock::new(holder);
+
+        let vectors = HashMap::from([
+            (DEFAULT_VECTOR_NAME.to_owned(), vec![2., 2., 2., 2.]),
+            ("extra".to_owned(), vec![3., 3., 3., 3.]),
+        ]);
+        let points = vec![
+            PointStruct {
+                id: 1.into(),
+                vector: VectorStruct::Multi(vectors),
+                payload: None,
+                sequence: None,
+                content_hash: None,
+            },
+            PointStruct {
+                id: 500.into(),
+                vector: vec![2., 0., 2., 0.].into(),
+                payload: None,
+                sequence: None,
+                content_hash: None,
+            },
+        ];
+        upsert_points(&segments.read(), 100, &points).unwrap();
+
+        {
+            // Point with the new vector is moved into the segment, which stores it
+            let holder = segments.read();
+            let old_segment = holder.get(old_sid).unwrap().get();
+            let new_segment = holder.get(new_sid).unwrap().get();
+            assert!(!old_segment.read().has_point(1.into()));
+            assert!(new_segment.read().has_point(1.into()));
+
+            // Point without the new vector goes to the segment with the old layout
+            assert!(old_segment.read().has_point(500.into()));
+            assert!(!new_segment.read().has_point(500.into()));
+        }
+
+        let records = SegmentsSearcher::retrieve(
+            &segments,
+            &[1.into()],
+            &WithPayload::from(true),
+            &true.into(),
+        )
+        .await
+        .unwrap();
+        let vector = records[0].vector.as_ref().unwrap();
+        assert_eq!(vector.get("extra"), Some(&vec![3., 3., 3., 3.]));
+        // Payload is preserved on relocation
+        let payload = records[0].payload.as_ref().unwrap().to_payload().unwrap();
+        assert!(payload.contains_key("color"));
     }
 }
@@ -178,11 +178,21 @@ pub(crate) fn get_merge_optimizer(
                 distance: Distance::Dot,
                 hnsw_config: None,
                 quantization_config: None,
+                projection: None,
             }),
             shard_number: NonZeroU32::new(1).unwrap(),
             on_disk_payload: false,
             replication_factor: NonZeroU32::new(1).unwrap(),
             write_consistency_factor: NonZeroU32::new(1).unwrap(),
+            vector_aliases: Default::default(),
+            default_vector: None,
+            defrag_key: None,
+            deduplicate_upserts: false,
+            max_concurrent_searches: 0,
+            acl_key: None,
+            default_write_ordering: None,
+            default_read_consistency: None,
+            sparse_vectors: Default::default(),
         },
         Default::default(),
         Default::default(),
@@ -208,11 +218,21 @@ pub(crate) fn get_indexing_optimizer(
                 distance: Distance::Dot,
                 hnsw_config: None,
                 quantization_config: None,
+                projection: None,
             }),
             shard_number: NonZeroU32::new(1).unwrap(),
             on_disk_payload: false,
             replication_factor: NonZeroU32::new(1).unwrap(),
             write_consistency_factor: NonZeroU32::new(1).unwrap(),
+            vector_aliases: Default::default(),
+            default_vector: None,
+            defrag_key: None,
+            deduplicate_upserts: false,
+            max_concurrent_searches: 0,
+            acl_key: None,
+            default_write_ordering: None,
+            default_read_consistency: None,
+            sparse_vectors: Default::default(),
         },
         Default::default(),
         Default::default(),
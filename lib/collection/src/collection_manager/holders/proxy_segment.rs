@@ -1,19 +1,26 @@
 use std::cmp::max;
 use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
+use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
 
 use parking_lot::{RwLock, RwLockUpgradableReadGuard};
+use segment::common::utils::sampling_key;
+use segment::data_types::distinct::DistinctValues;
+use segment::data_types::histogram::{HistogramBuckets, ValueBounds};
 use segment::data_types::named_vectors::NamedVectors;
+use segment::data_types::order_by::Direction;
+use segment::data_types::sparse_vector::{SparseVector, SparseVectors};
 use segment::data_types::vectors::VectorElementType;
 use segment::entry::entry_point::{OperationResult, SegmentEntry, SegmentFailedState};
 use segment::index::field_index::CardinalityEstimation;
 use segment::telemetry::SegmentTelemetry;
 use segment::types::{
-    Condition, Filter, Payload, PayloadFieldSchema, PayloadKeyType, PayloadKeyTypeRef, PointIdType,
-    ScoredPoint, SearchParams, SegmentConfig, SegmentInfo, SegmentType, SeqNumberType, WithPayload,
-    WithVector,
+    Condition, Filter, GeoPoint, MemoryUsage, Payload, PayloadFieldSchema, PayloadKeyType,
+    PayloadKeyTypeRef, PlannerHint, PointIdType, PointMetadata, RecordPayload, ScoredPoint,
+    SearchParams, SegmentConfig, SegmentInfo, SegmentType, SeqNumberType, WithPayload, WithVector,
 };
+use segment::vector_storage::scoring_benchmark::ScoringMeasurement;
 
 use crate::collection_manager::holders::segment_holder::LockedSegment;
 
@@ -105,9 +112,11 @@ impl ProxySegment {
             return Ok(false);
         }
 
-        let (all_vectors, payload) = (
+        let (all_vectors, sparse_vectors, payload, metadata) = (
             wrapped_segment_guard.all_vectors(point_id)?,
+            wrapped_segment_guard.sparse_vectors(point_id)?,
             wrapped_segment_guard.payload(point_id)?,
+            wrapped_segment_guard.point_metadata(point_id)?,
         );
 
         {
@@ -119,7 +128,9 @@ impl ProxySegment {
         let mut write_segment = segment_arc.write();
 
         write_segment.upsert_vector(op_num, point_id, &all_vectors)?;
+        write_segment.update_sparse_vectors(op_num, point_id, &sparse_vectors)?;
         write_segment.set_full_payload(op_num, point_id, &payload)?;
+        write_segment.set_point_metadata(op_num, point_id, &metadata)?;
 
         Ok(true)
     }
@@ -234,6 +245,7 @@ impl SegmentEntry for ProxySegment {
         filter: Option<&Filter>,
         top: usize,
         params: Option<&SearchParams>,
+        is_stopped: &AtomicBool,
     ) -> OperationResult<Vec<Vec<ScoredPoint>>> {
         let deleted_points = self.deleted_points.read();
 
@@ -256,6 +268,7 @@ impl SegmentEntry for ProxySegment {
                 Some(&wrapped_filter),
                 top,
                 params,
+                is_stopped,
             )?
         } else {
             self.wrapped_segment.get().read().search_batch(
@@ -266,6 +279,7 @@ impl SegmentEntry for ProxySegment {
                 filter,
                 top,
                 params,
+                is_stopped,
             )?
         };
         let mut write_results = self.write_segment.get().read().search_batch(
@@ -276,6 +290,7 @@ impl SegmentEntry for ProxySegment {
             filter,
             top,
             params,
+            is_stopped,
         )?;
         for (index, write_result) in write_results.iter_mut().enumerate() {
             wrapped_results[index].append(write_result)
@@ -283,6 +298,67 @@ impl SegmentEntry for ProxySegment {
         Ok(wrapped_results)
     }
 
+    fn search_sparse(
+        &self,
+        vector_name: &str,
+        vector: &SparseVector,
+        with_payload: &WithPayload,
+        with_vector: &WithVector,
+        filter: Option<&Filter>,
+        top: usize,
+    ) -> OperationResult<Vec<ScoredPoint>> {
+        let deleted_points = self.deleted_points.read();
+
+        // Points, moved into the write segment, are excluded from the wrapped one, as in `search`
+        let mut wrapped_result = if !deleted_points.is_empty() {
+            let wrapped_filter =
+                self.add_deleted_points_condition_to_filter(filter, &deleted_points);
+
+            self.wrapped_segment.get().read().search_sparse(
+                vector_name,
+                vector,
+                with_payload,
+                with_vector,
+                Some(&wrapped_filter),
+                top,
+            )?
+        } else {
+            self.wrapped_segment.get().read().search_sparse(
+                vector_name,
+                vector,
+                with_payload,
+                with_vector,
+                filter,
+                top,
+            )?
+        };
+
+        let mut write_result = self.write_segment.get().read().search_sparse(
+            vector_name,
+            vector,
+            with_payload,
+            with_vector,
+            filter,
+            top,
+        )?;
+
+        wrapped_result.append(&mut write_result);
+        Ok(wrapped_result)
+    }
+
+    fn update_sparse_vectors(
+        &mut self,
+        op_num: SeqNumberType,
+        point_id: PointIdType,
+        vectors: &SparseVectors,
+    ) -> OperationResult<bool> {
+        self.move_if_exists(op_num, point_id)?;
+        self.write_segment
+            .get()
+            .write()
+            .update_sparse_vectors(op_num, point_id, vectors)
+    }
+
     fn upsert_vector(
         &mut self,
         op_num: SeqNumberType,
@@ -366,6 +442,19 @@ impl SegmentEntry for ProxySegment {
             .clear_payload(op_num, point_id)
     }
 
+    fn set_point_metadata(
+        &mut self,
+        op_num: SeqNumberType,
+        point_id: PointIdType,
+        metadata: &PointMetadata,
+    ) -> OperationResult<bool> {
+        self.move_if_exists(op_num, point_id)?;
+        self.write_segment
+            .get()
+            .write()
+            .set_point_metadata(op_num, point_id, metadata)
+    }
+
     fn vector(
         &self,
         vector_name: &str,
@@ -406,6 +495,21 @@ impl SegmentEntry for ProxySegment {
         Ok(result)
     }
 
+    fn sparse_vectors(&self, point_id: PointIdType) -> OperationResult<SparseVectors> {
+        return if self.deleted_points.read().contains(&point_id) {
+            self.write_segment.get().read().sparse_vectors(point_id)
+        } else {
+            {
+                let write_segment = self.write_segment.get();
+                let segment_guard = write_segment.read();
+                if segment_guard.has_point(point_id) {
+                    return segment_guard.sparse_vectors(point_id);
+                }
+            }
+            self.wrapped_segment.get().read().sparse_vectors(point_id)
+        };
+    }
+
     fn payload(&self, point_id: PointIdType) -> OperationResult<Payload> {
         return if self.deleted_points.read().contains(&point_id) {
             self.write_segment.get().read().payload(point_id)
@@ -421,6 +525,36 @@ impl SegmentEntry for ProxySegment {
         };
     }
 
+    fn point_metadata(&self, point_id: PointIdType) -> OperationResult<PointMetadata> {
+        return if self.deleted_points.read().contains(&point_id) {
+            self.write_segment.get().read().point_metadata(point_id)
+        } else {
+            {
+                let write_segment = self.write_segment.get();
+                let segment_guard = write_segment.read();
+                if segment_guard.has_point(point_id) {
+                    return segment_guard.point_metadata(point_id);
+                }
+            }
+            self.wrapped_segment.get().read().point_metadata(point_id)
+        };
+    }
+
+    fn raw_payload(&self, point_id: PointIdType) -> OperationResult<RecordPayload> {
+        return if self.deleted_points.read().contains(&point_id) {
+            self.write_segment.get().read().raw_payload(point_id)
+        } else {
+            {
+                let write_segment = self.write_segment.get();
+                let segment_guard = write_segment.read();
+                if segment_guard.has_point(point_id) {
+                    return segment_guard.raw_payload(point_id);
+                }
+            }
+            self.wrapped_segment.get().read().raw_payload(point_id)
+        };
+    }
+
     /// Not implemented for proxy
     fn iter_points(&self) -> Box<dyn Iterator<Item = PointIdType> + '_> {
         // iter_points is not available for Proxy implementation
@@ -433,29 +567,64 @@ impl SegmentEntry for ProxySegment {
         offset: Option<PointIdType>,
         limit: Option<usize>,
         filter: Option<&'a Filter>,
+        planner_hint: Option<PlannerHint>,
     ) -> Vec<PointIdType> {
         let deleted_points = self.deleted_points.read();
         let mut read_points = if deleted_points.is_empty() {
             self.wrapped_segment
                 .get()
                 .read()
-                .read_filtered(offset, limit, filter)
+                .read_filtered(offset, limit, filter, planner_hint)
         } else {
             let wrapped_filter =
                 self.add_deleted_points_condition_to_filter(filter, &deleted_points);
+            self.wrapped_segment.get().read().read_filtered(
+                offset,
+                limit,
+                Some(&wrapped_filter),
+                planner_hint,
+            )
+        };
+        let mut write_segment_points =
+            self.write_segment
+                .get()
+                .read()
+                .read_filtered(offset, limit, filter, planner_hint);
+        read_points.append(&mut write_segment_points);
+        read_points.sort_unstable();
+        read_points
+    }
+
+    fn sample_filtered<'a>(
+        &'a self,
+        limit: usize,
+        filter: Option<&'a Filter>,
+        seed: u64,
+    ) -> Vec<PointIdType> {
+        let deleted_points = self.deleted_points.read();
+        let mut sampled_points = if deleted_points.is_empty() {
             self.wrapped_segment
                 .get()
                 .read()
-                .read_filtered(offset, limit, Some(&wrapped_filter))
+                .sample_filtered(limit, filter, seed)
+        } else {
+            let wrapped_filter =
+                self.add_deleted_points_condition_to_filter(filter, &deleted_points);
+            self.wrapped_segment
+                .get()
+                .read()
+                .sample_filtered(limit, Some(&wrapped_filter), seed)
         };
         let mut write_segment_points = self
             .write_segment
             .get()
             .read()
-            .read_filtered(offset, limit, filter);
-        read_points.append(&mut write_segment_points);
-        read_points.sort_unstable();
-        read_points
+            .sample_filtered(limit, filter, seed);
+        sampled_points.append(&mut write_segment_points);
+        sampled_points.sort_unstable_by_key(|point_id| (sampling_key(seed, *point_id), *point_id));
+        sampled_points.dedup();
+        sampled_points.truncate(limit);
+        sampled_points
     }
 
     /// Read points in [from; to) range
@@ -491,6 +660,219 @@ impl SegmentEntry for ProxySegment {
         count
     }
 
+    fn term_counts<'a>(
+        &'a self,
+        key: PayloadKeyTypeRef,
+        filter: Option<&'a Filter>,
+    ) -> Option<HashMap<String, usize>> {
+        let deleted_points = self.deleted_points.read();
+        let wrapped_counts = if deleted_points.is_empty() {
+            self.wrapped_segment.get().read().term_counts(key, filter)
+        } else {
+            let wrapped_filter =
+                self.add_deleted_points_condition_to_filter(filter, &deleted_points);
+            self.wrapped_segment
+                .get()
+                .read()
+                .term_counts(key, Some(&wrapped_filter))
+        };
+        let write_segment_counts = self.write_segment.get().read().term_counts(key, filter);
+
+        match (wrapped_counts, write_segment_counts) {
+            (Some(mut counts), Some(write_segment_counts)) => {
+                for (term, count) in write_segment_counts {
+                    *counts.entry(term).or_default() += count;
+                }
+                Some(counts)
+            }
+            (counts, None) | (None, counts) => counts,
+        }
+    }
+
+    fn distinct_values<'a>(
+        &'a self,
+        key: PayloadKeyTypeRef,
+        filter: Option<&'a Filter>,
+    ) -> Option<DistinctValues> {
+        let deleted_points = self.deleted_points.read();
+        let wrapped_values = if deleted_points.is_empty() {
+            self.wrapped_segment
+                .get()
+                .read()
+                .distinct_values(key, filter)
+        } else {
+            let wrapped_filter =
+                self.add_deleted_points_condition_to_filter(filter, &deleted_points);
+            self.wrapped_segment
+                .get()
+                .read()
+                .distinct_values(key, Some(&wrapped_filter))
+        };
+        let write_segment_values = self.write_segment.get().read().distinct_values(key, filter);
+
+        match (wrapped_values, write_segment_values) {
+            (Some(mut values), Some(write_segment_values)) => {
+                values.merge(write_segment_values);
+                Some(values)
+            }
+            (values, None) | (None, values) => values,
+        }
+    }
+
+    fn numeric_bounds<'a>(
+        &'a self,
+        key: PayloadKeyTypeRef,
+        filter: Option<&'a Filter>,
+    ) -> Option<Option<ValueBounds>> {
+        let deleted_points = self.deleted_points.read();
+        let wrapped_bounds = if deleted_points.is_empty() {
+            self.wrapped_segment
+                .get()
+                .read()
+                .numeric_bounds(key, filter)
+        } else {
+            let wrapped_filter =
+                self.add_deleted_points_condition_to_filter(filter, &deleted_points);
+            self.wrapped_segment
+                .get()
+                .read()
+                .numeric_bounds(key, Some(&wrapped_filter))
+        };
+        let write_segment_bounds = self.write_segment.get().read().numeric_bounds(key, filter);
+
+        match (wrapped_bounds, write_segment_bounds) {
+            (Some(bounds), Some(write_segment_bounds)) => {
+                Some(ValueBounds::merge_opt(bounds, write_segment_bounds))
+            }
+            (bounds, None) | (None, bounds) => bounds,
+        }
+    }
+
+    fn numeric_histogram<'a>(
+        &'a self,
+        key: PayloadKeyTypeRef,
+        buckets: &HistogramBuckets,
+        filter: Option<&'a Filter>,
+    ) -> Option<Vec<usize>> {
+        let deleted_points = self.deleted_points.read();
+        let wrapped_counts = if deleted_points.is_empty() {
+            self.wrapped_segment
+                .get()
+                .read()
+                .numeric_histogram(key, buckets, filter)
+        } else {
+            let wrapped_filter =
+                self.add_deleted_points_condition_to_filter(filter, &deleted_points);
+            self.wrapped_segment
+                .get()
+                .read()
+                .numeric_histogram(key, buckets, Some(&wrapped_filter))
+        };
+        let write_segment_counts = self
+            .write_segment
+            .get()
+            .read()
+            .numeric_histogram(key, buckets, filter);
+
+        match (wrapped_counts, write_segment_counts) {
+            (Some(mut counts), Some(write_segment_counts)) => {
+                for (count, write_segment_count) in counts.iter_mut().zip(write_segment_counts) {
+                    *count += write_segment_count;
+                }
+                Some(counts)
+            }
+            (counts, None) | (None, counts) => counts,
+        }
+    }
+
+    fn nearest_points<'a>(
+        &'a self,
+        key: PayloadKeyTypeRef,
+        origin: &GeoPoint,
+        limit: usize,
+        filter: Option<&'a Filter>,
+    ) -> Option<Vec<(PointIdType, f64)>> {
+        let deleted_points = self.deleted_points.read();
+        let wrapped_points = if deleted_points.is_empty() {
+            self.wrapped_segment
+                .get()
+                .read()
+                .nearest_points(key, origin, limit, filter)
+        } else {
+            let wrapped_filter =
+                self.add_deleted_points_condition_to_filter(filter, &deleted_points);
+            self.wrapped_segment.get().read().nearest_points(
+                key,
+                origin,
+                limit,
+                Some(&wrapped_filter),
+            )
+        };
+        let write_segment_points = self
+            .write_segment
+            .get()
+            .read()
+            .nearest_points(key, origin, limit, filter);
+
+        match (wrapped_points, write_segment_points) {
+            (Some(mut points), Some(write_segment_points)) => {
+                points.extend(write_segment_points);
+                points.sort_unstable_by(|(id_a, distance_a), (id_b, distance_b)| {
+                    distance_a.total_cmp(distance_b).then(id_a.cmp(id_b))
+                });
+                points.truncate(limit);
+                Some(points)
+            }
+            (points, None) | (None, points) => points,
+        }
+    }
+
+    fn ordered_points<'a>(
+        &'a self,
+        key: PayloadKeyTypeRef,
+        direction: Direction,
+        start_from: Option<f64>,
+        offset: Option<PointIdType>,
+        limit: usize,
+        filter: Option<&'a Filter>,
+    ) -> Option<Vec<(PointIdType, f64)>> {
+        let deleted_points = self.deleted_points.read();
+        let wrapped_points = if deleted_points.is_empty() {
+            self.wrapped_segment
+                .get()
+                .read()
+                .ordered_points(key, direction, start_from, offset, limit, filter)
+        } else {
+            let wrapped_filter =
+                self.add_deleted_points_condition_to_filter(filter, &deleted_points);
+            self.wrapped_segment.get().read().ordered_points(
+                key,
+                direction,
+                start_from,
+                offset,
+                limit,
+                Some(&wrapped_filter),
+            )
+        };
+        let write_segment_points = self
+            .write_segment
+            .get()
+            .read()
+            .ordered_points(key, direction, start_from, offset, limit, filter);
+
+        match (wrapped_points, write_segment_points) {
+            (Some(mut points), Some(write_segment_points)) => {
+                points.extend(write_segment_points);
+                points.sort_unstable_by(|(id_a, value_a), (id_b, value_b)| {
+                    direction.cmp(*value_a, *value_b).then(id_a.cmp(id_b))
+                });
+                points.truncate(limit);
+                Some(points)
+            }
+            (points, None) | (None, points) => points,
+        }
+    }
+
     fn estimate_points_count<'a>(&'a self, filter: Option<&'a Filter>) -> CardinalityEstimation {
         let deleted_points_count = self.deleted_points.read().len();
 
@@ -671,7 +1053,7 @@ impl SegmentEntry for ProxySegment {
             self.wrapped_segment
                 .get()
                 .read()
-                .read_filtered(None, None, Some(filter));
+                .read_filtered(None, None, Some(filter), None);
         if !points_to_delete.is_empty() {
             deleted_points += points_to_delete.len();
             let mut deleted_points_guard = self.deleted_points.write();
@@ -722,6 +1104,23 @@ impl SegmentEntry for ProxySegment {
     fn get_telemetry_data(&self) -> SegmentTelemetry {
         self.wrapped_segment.get().read().get_telemetry_data()
     }
+
+    fn memory_usage(&self) -> MemoryUsage {
+        // Write segment is shared by all proxies of the optimization, it is not accounted
+        // to avoid counting it multiple times
+        self.wrapped_segment.get().read().memory_usage()
+    }
+
+    fn benchmark_scoring(
+        &self,
+        vector_name: &str,
+        points_limit: usize,
+    ) -> OperationResult<Vec<ScoringMeasurement>> {
+        self.wrapped_segment
+            .get()
+            .read()
+            .benchmark_scoring(vector_name, points_limit)
+    }
 }
 
 #[cfg(test)]
@@ -859,6 +1258,7 @@ mod tests {
                 None,
                 10,
                 None,
+                &false.into(),
             )
             .unwrap();
 
@@ -912,6 +1312,7 @@ mod tests {
                 None,
                 10,
                 None,
+                &false.into(),
             )
             .unwrap();
 
@@ -976,6 +1377,7 @@ mod tests {
                 None,
                 10,
                 None,
+                &false.into(),
             )
             .unwrap();
 
@@ -1012,23 +1414,24 @@ mod tests {
             "blue".to_string().into(),
         )));
 
-        let original_points = original_segment
-            .get()
-            .read()
-            .read_filtered(None, Some(100), None);
+        let original_points =
+            original_segment
+                .get()
+                .read()
+                .read_filtered(None, Some(100), None, None);
 
         let original_points_filtered =
             original_segment
                 .get()
                 .read()
-                .read_filtered(None, Some(100), Some(&filter));
+                .read_filtered(None, Some(100), Some(&filter), None);
 
         let mut proxy_segment = wrap_proxy(&dir, original_segment);
 
         proxy_segment.delete_point(100, 2.into()).unwrap();
 
-        let proxy_res = proxy_segment.read_filtered(None, Some(100), None);
-        let proxy_res_filtered = proxy_segment.read_filtered(None, Some(100), Some(&filter));
+        let proxy_res = proxy_segment.read_filtered(None, Some(100), None, None);
+        let proxy_res_filtered = proxy_segment.read_filtered(None, Some(100), Some(&filter), None);
 
         assert_eq!(original_points_filtered.len() - 1, proxy_res_filtered.len());
         assert_eq!(original_points.len() - 1, proxy_res.len());
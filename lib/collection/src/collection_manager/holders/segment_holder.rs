@@ -220,6 +220,35 @@ impl<'s> SegmentHolder {
             .and_then(|idx| self.segments.get(idx).cloned())
     }
 
+    /// Appendable segments, grouped by the set of vectors they store.
+    ///
+    /// Segments, created before a new vector was added to the collection, do not store it.
+    pub fn appendable_segments_by_vectors(&self) -> HashMap<BTreeSet<String>, Vec<SegmentId>> {
+        let mut segments_by_vectors: HashMap<_, Vec<_>> = HashMap::new();
+        for (idx, segment) in &self.segments {
+            let segment_arc = segment.get();
+            let read_segment = segment_arc.read();
+            if read_segment.is_appendable() {
+                segments_by_vectors
+                    .entry(read_segment.config().vector_data.into_keys().collect())
+                    .or_default()
+                    .push(*idx);
+            }
+        }
+        segments_by_vectors
+    }
+
+    /// Random appendable segment, which stores exactly the given set of vectors
+    pub fn random_appendable_segment_with_vectors(
+        &self,
+        vector_names: &BTreeSet<String>,
+    ) -> Option<LockedSegment> {
+        self.appendable_segments_by_vectors()
+            .get(vector_names)
+            .and_then(|segment_ids| segment_ids.choose(&mut rand::thread_rng()))
+            .and_then(|idx| self.segments.get(idx).cloned())
+    }
+
     /// Selects point ids, which is stored in this segment
     fn segment_points(&self, ids: &[PointIdType], segment: &dyn SegmentEntry) -> Vec<PointIdType> {
         ids.iter()
@@ -338,8 +367,9 @@ impl<'s> SegmentHolder {
     where
         F: FnMut(PointIdType, &mut RwLockWriteGuard<dyn SegmentEntry>) -> OperationResult<bool>,
     {
-        // Choose random appendable segment from this
+        // Choose random appendable segment from this, preferably storing the same vectors
         let appendable_segments = self.appendable_segments();
+        let appendable_segments_by_vectors = self.appendable_segments_by_vectors();
 
         let mut applied_points: HashSet<PointIdType> = Default::default();
 
@@ -354,14 +384,26 @@ impl<'s> SegmentHolder {
             let is_applied = if write_segment.is_appendable() {
                 f(point_id, write_segment)?
             } else {
+                let vector_names: BTreeSet<_> =
+                    write_segment.config().vector_data.into_keys().collect();
                 self.aloha_random_write(
-                    &appendable_segments,
+                    appendable_segments_by_vectors
+                        .get(&vector_names)
+                        .unwrap_or(&appendable_segments),
                     |_appendable_idx, appendable_write_segment| {
                         let all_vectors = write_segment.all_vectors(point_id)?;
+                        let sparse_vectors = write_segment.sparse_vectors(point_id)?;
                         let payload = write_segment.payload(point_id)?;
+                        let metadata = write_segment.point_metadata(point_id)?;
 
                         appendable_write_segment.upsert_vector(op_num, point_id, &all_vectors)?;
+                        appendable_write_segment.update_sparse_vectors(
+                            op_num,
+                            point_id,
+                            &sparse_vectors,
+                        )?;
                         appendable_write_segment.set_full_payload(op_num, point_id, &payload)?;
+                        appendable_write_segment.set_point_metadata(op_num, point_id, &metadata)?;
 
                         write_segment.delete_point(op_num, point_id)?;
 
@@ -85,6 +85,10 @@ impl IndexingOptimizer {
 
                 let segment_config = read_segment.config();
 
+                if segment_config.has_quantized_only_vectors() {
+                    return None; // Original vectors are removed, segment can't be rebuilt
+                }
+
                 let is_vector_indexed = match segment_config.index {
                     Indexes::Plain {} => false,
                     Indexes::Hnsw(_) => true,
@@ -137,6 +141,10 @@ impl IndexingOptimizer {
                     return None; // Never optimize already optimized segment
                 }
 
+                if segment_config.has_quantized_only_vectors() {
+                    return None; // Original vectors are removed, segment can't be rebuilt
+                }
+
                 // Apply indexing to plain segments which have grown too big
                 let is_vector_indexed = match segment_config.index {
                     Indexes::Plain {} => false,
@@ -159,8 +167,14 @@ impl IndexingOptimizer {
                         .indexing_threshold
                         .saturating_mul(BYTES_IN_KB);
 
-                let require_indexing =
-                    (big_for_mmap && !is_memmaped) || (big_for_index && !is_vector_indexed);
+                // Quantization is enabled after the segment was indexed
+                let require_quantization = is_vector_indexed
+                    && self.quantization_config.is_some()
+                    && segment_config.quantization_config.is_none();
+
+                let require_indexing = (big_for_mmap && !is_memmaped)
+                    || (big_for_index && !is_vector_indexed)
+                    || require_quantization;
 
                 match require_indexing {
                     true => Some((*idx, vector_size)),
@@ -226,7 +240,7 @@ impl SegmentOptimizer for IndexingOptimizer {
     }
 
     fn hnsw_config(&self) -> HnswConfig {
-        self.hnsw_config
+        self.hnsw_config.clone()
     }
 
     fn quantization_config(&self) -> Option<QuantizationConfig> {
@@ -319,6 +333,7 @@ mod tests {
                         distance: params.distance,
                         hnsw_config: None,
                         quantization_config: None,
+                        projection: None,
                     },
                 )
             })
@@ -338,6 +353,15 @@ mod tests {
                 replication_factor: NonZeroU32::new(1).unwrap(),
                 write_consistency_factor: NonZeroU32::new(1).unwrap(),
                 on_disk_payload: false,
+                vector_aliases: Default::default(),
+                default_vector: None,
+                defrag_key: None,
+                deduplicate_upserts: false,
+                max_concurrent_searches: 0,
+                acl_key: None,
+                default_write_ordering: None,
+                default_read_consistency: None,
+                sparse_vectors: Default::default(),
             },
             Default::default(),
             Default::default(),
@@ -436,11 +460,21 @@ mod tests {
                     distance: segment_config.vector_data[DEFAULT_VECTOR_NAME].distance,
                     hnsw_config: None,
                     quantization_config: None,
+                    projection: None,
                 }),
                 shard_number: NonZeroU32::new(1).unwrap(),
                 replication_factor: NonZeroU32::new(1).unwrap(),
                 write_consistency_factor: NonZeroU32::new(1).unwrap(),
                 on_disk_payload: false,
+                vector_aliases: Default::default(),
+                default_vector: None,
+                defrag_key: None,
+                deduplicate_upserts: false,
+                max_concurrent_searches: 0,
+                acl_key: None,
+                default_write_ordering: None,
+                default_read_consistency: None,
+                sparse_vectors: Default::default(),
             },
             Default::default(),
             Default::default(),
@@ -1,4 +1,4 @@
-use std::collections::HashSet;
+use std::collections::{BTreeSet, HashSet};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
@@ -72,7 +72,7 @@ impl SegmentOptimizer for MergeOptimizer {
     }
 
     fn hnsw_config(&self) -> HnswConfig {
-        self.hnsw_config
+        self.hnsw_config.clone()
     }
 
     fn quantization_config(&self) -> Option<QuantizationConfig> {
@@ -105,13 +105,16 @@ impl SegmentOptimizer for MergeOptimizer {
         // Find at least top-3 smallest segments to join.
         // We need 3 segments because in this case we can guarantee that total segments number will be less
 
-        let candidates: Vec<_> = raw_segments
+        let sorted_segments: Vec<_> = raw_segments
             .iter()
             .cloned()
             .filter_map(|(idx, segment)| {
                 let segment_entry = segment.get();
                 let read_segment = segment_entry.read();
-                match read_segment.segment_type() != SegmentType::Special {
+                // Segments without original vectors can't be merged
+                let can_merge = read_segment.segment_type() != SegmentType::Special
+                    && !read_segment.config().has_quantized_only_vectors();
+                match can_merge {
                     true => Some((
                         *idx,
                         read_segment.points_count()
@@ -122,11 +125,35 @@ impl SegmentOptimizer for MergeOptimizer {
                                 .copied()
                                 .unwrap_or(0)
                             * VECTOR_ELEMENT_SIZE,
+                        read_segment
+                            .config()
+                            .vector_data
+                            .into_keys()
+                            .collect::<BTreeSet<_>>(),
                     )),
                     false => None,
                 }
             })
-            .sorted_by_key(|(_, size)| *size)
+            .sorted_by_key(|(_, size, _)| *size)
+            .collect();
+
+        // Only segments, which store the same set of vectors, can be merged.
+        // Prefer the most common set, segments with other sets are merged later.
+        let merge_vectors = match sorted_segments
+            .iter()
+            .map(|(_, _, vector_names)| vector_names)
+            .counts()
+            .into_iter()
+            .max_by_key(|(_, count)| *count)
+        {
+            Some((vector_names, _)) => vector_names.clone(),
+            None => return vec![],
+        };
+
+        let candidates: Vec<_> = sorted_segments
+            .into_iter()
+            .filter(|(_, _, vector_names)| *vector_names == merge_vectors)
+            .map(|(sid, size, _)| (sid, size))
             .scan(0, |size_sum, (sid, size)| {
                 *size_sum += size; // produce a cumulative sum of segment sizes starting from smallest
                 Some((sid, *size_sum))
@@ -1,4 +1,4 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::path::Path;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
@@ -14,7 +14,8 @@ use segment::segment_constructor::build_segment;
 use segment::segment_constructor::segment_builder::SegmentBuilder;
 use segment::types::{
     HnswConfig, Indexes, PayloadFieldSchema, PayloadKeyType, PayloadStorageType, PointIdType,
-    QuantizationConfig, SegmentConfig, StorageType, VECTOR_ELEMENT_SIZE,
+    QuantizationConfig, SegmentConfig, SparseVectorDataConfig, StorageType, VectorDataConfig,
+    VECTOR_ELEMENT_SIZE,
 };
 
 use crate::collection_manager::holders::proxy_segment::ProxySegment;
@@ -71,12 +72,48 @@ pub trait SegmentOptimizer {
 
     fn get_telemetry_counter(&self) -> Arc<Mutex<OperationDurationsAggregator>>;
 
+    /// Get params of the vectors, stored in optimizing segments
+    ///
+    /// Segments, created before a vector was added to the collection, do not store it.
+    /// Optimized segment keeps the same set of vectors as the original ones.
+    fn segments_vector_params(
+        &self,
+        optimizing_segments: &[LockedSegment],
+    ) -> CollectionResult<HashMap<String, VectorDataConfig>> {
+        let mut vector_params = self
+            .collection_params()
+            .get_all_vector_params(&self.hnsw_config(), self.quantization_config().as_ref())?;
+        if let Some(segment) = optimizing_segments.first() {
+            let segment_config = segment.get().read().config();
+            vector_params
+                .retain(|vector_name, _| segment_config.vector_data.contains_key(vector_name));
+        }
+        Ok(vector_params)
+    }
+
+    /// Get params of the sparse vectors, stored in optimizing segments
+    fn segments_sparse_vector_params(
+        &self,
+        optimizing_segments: &[LockedSegment],
+    ) -> HashMap<String, SparseVectorDataConfig> {
+        let mut sparse_vector_params = self.collection_params().get_all_sparse_vector_params();
+        if let Some(segment) = optimizing_segments.first() {
+            let segment_config = segment.get().read().config();
+            sparse_vector_params.retain(|vector_name, _| {
+                segment_config.sparse_vector_data.contains_key(vector_name)
+            });
+        }
+        sparse_vector_params
+    }
+
     /// Build temp segment
-    fn temp_segment(&self) -> CollectionResult<LockedSegment> {
+    fn temp_segment(
+        &self,
+        optimizing_segments: &[LockedSegment],
+    ) -> CollectionResult<LockedSegment> {
         let collection_params = self.collection_params();
         let config = SegmentConfig {
-            vector_data: collection_params
-                .get_all_vector_params(&self.hnsw_config(), self.quantization_config().as_ref())?,
+            vector_data: self.segments_vector_params(optimizing_segments)?,
             index: Indexes::Plain {},
             storage_type: StorageType::InMemory,
             payload_storage_type: match collection_params.on_disk_payload {
@@ -84,6 +121,7 @@ pub trait SegmentOptimizer {
                 false => PayloadStorageType::InMemory,
             },
             quantization_config: None,
+            sparse_vector_data: self.segments_sparse_vector_params(optimizing_segments),
         };
         Ok(LockedSegment::new(build_segment(
             self.collection_path(),
@@ -122,8 +160,7 @@ pub trait SegmentOptimizer {
             total_vectors_size >= thresholds.memmap_threshold.saturating_mul(BYTES_IN_KB);
 
         let optimized_config = SegmentConfig {
-            vector_data: collection_params
-                .get_all_vector_params(&self.hnsw_config(), self.quantization_config().as_ref())?,
+            vector_data: self.segments_vector_params(optimizing_segments)?,
             index: if is_indexed {
                 Indexes::Hnsw(self.hnsw_config())
             } else {
@@ -144,13 +181,13 @@ pub trait SegmentOptimizer {
             } else {
                 Default::default()
             },
+            sparse_vector_data: self.segments_sparse_vector_params(optimizing_segments),
         };
 
-        Ok(SegmentBuilder::new(
-            self.collection_path(),
-            self.temp_path(),
-            &optimized_config,
-        )?)
+        let mut segment_builder =
+            SegmentBuilder::new(self.collection_path(), self.temp_path(), &optimized_config)?;
+        segment_builder.defrag_key = collection_params.defrag_key.clone();
+        Ok(segment_builder)
     }
 
     /// Restores original segments from proxies
@@ -256,15 +293,17 @@ pub trait SegmentOptimizer {
 
         self.check_cancellation(stopped)?;
 
-        for segment in optimizing_segments {
-            match segment {
-                LockedSegment::Original(segment_arc) => {
-                    let segment_guard = segment_arc.read();
-                    segment_builder.update_from(&segment_guard, stopped)?;
-                }
+        // All segments are added at once, so points are grouped by `defrag_key` across segments
+        let segment_guards: Vec<_> = optimizing_segments
+            .iter()
+            .map(|segment| match segment {
+                LockedSegment::Original(segment_arc) => segment_arc.read(),
                 LockedSegment::Proxy(_) => panic!("Attempt to optimize segment which is already currently under optimization. Should never happen"),
-            }
-        }
+            })
+            .collect();
+        let segments: Vec<&Segment> = segment_guards.iter().map(|guard| &**guard).collect();
+        segment_builder.update_from_segments(&segments, stopped)?;
+        drop(segment_guards);
 
         for field in proxy_deleted_indexes.read().iter() {
             segment_builder.indexed_fields.remove(field);
@@ -359,6 +398,24 @@ pub trait SegmentOptimizer {
                 .iter()
                 .all(|s| matches!(s, LockedSegment::Original(_)));
 
+        // Segments with different sets of vectors can't be merged
+        let vector_names: BTreeSet<_> = match optimizing_segments.first() {
+            Some(segment) => segment
+                .get()
+                .read()
+                .config()
+                .vector_data
+                .into_keys()
+                .collect(),
+            None => BTreeSet::new(),
+        };
+        let all_segments_ok = all_segments_ok
+            && optimizing_segments.iter().all(|s| {
+                let segment_vectors: BTreeSet<_> =
+                    s.get().read().config().vector_data.into_keys().collect();
+                segment_vectors == vector_names
+            });
+
         if !all_segments_ok {
             // Cancel the optimization
             return Ok(false);
@@ -366,7 +423,7 @@ pub trait SegmentOptimizer {
 
         check_process_stopped(stopped)?;
 
-        let tmp_segment = self.temp_segment()?;
+        let tmp_segment = self.temp_segment(&optimizing_segments)?;
 
         let proxy_deleted_points = Arc::new(RwLock::new(HashSet::<PointIdType>::new()));
         let proxy_deleted_indexes = Arc::new(RwLock::new(HashSet::<PayloadKeyType>::new()));
@@ -475,8 +532,9 @@ pub trait SegmentOptimizer {
 
             let (_, proxies) = write_segments_guard.swap(optimized_segment, &proxy_ids);
 
-            let has_appendable_segments =
-                write_segments_guard.random_appendable_segment().is_some();
+            let has_appendable_segments = write_segments_guard
+                .random_appendable_segment_with_vectors(&vector_names)
+                .is_some();
 
             // Release reference counter of the optimized segments
             drop(optimizing_segments);
@@ -79,8 +79,10 @@ impl VacuumOptimizer {
                 let is_big = read_segment.points_count() >= self.min_vectors_number;
                 let is_not_special = read_segment.segment_type() != SegmentType::Special;
                 let is_littered = littered_ratio > self.deleted_threshold;
+                // Original vectors are removed, segment can't be rebuilt
+                let has_originals = !read_segment.config().has_quantized_only_vectors();
 
-                match is_big && is_not_special && is_littered {
+                match is_big && is_not_special && is_littered && has_originals {
                     true => Some((*idx, littered_ratio)),
                     false => None,
                 }
@@ -104,7 +106,7 @@ impl SegmentOptimizer for VacuumOptimizer {
     }
 
     fn hnsw_config(&self) -> HnswConfig {
-        self.hnsw_config
+        self.hnsw_config.clone()
     }
 
     fn quantization_config(&self) -> Option<QuantizationConfig> {
@@ -228,11 +230,21 @@ mod tests {
                     distance: Distance::Dot,
                     hnsw_config: None,
                     quantization_config: None,
+                    projection: None,
                 }),
                 shard_number: NonZeroU32::new(1).unwrap(),
                 on_disk_payload: false,
                 replication_factor: NonZeroU32::new(1).unwrap(),
                 write_consistency_factor: NonZeroU32::new(1).unwrap(),
+                vector_aliases: Default::default(),
+                default_vector: None,
+                defrag_key: None,
+                deduplicate_upserts: false,
+                max_concurrent_searches: 0,
+                acl_key: None,
+                default_write_ordering: None,
+                default_read_consistency: None,
+                sparse_vectors: Default::default(),
             },
             Default::default(),
             Default::default(),
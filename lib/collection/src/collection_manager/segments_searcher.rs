@@ -1,15 +1,17 @@
 use std::collections::HashMap;
+use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
 
 use futures::future::try_join_all;
 use ordered_float::Float;
 use parking_lot::RwLock;
+use segment::common::log_context::in_current_log_context;
 use segment::data_types::named_vectors::NamedVectors;
-use segment::data_types::vectors::VectorElementType;
-use segment::entry::entry_point::OperationError;
+use segment::data_types::vectors::{VectorElementType, VectorStruct};
+use segment::entry::entry_point::{OperationError, OperationResult, SegmentEntry};
 use segment::types::{
     Filter, Indexes, PointIdType, ScoreType, ScoredPoint, SearchParams, SegmentConfig,
-    SeqNumberType, WithPayload, WithPayloadInterface, WithVector,
+    SeqNumberType, VectorSlice, WithPayload, WithPayloadInterface, WithVector,
 };
 use tokio::runtime::Handle;
 use tokio::task::JoinHandle;
@@ -17,6 +19,7 @@ use tokio::task::JoinHandle;
 use crate::collection_manager::holders::segment_holder::{LockedSegment, SegmentHolder};
 use crate::collection_manager::probabilistic_segment_search_sampling::find_search_sampling_over_point_distribution;
 use crate::collection_manager::search_result_aggregator::BatchResultAggregator;
+use crate::common::stopping_guard::StoppingGuard;
 use crate::operations::types::{CollectionResult, Record, SearchRequestBatch};
 
 type BatchOffset = usize;
@@ -149,6 +152,10 @@ impl SegmentsSearcher {
         runtime_handle: &Handle,
         sampling_enabled: bool,
     ) -> CollectionResult<Vec<Vec<ScoredPoint>>> {
+        // Searches in segments are spawned, so they would keep running if the search is dropped,
+        // e.g. on timeout. The guard stops them along with the search.
+        let stopping_guard = StoppingGuard::new();
+
         // Using { } block to ensure segments variable is dropped in the end of it
         // and is not transferred across the all_searches.await? boundary as it
         // does not impl Send trait
@@ -184,10 +191,11 @@ impl SegmentsSearcher {
                             batch_request.clone(),
                             total_points_segments,
                             use_sampling,
+                            stopping_guard.get_is_stopped(),
                         ),
                     )
                 })
-                .map(|(segment, f)| (segment, runtime_handle.spawn(f)))
+                .map(|(segment, f)| (segment, runtime_handle.spawn(in_current_log_context(f))))
                 .unzip()
         };
         // perform search on all segments concurrently
@@ -224,8 +232,14 @@ impl SegmentsSearcher {
                             .collect(),
                     });
 
-                    let search = search_in_segment(segment, partial_batch_request, 0, false);
-                    res.push(runtime_handle.spawn(search))
+                    let search = search_in_segment(
+                        segment,
+                        partial_batch_request,
+                        0,
+                        false,
+                        stopping_guard.get_is_stopped(),
+                    );
+                    res.push(runtime_handle.spawn(in_current_log_context(search)))
                 }
                 res
             };
@@ -271,29 +285,15 @@ impl SegmentsSearcher {
                     id,
                     Record {
                         id,
-                        payload: if with_payload.enable {
-                            if let Some(selector) = &with_payload.payload_selector {
-                                Some(selector.process(segment.payload(id)?))
-                            } else {
-                                Some(segment.payload(id)?)
-                            }
-                        } else {
+                        payload: if !with_payload.enable {
                             None
+                        } else if with_payload.is_unchanged() {
+                            Some(segment.raw_payload(id)?)
+                        } else {
+                            Some(with_payload.process(segment.payload(id)?).into())
                         },
-                        vector: match with_vector {
-                            WithVector::Bool(true) => Some(segment.all_vectors(id)?.into()),
-                            WithVector::Bool(false) => None,
-                            WithVector::Selector(vector_names) => {
-                                let mut selected_vectors = NamedVectors::default();
-                                for vector_name in vector_names {
-                                    selected_vectors.insert(
-                                        vector_name.clone(),
-                                        segment.vector(vector_name, id)?,
-                                    );
-                                }
-                                Some(selected_vectors.into())
-                            }
-                        },
+                        vector: retrieve_vectors(&**segment, with_vector, id)?,
+                        geo_distance: None,
                     },
                 );
                 point_version.insert(id, version);
@@ -348,6 +348,7 @@ fn sampling_limit(
 /// * `request` - Batch of search requests
 /// * `total_points` - Number of points in all segments combined
 /// * `use_sampling` - If true, try to use probabilistic sampling
+/// * `is_stopped` - Flag to interrupt the search
 ///
 /// # Returns
 ///
@@ -359,6 +360,7 @@ async fn search_in_segment(
     request: Arc<SearchRequestBatch>,
     total_points: usize,
     use_sampling: bool,
+    is_stopped: Arc<AtomicBool>,
 ) -> CollectionResult<(Vec<Vec<ScoredPoint>>, Vec<bool>)> {
     let batch_size = request.searches.len();
 
@@ -366,6 +368,7 @@ async fn search_in_segment(
     let mut further_results: Vec<bool> = Vec::with_capacity(batch_size); // true if segment have more points to return
     let mut vectors_batch: Vec<&[VectorElementType]> = vec![];
     let mut prev_params = BatchSearchParams::default();
+    let segment_config = segment.get().read().config();
 
     for search_query in &request.searches {
         let with_payload_interface = search_query
@@ -377,7 +380,10 @@ async fn search_in_segment(
             vector_name: search_query.vector.get_name(),
             filter: search_query.filter.as_ref(),
             with_payload: WithPayload::from(with_payload_interface),
-            with_vector: search_query.with_vector.clone().unwrap_or_default(),
+            with_vector: segment_with_vector(
+                &search_query.with_vector.clone().unwrap_or_default(),
+                &segment_config,
+            ),
             top: search_query.limit + search_query.offset,
             params: search_query.params.as_ref(),
         };
@@ -401,15 +407,24 @@ async fn search_in_segment(
                     prev_params.top
                 };
 
-                let mut res = read_segment.search_batch(
-                    prev_params.vector_name,
-                    &vectors_batch,
-                    &prev_params.with_payload,
-                    &prev_params.with_vector,
-                    prev_params.filter,
-                    top,
-                    prev_params.params,
-                )?;
+                let mut res = if segment_config
+                    .vector_data
+                    .contains_key(prev_params.vector_name)
+                {
+                    read_segment.search_batch(
+                        prev_params.vector_name,
+                        &vectors_batch,
+                        &prev_params.with_payload,
+                        &prev_params.with_vector,
+                        prev_params.filter,
+                        top,
+                        prev_params.params,
+                        &is_stopped,
+                    )?
+                } else {
+                    // Segment was created before the vector was added to the collection
+                    vec![vec![]; vectors_batch.len()]
+                };
                 for batch_result in &res {
                     further_results.push(batch_result.len() == top);
                 }
@@ -437,15 +452,24 @@ async fn search_in_segment(
         } else {
             prev_params.top
         };
-        let mut res = read_segment.search_batch(
-            prev_params.vector_name,
-            &vectors_batch,
-            &prev_params.with_payload,
-            &prev_params.with_vector,
-            prev_params.filter,
-            top,
-            prev_params.params,
-        )?;
+        let mut res = if segment_config
+            .vector_data
+            .contains_key(prev_params.vector_name)
+        {
+            read_segment.search_batch(
+                prev_params.vector_name,
+                &vectors_batch,
+                &prev_params.with_payload,
+                &prev_params.with_vector,
+                prev_params.filter,
+                top,
+                prev_params.params,
+                &is_stopped,
+            )?
+        } else {
+            // Segment was created before the vector was added to the collection
+            vec![vec![]; vectors_batch.len()]
+        };
         for batch_result in &res {
             further_results.push(batch_result.len() == top);
         }
@@ -455,6 +479,82 @@ async fn search_in_segment(
     Ok((result, further_results))
 }
 
+/// Select only vectors, which are stored in the segment
+///
+/// Segments, created before a vector was added to the collection, do not store it.
+/// Points of such segments are returned without the missing vector.
+fn segment_with_vector(with_vector: &WithVector, config: &SegmentConfig) -> WithVector {
+    match with_vector {
+        WithVector::Bool(enable) => WithVector::Bool(*enable),
+        WithVector::Selector(vector_names) => WithVector::Selector(
+            vector_names
+                .iter()
+                .filter(|vector_name| config.vector_data.contains_key(vector_name.as_str()))
+                .cloned()
+                .collect(),
+        ),
+        WithVector::Slices(slices) => WithVector::Slices(
+            slices
+                .iter()
+                .filter(|slice| config.vector_data.contains_key(&slice.name))
+                .cloned()
+                .collect(),
+        ),
+    }
+}
+
+/// Vectors of the point, selected by `with_vector`.
+///
+/// Original values of quantized-only vectors are not stored, so they are omitted.
+/// Returns `None` if none of the selected vectors are stored.
+fn retrieve_vectors(
+    segment: &dyn SegmentEntry,
+    with_vector: &WithVector,
+    point_id: PointIdType,
+) -> OperationResult<Option<VectorStruct>> {
+    let config = segment.config();
+    let selected: Vec<(String, Option<VectorSlice>)> =
+        match segment_with_vector(with_vector, &config) {
+            WithVector::Bool(false) => return Ok(None),
+            WithVector::Bool(true) => config
+                .vector_data
+                .keys()
+                .map(|vector_name| (vector_name.clone(), None))
+                .collect(),
+            WithVector::Selector(vector_names) => vector_names
+                .into_iter()
+                .map(|vector_name| (vector_name, None))
+                .collect(),
+            WithVector::Slices(slices) => slices
+                .into_iter()
+                .map(|slice| (slice.name.clone(), Some(slice)))
+                .collect(),
+        };
+
+    let mut vectors = NamedVectors::default();
+    let mut omitted = false;
+    for (vector_name, slice) in selected {
+        let vector = match segment.vector(&vector_name, point_id) {
+            Ok(vector) => vector,
+            Err(OperationError::VectorsNotStored { .. }) => {
+                omitted = true;
+                continue;
+            }
+            Err(err) => return Err(err),
+        };
+        let vector = match slice {
+            Some(slice) => slice.apply(vector),
+            None => vector,
+        };
+        vectors.insert(vector_name, vector);
+    }
+
+    if omitted && vectors.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(vectors.into()))
+}
+
 /// Find the maximum segment or vector specific HNSW ef_construct in this config
 ///
 /// If the index is `Plain`, `None` is returned.
@@ -465,7 +565,7 @@ fn get_hnsw_ef_construct(config: SegmentConfig, vector_name: &str) -> Option<usi
             config
                 .vector_data
                 .get(vector_name)
-                .and_then(|c| c.hnsw_config)
+                .and_then(|c| c.hnsw_config.as_ref())
                 .map(|c| c.ef_construct)
                 .unwrap_or(hnsw_config.ef_construct),
         ),
@@ -475,11 +575,13 @@ fn get_hnsw_ef_construct(config: SegmentConfig, vector_name: &str) -> Option<usi
 #[cfg(test)]
 mod tests {
     use segment::fixtures::index_fixtures::random_vector;
+    use segment::segment_constructor::segment_builder::SegmentBuilder;
+    use segment::types::{ScalarQuantizationConfig, StorageType};
     use tempfile::Builder;
 
     use super::*;
-    use crate::collection_manager::fixtures::{build_test_holder, random_segment};
-    use crate::operations::types::SearchRequest;
+    use crate::collection_manager::fixtures::{build_segment_1, build_test_holder, random_segment};
+    use crate::operations::types::{CollectionError, SearchRequest};
 
     #[tokio::test]
     async fn test_segments_search() {
@@ -498,6 +600,8 @@ mod tests {
             limit: 5,
             score_threshold: None,
             offset: 0,
+            with_highlights: None,
+            with_geo_distance: None,
         };
 
         let batch_request = SearchRequestBatch {
@@ -550,6 +654,8 @@ mod tests {
                 filter: None,
                 params: None,
                 score_threshold: None,
+                with_highlights: None,
+                with_geo_distance: None,
             };
             let req2 = SearchRequest {
                 vector: random_vector(&mut rnd, 4).into(),
@@ -560,6 +666,8 @@ mod tests {
                 with_payload: None,
                 with_vector: None,
                 score_threshold: None,
+                with_highlights: None,
+                with_geo_distance: None,
             };
 
             let batch_request = SearchRequestBatch {
@@ -615,6 +723,77 @@ mod tests {
         assert_eq!(records.len(), 3);
     }
 
+    #[tokio::test]
+    async fn test_retrieve_quantized_only() {
+        let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+        let temp_dir = Builder::new().prefix("segment_temp_dir").tempdir().unwrap();
+        let segment = build_segment_1(dir.path());
+
+        let segment_config = SegmentConfig {
+            storage_type: StorageType::Mmap,
+            quantization_config: Some(
+                ScalarQuantizationConfig {
+                    r#type: Default::default(),
+                    quantile: None,
+                    always_ram: None,
+                    quantized_only: Some(true),
+                }
+                .into(),
+            ),
+            ..segment.segment_config.clone()
+        };
+        let stopped = AtomicBool::new(false);
+        let mut builder =
+            SegmentBuilder::new(dir.path(), temp_dir.path(), &segment_config).unwrap();
+        builder.update_from(&segment, &stopped).unwrap();
+        let mut holder = SegmentHolder::default();
+        holder.add(builder.build(&stopped).unwrap());
+        let segment_holder = RwLock::new(holder);
+
+        // Points are returned without vectors, which are not stored, instead of failing
+        let records = SegmentsSearcher::retrieve(
+            &segment_holder,
+            &[1.into(), 2.into(), 3.into()],
+            &WithPayload::from(true),
+            &true.into(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(records.len(), 3);
+        assert!(records.iter().all(|record| record.vector.is_none()));
+    }
+
+    #[tokio::test]
+    async fn test_search_in_segment_stopped() {
+        let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+        let segment = LockedSegment::new(random_segment(dir.path(), 10, 200, 4));
+
+        let batch_request = SearchRequestBatch {
+            searches: vec![SearchRequest {
+                vector: random_vector(&mut rand::thread_rng(), 4).into(),
+                with_payload: None,
+                with_vector: None,
+                filter: None,
+                params: None,
+                limit: 5,
+                score_threshold: None,
+                offset: 0,
+                with_highlights: None,
+                with_geo_distance: None,
+            }],
+        };
+
+        let result = search_in_segment(
+            segment,
+            Arc::new(batch_request),
+            0,
+            false,
+            Arc::new(AtomicBool::new(true)),
+        )
+        .await;
+        assert!(matches!(result, Err(CollectionError::Cancelled { .. })));
+    }
+
     #[test]
     fn test_sampling_limit() {
         assert_eq!(sampling_limit(1000, None, 464530, 35103551), 30);
@@ -1,17 +1,20 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeSet, HashMap, HashSet};
 
 use itertools::Itertools;
-use parking_lot::{RwLock, RwLockWriteGuard};
+use parking_lot::{RwLock, RwLockUpgradableReadGuard, RwLockWriteGuard};
 use segment::data_types::named_vectors::NamedVectors;
+use segment::data_types::vectors::VectorStruct;
 use segment::entry::entry_point::{OperationResult, SegmentEntry};
 use segment::types::{
     Filter, Payload, PayloadFieldSchema, PayloadKeyType, PayloadKeyTypeRef, PointIdType,
-    SeqNumberType,
+    PointMetadata, SeqNumberType,
 };
 
-use crate::collection_manager::holders::segment_holder::SegmentHolder;
+use crate::collection_manager::holders::segment_holder::{LockedSegment, SegmentHolder};
 use crate::operations::payload_ops::PayloadOps;
-use crate::operations::point_ops::{PointInsertOperations, PointOperations, PointStruct};
+use crate::operations::point_ops::{
+    PointInsertOperations, PointOperations, PointStruct, PointVectors,
+};
 use crate::operations::types::{CollectionError, CollectionResult};
 use crate::operations::FieldIndexOperations;
 
@@ -50,6 +53,20 @@ pub(crate) fn delete_points(
     Ok(res)
 }
 
+/// Content of the point is changed, so the stored hash is no longer valid.
+/// Sequence number of the point is kept.
+fn invalidate_content_hash(
+    segment: &mut RwLockWriteGuard<dyn SegmentEntry>,
+    op_num: SeqNumberType,
+    point_id: PointIdType,
+) -> OperationResult<()> {
+    let mut metadata = segment.point_metadata(point_id)?;
+    if metadata.content_hash.take().is_some() {
+        segment.set_point_metadata(op_num, point_id, &metadata)?;
+    }
+    Ok(())
+}
+
 pub(crate) fn overwrite_payload(
     segments: &SegmentHolder,
     op_num: SeqNumberType,
@@ -59,6 +76,7 @@ pub(crate) fn overwrite_payload(
     let updated_points =
         segments.apply_points_to_appendable(op_num, points, |id, write_segment| {
             write_segment.set_full_payload(op_num, id, payload)?;
+            invalidate_content_hash(write_segment, op_num, id)?;
             Ok(true)
         })?;
 
@@ -85,6 +103,7 @@ pub(crate) fn set_payload(
     let updated_points =
         segments.apply_points_to_appendable(op_num, points, |id, write_segment| {
             write_segment.set_payload(op_num, id, payload)?;
+            invalidate_content_hash(write_segment, op_num, id)?;
             Ok(true)
         })?;
 
@@ -98,7 +117,7 @@ fn points_by_filter(
 ) -> CollectionResult<Vec<PointIdType>> {
     let mut affected_points: Vec<PointIdType> = Vec::new();
     segments.for_each_segment(|s| {
-        let points = s.read_filtered(None, None, Some(filter));
+        let points = s.read_filtered(None, None, Some(filter), None);
         affected_points.extend_from_slice(points.as_slice());
         Ok(true)
     })?;
@@ -127,6 +146,7 @@ pub(crate) fn delete_payload(
             for key in keys {
                 res = write_segment.delete_payload(op_num, id, key)? && res;
             }
+            invalidate_content_hash(write_segment, op_num, id)?;
             Ok(res)
         })?;
 
@@ -151,7 +171,9 @@ pub(crate) fn clear_payload(
 ) -> CollectionResult<usize> {
     let updated_points =
         segments.apply_points_to_appendable(op_num, points, |id, write_segment| {
-            write_segment.clear_payload(op_num, id)
+            let res = write_segment.clear_payload(op_num, id)?;
+            invalidate_content_hash(write_segment, op_num, id)?;
+            Ok(res)
         })?;
 
     check_unprocessed_points(points, &updated_points)?;
@@ -169,7 +191,11 @@ pub(crate) fn clear_payload_by_filter(
     let updated_points = segments.apply_points_to_appendable(
         op_num,
         points_to_clear.as_slice(),
-        |id, write_segment| write_segment.clear_payload(op_num, id),
+        |id, write_segment| {
+            let res = write_segment.clear_payload(op_num, id)?;
+            invalidate_content_hash(write_segment, op_num, id)?;
+            Ok(res)
+        },
     )?;
 
     Ok(updated_points.len())
@@ -181,9 +207,31 @@ pub(crate) fn create_field_index(
     field_name: PayloadKeyTypeRef,
     field_schema: Option<&PayloadFieldSchema>,
 ) -> CollectionResult<usize> {
-    let res = segments.apply_segments(|write_segment| {
-        write_segment.create_field_index(op_num, field_name, field_schema)
-    })?;
+    let mut res = 0;
+    for (_id, segment) in segments.iter() {
+        let is_applied = match segment {
+            LockedSegment::Original(original_segment) => {
+                // Build the index under upgradable lock, so the segment remains readable
+                // and the progress of the build is visible in the collection info
+                let segment_lock = original_segment.upgradable_read();
+                match segment_lock.build_field_index(op_num, field_name, field_schema)? {
+                    Some((schema, field_index)) => RwLockUpgradableReadGuard::upgrade(segment_lock)
+                        .apply_field_index(op_num, field_name, schema, field_index)?,
+                    None => RwLockUpgradableReadGuard::upgrade(segment_lock).create_field_index(
+                        op_num,
+                        field_name,
+                        field_schema,
+                    )?,
+                }
+            }
+            LockedSegment::Proxy(proxy_segment) => {
+                proxy_segment
+                    .write()
+                    .create_field_index(op_num, field_name, field_schema)?
+            }
+        };
+        res += is_applied as usize;
+    }
     Ok(res)
 }
 
@@ -197,6 +245,26 @@ pub(crate) fn delete_field_index(
     Ok(res)
 }
 
+/// Drop the index of the field in the segment and build it again with the same schema.
+/// Segment version is not changed, as the content of the segment stays the same.
+///
+/// Returns `false` if the segment doesn't have the index of the field.
+pub(crate) fn rebuild_field_index(
+    segment: &LockedSegment,
+    field_name: PayloadKeyTypeRef,
+) -> CollectionResult<bool> {
+    let segment = segment.get();
+    let mut write_segment = segment.write();
+    let field_schema = match write_segment.get_indexed_fields().remove(field_name) {
+        Some(field_schema) => field_schema,
+        None => return Ok(false),
+    };
+    let op_num = write_segment.version();
+    write_segment.delete_field_index(op_num, field_name)?;
+    write_segment.create_field_index(op_num, field_name, Some(&field_schema))?;
+    Ok(true)
+}
+
 ///
 /// Returns
 /// - Ok(true) if the operation was successful and point replaced existing value
@@ -208,14 +276,98 @@ fn upsert_with_payload(
     point_id: PointIdType,
     vectors: &NamedVectors,
     payload: Option<&Payload>,
+    metadata: &PointMetadata,
 ) -> OperationResult<bool> {
     let mut res = segment.upsert_vector(op_num, point_id, vectors)?;
     if let Some(full_payload) = payload {
         res &= segment.set_full_payload(op_num, point_id, full_payload)?;
     }
+    segment.set_point_metadata(op_num, point_id, metadata)?;
     Ok(res)
 }
 
+/// Metadata of the point after the upsert.
+/// Sequence number is kept if the upsert doesn't provide a new one,
+/// so stale writes are still rejected after it.
+/// Content hash is replaced, as the content of the point is replaced.
+fn upserted_metadata(stored: PointMetadata, point: &PointStruct) -> PointMetadata {
+    PointMetadata {
+        sequence: point.sequence.or(stored.sequence),
+        content_hash: point.content_hash.clone(),
+    }
+}
+
+/// Drop points, which carry an external sequence number not newer than the stored one.
+/// Such writes are already applied (or superseded), so repeating them must be a no-op.
+fn skip_stale_points<'a>(
+    segments: &SegmentHolder,
+    points: Vec<&'a PointStruct>,
+) -> CollectionResult<Vec<&'a PointStruct>> {
+    let sequenced_ids: Vec<_> = points
+        .iter()
+        .filter(|point| point.sequence.is_some())
+        .map(|point| point.id)
+        .collect();
+
+    if sequenced_ids.is_empty() {
+        return Ok(points);
+    }
+
+    let mut stored_sequences: HashMap<PointIdType, SeqNumberType> = HashMap::new();
+    segments.read_points(&sequenced_ids, |id, segment| {
+        if let Some(sequence) = segment.point_metadata(id)?.sequence {
+            let stored = stored_sequences.entry(id).or_insert(sequence);
+            *stored = (*stored).max(sequence);
+        }
+        Ok(true)
+    })?;
+
+    Ok(points
+        .into_iter()
+        .filter(
+            |point| match (point.sequence, stored_sequences.get(&point.id)) {
+                (Some(sequence), Some(stored)) => sequence > *stored,
+                _ => true,
+            },
+        )
+        .collect())
+}
+
+/// Drop points, which carry the same content hash as the stored one.
+/// Such writes would not change the point, so vector storage and indexes are not touched.
+fn skip_unchanged_points<'a>(
+    segments: &SegmentHolder,
+    points: Vec<&'a PointStruct>,
+) -> CollectionResult<Vec<&'a PointStruct>> {
+    let hashed_ids: Vec<_> = points
+        .iter()
+        .filter(|point| point.content_hash.is_some())
+        .map(|point| point.id)
+        .collect();
+
+    if hashed_ids.is_empty() {
+        return Ok(points);
+    }
+
+    let mut stored_hashes: HashMap<PointIdType, String> = HashMap::new();
+    segments.read_points(&hashed_ids, |id, segment| {
+        if let Some(content_hash) = segment.point_metadata(id)?.content_hash {
+            stored_hashes.insert(id, content_hash);
+        }
+        Ok(true)
+    })?;
+
+    Ok(points
+        .into_iter()
+        .filter(
+            |point| match (&point.content_hash, stored_hashes.get(&point.id)) {
+                (Some(content_hash), Some(stored)) => content_hash != stored,
+                _ => true,
+            },
+        )
+        .collect())
+}
+
 /// Sync points within a given [from_id; to_id) range
 ///
 /// 1. Retrieve existing points for a range
@@ -286,10 +438,75 @@ pub(crate) fn sync_points(
         });
 
     // 5. Upsert points which differ from the stored ones
+    // Points with stale sequence numbers or unchanged content hashes are skipped by the upsert,
+    // so they are not counted
     let num_replaced = upsert_points(segments, op_num, points_to_update)?;
-    debug_assert_eq!(num_replaced, num_updated);
+    debug_assert!(num_replaced <= num_updated);
+
+    Ok((deleted, num_new, num_replaced))
+}
 
-    Ok((deleted, num_new, num_updated))
+/// Names of the vectors, provided for the point
+fn point_vector_names(point: &PointStruct) -> BTreeSet<String> {
+    point.get_vectors().keys().map(str::to_owned).collect()
+}
+
+/// Removes points from segments, which store a different set of vectors than the upserted point has.
+///
+/// Segments, created before a vector was added to the collection, do not store it.
+/// Once the new vector is provided for a point, it is moved into a segment with matching vectors.
+/// Points are only moved if there is an appendable segment to receive them.
+///
+/// Points are also removed from segments, which keep only quantized vectors:
+/// original vectors can't be copied from there, but they are replaced by the upsert anyway.
+///
+/// Returns: payloads and metadata of removed points, which should be preserved on re-insert.
+fn relocate_points_with_other_vectors(
+    segments: &SegmentHolder,
+    op_num: SeqNumberType,
+    points_map: &HashMap<PointIdType, &PointStruct>,
+) -> CollectionResult<HashMap<PointIdType, (Payload, PointMetadata)>> {
+    let segments_vectors: HashMap<_, BTreeSet<_>> = segments
+        .iter()
+        .map(|(idx, segment)| {
+            let vector_names = segment.get().read().config().vector_data.into_keys();
+            (*idx, vector_names.collect())
+        })
+        .collect();
+    let quantized_only_segments: HashSet<_> = segments
+        .iter()
+        .filter(|(_, segment)| segment.get().read().config().has_quantized_only_vectors())
+        .map(|(idx, _)| *idx)
+        .collect();
+    if segments_vectors.values().all_equal() && quantized_only_segments.is_empty() {
+        // All segments store the same vectors, there is nowhere to move
+        return Ok(HashMap::new());
+    }
+    let appendable_vectors: HashSet<_> = segments
+        .appendable_segments_by_vectors()
+        .into_keys()
+        .collect();
+
+    let ids: Vec<PointIdType> = points_map.keys().copied().collect();
+    let mut relocated_points = HashMap::new();
+    segments.apply_points(&ids, |point_id, idx, write_segment| {
+        if let Some(point_version) = write_segment.point_version(point_id) {
+            if point_version >= op_num {
+                return Ok(false);
+            }
+        }
+        let point_vectors = point_vector_names(points_map[&point_id]);
+        let keeps_point =
+            point_vectors == segments_vectors[&idx] && !quantized_only_segments.contains(&idx);
+        if keeps_point || !appendable_vectors.contains(&point_vectors) {
+            return Ok(false);
+        }
+        let payload = write_segment.payload(point_id)?;
+        let metadata = write_segment.point_metadata(point_id)?;
+        relocated_points.insert(point_id, (payload, metadata));
+        write_segment.delete_point(op_num, point_id)
+    })?;
+    Ok(relocated_points)
 }
 
 /// Checks point id in each segment, update point if found.
@@ -303,20 +520,26 @@ pub(crate) fn upsert_points<'a, T>(
 where
     T: IntoIterator<Item = &'a PointStruct>,
 {
+    let points = skip_stale_points(segments, points.into_iter().collect())?;
+    let points = skip_unchanged_points(segments, points)?;
     let points_map: HashMap<PointIdType, &PointStruct> =
         points.into_iter().map(|p| (p.id, p)).collect();
     let ids: Vec<PointIdType> = points_map.keys().copied().collect();
 
+    let mut relocated_points = relocate_points_with_other_vectors(segments, op_num, &points_map)?;
+
     // Update points in writable segments
     let updated_points =
         segments.apply_points_to_appendable(op_num, &ids, |id, write_segment| {
             let point = points_map[&id];
+            let metadata = upserted_metadata(write_segment.point_metadata(id)?, point);
             upsert_with_payload(
                 write_segment,
                 op_num,
                 id,
                 &point.get_vectors(),
                 point.payload.as_ref(),
+                &metadata,
             )
         })?;
 
@@ -325,31 +548,84 @@ where
     let new_point_ids = ids
         .iter()
         .cloned()
-        .filter(|x| !(updated_points.contains(x)));
-
-    {
-        let default_write_segment = segments.random_appendable_segment().ok_or_else(|| {
-            CollectionError::service_error("No segments exists, expected at least one".to_string())
-        })?;
+        .filter(|x| !(updated_points.contains(x)))
+        .into_group_map_by(|point_id| point_vector_names(points_map[point_id]));
+
+    for (vector_names, point_ids) in new_point_ids {
+        let default_write_segment = segments
+            .random_appendable_segment_with_vectors(&vector_names)
+            .or_else(|| segments.random_appendable_segment())
+            .ok_or_else(|| {
+                CollectionError::service_error(
+                    "No segments exists, expected at least one".to_string(),
+                )
+            })?;
 
         let segment_arc = default_write_segment.get();
         let mut write_segment = segment_arc.write();
-        for point_id in new_point_ids {
+        for point_id in point_ids {
             let point = points_map[&point_id];
+            let (relocated_payload, relocated_metadata) = relocated_points
+                .remove(&point_id)
+                .map_or((None, PointMetadata::default()), |(payload, metadata)| {
+                    (Some(payload), metadata)
+                });
             res += upsert_with_payload(
                 &mut write_segment,
                 op_num,
                 point_id,
                 &point.get_vectors(),
-                point.payload.as_ref(),
+                point.payload.as_ref().or(relocated_payload.as_ref()),
+                &upserted_metadata(relocated_metadata, point),
             )? as usize;
         }
         RwLockWriteGuard::unlock_fair(write_segment);
-    };
+    }
 
     Ok(res)
 }
 
+/// Set the given vectors of existing points, other vectors and payload of the points are kept.
+/// Points, which don't exist anymore, are skipped.
+///
+/// Stored vectors are read while the operation is applied,
+/// so updates of the points, applied before it, are not overwritten.
+pub(crate) fn update_vectors(
+    segments: &SegmentHolder,
+    op_num: SeqNumberType,
+    points: &[PointVectors],
+) -> CollectionResult<usize> {
+    let ids: Vec<_> = points.iter().map(|point| point.id).collect();
+    // Point can be stored in several segments during optimization, the newest version is used
+    let mut stored_vectors = HashMap::new();
+    segments.read_points(&ids, |id, segment| {
+        let version = segment.point_version(id);
+        let is_newer = stored_vectors
+            .get(&id)
+            .map_or(true, |(stored_version, _)| version > *stored_version);
+        if is_newer {
+            stored_vectors.insert(id, (version, segment.all_vectors(id)?.into_owned_map()));
+        }
+        Ok(true)
+    })?;
+
+    let updated_points: Vec<_> = points
+        .iter()
+        .filter_map(|point| {
+            let (_, mut vectors) = stored_vectors.remove(&point.id)?;
+            vectors.extend(point.vector.clone().into_all_vectors().into_owned_map());
+            Some(PointStruct {
+                id: point.id,
+                vector: VectorStruct::Multi(vectors),
+                payload: None,
+                sequence: None,
+                content_hash: None,
+            })
+        })
+        .collect();
+    upsert_points(segments, op_num, &updated_points)
+}
+
 pub(crate) fn process_point_operation(
     segments: &RwLock<SegmentHolder>,
     op_num: SeqNumberType,
@@ -359,27 +635,7 @@ pub(crate) fn process_point_operation(
         PointOperations::DeletePoints { ids, .. } => delete_points(&segments.read(), op_num, &ids),
         PointOperations::UpsertPoints(operation) => {
             let points: Vec<_> = match operation {
-                PointInsertOperations::PointsBatch(batch) => {
-                    let all_vectors = batch.vectors.into_all_vectors(batch.ids.len());
-                    let vectors_iter = batch.ids.into_iter().zip(all_vectors.into_iter());
-                    match batch.payloads {
-                        None => vectors_iter
-                            .map(|(id, vectors)| PointStruct {
-                                id,
-                                vector: vectors.into(),
-                                payload: None,
-                            })
-                            .collect(),
-                        Some(payloads) => vectors_iter
-                            .zip(payloads.into_iter())
-                            .map(|((id, vectors), payload)| PointStruct {
-                                id,
-                                vector: vectors.into(),
-                                payload,
-                            })
-                            .collect(),
-                    }
-                }
+                PointInsertOperations::PointsBatch(batch) => batch.into(),
                 PointInsertOperations::PointsList(points) => points,
             };
             let res = upsert_points(&segments.read(), op_num, points.iter())?;
@@ -398,6 +654,9 @@ pub(crate) fn process_point_operation(
             )?;
             Ok(deleted + new + updated)
         }
+        PointOperations::UpdateVectors { points } => {
+            update_vectors(&segments.read(), op_num, &points)
+        }
     }
 }
 
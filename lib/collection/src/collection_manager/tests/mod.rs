@@ -71,11 +71,15 @@ fn test_update_proxy_segments() {
                 id: (100 * i + 1).into(),
                 vector: vectors[0].clone().into(),
                 payload: None,
+                sequence: None,
+                content_hash: None,
             },
             PointStruct {
                 id: (100 * i + 2).into(),
                 vector: vectors[1].clone().into(),
                 payload: None,
+                sequence: None,
+                content_hash: None,
             },
         ];
         upsert_points(&segments.read(), 1000 + i, &points).unwrap();
@@ -84,7 +88,12 @@ fn test_update_proxy_segments() {
     let all_ids = segments
         .read()
         .iter()
-        .flat_map(|(_id, segment)| segment.get().read().read_filtered(None, Some(100), None))
+        .flat_map(|(_id, segment)| {
+            segment
+                .get()
+                .read()
+                .read_filtered(None, Some(100), None, None)
+        })
         .sorted()
         .collect_vec();
 
@@ -115,11 +124,15 @@ fn test_move_points_to_copy_on_write() {
             id: 1.into(),
             vector: vec![0.0, 0.0, 0.0, 0.0].into(),
             payload: None,
+            sequence: None,
+            content_hash: None,
         },
         PointStruct {
             id: 2.into(),
             vector: vec![0.0, 0.0, 0.0, 0.0].into(),
             payload: None,
+            sequence: None,
+            content_hash: None,
         },
     ];
 
@@ -130,11 +143,15 @@ fn test_move_points_to_copy_on_write() {
             id: 2.into(),
             vector: vec![0.0, 0.0, 0.0, 0.0].into(),
             payload: None,
+            sequence: None,
+            content_hash: None,
         },
         PointStruct {
             id: 3.into(),
             vector: vec![0.0, 0.0, 0.0, 0.0].into(),
             payload: None,
+            sequence: None,
+            content_hash: None,
         },
     ];
 
@@ -9,6 +9,8 @@ fn score_point(id: usize, score: ScoreType, version: SeqNumberType) -> ScoredPoi
         score,
         payload: None,
         vector: None,
+        highlights: None,
+        geo_distance: None,
     }
 }
 
@@ -0,0 +1,121 @@
+//! Faults, injected into the running service on request, to test recovery and alerting of
+//! highly available deployments against realistic failures.
+//!
+//! Faults are only injected if the crate is built with the `fault-injection` feature,
+//! otherwise all hooks are no-ops.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+use parking_lot::{const_rwlock, RwLock};
+use schemars::JsonSchema;
+use segment::entry::entry_point::{OperationError, OperationResult};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+/// Interval, in which stalled optimizations check if they are cancelled
+const STALL_CHECK_INTERVAL: Duration = Duration::from_millis(100);
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, Default, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct FaultInjectionConfig {
+    /// Delay each flush of segments by the given number of milliseconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub flush_delay_ms: Option<u64>,
+    /// Fail each flush of segments, as if the disk is broken.
+    /// Failed flushes are reported as errors of the collection
+    #[serde(default)]
+    pub fail_flushes: bool,
+    /// Fraction of outgoing consensus messages to other peers, which are dropped. From 0 to 1
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 0.0, max = 1.0))]
+    pub drop_p2p_messages: Option<f64>,
+    /// Stall each optimization by the given number of milliseconds before it starts
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stall_optimizers_ms: Option<u64>,
+}
+
+static FAULTS: RwLock<FaultInjectionConfig> = const_rwlock(FaultInjectionConfig {
+    flush_delay_ms: None,
+    fail_flushes: false,
+    drop_p2p_messages: None,
+    stall_optimizers_ms: None,
+});
+
+pub fn is_enabled() -> bool {
+    cfg!(feature = "fault-injection")
+}
+
+/// Currently injected faults
+pub fn faults() -> FaultInjectionConfig {
+    FAULTS.read().clone()
+}
+
+/// Replace injected faults. Default config removes all of them
+pub fn set_faults(config: FaultInjectionConfig) {
+    if is_enabled() {
+        log::warn!("Injected faults: {config:?}");
+        *FAULTS.write() = config;
+    }
+}
+
+/// Hook of segment flushes
+pub fn inject_flush_fault() -> OperationResult<()> {
+    if !is_enabled() {
+        return Ok(());
+    }
+    let faults = faults();
+    if let Some(delay_ms) = faults.flush_delay_ms {
+        std::thread::sleep(Duration::from_millis(delay_ms));
+    }
+    if faults.fail_flushes {
+        return Err(OperationError::service_error("Injected flush failure"));
+    }
+    Ok(())
+}
+
+/// Hook of optimizations. Returns early, if the optimization is cancelled
+pub fn inject_optimizer_stall(stopped: &AtomicBool) {
+    if !is_enabled() {
+        return;
+    }
+    let stall = match faults().stall_optimizers_ms {
+        Some(stall_ms) => Duration::from_millis(stall_ms),
+        None => return,
+    };
+    let start = Instant::now();
+    while start.elapsed() < stall && !stopped.load(Ordering::Relaxed) {
+        std::thread::sleep(STALL_CHECK_INTERVAL.min(stall));
+    }
+}
+
+/// Hook of outgoing consensus messages. Returns true, if the message should be dropped
+pub fn drop_p2p_message() -> bool {
+    if !is_enabled() {
+        return false;
+    }
+    match faults().drop_p2p_messages {
+        Some(fraction) => rand::random::<f64>() < fraction,
+        None => false,
+    }
+}
+
+#[cfg(all(test, feature = "fault-injection"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flush_fault() {
+        assert!(inject_flush_fault().is_ok());
+        set_faults(FaultInjectionConfig {
+            fail_flushes: true,
+            drop_p2p_messages: Some(1.0),
+            ..Default::default()
+        });
+        assert!(inject_flush_fault().is_err());
+        assert!(drop_p2p_message());
+        set_faults(FaultInjectionConfig::default());
+        assert!(inject_flush_fault().is_ok());
+        assert!(!drop_p2p_message());
+    }
+}
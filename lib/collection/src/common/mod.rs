@@ -1,3 +1,6 @@
+pub mod fault_injection;
 pub mod is_ready;
+pub mod optimization_budget;
 pub mod stoppable_task;
 pub mod stoppable_task_async;
+pub mod stopping_guard;
@@ -0,0 +1,104 @@
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use parking_lot::{Condvar, Mutex};
+
+/// Interval of checking the stop flag while waiting for the budget
+const STOP_CHECK_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Limits number of optimizations, running concurrently in all collections of the node.
+///
+/// Unlike a semaphore, the limit can be changed at runtime.
+/// Running optimizations are not interrupted if the limit is lowered.
+#[derive(Debug, Default)]
+pub struct OptimizationBudget {
+    /// Max number of running optimizations. If 0 - unlimited
+    limit: AtomicUsize,
+    running: Mutex<usize>,
+    released: Condvar,
+}
+
+impl OptimizationBudget {
+    pub fn new(limit: usize) -> Self {
+        Self {
+            limit: AtomicUsize::new(limit),
+            running: Mutex::new(0),
+            released: Condvar::new(),
+        }
+    }
+
+    pub fn limit(&self) -> usize {
+        self.limit.load(Ordering::Relaxed)
+    }
+
+    pub fn set_limit(&self, limit: usize) {
+        self.limit.store(limit, Ordering::Relaxed);
+        self.released.notify_all();
+    }
+
+    /// Blocks until an optimization is allowed to run.
+    /// The permit is returned to the budget when dropped.
+    ///
+    /// Returns `None`, if `stopped` is set while waiting.
+    pub fn acquire(self: &Arc<Self>, stopped: &AtomicBool) -> Option<OptimizationPermit> {
+        let mut running = self.running.lock();
+        loop {
+            if stopped.load(Ordering::Relaxed) {
+                return None;
+            }
+            let limit = self.limit();
+            if limit == 0 || *running < limit {
+                *running += 1;
+                return Some(OptimizationPermit {
+                    budget: self.clone(),
+                });
+            }
+            self.released.wait_for(&mut running, STOP_CHECK_INTERVAL);
+        }
+    }
+}
+
+pub struct OptimizationPermit {
+    budget: Arc<OptimizationBudget>,
+}
+
+impl Drop for OptimizationPermit {
+    fn drop(&mut self) {
+        *self.budget.running.lock() -= 1;
+        self.budget.released.notify_one();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+
+    use super::*;
+
+    #[test]
+    fn test_optimization_budget() {
+        let budget = Arc::new(OptimizationBudget::new(1));
+        let not_stopped = AtomicBool::new(false);
+
+        let permit = budget.acquire(&not_stopped).unwrap();
+
+        // Budget is exhausted, waiting is interrupted by the stop flag
+        let stopped = AtomicBool::new(true);
+        assert!(budget.acquire(&stopped).is_none());
+
+        // Raised limit is applied to the waiting optimization
+        let waiting = {
+            let budget = budget.clone();
+            thread::spawn(move || budget.acquire(&AtomicBool::new(false)).is_some())
+        };
+        budget.set_limit(2);
+        assert!(waiting.join().unwrap());
+
+        drop(permit);
+        budget.set_limit(0);
+        let _permits: Vec<_> = (0..3)
+            .map(|_| budget.acquire(&not_stopped).unwrap())
+            .collect();
+    }
+}
@@ -1,6 +1,8 @@
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Weak};
 
+use segment::common::log_context;
+use segment::common::log_context::LogContext;
 use tokio::task::JoinHandle;
 
 pub struct StoppableTaskHandle<T> {
@@ -40,8 +42,12 @@ where
     // Weak reference is sufficient
     let stopped_w = Arc::downgrade(&stopped);
 
+    // Keep the log context of the caller in the blocking thread
+    let log_context = LogContext::current();
+
     StoppableTaskHandle {
         join_handle: tokio::task::spawn_blocking(move || {
+            let _log_context = log_context::enter(log_context);
             let res = f(&stopped);
             // We use `Release` ordering to ensure that `f` won't be moved after the `store`
             // by the compiler
@@ -3,6 +3,7 @@ use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Weak};
 
 use parking_lot::Mutex;
+use segment::common::log_context::in_current_log_context;
 use tokio::task::JoinHandle;
 
 pub struct StoppableAsyncTaskHandle<T: Clone> {
@@ -52,7 +53,7 @@ where
     let result_holder_c = result_holder.clone();
 
     StoppableAsyncTaskHandle {
-        join_handle: tokio::task::spawn(async move {
+        join_handle: tokio::task::spawn(in_current_log_context(async move {
             let res = f(stopped).await;
             let mut result_holder_w = result_holder_c.lock();
             result_holder_w.replace(res.clone());
@@ -61,7 +62,7 @@ where
             // by the compiler
             finished.store(true, Ordering::Release);
             res
-        }),
+        })),
         result_holder,
         stopped: stopped_w,
         finished: finished_c,
@@ -0,0 +1,42 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// Sets the stop flag, when dropped.
+///
+/// Operations spawned for a request keep running, even if the request future is dropped,
+/// e.g. on timeout or on a closed client connection.
+/// The guard is kept by the request future and stops the spawned operations along with it.
+#[derive(Default)]
+pub struct StoppingGuard {
+    is_stopped: Arc<AtomicBool>,
+}
+
+impl StoppingGuard {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get_is_stopped(&self) -> Arc<AtomicBool> {
+        self.is_stopped.clone()
+    }
+}
+
+impl Drop for StoppingGuard {
+    fn drop(&mut self) {
+        self.is_stopped.store(true, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stopping_guard() {
+        let guard = StoppingGuard::new();
+        let is_stopped = guard.get_is_stopped();
+        assert!(!is_stopped.load(Ordering::Relaxed));
+        drop(guard);
+        assert!(is_stopped.load(Ordering::Relaxed));
+    }
+}
@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fs::File;
 use std::io::{Read, Write};
 use std::num::NonZeroU32;
@@ -8,14 +8,22 @@ use atomicwrites::AtomicFile;
 use atomicwrites::OverwriteBehavior::AllowOverwrite;
 use schemars::JsonSchema;
 use segment::common::anonymize::Anonymize;
-use segment::data_types::vectors::DEFAULT_VECTOR_NAME;
-use segment::types::{HnswConfig, QuantizationConfig, VectorDataConfig};
+use segment::data_types::vectors::{VectorType, DEFAULT_VECTOR_NAME};
+use segment::types::{
+    HnswConfig, PayloadKeyType, QuantizationConfig, SearchParams, SparseVectorDataConfig,
+    VectorDataConfig, WithVector,
+};
 use serde::{Deserialize, Serialize};
 use validator::Validate;
 use wal::WalOptions;
 
 use crate::operations::config_diff::DiffConfig;
-use crate::operations::types::{CollectionError, CollectionResult, VectorParams, VectorsConfig};
+use crate::operations::consistency_params::ReadConsistency;
+use crate::operations::lifecycle_ops::LifecycleConfig;
+use crate::operations::point_ops::WriteOrdering;
+use crate::operations::types::{
+    CollectionError, CollectionResult, SparseVectorParams, VectorParams, VectorsConfig,
+};
 use crate::operations::validation;
 use crate::optimizers_builder::OptimizersConfig;
 
@@ -54,6 +62,10 @@ pub struct CollectionParams {
     /// Configuration of the vector storage
     #[validate]
     pub vectors: VectorsConfig,
+    /// Configuration of the sparse vectors, stored along with dense ones.
+    /// Names of sparse vectors must not intersect with names of dense vectors.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub sparse_vectors: BTreeMap<String, SparseVectorParams>,
     /// Number of shards the collection has
     #[serde(default = "default_shard_number")]
     pub shard_number: NonZeroU32,
@@ -72,16 +84,61 @@ pub struct CollectionParams {
     /// Note: those payload values that are involved in filtering and are indexed - remain in RAM.
     #[serde(default = "default_on_disk_payload")]
     pub on_disk_payload: bool,
+    /// Alternative names of vectors. Requests may refer to a vector by its alias,
+    /// which allows to migrate the underlying vector without changes on the client side.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub vector_aliases: BTreeMap<String, String>,
+    /// Name of the vector to use if request does not specify any vector name
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_vector: Option<String>,
+    /// Payload key to group points by, when optimizers rebuild segments.
+    /// Points with the same value of the key are stored next to each other,
+    /// which improves locality of filtered search on mmap storage, e.g. for per-tenant filters.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub defrag_key: Option<PayloadKeyType>,
+    /// If true - content hash is computed for upserted points, which don't provide one.
+    /// Upserts of points with the same content hash as the stored one are skipped,
+    /// which avoids re-indexing of unchanged points.
+    #[serde(default)]
+    pub deduplicate_upserts: bool,
+    /// Max number of search requests to this collection, executed concurrently.
+    /// Further requests wait until one of the running searches completes,
+    /// so expensive searches in one collection can't occupy the whole search pool.
+    /// 0 - unlimited
+    #[serde(default)]
+    pub max_concurrent_searches: usize,
+    /// Payload key with the list of identity claims, allowed to read the point.
+    /// If set, reads of external clients are restricted to points, which list
+    /// at least one of the caller's claims under this key.
+    /// Claims are taken from the `x-acl-claims` header, set by the authenticating proxy.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub acl_key: Option<PayloadKeyType>,
+    /// Write ordering of updates, which don't specify it. Default: `weak`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_write_ordering: Option<WriteOrdering>,
+    /// Read consistency of requests, which don't specify it. Default: `1`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate]
+    pub default_read_consistency: Option<ReadConsistency>,
 }
 
 impl Anonymize for CollectionParams {
     fn anonymize(&self) -> Self {
         CollectionParams {
             vectors: self.vectors.anonymize(),
+            sparse_vectors: self.sparse_vectors.anonymize(),
             shard_number: self.shard_number,
             replication_factor: self.replication_factor,
             write_consistency_factor: self.write_consistency_factor,
             on_disk_payload: self.on_disk_payload,
+            vector_aliases: self.vector_aliases.anonymize(),
+            default_vector: self.default_vector.anonymize(),
+            defrag_key: self.defrag_key.anonymize(),
+            deduplicate_upserts: self.deduplicate_upserts,
+            max_concurrent_searches: self.max_concurrent_searches,
+            acl_key: self.acl_key.anonymize(),
+            default_write_ordering: self.default_write_ordering,
+            default_read_consistency: self.default_read_consistency,
         }
     }
 }
@@ -114,6 +171,14 @@ pub struct CollectionConfig {
     pub wal_config: WalConfig,
     #[serde(default)]
     pub quantization_config: Option<QuantizationConfig>,
+    /// Rules of the collection lifecycle. If none - collection is kept as is.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate]
+    pub lifecycle_config: Option<LifecycleConfig>,
+    /// Unix timestamp of the collection creation, in seconds.
+    /// Not known for collections, created before it was recorded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<u64>,
 }
 
 impl CollectionConfig {
@@ -146,9 +211,180 @@ impl CollectionConfig {
             validation::warn_validation_errors("Collection configuration file", errs);
         }
     }
+
+    /// Only quantized vectors are kept for the vector in optimized segments, originals are removed
+    pub fn is_quantized_only(&self, vector_name: &str) -> bool {
+        self.params
+            .vectors
+            .get_params(vector_name)
+            .and_then(|params| params.quantization_config.as_ref())
+            .or(self.quantization_config.as_ref())
+            .map_or(false, QuantizationConfig::is_quantized_only)
+    }
+
+    /// Some vectors keep only quantized values in optimized segments
+    pub fn has_quantized_only_vectors(&self) -> bool {
+        self.params
+            .vectors
+            .params_iter()
+            .any(|(vector_name, _)| self.is_quantized_only(vector_name))
+    }
+
+    /// Check that collection with quantized-only vectors is not replicated.
+    /// Replicas are recovered by streaming points with original vectors, which are not stored.
+    pub fn check_quantized_only_replication(&self) -> CollectionResult<()> {
+        if self.params.replication_factor.get() > 1 && self.has_quantized_only_vectors() {
+            return Err(CollectionError::BadRequest {
+                description: "Vectors, which keep only quantized values, \
+                              can't be used with replication_factor greater than 1"
+                    .to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Check that shards of the collection can be transferred to other peers.
+    /// Points are streamed with original vectors, which are not stored for quantized-only vectors.
+    pub fn check_quantized_only_transfer(&self) -> CollectionResult<()> {
+        if self.has_quantized_only_vectors() {
+            return Err(CollectionError::BadRequest {
+                description: "Collection has vectors, which keep only quantized values, \
+                              its shards can't be transferred"
+                    .to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Check that search doesn't require original values of quantized-only vector.
+    /// Vector name is expected to be resolved.
+    pub fn check_quantized_only_search(
+        &self,
+        vector_name: &str,
+        params: Option<&SearchParams>,
+    ) -> CollectionResult<()> {
+        if !self.is_quantized_only(vector_name) {
+            return Ok(());
+        }
+        let exact = params.map_or(false, |params| params.exact);
+        let quantization = params.and_then(|params| params.quantization);
+        let ignore = quantization.map_or(false, |quantization| quantization.ignore);
+        let rescore = quantization.map_or(false, |quantization| quantization.is_rescore_required());
+        if exact || ignore || rescore {
+            return Err(CollectionError::BadRequest {
+                description: format!(
+                    "Vector {vector_name} keeps only quantized vectors, \
+                     exact search, ignoring quantization and rescoring are not supported"
+                ),
+            });
+        }
+        Ok(())
+    }
 }
 
 impl CollectionParams {
+    /// Payload key with identity claims, allowed to read the point, if point-level ACL is enabled
+    pub fn enabled_acl_key(&self) -> Option<&str> {
+        self.acl_key.as_deref().filter(|key| !key.is_empty())
+    }
+
+    /// Write ordering of the update: the requested one, or the default of the collection
+    pub fn resolve_write_ordering(&self, ordering: Option<WriteOrdering>) -> WriteOrdering {
+        ordering.or(self.default_write_ordering).unwrap_or_default()
+    }
+
+    /// Read consistency of the request: the requested one, or the default of the collection
+    pub fn resolve_read_consistency(
+        &self,
+        read_consistency: Option<ReadConsistency>,
+    ) -> ReadConsistency {
+        read_consistency
+            .or(self.default_read_consistency)
+            .unwrap_or_default()
+    }
+
+    /// Get real name of the vector, referred by alias or by an empty (default) name
+    pub fn resolve_vector_name(&self, vector_name: &str) -> String {
+        let vector_name = match &self.default_vector {
+            Some(default_vector) if vector_name == DEFAULT_VECTOR_NAME => default_vector,
+            _ => vector_name,
+        };
+        match self.vector_aliases.get(vector_name) {
+            Some(aliased_name) => aliased_name.clone(),
+            None => vector_name.to_owned(),
+        }
+    }
+
+    /// Replace vector aliases in the selector with real names of the vectors
+    pub fn resolve_with_vector(&self, with_vector: WithVector) -> WithVector {
+        match with_vector {
+            WithVector::Bool(enable) => WithVector::Bool(enable),
+            WithVector::Selector(names) => WithVector::Selector(
+                names
+                    .iter()
+                    .map(|name| self.resolve_vector_name(name))
+                    .collect(),
+            ),
+            WithVector::Slices(slices) => WithVector::Slices(
+                slices
+                    .into_iter()
+                    .map(|mut slice| {
+                        slice.name = self.resolve_vector_name(&slice.name);
+                        slice
+                    })
+                    .collect(),
+            ),
+        }
+    }
+
+    /// Check that aliases and default vector refer to existing vectors
+    pub fn check_vector_aliases(&self) -> CollectionResult<()> {
+        for (alias, vector_name) in &self.vector_aliases {
+            if self.vectors.get_params(alias).is_some() {
+                return Err(CollectionError::bad_input(format!(
+                    "Alias {alias} conflicts with existing vector name"
+                )));
+            }
+            self.get_vector_params(vector_name)?;
+        }
+        if let Some(default_vector) = &self.default_vector {
+            self.get_vector_params(&self.resolve_vector_name(default_vector))?;
+        }
+        Ok(())
+    }
+
+    /// Check that sparse vectors don't share names with dense vectors
+    pub fn check_sparse_vectors(&self) -> CollectionResult<()> {
+        for vector_name in self.sparse_vectors.keys() {
+            if self.vectors.get_params(vector_name).is_some() {
+                return Err(CollectionError::bad_input(format!(
+                    "Sparse vector {vector_name} conflicts with existing vector name"
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Get all sparse vector params as `SparseVectorDataConfig`
+    pub fn get_all_sparse_vector_params(&self) -> HashMap<String, SparseVectorDataConfig> {
+        self.sparse_vectors
+            .keys()
+            .map(|name| (name.clone(), SparseVectorDataConfig::default()))
+            .collect()
+    }
+
+    /// Apply projection of the vector, if it is configured. Vector is expected under its real name.
+    pub fn project_vector(
+        &self,
+        vector_name: &str,
+        vector: VectorType,
+    ) -> CollectionResult<VectorType> {
+        match self.vectors.get_params(vector_name) {
+            Some(params) => params.project_vector(vector),
+            None => Ok(vector),
+        }
+    }
+
     pub fn get_vector_params(&self, vector_name: &str) -> CollectionResult<VectorParams> {
         self.vectors
             .get_params(vector_name)
@@ -181,6 +417,7 @@ impl CollectionParams {
                         distance: params.distance,
                         hnsw_config: params
                             .hnsw_config
+                            .clone()
                             .and_then(|c| c.update(collection_hnsw).ok()),
                         quantization_config: params
                             .quantization_config
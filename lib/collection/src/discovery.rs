@@ -0,0 +1,170 @@
+use std::future::Future;
+
+use segment::data_types::discovery::{ContextPair, DiscoveryQuery};
+use segment::data_types::vectors::{NamedVector, DEFAULT_VECTOR_NAME};
+use segment::types::{
+    Condition, Filter, HasIdCondition, ScoredPoint, WithPayloadInterface, WithVector,
+};
+use tokio::sync::RwLockReadGuard;
+
+use crate::collection::Collection;
+use crate::operations::consistency_params::ReadConsistency;
+use crate::operations::types::{
+    CollectionError, CollectionResult, DiscoverRequest, RecommendExample, SearchRequest,
+    SearchRequestBatch, UsingVector,
+};
+use crate::recommendations::{
+    example_vectors, fill_rescored_points, rescore_candidates, retrieve_examples,
+};
+
+/// Search points close to the target, respecting the context pairs of the request.
+///
+/// Function works in following stages:
+///
+/// - Retrieves vectors of the examples, given by ids, from this or the lookup collection
+/// - Searches candidates by the target and by positive example of each context pair,
+///   as a single batch
+/// - Rescores the candidates by the discovery query and selects the best ones
+///
+/// Candidates are only the points close to the target or to the positive examples,
+/// so points far from all of them are not found, even if they satisfy all context pairs.
+pub async fn discover_by<'a, F, Fut>(
+    request: DiscoverRequest,
+    collection: &Collection,
+    collection_by_name: F,
+    read_consistency: Option<ReadConsistency>,
+) -> CollectionResult<Vec<ScoredPoint>>
+where
+    F: Fn(String) -> Fut,
+    Fut: Future<Output = Option<RwLockReadGuard<'a, Collection>>>,
+{
+    if request.limit == 0 {
+        return Ok(vec![]);
+    }
+
+    let vector_name = match &request.using {
+        None => DEFAULT_VECTOR_NAME,
+        Some(UsingVector::Name(name)) => name,
+    };
+    let lookup_vector_name = match &request.lookup_from {
+        None => vector_name.to_owned(),
+        Some(lookup_from) => lookup_from
+            .vector
+            .clone()
+            .unwrap_or_else(|| DEFAULT_VECTOR_NAME.to_owned()),
+    };
+
+    let example_ids: Vec<_> = request
+        .examples()
+        .filter_map(RecommendExample::as_point_id)
+        .collect();
+
+    let records = retrieve_examples(
+        collection,
+        collection_by_name,
+        request.lookup_from.as_ref(),
+        example_ids.clone(),
+        &lookup_vector_name,
+        read_consistency,
+    )
+    .await?;
+
+    let (resolved_vector_name, vector_params) = {
+        let collection_params = &collection.collection_config.read().await.params;
+        let resolved_vector_name = collection_params.resolve_vector_name(vector_name);
+        let vector_params = collection_params
+            .get_vector_params(&resolved_vector_name)
+            .map_err(|_| {
+                CollectionError::bad_request(format!(
+                    "Discovery is not supported by vector {vector_name}"
+                ))
+            })?;
+        (resolved_vector_name, vector_params)
+    };
+
+    let vectors = |example: &RecommendExample| {
+        example_vectors(
+            [example],
+            |point_id| records.get(&point_id),
+            &lookup_vector_name,
+            Some(&vector_params),
+        )
+        .map(|vectors| vectors.into_iter().next())
+    };
+    let missing_vector = || {
+        CollectionError::bad_request(format!("Example point has no vector {lookup_vector_name}"))
+    };
+
+    let target = vectors(&request.target)?.ok_or_else(missing_vector)?;
+    let pairs = request
+        .context
+        .iter()
+        .map(|pair| {
+            Ok(ContextPair {
+                positive: vectors(&pair.positive)?.ok_or_else(missing_vector)?,
+                negative: vectors(&pair.negative)?.ok_or_else(missing_vector)?,
+            })
+        })
+        .collect::<CollectionResult<Vec<_>>>()?;
+    let query = DiscoveryQuery { target, pairs };
+
+    let filter = Filter {
+        should: None,
+        must: request
+            .filter
+            .clone()
+            .map(|filter| vec![Condition::Filter(filter)]),
+        must_not: Some(vec![Condition::HasId(HasIdCondition {
+            has_id: example_ids.into_iter().collect(),
+        })]),
+    };
+
+    // Points in the preferred half of the context might be far from the target,
+    // so positive examples provide candidates too
+    let searches = std::iter::once(&query.target)
+        .chain(query.pairs.iter().map(|pair| &pair.positive))
+        .map(|vector| SearchRequest {
+            vector: NamedVector {
+                name: vector_name.to_string(),
+                vector: vector.clone(),
+            }
+            .into(),
+            filter: Some(filter.clone()),
+            with_payload: Some(WithPayloadInterface::Bool(false)),
+            with_vector: Some(WithVector::Selector(vec![resolved_vector_name.clone()])),
+            params: request.params,
+            limit: request.offset + request.limit,
+            score_threshold: None,
+            offset: 0,
+            with_highlights: None,
+            with_geo_distance: None,
+        })
+        .collect();
+
+    let candidates = collection
+        .search_batch_by_stored_vectors(
+            SearchRequestBatch { searches },
+            read_consistency,
+            None,
+            None,
+        )
+        .await?;
+
+    let distance = vector_params.distance;
+    let points = rescore_candidates(candidates, &resolved_vector_name, |vector| {
+        query.score(vector, distance)
+    })
+    .into_iter()
+    .skip(request.offset)
+    .take(request.limit)
+    .collect();
+
+    fill_rescored_points(
+        collection,
+        points,
+        request.with_payload,
+        request.with_vector,
+        read_consistency,
+    )
+    .await
+}
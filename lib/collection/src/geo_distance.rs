@@ -0,0 +1,224 @@
+use std::collections::HashMap;
+
+use segment::types::{
+    Condition, Filter, GeoPoint, Payload, PayloadKeyType, ScoredPoint, WithPayloadInterface,
+    WithVector,
+};
+use serde_json::Value;
+
+use crate::collection::Collection;
+use crate::operations::consistency_params::ReadConsistency;
+use crate::operations::types::{
+    CollectionResult, GeoDistanceOrder, PointRequest, Record, SearchRequest,
+};
+use crate::shards::shard::ShardId;
+
+/// Field and center of the first geo condition of the filter, which found points are required
+/// or may satisfy. Center of the bounding box is used for the `geo_bounding_box` condition.
+fn geo_reference(filter: &Filter) -> Option<(PayloadKeyType, GeoPoint)> {
+    filter
+        .must
+        .iter()
+        .chain(&filter.should)
+        .flatten()
+        .find_map(|condition| match condition {
+            Condition::Field(field) => {
+                let center = field
+                    .geo_radius
+                    .as_ref()
+                    .map(|radius| radius.center.clone())
+                    .or_else(|| field.geo_bounding_box.as_ref().map(|bbox| bbox.center()))?;
+                Some((field.key.clone(), center))
+            }
+            Condition::Filter(filter) => geo_reference(filter),
+            Condition::IsEmpty(_) | Condition::IsNull(_) | Condition::HasId(_) => None,
+        })
+}
+
+/// Distance in meters from the `origin` to the closest geo point in the `key` field of the payload
+pub fn payload_geo_distance(payload: &Payload, key: &str, origin: &GeoPoint) -> Option<f64> {
+    payload
+        .get_value(key)
+        .flat_map(|value| match value {
+            Value::Array(values) => values.iter().collect(),
+            value => vec![value],
+        })
+        .filter_map(|value| serde_json::from_value::<GeoPoint>(value.clone()).ok())
+        .map(|point| origin.distance(&point))
+        .min_by(|a, b| a.total_cmp(b))
+}
+
+/// Fill distances from the center of the geo condition to the found points
+/// for the searches, which request them.
+pub async fn add_geo_distances(
+    collection: &Collection,
+    searches: &[SearchRequest],
+    results: &mut [Vec<ScoredPoint>],
+    read_consistency: Option<ReadConsistency>,
+) -> CollectionResult<()> {
+    let references: Vec<_> = searches
+        .iter()
+        .map(|search| match (search.with_geo_distance, &search.filter) {
+            (Some(true), Some(filter)) => geo_reference(filter),
+            _ => None,
+        })
+        .collect();
+    if references.iter().all(Option::is_none) {
+        return Ok(());
+    }
+
+    let mut keys: Vec<_> = references.iter().flatten().map(|(key, _)| key).collect();
+    keys.sort();
+    keys.dedup();
+    let mut ids: Vec<_> = results
+        .iter()
+        .zip(&references)
+        .filter(|(_, reference)| reference.is_some())
+        .flat_map(|(points, _)| points.iter().map(|point| point.id))
+        .collect();
+    ids.sort_unstable();
+    ids.dedup();
+    let payloads: HashMap<_, _> = collection
+        .retrieve(
+            PointRequest {
+                ids,
+                with_payload: Some(WithPayloadInterface::Fields(
+                    keys.into_iter().cloned().collect(),
+                )),
+                with_vector: Default::default(),
+            },
+            read_consistency,
+            None,
+        )
+        .await?
+        .into_iter()
+        .filter_map(|record| {
+            let payload = record.payload?.into_payload();
+            Some(payload.map(|payload| (record.id, payload)))
+        })
+        .collect::<Result<_, _>>()?;
+
+    for (points, reference) in results.iter_mut().zip(&references) {
+        let (key, center) = match reference {
+            Some(reference) => reference,
+            None => continue,
+        };
+        for point in points {
+            point.geo_distance = payloads
+                .get(&point.id)
+                .and_then(|payload| payload_geo_distance(payload, key, center));
+        }
+    }
+    Ok(())
+}
+
+/// Read points nearest to the origin of the geo order, along with distances to them.
+///
+/// Nearest points are selected by the geo index of local replicas,
+/// then the requested payload and vectors are retrieved for them.
+#[allow(clippy::too_many_arguments)]
+pub async fn scroll_by_geo_distance(
+    collection: &Collection,
+    geo_order: &GeoDistanceOrder,
+    limit: usize,
+    filter: Option<&Filter>,
+    with_payload: WithPayloadInterface,
+    with_vector: WithVector,
+    read_consistency: Option<ReadConsistency>,
+    shard_selection: Option<ShardId>,
+    shard_key: Option<ShardId>,
+) -> CollectionResult<Vec<Record>> {
+    let nearest = collection
+        .nearest_points(geo_order, limit, filter, shard_selection.or(shard_key))
+        .await?;
+
+    let mut records: HashMap<_, _> = collection
+        .retrieve(
+            PointRequest {
+                ids: nearest.iter().map(|(point_id, _)| *point_id).collect(),
+                with_payload: Some(with_payload),
+                with_vector,
+            },
+            read_consistency,
+            shard_selection,
+        )
+        .await?
+        .into_iter()
+        .map(|record| (record.id, record))
+        .collect();
+
+    // Points might get deleted between reading the index and retrieve
+    let points = nearest
+        .into_iter()
+        .filter_map(|(point_id, distance)| {
+            records.remove(&point_id).map(|record| Record {
+                geo_distance: Some(distance),
+                ..record
+            })
+        })
+        .collect();
+    Ok(points)
+}
+
+#[cfg(test)]
+mod tests {
+    use segment::types::{FieldCondition, GeoBoundingBox, GeoRadius};
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_geo_reference() {
+        let berlin = GeoPoint {
+            lon: 13.41053,
+            lat: 52.52437,
+        };
+        let radius = Condition::Field(FieldCondition::new_geo_radius(
+            "location".to_owned(),
+            GeoRadius {
+                center: berlin.clone(),
+                radius: 1000.0,
+            },
+        ));
+        let filter = Filter {
+            should: None,
+            must: Some(vec![Condition::Filter(Filter::new_must(radius.clone()))]),
+            must_not: None,
+        };
+        assert_eq!(
+            geo_reference(&filter),
+            Some(("location".to_owned(), berlin.clone()))
+        );
+
+        let filter = Filter::new_must_not(radius);
+        assert_eq!(geo_reference(&filter), None);
+
+        let filter = Filter::new_must(Condition::Field(FieldCondition::new_geo_bounding_box(
+            "location".to_owned(),
+            GeoBoundingBox {
+                top_left: GeoPoint { lon: 0.0, lat: 2.0 },
+                bottom_right: GeoPoint { lon: 2.0, lat: 0.0 },
+            },
+        )));
+        assert_eq!(
+            geo_reference(&filter),
+            Some(("location".to_owned(), GeoPoint { lon: 1.0, lat: 1.0 }))
+        );
+
+        let payload: Payload = serde_json::from_value(json!({
+            "location": [
+                {"lon": 13.0, "lat": 52.52437},
+                {"lon": 13.41053, "lat": 52.53},
+            ],
+        }))
+        .unwrap();
+        let nearest = GeoPoint {
+            lon: 13.41053,
+            lat: 52.53,
+        };
+        let distance = payload_geo_distance(&payload, "location", &berlin).unwrap();
+        assert_eq!(distance, berlin.distance(&nearest));
+        assert!(distance < 1000.0);
+        assert_eq!(payload_geo_distance(&payload, "other", &berlin), None);
+    }
+}
@@ -0,0 +1,370 @@
+use std::collections::HashMap;
+use std::future::Future;
+
+use segment::types::{
+    Condition, Filter, IsEmptyCondition, PayloadField, PointIdType, RecordPayload, ScoredPoint,
+    WithPayloadInterface, WithVector,
+};
+use serde_json::Value;
+use tokio::sync::RwLockReadGuard;
+use uuid::Uuid;
+
+use crate::collection::Collection;
+use crate::operations::consistency_params::ReadConsistency;
+use crate::operations::types::{
+    CollectionError, CollectionResult, GroupId, GroupsResult, PointGroup, PointRequest,
+    SearchGroupsRequest, SearchRequest, WithLookup,
+};
+
+/// Max number of searches, made to fill the groups.
+/// Each next search continues from the offset, where the previous one stopped.
+const MAX_GROUP_SEARCHES: usize = 5;
+
+/// Values of the `key` field of the point, which can be used as a group id
+fn group_ids(point: &ScoredPoint, key: &str) -> Vec<GroupId> {
+    let payload = match &point.payload {
+        Some(payload) => payload,
+        None => return vec![],
+    };
+    let mut ids = vec![];
+    for value in payload.get_value(key) {
+        let values = match value {
+            Value::Array(values) => values.iter().collect(),
+            value => vec![value],
+        };
+        for value in values {
+            let id = match value {
+                Value::String(value) => GroupId::String(value.clone()),
+                Value::Number(value) => match value.as_i64() {
+                    Some(value) => GroupId::Number(value),
+                    None => continue,
+                },
+                _ => continue,
+            };
+            if !ids.contains(&id) {
+                ids.push(id);
+            }
+        }
+    }
+    ids
+}
+
+/// Id of the point in the lookup collection, which corresponds to the group.
+/// Only non-negative numbers and UUID strings can be point ids.
+fn lookup_point_id(group_id: &GroupId) -> Option<PointIdType> {
+    match group_id {
+        GroupId::Number(id) => u64::try_from(*id).ok().map(PointIdType::NumId),
+        GroupId::String(id) => Uuid::parse_str(id).ok().map(PointIdType::Uuid),
+    }
+}
+
+/// Collects points into groups in the order of search results
+struct GroupsAggregator {
+    groups: Vec<PointGroup>,
+    group_positions: HashMap<GroupId, usize>,
+    max_groups: usize,
+    group_size: usize,
+    group_by: String,
+}
+
+impl GroupsAggregator {
+    fn new(max_groups: usize, group_size: usize, group_by: String) -> Self {
+        Self {
+            groups: Vec::with_capacity(max_groups),
+            group_positions: HashMap::new(),
+            max_groups,
+            group_size,
+            group_by,
+        }
+    }
+
+    /// Points are expected to be ordered from the best to the worst,
+    /// so groups, which appear first, are the best ones
+    fn add_points(&mut self, points: Vec<ScoredPoint>) {
+        for point in points {
+            for group_id in group_ids(&point, &self.group_by) {
+                let position = match self.group_positions.get(&group_id) {
+                    Some(position) => *position,
+                    None if self.groups.len() < self.max_groups => {
+                        self.groups.push(PointGroup {
+                            hits: Vec::with_capacity(self.group_size),
+                            id: group_id.clone(),
+                            lookup: None,
+                        });
+                        self.group_positions.insert(group_id, self.groups.len() - 1);
+                        self.groups.len() - 1
+                    }
+                    None => continue,
+                };
+                let group = &mut self.groups[position];
+                if group.hits.len() < self.group_size
+                    && group.hits.iter().all(|hit| hit.id != point.id)
+                {
+                    group.hits.push(point.clone());
+                }
+            }
+        }
+    }
+
+    fn is_full(&self) -> bool {
+        self.groups.len() == self.max_groups
+            && self
+                .groups
+                .iter()
+                .all(|group| group.hits.len() == self.group_size)
+    }
+
+    fn into_groups(self) -> Vec<PointGroup> {
+        self.groups
+    }
+}
+
+/// Search points and group them by the value of the `group_by` payload field.
+///
+/// Function works in following stages:
+///
+/// - Searches for points, which have the `group_by` field, with its value only in the payload
+/// - Distributes found points into groups, until the groups are filled or points are exhausted
+/// - Retrieves the requested payload and vectors for the points of the resulting groups
+/// - Looks up points with ids of the groups in the `with_lookup` collection, if requested
+///
+/// # Arguments
+///
+/// * `collection_by_name` - function to retrieve collection by name, used for the lookup
+pub async fn group_by<'a, F, Fut>(
+    request: SearchGroupsRequest,
+    collection: &Collection,
+    collection_by_name: F,
+    read_consistency: Option<ReadConsistency>,
+) -> CollectionResult<GroupsResult>
+where
+    F: Fn(String) -> Fut,
+    Fut: Future<Output = Option<RwLockReadGuard<'a, Collection>>>,
+{
+    let SearchGroupsRequest {
+        vector,
+        filter,
+        params,
+        with_payload,
+        with_vector,
+        score_threshold,
+        group_by,
+        group_size,
+        limit,
+        with_lookup,
+    } = request;
+
+    let filter = Filter {
+        should: None,
+        must: filter.map(|filter| vec![Condition::Filter(filter)]),
+        must_not: Some(vec![Condition::IsEmpty(IsEmptyCondition {
+            is_empty: PayloadField {
+                key: group_by.clone(),
+            },
+        })]),
+    };
+
+    let batch_size = limit * group_size;
+    let mut aggregator = GroupsAggregator::new(limit, group_size, group_by.clone());
+    let mut offset = 0;
+    for _ in 0..MAX_GROUP_SEARCHES {
+        let points = collection
+            .search(
+                SearchRequest {
+                    vector: vector.clone(),
+                    filter: Some(filter.clone()),
+                    params,
+                    limit: batch_size,
+                    offset,
+                    with_payload: Some(WithPayloadInterface::Fields(vec![group_by.clone()])),
+                    with_vector: None,
+                    score_threshold,
+                    with_highlights: None,
+                    with_geo_distance: None,
+                },
+                read_consistency,
+                None,
+            )
+            .await?;
+        let found = points.len();
+        aggregator.add_points(points);
+        if aggregator.is_full() || found < batch_size {
+            break;
+        }
+        offset += found;
+    }
+    let mut groups = aggregator.into_groups();
+
+    if with_payload.is_none() && with_vector.is_none() {
+        for hit in groups.iter_mut().flat_map(|group| group.hits.iter_mut()) {
+            hit.payload = None;
+        }
+    } else {
+        fill_hits(
+            &mut groups,
+            collection,
+            with_payload,
+            with_vector.unwrap_or_default(),
+            read_consistency,
+        )
+        .await?;
+    }
+
+    if let Some(with_lookup) = with_lookup {
+        let with_lookup = WithLookup::from(with_lookup);
+        let lookup_collection = collection_by_name(with_lookup.collection_name.clone())
+            .await
+            .ok_or_else(|| CollectionError::NotFound {
+                what: format!("Collection {}", with_lookup.collection_name),
+            })?;
+        lookup_groups(
+            &mut groups,
+            &lookup_collection,
+            with_lookup,
+            read_consistency,
+        )
+        .await?;
+    }
+
+    Ok(GroupsResult { groups })
+}
+
+/// Load payload and vectors only for the points, which made it into the groups
+async fn fill_hits(
+    groups: &mut [PointGroup],
+    collection: &Collection,
+    with_payload: Option<WithPayloadInterface>,
+    with_vector: WithVector,
+    read_consistency: Option<ReadConsistency>,
+) -> CollectionResult<()> {
+    let mut ids: Vec<_> = groups
+        .iter()
+        .flat_map(|group| group.hits.iter().map(|hit| hit.id))
+        .collect();
+    ids.sort_unstable();
+    ids.dedup();
+    let records: HashMap<_, _> = collection
+        .retrieve(
+            PointRequest {
+                ids,
+                with_payload,
+                with_vector,
+            },
+            read_consistency,
+            None,
+        )
+        .await?
+        .into_iter()
+        .map(|record| (record.id, record))
+        .collect();
+    for hit in groups.iter_mut().flat_map(|group| group.hits.iter_mut()) {
+        let record = records.get(&hit.id);
+        hit.payload = record
+            .and_then(|record| record.payload.clone())
+            .map(RecordPayload::into_payload)
+            .transpose()?;
+        hit.vector = record.and_then(|record| record.vector.clone());
+    }
+    Ok(())
+}
+
+/// Attach points of the lookup collection to the groups with the same ids
+async fn lookup_groups(
+    groups: &mut [PointGroup],
+    lookup_collection: &Collection,
+    with_lookup: WithLookup,
+    read_consistency: Option<ReadConsistency>,
+) -> CollectionResult<()> {
+    let mut ids: Vec<_> = groups
+        .iter()
+        .filter_map(|group| lookup_point_id(&group.id))
+        .collect();
+    if ids.is_empty() {
+        return Ok(());
+    }
+    ids.sort_unstable();
+    ids.dedup();
+    let records: HashMap<_, _> = lookup_collection
+        .retrieve(
+            PointRequest {
+                ids,
+                with_payload: Some(
+                    with_lookup
+                        .with_payload
+                        .unwrap_or(WithPayloadInterface::Bool(true)),
+                ),
+                with_vector: with_lookup.with_vectors.unwrap_or_default(),
+            },
+            read_consistency,
+            None,
+        )
+        .await?
+        .into_iter()
+        .map(|record| (record.id, record))
+        .collect();
+    for group in groups {
+        group.lookup = lookup_point_id(&group.id).and_then(|id| records.get(&id).cloned());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use segment::types::Payload;
+    use serde_json::json;
+
+    use super::*;
+
+    fn scored(id: u64, score: f32, payload: Value) -> ScoredPoint {
+        let payload: Payload = serde_json::from_value(payload).unwrap();
+        ScoredPoint {
+            id: id.into(),
+            version: 0,
+            score,
+            payload: Some(payload),
+            vector: None,
+            highlights: None,
+            geo_distance: None,
+        }
+    }
+
+    #[test]
+    fn test_groups_aggregator() {
+        let mut aggregator = GroupsAggregator::new(2, 2, "doc".to_owned());
+        aggregator.add_points(vec![
+            scored(1, 0.9, json!({"doc": "a"})),
+            scored(2, 0.8, json!({"doc": "a"})),
+            scored(3, 0.7, json!({"doc": "a"})),
+            scored(4, 0.6, json!({"doc": [7, "a"]})),
+            scored(5, 0.5, json!({"doc": "b"})),
+            scored(6, 0.4, json!({"other": "c"})),
+        ]);
+        assert!(!aggregator.is_full());
+
+        aggregator.add_points(vec![scored(7, 0.3, json!({"doc": 7}))]);
+        assert!(aggregator.is_full());
+
+        let groups = aggregator.into_groups();
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].id, GroupId::String("a".to_owned()));
+        let hits: Vec<_> = groups[0].hits.iter().map(|hit| hit.id).collect();
+        assert_eq!(hits, [1, 2].map(PointIdType::from));
+        assert_eq!(groups[1].id, GroupId::Number(7));
+        let hits: Vec<_> = groups[1].hits.iter().map(|hit| hit.id).collect();
+        assert_eq!(hits, [4, 7].map(PointIdType::from));
+    }
+    #[test]
+    fn test_lookup_point_id() {
+        assert_eq!(
+            lookup_point_id(&GroupId::Number(7)),
+            Some(PointIdType::NumId(7))
+        );
+        assert_eq!(lookup_point_id(&GroupId::Number(-1)), None);
+        let uuid = "550e8400-e29b-41d4-a716-446655440000";
+        assert_eq!(
+            lookup_point_id(&GroupId::String(uuid.to_owned())),
+            Some(PointIdType::Uuid(Uuid::parse_str(uuid).unwrap()))
+        );
+        assert_eq!(lookup_point_id(&GroupId::String("doc".to_owned())), None);
+    }
+}
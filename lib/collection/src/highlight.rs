@@ -0,0 +1,199 @@
+use std::collections::HashMap;
+
+use segment::data_types::text_index::TextIndexParams;
+use segment::index::field_index::full_text_index::highlight::highlight_text;
+use segment::types::{
+    Condition, Filter, Highlight, Match, Payload, PayloadKeyType, PayloadSchemaParams, ScoredPoint,
+    WithPayloadInterface,
+};
+use serde_json::Value;
+
+use crate::collection::Collection;
+use crate::operations::consistency_params::ReadConsistency;
+use crate::operations::types::{CollectionResult, PointRequest, SearchRequest};
+
+/// Full-text conditions of the filter as pairs of the field and the query text.
+///
+/// Conditions of `must_not` are skipped, as found points can't contain their words.
+fn text_conditions(filter: &Filter, conditions: &mut Vec<(PayloadKeyType, String)>) {
+    for condition in filter.must.iter().chain(&filter.should).flatten() {
+        match condition {
+            Condition::Field(field) => {
+                if let Some(Match::Text(text)) = &field.r#match {
+                    conditions.push((field.key.clone(), text.text.clone()));
+                }
+            }
+            Condition::Filter(filter) => text_conditions(filter, conditions),
+            Condition::IsEmpty(_) | Condition::IsNull(_) | Condition::HasId(_) => {}
+        }
+    }
+}
+
+/// Full-text conditions of the search, if it requests highlights
+fn search_text_conditions(search: &SearchRequest) -> Vec<(PayloadKeyType, String)> {
+    let mut conditions = vec![];
+    if let (Some(true), Some(filter)) = (search.with_highlights, &search.filter) {
+        text_conditions(filter, &mut conditions);
+    }
+    conditions.sort();
+    conditions.dedup();
+    conditions
+}
+
+/// Highlights of the words of `payload`, matched by the full-text conditions.
+///
+/// Text of each field is split into words according to its full-text index params.
+/// Fields without the full-text index are processed with the default params.
+pub fn point_highlights(
+    payload: &Payload,
+    conditions: &[(PayloadKeyType, String)],
+    text_params: &HashMap<PayloadKeyType, TextIndexParams>,
+) -> Vec<Highlight> {
+    let default_params = TextIndexParams::default();
+    let mut highlights = vec![];
+    for (key, query) in conditions {
+        let params = text_params.get(key).unwrap_or(&default_params);
+        let texts = payload
+            .get_value(key)
+            .flat_map(|value| match value {
+                Value::Array(values) => values.iter().collect(),
+                value => vec![value],
+            })
+            .filter_map(|value| value.as_str());
+        for (index, text) in texts.enumerate() {
+            for range in highlight_text(text, query, params) {
+                let highlight = Highlight {
+                    key: key.clone(),
+                    index,
+                    start: range.start,
+                    end: range.end,
+                };
+                if !highlights.contains(&highlight) {
+                    highlights.push(highlight);
+                }
+            }
+        }
+    }
+    highlights
+}
+
+/// Fill highlights of the found points for the searches, which request them.
+///
+/// Only the text fields of the full-text conditions are retrieved,
+/// regardless of the payload requested by the search.
+pub async fn add_highlights(
+    collection: &Collection,
+    searches: &[SearchRequest],
+    results: &mut [Vec<ScoredPoint>],
+    read_consistency: Option<ReadConsistency>,
+) -> CollectionResult<()> {
+    let conditions: Vec<_> = searches.iter().map(search_text_conditions).collect();
+    if conditions.iter().all(|conditions| conditions.is_empty()) {
+        return Ok(());
+    }
+
+    let mut keys: Vec<_> = conditions.iter().flatten().map(|(key, _)| key).collect();
+    keys.sort();
+    keys.dedup();
+    let text_params: HashMap<_, _> = collection
+        .info(None)
+        .await?
+        .payload_schema
+        .into_iter()
+        .filter_map(|(key, info)| {
+            info.params
+                .map(|PayloadSchemaParams::Text(params)| (key, params))
+        })
+        .collect();
+
+    let mut ids: Vec<_> = results
+        .iter()
+        .zip(&conditions)
+        .filter(|(_, conditions)| !conditions.is_empty())
+        .flat_map(|(points, _)| points.iter().map(|point| point.id))
+        .collect();
+    ids.sort_unstable();
+    ids.dedup();
+    let payloads: HashMap<_, _> = collection
+        .retrieve(
+            PointRequest {
+                ids,
+                with_payload: Some(WithPayloadInterface::Fields(
+                    keys.into_iter().cloned().collect(),
+                )),
+                with_vector: Default::default(),
+            },
+            read_consistency,
+            None,
+        )
+        .await?
+        .into_iter()
+        .filter_map(|record| {
+            let payload = record.payload?.into_payload();
+            Some(payload.map(|payload| (record.id, payload)))
+        })
+        .collect::<Result<_, _>>()?;
+
+    for (points, conditions) in results.iter_mut().zip(&conditions) {
+        if conditions.is_empty() {
+            continue;
+        }
+        for point in points {
+            let highlights = payloads
+                .get(&point.id)
+                .map(|payload| point_highlights(payload, conditions, &text_params))
+                .unwrap_or_default();
+            point.highlights = Some(highlights);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use segment::types::FieldCondition;
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_point_highlights() {
+        let filter = Filter {
+            should: Some(vec![Condition::Field(FieldCondition::new_match(
+                "title".to_owned(),
+                Match::Text("rust".to_owned().into()),
+            ))]),
+            must: Some(vec![Condition::Filter(Filter::new_must(Condition::Field(
+                FieldCondition::new_match("tags".to_owned(), Match::Text("fast".to_owned().into())),
+            )))]),
+            must_not: Some(vec![Condition::Field(FieldCondition::new_match(
+                "title".to_owned(),
+                Match::Text("slow".to_owned().into()),
+            ))]),
+        };
+        let mut conditions = vec![];
+        text_conditions(&filter, &mut conditions);
+        assert_eq!(
+            conditions,
+            vec![
+                ("tags".to_owned(), "fast".to_owned()),
+                ("title".to_owned(), "rust".to_owned()),
+            ]
+        );
+
+        let payload: Payload = serde_json::from_value(json!({
+            "title": "Rust is fast, rust is slow",
+            "tags": ["safe", "Fast"],
+        }))
+        .unwrap();
+        let highlights = point_highlights(&payload, &conditions, &HashMap::new());
+        let highlights: Vec<_> = highlights
+            .iter()
+            .map(|highlight| (highlight.key.as_str(), highlight.index, highlight.start))
+            .collect();
+        assert_eq!(
+            highlights,
+            vec![("tags", 1, 0), ("title", 0, 0), ("title", 0, 14)]
+        );
+    }
+}
@@ -0,0 +1,182 @@
+use std::collections::HashMap;
+
+use segment::types::{Condition, Filter, PointIdType, ScoreType, ScoredPoint};
+
+use crate::collection::Collection;
+use crate::operations::consistency_params::ReadConsistency;
+use crate::operations::types::{
+    CollectionResult, Fusion, QueryRequest, SearchRequest, SearchRequestBatch,
+};
+
+/// Constant of reciprocal rank fusion, which smooths the difference between top ranks
+pub const RRF_K: ScoreType = 60.0;
+
+fn merge_filters(filter: Option<Filter>, other: Option<Filter>) -> Option<Filter> {
+    match (filter, other) {
+        (None, other) => other,
+        (filter, None) => filter,
+        (Some(filter), Some(other)) => Some(Filter {
+            should: None,
+            must: Some(vec![Condition::Filter(filter), Condition::Filter(other)]),
+            must_not: None,
+        }),
+    }
+}
+
+/// Combine ranked results of the prefetches into a single ranking, best points first
+///
+/// Each result list contributes to the fused score of the points it contains,
+/// multiplied by the weight of the corresponding prefetch.
+pub fn fuse(
+    results: Vec<Vec<ScoredPoint>>,
+    weights: &[ScoreType],
+    fusion: Fusion,
+) -> Vec<ScoredPoint> {
+    let mut fused: HashMap<PointIdType, ScoredPoint> = HashMap::new();
+    for (points, weight) in results.into_iter().zip(weights) {
+        // Results are ordered best first, whatever the order of the distance is
+        let best = points.first().map_or(0.0, |point| point.score);
+        let worst = points.last().map_or(0.0, |point| point.score);
+        for (rank, point) in points.into_iter().enumerate() {
+            let score = match fusion {
+                Fusion::Rrf => weight / (RRF_K + (rank + 1) as ScoreType),
+                Fusion::WeightedSum => weight * point.score,
+                Fusion::NormalizedSum if best == worst => *weight,
+                Fusion::NormalizedSum => weight * (point.score - worst) / (best - worst),
+            };
+            fused
+                .entry(point.id)
+                .and_modify(|fused_point| fused_point.score += score)
+                .or_insert(ScoredPoint { score, ..point });
+        }
+    }
+    let mut fused: Vec<_> = fused.into_values().collect();
+    // Ties are broken by id to keep pagination stable
+    fused.sort_unstable_by(|a, b| b.cmp(a).then_with(|| a.id.cmp(&b.id)));
+    fused
+}
+
+/// Search points by several prefetch queries and fuse their results.
+///
+/// All prefetches are executed as a single search batch, each one retrieving
+/// `limit + offset` candidates unless specified otherwise.
+/// Filter of the query is applied to every prefetch.
+pub async fn query_by(
+    request: QueryRequest,
+    collection: &Collection,
+    read_consistency: Option<ReadConsistency>,
+) -> CollectionResult<Vec<ScoredPoint>> {
+    if request.limit == 0 {
+        return Ok(vec![]);
+    }
+    let QueryRequest {
+        prefetch,
+        fusion,
+        filter,
+        limit,
+        offset,
+        with_payload,
+        with_vector,
+        score_threshold,
+    } = request;
+
+    let weights: Vec<_> = prefetch
+        .iter()
+        .map(|prefetch| prefetch.weight.unwrap_or(1.0))
+        .collect();
+    let searches = prefetch
+        .into_iter()
+        .map(|prefetch| SearchRequest {
+            vector: prefetch.vector,
+            filter: merge_filters(prefetch.filter, filter.clone()),
+            params: prefetch.params,
+            limit: prefetch.limit.unwrap_or(limit + offset),
+            offset: 0,
+            with_payload: with_payload.clone(),
+            with_vector: with_vector.clone(),
+            score_threshold: prefetch.score_threshold,
+            with_highlights: None,
+            with_geo_distance: None,
+        })
+        .collect();
+
+    let results = collection
+        .search_batch(SearchRequestBatch { searches }, read_consistency, None)
+        .await?;
+
+    Ok(fuse(results, &weights, fusion)
+        .into_iter()
+        .take_while(|point| score_threshold.map_or(true, |threshold| point.score >= threshold))
+        .skip(offset)
+        .take(limit)
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scored(id: u64, score: ScoreType) -> ScoredPoint {
+        ScoredPoint {
+            id: id.into(),
+            version: 0,
+            score,
+            payload: None,
+            vector: None,
+            highlights: None,
+            geo_distance: None,
+        }
+    }
+
+    fn ids(points: &[ScoredPoint]) -> Vec<PointIdType> {
+        points.iter().map(|point| point.id).collect()
+    }
+
+    fn point_ids(ids: &[u64]) -> Vec<PointIdType> {
+        ids.iter().map(|&id| id.into()).collect()
+    }
+
+    #[test]
+    fn test_fusion() {
+        let dense = vec![scored(1, 0.9), scored(2, 0.8), scored(3, 0.1)];
+        let sparse = vec![scored(3, 12.0), scored(2, 10.0)];
+
+        let fused = fuse(
+            vec![dense.clone(), sparse.clone()],
+            &[1.0, 1.0],
+            Fusion::Rrf,
+        );
+        assert_eq!(ids(&fused), point_ids(&[3, 2, 1]));
+        assert_eq!(fused[0].score, 1.0 / 63.0 + 1.0 / 61.0);
+
+        // Raw scores of the sparse search dominate without weights
+        let fused = fuse(
+            vec![dense.clone(), sparse.clone()],
+            &[1.0, 1.0],
+            Fusion::WeightedSum,
+        );
+        assert_eq!(ids(&fused), point_ids(&[3, 2, 1]));
+
+        let fused = fuse(
+            vec![dense.clone(), sparse.clone()],
+            &[1.0, 0.005],
+            Fusion::WeightedSum,
+        );
+        assert_eq!(ids(&fused), point_ids(&[1, 2, 3]));
+
+        // Scales of the scores don't matter after normalization
+        let fused = fuse(
+            vec![dense.clone(), sparse.clone()],
+            &[1.0, 1.0],
+            Fusion::NormalizedSum,
+        );
+        assert_eq!(ids(&fused), point_ids(&[1, 3, 2]));
+        assert_eq!(fused[0].score, 1.0);
+        assert_eq!(fused[1].score, 1.0);
+
+        // Euclid distances: smaller is better, but the first point is the best one
+        let euclid = vec![scored(3, 0.5), scored(1, 2.5)];
+        let fused = fuse(vec![dense, euclid], &[1.0, 2.0], Fusion::NormalizedSum);
+        assert_eq!(ids(&fused), point_ids(&[3, 1, 2]));
+    }
+}
@@ -5,15 +5,23 @@ pub mod collection_manager;
 pub mod collection_state;
 pub mod common;
 pub mod config;
+pub mod discovery;
+pub mod geo_distance;
+pub mod grouping;
 pub mod hash_ring;
+pub mod highlight;
+pub mod hybrid_query;
 pub mod operations;
 pub mod optimizers_builder;
+pub mod order_by;
 pub mod recommendations;
 pub mod save_on_disk;
 pub mod shards;
 pub mod telemetry;
 mod update_handler;
+pub mod vector_arithmetic;
 pub mod wal;
+pub mod wal_archive;
 
 #[cfg(test)]
 mod tests;
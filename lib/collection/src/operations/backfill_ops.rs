@@ -0,0 +1,132 @@
+use std::collections::HashMap;
+
+use schemars::JsonSchema;
+use segment::data_types::vectors::VectorType;
+use segment::types::{PointIdType, WithPayloadInterface};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::operations::types::{CollectionError, CollectionResult, Record};
+use crate::shards::shard::ShardId;
+
+pub type BackfillJobId = u64;
+
+/// Start a job, which fills the named vector for points stored before the vector was added
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct CreateBackfillJob {
+    /// Name of the vector to fill
+    #[validate(length(min = 1))]
+    pub vector: String,
+}
+
+/// Request next points of the backfill job, which don't have the vector yet
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, Default)]
+#[serde(rename_all = "snake_case")]
+pub struct BackfillPointsRequest {
+    /// Max number of points to return. Default: 10
+    #[validate(range(min = 1))]
+    pub limit: Option<usize>,
+    /// Select which payload to return with the points. Default: All
+    pub with_payload: Option<WithPayloadInterface>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct BackfillPointsResult {
+    /// Points without the vector, with all vectors they have
+    pub points: Vec<Record>,
+    /// All shards are scanned, no more points will be returned
+    pub completed: bool,
+}
+
+/// Vectors computed by the client for points of the backfill job
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct BackfillBatch {
+    #[validate(length(min = 1))]
+    pub points: Vec<BackfillPoint>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct BackfillPoint {
+    pub id: PointIdType,
+    /// Value of the backfilled vector
+    pub vector: VectorType,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct BackfillShardProgress {
+    pub shard_id: ShardId,
+    /// Offset to continue scanning the shard from
+    pub offset: Option<PointIdType>,
+    /// All points of the shard are scanned
+    pub scanned: bool,
+    /// Number of points found without the vector
+    pub missing: usize,
+    /// Number of points, which received the vector
+    pub backfilled: usize,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct BackfillJobInfo {
+    pub job_id: BackfillJobId,
+    /// Name of the vector to fill
+    pub vector: String,
+    /// Progress of the job in each shard of the collection
+    pub shards: Vec<BackfillShardProgress>,
+}
+
+/// Backfill jobs of a collection.
+///
+/// Jobs are not persisted, they only live until the collection is reloaded.
+#[derive(Default)]
+pub struct BackfillJobs {
+    last_job_id: BackfillJobId,
+    jobs: HashMap<BackfillJobId, BackfillJobInfo>,
+}
+
+impl BackfillJobs {
+    pub fn create(
+        &mut self,
+        vector: String,
+        shard_ids: impl IntoIterator<Item = ShardId>,
+    ) -> BackfillJobInfo {
+        self.last_job_id += 1;
+        let job = BackfillJobInfo {
+            job_id: self.last_job_id,
+            vector,
+            shards: shard_ids
+                .into_iter()
+                .map(|shard_id| BackfillShardProgress {
+                    shard_id,
+                    offset: None,
+                    scanned: false,
+                    missing: 0,
+                    backfilled: 0,
+                })
+                .collect(),
+        };
+        self.jobs.insert(job.job_id, job.clone());
+        job
+    }
+
+    pub fn get(&self, job_id: BackfillJobId) -> CollectionResult<&BackfillJobInfo> {
+        self.jobs.get(&job_id).ok_or_else(|| job_not_found(job_id))
+    }
+
+    pub fn get_mut(&mut self, job_id: BackfillJobId) -> CollectionResult<&mut BackfillJobInfo> {
+        self.jobs
+            .get_mut(&job_id)
+            .ok_or_else(|| job_not_found(job_id))
+    }
+}
+
+fn job_not_found(job_id: BackfillJobId) -> CollectionError {
+    CollectionError::NotFound {
+        what: format!("Backfill job {job_id}"),
+    }
+}
@@ -0,0 +1,124 @@
+use schemars::JsonSchema;
+use segment::index::field_index::{CardinalityEstimation, PrimaryCondition};
+use segment::types::{FieldCondition, Filter, IsEmptyCondition, IsNullCondition, SegmentType};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::shards::shard::ShardId;
+
+/// Estimate the number of points, which satisfy the filter, the same way the query planner does
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, Default)]
+#[serde(rename_all = "snake_case")]
+pub struct CardinalityEstimationRequest {
+    /// Look only for points which satisfies this conditions
+    pub filter: Option<Filter>,
+}
+
+/// Condition, which is used to select candidate points from the payload index
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
+#[serde(rename_all = "snake_case")]
+pub enum PrimaryClause {
+    Field(FieldCondition),
+    IsEmpty(IsEmptyCondition),
+    IsNull(IsNullCondition),
+    /// Number of requested ids, which exist in the segment
+    HasId(usize),
+}
+
+impl From<PrimaryCondition> for PrimaryClause {
+    fn from(condition: PrimaryCondition) -> Self {
+        match condition {
+            PrimaryCondition::Condition(condition) => PrimaryClause::Field(condition),
+            PrimaryCondition::IsEmpty(condition) => PrimaryClause::IsEmpty(condition),
+            PrimaryCondition::IsNull(condition) => PrimaryClause::IsNull(condition),
+            PrimaryCondition::Ids(ids) => PrimaryClause::HasId(ids.len()),
+        }
+    }
+}
+
+/// How points, which satisfy the filter, are selected in the segment
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum FilteringStrategy {
+    /// All points of the segment are checked against the filter
+    FullScan,
+    /// Only points, selected by primary clauses, are checked against the filter
+    Index,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct SegmentCardinalityEstimation {
+    pub segment_type: SegmentType,
+    /// Number of points in the segment
+    pub points_count: usize,
+    /// Minimal possible number of points, which satisfy the filter
+    pub min: usize,
+    /// Expected number of points, which satisfy the filter
+    pub exp: usize,
+    /// Maximal possible number of points, which satisfy the filter
+    pub max: usize,
+    pub strategy: FilteringStrategy,
+    /// Conditions, used to select candidate points, if the strategy is `index`
+    pub primary_clauses: Vec<PrimaryClause>,
+}
+
+impl SegmentCardinalityEstimation {
+    pub fn new(
+        segment_type: SegmentType,
+        points_count: usize,
+        estimation: CardinalityEstimation,
+    ) -> Self {
+        let strategy = if estimation.primary_clauses.is_empty() {
+            FilteringStrategy::FullScan
+        } else {
+            FilteringStrategy::Index
+        };
+        SegmentCardinalityEstimation {
+            segment_type,
+            points_count,
+            min: estimation.min,
+            exp: estimation.exp,
+            max: estimation.max,
+            strategy,
+            primary_clauses: estimation
+                .primary_clauses
+                .into_iter()
+                .map(PrimaryClause::from)
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct ShardCardinalityEstimation {
+    pub shard_id: ShardId,
+    pub segments: Vec<SegmentCardinalityEstimation>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct CardinalityEstimationResult {
+    /// Minimal possible number of points in local shards, which satisfy the filter
+    pub min: usize,
+    /// Expected number of points in local shards, which satisfy the filter
+    pub exp: usize,
+    /// Maximal possible number of points in local shards, which satisfy the filter
+    pub max: usize,
+    /// Estimations of each segment of local shards
+    pub shards: Vec<ShardCardinalityEstimation>,
+}
+
+impl CardinalityEstimationResult {
+    pub fn new(shards: Vec<ShardCardinalityEstimation>) -> Self {
+        let segments = || shards.iter().flat_map(|shard| shard.segments.iter());
+        CardinalityEstimationResult {
+            min: segments().map(|segment| segment.min).sum(),
+            exp: segments().map(|segment| segment.exp).sum(),
+            max: segments().map(|segment| segment.max).sum(),
+            shards,
+        }
+    }
+}
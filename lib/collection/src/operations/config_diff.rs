@@ -1,14 +1,17 @@
+use std::collections::BTreeMap;
 use std::num::NonZeroU32;
 
 use merge::Merge;
 use schemars::JsonSchema;
-use segment::types::HnswConfig;
+use segment::types::{HnswConfig, PayloadKeyType};
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use validator::Validate;
 
 use crate::config::{CollectionParams, WalConfig};
+use crate::operations::consistency_params::ReadConsistency;
+use crate::operations::point_ops::WriteOrdering;
 use crate::operations::types::CollectionResult;
 use crate::optimizers_builder::OptimizersConfig;
 
@@ -32,7 +35,7 @@ pub trait DiffConfig<T: DeserializeOwned + Serialize> {
 }
 
 #[derive(
-    Debug, Deserialize, Serialize, JsonSchema, Validate, Copy, Clone, PartialEq, Eq, Merge, Hash,
+    Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq, Eq, Merge, Hash,
 )]
 #[serde(rename_all = "snake_case")]
 pub struct HnswConfigDiff {
@@ -64,6 +67,10 @@ pub struct HnswConfigDiff {
     /// Custom M param for additional payload-aware HNSW links. If not set, default M will be used.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub payload_m: Option<usize>,
+    /// Payload fields, for which additional payload-aware HNSW links are built.
+    /// If not set, links are built for all indexed payload fields.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub payload_fields: Option<Vec<PayloadKeyType>>,
 }
 
 #[derive(
@@ -82,6 +89,30 @@ pub struct CollectionParamsDiff {
     pub replication_factor: Option<NonZeroU32>,
     /// Minimal number successful responses from replicas to consider operation successful
     pub write_consistency_factor: Option<NonZeroU32>,
+    /// Alternative names of vectors. Replaces all existing aliases
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vector_aliases: Option<BTreeMap<String, String>>,
+    /// Name of the vector to use if request does not specify any vector name
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_vector: Option<String>,
+    /// Payload key to group points by, when optimizers rebuild segments
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub defrag_key: Option<PayloadKeyType>,
+    /// Skip upserts of points, which content hash matches the stored one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deduplicate_upserts: Option<bool>,
+    /// Max number of search requests to this collection, executed concurrently. 0 - unlimited
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_concurrent_searches: Option<usize>,
+    /// Payload key with the list of identity claims, allowed to read the point. Empty string - disable
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub acl_key: Option<PayloadKeyType>,
+    /// Write ordering of updates, which don't specify it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_write_ordering: Option<WriteOrdering>,
+    /// Read consistency of requests, which don't specify it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_read_consistency: Option<ReadConsistency>,
 }
 
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, Merge)]
@@ -126,6 +157,16 @@ pub struct OptimizersConfigDiff {
     /// Maximum available threads for optimization workers
     #[validate(range(min = 1))]
     pub max_optimization_threads: Option<usize>,
+    /// Exact number of segments optimizer will try to keep.
+    /// If set, takes precedence over `default_segment_number`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1))]
+    pub target_segment_count: Option<usize>,
+    /// Do not create segments larger this size (in MegaBytes).
+    /// If set, takes precedence over `max_segment_size`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1))]
+    pub max_segment_size_mb: Option<usize>,
 }
 
 impl std::hash::Hash for OptimizersConfigDiff {
@@ -138,6 +179,8 @@ impl std::hash::Hash for OptimizersConfigDiff {
         self.indexing_threshold.hash(state);
         self.flush_interval_sec.hash(state);
         self.max_optimization_threads.hash(state);
+        self.target_segment_count.hash(state);
+        self.max_segment_size_mb.hash(state);
     }
 }
 
@@ -152,6 +195,8 @@ impl PartialEq for OptimizersConfigDiff {
             && self.indexing_threshold == other.indexing_threshold
             && self.flush_interval_sec == other.flush_interval_sec
             && self.max_optimization_threads == other.max_optimization_threads
+            && self.target_segment_count == other.target_segment_count
+            && self.max_segment_size_mb == other.max_segment_size_mb
     }
 }
 
@@ -243,7 +288,7 @@ mod tests {
     use segment::types::{Distance, HnswConfig};
 
     use super::*;
-    use crate::operations::types::VectorParams;
+    use crate::operations::types::{VectorParams, VectorsConfig};
     use crate::optimizers_builder::OptimizersConfig;
 
     #[test]
@@ -254,17 +299,35 @@ mod tests {
                 distance: Distance::Cosine,
                 hnsw_config: None,
                 quantization_config: None,
+                projection: None,
             }
             .into(),
             shard_number: NonZeroU32::new(1).unwrap(),
             replication_factor: NonZeroU32::new(1).unwrap(),
             write_consistency_factor: NonZeroU32::new(1).unwrap(),
             on_disk_payload: false,
+            vector_aliases: Default::default(),
+            default_vector: None,
+            defrag_key: None,
+            deduplicate_upserts: false,
+            max_concurrent_searches: 0,
+            acl_key: None,
+            default_write_ordering: None,
+            default_read_consistency: None,
+            sparse_vectors: Default::default(),
         };
 
         let diff = CollectionParamsDiff {
             replication_factor: None,
             write_consistency_factor: Some(NonZeroU32::new(2).unwrap()),
+            vector_aliases: None,
+            default_vector: None,
+            defrag_key: None,
+            deduplicate_upserts: None,
+            max_concurrent_searches: None,
+            acl_key: None,
+            default_write_ordering: None,
+            default_read_consistency: None,
         };
 
         let new_params = diff.update(&params).unwrap();
@@ -273,6 +336,53 @@ mod tests {
         assert_eq!(new_params.write_consistency_factor.get(), 2);
     }
 
+    #[test]
+    fn test_update_vector_aliases() {
+        let vector_params = VectorParams {
+            size: NonZeroU64::new(128).unwrap(),
+            distance: Distance::Cosine,
+            hnsw_config: None,
+            quantization_config: None,
+            projection: None,
+        };
+        let params = CollectionParams {
+            vectors: VectorsConfig::Multi(BTreeMap::from([
+                ("text-v1".to_string(), vector_params.clone()),
+                ("text-v2".to_string(), vector_params),
+            ])),
+            shard_number: NonZeroU32::new(1).unwrap(),
+            replication_factor: NonZeroU32::new(1).unwrap(),
+            write_consistency_factor: NonZeroU32::new(1).unwrap(),
+            on_disk_payload: false,
+            vector_aliases: Default::default(),
+            default_vector: None,
+            defrag_key: None,
+            deduplicate_upserts: false,
+            max_concurrent_searches: 0,
+            acl_key: None,
+            default_write_ordering: None,
+            default_read_consistency: None,
+            sparse_vectors: Default::default(),
+        };
+
+        let diff: CollectionParamsDiff = serde_json::from_str(
+            r#"{ "vector_aliases": { "text": "text-v2" }, "default_vector": "text" }"#,
+        )
+        .unwrap();
+        let new_params = diff.update(&params).unwrap();
+
+        assert!(new_params.check_vector_aliases().is_ok());
+        assert_eq!(new_params.resolve_vector_name("text"), "text-v2");
+        assert_eq!(new_params.resolve_vector_name(""), "text-v2");
+        assert_eq!(new_params.resolve_vector_name("text-v1"), "text-v1");
+
+        let diff: CollectionParamsDiff =
+            serde_json::from_str(r#"{ "vector_aliases": { "text": "text-v3" } }"#).unwrap();
+        let new_params = diff.update(&params).unwrap();
+
+        assert!(new_params.check_vector_aliases().is_err());
+    }
+
     #[test]
     fn test_hnsw_update() {
         let base_config = HnswConfig::default();
@@ -281,6 +391,20 @@ mod tests {
         assert_eq!(new_config.m, 32)
     }
 
+    #[test]
+    fn test_hnsw_payload_fields_update() {
+        let base_config = HnswConfig::default();
+        let update: HnswConfigDiff =
+            serde_json::from_str(r#"{ "payload_m": 16, "payload_fields": ["tenant"] }"#).unwrap();
+        let new_config = update.update(&base_config).unwrap();
+        assert_eq!(new_config.payload_m, Some(16));
+        assert_eq!(new_config.payload_fields, Some(vec!["tenant".to_string()]));
+
+        let update: HnswConfigDiff = serde_json::from_str(r#"{ "m": 32 }"#).unwrap();
+        let new_config = update.update(&new_config).unwrap();
+        assert_eq!(new_config.payload_fields, Some(vec!["tenant".to_string()]));
+    }
+
     #[test]
     fn test_optimizer_update() {
         let base_config = OptimizersConfig {
@@ -292,6 +416,8 @@ mod tests {
             indexing_threshold: 50_000,
             flush_interval_sec: 30,
             max_optimization_threads: 1,
+            target_segment_count: None,
+            max_segment_size_mb: None,
         };
         let update: OptimizersConfigDiff =
             serde_json::from_str(r#"{ "indexing_threshold": 10000 }"#).unwrap();
@@ -299,6 +425,31 @@ mod tests {
         assert_eq!(new_config.indexing_threshold, 10000)
     }
 
+    #[test]
+    fn test_optimizer_segment_targets() {
+        let base_config = OptimizersConfig {
+            deleted_threshold: 0.9,
+            vacuum_min_vector_number: 1000,
+            default_segment_number: 10,
+            max_segment_size: Some(100_000),
+            memmap_threshold: None,
+            indexing_threshold: 50_000,
+            flush_interval_sec: 30,
+            max_optimization_threads: 1,
+            target_segment_count: None,
+            max_segment_size_mb: None,
+        };
+        assert_eq!(base_config.get_number_segments(), 10);
+        assert_eq!(base_config.get_max_segment_size(), 100_000);
+
+        let update: OptimizersConfigDiff =
+            serde_json::from_str(r#"{ "target_segment_count": 4, "max_segment_size_mb": 200 }"#)
+                .unwrap();
+        let new_config = update.update(&base_config).unwrap();
+        assert_eq!(new_config.get_number_segments(), 4);
+        assert_eq!(new_config.get_max_segment_size(), 200 * 1024);
+    }
+
     #[test]
     fn test_wal_config() {
         let base_config = WalConfig::default();
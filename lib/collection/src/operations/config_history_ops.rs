@@ -0,0 +1,144 @@
+use std::collections::BTreeMap;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::config::CollectionConfig;
+
+pub const CONFIG_HISTORY_FILE: &str = "config_history.json";
+
+/// Max number of config changes kept in the history, oldest changes are dropped first
+pub const CONFIG_HISTORY_SIZE: usize = 100;
+
+/// Change of a single config field
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct ConfigFieldChange {
+    /// Dot separated path of the field, e.g. `optimizer_config.indexing_threshold`
+    pub path: String,
+    /// Value before the change. If none - the field was not set
+    pub old: Option<Value>,
+    /// Value after the change. If none - the field was removed
+    pub new: Option<Value>,
+}
+
+/// Recorded change of the collection config
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct ConfigChange {
+    /// Sequential id of the change in the collection history
+    pub id: u64,
+    /// Unix timestamp of the change, in seconds
+    pub timestamp: u64,
+    /// Identity of the user, who requested the change, if known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+    /// Changed fields
+    pub diff: Vec<ConfigFieldChange>,
+    /// Full config of the collection before the change, used for rollbacks
+    pub previous: CollectionConfig,
+}
+
+/// Fields, which differ between the two configs
+pub fn config_diff(old: &CollectionConfig, new: &CollectionConfig) -> Vec<ConfigFieldChange> {
+    let mut old_fields = BTreeMap::new();
+    let mut new_fields = BTreeMap::new();
+    flatten(
+        String::new(),
+        serde_json::to_value(old).unwrap(),
+        &mut old_fields,
+    );
+    flatten(
+        String::new(),
+        serde_json::to_value(new).unwrap(),
+        &mut new_fields,
+    );
+
+    let mut paths: Vec<_> = old_fields
+        .keys()
+        .chain(new_fields.keys())
+        .cloned()
+        .collect();
+    paths.sort();
+    paths.dedup();
+
+    paths
+        .into_iter()
+        .filter_map(|path| {
+            let old = old_fields.remove(&path);
+            let new = new_fields.remove(&path);
+            (old != new).then_some(ConfigFieldChange { path, old, new })
+        })
+        .collect()
+}
+
+/// Collect non-null leaf values of nested objects by their dot separated path.
+/// Arrays are compared as a whole.
+fn flatten(prefix: String, value: Value, fields: &mut BTreeMap<String, Value>) {
+    match value {
+        Value::Object(object) => {
+            for (key, value) in object {
+                let path = if prefix.is_empty() {
+                    key
+                } else {
+                    format!("{prefix}.{key}")
+                };
+                flatten(path, value, fields);
+            }
+        }
+        Value::Null => {}
+        value => {
+            fields.insert(prefix, value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_config_diff() {
+        let old: CollectionConfig = serde_json::from_value(json!({
+            "params": { "vectors": { "size": 4, "distance": "Dot" } },
+            "hnsw_config": { "m": 16, "ef_construct": 100, "full_scan_threshold": 10000 },
+            "optimizer_config": {
+                "deleted_threshold": 0.2,
+                "vacuum_min_vector_number": 1000,
+                "default_segment_number": 0,
+                "max_segment_size": null,
+                "memmap_threshold": null,
+                "indexing_threshold": 20000,
+                "flush_interval_sec": 5,
+                "max_optimization_threads": 1
+            },
+            "wal_config": { "wal_capacity_mb": 32, "wal_segments_ahead": 0 }
+        }))
+        .unwrap();
+
+        assert!(config_diff(&old, &old).is_empty());
+
+        let mut new = old.clone();
+        new.optimizer_config.indexing_threshold = 10000;
+        new.params.acl_key = Some("readers".to_string());
+
+        assert_eq!(
+            config_diff(&old, &new),
+            vec![
+                ConfigFieldChange {
+                    path: "optimizer_config.indexing_threshold".to_string(),
+                    old: Some(json!(20000)),
+                    new: Some(json!(10000)),
+                },
+                ConfigFieldChange {
+                    path: "params.acl_key".to_string(),
+                    old: None,
+                    new: Some(json!("readers")),
+                },
+            ]
+        );
+    }
+}
@@ -1,13 +1,25 @@
 use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
 
 use api::grpc::qdrant::{
     read_consistency, ReadConsistency as ReadConsistencyGrpc,
     ReadConsistencyType as ReadConsistencyTypeGrpc,
 };
+use schemars::gen::SchemaGenerator;
+use schemars::schema::Schema;
 use schemars::JsonSchema;
-use serde::{Deserialize, Serialize};
+use segment::types::SeqNumberType;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use validator::{Validate, ValidationError as ValidatorError, ValidationErrors};
 
+use crate::shards::shard::{PeerId, ShardId};
+
+/// How long reads wait for replicas to apply the writes of a consistency token
+pub const CONSISTENCY_TOKEN_WAIT_TIMEOUT: Duration = Duration::from_secs(10);
+
 /// Read consistency parameter
 ///
 /// Defines how many replicas should be queried to get the result
@@ -21,7 +33,7 @@ use validator::{Validate, ValidationError as ValidatorError, ValidationErrors};
 /// * `all` - send requests to all nodes and return points which present on all of them
 ///
 /// Default value is `Factor(1)`
-#[derive(Copy, Clone, Debug, Eq, PartialEq, Deserialize, Serialize, JsonSchema)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Deserialize, Serialize, JsonSchema)]
 #[serde(untagged)]
 pub enum ReadConsistency {
     // send N random request and return points, which present on all of them
@@ -145,7 +157,7 @@ where
 /// * `quorum` - send requests to all nodes and return points which present on majority of nodes
 ///
 /// * `all` - send requests to all nodes and return points which present on all nodes
-#[derive(Debug, Deserialize, Serialize, JsonSchema, Copy, Clone, PartialEq, Eq)]
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Copy, Clone, PartialEq, Eq, Hash)]
 #[serde(rename_all = "snake_case")]
 pub enum ReadConsistencyType {
     // send N/2+1 random request and return points, which present on all of them
@@ -200,6 +212,109 @@ impl From<ReadConsistencyType> for ReadConsistencyTypeGrpc {
 #[error("Read consistency factor cannot be less than 1")]
 pub struct ValidationError;
 
+/// Writes of update operations, which subsequent reads should observe.
+///
+/// For each shard it keeps the number of the operation in the WAL of each replica, which
+/// acknowledged the write, as every replica numbers operations on its own.
+/// Clients treat it as an opaque string.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ConsistencyToken {
+    shards: BTreeMap<ShardId, BTreeMap<PeerId, SeqNumberType>>,
+}
+
+impl ConsistencyToken {
+    pub fn for_shard(
+        shard_id: ShardId,
+        peer_versions: impl IntoIterator<Item = (PeerId, SeqNumberType)>,
+    ) -> Self {
+        Self {
+            shards: BTreeMap::from([(shard_id, peer_versions.into_iter().collect())]),
+        }
+    }
+
+    /// Combine writes of both tokens, keeping the latest operation of each replica
+    pub fn merge(&mut self, other: ConsistencyToken) {
+        for (shard_id, peer_versions) in other.shards {
+            let versions = self.shards.entry(shard_id).or_default();
+            for (peer_id, version) in peer_versions {
+                let latest = versions.entry(peer_id).or_insert(version);
+                *latest = (*latest).max(version);
+            }
+        }
+    }
+
+    pub fn shards(&self) -> impl Iterator<Item = (ShardId, &BTreeMap<PeerId, SeqNumberType>)> {
+        self.shards
+            .iter()
+            .map(|(shard_id, peer_versions)| (*shard_id, peer_versions))
+    }
+}
+
+/// Encoded as `shard:peer.version+peer.version;shard:...`
+impl fmt::Display for ConsistencyToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (shard_id, peer_versions)) in self.shards.iter().enumerate() {
+            if i > 0 {
+                write!(f, ";")?;
+            }
+            write!(f, "{shard_id}:")?;
+            for (j, (peer_id, version)) in peer_versions.iter().enumerate() {
+                if j > 0 {
+                    write!(f, "+")?;
+                }
+                write!(f, "{peer_id}.{version}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for ConsistencyToken {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let malformed = || format!("Malformed consistency token: {s}");
+        let mut shards = BTreeMap::new();
+        for shard in s.split(';').filter(|shard| !shard.is_empty()) {
+            let (shard_id, peer_versions) = shard.split_once(':').ok_or_else(malformed)?;
+            let shard_id: ShardId = shard_id.parse().map_err(|_| malformed())?;
+            let mut versions = BTreeMap::new();
+            for peer_version in peer_versions.split('+') {
+                let (peer_id, version) = peer_version.split_once('.').ok_or_else(malformed)?;
+                versions.insert(
+                    peer_id.parse().map_err(|_| malformed())?,
+                    version.parse().map_err(|_| malformed())?,
+                );
+            }
+            shards.insert(shard_id, versions);
+        }
+        Ok(Self { shards })
+    }
+}
+
+impl Serialize for ConsistencyToken {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for ConsistencyToken {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let token = String::deserialize(deserializer)?;
+        token.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+impl JsonSchema for ConsistencyToken {
+    fn schema_name() -> String {
+        "ConsistencyToken".to_string()
+    }
+
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        String::json_schema(gen)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use schemars::schema_for;
@@ -243,4 +358,28 @@ mod tests {
         let schema_str = serde_json::to_string_pretty(&schema).unwrap();
         println!("{schema_str}")
     }
+
+    #[test]
+    fn test_consistency_token_encoding() {
+        let mut token = ConsistencyToken::for_shard(0, [(100, 5), (200, 7)]);
+        token.merge(ConsistencyToken::for_shard(3, [(100, 2)]));
+        token.merge(ConsistencyToken::for_shard(0, [(100, 4), (300, 1)]));
+
+        let encoded = token.to_string();
+        assert_eq!(encoded, "0:100.5+200.7+300.1;3:100.2");
+        assert_eq!(encoded.parse::<ConsistencyToken>().unwrap(), token);
+
+        let json = serde_json::to_string(&token).unwrap();
+        assert_eq!(
+            serde_json::from_str::<ConsistencyToken>(&json).unwrap(),
+            token
+        );
+
+        assert!("0:100".parse::<ConsistencyToken>().is_err());
+        assert!("x:1.2".parse::<ConsistencyToken>().is_err());
+        assert_eq!(
+            "".parse::<ConsistencyToken>().unwrap(),
+            ConsistencyToken::default()
+        );
+    }
 }
@@ -2,11 +2,14 @@ use std::collections::{BTreeMap, HashMap};
 use std::num::{NonZeroU32, NonZeroU64};
 
 use api::grpc::conversions::{from_grpc_dist, payload_to_proto, proto_to_payloads};
-use api::grpc::qdrant::QuantizationType;
+use api::grpc::qdrant::{CompressionRatio, QuantizationType};
 use itertools::Itertools;
+use segment::data_types::distinct::DistinctValues;
+use segment::data_types::order_by::Direction;
 use segment::data_types::vectors::{NamedVector, VectorStruct, DEFAULT_VECTOR_NAME};
 use segment::types::{
-    Distance, QuantizationConfig, ScalarQuantization, ScalarQuantizationConfig, ScalarType,
+    Distance, PlannerHint, PointIdType, ProductQuantization, ProductQuantizationConfig,
+    QuantizationConfig, ScalarQuantization, ScalarQuantizationConfig, ScalarType,
 };
 use tonic::Status;
 
@@ -16,14 +19,18 @@ use crate::config::{
     CollectionParams, WalConfig,
 };
 use crate::operations::config_diff::{HnswConfigDiff, OptimizersConfigDiff, WalConfigDiff};
+use crate::operations::consistency_params::ReadConsistency;
+use crate::operations::id_range_ops::IdChunk;
 use crate::operations::point_ops::PointsSelector::PointIdsSelector;
 use crate::operations::point_ops::{
-    Batch, FilterSelector, PointIdsList, PointStruct, PointsSelector, WriteOrdering,
+    Batch, FilterSelector, PointIdsList, PointStruct, PointVectors, PointsSelector, WriteOrdering,
 };
 use crate::operations::types::{
-    AliasDescription, CollectionInfo, CollectionStatus, CountResult, LookupLocation,
-    OptimizersStatus, RecommendRequest, Record, SearchRequest, UpdateResult, UpdateStatus,
-    VectorParams, VectorsConfig,
+    AliasDescription, ArithmeticSearchRequest, CollectionInfo, CollectionStatus,
+    ContextExamplePair, CountResult, DiscoverRequest, Fusion, GeoDistanceOrder, LookupLocation,
+    OptimizersStatus, OrderBy, Prefetch, QueryRequest, RecommendExample, RecommendRequest,
+    RecommendStrategy, Record, ScrollOrder, SearchRequest, SparseVectorParams, UpdateResult,
+    UpdateStatus, VectorParams, VectorProjection, VectorsConfig, WeightedExample,
 };
 use crate::optimizers_builder::OptimizersConfig;
 use crate::shards::remote_shard::CollectionSearchRequest;
@@ -38,29 +45,97 @@ pub fn write_ordering_to_proto(ordering: WriteOrdering) -> api::grpc::qdrant::Wr
     }
 }
 
+/// Returns `None` if the ordering is not specified, so the default of the collection is used
 pub fn write_ordering_from_proto(
     ordering: Option<api::grpc::qdrant::WriteOrdering>,
-) -> Result<WriteOrdering, Status> {
-    let ordering_parsed = match ordering {
-        None => api::grpc::qdrant::WriteOrderingType::Weak,
-        Some(write_ordering) => {
-            match api::grpc::qdrant::WriteOrderingType::from_i32(write_ordering.r#type) {
+) -> Result<Option<WriteOrdering>, Status> {
+    ordering.map(WriteOrdering::try_from).transpose()
+}
+
+impl TryFrom<api::grpc::qdrant::WriteOrdering> for WriteOrdering {
+    type Error = Status;
+
+    fn try_from(ordering: api::grpc::qdrant::WriteOrdering) -> Result<Self, Self::Error> {
+        match api::grpc::qdrant::WriteOrderingType::from_i32(ordering.r#type) {
+            None => Err(Status::invalid_argument(format!(
+                "cannot convert ordering: {}",
+                ordering.r#type
+            ))),
+            Some(api::grpc::qdrant::WriteOrderingType::Weak) => Ok(WriteOrdering::Weak),
+            Some(api::grpc::qdrant::WriteOrderingType::Medium) => Ok(WriteOrdering::Medium),
+            Some(api::grpc::qdrant::WriteOrderingType::Strong) => Ok(WriteOrdering::Strong),
+        }
+    }
+}
+
+pub fn scroll_order_from_proto(order: Option<i32>) -> Result<ScrollOrder, Status> {
+    let order_parsed = match order {
+        None => api::grpc::qdrant::ScrollOrder::Id,
+        Some(order) => match api::grpc::qdrant::ScrollOrder::from_i32(order) {
+            None => {
+                return Err(Status::invalid_argument(format!(
+                    "cannot convert scroll order: {order}"
+                )))
+            }
+            Some(res) => res,
+        },
+    };
+
+    Ok(match order_parsed {
+        api::grpc::qdrant::ScrollOrder::Id => ScrollOrder::Id,
+        api::grpc::qdrant::ScrollOrder::Random => ScrollOrder::Random,
+        api::grpc::qdrant::ScrollOrder::GeoDistance => ScrollOrder::GeoDistance,
+    })
+}
+
+impl TryFrom<api::grpc::qdrant::GeoDistanceOrder> for GeoDistanceOrder {
+    type Error = Status;
+
+    fn try_from(value: api::grpc::qdrant::GeoDistanceOrder) -> Result<Self, Self::Error> {
+        Ok(Self {
+            key: value.key,
+            origin: value
+                .origin
+                .ok_or_else(|| Status::invalid_argument("Origin of geo order is required"))?
+                .into(),
+        })
+    }
+}
+
+impl TryFrom<api::grpc::qdrant::OrderBy> for OrderBy {
+    type Error = Status;
+
+    fn try_from(value: api::grpc::qdrant::OrderBy) -> Result<Self, Self::Error> {
+        let direction = match value.direction {
+            None => Direction::default(),
+            Some(direction) => match api::grpc::qdrant::Direction::from_i32(direction) {
+                Some(api::grpc::qdrant::Direction::Asc) => Direction::Asc,
+                Some(api::grpc::qdrant::Direction::Desc) => Direction::Desc,
                 None => {
                     return Err(Status::invalid_argument(format!(
-                        "cannot convert ordering: {}",
-                        write_ordering.r#type
+                        "cannot convert order direction: {direction}"
                     )))
                 }
-                Some(res) => res,
-            }
-        }
-    };
+            },
+        };
+        Ok(Self {
+            key: value.key,
+            direction,
+            start_from: value.start_from,
+        })
+    }
+}
 
-    Ok(match ordering_parsed {
-        api::grpc::qdrant::WriteOrderingType::Weak => WriteOrdering::Weak,
-        api::grpc::qdrant::WriteOrderingType::Medium => WriteOrdering::Medium,
-        api::grpc::qdrant::WriteOrderingType::Strong => WriteOrdering::Strong,
-    })
+pub fn planner_hint_from_proto(hint: Option<i32>) -> Result<Option<PlannerHint>, Status> {
+    hint.map(
+        |hint| match api::grpc::qdrant::PlannerHint::from_i32(hint) {
+            None => Err(Status::invalid_argument(format!(
+                "cannot convert planner hint: {hint}"
+            ))),
+            Some(res) => Ok(res.into()),
+        },
+    )
+    .transpose()
 }
 
 pub fn try_record_from_grpc(
@@ -73,7 +148,7 @@ pub fn try_record_from_grpc(
         .try_into()?;
 
     let payload = if with_payload {
-        Some(api::grpc::conversions::proto_to_payloads(point.payload)?)
+        Some(api::grpc::conversions::proto_to_payloads(point.payload)?.into())
     } else {
         debug_assert!(point.payload.is_empty());
         None
@@ -88,6 +163,7 @@ pub fn try_record_from_grpc(
         id,
         payload,
         vector,
+        geo_distance: point.geo_distance,
     })
 }
 
@@ -100,6 +176,7 @@ impl From<api::grpc::qdrant::HnswConfigDiff> for HnswConfigDiff {
             max_indexing_threads: value.max_indexing_threads.map(|v| v as usize),
             on_disk: value.on_disk,
             payload_m: value.payload_m.map(|v| v as usize),
+            payload_fields: value.payload_fields.map(|v| v.fields),
         }
     }
 }
@@ -113,6 +190,9 @@ impl From<HnswConfigDiff> for api::grpc::qdrant::HnswConfigDiff {
             max_indexing_threads: value.max_indexing_threads.map(|v| v as u64),
             on_disk: value.on_disk,
             payload_m: value.payload_m.map(|v| v as u64),
+            payload_fields: value
+                .payload_fields
+                .map(|fields| api::grpc::qdrant::HnswPayloadFields { fields }),
         }
     }
 }
@@ -146,6 +226,18 @@ impl TryFrom<api::grpc::qdrant::CollectionParamsDiff> for CollectionParamsDiff {
                     })
                 })
                 .transpose()?,
+            vector_aliases: value
+                .vector_aliases
+                .map(|aliases| aliases.map.into_iter().collect()),
+            default_vector: value.default_vector,
+            defrag_key: value.defrag_key,
+            deduplicate_upserts: value.deduplicate_upserts,
+            max_concurrent_searches: value.max_concurrent_searches.map(|limit| limit as usize),
+            acl_key: value.acl_key,
+            default_write_ordering: write_ordering_from_proto(value.default_write_ordering)?,
+            default_read_consistency: ReadConsistency::try_from_optional(
+                value.default_read_consistency,
+            )?,
         })
     }
 }
@@ -161,6 +253,8 @@ impl From<api::grpc::qdrant::OptimizersConfigDiff> for OptimizersConfigDiff {
             indexing_threshold: value.indexing_threshold.map(|v| v as usize),
             flush_interval_sec: value.flush_interval_sec,
             max_optimization_threads: value.max_optimization_threads.map(|v| v as usize),
+            target_segment_count: value.target_segment_count.map(|v| v as usize),
+            max_segment_size_mb: value.max_segment_size_mb.map(|v| v as usize),
         }
     }
 }
@@ -226,6 +320,38 @@ impl From<CollectionInfo> for api::grpc::qdrant::CollectionInfo {
                     replication_factor: Some(config.params.replication_factor.get()),
                     on_disk_payload: config.params.on_disk_payload,
                     write_consistency_factor: Some(config.params.write_consistency_factor.get()),
+                    vector_aliases: if config.params.vector_aliases.is_empty() {
+                        None
+                    } else {
+                        Some(api::grpc::qdrant::VectorAliases {
+                            map: config.params.vector_aliases.into_iter().collect(),
+                        })
+                    },
+                    default_vector: config.params.default_vector,
+                    defrag_key: config.params.defrag_key,
+                    deduplicate_upserts: Some(config.params.deduplicate_upserts),
+                    max_concurrent_searches: Some(config.params.max_concurrent_searches as u64),
+                    acl_key: config.params.acl_key,
+                    default_write_ordering: config
+                        .params
+                        .default_write_ordering
+                        .map(write_ordering_to_proto),
+                    default_read_consistency: config
+                        .params
+                        .default_read_consistency
+                        .map(Into::into),
+                    sparse_vectors_config: if config.params.sparse_vectors.is_empty() {
+                        None
+                    } else {
+                        Some(api::grpc::qdrant::SparseVectorConfig {
+                            map: config
+                                .params
+                                .sparse_vectors
+                                .into_iter()
+                                .map(|(name, params)| (name, params.into()))
+                                .collect(),
+                        })
+                    },
                 }),
                 hnsw_config: Some(api::grpc::qdrant::HnswConfigDiff {
                     m: Some(config.hnsw_config.m as u64),
@@ -234,6 +360,10 @@ impl From<CollectionInfo> for api::grpc::qdrant::CollectionInfo {
                     max_indexing_threads: Some(config.hnsw_config.max_indexing_threads as u64),
                     on_disk: config.hnsw_config.on_disk,
                     payload_m: config.hnsw_config.payload_m.map(|v| v as u64),
+                    payload_fields: config
+                        .hnsw_config
+                        .payload_fields
+                        .map(|fields| api::grpc::qdrant::HnswPayloadFields { fields }),
                 }),
                 optimizer_config: Some(api::grpc::qdrant::OptimizersConfigDiff {
                     deleted_threshold: Some(config.optimizer_config.deleted_threshold),
@@ -250,6 +380,14 @@ impl From<CollectionInfo> for api::grpc::qdrant::CollectionInfo {
                     max_optimization_threads: Some(
                         config.optimizer_config.max_optimization_threads as u64,
                     ),
+                    target_segment_count: config
+                        .optimizer_config
+                        .target_segment_count
+                        .map(|x| x as u64),
+                    max_segment_size_mb: config
+                        .optimizer_config
+                        .max_segment_size_mb
+                        .map(|x| x as u64),
                 }),
                 wal_config: Some(api::grpc::qdrant::WalConfigDiff {
                     wal_capacity_mb: Some(config.wal_config.wal_capacity_mb as u64),
@@ -265,15 +403,23 @@ impl From<CollectionInfo> for api::grpc::qdrant::CollectionInfo {
     }
 }
 
-impl From<Record> for api::grpc::qdrant::RetrievedPoint {
-    fn from(record: Record) -> Self {
+impl TryFrom<Record> for api::grpc::qdrant::RetrievedPoint {
+    type Error = Status;
+
+    fn try_from(record: Record) -> Result<Self, Self::Error> {
         let vectors = record.vector.map(|vector_struct| vector_struct.into());
+        let payload = record
+            .payload
+            .map(|payload| payload.into_payload())
+            .transpose()
+            .map_err(|err| Status::internal(format!("Failed to read payload: {err}")))?;
 
-        Self {
+        Ok(Self {
             id: Some(record.id.into()),
-            payload: record.payload.map(payload_to_proto).unwrap_or_default(),
+            payload: payload.map(payload_to_proto).unwrap_or_default(),
             vectors,
-        }
+            geo_distance: record.geo_distance,
+        })
     }
 }
 
@@ -306,6 +452,8 @@ impl From<api::grpc::qdrant::OptimizersConfigDiff> for OptimizersConfig {
             max_optimization_threads: optimizer_config
                 .max_optimization_threads
                 .unwrap_or_default() as usize,
+            target_segment_count: optimizer_config.target_segment_count.map(|x| x as usize),
+            max_segment_size_mb: optimizer_config.max_segment_size_mb.map(|x| x as usize),
         }
     }
 }
@@ -319,6 +467,18 @@ impl From<api::grpc::qdrant::WalConfigDiff> for WalConfig {
     }
 }
 
+impl From<api::grpc::qdrant::SparseVectorParams> for SparseVectorParams {
+    fn from(_params: api::grpc::qdrant::SparseVectorParams) -> Self {
+        SparseVectorParams {}
+    }
+}
+
+impl From<SparseVectorParams> for api::grpc::qdrant::SparseVectorParams {
+    fn from(_params: SparseVectorParams) -> Self {
+        api::grpc::qdrant::SparseVectorParams {}
+    }
+}
+
 impl TryFrom<api::grpc::qdrant::VectorParams> for VectorParams {
     type Error = Status;
 
@@ -336,10 +496,33 @@ impl TryFrom<api::grpc::qdrant::VectorParams> for VectorParams {
                 ),
                 None => None,
             },
+            projection: vector_params.projection.map(Into::into),
         })
     }
 }
 
+impl From<api::grpc::qdrant::VectorProjection> for VectorProjection {
+    fn from(value: api::grpc::qdrant::VectorProjection) -> Self {
+        Self {
+            matrix: value.matrix.into_iter().map(|row| row.values).collect(),
+            mean: (!value.mean.is_empty()).then_some(value.mean),
+        }
+    }
+}
+
+impl From<VectorProjection> for api::grpc::qdrant::VectorProjection {
+    fn from(value: VectorProjection) -> Self {
+        Self {
+            matrix: value
+                .matrix
+                .into_iter()
+                .map(|values| api::grpc::qdrant::VectorProjectionRow { values })
+                .collect(),
+            mean: value.mean.unwrap_or_default(),
+        }
+    }
+}
+
 fn grpc_to_segment_quantization_config(
     value: api::grpc::qdrant::QuantizationConfig,
 ) -> Result<QuantizationConfig, String> {
@@ -358,6 +541,27 @@ fn grpc_to_segment_quantization_config(
                     },
                     quantile: config.quantile,
                     always_ram: config.always_ram,
+                    quantized_only: config.quantized_only,
+                },
+            }))
+        }
+        api::grpc::qdrant::quantization_config::Quantization::Product(config) => {
+            Ok(QuantizationConfig::Product(ProductQuantization {
+                product: ProductQuantizationConfig {
+                    compression: match CompressionRatio::from_i32(config.compression) {
+                        Some(CompressionRatio::X4) => segment::types::CompressionRatio::X4,
+                        Some(CompressionRatio::X8) => segment::types::CompressionRatio::X8,
+                        Some(CompressionRatio::X16) => segment::types::CompressionRatio::X16,
+                        Some(CompressionRatio::X32) => segment::types::CompressionRatio::X32,
+                        Some(CompressionRatio::X64) => segment::types::CompressionRatio::X64,
+                        None => {
+                            return Err(format!(
+                                "Cannot convert compression ratio: {}",
+                                config.compression
+                            ));
+                        }
+                    },
+                    always_ram: config.always_ram,
                 },
             }))
         }
@@ -417,6 +621,32 @@ impl TryFrom<api::grpc::qdrant::CollectionConfig> for CollectionConfig {
                     .ok_or_else(|| {
                         Status::invalid_argument("`write_consistency_factor` cannot be zero")
                     })?,
+                    vector_aliases: params
+                        .vector_aliases
+                        .map(|aliases| aliases.map.into_iter().collect())
+                        .unwrap_or_default(),
+                    default_vector: params.default_vector,
+                    defrag_key: params.defrag_key,
+                    deduplicate_upserts: params.deduplicate_upserts.unwrap_or_default(),
+                    max_concurrent_searches: params.max_concurrent_searches.unwrap_or_default()
+                        as usize,
+                    acl_key: params.acl_key,
+                    default_write_ordering: write_ordering_from_proto(
+                        params.default_write_ordering,
+                    )?,
+                    default_read_consistency: ReadConsistency::try_from_optional(
+                        params.default_read_consistency,
+                    )?,
+                    sparse_vectors: params
+                        .sparse_vectors_config
+                        .map(|config| {
+                            config
+                                .map
+                                .into_iter()
+                                .map(|(name, params)| (name, params.into()))
+                                .collect()
+                        })
+                        .unwrap_or_default(),
                 },
             },
             hnsw_config: match config.hnsw_config {
@@ -438,6 +668,8 @@ impl TryFrom<api::grpc::qdrant::CollectionConfig> for CollectionConfig {
                     None
                 }
             },
+            lifecycle_config: None,
+            created_at: None,
         })
     }
 }
@@ -492,6 +724,8 @@ impl TryFrom<api::grpc::qdrant::PointStruct> for PointStruct {
             id,
             vectors,
             payload,
+            sequence,
+            content_hash,
         } = value;
 
         let converted_payload = proto_to_payloads(payload)?;
@@ -507,6 +741,8 @@ impl TryFrom<api::grpc::qdrant::PointStruct> for PointStruct {
                 .try_into()?,
             vector: vector_struct,
             payload: Some(converted_payload),
+            sequence,
+            content_hash,
         })
     }
 }
@@ -519,6 +755,8 @@ impl TryFrom<PointStruct> for api::grpc::qdrant::PointStruct {
 
         let id = value.id;
         let payload = value.payload;
+        let sequence = value.sequence;
+        let content_hash = value.content_hash;
 
         let converted_payload = match payload {
             None => HashMap::new(),
@@ -529,10 +767,37 @@ impl TryFrom<PointStruct> for api::grpc::qdrant::PointStruct {
             id: Some(id.into()),
             vectors: Some(vectors),
             payload: converted_payload,
+            sequence,
+            content_hash,
         })
     }
 }
 
+impl TryFrom<api::grpc::qdrant::PointVectors> for PointVectors {
+    type Error = Status;
+
+    fn try_from(value: api::grpc::qdrant::PointVectors) -> Result<Self, Self::Error> {
+        let api::grpc::qdrant::PointVectors { id, vectors } = value;
+        Ok(Self {
+            id: id
+                .ok_or_else(|| Status::invalid_argument("Empty ID is not allowed"))?
+                .try_into()?,
+            vector: vectors
+                .ok_or_else(|| Status::invalid_argument("Expected some vectors"))?
+                .try_into()?,
+        })
+    }
+}
+
+impl From<PointVectors> for api::grpc::qdrant::PointVectors {
+    fn from(value: PointVectors) -> Self {
+        Self {
+            id: Some(value.id.into()),
+            vectors: Some(value.vector.into()),
+        }
+    }
+}
+
 impl TryFrom<Batch> for Vec<api::grpc::qdrant::PointStruct> {
     type Error = Status;
 
@@ -554,6 +819,8 @@ impl TryFrom<Batch> for Vec<api::grpc::qdrant::PointStruct> {
                 id,
                 vectors: vectors.map(|v| v.into()),
                 payload: payload.unwrap_or_default(),
+                sequence: None,
+                content_hash: None,
             };
             points.push(point);
         }
@@ -586,6 +853,29 @@ impl TryFrom<api::grpc::qdrant::PointsSelector> for PointsSelector {
     }
 }
 
+impl From<IdChunk> for api::grpc::qdrant::IdChunk {
+    fn from(value: IdChunk) -> Self {
+        Self {
+            start: Some(value.start.into()),
+            count: value.count as u64,
+        }
+    }
+}
+
+impl TryFrom<api::grpc::qdrant::IdChunk> for IdChunk {
+    type Error = Status;
+
+    fn try_from(value: api::grpc::qdrant::IdChunk) -> Result<Self, Self::Error> {
+        let start = value
+            .start
+            .ok_or_else(|| Status::invalid_argument("Malformed IdChunk: start is missing"))?;
+        Ok(Self {
+            start: start.try_into()?,
+            count: value.count as usize,
+        })
+    }
+}
+
 impl From<UpdateResult> for api::grpc::qdrant::UpdateResult {
     fn from(value: UpdateResult) -> Self {
         Self {
@@ -613,15 +903,25 @@ impl TryFrom<api::grpc::qdrant::UpdateResult> for UpdateResult {
                 }
                 _ => return Err(Status::invalid_argument("Malformed UpdateStatus type")),
             },
+            replication: None,
+            generated_ids: None,
+            consistency_token: None,
         })
     }
 }
 
-impl From<api::grpc::qdrant::CountResult> for CountResult {
-    fn from(value: api::grpc::qdrant::CountResult) -> Self {
-        Self {
+impl TryFrom<api::grpc::qdrant::CountResult> for CountResult {
+    type Error = Status;
+
+    fn try_from(value: api::grpc::qdrant::CountResult) -> Result<Self, Self::Error> {
+        Ok(Self {
             count: value.count as usize,
-        }
+            distinct: value.distinct.map(|distinct| distinct as usize),
+            distinct_values: value
+                .distinct_values
+                .map(DistinctValues::try_from)
+                .transpose()?,
+        })
     }
 }
 
@@ -629,6 +929,8 @@ impl From<CountResult> for api::grpc::qdrant::CountResult {
     fn from(value: CountResult) -> Self {
         Self {
             count: value.count as u64,
+            distinct: value.distinct.map(|distinct| distinct as u64),
+            distinct_values: value.distinct_values.map(|values| values.into()),
         }
     }
 }
@@ -653,6 +955,10 @@ impl<'a> From<CollectionSearchRequest<'a>> for api::grpc::qdrant::SearchPoints {
                 vector_name => Some(vector_name.to_string()),
             },
             read_consistency: None,
+            priority: None,
+            with_highlights: request.with_highlights,
+            with_geo_distance: request.with_geo_distance,
+            timeout: None,
         }
     }
 }
@@ -682,6 +988,77 @@ impl TryFrom<api::grpc::qdrant::SearchPoints> for SearchRequest {
                     .unwrap_or_default(),
             ),
             score_threshold: value.score_threshold,
+            with_highlights: value.with_highlights,
+            with_geo_distance: value.with_geo_distance,
+        })
+    }
+}
+
+impl From<api::grpc::qdrant::Fusion> for Fusion {
+    fn from(value: api::grpc::qdrant::Fusion) -> Self {
+        match value {
+            api::grpc::qdrant::Fusion::Rrf => Fusion::Rrf,
+            api::grpc::qdrant::Fusion::WeightedSum => Fusion::WeightedSum,
+            api::grpc::qdrant::Fusion::NormalizedSum => Fusion::NormalizedSum,
+        }
+    }
+}
+
+impl TryFrom<api::grpc::qdrant::PrefetchQuery> for Prefetch {
+    type Error = Status;
+
+    fn try_from(value: api::grpc::qdrant::PrefetchQuery) -> Result<Self, Self::Error> {
+        Ok(Prefetch {
+            vector: match value.vector_name {
+                Some(vector_name) => NamedVector {
+                    name: vector_name,
+                    vector: value.vector,
+                }
+                .into(),
+                None => value.vector.into(),
+            },
+            filter: value.filter.map(|f| f.try_into()).transpose()?,
+            params: value.params.map(|p| p.into()),
+            limit: value.limit.map(|limit| limit as usize),
+            weight: value.weight,
+            score_threshold: value.score_threshold,
+        })
+    }
+}
+
+impl TryFrom<api::grpc::qdrant::QueryPoints> for QueryRequest {
+    type Error = Status;
+
+    fn try_from(value: api::grpc::qdrant::QueryPoints) -> Result<Self, Self::Error> {
+        let fusion = match value.fusion {
+            None => Fusion::default(),
+            Some(fusion) => api::grpc::qdrant::Fusion::from_i32(fusion)
+                .map(Fusion::from)
+                .ok_or_else(|| Status::invalid_argument(format!("Unknown fusion: {fusion}")))?,
+        };
+        if value.prefetch.is_empty() {
+            return Err(Status::invalid_argument(
+                "At least one prefetch is required",
+            ));
+        }
+        Ok(QueryRequest {
+            prefetch: value
+                .prefetch
+                .into_iter()
+                .map(|prefetch| prefetch.try_into())
+                .collect::<Result<_, _>>()?,
+            fusion,
+            filter: value.filter.map(|f| f.try_into()).transpose()?,
+            limit: value.limit as usize,
+            offset: value.offset.unwrap_or_default() as usize,
+            with_payload: value.with_payload.map(|wp| wp.try_into()).transpose()?,
+            with_vector: Some(
+                value
+                    .with_vectors
+                    .map(|with_vectors| with_vectors.into())
+                    .unwrap_or_default(),
+            ),
+            score_threshold: value.score_threshold,
         })
     }
 }
@@ -695,20 +1072,137 @@ impl From<api::grpc::qdrant::LookupLocation> for LookupLocation {
     }
 }
 
-impl TryFrom<api::grpc::qdrant::RecommendPoints> for RecommendRequest {
+impl From<api::grpc::qdrant::RecommendStrategy> for RecommendStrategy {
+    fn from(value: api::grpc::qdrant::RecommendStrategy) -> Self {
+        match value {
+            api::grpc::qdrant::RecommendStrategy::AverageVector => RecommendStrategy::AverageVector,
+            api::grpc::qdrant::RecommendStrategy::BestScore => RecommendStrategy::BestScore,
+        }
+    }
+}
+
+/// Combine examples, given by ids and by raw vectors, into a single list
+pub fn recommend_examples_from_proto(
+    ids: Vec<api::grpc::qdrant::PointId>,
+    vectors: Vec<api::grpc::qdrant::Vector>,
+) -> Result<Vec<RecommendExample>, Status> {
+    ids.into_iter()
+        .map(|id| PointIdType::try_from(id).map(RecommendExample::from))
+        .chain(
+            vectors
+                .into_iter()
+                .map(|vector| Ok(RecommendExample::Vector(vector.data))),
+        )
+        .collect()
+}
+
+/// Weights of the examples are not specified, if the list is empty
+pub fn recommend_weights_from_proto(weights: Vec<f32>) -> Option<Vec<f32>> {
+    if weights.is_empty() {
+        None
+    } else {
+        Some(weights)
+    }
+}
+
+pub fn recommend_strategy_from_proto(
+    strategy: Option<i32>,
+) -> Result<Option<RecommendStrategy>, Status> {
+    strategy
+        .map(|strategy| {
+            api::grpc::qdrant::RecommendStrategy::from_i32(strategy)
+                .map(RecommendStrategy::from)
+                .ok_or_else(|| {
+                    Status::invalid_argument(format!("Unknown recommend strategy: {strategy}"))
+                })
+        })
+        .transpose()
+}
+
+impl TryFrom<api::grpc::qdrant::VectorExample> for RecommendExample {
     type Error = Status;
 
-    fn try_from(value: api::grpc::qdrant::RecommendPoints) -> Result<Self, Self::Error> {
-        Ok(RecommendRequest {
-            positive: value
-                .positive
+    fn try_from(value: api::grpc::qdrant::VectorExample) -> Result<Self, Self::Error> {
+        use api::grpc::qdrant::vector_example::Example;
+        match value.example {
+            Some(Example::Id(id)) => Ok(RecommendExample::PointId(id.try_into()?)),
+            Some(Example::Vector(vector)) => Ok(RecommendExample::Vector(vector.data)),
+            None => Err(Status::invalid_argument("Example is not specified")),
+        }
+    }
+}
+
+fn required_example(
+    example: Option<api::grpc::qdrant::VectorExample>,
+    name: &str,
+) -> Result<RecommendExample, Status> {
+    example
+        .ok_or_else(|| Status::invalid_argument(format!("{name} example is not specified")))?
+        .try_into()
+}
+
+impl TryFrom<api::grpc::qdrant::ContextExamplePair> for ContextExamplePair {
+    type Error = Status;
+
+    fn try_from(value: api::grpc::qdrant::ContextExamplePair) -> Result<Self, Self::Error> {
+        Ok(ContextExamplePair {
+            positive: required_example(value.positive, "Positive")?,
+            negative: required_example(value.negative, "Negative")?,
+        })
+    }
+}
+
+impl TryFrom<api::grpc::qdrant::DiscoverPoints> for DiscoverRequest {
+    type Error = Status;
+
+    fn try_from(value: api::grpc::qdrant::DiscoverPoints) -> Result<Self, Self::Error> {
+        Ok(DiscoverRequest {
+            target: required_example(value.target, "Target")?,
+            context: value
+                .context
                 .into_iter()
-                .map(|p| p.try_into())
+                .map(|pair| pair.try_into())
                 .collect::<Result<_, _>>()?,
-            negative: value
-                .negative
+            filter: value.filter.map(|f| f.try_into()).transpose()?,
+            params: value.params.map(|p| p.into()),
+            limit: value.limit as usize,
+            offset: value.offset.unwrap_or_default() as usize,
+            with_payload: value.with_payload.map(|wp| wp.try_into()).transpose()?,
+            with_vector: Some(
+                value
+                    .with_vectors
+                    .map(|with_vectors| with_vectors.into())
+                    .unwrap_or_default(),
+            ),
+            using: value.using.map(|name| name.into()),
+            lookup_from: value.lookup_from.map(|x| x.into()),
+        })
+    }
+}
+
+impl TryFrom<api::grpc::qdrant::WeightedVectorExample> for WeightedExample {
+    type Error = Status;
+
+    fn try_from(value: api::grpc::qdrant::WeightedVectorExample) -> Result<Self, Self::Error> {
+        Ok(WeightedExample {
+            example: required_example(value.example, "Term")?,
+            weight: value.weight,
+        })
+    }
+}
+
+impl TryFrom<api::grpc::qdrant::SearchArithmeticPoints> for ArithmeticSearchRequest {
+    type Error = Status;
+
+    fn try_from(value: api::grpc::qdrant::SearchArithmeticPoints) -> Result<Self, Self::Error> {
+        if value.terms.is_empty() {
+            return Err(Status::invalid_argument("At least one term is required"));
+        }
+        Ok(ArithmeticSearchRequest {
+            terms: value
+                .terms
                 .into_iter()
-                .map(|p| p.try_into())
+                .map(|term| term.try_into())
                 .collect::<Result<_, _>>()?,
             filter: value.filter.map(|f| f.try_into()).transpose()?,
             params: value.params.map(|p| p.into()),
@@ -728,6 +1222,34 @@ impl TryFrom<api::grpc::qdrant::RecommendPoints> for RecommendRequest {
     }
 }
 
+impl TryFrom<api::grpc::qdrant::RecommendPoints> for RecommendRequest {
+    type Error = Status;
+
+    fn try_from(value: api::grpc::qdrant::RecommendPoints) -> Result<Self, Self::Error> {
+        Ok(RecommendRequest {
+            positive: recommend_examples_from_proto(value.positive, value.positive_vectors)?,
+            negative: recommend_examples_from_proto(value.negative, value.negative_vectors)?,
+            positive_weights: recommend_weights_from_proto(value.positive_weights),
+            negative_weights: recommend_weights_from_proto(value.negative_weights),
+            strategy: recommend_strategy_from_proto(value.strategy)?,
+            filter: value.filter.map(|f| f.try_into()).transpose()?,
+            params: value.params.map(|p| p.into()),
+            limit: value.limit as usize,
+            offset: value.offset.unwrap_or_default() as usize,
+            with_payload: value.with_payload.map(|wp| wp.try_into()).transpose()?,
+            with_vector: Some(
+                value
+                    .with_vectors
+                    .map(|with_vectors| with_vectors.into())
+                    .unwrap_or_default(),
+            ),
+            score_threshold: value.score_threshold,
+            using: value.using.map(|name| name.into()),
+            lookup_from: value.lookup_from.map(|x| x.into()),
+        })
+    }
+}
+
 impl From<VectorParams> for api::grpc::qdrant::VectorParams {
     fn from(value: VectorParams) -> Self {
         api::grpc::qdrant::VectorParams {
@@ -740,6 +1262,7 @@ impl From<VectorParams> for api::grpc::qdrant::VectorParams {
             .into(),
             hnsw_config: value.hnsw_config.map(Into::into),
             quantization_config: value.quantization_config.map(Into::into),
+            projection: value.projection.map(Into::into),
         }
     }
 }
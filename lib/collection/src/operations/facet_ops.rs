@@ -0,0 +1,53 @@
+use std::collections::HashMap;
+
+use schemars::JsonSchema;
+use segment::types::{Filter, PayloadKeyType};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+const DEFAULT_FACET_LIMIT: usize = 10;
+
+/// Request of the number of points for each value of the keyword-indexed payload field
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct FacetRequest {
+    /// Payload key of the field. The field must have a keyword index
+    pub key: PayloadKeyType,
+    /// Count only points, which satisfy this conditions
+    pub filter: Option<Filter>,
+    /// Max number of returned values. Default: 10
+    #[validate(range(min = 1))]
+    pub limit: Option<usize>,
+}
+
+impl FacetRequest {
+    pub fn limit(&self) -> usize {
+        self.limit.unwrap_or(DEFAULT_FACET_LIMIT)
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub struct FacetValueHit {
+    pub value: String,
+    /// Number of points, which have the value
+    pub count: usize,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct FacetResponse {
+    /// Most frequent values in local shards, ordered by decreasing count
+    pub hits: Vec<FacetValueHit>,
+}
+
+/// Select `limit` most frequent values. Values with equal counts are ordered alphabetically.
+pub fn top_values(value_counts: HashMap<String, usize>, limit: usize) -> Vec<FacetValueHit> {
+    let mut hits: Vec<_> = value_counts
+        .into_iter()
+        .map(|(value, count)| FacetValueHit { value, count })
+        .collect();
+    hits.sort_unstable_by(|a, b| b.count.cmp(&a.count).then_with(|| a.value.cmp(&b.value)));
+    hits.truncate(limit);
+    hits
+}
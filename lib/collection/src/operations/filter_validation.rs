@@ -0,0 +1,162 @@
+use std::collections::{BTreeSet, HashMap};
+
+use schemars::JsonSchema;
+use segment::types::{
+    AnyVariants, Condition, FieldCondition, Filter, Match, PayloadFieldSchema, PayloadKeyType,
+    PayloadSchemaParams, PayloadSchemaType, ValueVariants,
+};
+use serde::{Deserialize, Serialize};
+
+/// How filters of read requests are checked against payload keys of the collection.
+/// Filters with unknown keys or with conditions, which can't match values of the indexed type,
+/// silently return empty results.
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum FilterValidation {
+    /// Filters are not checked
+    #[default]
+    Disabled,
+    /// Problems of the filter are logged, the request is executed anyway
+    Warn,
+    /// Requests with problems in the filter are rejected
+    Strict,
+}
+
+/// Result of checking the filter against the schema of indexed fields
+#[derive(Debug, Default)]
+pub struct FilterCheck {
+    /// Descriptions of conditions, which can't match values of the indexed type
+    pub problems: Vec<String>,
+    /// Keys without index, which existence can't be checked by the schema
+    pub unindexed_keys: BTreeSet<PayloadKeyType>,
+}
+
+fn schema_type(schema: &PayloadFieldSchema) -> PayloadSchemaType {
+    match schema {
+        PayloadFieldSchema::FieldType(schema_type) => *schema_type,
+        PayloadFieldSchema::FieldParams(PayloadSchemaParams::Text(_)) => PayloadSchemaType::Text,
+    }
+}
+
+/// Types of indexed values, which the condition is able to match, with the name of the condition
+fn matching_types(condition: &FieldCondition) -> Vec<(&'static str, &'static [PayloadSchemaType])> {
+    const STRING_TYPES: &[PayloadSchemaType] =
+        &[PayloadSchemaType::Keyword, PayloadSchemaType::Text];
+    const INTEGER_TYPES: &[PayloadSchemaType] = &[PayloadSchemaType::Integer];
+    const NUMBER_TYPES: &[PayloadSchemaType] =
+        &[PayloadSchemaType::Integer, PayloadSchemaType::Float];
+    const GEO_TYPES: &[PayloadSchemaType] = &[PayloadSchemaType::Geo];
+    const BOOL_TYPES: &[PayloadSchemaType] = &[PayloadSchemaType::Bool];
+
+    let mut types = Vec::new();
+    match &condition.r#match {
+        Some(Match::Value(value)) => match &value.value {
+            ValueVariants::Keyword(_) => types.push(("keyword match", STRING_TYPES)),
+            ValueVariants::Integer(_) => types.push(("integer match", INTEGER_TYPES)),
+            ValueVariants::Bool(_) => types.push(("bool match", BOOL_TYPES)),
+        },
+        Some(Match::Text(_)) => types.push(("text match", STRING_TYPES)),
+        Some(Match::Any(any)) => match &any.any {
+            AnyVariants::Keywords(_) => types.push(("keywords match", STRING_TYPES)),
+            AnyVariants::Integers(_) => types.push(("integers match", INTEGER_TYPES)),
+        },
+        None => {}
+    }
+    if condition.range.is_some() {
+        types.push(("range", NUMBER_TYPES));
+    }
+    if condition.geo_bounding_box.is_some() {
+        types.push(("geo bounding box", GEO_TYPES));
+    }
+    if condition.geo_radius.is_some() {
+        types.push(("geo radius", GEO_TYPES));
+    }
+    types
+}
+
+fn collect_field_conditions<'a>(filter: &'a Filter, conditions: &mut Vec<&'a FieldCondition>) {
+    let clauses = [&filter.must, &filter.should, &filter.must_not];
+    for condition in clauses.into_iter().flatten().flatten() {
+        match condition {
+            Condition::Field(field_condition) => conditions.push(field_condition),
+            Condition::Filter(nested) => collect_field_conditions(nested, conditions),
+            Condition::IsEmpty(_) | Condition::IsNull(_) | Condition::HasId(_) => {}
+        }
+    }
+}
+
+/// Check field conditions of the filter, including nested filters, against the schema of
+/// indexed fields
+pub fn check_filter_fields(
+    filter: &Filter,
+    indexed_fields: &HashMap<PayloadKeyType, PayloadFieldSchema>,
+) -> FilterCheck {
+    let mut conditions = Vec::new();
+    collect_field_conditions(filter, &mut conditions);
+
+    let mut check = FilterCheck::default();
+    for condition in conditions {
+        let indexed_type = match indexed_fields.get(&condition.key) {
+            Some(schema) => schema_type(schema),
+            None => {
+                check.unindexed_keys.insert(condition.key.clone());
+                continue;
+            }
+        };
+        for (condition_name, types) in matching_types(condition) {
+            if !types.contains(&indexed_type) {
+                check.problems.push(format!(
+                    "{condition_name} can't match values of key `{}`, indexed as {indexed_type:?}",
+                    condition.key,
+                ));
+            }
+        }
+    }
+    check
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_filter_fields() {
+        let indexed_fields = HashMap::from([
+            (
+                "city".to_string(),
+                PayloadFieldSchema::FieldType(PayloadSchemaType::Keyword),
+            ),
+            (
+                "price".to_string(),
+                PayloadFieldSchema::FieldType(PayloadSchemaType::Float),
+            ),
+        ]);
+
+        let filter: Filter = serde_json::from_str(
+            r#"{
+                "must": [
+                    { "key": "city", "match": { "value": "Berlin" } },
+                    { "key": "price", "range": { "lt": 100 } },
+                    { "key": "color", "match": { "value": "red" } }
+                ]
+            }"#,
+        )
+        .unwrap();
+        let check = check_filter_fields(&filter, &indexed_fields);
+        assert!(check.problems.is_empty());
+        assert_eq!(check.unindexed_keys, BTreeSet::from(["color".to_string()]));
+
+        let filter: Filter = serde_json::from_str(
+            r#"{
+                "should": [
+                    { "must_not": [{ "key": "city", "range": { "gt": 1 } }] },
+                    { "key": "price", "match": { "value": "cheap" } }
+                ]
+            }"#,
+        )
+        .unwrap();
+        let check = check_filter_fields(&filter, &indexed_fields);
+        assert_eq!(check.problems.len(), 2);
+        assert!(check.unindexed_keys.is_empty());
+    }
+}
@@ -0,0 +1,58 @@
+use schemars::JsonSchema;
+use segment::data_types::histogram::HistogramBuckets;
+use segment::types::{Filter, PayloadKeyType};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+const DEFAULT_HISTOGRAM_BUCKETS: usize = 10;
+
+/// Request of the distribution of values of the integer or float field index.
+/// Values are split into buckets of equal width between the min and max value.
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, Default)]
+#[serde(rename_all = "snake_case")]
+pub struct HistogramRequest {
+    /// Count only points, which satisfy this conditions
+    pub filter: Option<Filter>,
+    /// Number of buckets. Default: 10
+    #[validate(range(min = 1, max = 1000))]
+    pub buckets: Option<usize>,
+}
+
+impl HistogramRequest {
+    pub fn buckets(&self) -> usize {
+        self.buckets.unwrap_or(DEFAULT_HISTOGRAM_BUCKETS)
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct HistogramBucket {
+    /// Lower border of the bucket, inclusive
+    pub from: f64,
+    /// Upper border of the bucket, exclusive for all buckets but the last one
+    pub to: f64,
+    /// Number of points with values in the bucket.
+    /// Points with several values are counted in each bucket, which has any of them.
+    pub count: usize,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct HistogramResult {
+    pub field_name: PayloadKeyType,
+    /// Buckets ordered by value, empty if no point in local shards has values of the field
+    pub buckets: Vec<HistogramBucket>,
+}
+
+/// Combine borders of the buckets with the counts of points in them
+pub fn histogram_buckets(buckets: &HistogramBuckets, counts: Vec<usize>) -> Vec<HistogramBucket> {
+    counts
+        .into_iter()
+        .enumerate()
+        .map(|(bucket, count)| HistogramBucket {
+            from: buckets.bucket_start(bucket),
+            to: buckets.bucket_start(bucket + 1),
+            count,
+        })
+        .collect()
+}
@@ -0,0 +1,143 @@
+use schemars::JsonSchema;
+use segment::types::PointIdType;
+use serde::{Deserialize, Serialize};
+
+/// Number of chunks, each shard is split into for a single partition.
+/// More chunks give better balanced partitions of the collection, which consists of many shards.
+pub const CHUNKS_PER_PARTITION: usize = 8;
+
+/// Consecutive point ids of a shard, starting from `start`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IdChunk {
+    pub start: PointIdType,
+    /// Number of points in the chunk
+    pub count: usize,
+}
+
+/// Range of point ids, from `start` inclusive to `end` exclusive. Unbounded, if not set
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub struct IdRange {
+    pub start: Option<PointIdType>,
+    pub end: Option<PointIdType>,
+}
+
+impl IdRange {
+    pub fn contains(&self, id: PointIdType) -> bool {
+        self.start.map_or(true, |start| start <= id) && self.end.map_or(true, |end| id < end)
+    }
+}
+
+/// Split sorted unique ids into chunks with about the same number of points
+pub fn split_into_chunks(ids: &[PointIdType], chunks: usize) -> Vec<IdChunk> {
+    let chunks = chunks.min(ids.len());
+    (0..chunks)
+        .map(|chunk| {
+            let from = chunk * ids.len() / chunks;
+            let to = (chunk + 1) * ids.len() / chunks;
+            IdChunk {
+                start: ids[from],
+                count: to - from,
+            }
+        })
+        .collect()
+}
+
+/// Split the whole id space into at most `partitions` non-overlapping ranges with about the same
+/// number of points, according to the chunks of all shards of the collection.
+/// Ranges are ordered by id and cover all possible ids, so points, inserted later, also fall into
+/// exactly one of them.
+pub fn partition_id_ranges(mut chunks: Vec<IdChunk>, partitions: usize) -> Vec<IdRange> {
+    chunks.sort_by_key(|chunk| chunk.start);
+    let total: usize = chunks.iter().map(|chunk| chunk.count).sum();
+
+    let mut boundaries: Vec<PointIdType> = Vec::new();
+    let mut accumulated = 0;
+    for chunk in &chunks {
+        // Start the next partition from this chunk, if the previous chunks fill the current one
+        let filled = boundaries.len() + 1;
+        if accumulated > 0
+            && filled < partitions
+            && accumulated * partitions >= filled * total
+            && boundaries.last() != Some(&chunk.start)
+        {
+            boundaries.push(chunk.start);
+        }
+        accumulated += chunk.count;
+    }
+
+    let mut ranges = Vec::with_capacity(boundaries.len() + 1);
+    let mut start = None;
+    for boundary in boundaries {
+        ranges.push(IdRange {
+            start,
+            end: Some(boundary),
+        });
+        start = Some(boundary);
+    }
+    ranges.push(IdRange { start, end: None });
+    ranges
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(range: std::ops::Range<u64>) -> Vec<PointIdType> {
+        range.map(PointIdType::from).collect()
+    }
+
+    #[test]
+    fn test_split_into_chunks() {
+        let chunks = split_into_chunks(&ids(0..10), 3);
+        assert_eq!(
+            chunks,
+            vec![
+                IdChunk {
+                    start: 0.into(),
+                    count: 3
+                },
+                IdChunk {
+                    start: 3.into(),
+                    count: 3
+                },
+                IdChunk {
+                    start: 6.into(),
+                    count: 4
+                },
+            ]
+        );
+        assert_eq!(split_into_chunks(&ids(0..2), 3).len(), 2);
+        assert!(split_into_chunks(&[], 3).is_empty());
+    }
+
+    #[test]
+    fn test_partition_id_ranges() {
+        // Two shards with interleaved ids
+        let even: Vec<_> = (0..100).map(|id| PointIdType::from(id * 2)).collect();
+        let odd: Vec<_> = (0..100).map(|id| PointIdType::from(id * 2 + 1)).collect();
+        let mut chunks = split_into_chunks(&even, 2 * CHUNKS_PER_PARTITION);
+        chunks.extend(split_into_chunks(&odd, 2 * CHUNKS_PER_PARTITION));
+
+        let ranges = partition_id_ranges(chunks, 2);
+        assert_eq!(ranges.len(), 2);
+        assert_eq!(ranges[0].start, None);
+        assert_eq!(ranges[0].end, ranges[1].start);
+        assert_eq!(ranges[1].end, None);
+
+        let all_ids: Vec<_> = even.iter().chain(&odd).copied().collect();
+        for id in &all_ids {
+            assert_eq!(ranges.iter().filter(|range| range.contains(*id)).count(), 1);
+        }
+        let first = all_ids.iter().filter(|id| ranges[0].contains(**id)).count();
+        assert!(
+            (90..=110).contains(&first),
+            "unbalanced partitions: {first}"
+        );
+    }
+
+    #[test]
+    fn test_partition_empty_collection() {
+        assert_eq!(partition_id_ranges(vec![], 4), vec![IdRange::default()]);
+    }
+}
@@ -0,0 +1,29 @@
+use schemars::JsonSchema;
+use segment::types::PayloadKeyType;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum IndexRebuildStatus {
+    /// Segments are being re-indexed
+    Running,
+    /// Index is rebuilt in all segments
+    Completed,
+    /// Rebuild is stopped by an error
+    Failed,
+}
+
+/// Progress of the payload index rebuild in local shards of the collection
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct IndexRebuildInfo {
+    pub field_name: PayloadKeyType,
+    pub status: IndexRebuildStatus,
+    /// Number of segments, which have the index of the field
+    pub segments_total: usize,
+    /// Number of segments, in which the index is already rebuilt
+    pub segments_rebuilt: usize,
+    /// Error, which stopped the rebuild
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
@@ -0,0 +1,80 @@
+use std::time::Duration;
+
+use schemars::JsonSchema;
+use segment::types::QuantizationConfig;
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+const SECONDS_IN_DAY: u64 = 24 * 60 * 60;
+
+/// Action, applied to the collection once it reaches the age of the rule
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum LifecycleAction {
+    /// Store vectors of all optimized segments in memory mapped files
+    OnDisk,
+    /// Quantize vectors of all optimized segments with the given params
+    Quantize(QuantizationConfig),
+    /// Delete the collection
+    Delete,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub struct LifecycleRule {
+    /// Age of the collection in days, after which the action is applied
+    pub after_days: u64,
+    pub action: LifecycleAction,
+}
+
+/// Rules, which are periodically evaluated against the age of the collection.
+///
+/// Rules are applied through regular collection updates, so the optimizers rebuild existing
+/// segments according to the new config in the background.
+#[derive(
+    Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq, Eq, Hash, Default,
+)]
+#[serde(rename_all = "snake_case")]
+pub struct LifecycleConfig {
+    #[validate]
+    pub rules: Vec<LifecycleRule>,
+}
+
+impl LifecycleConfig {
+    /// Actions of all rules, which are due for the collection of the given age
+    pub fn due_actions(&self, age: Duration) -> impl Iterator<Item = &LifecycleAction> {
+        self.rules
+            .iter()
+            .filter(move |rule| age.as_secs() >= rule.after_days.saturating_mul(SECONDS_IN_DAY))
+            .map(|rule| &rule.action)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_due_actions() {
+        let config = LifecycleConfig {
+            rules: vec![
+                LifecycleRule {
+                    after_days: 30,
+                    action: LifecycleAction::OnDisk,
+                },
+                LifecycleRule {
+                    after_days: 90,
+                    action: LifecycleAction::Delete,
+                },
+            ],
+        };
+
+        let days = |days: u64| Duration::from_secs(days * SECONDS_IN_DAY);
+        assert_eq!(config.due_actions(days(29)).count(), 0);
+        assert_eq!(
+            config.due_actions(days(30)).collect::<Vec<_>>(),
+            vec![&LifecycleAction::OnDisk]
+        );
+        assert_eq!(config.due_actions(days(365)).count(), 2);
+    }
+}
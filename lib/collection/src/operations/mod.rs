@@ -1,14 +1,31 @@
+pub mod backfill_ops;
+pub mod cardinality_ops;
 pub mod cluster_ops;
 pub mod config_diff;
+pub mod config_history_ops;
 pub mod consistency_params;
 pub mod conversions;
+pub mod facet_ops;
+pub mod filter_validation;
+pub mod histogram_ops;
+pub mod id_range_ops;
+pub mod index_rebuild_ops;
+pub mod lifecycle_ops;
 pub mod operation_effect;
+pub mod orphan_files_ops;
 pub mod payload_ops;
+pub mod payload_schema_ops;
+pub mod point_access_ops;
 pub mod point_ops;
+pub mod recall_ops;
+pub mod scoring_benchmark_ops;
 pub mod shared_storage_config;
 pub mod snapshot_ops;
+pub mod terms_ops;
 pub mod types;
+pub mod vacuum_ops;
 pub mod validation;
+pub mod wal_replay_ops;
 
 use std::collections::HashMap;
 
@@ -105,7 +122,7 @@ impl Validate for CollectionUpdateOperations {
     }
 }
 
-fn point_to_shard(point_id: ExtendedPointId, ring: &HashRing<ShardId>) -> ShardId {
+pub(crate) fn point_to_shard(point_id: ExtendedPointId, ring: &HashRing<ShardId>) -> ShardId {
     *ring
         .get(&point_id)
         .expect("Hash ring is guaranteed to be non-empty")
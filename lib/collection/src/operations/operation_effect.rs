@@ -57,6 +57,9 @@ impl EstimateOperationEffectArea for point_ops::PointOperations {
                 );
                 OperationEffectArea::Points(sync_op.points.iter().map(|x| x.id).collect())
             }
+            point_ops::PointOperations::UpdateVectors { points } => {
+                OperationEffectArea::Points(points.iter().map(|x| x.id).collect())
+            }
         }
     }
 }
@@ -0,0 +1,138 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+/// Remove files in segment directories, which are not used by any segment of the collection.
+///
+/// WAL and snapshot directories are not checked: WAL files are removed by the WAL on truncation,
+/// and snapshots are managed with the snapshot API.
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, Default)]
+#[serde(rename_all = "snake_case")]
+pub struct OrphanFilesRequest {
+    /// Files are removed only if they stay orphaned for this number of seconds since they were found.
+    /// Default: `orphan_files.grace_period_sec` of the storage config
+    #[validate(range(min = 1))]
+    pub grace_period_sec: Option<u64>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Default)]
+#[serde(rename_all = "snake_case")]
+pub struct OrphanFilesResult {
+    /// Removed orphaned files and directories
+    pub removed: Vec<String>,
+    /// Disk space reclaimed by the removal, in bytes
+    pub reclaimed_bytes: u64,
+    /// Orphaned files and directories, which are going to be removed after the grace period
+    pub pending: Vec<String>,
+}
+
+/// Orphaned paths with the time they were found first, not persisted
+#[derive(Debug, Default)]
+pub struct OrphanedFiles {
+    found: HashMap<PathBuf, Instant>,
+}
+
+impl OrphanedFiles {
+    /// Replace tracked paths with the currently orphaned ones, keeping the time they were found first.
+    ///
+    /// Returns paths, which are orphaned for longer than the grace period, and the rest of them.
+    /// Paths, which are found for the first time, are never expired, even with zero grace period:
+    /// they might belong to an operation, which started right before the check.
+    pub fn update(
+        &mut self,
+        orphaned: Vec<PathBuf>,
+        grace_period: Duration,
+    ) -> (Vec<PathBuf>, Vec<PathBuf>) {
+        let now = Instant::now();
+        let mut expired = Vec::new();
+        let mut pending = Vec::new();
+        let mut found = HashMap::with_capacity(orphaned.len());
+        for path in orphaned {
+            match self.found.get(&path).copied() {
+                Some(found_at) if now.duration_since(found_at) >= grace_period => {
+                    expired.push(path.clone());
+                    found.insert(path, found_at);
+                }
+                found_at => {
+                    pending.push(path.clone());
+                    found.insert(path, found_at.unwrap_or(now));
+                }
+            }
+        }
+        self.found = found;
+        (expired, pending)
+    }
+}
+
+/// Total size of the file or all files in the directory, in bytes
+fn disk_size(path: &Path) -> std::io::Result<u64> {
+    let metadata = fs::symlink_metadata(path)?;
+    if !metadata.is_dir() {
+        return Ok(metadata.len());
+    }
+    let mut size = 0;
+    for entry in fs::read_dir(path)? {
+        size += disk_size(&entry?.path())?;
+    }
+    Ok(size)
+}
+
+/// Remove file or directory, returns reclaimed space in bytes
+pub fn remove_orphaned_path(path: &Path) -> std::io::Result<u64> {
+    let size = disk_size(path)?;
+    if fs::symlink_metadata(path)?.is_dir() {
+        fs::remove_dir_all(path)?;
+    } else {
+        fs::remove_file(path)?;
+    }
+    Ok(size)
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::Builder;
+
+    use super::*;
+
+    #[test]
+    fn test_orphaned_files_grace_period() {
+        let mut orphaned_files = OrphanedFiles::default();
+        let a = PathBuf::from("a");
+        let b = PathBuf::from("b");
+
+        let (expired, pending) = orphaned_files.update(vec![a.clone()], Duration::from_secs(60));
+        assert!(expired.is_empty());
+        assert_eq!(pending, vec![a.clone()]);
+
+        // `a` is found before, `b` is new and is kept even without grace period
+        let (expired, pending) =
+            orphaned_files.update(vec![a.clone(), b.clone()], Duration::from_millis(0));
+        assert_eq!(expired, vec![a.clone()]);
+        assert_eq!(pending, vec![b.clone()]);
+
+        // Paths, which are not orphaned anymore, are forgotten
+        orphaned_files.update(vec![b], Duration::from_secs(60));
+        assert!(!orphaned_files.found.contains_key(&a));
+    }
+
+    #[test]
+    fn test_remove_orphaned_path() {
+        let dir = Builder::new().prefix("orphaned").tempdir().unwrap();
+        let segment_path = dir.path().join("segment");
+        fs::create_dir_all(segment_path.join("index")).unwrap();
+        fs::write(segment_path.join("data"), [0u8; 100]).unwrap();
+        fs::write(segment_path.join("index").join("data"), [0u8; 20]).unwrap();
+        let file_path = dir.path().join("file");
+        fs::write(&file_path, [0u8; 10]).unwrap();
+
+        assert_eq!(remove_orphaned_path(&segment_path).unwrap(), 120);
+        assert_eq!(remove_orphaned_path(&file_path).unwrap(), 10);
+        assert!(!segment_path.exists());
+        assert!(!file_path.exists());
+    }
+}
@@ -0,0 +1,245 @@
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, HashMap};
+
+use schemars::JsonSchema;
+use segment::types::{infer_collection_value_type, Filter, Payload, PayloadSchemaType};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use validator::Validate;
+
+const DEFAULT_INFERENCE_SAMPLES: usize = 1000;
+
+/// Keyword values with this average number of words or more are suggested to be full-text indexed
+const TEXT_SUGGESTION_MIN_AVG_WORDS: f64 = 4.0;
+
+/// Infer types of payload fields from a random sample of points
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, Default)]
+#[serde(rename_all = "snake_case")]
+pub struct PayloadSchemaInferenceRequest {
+    /// Sample only points, which satisfy this conditions
+    pub filter: Option<Filter>,
+    /// Max number of sampled points. Default: 1000
+    #[validate(range(min = 1, max = 100000))]
+    pub samples: Option<usize>,
+    /// Seed of the sample, makes the inference reproducible.
+    /// If not specified - a new random sample is used for each request.
+    pub seed: Option<u64>,
+}
+
+impl PayloadSchemaInferenceRequest {
+    pub fn samples(&self) -> usize {
+        self.samples.unwrap_or(DEFAULT_INFERENCE_SAMPLES)
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub struct PayloadTypeCount {
+    pub data_type: PayloadSchemaType,
+    /// Number of sampled points, which values of the field have this type
+    pub count: usize,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct InferredPayloadField {
+    /// Number of sampled points, which have the field
+    pub points: usize,
+    /// Number of sampled points by the type of the field values
+    pub types: Vec<PayloadTypeCount>,
+    /// Number of sampled points, which values can't be indexed or mix several types,
+    /// e.g. nulls or arrays of strings and numbers
+    pub untyped: usize,
+    /// Schema of the index, suggested for the field. Absent if types of values are inconsistent
+    pub suggested_schema: Option<PayloadSchemaType>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct PayloadSchemaInferenceResult {
+    /// Number of points in the sample
+    pub sampled_points: usize,
+    /// Inferred fields by their path. Nested fields are separated by `.`,
+    /// fields of objects inside of arrays are marked by `[]`
+    pub fields: BTreeMap<String, InferredPayloadField>,
+}
+
+#[derive(Default)]
+struct FieldStats {
+    points: usize,
+    untyped: usize,
+    types: HashMap<PayloadSchemaType, usize>,
+    keyword_values: usize,
+    keyword_words: usize,
+}
+
+impl FieldStats {
+    fn add_point(&mut self, values: &[&Value]) {
+        self.points += 1;
+        match infer_collection_value_type(values.iter().copied()) {
+            Some(data_type) => {
+                *self.types.entry(data_type).or_default() += 1;
+                if data_type == PayloadSchemaType::Keyword {
+                    for value in values.iter().filter_map(|value| value.as_str()) {
+                        self.keyword_values += 1;
+                        self.keyword_words += value.split_whitespace().count();
+                    }
+                }
+            }
+            None => self.untyped += 1,
+        }
+    }
+
+    fn suggested_schema(&self) -> Option<PayloadSchemaType> {
+        let has_type = |data_type| self.types.contains_key(&data_type);
+        match self.types.len() {
+            1 if has_type(PayloadSchemaType::Keyword) => {
+                let avg_words = self.keyword_words as f64 / self.keyword_values.max(1) as f64;
+                if avg_words >= TEXT_SUGGESTION_MIN_AVG_WORDS {
+                    Some(PayloadSchemaType::Text)
+                } else {
+                    Some(PayloadSchemaType::Keyword)
+                }
+            }
+            1 => self.types.keys().next().copied(),
+            // Integers may be indexed by the float index as well
+            2 if has_type(PayloadSchemaType::Integer) && has_type(PayloadSchemaType::Float) => {
+                Some(PayloadSchemaType::Float)
+            }
+            _ => None,
+        }
+    }
+
+    fn into_field(self) -> InferredPayloadField {
+        let suggested_schema = self.suggested_schema();
+        let mut types: Vec<_> = self
+            .types
+            .into_iter()
+            .map(|(data_type, count)| PayloadTypeCount { data_type, count })
+            .collect();
+        types.sort_by_key(|type_count| Reverse(type_count.count));
+        InferredPayloadField {
+            points: self.points,
+            types,
+            untyped: self.untyped,
+            suggested_schema,
+        }
+    }
+}
+
+/// Whether the object is a geo point, which is a value itself rather than an object with fields
+fn is_geo_point(object: &Map<String, Value>) -> bool {
+    let is_number = |key| object.get(key).and_then(Value::as_f64).is_some();
+    is_number("lon") && is_number("lat")
+}
+
+/// Collect values of all fields of the object, including nested ones, by their path
+fn collect_field_values<'a>(
+    prefix: &str,
+    object: &'a Map<String, Value>,
+    field_values: &mut BTreeMap<String, Vec<&'a Value>>,
+) {
+    for (key, value) in object {
+        let path = if prefix.is_empty() {
+            key.to_owned()
+        } else {
+            format!("{prefix}.{key}")
+        };
+        match value {
+            Value::Object(nested) if !is_geo_point(nested) => {
+                collect_field_values(&path, nested, field_values);
+            }
+            Value::Array(array) => {
+                let array_path = format!("{path}[]");
+                for element in array {
+                    match element {
+                        Value::Object(nested) if !is_geo_point(nested) => {
+                            collect_field_values(&array_path, nested, field_values);
+                        }
+                        _ => field_values.entry(path.clone()).or_default().push(element),
+                    }
+                }
+            }
+            _ => field_values.entry(path).or_default().push(value),
+        }
+    }
+}
+
+/// Infer types of all payload fields, found in the sampled payloads,
+/// and suggest index schemas for them
+pub fn infer_payload_schema<'a>(
+    payloads: impl IntoIterator<Item = &'a Payload>,
+) -> PayloadSchemaInferenceResult {
+    let mut sampled_points = 0;
+    let mut stats: BTreeMap<String, FieldStats> = BTreeMap::new();
+    for payload in payloads {
+        sampled_points += 1;
+        let mut field_values = BTreeMap::new();
+        collect_field_values("", &payload.0, &mut field_values);
+        for (path, values) in field_values {
+            stats.entry(path).or_default().add_point(&values);
+        }
+    }
+
+    PayloadSchemaInferenceResult {
+        sampled_points,
+        fields: stats
+            .into_iter()
+            .map(|(path, field_stats)| (path, field_stats.into_field()))
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_infer_payload_schema() {
+        let payloads: Vec<Payload> = serde_json::from_str(
+            r#"[
+                {
+                    "city": "Berlin",
+                    "price": 10,
+                    "location": { "lon": 13.4, "lat": 52.5 },
+                    "meta": { "tags": ["a", "b"], "flag": true },
+                    "reviews": [{ "text": "nice and quiet place to stay" }]
+                },
+                {
+                    "city": "Paris",
+                    "price": 10.5,
+                    "meta": { "tags": "c", "flag": false },
+                    "reviews": [{ "text": "too loud for a good night sleep" }]
+                },
+                { "city": 42, "price": "free" }
+            ]"#,
+        )
+        .unwrap();
+
+        let result = infer_payload_schema(&payloads);
+        assert_eq!(result.sampled_points, 3);
+
+        let city = &result.fields["city"];
+        assert_eq!(city.points, 3);
+        assert_eq!(city.suggested_schema, None);
+
+        let location = &result.fields["location"];
+        assert_eq!(location.suggested_schema, Some(PayloadSchemaType::Geo));
+
+        let tags = &result.fields["meta.tags"];
+        assert_eq!(tags.points, 2);
+        assert_eq!(tags.suggested_schema, Some(PayloadSchemaType::Keyword));
+
+        let flag = &result.fields["meta.flag"];
+        assert_eq!(flag.untyped, 0);
+        assert_eq!(flag.suggested_schema, Some(PayloadSchemaType::Bool));
+
+        let reviews = &result.fields["reviews[].text"];
+        assert_eq!(reviews.suggested_schema, Some(PayloadSchemaType::Text));
+
+        let price = &result.fields["price"];
+        assert_eq!(price.suggested_schema, None);
+        let price = infer_payload_schema(&payloads[..2]).fields["price"].clone();
+        assert_eq!(price.suggested_schema, Some(PayloadSchemaType::Float));
+    }
+}
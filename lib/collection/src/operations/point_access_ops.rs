@@ -0,0 +1,165 @@
+use std::collections::HashMap;
+
+use schemars::JsonSchema;
+use segment::types::PointIdType;
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+const DEFAULT_HOT_POINTS_LIMIT: usize = 10;
+
+/// Max number of points with access counters. If exceeded, all counters are halved and
+/// points with zero counters are forgotten, so rarely accessed points don't take the space.
+const MAX_TRACKED_POINTS: usize = 100_000;
+
+/// Report the most frequently accessed points of the collection
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, Default)]
+#[serde(rename_all = "snake_case")]
+pub struct HotPointsRequest {
+    /// Max number of points in the report. Default: 10
+    #[validate(range(min = 1))]
+    pub limit: Option<usize>,
+}
+
+impl HotPointsRequest {
+    pub fn limit(&self) -> usize {
+        self.limit.unwrap_or(DEFAULT_HOT_POINTS_LIMIT)
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub struct HotPoint {
+    pub id: PointIdType,
+    /// Number of sampled retrieve requests, which returned the point
+    pub reads: usize,
+    /// Number of sampled search requests, which returned the point in results
+    pub hits: usize,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct HotPointsReport {
+    /// Fraction of read requests, which are accounted. If 0 - access is not tracked
+    pub sample_rate: f64,
+    /// Number of accounted read requests since the collection was loaded
+    pub sampled_requests: usize,
+    /// Number of points with access counters
+    pub tracked_points: usize,
+    /// Points with the most reads and hits, most accessed first
+    pub points: Vec<HotPoint>,
+}
+
+#[derive(Default, Clone, Copy)]
+struct AccessCounter {
+    reads: usize,
+    hits: usize,
+}
+
+impl AccessCounter {
+    fn total(&self) -> usize {
+        self.reads + self.hits
+    }
+}
+
+/// Access counters of points, accumulated from sampled read requests. Not persisted.
+#[derive(Default)]
+pub struct PointAccessStats {
+    sampled_requests: usize,
+    points: HashMap<PointIdType, AccessCounter>,
+}
+
+impl PointAccessStats {
+    /// Record points, returned by a retrieve request
+    pub fn add_reads(&mut self, ids: impl IntoIterator<Item = PointIdType>) {
+        self.sampled_requests += 1;
+        for id in ids {
+            self.counter(id).reads += 1;
+        }
+    }
+
+    /// Record points, returned in results of a search request
+    pub fn add_hits(&mut self, ids: impl IntoIterator<Item = PointIdType>) {
+        self.sampled_requests += 1;
+        for id in ids {
+            self.counter(id).hits += 1;
+        }
+    }
+
+    fn counter(&mut self, id: PointIdType) -> &mut AccessCounter {
+        if self.points.len() >= MAX_TRACKED_POINTS && !self.points.contains_key(&id) {
+            self.decay();
+        }
+        self.points.entry(id).or_default()
+    }
+
+    fn decay(&mut self) {
+        self.points.retain(|_, counter| {
+            counter.reads /= 2;
+            counter.hits /= 2;
+            counter.total() > 0
+        });
+    }
+
+    pub fn report(&self, sample_rate: f64, limit: usize) -> HotPointsReport {
+        let mut points: Vec<_> = self
+            .points
+            .iter()
+            .map(|(id, counter)| HotPoint {
+                id: *id,
+                reads: counter.reads,
+                hits: counter.hits,
+            })
+            .collect();
+        points.sort_unstable_by(|a, b| {
+            (b.reads + b.hits)
+                .cmp(&(a.reads + a.hits))
+                .then_with(|| a.id.cmp(&b.id))
+        });
+        points.truncate(limit);
+
+        HotPointsReport {
+            sample_rate,
+            sampled_requests: self.sampled_requests,
+            tracked_points: self.points.len(),
+            points,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_point_access_stats() {
+        let id = |num: u64| PointIdType::from(num);
+        let mut stats = PointAccessStats::default();
+        stats.add_reads([id(1), id(2)]);
+        stats.add_hits([id(2), id(3)]);
+        stats.add_hits([id(2)]);
+
+        let report = stats.report(1.0, 2);
+        assert_eq!(report.sampled_requests, 3);
+        assert_eq!(report.tracked_points, 3);
+        assert_eq!(
+            report.points,
+            vec![
+                HotPoint {
+                    id: id(2),
+                    reads: 1,
+                    hits: 2,
+                },
+                HotPoint {
+                    id: id(1),
+                    reads: 1,
+                    hits: 0,
+                },
+            ],
+        );
+
+        stats.decay();
+        let report = stats.report(1.0, 10);
+        assert_eq!(report.tracked_points, 1);
+        assert_eq!(report.points[0].hits, 1);
+    }
+}
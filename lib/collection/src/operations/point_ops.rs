@@ -1,20 +1,26 @@
 use std::borrow::Cow;
+use std::collections::hash_map::DefaultHasher;
 use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
 
-use itertools::izip;
+use itertools::{izip, Itertools};
 use schemars::gen::SchemaGenerator;
 use schemars::schema::{ObjectValidation, Schema, SchemaObject, SubschemaValidation};
 use schemars::JsonSchema;
 use segment::common::utils::transpose_map_into_named_vector;
 use segment::data_types::named_vectors::NamedVectors;
-use segment::data_types::vectors::{only_default_vector, BatchVectorStruct, VectorStruct};
-use segment::types::{Filter, Payload, PointIdType};
+use segment::data_types::vectors::{
+    only_default_vector, BatchVectorStruct, VectorStruct, DEFAULT_VECTOR_NAME,
+};
+use segment::types::{Filter, Payload, PointIdType, SeqNumberType};
 use serde::{Deserialize, Serialize};
+use uuid::Uuid;
 use validator::Validate;
 
 use super::{point_to_shard, split_iter_by_shard, OperationToShard, SplitByShard};
+use crate::config::CollectionParams;
 use crate::hash_ring::HashRing;
-use crate::operations::types::Record;
+use crate::operations::types::{CollectionResult, Record};
 use crate::shards::shard::ShardId;
 
 /// Defines write ordering guarantees for collection operations
@@ -25,7 +31,7 @@ use crate::shards::shard::ShardId;
 ///
 /// * `strong` - Write operations go through the permanent leader, consistent, but may be unavailable if leader is down
 ///
-#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, Default)]
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, Default, PartialEq, Eq, Hash)]
 #[serde(rename_all = "snake_case")]
 pub enum WriteOrdering {
     #[default]
@@ -37,13 +43,32 @@ pub enum WriteOrdering {
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
 #[serde(rename_all = "snake_case")]
 pub struct PointStruct {
-    /// Point id
+    /// Point id. If not set, UUID is generated by the server and returned in the response of the upsert
+    #[serde(default = "missing_point_id")]
     pub id: PointIdType,
     /// Vectors
     #[serde(alias = "vectors")]
     pub vector: VectorStruct,
     /// Payload values (optional)
     pub payload: Option<Payload>,
+    /// External sequence number of the point (optional).
+    /// Must increase monotonically for each point, e.g. an offset of the source topic.
+    /// Update is skipped if the stored point already has the same or a higher sequence number.
+    /// The sequence number is kept by payload updates and upserts without it, and is not returned in responses.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sequence: Option<SeqNumberType>,
+    /// Hash of the point content (optional).
+    /// Update is skipped if the stored point has the same hash.
+    /// The hash is not returned in responses, and is dropped once the payload of the point is changed.
+    /// If not provided, it is computed from vectors and payload in collections with `deduplicate_upserts` enabled.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_hash: Option<String>,
+}
+
+/// Placeholder of the id of a point, which is not set in the request.
+/// Replaced with a generated UUID before the upsert, see `PointInsertOperations::generate_missing_ids`.
+fn missing_point_id() -> PointIdType {
+    PointIdType::Uuid(Uuid::nil())
 }
 
 /// Warn: panics if the vector is empty
@@ -55,16 +80,23 @@ impl TryFrom<Record> for PointStruct {
             id,
             payload,
             vector,
+            geo_distance: _,
         } = record;
 
         if vector.is_none() {
             return Err("Vector is empty".to_string());
         }
+        let payload = payload
+            .map(|payload| payload.into_payload())
+            .transpose()
+            .map_err(|err| err.to_string())?;
 
         Ok(Self {
             id,
             payload,
             vector: vector.unwrap(),
+            sequence: None,
+            content_hash: None,
         })
     }
 }
@@ -72,12 +104,25 @@ impl TryFrom<Record> for PointStruct {
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
 #[serde(rename_all = "snake_case")]
 pub struct Batch {
+    /// Ids of the points. If not set, UUIDs are generated by the server and returned in the response of the upsert
+    #[serde(default)]
     pub ids: Vec<PointIdType>,
     pub vectors: BatchVectorStruct,
     pub payloads: Option<Vec<Option<Payload>>>,
 }
 
 impl Batch {
+    /// Number of points in the batch, according to the vectors, without relying on ids
+    fn vectors_count(&self) -> usize {
+        match &self.vectors {
+            BatchVectorStruct::Single(vectors) => vectors.len(),
+            BatchVectorStruct::Multi(named_vectors) => match named_vectors.values().next() {
+                Some(vectors) => vectors.len(),
+                None => self.payloads.as_ref().map_or(0, |payloads| payloads.len()),
+            },
+        }
+    }
+
     pub fn empty() -> Self {
         Self {
             ids: vec![],
@@ -132,6 +177,13 @@ struct PointsList {
     points: Vec<PointStruct>,
 }
 
+/// Vectors to set for an existing point, other vectors of the point are kept
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct PointVectors {
+    pub id: PointIdType,
+    pub vector: VectorStruct,
+}
+
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct PointSyncOperation {
     /// Minimal id of the sync range
@@ -226,20 +278,26 @@ impl Validate for PointInsertOperations {
                     errors
                 };
 
+                // Missing ids are generated by the server, one for each vector
+                let ids_count = if batch.ids.is_empty() {
+                    batch.vectors_count()
+                } else {
+                    batch.ids.len()
+                };
                 match &batch.vectors {
                     BatchVectorStruct::Single(vectors) => {
-                        if batch.ids.len() != vectors.len() {
+                        if ids_count != vectors.len() {
                             return Err(create_error(bad_input_description(
-                                batch.ids.len(),
+                                ids_count,
                                 vectors.len(),
                             )));
                         }
                     }
                     BatchVectorStruct::Multi(named_vectors) => {
                         for vectors in named_vectors.values() {
-                            if batch.ids.len() != vectors.len() {
+                            if ids_count != vectors.len() {
                                 return Err(create_error(bad_input_description(
-                                    batch.ids.len(),
+                                    ids_count,
                                     vectors.len(),
                                 )));
                             }
@@ -247,10 +305,10 @@ impl Validate for PointInsertOperations {
                     }
                 }
                 if let Some(payload_vector) = &batch.payloads {
-                    if payload_vector.len() != batch.ids.len() {
+                    if payload_vector.len() != ids_count {
                         return Err(create_error(format!(
                             "number of ids and payloads must be equal ({} != {})",
-                            batch.ids.len(),
+                            ids_count,
                             payload_vector.len(),
                         )));
                     }
@@ -274,6 +332,24 @@ impl SplitByShard for PointInsertOperations {
     }
 }
 
+impl From<Batch> for Vec<PointStruct> {
+    fn from(batch: Batch) -> Self {
+        let all_vectors = batch.vectors.into_all_vectors(batch.ids.len());
+        let payloads = batch
+            .payloads
+            .unwrap_or_else(|| vec![None; batch.ids.len()]);
+        izip!(batch.ids, all_vectors, payloads)
+            .map(|(id, vectors, payload)| PointStruct {
+                id,
+                vector: vectors.into(),
+                payload,
+                sequence: None,
+                content_hash: None,
+            })
+            .collect()
+    }
+}
+
 impl From<Batch> for PointInsertOperations {
     fn from(batch: Batch) -> Self {
         PointInsertOperations::PointsBatch(batch)
@@ -286,6 +362,121 @@ impl From<Vec<PointStruct>> for PointInsertOperations {
     }
 }
 
+impl PointInsertOperations {
+    /// Assign generated UUIDs to points, which don't have ids in the request.
+    /// Returns the generated ids in the order of the points.
+    pub fn generate_missing_ids(&mut self) -> Vec<PointIdType> {
+        match self {
+            PointInsertOperations::PointsBatch(batch) => {
+                if !batch.ids.is_empty() {
+                    return vec![];
+                }
+                batch.ids = (0..batch.vectors_count())
+                    .map(|_| PointIdType::Uuid(Uuid::new_v4()))
+                    .collect();
+                batch.ids.clone()
+            }
+            PointInsertOperations::PointsList(points) => points
+                .iter_mut()
+                .filter(|point| point.id == missing_point_id())
+                .map(|point| {
+                    point.id = PointIdType::Uuid(Uuid::new_v4());
+                    point.id
+                })
+                .collect(),
+        }
+    }
+
+    /// Replace vector aliases and unnamed vectors with the real names of the collection vectors
+    pub fn resolve_vector_names(self, params: &CollectionParams) -> Self {
+        match self {
+            PointInsertOperations::PointsBatch(mut batch) => {
+                batch.vectors = match batch.vectors {
+                    BatchVectorStruct::Single(vectors) => {
+                        let vector_name = params.resolve_vector_name(DEFAULT_VECTOR_NAME);
+                        if vector_name == DEFAULT_VECTOR_NAME {
+                            BatchVectorStruct::Single(vectors)
+                        } else {
+                            BatchVectorStruct::Multi(HashMap::from([(vector_name, vectors)]))
+                        }
+                    }
+                    BatchVectorStruct::Multi(named_vectors) => BatchVectorStruct::Multi(
+                        named_vectors
+                            .into_iter()
+                            .map(|(name, vectors)| (params.resolve_vector_name(&name), vectors))
+                            .collect(),
+                    ),
+                };
+                PointInsertOperations::PointsBatch(batch)
+            }
+            PointInsertOperations::PointsList(points) => PointInsertOperations::PointsList(
+                points
+                    .into_iter()
+                    .map(|point| point.resolve_vector_names(params))
+                    .collect(),
+            ),
+        }
+    }
+
+    /// Apply configured projections to vectors. Vector names are expected to be resolved.
+    pub fn project_vectors(self, params: &CollectionParams) -> CollectionResult<Self> {
+        Ok(match self {
+            PointInsertOperations::PointsBatch(mut batch) => {
+                batch.vectors = match batch.vectors {
+                    BatchVectorStruct::Single(vectors) => BatchVectorStruct::Single(
+                        vectors
+                            .into_iter()
+                            .map(|vector| params.project_vector(DEFAULT_VECTOR_NAME, vector))
+                            .collect::<CollectionResult<_>>()?,
+                    ),
+                    BatchVectorStruct::Multi(named_vectors) => BatchVectorStruct::Multi(
+                        named_vectors
+                            .into_iter()
+                            .map(|(name, vectors)| {
+                                let vectors = vectors
+                                    .into_iter()
+                                    .map(|vector| params.project_vector(&name, vector))
+                                    .collect::<CollectionResult<_>>()?;
+                                Ok((name, vectors))
+                            })
+                            .collect::<CollectionResult<_>>()?,
+                    ),
+                };
+                PointInsertOperations::PointsBatch(batch)
+            }
+            PointInsertOperations::PointsList(points) => PointInsertOperations::PointsList(
+                points
+                    .into_iter()
+                    .map(|point| point.project_vectors(params))
+                    .collect::<CollectionResult<_>>()?,
+            ),
+        })
+    }
+
+    /// Compute content hashes of points, which don't provide one, if the collection deduplicates upserts.
+    /// Batches are converted into lists of points to carry the hashes.
+    pub fn compute_content_hashes(self, params: &CollectionParams) -> Self {
+        if !params.deduplicate_upserts {
+            return self;
+        }
+        let points = match self {
+            PointInsertOperations::PointsBatch(batch) => batch.into(),
+            PointInsertOperations::PointsList(points) => points,
+        };
+        PointInsertOperations::PointsList(
+            points
+                .into_iter()
+                .map(|mut point| {
+                    if point.content_hash.is_none() {
+                        point.content_hash = Some(point.compute_content_hash());
+                    }
+                    point
+                })
+                .collect(),
+        )
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize, Clone)]
 #[serde(rename_all = "snake_case")]
 pub enum PointOperations {
@@ -297,6 +488,8 @@ pub enum PointOperations {
     DeletePointsByFilter(Filter),
     /// Points Sync
     SyncPoints(PointSyncOperation),
+    /// Set vectors of existing points, other vectors and payload are kept
+    UpdateVectors { points: Vec<PointVectors> },
 }
 
 impl PointOperations {
@@ -306,6 +499,34 @@ impl PointOperations {
             PointOperations::DeletePoints { .. } => false,
             PointOperations::DeletePointsByFilter(_) => false,
             PointOperations::SyncPoints(_) => true,
+            PointOperations::UpdateVectors { .. } => true,
+        }
+    }
+
+    pub fn resolve_vector_names(self, params: &CollectionParams) -> Self {
+        match self {
+            PointOperations::UpsertPoints(upsert_points) => {
+                PointOperations::UpsertPoints(upsert_points.resolve_vector_names(params))
+            }
+            other => other,
+        }
+    }
+
+    pub fn project_vectors(self, params: &CollectionParams) -> CollectionResult<Self> {
+        match self {
+            PointOperations::UpsertPoints(upsert_points) => Ok(PointOperations::UpsertPoints(
+                upsert_points.project_vectors(params)?,
+            )),
+            other => Ok(other),
+        }
+    }
+
+    pub fn compute_content_hashes(self, params: &CollectionParams) -> Self {
+        match self {
+            PointOperations::UpsertPoints(upsert_points) => {
+                PointOperations::UpsertPoints(upsert_points.compute_content_hashes(params))
+            }
+            other => other,
         }
     }
 }
@@ -317,6 +538,7 @@ impl Validate for PointOperations {
             PointOperations::DeletePoints { ids: _ } => Ok(()),
             PointOperations::DeletePointsByFilter(_) => Ok(()),
             PointOperations::SyncPoints(_) => Ok(()),
+            PointOperations::UpdateVectors { .. } => Ok(()),
         }
     }
 }
@@ -443,6 +665,10 @@ impl SplitByShard for PointOperations {
                 );
                 OperationToShard::by_shard(vec![])
             }
+            PointOperations::UpdateVectors { points } => {
+                split_iter_by_shard(points, |point| point.id, ring)
+                    .map(|points| PointOperations::UpdateVectors { points })
+            }
         }
     }
 }
@@ -466,6 +692,62 @@ impl PointStruct {
             VectorStruct::Multi(vectors) => NamedVectors::from_map_ref(vectors),
         }
     }
+    pub fn resolve_vector_names(mut self, params: &CollectionParams) -> Self {
+        self.vector = match self.vector {
+            VectorStruct::Single(vector) => {
+                let vector_name = params.resolve_vector_name(DEFAULT_VECTOR_NAME);
+                if vector_name == DEFAULT_VECTOR_NAME {
+                    VectorStruct::Single(vector)
+                } else {
+                    VectorStruct::Multi(HashMap::from([(vector_name, vector)]))
+                }
+            }
+            VectorStruct::Multi(vectors) => VectorStruct::Multi(
+                vectors
+                    .into_iter()
+                    .map(|(name, vector)| (params.resolve_vector_name(&name), vector))
+                    .collect(),
+            ),
+        };
+        self
+    }
+
+    pub fn project_vectors(mut self, params: &CollectionParams) -> CollectionResult<Self> {
+        self.vector = match self.vector {
+            VectorStruct::Single(vector) => {
+                VectorStruct::Single(params.project_vector(DEFAULT_VECTOR_NAME, vector)?)
+            }
+            VectorStruct::Multi(vectors) => VectorStruct::Multi(
+                vectors
+                    .into_iter()
+                    .map(|(name, vector)| {
+                        let vector = params.project_vector(&name, vector)?;
+                        Ok((name, vector))
+                    })
+                    .collect::<CollectionResult<_>>()?,
+            ),
+        };
+        Ok(self)
+    }
+
+    /// Hash of vectors and payload of the point.
+    /// Only used to detect unchanged points, so it is not required to be stable across versions.
+    pub fn compute_content_hash(&self) -> String {
+        let mut hasher = DefaultHasher::new();
+        let vectors = self.get_vectors();
+        for (name, vector) in vectors.iter().sorted_by_key(|(name, _)| *name) {
+            name.hash(&mut hasher);
+            for value in vector {
+                value.to_bits().hash(&mut hasher);
+            }
+        }
+        if let Some(payload) = &self.payload {
+            serde_json::to_vec(payload)
+                .unwrap_or_default()
+                .hash(&mut hasher);
+        }
+        format!("{:016x}", hasher.finish())
+    }
 }
 
 #[cfg(test)]
@@ -494,5 +776,119 @@ mod tests {
             payloads: Some(vec![]),
         });
         assert!(matches!(batch.validate(), Err(_)));
+
+        let batch = PointInsertOperations::PointsBatch(Batch {
+            ids: vec![],
+            vectors: vec![vec![0.1], vec![0.2]].into(),
+            payloads: Some(vec![None, None]),
+        });
+        assert!(matches!(batch.validate(), Ok(())));
+    }
+
+    #[test]
+    fn generate_missing_ids() {
+        let mut batch = PointInsertOperations::PointsBatch(Batch {
+            ids: vec![],
+            vectors: vec![vec![0.1], vec![0.2]].into(),
+            payloads: None,
+        });
+        let generated = batch.generate_missing_ids();
+        assert_eq!(generated.len(), 2);
+        assert_ne!(generated[0], generated[1]);
+        assert!(batch.generate_missing_ids().is_empty());
+
+        let mut points: PointInsertOperations =
+            serde_json::from_value::<Vec<PointStruct>>(serde_json::json!([
+                { "id": 1, "vector": [0.1] },
+                { "vector": [0.2] },
+            ]))
+            .unwrap()
+            .into();
+        let generated = points.generate_missing_ids();
+        assert_eq!(generated.len(), 1);
+        let points = match points {
+            PointInsertOperations::PointsList(points) => points,
+            PointInsertOperations::PointsBatch(_) => panic!("expected list of points"),
+        };
+        assert_eq!(points[0].id, 1.into());
+        assert_eq!(points[1].id, generated[0]);
+    }
+
+    #[test]
+    fn project_point_vectors() {
+        use std::num::{NonZeroU32, NonZeroU64};
+
+        use segment::types::Distance;
+
+        use crate::operations::types::{VectorParams, VectorProjection, VectorsConfig};
+
+        let params = CollectionParams {
+            vectors: VectorsConfig::Single(VectorParams {
+                size: NonZeroU64::new(2).unwrap(),
+                distance: Distance::Dot,
+                hnsw_config: None,
+                quantization_config: None,
+                projection: Some(VectorProjection {
+                    matrix: vec![vec![1.0, 0.0, 0.0], vec![0.0, 0.5, 0.5]],
+                    mean: Some(vec![1.0, 1.0, 1.0]),
+                }),
+            }),
+            shard_number: NonZeroU32::new(1).unwrap(),
+            replication_factor: NonZeroU32::new(1).unwrap(),
+            write_consistency_factor: NonZeroU32::new(1).unwrap(),
+            on_disk_payload: false,
+            vector_aliases: Default::default(),
+            default_vector: None,
+            defrag_key: None,
+            deduplicate_upserts: false,
+            max_concurrent_searches: 0,
+            acl_key: None,
+            default_write_ordering: None,
+            default_read_consistency: None,
+            sparse_vectors: Default::default(),
+        };
+        assert!(params.vectors.validate().is_ok());
+
+        let point = PointStruct {
+            id: 1.into(),
+            vector: vec![3.0, 2.0, 4.0].into(),
+            payload: None,
+            sequence: None,
+            content_hash: None,
+        };
+        let point = point.project_vectors(&params).unwrap();
+        assert_eq!(point.vector.get(DEFAULT_VECTOR_NAME), Some(&vec![2.0, 2.0]));
+
+        let point = PointStruct {
+            id: 1.into(),
+            vector: vec![3.0, 2.0].into(),
+            payload: None,
+            sequence: None,
+            content_hash: None,
+        };
+        assert!(point.project_vectors(&params).is_err());
+    }
+
+    #[test]
+    fn point_content_hash() {
+        let point = |vector: Vec<f32>, payload: Option<Payload>| PointStruct {
+            id: 1.into(),
+            vector: vector.into(),
+            payload,
+            sequence: None,
+            content_hash: None,
+        };
+        let payload: Payload = serde_json::json!({ "color": "red" }).into();
+
+        let hash = point(vec![1.0, 2.0], Some(payload.clone())).compute_content_hash();
+        assert_eq!(
+            hash,
+            point(vec![1.0, 2.0], Some(payload.clone())).compute_content_hash()
+        );
+        assert_ne!(
+            hash,
+            point(vec![1.0, 3.0], Some(payload)).compute_content_hash()
+        );
+        assert_ne!(hash, point(vec![1.0, 2.0], None).compute_content_hash());
     }
 }
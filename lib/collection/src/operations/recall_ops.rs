@@ -0,0 +1,155 @@
+use std::collections::HashSet;
+use std::time::Instant;
+
+use schemars::JsonSchema;
+use segment::entry::entry_point::{OperationResult, SegmentEntry};
+use segment::types::{SearchParams, SegmentType, WithPayload, WithVector};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::collection_manager::holders::segment_holder::SegmentId;
+use crate::shards::shard::ShardId;
+
+const DEFAULT_RECALL_SAMPLES: usize = 10;
+const DEFAULT_RECALL_LIMIT: usize = 10;
+
+/// Estimate quality of the vector index.
+///
+/// Stored points are sampled from each segment and used as queries for both exact and
+/// approximate search within the segment.
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, Default)]
+#[serde(rename_all = "snake_case")]
+pub struct RecallEstimationRequest {
+    /// Name of the vector to check. Default: default vector
+    pub vector: Option<String>,
+    /// Number of points to sample from each segment. Default: 10
+    #[validate(range(min = 1, max = 1000))]
+    pub samples: Option<usize>,
+    /// Number of search results to compare, `k` in recall@k. Default: 10
+    #[validate(range(min = 1, max = 1000))]
+    pub limit: Option<usize>,
+    /// Params of the approximate search
+    pub params: Option<SearchParams>,
+}
+
+impl RecallEstimationRequest {
+    pub fn samples(&self) -> usize {
+        self.samples.unwrap_or(DEFAULT_RECALL_SAMPLES)
+    }
+
+    pub fn limit(&self) -> usize {
+        self.limit.unwrap_or(DEFAULT_RECALL_LIMIT)
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct SegmentRecall {
+    pub shard_id: ShardId,
+    pub segment_id: SegmentId,
+    pub segment_type: SegmentType,
+    /// Number of points used as queries
+    pub samples: usize,
+    /// Average share of exact search results found by approximate search
+    pub recall: Option<f64>,
+    /// Average duration of exact search
+    pub exact_latency_micros: Option<f64>,
+    /// Average duration of approximate search
+    pub approximate_latency_micros: Option<f64>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct RecallEstimationResult {
+    /// Name of the checked vector
+    pub vector: String,
+    /// Number of compared search results
+    pub limit: usize,
+    /// Recall of each segment of local shards
+    pub segments: Vec<SegmentRecall>,
+}
+
+/// Compare exact and approximate search within a single segment, using sampled stored points
+/// as queries.
+///
+/// Returns `None` if the segment doesn't store the vector.
+pub fn estimate_segment_recall(
+    segment: &dyn SegmentEntry,
+    shard_id: ShardId,
+    segment_id: SegmentId,
+    vector_name: &str,
+    request: &RecallEstimationRequest,
+) -> OperationResult<Option<SegmentRecall>> {
+    if !segment.vector_dims().contains_key(vector_name) {
+        return Ok(None);
+    }
+
+    let limit = request.limit();
+    let exact_params = SearchParams {
+        exact: true,
+        ..Default::default()
+    };
+    let approximate_params = SearchParams {
+        exact: false,
+        ..request.params.unwrap_or_default()
+    };
+
+    let mut samples = 0;
+    let mut recall_sum = 0.0;
+    let mut exact_micros = 0.0;
+    let mut approximate_micros = 0.0;
+
+    for point_id in segment.sample_filtered(request.samples(), None, segment_id as u64) {
+        // Points stored before the vector was added don't have it
+        let vector = match segment.vector(vector_name, point_id) {
+            Ok(vector) => vector,
+            Err(_) => continue,
+        };
+
+        let timer = Instant::now();
+        let exact = segment.search(
+            vector_name,
+            &vector,
+            &WithPayload::from(false),
+            &WithVector::Bool(false),
+            None,
+            limit,
+            Some(&exact_params),
+        )?;
+        exact_micros += timer.elapsed().as_micros() as f64;
+
+        let timer = Instant::now();
+        let approximate = segment.search(
+            vector_name,
+            &vector,
+            &WithPayload::from(false),
+            &WithVector::Bool(false),
+            None,
+            limit,
+            Some(&approximate_params),
+        )?;
+        approximate_micros += timer.elapsed().as_micros() as f64;
+
+        if exact.is_empty() {
+            continue;
+        }
+        let found: HashSet<_> = approximate.iter().map(|point| point.id).collect();
+        let matched = exact
+            .iter()
+            .filter(|point| found.contains(&point.id))
+            .count();
+        recall_sum += matched as f64 / exact.len() as f64;
+        samples += 1;
+    }
+
+    let average = |sum: f64| (samples > 0).then(|| sum / samples as f64);
+    Ok(Some(SegmentRecall {
+        shard_id,
+        segment_id,
+        segment_type: segment.segment_type(),
+        samples,
+        recall: average(recall_sum),
+        exact_latency_micros: average(exact_micros),
+        approximate_latency_micros: average(approximate_micros),
+    }))
+}
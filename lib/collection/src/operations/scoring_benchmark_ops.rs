@@ -0,0 +1,111 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use schemars::JsonSchema;
+use segment::types::Distance;
+use segment::vector_storage::scoring_benchmark::{ScoringMeasurement, ScoringStorageType};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+const DEFAULT_BENCHMARK_POINTS: usize = 10_000;
+
+/// Measure raw scoring throughput of the stored vectors.
+///
+/// A random query is scored against stored vectors of each segment of local shards. Original
+/// vectors are scored with every distance metric, quantized vectors with the configured one.
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, Default)]
+#[serde(rename_all = "snake_case")]
+pub struct ScoringBenchmarkRequest {
+    /// Name of the vector to score. Default: default vector
+    pub vector: Option<String>,
+    /// Max number of vectors to score in each segment. Default: 10000
+    #[validate(range(min = 1))]
+    pub points: Option<usize>,
+}
+
+impl ScoringBenchmarkRequest {
+    pub fn points(&self) -> usize {
+        self.points.unwrap_or(DEFAULT_BENCHMARK_POINTS)
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct ScoringThroughput {
+    pub storage_type: ScoringStorageType,
+    pub distance: Distance,
+    /// Total number of scored vectors
+    pub scored_points: usize,
+    /// Total duration of scoring
+    pub elapsed_micros: u64,
+    /// Number of vectors scored per second
+    pub scores_per_second: Option<f64>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct ScoringBenchmarkResult {
+    /// Name of the scored vector
+    pub vector: String,
+    /// Throughput for each storage type and distance metric, summed over local segments
+    pub measurements: Vec<ScoringThroughput>,
+}
+
+/// Sum measurements of the same storage type and distance metric
+pub fn aggregate_measurements(measurements: Vec<ScoringMeasurement>) -> Vec<ScoringThroughput> {
+    let mut totals: HashMap<(ScoringStorageType, Distance), (usize, Duration)> = HashMap::new();
+    for measurement in measurements {
+        let total = totals
+            .entry((measurement.storage_type, measurement.distance))
+            .or_default();
+        total.0 += measurement.scored;
+        total.1 += measurement.elapsed;
+    }
+
+    let mut throughputs: Vec<_> = totals
+        .into_iter()
+        .map(
+            |((storage_type, distance), (scored_points, elapsed))| ScoringThroughput {
+                storage_type,
+                distance,
+                scored_points,
+                elapsed_micros: elapsed.as_micros() as u64,
+                scores_per_second: (!elapsed.is_zero())
+                    .then(|| scored_points as f64 / elapsed.as_secs_f64()),
+            },
+        )
+        .collect();
+    throughputs
+        .sort_by_key(|throughput| (throughput.storage_type as u8, throughput.distance as u8));
+    throughputs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_aggregate_measurements() {
+        let measurement = |storage_type, distance, scored, millis| ScoringMeasurement {
+            storage_type,
+            distance,
+            scored,
+            elapsed: Duration::from_millis(millis),
+        };
+        let throughputs = aggregate_measurements(vec![
+            measurement(ScoringStorageType::Quantized, Distance::Dot, 100, 1),
+            measurement(ScoringStorageType::InMemory, Distance::Dot, 100, 10),
+            measurement(ScoringStorageType::InMemory, Distance::Dot, 300, 10),
+            measurement(ScoringStorageType::InMemory, Distance::Cosine, 0, 0),
+        ]);
+
+        assert_eq!(throughputs.len(), 3);
+        assert_eq!(throughputs[0].storage_type, ScoringStorageType::InMemory);
+        assert_eq!(throughputs[0].distance, Distance::Cosine);
+        assert_eq!(throughputs[0].scores_per_second, None);
+        assert_eq!(throughputs[1].scored_points, 400);
+        assert_eq!(throughputs[1].elapsed_micros, 20_000);
+        assert_eq!(throughputs[1].scores_per_second, Some(20_000.0));
+        assert_eq!(throughputs[2].storage_type, ScoringStorageType::Quantized);
+    }
+}
@@ -1,15 +1,72 @@
-use crate::operations::types::NodeType;
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::common::optimization_budget::OptimizationBudget;
+use crate::operations::filter_validation::FilterValidation;
+use crate::operations::types::{NodeType, ReadPreference};
+use crate::wal_archive::WalArchiveConfig;
 
 const DEFAULT_UPDATE_QUEUE_SIZE: usize = 100;
 const DEFAULT_UPDATE_QUEUE_SIZE_LISTENER: usize = 10_000;
 
+/// Automatic recovery of replicas of this peer, which are marked as dead
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+pub struct DeadReplicaRecoveryConfig {
+    /// If false, dead replicas are only recovered on request of the recovery API
+    #[serde(default = "default_dead_replica_recovery_enabled")]
+    pub enabled: bool,
+    /// Dead replica is recovered only after it stays dead for this number of seconds.
+    /// Also the delay between recovery attempts, if the recovery fails.
+    #[serde(default = "default_dead_replica_recovery_cooldown_sec")]
+    pub cooldown_sec: u64,
+}
+
+impl DeadReplicaRecoveryConfig {
+    pub fn cooldown(&self) -> Duration {
+        Duration::from_secs(self.cooldown_sec)
+    }
+}
+
+impl Default for DeadReplicaRecoveryConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_dead_replica_recovery_enabled(),
+            cooldown_sec: default_dead_replica_recovery_cooldown_sec(),
+        }
+    }
+}
+
+fn default_dead_replica_recovery_enabled() -> bool {
+    true
+}
+
+fn default_dead_replica_recovery_cooldown_sec() -> u64 {
+    10
+}
+
 /// Storage configuration shared between all collections.
 /// Represents a per-node configuration, which might be changes with restart.
 /// Vales of this struct are not persisted.
-#[derive(Copy, Clone, Debug)]
+#[derive(Clone, Debug)]
 pub struct SharedStorageConfig {
     pub update_queue_size: usize,
     pub node_type: NodeType,
+    /// Fraction of approximate searches, which are compared with exact search to estimate recall
+    pub search_recall_sample_rate: f64,
+    /// Fraction of retrieve and search requests, which are accounted in point access counters
+    pub point_access_sample_rate: f64,
+    /// Replica selection policy for reads, which can be served by any replica
+    pub read_preference: ReadPreference,
+    /// Validation of filters of read requests, which don't specify it explicitly
+    pub filter_validation: FilterValidation,
+    /// Archiving of WAL records before truncation. If not set - records are not archived
+    pub wal_archive: Option<WalArchiveConfig>,
+    /// Automatic recovery of dead replicas of this peer
+    pub dead_replica_recovery: DeadReplicaRecoveryConfig,
+    /// Limit of concurrent optimizations, shared by all collections of the node
+    pub optimization_budget: Arc<OptimizationBudget>,
 }
 
 impl Default for SharedStorageConfig {
@@ -17,12 +74,30 @@ impl Default for SharedStorageConfig {
         Self {
             update_queue_size: DEFAULT_UPDATE_QUEUE_SIZE,
             node_type: Default::default(),
+            search_recall_sample_rate: 0.0,
+            point_access_sample_rate: 0.0,
+            read_preference: Default::default(),
+            filter_validation: Default::default(),
+            wal_archive: None,
+            dead_replica_recovery: Default::default(),
+            optimization_budget: Default::default(),
         }
     }
 }
 
 impl SharedStorageConfig {
-    pub fn new(update_queue_size: Option<usize>, node_type: NodeType) -> Self {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        update_queue_size: Option<usize>,
+        node_type: NodeType,
+        search_recall_sample_rate: f64,
+        point_access_sample_rate: f64,
+        read_preference: ReadPreference,
+        filter_validation: FilterValidation,
+        wal_archive: Option<WalArchiveConfig>,
+        dead_replica_recovery: DeadReplicaRecoveryConfig,
+        max_optimization_threads: usize,
+    ) -> Self {
         let update_queue_size = update_queue_size.unwrap_or(match node_type {
             NodeType::Normal => DEFAULT_UPDATE_QUEUE_SIZE,
             NodeType::Listener => DEFAULT_UPDATE_QUEUE_SIZE_LISTENER,
@@ -31,6 +106,13 @@ impl SharedStorageConfig {
         Self {
             update_queue_size,
             node_type,
+            search_recall_sample_rate,
+            point_access_sample_rate,
+            read_preference,
+            filter_validation,
+            wal_archive,
+            dead_replica_recovery,
+            optimization_budget: Arc::new(OptimizationBudget::new(max_optimization_threads)),
         }
     }
 }
@@ -4,11 +4,14 @@ use std::time::SystemTime;
 use api::grpc::conversions::date_time_to_proto;
 use chrono::NaiveDateTime;
 use schemars::JsonSchema;
+use segment::types::{SegmentInfo, SegmentVerification};
 use serde::{Deserialize, Serialize};
 use url::Url;
 use validator::Validate;
 
+use crate::config::CollectionConfig;
 use crate::operations::types::CollectionResult;
+use crate::shards::shard::ShardId;
 
 /// Defines source of truth for snapshot recovery
 /// `Snapshot` means - prefer snapshot data over the current state
@@ -52,6 +55,99 @@ impl From<SnapshotDescription> for api::grpc::qdrant::SnapshotDescription {
     }
 }
 
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct SegmentSnapshotInspection {
+    pub segment_id: String,
+    pub info: SegmentInfo,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct ShardSnapshotInspection {
+    pub shard_id: ShardId,
+    /// Whether the snapshot contains data of the shard.
+    /// Replicas, hosted on other peers, are not included into the snapshot
+    pub has_local_data: bool,
+    /// Number of points in segments of the shard
+    pub points_count: usize,
+    pub segments: Vec<SegmentSnapshotInspection>,
+}
+
+/// Content of the collection snapshot archive
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct SnapshotInspection {
+    /// Version of Qdrant, which created the snapshot
+    pub version: String,
+    pub config: CollectionConfig,
+    /// Number of points in all shards of the snapshot
+    pub points_count: usize,
+    pub shards: Vec<ShardSnapshotInspection>,
+}
+
+impl ShardSnapshotInspection {
+    pub fn new(
+        shard_id: ShardId,
+        has_local_data: bool,
+        segments: Vec<SegmentSnapshotInspection>,
+    ) -> Self {
+        ShardSnapshotInspection {
+            shard_id,
+            has_local_data,
+            points_count: segments.iter().map(|segment| segment.info.num_points).sum(),
+            segments,
+        }
+    }
+}
+
+impl SnapshotInspection {
+    pub fn new(
+        version: String,
+        config: CollectionConfig,
+        shards: Vec<ShardSnapshotInspection>,
+    ) -> Self {
+        SnapshotInspection {
+            version,
+            config,
+            points_count: shards.iter().map(|shard| shard.points_count).sum(),
+            shards,
+        }
+    }
+}
+
+/// Summary of the verification pass over segments of the restored snapshot
+#[derive(Debug, Default, Deserialize, Serialize, JsonSchema, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct SnapshotVerification {
+    /// Number of verified segments
+    pub segments: usize,
+    /// Number of points in verified segments
+    pub points_count: usize,
+    /// Number of vectors, read from verified segments
+    pub sampled_vectors: usize,
+    /// Descriptions of found inconsistencies
+    pub problems: Vec<String>,
+}
+
+impl SnapshotVerification {
+    pub fn is_ok(&self) -> bool {
+        self.problems.is_empty()
+    }
+
+    pub fn add_segment(&mut self, segment_path: &Path, verification: &SegmentVerification) {
+        self.segments += 1;
+        self.points_count += verification.points_count;
+        self.sampled_vectors += verification.sampled_vectors;
+        self.problems.extend(
+            verification
+                .problems()
+                .into_iter()
+                .map(|problem| format!("Segment {}: {problem}", segment_path.display())),
+        );
+    }
+}
+
 pub async fn get_snapshot_description(path: &Path) -> CollectionResult<SnapshotDescription> {
     let name = path.file_name().unwrap().to_str().unwrap();
     let file_meta = tokio::fs::metadata(&path).await?;
@@ -0,0 +1,53 @@
+use std::collections::HashMap;
+
+use schemars::JsonSchema;
+use segment::types::{Filter, PayloadKeyType};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+const DEFAULT_TERMS_LIMIT: usize = 10;
+
+/// Request of the most frequent terms of the keyword or full-text field index.
+/// Terms of the full-text index are tokens, produced by the tokenizer of the index.
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, Default)]
+#[serde(rename_all = "snake_case")]
+pub struct TermsRequest {
+    /// Count only points, which satisfy this conditions
+    pub filter: Option<Filter>,
+    /// Max number of returned terms. Default: 10
+    #[validate(range(min = 1))]
+    pub limit: Option<usize>,
+}
+
+impl TermsRequest {
+    pub fn limit(&self) -> usize {
+        self.limit.unwrap_or(DEFAULT_TERMS_LIMIT)
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub struct TermCount {
+    pub term: String,
+    /// Number of points, which have the term
+    pub count: usize,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct TermsResult {
+    pub field_name: PayloadKeyType,
+    /// Most frequent terms in local shards, ordered by decreasing count
+    pub terms: Vec<TermCount>,
+}
+
+/// Select `limit` most frequent terms. Terms with equal counts are ordered alphabetically.
+pub fn top_terms(term_counts: HashMap<String, usize>, limit: usize) -> Vec<TermCount> {
+    let mut terms: Vec<_> = term_counts
+        .into_iter()
+        .map(|(term, count)| TermCount { term, count })
+        .collect();
+    terms.sort_unstable_by(|a, b| b.count.cmp(&a.count).then_with(|| a.term.cmp(&b.term)));
+    terms.truncate(limit);
+    terms
+}
@@ -1,4 +1,5 @@
 use std::backtrace::Backtrace;
+use std::borrow::Cow;
 use std::collections::{BTreeMap, HashMap};
 use std::num::NonZeroU64;
 use std::time::SystemTimeError;
@@ -8,13 +9,16 @@ use futures::io;
 use schemars::JsonSchema;
 use segment::common::anonymize::Anonymize;
 use segment::common::file_operations::FileStorageError;
+use segment::data_types::distinct::DistinctValues;
+use segment::data_types::order_by::Direction;
 use segment::data_types::vectors::{
-    NamedVectorStruct, VectorStruct, VectorType, DEFAULT_VECTOR_NAME,
+    NamedVectorStruct, VectorElementType, VectorStruct, VectorType, DEFAULT_VECTOR_NAME,
 };
 use segment::entry::entry_point::OperationError;
 use segment::types::{
-    Distance, Filter, Payload, PayloadIndexInfo, PayloadKeyType, PointIdType, QuantizationConfig,
-    ScoreType, SearchParams, SeqNumberType, WithPayloadInterface, WithVector,
+    Distance, Filter, GeoPoint, PayloadIndexInfo, PayloadKeyType, PlannerHint, PointIdType,
+    QuantizationConfig, RecordPayload, ScoreType, ScoredPoint, SearchParams, SeqNumberType,
+    WithPayloadInterface, WithVector,
 };
 use serde;
 use serde::{Deserialize, Serialize};
@@ -24,13 +28,16 @@ use tokio::sync::mpsc::error::SendError;
 use tokio::sync::oneshot::error::RecvError as OneshotRecvError;
 use tokio::task::JoinError;
 use tonic::codegen::http::uri::InvalidUri;
-use validator::{Validate, ValidationErrors};
+use validator::{Validate, ValidationError, ValidationErrors};
 
 use crate::config::CollectionConfig;
 use crate::operations::config_diff::HnswConfigDiff;
+use crate::operations::consistency_params::ConsistencyToken;
+use crate::operations::point_ops::WriteOrdering;
 use crate::save_on_disk;
 use crate::shards::replica_set::ReplicaState;
 use crate::shards::shard::{PeerId, ShardId};
+use crate::shards::telemetry::WalTelemetry;
 use crate::wal::WalError;
 
 /// Current state of the collection.
@@ -69,9 +76,12 @@ pub struct Record {
     /// Id of the point
     pub id: PointIdType,
     /// Payload - values assigned to the point
-    pub payload: Option<Payload>,
+    pub payload: Option<RecordPayload>,
     /// Vector of the point
     pub vector: Option<VectorStruct>,
+    /// Distance in meters to the origin of the `geo_distance` order of scroll
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub geo_distance: Option<f64>,
 }
 
 /// Current statistics and configuration of the collection
@@ -116,6 +126,26 @@ pub struct CollectionClusterInfo {
     pub remote_shards: Vec<RemoteShardInfo>,
     /// Shard transfers
     pub shard_transfers: Vec<ShardTransferInfo>,
+    /// Peers with active replicas of each shard, if requested.
+    /// Allows clients to send requests directly to the peers, which store the points.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub routing: Option<Vec<ShardRoute>>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct ShardRoute {
+    pub shard_id: ShardId,
+    /// Peers with active replicas of the shard
+    pub peers: Vec<PeerRoute>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct PeerRoute {
+    pub peer_id: PeerId,
+    /// URI of the peer, `None` if the address of the peer is not known yet
+    pub uri: Option<String>,
 }
 
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
@@ -166,6 +196,119 @@ pub struct UpdateResult {
     pub operation_id: SeqNumberType,
     /// Update status
     pub status: UpdateStatus,
+    /// Write of the update to the replicas of each affected shard. Only returned on request
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub replication: Option<Vec<ReplicationReport>>,
+    /// Ids, generated by the server for the upserted points without ids, in the order of the points
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub generated_ids: Option<Vec<PointIdType>>,
+    /// Token to attach to subsequent reads, so they observe this update.
+    /// Not returned if the update was forwarded to another peer
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub consistency_token: Option<ConsistencyToken>,
+}
+
+/// Write of the update to the replicas of a shard
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct ReplicationReport {
+    pub shard_id: ShardId,
+    /// Ordering of the update
+    pub ordering: WriteOrdering,
+    /// Peer, which sent the update to the replicas
+    pub leader_peer: PeerId,
+    /// If true, the update was forwarded to the leader peer because of the ordering.
+    /// Replicas of forwarded updates are only reported in the logs of the leader peer
+    pub forwarded: bool,
+    /// Minimal number of replicas, which must acknowledge the update
+    pub write_consistency_factor: u32,
+    /// Replicas, which applied the update
+    pub acknowledged: Vec<PeerId>,
+    /// Replicas, which failed to apply the update
+    pub failed: Vec<ReplicaWriteFailure>,
+    /// Failed replicas, which were reported to the consensus as dead
+    pub deactivated: Vec<PeerId>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct ReplicaWriteFailure {
+    pub peer_id: PeerId,
+    pub error: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct ShardFlushResult {
+    pub shard_id: ShardId,
+    /// Sequential number of the last operation, persisted in all segments of the shard
+    pub operation_id: SeqNumberType,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct FlushResult {
+    /// Persisted operations of each local shard
+    pub shards: Vec<ShardFlushResult>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct ShardWalTruncateResult {
+    pub shard_id: ShardId,
+    /// Sequential number of the last operation, removed from WAL
+    pub operation_id: SeqNumberType,
+    /// State of WAL after truncation
+    pub wal: WalTelemetry,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct WalTruncateResult {
+    /// Truncated WAL of each local shard
+    pub shards: Vec<ShardWalTruncateResult>,
+}
+
+/// Defines in which order scroll returns points
+///
+/// * `id` - ascending order of point IDs, supports pagination with `offset`, default
+///
+/// * `random` - pseudo-random sample of matching points, same `seed` produces same sample
+///
+/// * `geo_distance` - nearest to the origin of `geo_order` points first, requires geo index of the field
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ScrollOrder {
+    #[default]
+    Id,
+    Random,
+    GeoDistance,
+}
+
+/// Reference point for the `geo_distance` order of scroll
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct GeoDistanceOrder {
+    /// Payload field with geo points, must have a geo index
+    pub key: PayloadKeyType,
+    /// Distances are measured from this point to the closest geo point of the field
+    pub origin: GeoPoint,
+}
+
+/// Order of scroll by values of the payload field
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct OrderBy {
+    /// Payload field to order by, must have an integer or float index with range support
+    pub key: PayloadKeyType,
+    /// Direction of the order. Default: asc
+    #[serde(default)]
+    pub direction: Direction,
+    /// Value of the field to start reading points from, inclusive.
+    /// Together with `offset` continues from the first point of the next page,
+    /// returned as `next_page_order_value` and `next_page_offset` of the previous page.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start_from: Option<f64>,
 }
 
 /// Scroll request - paginate over all points which matches given condition
@@ -184,6 +327,25 @@ pub struct ScrollRequest {
     /// Whether to return the point vector with the result?
     #[serde(default, alias = "with_vectors")]
     pub with_vector: WithVector,
+    /// Order of returned points. Default: by ID
+    #[serde(default)]
+    pub order: ScrollOrder,
+    /// Seed for the `random` order, makes the sample reproducible.
+    /// If not specified - a new random sample is returned for each request.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u64>,
+    /// Override the strategy of reading filtered points, selected by the query planner.
+    /// Default: strategy is selected automatically
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub planner_hint: Option<PlannerHint>,
+    /// Reference point for the `geo_distance` order, required for it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub geo_order: Option<GeoDistanceOrder>,
+    /// Order points by values of the payload field instead of ids.
+    /// Points with several values are ordered by the first of them in the direction of the order,
+    /// points with equal values - by id.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub order_by: Option<OrderBy>,
 }
 
 impl Default for ScrollRequest {
@@ -194,6 +356,11 @@ impl Default for ScrollRequest {
             filter: None,
             with_payload: Some(WithPayloadInterface::Bool(true)),
             with_vector: WithVector::Bool(false),
+            order: ScrollOrder::Id,
+            seed: None,
+            planner_hint: None,
+            geo_order: None,
+            order_by: None,
         }
     }
 }
@@ -206,6 +373,10 @@ pub struct ScrollResult {
     pub points: Vec<Record>,
     /// Offset which should be used to retrieve a next page result
     pub next_page_offset: Option<PointIdType>,
+    /// Value of the `order_by` field, from which the next page starts.
+    /// Should be used as `start_from` of the `order_by` together with the `next_page_offset`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_page_order_value: Option<f64>,
 }
 
 /// Search request.
@@ -238,6 +409,14 @@ pub struct SearchRequest {
     /// Score of the returned result might be higher or smaller than the threshold depending on the
     /// Distance function used. E.g. for cosine similarity only higher scores will be returned.
     pub score_threshold: Option<ScoreType>,
+    /// If true, return positions of the words in the payload text,
+    /// which matched full-text conditions of the filter. Default: false
+    #[serde(default)]
+    pub with_highlights: Option<bool>,
+    /// If true, return distance in meters from the center of the geo condition of the filter
+    /// to the closest geo point of the found point. Default: false
+    #[serde(default)]
+    pub with_geo_distance: Option<bool>,
 }
 
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
@@ -246,6 +425,103 @@ pub struct SearchRequestBatch {
     pub searches: Vec<SearchRequest>,
 }
 
+/// Search request, which groups found points by a value of a payload field.
+/// Returns best points of each group, groups are ordered by their best point.
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct SearchGroupsRequest {
+    /// Look for vectors closest to this
+    pub vector: NamedVectorStruct,
+    /// Look only for points which satisfies this conditions
+    pub filter: Option<Filter>,
+    /// Additional search params
+    pub params: Option<SearchParams>,
+    /// Select which payload to return with the response. Default: None
+    pub with_payload: Option<WithPayloadInterface>,
+    /// Whether to return the point vector with the result?
+    #[serde(default, alias = "with_vectors")]
+    pub with_vector: Option<WithVector>,
+    /// Define a minimal score threshold for the result.
+    pub score_threshold: Option<ScoreType>,
+    /// Payload field to group by, must contain strings or integers.
+    /// Points with several values of the field are put into each of the groups.
+    #[validate(length(min = 1))]
+    pub group_by: String,
+    /// Max number of points in each group
+    #[validate(range(min = 1))]
+    pub group_size: usize,
+    /// Max number of groups to return
+    #[validate(range(min = 1))]
+    pub limit: usize,
+    /// Look up points of another collection with ids equal to the ids of the groups.
+    /// Default: no lookup
+    #[serde(default)]
+    pub with_lookup: Option<WithLookupInterface>,
+}
+
+/// Collection to look up a point for each group, which id is the id of the group.
+/// Allows to keep data of the whole group, e.g. a document, apart from its points, e.g. chunks.
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct WithLookup {
+    /// Name of the collection used for lookup
+    #[serde(rename = "collection")]
+    pub collection_name: String,
+    /// Select which payload of the looked up point to return. Default: All
+    #[serde(default)]
+    pub with_payload: Option<WithPayloadInterface>,
+    /// Whether to return vectors of the looked up point. Default: false
+    #[serde(default)]
+    pub with_vectors: Option<WithVector>,
+}
+
+/// Name of the lookup collection, or the collection along with the options of the lookup
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
+#[serde(untagged)]
+pub enum WithLookupInterface {
+    Collection(String),
+    WithLookup(WithLookup),
+}
+
+impl From<WithLookupInterface> for WithLookup {
+    fn from(with_lookup: WithLookupInterface) -> Self {
+        match with_lookup {
+            WithLookupInterface::Collection(collection_name) => WithLookup {
+                collection_name,
+                with_payload: None,
+                with_vectors: None,
+            },
+            WithLookupInterface::WithLookup(with_lookup) => with_lookup,
+        }
+    }
+}
+
+/// Value of the payload field, which identifies a group
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Eq, Hash)]
+#[serde(untagged)]
+pub enum GroupId {
+    String(String),
+    Number(i64),
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct PointGroup {
+    /// Best points of the group, ordered by score
+    pub hits: Vec<ScoredPoint>,
+    /// Value of the `group_by` field, shared by all points of the group
+    pub id: GroupId,
+    /// Point of the lookup collection with the id of the group, if requested and found
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lookup: Option<Record>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct GroupsResult {
+    pub groups: Vec<PointGroup>,
+}
+
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Validate)]
 #[serde(rename_all = "snake_case")]
 pub struct PointRequest {
@@ -258,6 +534,26 @@ pub struct PointRequest {
     pub with_vector: WithVector,
 }
 
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate)]
+#[serde(rename_all = "snake_case")]
+pub struct PointsExistRequest {
+    /// Check existence of points with ids
+    pub ids: Vec<PointIdType>,
+}
+
+/// Existence of a point, without its payload and vectors
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub struct PointExistence {
+    /// Id of the point
+    pub id: PointIdType,
+    /// Whether the point exists in the collection
+    pub exists: bool,
+    /// Version of the last update of the point, if it exists
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<SeqNumberType>,
+}
+
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
 #[serde(rename_all = "snake_case")]
 #[serde(untagged)]
@@ -284,9 +580,60 @@ pub struct LookupLocation {
     pub vector: Option<String>,
 }
 
+/// Example of the recommendation: either id of the point, which vector is looked up,
+/// or the raw vector itself
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum RecommendExample {
+    PointId(PointIdType),
+    Vector(Vec<VectorElementType>),
+}
+
+impl RecommendExample {
+    pub fn as_point_id(&self) -> Option<PointIdType> {
+        match self {
+            RecommendExample::PointId(point_id) => Some(*point_id),
+            RecommendExample::Vector(_) => None,
+        }
+    }
+}
+
+impl From<PointIdType> for RecommendExample {
+    fn from(point_id: PointIdType) -> Self {
+        RecommendExample::PointId(point_id)
+    }
+}
+
+impl From<u64> for RecommendExample {
+    fn from(point_id: u64) -> Self {
+        RecommendExample::PointId(point_id.into())
+    }
+}
+
+impl From<Vec<VectorElementType>> for RecommendExample {
+    fn from(vector: Vec<VectorElementType>) -> Self {
+        RecommendExample::Vector(vector)
+    }
+}
+
+/// How to use positive and negative examples of the recommendation
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum RecommendStrategy {
+    /// Search by a single vector: weighted average of positive examples shifted away from the
+    /// weighted average of negative examples
+    #[default]
+    AverageVector,
+    /// Search by each positive example and score candidates by the most similar example.
+    /// Similarity to each example is scaled by its weight.
+    /// Candidates, which are closer to a negative example than to any positive one, get a
+    /// negative score. Score is always in range from -1 to 1, if all weights are 1.
+    BestScore,
+}
+
 /// Recommendation request.
-/// Provides positive and negative examples of the vectors, which
-/// are already stored in the collection.
+/// Provides positive and negative examples of the vectors, which are either
+/// already stored in the collection or given as raw vectors.
 ///
 /// Service should look for the points which are closer to positive examples and at the same time
 /// further to negative examples. The concrete way of how to compare negative and positive distances
@@ -294,11 +641,20 @@ pub struct LookupLocation {
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Default)]
 #[serde(rename_all = "snake_case")]
 pub struct RecommendRequest {
-    /// Look for vectors closest to those
-    pub positive: Vec<PointIdType>,
-    /// Try to avoid vectors like this
+    /// Look for vectors closest to those: ids of the points or raw vectors
+    pub positive: Vec<RecommendExample>,
+    /// Try to avoid vectors like this: ids of the points or raw vectors
     #[serde(default)]
-    pub negative: Vec<PointIdType>,
+    pub negative: Vec<RecommendExample>,
+    /// Weights of the positive examples, in the same order. Default: 1 for each example
+    #[serde(default)]
+    pub positive_weights: Option<Vec<f32>>,
+    /// Weights of the negative examples, in the same order. Default: 1 for each example
+    #[serde(default)]
+    pub negative_weights: Option<Vec<f32>>,
+    /// How to use the examples. Default: `average_vector`
+    #[serde(default)]
+    pub strategy: Option<RecommendStrategy>,
     /// Look only for points which satisfies this conditions
     pub filter: Option<Filter>,
     /// Additional search params
@@ -336,6 +692,180 @@ pub struct RecommendRequestBatch {
     pub searches: Vec<RecommendRequest>,
 }
 
+/// Pair of examples, which constrains the discovery: found points should be closer to the
+/// positive example than to the negative one
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct ContextExamplePair {
+    pub positive: RecommendExample,
+    pub negative: RecommendExample,
+}
+
+/// Discovery request.
+/// Looks for points close to the target, which are closer to the positive example than
+/// to the negative one for as many context pairs as possible.
+///
+/// Points, which satisfy more context pairs, are always ranked higher. Among points, which
+/// satisfy the same number of pairs, the ones closer to the target are ranked higher.
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct DiscoverRequest {
+    /// Look for vectors closest to this: id of the point or raw vector
+    pub target: RecommendExample,
+    /// Pairs of positive and negative examples, which constrain the search space
+    #[serde(default)]
+    pub context: Vec<ContextExamplePair>,
+    /// Look only for points which satisfies this conditions
+    pub filter: Option<Filter>,
+    /// Additional search params
+    pub params: Option<SearchParams>,
+    /// Max number of result to return
+    #[serde(alias = "top")]
+    pub limit: usize,
+    /// Offset of the first result to return.
+    /// May be used to paginate results.
+    /// Note: large offset values may cause performance issues.
+    #[serde(default)]
+    pub offset: usize,
+    /// Select which payload to return with the response. Default: None
+    pub with_payload: Option<WithPayloadInterface>,
+    /// Whether to return the point vector with the result?
+    #[serde(default, alias = "with_vectors")]
+    pub with_vector: Option<WithVector>,
+    /// Define which vector to use for discovery, if not specified - try to use default vector
+    #[serde(default)]
+    pub using: Option<UsingVector>,
+    /// The location used to lookup vectors of examples, given by ids.
+    /// If not specified - use current collection.
+    #[serde(default)]
+    pub lookup_from: Option<LookupLocation>,
+}
+
+impl DiscoverRequest {
+    /// All examples of the request: the target and both examples of each context pair
+    pub fn examples(&self) -> impl Iterator<Item = &RecommendExample> {
+        std::iter::once(&self.target).chain(
+            self.context
+                .iter()
+                .flat_map(|pair| [&pair.positive, &pair.negative]),
+        )
+    }
+}
+
+/// Term of the vector arithmetic: vector of the example, multiplied by the weight
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct WeightedExample {
+    /// Id of the point or raw vector
+    pub example: RecommendExample,
+    /// Weight of the example vector in the query, might be negative
+    pub weight: f32,
+}
+
+/// Search request by the vector, composed from the examples.
+/// The query vector is the weighted sum of the vectors of the examples,
+/// e.g. `0.7 * A + 0.3 * B - 0.2 * C`.
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct ArithmeticSearchRequest {
+    /// Weighted examples, which sum up to the query vector
+    #[validate(length(min = 1))]
+    pub terms: Vec<WeightedExample>,
+    /// Look only for points which satisfies this conditions
+    pub filter: Option<Filter>,
+    /// Additional search params
+    pub params: Option<SearchParams>,
+    /// Max number of result to return
+    #[serde(alias = "top")]
+    pub limit: usize,
+    /// Offset of the first result to return.
+    /// May be used to paginate results.
+    /// Note: large offset values may cause performance issues.
+    #[serde(default)]
+    pub offset: usize,
+    /// Select which payload to return with the response. Default: None
+    pub with_payload: Option<WithPayloadInterface>,
+    /// Whether to return the point vector with the result?
+    #[serde(default, alias = "with_vectors")]
+    pub with_vector: Option<WithVector>,
+    /// Define a minimal score threshold for the result.
+    /// If defined, less similar results will not be returned.
+    pub score_threshold: Option<ScoreType>,
+    /// Define which vector to search by, if not specified - try to use default vector
+    #[serde(default)]
+    pub using: Option<UsingVector>,
+    /// The location used to lookup vectors of examples, given by ids.
+    /// If not specified - use current collection.
+    #[serde(default)]
+    pub lookup_from: Option<LookupLocation>,
+}
+
+/// Method of combining results of the prefetch queries into a single ranking
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum Fusion {
+    /// Reciprocal rank fusion: sum of `1 / (k + rank)` over the prefetches, which found the point.
+    /// Does not depend on the scale of scores, so works well for vectors with different distances.
+    #[default]
+    Rrf,
+    /// Sum of scores of the prefetches, which found the point, multiplied by their weights.
+    /// Assumes larger scores are better, use negative weights for distances where smaller is better.
+    WeightedSum,
+    /// Sum of scores of the prefetches, which found the point, multiplied by their weights.
+    /// Scores of each prefetch are scaled into range from 0 for the last candidate to 1 for
+    /// the best one, so weights are comparable between vectors with different distances.
+    NormalizedSum,
+}
+
+/// Sub-query of the hybrid query, which retrieves candidates by a single named vector
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct Prefetch {
+    /// Look for vectors closest to this
+    pub vector: NamedVectorStruct,
+    /// Look only for points which satisfies this conditions, in addition to the filter of the query
+    pub filter: Option<Filter>,
+    /// Additional search params
+    pub params: Option<SearchParams>,
+    /// Max number of candidates to retrieve. Default: `limit + offset` of the query
+    pub limit: Option<usize>,
+    /// Multiplier of the prefetch contribution to the fused score. Default: 1.0
+    pub weight: Option<ScoreType>,
+    /// Minimal score of the candidates of this prefetch
+    pub score_threshold: Option<ScoreType>,
+}
+
+/// Hybrid query request.
+/// Runs all prefetch sub-queries as a single search batch and fuses their results
+/// into a single ranking of points.
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct QueryRequest {
+    /// Sub-queries, which results are fused
+    #[validate(length(min = 1))]
+    pub prefetch: Vec<Prefetch>,
+    /// Method of fusion of the prefetch results. Default: rrf
+    #[serde(default)]
+    pub fusion: Fusion,
+    /// Look only for points which satisfies this conditions. Applied to all prefetches
+    pub filter: Option<Filter>,
+    /// Max number of result to return
+    #[serde(alias = "top")]
+    pub limit: usize,
+    /// Offset of the first result to return.
+    /// May be used to paginate results.
+    /// Note: large offset values may cause performance issues.
+    #[serde(default)]
+    pub offset: usize,
+    /// Select which payload to return with the response. Default: None
+    pub with_payload: Option<WithPayloadInterface>,
+    /// Whether to return the point vector with the result?
+    #[serde(default, alias = "with_vectors")]
+    pub with_vector: Option<WithVector>,
+    /// Define a minimal fused score threshold for the result.
+    pub score_threshold: Option<ScoreType>,
+}
+
 /// Count Request
 /// Counts the number of points which satisfy the given filter.
 /// If filter is not provided, the count of all points in the collection will be returned.
@@ -348,6 +878,15 @@ pub struct CountRequest {
     /// Approximate count might be unreliable during the indexing process. Default: true
     #[serde(default = "default_exact_count")]
     pub exact: bool,
+    /// Override the strategy of reading filtered points for the exact count.
+    /// Default: strategy is selected automatically
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub planner_hint: Option<PlannerHint>,
+    /// Also count distinct values of this payload field among the counted points.
+    /// The field must have a keyword or integer index.
+    /// Values are counted exactly up to 10000 of them, and approximately above.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub distinct: Option<PayloadKeyType>,
 }
 
 pub fn default_exact_count() -> bool {
@@ -359,6 +898,12 @@ pub fn default_exact_count() -> bool {
 pub struct CountResult {
     /// Number of points which satisfy the conditions
     pub count: usize,
+    /// Number of distinct values of the requested field among the points
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub distinct: Option<usize>,
+    /// Distinct values of the requested field, used to merge counts of shards
+    #[serde(skip)]
+    pub distinct_values: Option<DistinctValues>,
 }
 
 #[derive(Error, Debug, Clone)]
@@ -452,6 +997,9 @@ impl From<OperationError> for CollectionError {
             OperationError::WrongVector { .. } => Self::BadInput {
                 description: format!("{err}"),
             },
+            OperationError::WrongSparseVector { .. } => Self::BadInput {
+                description: format!("{err}"),
+            },
             OperationError::VectorNameNotExists { .. } => Self::BadInput {
                 description: format!("{err}"),
             },
@@ -475,6 +1023,9 @@ impl From<OperationError> for CollectionError {
             OperationError::TypeInferenceError { .. } => Self::BadInput {
                 description: format!("{err}"),
             },
+            OperationError::VectorsNotStored { .. } => Self::BadInput {
+                description: format!("{err}"),
+            },
         }
     }
 }
@@ -664,6 +1215,7 @@ impl Record {
 /// Params of single vector data storage
 #[derive(Debug, Hash, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
+#[validate(schema(function = "validate_projection_size"))]
 pub struct VectorParams {
     /// Size of a vectors used
     pub size: NonZeroU64,
@@ -681,14 +1233,136 @@ pub struct VectorParams {
     )]
     #[validate]
     pub quantization_config: Option<QuantizationConfig>,
+    /// Linear projection applied to vectors on upsert and to query vectors on search.
+    /// If set - `size` is the size of projected vectors, which are stored in the collection.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate]
+    pub projection: Option<VectorProjection>,
+}
+
+impl VectorParams {
+    /// Project the vector into the space of stored vectors, if projection is configured
+    pub fn project_vector(&self, vector: VectorType) -> CollectionResult<VectorType> {
+        match &self.projection {
+            Some(projection) => projection.project(&vector),
+            None => Ok(vector),
+        }
+    }
+}
+
+fn validate_projection_size(params: &VectorParams) -> Result<(), ValidationError> {
+    match &params.projection {
+        Some(projection) if projection.matrix.len() as u64 != params.size.get() => {
+            let mut error = ValidationError::new("projection_size");
+            error.message = Some(
+                format!(
+                    "projection matrix must have {} rows, one per dimension of stored vectors",
+                    params.size
+                )
+                .into(),
+            );
+            Err(error)
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Linear projection of vectors into a space of lower dimension, e.g. obtained with PCA or OPQ.
+///
+/// Projected vector is `matrix * (vector - mean)`.
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct VectorProjection {
+    /// Projection matrix, one row per dimension of the projected vector.
+    /// Each row has the size of the original vector.
+    pub matrix: Vec<VectorType>,
+    /// Mean of the original vectors, subtracted before the projection
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mean: Option<VectorType>,
+}
+
+impl VectorProjection {
+    /// Size of vectors accepted by the projection
+    pub fn input_size(&self) -> usize {
+        self.matrix.first().map(|row| row.len()).unwrap_or(0)
+    }
+
+    pub fn project(&self, vector: &[VectorElementType]) -> CollectionResult<VectorType> {
+        if vector.len() != self.input_size() {
+            return Err(CollectionError::bad_input(format!(
+                "Wrong input vector dimension: expected {}, got {}",
+                self.input_size(),
+                vector.len()
+            )));
+        }
+        let centered: Cow<[VectorElementType]> = match &self.mean {
+            Some(mean) => Cow::Owned(vector.iter().zip(mean).map(|(x, m)| x - m).collect()),
+            None => Cow::Borrowed(vector),
+        };
+        Ok(self
+            .matrix
+            .iter()
+            .map(|row| row.iter().zip(centered.iter()).map(|(a, x)| a * x).sum())
+            .collect())
+    }
+}
+
+impl Validate for VectorProjection {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let input_size = self.input_size();
+        let mut errors = ValidationErrors::new();
+        if input_size == 0 || self.matrix.iter().any(|row| row.len() != input_size) {
+            let mut error = ValidationError::new("matrix");
+            error.message =
+                Some("projection matrix must be non-empty with rows of equal size".into());
+            errors.add("matrix", error);
+        }
+        if let Some(mean) = &self.mean {
+            if mean.len() != input_size {
+                let mut error = ValidationError::new("mean");
+                error.message = Some("mean must have the size of the projection input".into());
+                errors.add("mean", error);
+            }
+        }
+        errors.is_empty().then_some(()).ok_or(errors)
+    }
 }
 
+impl PartialEq for VectorProjection {
+    fn eq(&self, other: &Self) -> bool {
+        self.matrix == other.matrix && self.mean == other.mean
+    }
+}
+
+impl std::hash::Hash for VectorProjection {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.matrix.len().hash(state);
+        self.input_size().hash(state);
+    }
+}
+
+impl Eq for VectorProjection {}
+
 impl Anonymize for VectorParams {
     fn anonymize(&self) -> Self {
         self.clone()
     }
 }
 
+/// Params of single sparse vector data storage
+///
+/// Sparse vectors are stored as pairs of dimension indices and values,
+/// and are searched by dot product using the inverted index.
+#[derive(Debug, Hash, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub struct SparseVectorParams {}
+
+impl Anonymize for SparseVectorParams {
+    fn anonymize(&self) -> Self {
+        self.clone()
+    }
+}
+
 /// Vector params separator for single and multiple vector modes
 /// Single mode:
 ///
@@ -768,11 +1442,87 @@ impl From<VectorParams> for VectorsConfig {
     }
 }
 
-#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
 #[serde(rename_all = "snake_case")]
 pub struct AliasDescription {
     pub alias_name: String,
     pub collection_name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Unix timestamp of the alias creation, in seconds.
+    /// Not known for aliases, created before it was recorded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<u64>,
+    /// Identity of the user, who created the alias, if known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_by: Option<String>,
+}
+
+/// Order of aliases in the list
+///
+/// * `alias_name` - alphabetically by alias name
+/// * `collection_name` - alphabetically by collection name, then by alias name
+/// * `created_at` - by creation time, aliases without known creation time first
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum AliasSortBy {
+    #[default]
+    AliasName,
+    CollectionName,
+    CreatedAt,
+}
+
+/// Filtering and sorting of the alias list
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, Default)]
+#[serde(rename_all = "snake_case")]
+pub struct ListAliasesParams {
+    /// Only aliases, which names start with the prefix
+    pub prefix: Option<String>,
+    /// Only aliases, created by the given identity
+    pub created_by: Option<String>,
+    /// Only aliases, created at or after the unix timestamp in seconds
+    pub created_after: Option<u64>,
+    #[serde(default)]
+    pub sort_by: AliasSortBy,
+    /// Sort in descending order
+    #[serde(default)]
+    pub desc: bool,
+    /// Max number of aliases to return
+    #[validate(range(min = 1))]
+    pub limit: Option<usize>,
+}
+
+impl ListAliasesParams {
+    pub fn apply(&self, mut aliases: Vec<AliasDescription>) -> Vec<AliasDescription> {
+        aliases.retain(|alias| {
+            self.prefix
+                .as_ref()
+                .map_or(true, |prefix| alias.alias_name.starts_with(prefix.as_str()))
+                && self.created_by.as_ref().map_or(true, |created_by| {
+                    alias.created_by.as_ref() == Some(created_by)
+                })
+                && self.created_after.map_or(true, |created_after| {
+                    alias
+                        .created_at
+                        .map_or(false, |created_at| created_at >= created_after)
+                })
+        });
+        match self.sort_by {
+            AliasSortBy::AliasName => aliases.sort_by(|a, b| a.alias_name.cmp(&b.alias_name)),
+            AliasSortBy::CollectionName => aliases.sort_by(|a, b| {
+                (&a.collection_name, &a.alias_name).cmp(&(&b.collection_name, &b.alias_name))
+            }),
+            AliasSortBy::CreatedAt => aliases
+                .sort_by(|a, b| (a.created_at, &a.alias_name).cmp(&(b.created_at, &b.alias_name))),
+        }
+        if self.desc {
+            aliases.reverse();
+        }
+        if let Some(limit) = self.limit {
+            aliases.truncate(limit);
+        }
+        aliases
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize, JsonSchema)]
@@ -791,3 +1541,18 @@ pub enum NodeType {
     /// and backup purposes
     Listener,
 }
+
+/// Which replica of the shard should serve read requests, if any replica can serve it
+#[derive(Clone, Debug, Deserialize, Default, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ReadPreference {
+    /// Prefer local replica, other replicas are queried in random order
+    #[default]
+    PreferLocal,
+    /// Query only the leader replica - the alive replica with the highest peer id
+    LeaderOnly,
+    /// Spread requests evenly across all replicas
+    RoundRobin,
+    /// Prefer replicas with the lowest observed read latency
+    LowestLatency,
+}
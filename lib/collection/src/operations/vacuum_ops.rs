@@ -0,0 +1,43 @@
+use schemars::JsonSchema;
+use segment::types::SegmentType;
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::collection_manager::holders::segment_holder::SegmentId;
+use crate::shards::shard::ShardId;
+
+/// Rebuild segments with a large fraction of deleted points to reclaim disk space
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, Default)]
+#[serde(rename_all = "snake_case")]
+pub struct VacuumRequest {
+    /// Minimal ratio of deleted points to available points in a segment, required to rebuild it.
+    /// Default: `deleted_threshold` of the collection optimizers config
+    #[validate(range(min = 0.0, max = 1.0))]
+    pub deleted_threshold: Option<f64>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct SegmentDeletedInfo {
+    pub shard_id: ShardId,
+    pub segment_id: SegmentId,
+    pub segment_type: SegmentType,
+    pub points_count: usize,
+    pub deleted_count: usize,
+    /// Ratio of deleted points to available points, which is compared with the threshold.
+    /// Not defined if there are no available points in the segment.
+    pub deleted_ratio: Option<f64>,
+    /// Segment exceeds the threshold and is going to be rebuilt
+    pub vacuum: bool,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct VacuumResult {
+    /// Applied threshold of deleted points fraction
+    pub deleted_threshold: f64,
+    /// Deleted points in each segment of local shards
+    pub segments: Vec<SegmentDeletedInfo>,
+    /// Number of started segment optimizations
+    pub optimizations_started: usize,
+}
@@ -1,5 +1,4 @@
-use actix_web_validator::error::flatten_errors;
-use validator::{ValidationError, ValidationErrors};
+use validator::{ValidationError, ValidationErrors, ValidationErrorsKind};
 
 /// Warn about validation errors in the log.
 ///
@@ -30,10 +29,45 @@ pub fn label_errors(label: impl AsRef<str>, errs: &ValidationErrors) -> String {
 fn describe_errors(errs: &ValidationErrors) -> Vec<(String, String)> {
     flatten_errors(errs)
         .into_iter()
-        .map(|(_, name, err)| (name, describe_error(err)))
+        .map(|(name, err)| (name, describe_error(err)))
         .collect()
 }
 
+/// Flatten nested validation errors into errors of fields, named by their paths,
+/// such as `things[0].idx`.
+fn flatten_errors(errs: &ValidationErrors) -> Vec<(String, &ValidationError)> {
+    let mut flat_errors = Vec::new();
+    flatten_errors_into(errs, None, &mut flat_errors);
+    flat_errors
+}
+
+fn flatten_errors_into<'a>(
+    errs: &'a ValidationErrors,
+    path: Option<&str>,
+    flat_errors: &mut Vec<(String, &'a ValidationError)>,
+) {
+    for (field, kind) in errs.errors() {
+        let field_path = match path {
+            Some(path) => format!("{path}.{field}"),
+            None => field.to_string(),
+        };
+        match kind {
+            ValidationErrorsKind::Field(errors) => {
+                flat_errors.extend(errors.iter().map(|err| (field_path.clone(), err)))
+            }
+            ValidationErrorsKind::Struct(errors) => {
+                flatten_errors_into(errors, Some(&field_path), flat_errors)
+            }
+            ValidationErrorsKind::List(errors) => {
+                for (index, errors) in errors {
+                    let item_path = format!("{field_path}[{index}]");
+                    flatten_errors_into(errors, Some(&item_path), flat_errors);
+                }
+            }
+        }
+    }
+}
+
 /// Describe a specific validation error.
 fn describe_error(
     err @ ValidationError {
@@ -0,0 +1,41 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::shards::shard::ShardId;
+
+/// Replay operations, archived from WAL of the collection, e.g. on top of the recovered snapshot
+/// to recover the collection to a later point in time.
+/// Sequence numbers of operations are counted separately in each shard.
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct WalReplayRequest {
+    /// Directory with the WAL archive of the collection, relative to the `wal_archive.path`
+    /// of the node, e.g. downloaded there from the external storage.
+    /// Default: name of the collection, i.e. the archive of the collection itself
+    #[validate(length(min = 1))]
+    pub path: Option<String>,
+    /// Replay only operations with greater sequence numbers.
+    /// Default: replay all archived operations
+    pub after: Option<u64>,
+    /// Replay operations up to this sequence number, inclusive.
+    /// Default: replay up to the latest archived operation
+    pub until: Option<u64>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct ShardWalReplayResult {
+    pub shard_id: ShardId,
+    /// Number of replayed operations
+    pub operations: usize,
+    /// Sequence number of the last replayed operation
+    pub last_operation: Option<u64>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct WalReplayResult {
+    /// Shards, which have archived operations
+    pub shards: Vec<ShardWalReplayResult>,
+}
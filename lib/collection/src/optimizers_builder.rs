@@ -3,6 +3,7 @@ use std::sync::Arc;
 
 use schemars::JsonSchema;
 use segment::common::cpu::get_num_cpus;
+use segment::common::memory::get_total_memory;
 use segment::types::{HnswConfig, QuantizationConfig};
 use serde::{Deserialize, Serialize};
 use validator::Validate;
@@ -12,9 +13,12 @@ use crate::collection_manager::optimizers::merge_optimizer::MergeOptimizer;
 use crate::collection_manager::optimizers::segment_optimizer::OptimizerThresholds;
 use crate::collection_manager::optimizers::vacuum_optimizer::VacuumOptimizer;
 use crate::config::CollectionParams;
+use crate::shards::local_shard::LocalShard;
 use crate::update_handler::Optimizer;
 
 const DEFAULT_MAX_SEGMENT_PER_CPU_KB: usize = 200_000;
+/// Default max segment size should not exceed this fraction of the memory available to the process
+const DEFAULT_MAX_SEGMENT_MEMORY_DIVISOR: usize = 4;
 
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq)]
 pub struct OptimizersConfig {
@@ -40,7 +44,7 @@ pub struct OptimizersConfig {
     /// If indexation speed have more priority for your - make this parameter lower.
     /// If search speed is more important - make this parameter higher.
     /// Note: 1Kb = 1 vector of size 256
-    /// If not set, will be automatically selected considering the number of available CPUs.
+    /// If not set, will be automatically selected considering the number of available CPUs and memory.
     #[serde(alias = "max_segment_size_kb")]
     #[serde(default)]
     pub max_segment_size: Option<usize>,
@@ -63,6 +67,16 @@ pub struct OptimizersConfig {
     pub flush_interval_sec: u64,
     /// Maximum available threads for optimization workers
     pub max_optimization_threads: usize,
+    /// Exact number of segments optimizer will try to keep.
+    /// If set, takes precedence over `default_segment_number`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1))]
+    pub target_segment_count: Option<usize>,
+    /// Do not create segments larger this size (in MegaBytes).
+    /// If set, takes precedence over `max_segment_size`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1))]
+    pub max_segment_size_mb: Option<usize>,
 }
 
 impl OptimizersConfig {
@@ -77,11 +91,15 @@ impl OptimizersConfig {
             indexing_threshold: 100_000,
             flush_interval_sec: 60,
             max_optimization_threads: 0,
+            target_segment_count: None,
+            max_segment_size_mb: None,
         }
     }
 
     pub fn get_number_segments(&self) -> usize {
-        if self.default_segment_number == 0 {
+        if let Some(target_segment_count) = self.target_segment_count {
+            target_segment_count
+        } else if self.default_segment_number == 0 {
             let num_cpus = get_num_cpus();
             // Do not configure less than 2 and more than 8 segments
             // until it is not explicitly requested
@@ -91,12 +109,23 @@ impl OptimizersConfig {
         }
     }
 
+    /// Max size of a segment in KiloBytes
     pub fn get_max_segment_size(&self) -> usize {
-        if let Some(max_segment_size) = self.max_segment_size {
+        if let Some(max_segment_size_mb) = self.max_segment_size_mb {
+            max_segment_size_mb.saturating_mul(1024)
+        } else if let Some(max_segment_size) = self.max_segment_size {
             max_segment_size
         } else {
             let num_cpus = get_num_cpus();
-            num_cpus.saturating_mul(DEFAULT_MAX_SEGMENT_PER_CPU_KB)
+            let max_segment_size = num_cpus.saturating_mul(DEFAULT_MAX_SEGMENT_PER_CPU_KB);
+            // Optimizers build the whole segment at once, so it should comfortably fit in memory
+            match get_total_memory() {
+                Some(total_memory) => {
+                    let memory_budget = total_memory / 1024 / DEFAULT_MAX_SEGMENT_MEMORY_DIVISOR;
+                    max_segment_size.min(memory_budget).max(1)
+                }
+                None => max_segment_size,
+            }
         }
     }
 }
@@ -109,13 +138,9 @@ pub fn build_optimizers(
     quantization_config: &Option<QuantizationConfig>,
 ) -> Arc<Vec<Arc<Optimizer>>> {
     let segments_path = shard_path.join("segments");
-    let temp_segments_path = shard_path.join("temp_segments");
+    let temp_segments_path = LocalShard::temp_segments_path(shard_path);
 
-    let threshold_config = OptimizerThresholds {
-        memmap_threshold: optimizers_config.memmap_threshold.unwrap_or(usize::MAX),
-        indexing_threshold: optimizers_config.indexing_threshold,
-        max_segment_size: optimizers_config.get_max_segment_size(),
-    };
+    let threshold_config = optimizer_thresholds(optimizers_config);
 
     Arc::new(vec![
         Arc::new(MergeOptimizer::new(
@@ -124,7 +149,7 @@ pub fn build_optimizers(
             segments_path.clone(),
             temp_segments_path.clone(),
             collection_params.clone(),
-            *hnsw_config,
+            hnsw_config.clone(),
             quantization_config.clone(),
         )),
         Arc::new(IndexingOptimizer::new(
@@ -132,7 +157,7 @@ pub fn build_optimizers(
             segments_path.clone(),
             temp_segments_path.clone(),
             collection_params.clone(),
-            *hnsw_config,
+            hnsw_config.clone(),
             quantization_config.clone(),
         )),
         Arc::new(VacuumOptimizer::new(
@@ -142,8 +167,38 @@ pub fn build_optimizers(
             segments_path,
             temp_segments_path,
             collection_params.clone(),
-            *hnsw_config,
+            hnsw_config.clone(),
             quantization_config.clone(),
         )),
     ])
 }
+
+/// Build an optimizer, which rebuilds every segment with the fraction of deleted points
+/// above `deleted_threshold`, regardless of the segment size
+pub fn build_vacuum_optimizer(
+    shard_path: &Path,
+    collection_params: &CollectionParams,
+    optimizers_config: &OptimizersConfig,
+    hnsw_config: &HnswConfig,
+    quantization_config: &Option<QuantizationConfig>,
+    deleted_threshold: f64,
+) -> Arc<Optimizer> {
+    Arc::new(VacuumOptimizer::new(
+        deleted_threshold,
+        0,
+        optimizer_thresholds(optimizers_config),
+        shard_path.join("segments"),
+        LocalShard::temp_segments_path(shard_path),
+        collection_params.clone(),
+        hnsw_config.clone(),
+        quantization_config.clone(),
+    ))
+}
+
+fn optimizer_thresholds(optimizers_config: &OptimizersConfig) -> OptimizerThresholds {
+    OptimizerThresholds {
+        memmap_threshold: optimizers_config.memmap_threshold.unwrap_or(usize::MAX),
+        indexing_threshold: optimizers_config.indexing_threshold,
+        max_segment_size: optimizers_config.get_max_segment_size(),
+    }
+}
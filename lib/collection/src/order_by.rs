@@ -0,0 +1,69 @@
+use std::collections::HashMap;
+
+use segment::types::{Filter, PointIdType, WithPayloadInterface, WithVector};
+
+use crate::collection::Collection;
+use crate::operations::consistency_params::ReadConsistency;
+use crate::operations::types::{CollectionResult, OrderBy, PointRequest, ScrollResult};
+use crate::shards::shard::ShardId;
+
+/// Read a page of points ordered by values of the payload field.
+///
+/// Ordered points are selected by the integer or float index of local replicas,
+/// then the requested payload and vectors are retrieved for them.
+/// One extra point is selected to return the position of the next page: its value and id.
+#[allow(clippy::too_many_arguments)]
+pub async fn scroll_by_order(
+    collection: &Collection,
+    order_by: &OrderBy,
+    offset: Option<PointIdType>,
+    limit: usize,
+    filter: Option<&Filter>,
+    with_payload: WithPayloadInterface,
+    with_vector: WithVector,
+    read_consistency: Option<ReadConsistency>,
+    shard_selection: Option<ShardId>,
+    shard_key: Option<ShardId>,
+) -> CollectionResult<ScrollResult> {
+    let mut ordered = collection
+        .ordered_points(
+            order_by,
+            offset,
+            limit + 1,
+            filter,
+            shard_selection.or(shard_key),
+        )
+        .await?;
+
+    let next_page = if ordered.len() > limit {
+        ordered.pop()
+    } else {
+        None
+    };
+
+    let mut records: HashMap<_, _> = collection
+        .retrieve(
+            PointRequest {
+                ids: ordered.iter().map(|(point_id, _)| *point_id).collect(),
+                with_payload: Some(with_payload),
+                with_vector,
+            },
+            read_consistency,
+            shard_selection,
+        )
+        .await?
+        .into_iter()
+        .map(|record| (record.id, record))
+        .collect();
+
+    // Points might get deleted between reading the index and retrieve
+    let points = ordered
+        .into_iter()
+        .filter_map(|(point_id, _)| records.remove(&point_id))
+        .collect();
+    Ok(ScrollResult {
+        points,
+        next_page_offset: next_page.map(|(point_id, _)| point_id),
+        next_page_order_value: next_page.map(|(_, value)| value),
+    })
+}
@@ -3,37 +3,43 @@ use std::future::Future;
 
 use futures::future::try_join_all;
 use itertools::Itertools;
-use segment::data_types::vectors::{NamedVector, VectorElementType, DEFAULT_VECTOR_NAME};
+use segment::data_types::vectors::{
+    NamedVector, VectorElementType, VectorStruct, DEFAULT_VECTOR_NAME,
+};
+use segment::spaces::tools::sigmoid;
 use segment::types::{
-    Condition, Filter, HasIdCondition, PointIdType, ScoredPoint, WithPayloadInterface, WithVector,
+    Condition, Distance, Filter, HasIdCondition, PointIdType, RecordPayload, ScoreType,
+    ScoredPoint, WithPayloadInterface, WithVector,
 };
 use tokio::sync::RwLockReadGuard;
 
 use crate::collection::Collection;
 use crate::operations::consistency_params::ReadConsistency;
 use crate::operations::types::{
-    CollectionError, CollectionResult, PointRequest, RecommendRequest, RecommendRequestBatch,
-    Record, SearchRequest, SearchRequestBatch, UsingVector,
+    CollectionError, CollectionResult, LookupLocation, PointRequest, RecommendExample,
+    RecommendRequest, RecommendRequestBatch, RecommendStrategy, Record, SearchRequest,
+    SearchRequestBatch, UsingVector, VectorParams,
 };
 
-fn avg_vectors<'a>(
-    vectors: impl Iterator<Item = &'a Vec<VectorElementType>>,
-) -> Vec<VectorElementType> {
-    let mut count: usize = 0;
+/// Vector of the example along with its weight
+type WeightedVector = (Vec<VectorElementType>, VectorElementType);
+
+fn avg_vectors<'a>(vectors: impl Iterator<Item = &'a WeightedVector>) -> Vec<VectorElementType> {
+    let mut total_weight: VectorElementType = 0.0;
     let mut avg_vector: Vec<VectorElementType> = vec![];
-    for vector in vectors {
-        count += 1;
+    for (vector, weight) in vectors {
+        total_weight += weight;
         for i in 0..vector.len() {
             if i >= avg_vector.len() {
-                avg_vector.push(vector[i])
+                avg_vector.push(vector[i] * weight)
             } else {
-                avg_vector[i] += vector[i];
+                avg_vector[i] += vector[i] * weight;
             }
         }
     }
 
     for item in &mut avg_vector {
-        *item /= count as VectorElementType;
+        *item /= total_weight;
     }
 
     avg_vector
@@ -66,23 +72,88 @@ where
     Ok(results.into_iter().next().unwrap())
 }
 
-async fn retrieve_points(
+pub(crate) async fn retrieve_points(
     collection: &Collection,
     ids: Vec<PointIdType>,
     vector_names: Vec<String>,
     read_consistency: Option<ReadConsistency>,
 ) -> CollectionResult<Vec<Record>> {
-    collection
+    let resolved_names: Vec<_> = {
+        let collection_params = &collection.collection_config.read().await.params;
+        vector_names
+            .iter()
+            .map(|name| collection_params.resolve_vector_name(name))
+            .collect()
+    };
+    let records = collection
         .retrieve(
             PointRequest {
                 ids,
                 with_payload: Some(WithPayloadInterface::Bool(false)),
-                with_vector: WithVector::Selector(vector_names),
+                with_vector: WithVector::Selector(resolved_names.clone()),
             },
             read_consistency,
             None,
         )
-        .await
+        .await?;
+    // Reference vectors are looked up by the requested names, which might be aliases
+    Ok(records
+        .into_iter()
+        .map(|mut record| {
+            for (name, resolved_name) in vector_names.iter().zip(&resolved_names) {
+                if name == resolved_name {
+                    continue;
+                }
+                if let Some(vector) = record.get_vector_by_name(resolved_name).cloned() {
+                    let mut vectors = match record.vector.take() {
+                        Some(VectorStruct::Multi(vectors)) => vectors,
+                        Some(VectorStruct::Single(vector)) => {
+                            HashMap::from([(DEFAULT_VECTOR_NAME.to_owned(), vector)])
+                        }
+                        None => HashMap::new(),
+                    };
+                    vectors.insert(name.clone(), vector);
+                    record.vector = Some(VectorStruct::Multi(vectors));
+                }
+            }
+            record
+        })
+        .collect())
+}
+
+/// Records of the examples, given by ids, with the vector they are looked up by.
+/// Examples are retrieved from the lookup collection, if given, or from this collection.
+pub(crate) async fn retrieve_examples<'a, F, Fut>(
+    collection: &Collection,
+    collection_by_name: F,
+    lookup_from: Option<&LookupLocation>,
+    ids: Vec<PointIdType>,
+    lookup_vector_name: &str,
+    read_consistency: Option<ReadConsistency>,
+) -> CollectionResult<HashMap<PointIdType, Record>>
+where
+    F: Fn(String) -> Fut,
+    Fut: Future<Output = Option<RwLockReadGuard<'a, Collection>>>,
+{
+    if ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+    let vector_names = vec![lookup_vector_name.to_owned()];
+    let records = match lookup_from {
+        None => retrieve_points(collection, ids, vector_names, read_consistency).await?,
+        Some(lookup_from) => {
+            let other_collection = collection_by_name(lookup_from.collection.clone())
+                .await
+                .ok_or_else(|| CollectionError::NotFound {
+                    what: format!("Collection {}", lookup_from.collection),
+                })?;
+            retrieve_points(&other_collection, ids, vector_names, read_consistency).await?
+        }
+    };
+    Ok(records
+        .into_iter()
+        .map(|record| (record.id, record))
+        .collect())
 }
 
 enum CollectionRefHolder<'a> {
@@ -119,7 +190,223 @@ fn get_search_vector_name(request: &RecommendRequest) -> String {
     }
 }
 
-/// Search points in a collection by already existing points in this or another collection.
+/// Vectors of the examples, given either by ids or as raw vectors.
+///
+/// Raw vectors are preprocessed the same way as the stored ones, so both kinds of examples
+/// are comparable with each other and with the vectors of the collection.
+pub(crate) fn example_vectors<'a>(
+    examples: impl IntoIterator<Item = &'a RecommendExample>,
+    records: impl Fn(PointIdType) -> Option<&'a Record>,
+    lookup_vector_name: &str,
+    vector_params: Option<&VectorParams>,
+) -> CollectionResult<Vec<Vec<VectorElementType>>> {
+    let mut vectors = Vec::new();
+    for example in examples {
+        if let Some(vector) = example_vector(example, &records, lookup_vector_name, vector_params)?
+        {
+            vectors.push(vector);
+        }
+    }
+    Ok(vectors)
+}
+
+/// Vector of the example, `None` if the point of the example has no vector with the name.
+fn example_vector<'a>(
+    example: &RecommendExample,
+    records: impl Fn(PointIdType) -> Option<&'a Record>,
+    lookup_vector_name: &str,
+    vector_params: Option<&VectorParams>,
+) -> CollectionResult<Option<Vec<VectorElementType>>> {
+    match example {
+        RecommendExample::PointId(point_id) => {
+            let record = records(*point_id).ok_or(CollectionError::PointNotFound {
+                missed_point_id: *point_id,
+            })?;
+            Ok(record.get_vector_by_name(lookup_vector_name).cloned())
+        }
+        RecommendExample::Vector(vector) => {
+            let Some(vector_params) = vector_params else {
+                return Err(CollectionError::bad_request(format!(
+                    "Raw vector examples are not supported by vector {lookup_vector_name}"
+                )));
+            };
+            // Examples are searched as stored vectors, so the projection is applied here
+            let vector = vector_params.project_vector(vector.clone())?;
+            let dim = vector_params.size.get() as usize;
+            if vector.len() != dim {
+                return Err(CollectionError::bad_request(format!(
+                    "Wrong dimension of the example vector: expected {dim}, got {}",
+                    vector.len()
+                )));
+            }
+            let vector = vector_params
+                .distance
+                .preprocess_vector(&vector)
+                .unwrap_or(vector);
+            Ok(Some(vector))
+        }
+    }
+}
+
+/// Vectors of the examples along with their weights, 1 if the weights are not given.
+fn weighted_example_vectors<'a>(
+    examples: &[RecommendExample],
+    weights: Option<&[VectorElementType]>,
+    records: impl Fn(PointIdType) -> Option<&'a Record>,
+    lookup_vector_name: &str,
+    vector_params: Option<&VectorParams>,
+) -> CollectionResult<Vec<WeightedVector>> {
+    let mut vectors = Vec::new();
+    for (i, example) in examples.iter().enumerate() {
+        let weight = weights.map_or(1.0, |weights| weights[i]);
+        if let Some(vector) = example_vector(example, &records, lookup_vector_name, vector_params)?
+        {
+            vectors.push((vector, weight));
+        }
+    }
+    Ok(vectors)
+}
+
+/// Weights must be given for each of the examples. Zero or negative weights are not allowed,
+/// as averages and best scores are not defined for them.
+fn check_weights(
+    examples: &[RecommendExample],
+    weights: Option<&[VectorElementType]>,
+    kind: &str,
+) -> CollectionResult<()> {
+    let Some(weights) = weights else {
+        return Ok(());
+    };
+    if weights.len() != examples.len() {
+        return Err(CollectionError::bad_request(format!(
+            "Number of {kind} weights {} doesn't match the number of {kind} examples {}",
+            weights.len(),
+            examples.len()
+        )));
+    }
+    if let Some(weight) = weights
+        .iter()
+        .find(|weight| !weight.is_finite() || **weight <= 0.0)
+    {
+        return Err(CollectionError::bad_request(format!(
+            "Weight of {kind} example must be a positive number, got {weight}"
+        )));
+    }
+    Ok(())
+}
+
+/// Score of the candidate by the most similar example, similarity is scaled by the weight
+/// of the example.
+/// Positive, if the candidate is closer to some positive example than to any negative one.
+fn best_score(
+    candidate: &[VectorElementType],
+    positive: &[WeightedVector],
+    negative: &[WeightedVector],
+    distance: Distance,
+) -> ScoreType {
+    let best_similarity = |examples: &[WeightedVector]| {
+        examples
+            .iter()
+            .map(|(example, weight)| weight * sigmoid(distance.similarity(candidate, example)))
+            .fold(ScoreType::NEG_INFINITY, ScoreType::max)
+    };
+    let best_positive = best_similarity(positive);
+    let best_negative = best_similarity(negative);
+    if best_positive > best_negative {
+        best_positive
+    } else {
+        -best_negative
+    }
+}
+
+/// How to turn results of the searches of a single recommendation into its response
+enum RecommendPlan {
+    /// Results of the only search are returned as is
+    AverageVector,
+    /// Candidates, found by each positive example, are rescored by all examples
+    BestScore {
+        positive: Vec<WeightedVector>,
+        negative: Vec<WeightedVector>,
+        distance: Distance,
+        /// Real name of the vector, candidates are compared by
+        vector_name: String,
+    },
+}
+
+/// Score the candidates, found by several searches, by their vectors.
+/// Returns each candidate once, best first.
+pub(crate) fn rescore_candidates(
+    candidates: Vec<Vec<ScoredPoint>>,
+    vector_name: &str,
+    score: impl Fn(&[VectorElementType]) -> ScoreType,
+) -> Vec<ScoredPoint> {
+    let mut rescored: HashMap<PointIdType, ScoredPoint> = HashMap::new();
+    for mut point in candidates.into_iter().flatten() {
+        if rescored.contains_key(&point.id) {
+            continue;
+        }
+        let Some(vector) = point
+            .vector
+            .as_ref()
+            .and_then(|vector| vector.get(vector_name))
+        else {
+            continue;
+        };
+        point.score = score(vector);
+        point.vector = None;
+        rescored.insert(point.id, point);
+    }
+    rescored
+        .into_values()
+        .sorted_unstable_by(|a, b| b.score.total_cmp(&a.score).then_with(|| a.id.cmp(&b.id)))
+        .collect()
+}
+
+/// Fill the rescored points with the requested payload and vectors.
+/// Points, deleted in between, are not returned.
+pub(crate) async fn fill_rescored_points(
+    collection: &Collection,
+    points: Vec<ScoredPoint>,
+    with_payload: Option<WithPayloadInterface>,
+    with_vector: Option<WithVector>,
+    read_consistency: Option<ReadConsistency>,
+) -> CollectionResult<Vec<ScoredPoint>> {
+    let with_vector = with_vector.unwrap_or_default();
+    let with_payload = with_payload.unwrap_or(WithPayloadInterface::Bool(false));
+    if !with_vector.is_some() && !with_payload.is_required() {
+        return Ok(points);
+    }
+
+    let mut records: HashMap<_, _> = collection
+        .retrieve(
+            PointRequest {
+                ids: points.iter().map(|point| point.id).collect(),
+                with_payload: Some(with_payload),
+                with_vector,
+            },
+            read_consistency,
+            None,
+        )
+        .await?
+        .into_iter()
+        .map(|record| (record.id, record))
+        .collect();
+    let mut filled = Vec::with_capacity(points.len());
+    for mut point in points {
+        if let Some(record) = records.remove(&point.id) {
+            point.payload = record
+                .payload
+                .map(RecordPayload::into_payload)
+                .transpose()?;
+            point.vector = record.vector;
+            filled.push(point);
+        }
+    }
+    Ok(filled)
+}
+
+/// Search points in a collection by examples: already existing points in this or another
+/// collection, or raw vectors.
 ///
 /// Function works in following stages:
 ///
@@ -127,6 +414,7 @@ fn get_search_vector_name(request: &RecommendRequest) -> String {
 /// - Executes queries in parallel
 /// - Converts retrieve results into lookup table
 /// - Constructs regular search queries, execute them as single batch
+/// - For the `best_score` strategy, rescores the found candidates by all examples
 ///
 /// # Arguments
 ///
@@ -156,9 +444,30 @@ where
     for request in &request_batch.searches {
         if request.positive.is_empty() {
             return Err(CollectionError::BadRequest {
-                description: "At least one positive vector ID required".to_owned(),
+                description: "At least one positive example required".to_owned(),
             });
         }
+        check_weights(
+            &request.positive,
+            request.positive_weights.as_deref(),
+            "positive",
+        )?;
+        check_weights(
+            &request.negative,
+            request.negative_weights.as_deref(),
+            "negative",
+        )?;
+        let mut point_ids = request
+            .positive
+            .iter()
+            .chain(&request.negative)
+            .filter_map(RecommendExample::as_point_id)
+            .peekable();
+        // Raw vectors don't need a lookup
+        if point_ids.peek().is_none() {
+            continue;
+        }
+
         let collection_name = request.lookup_from.as_ref().map(|x| &x.collection);
 
         let reference_vectors_ids = all_reference_vectors_ids
@@ -171,9 +480,7 @@ where
 
         vector_names.insert(get_search_vector_name(request));
 
-        for point_id in request.positive.iter().chain(&request.negative) {
-            reference_vectors_ids.insert(*point_id);
-        }
+        reference_vectors_ids.extend(point_ids);
     }
 
     debug_assert!(all_reference_vectors_ids.len() == vector_names_per_collection.len());
@@ -229,86 +536,180 @@ where
     }
 
     let mut searches = Vec::with_capacity(request_batch.searches.len());
+    // Plan of each request and the number of searches it made
+    let mut plans = Vec::with_capacity(request_batch.searches.len());
 
-    for request in &request_batch.searches {
-        let vector_name = match &request.using {
-            None => DEFAULT_VECTOR_NAME,
-            Some(UsingVector::Name(name)) => name,
-        };
-
-        let lookup_vector_name = get_search_vector_name(request);
-
-        let reference_vectors_ids = request
-            .positive
-            .iter()
-            .chain(&request.negative)
-            .cloned()
-            .collect_vec();
-
-        let request_from_collection = request.lookup_from.as_ref().map(|x| &x.collection);
-
-        for &point_id in &reference_vectors_ids {
-            if !all_vectors_records_map.contains_key(&(request_from_collection, point_id)) {
-                return Err(CollectionError::PointNotFound {
-                    missed_point_id: point_id,
-                });
-            }
-        }
-
-        let avg_positive = avg_vectors(request.positive.iter().filter_map(|vid| {
-            let rec = all_vectors_records_map
-                .get(&(request_from_collection, *vid))
-                .unwrap();
-            rec.get_vector_by_name(&lookup_vector_name)
-        }));
-
-        let search_vector = if request.negative.is_empty() {
-            avg_positive
-        } else {
-            let avg_negative = avg_vectors(request.negative.iter().filter_map(|vid| {
-                let rec = all_vectors_records_map
-                    .get(&(request_from_collection, *vid))
-                    .unwrap();
-                rec.get_vector_by_name(&lookup_vector_name)
-            }));
-
-            avg_positive
-                .iter()
-                .cloned()
-                .zip(avg_negative.iter().cloned())
-                .map(|(pos, neg)| pos + pos - neg)
-                .collect()
-        };
-
-        let search_request = SearchRequest {
-            vector: NamedVector {
-                name: vector_name.to_string(),
-                vector: search_vector,
-            }
-            .into(),
-            filter: Some(Filter {
+    {
+        let collection_params = &collection.collection_config.read().await.params;
+
+        for request in &request_batch.searches {
+            let vector_name = match &request.using {
+                None => DEFAULT_VECTOR_NAME,
+                Some(UsingVector::Name(name)) => name,
+            };
+            let resolved_vector_name = collection_params.resolve_vector_name(vector_name);
+            // Sparse vectors have no params, they can be used with the ids of examples only
+            let vector_params = collection_params
+                .get_vector_params(&resolved_vector_name)
+                .ok();
+
+            let lookup_vector_name = get_search_vector_name(request);
+
+            let request_from_collection = request.lookup_from.as_ref().map(|x| &x.collection);
+
+            let records = |point_id: PointIdType| {
+                all_vectors_records_map.get(&(request_from_collection, point_id))
+            };
+
+            let positive_vectors = weighted_example_vectors(
+                &request.positive,
+                request.positive_weights.as_deref(),
+                records,
+                &lookup_vector_name,
+                vector_params.as_ref(),
+            )?;
+            let negative_vectors = weighted_example_vectors(
+                &request.negative,
+                request.negative_weights.as_deref(),
+                records,
+                &lookup_vector_name,
+                vector_params.as_ref(),
+            )?;
+
+            let filter = Filter {
                 should: None,
                 must: request
                     .filter
                     .clone()
                     .map(|filter| vec![Condition::Filter(filter)]),
                 must_not: Some(vec![Condition::HasId(HasIdCondition {
-                    has_id: reference_vectors_ids.iter().cloned().collect(),
+                    has_id: request
+                        .positive
+                        .iter()
+                        .chain(&request.negative)
+                        .filter_map(RecommendExample::as_point_id)
+                        .collect(),
                 })]),
-            }),
-            with_payload: request.with_payload.clone(),
-            with_vector: request.with_vector.clone(),
-            params: request.params,
-            limit: request.limit,
-            score_threshold: request.score_threshold,
-            offset: request.offset,
-        };
-        searches.push(search_request)
+            };
+
+            match request.strategy.unwrap_or_default() {
+                RecommendStrategy::AverageVector => {
+                    let avg_positive = avg_vectors(positive_vectors.iter());
+
+                    let search_vector = if negative_vectors.is_empty() {
+                        avg_positive
+                    } else {
+                        let avg_negative = avg_vectors(negative_vectors.iter());
+
+                        avg_positive
+                            .iter()
+                            .cloned()
+                            .zip(avg_negative.iter().cloned())
+                            .map(|(pos, neg)| pos + pos - neg)
+                            .collect()
+                    };
+
+                    searches.push(SearchRequest {
+                        vector: NamedVector {
+                            name: vector_name.to_string(),
+                            vector: search_vector,
+                        }
+                        .into(),
+                        filter: Some(filter),
+                        with_payload: request.with_payload.clone(),
+                        with_vector: request.with_vector.clone(),
+                        params: request.params,
+                        limit: request.limit,
+                        score_threshold: request.score_threshold,
+                        offset: request.offset,
+                        with_highlights: None,
+                        with_geo_distance: None,
+                    });
+                    plans.push((RecommendPlan::AverageVector, 1));
+                }
+                RecommendStrategy::BestScore => {
+                    let Some(vector_params) = vector_params else {
+                        return Err(CollectionError::bad_request(format!(
+                            "Strategy best_score is not supported by vector {vector_name}"
+                        )));
+                    };
+                    // Any of the candidates of any positive example might get to the result
+                    for (vector, _weight) in &positive_vectors {
+                        searches.push(SearchRequest {
+                            vector: NamedVector {
+                                name: vector_name.to_string(),
+                                vector: vector.clone(),
+                            }
+                            .into(),
+                            filter: Some(filter.clone()),
+                            with_payload: Some(WithPayloadInterface::Bool(false)),
+                            with_vector: Some(WithVector::Selector(vec![
+                                resolved_vector_name.clone()
+                            ])),
+                            params: request.params,
+                            limit: request.offset + request.limit,
+                            score_threshold: None,
+                            offset: 0,
+                            with_highlights: None,
+                            with_geo_distance: None,
+                        });
+                    }
+                    let searches_count = positive_vectors.len();
+                    plans.push((
+                        RecommendPlan::BestScore {
+                            positive: positive_vectors,
+                            negative: negative_vectors,
+                            distance: vector_params.distance,
+                            vector_name: resolved_vector_name,
+                        },
+                        searches_count,
+                    ));
+                }
+            }
+        }
     }
 
     let search_batch_request = SearchRequestBatch { searches };
 
-    collection
-        .search_batch(search_batch_request, read_consistency, None)
-        .await
+    let mut search_results = collection
+        .search_batch_by_stored_vectors(search_batch_request, read_consistency, None, None)
+        .await?
+        .into_iter();
+
+    let mut results = Vec::with_capacity(plans.len());
+    for (request, (plan, searches_count)) in request_batch.searches.iter().zip(plans) {
+        let candidates = search_results.by_ref().take(searches_count).collect_vec();
+        let result = match plan {
+            RecommendPlan::AverageVector => candidates.into_iter().next().unwrap_or_default(),
+            RecommendPlan::BestScore {
+                positive,
+                negative,
+                distance,
+                vector_name,
+            } => {
+                let points = rescore_candidates(candidates, &vector_name, |vector| {
+                    best_score(vector, &positive, &negative, distance)
+                })
+                .into_iter()
+                .filter(|point| {
+                    request
+                        .score_threshold
+                        .map_or(true, |threshold| point.score >= threshold)
+                })
+                .skip(request.offset)
+                .take(request.limit)
+                .collect();
+                fill_rescored_points(
+                    collection,
+                    points,
+                    request.with_payload.clone(),
+                    request.with_vector.clone(),
+                    read_consistency,
+                )
+                .await?
+            }
+        };
+        results.push(result);
+    }
+    Ok(results)
 }
@@ -5,14 +5,17 @@ use api::grpc::qdrant::{
     CreateFieldIndexCollectionInternal, DeleteFieldIndexCollection,
     DeleteFieldIndexCollectionInternal, DeletePayloadPoints, DeletePayloadPointsInternal,
     DeletePoints, DeletePointsInternal, PointsIdsList, PointsSelector, SetPayloadPoints,
-    SetPayloadPointsInternal, SyncPoints, SyncPointsInternal, UpsertPoints, UpsertPointsInternal,
+    SetPayloadPointsInternal, SyncPoints, SyncPointsInternal, UpdatePointVectors,
+    UpdateVectorsInternal, UpsertPoints, UpsertPointsInternal,
 };
 use segment::types::{Filter, PayloadFieldSchema, PayloadSchemaParams, PointIdType, ScoredPoint};
 use tonic::Status;
 
 use crate::operations::conversions::write_ordering_to_proto;
 use crate::operations::payload_ops::{DeletePayload, SetPayload};
-use crate::operations::point_ops::{PointInsertOperations, PointSyncOperation, WriteOrdering};
+use crate::operations::point_ops::{
+    PointInsertOperations, PointSyncOperation, PointVectors, WriteOrdering,
+};
 use crate::operations::types::CollectionResult;
 use crate::operations::CreateIndex;
 use crate::shards::shard::ShardId;
@@ -41,6 +44,24 @@ pub fn internal_sync_points(
     })
 }
 
+pub fn internal_update_vectors(
+    shard_id: Option<ShardId>,
+    collection_name: String,
+    points: Vec<PointVectors>,
+    wait: bool,
+    ordering: Option<WriteOrdering>,
+) -> UpdateVectorsInternal {
+    UpdateVectorsInternal {
+        shard_id,
+        update_vectors: Some(UpdatePointVectors {
+            collection_name,
+            wait: Some(wait),
+            points: points.into_iter().map(|point| point.into()).collect(),
+            ordering: ordering.map(write_ordering_to_proto),
+        }),
+    }
+}
+
 pub fn internal_upsert_points(
     shard_id: Option<ShardId>,
     collection_name: String,
@@ -61,6 +82,7 @@ pub fn internal_upsert_points(
                     .collect::<Result<Vec<_>, Status>>()?,
             },
             ordering: ordering.map(write_ordering_to_proto),
+            shard_key: None,
         }),
     })
 }
@@ -83,6 +105,7 @@ pub fn internal_delete_points(
                 })),
             }),
             ordering: ordering.map(write_ordering_to_proto),
+            shard_key: None,
         }),
     }
 }
@@ -103,6 +126,7 @@ pub fn internal_delete_points_by_filter(
                 points_selector_one_of: Some(PointsSelectorOneOf::Filter(filter.into())),
             }),
             ordering: ordering.map(write_ordering_to_proto),
+            shard_key: None,
         }),
     }
 }
@@ -238,6 +262,9 @@ pub fn internal_create_index(
                     segment::types::PayloadSchemaType::Text => {
                         api::grpc::qdrant::FieldType::Text as i32
                     }
+                    segment::types::PayloadSchemaType::Bool => {
+                        api::grpc::qdrant::FieldType::Bool as i32
+                    }
                 },
                 None,
             ),
@@ -309,5 +336,13 @@ pub fn try_scored_point_from_grpc(
         score: point.score,
         payload,
         vector,
+        highlights: (!point.highlights.is_empty()).then(|| {
+            point
+                .highlights
+                .into_iter()
+                .map(|highlight| highlight.into())
+                .collect()
+        }),
+        geo_distance: point.geo_distance,
     })
 }
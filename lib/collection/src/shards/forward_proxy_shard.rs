@@ -1,24 +1,36 @@
-use std::path::Path;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::time::Duration;
 
 use async_trait::async_trait;
+use segment::data_types::histogram::{HistogramBuckets, ValueBounds};
+use segment::data_types::order_by::Direction;
 use segment::types::{
-    ExtendedPointId, Filter, PointIdType, ScoredPoint, WithPayload, WithPayloadInterface,
-    WithVector,
+    ExtendedPointId, Filter, GeoPoint, MemoryUsage, PayloadFieldSchema, PayloadKeyType,
+    PayloadKeyTypeRef, PlannerHint, PointIdType, ScoredPoint, SeqNumberType, WithPayload,
+    WithPayloadInterface, WithVector,
 };
+use segment::vector_storage::scoring_benchmark::ScoringMeasurement;
 use tokio::runtime::Handle;
 use tokio::sync::Mutex;
 
+use crate::collection_manager::holders::segment_holder::LockedSegment;
+use crate::operations::cardinality_ops::SegmentCardinalityEstimation;
+use crate::operations::id_range_ops::IdChunk;
 use crate::operations::point_ops::{PointOperations, PointStruct, PointSyncOperation};
+use crate::operations::recall_ops::{RecallEstimationRequest, SegmentRecall};
 use crate::operations::types::{
     CollectionError, CollectionInfo, CollectionResult, CountRequest, CountResult, PointRequest,
     Record, SearchRequestBatch, UpdateResult,
 };
+use crate::operations::vacuum_ops::SegmentDeletedInfo;
 use crate::operations::{CollectionUpdateOperations, CreateIndex, FieldIndexOperations};
 use crate::shards::local_shard::LocalShard;
 use crate::shards::remote_shard::RemoteShard;
+use crate::shards::shard::ShardId;
 use crate::shards::shard_trait::ShardOperation;
-use crate::shards::telemetry::LocalShardTelemetry;
+use crate::shards::telemetry::{LocalShardTelemetry, WalTelemetry};
 
 /// ForwardProxyShard
 ///
@@ -72,6 +84,15 @@ impl ForwardProxyShard {
         debug_assert!(batch_size > 0);
         let limit = batch_size + 1;
         let _update_lock = self.update_lock.lock().await;
+        // Points are transferred with original vectors, which are removed in quantized-only segments.
+        // Stop the transfer instead of sending points without them.
+        if self.wrapped_shard.has_quantized_only_segments() {
+            return Err(CollectionError::bad_request(
+                "Shard contains segments with quantized-only vectors, \
+                 points can't be transferred without original vectors"
+                    .to_string(),
+            ));
+        }
         let mut batch = self
             .wrapped_shard
             .scroll_by(
@@ -80,6 +101,8 @@ impl ForwardProxyShard {
                 &WithPayloadInterface::Bool(true),
                 &true.into(),
                 None,
+                None,
+                None,
             )
             .await?;
         let next_page_offset = if batch.len() < limit {
@@ -90,10 +113,21 @@ impl ForwardProxyShard {
             Some(batch.pop().unwrap().id)
         };
 
+        let ids: Vec<_> = batch.iter().map(|point| point.id).collect();
+        let mut points_metadata = self.wrapped_shard.points_metadata(&ids)?;
+
         let points: Result<Vec<PointStruct>, String> =
             batch.into_iter().map(|point| point.try_into()).collect();
 
-        let points = points?;
+        let mut points = points?;
+        // Sequence numbers and content hashes are not returned in records,
+        // so they are transferred separately
+        for point in &mut points {
+            if let Some(metadata) = points_metadata.remove(&point.id) {
+                point.sequence = metadata.sequence;
+                point.content_hash = metadata.content_hash;
+            }
+        }
 
         // Use sync API to leverage potentially existing points
         let insert_points_operation = {
@@ -134,10 +168,140 @@ impl ForwardProxyShard {
         self.wrapped_shard.on_optimizer_config_update().await
     }
 
+    pub async fn on_vectors_added(&self) -> CollectionResult<()> {
+        self.wrapped_shard.on_vectors_added().await
+    }
+
     pub fn get_telemetry_data(&self) -> LocalShardTelemetry {
         self.wrapped_shard.get_telemetry_data()
     }
 
+    pub async fn estimate_recall(
+        &self,
+        shard_id: ShardId,
+        vector_name: &str,
+        request: &RecallEstimationRequest,
+        search_runtime_handle: &Handle,
+    ) -> CollectionResult<Vec<SegmentRecall>> {
+        self.wrapped_shard
+            .estimate_recall(shard_id, vector_name, request, search_runtime_handle)
+            .await
+    }
+
+    pub fn benchmark_scoring(
+        &self,
+        vector_name: &str,
+        points_limit: usize,
+    ) -> CollectionResult<Vec<ScoringMeasurement>> {
+        self.wrapped_shard
+            .benchmark_scoring(vector_name, points_limit)
+    }
+
+    pub fn indexed_fields(&self) -> HashMap<PayloadKeyType, PayloadFieldSchema> {
+        self.wrapped_shard.indexed_fields()
+    }
+
+    pub fn estimate_segments_cardinality(
+        &self,
+        filter: Option<&Filter>,
+    ) -> Vec<SegmentCardinalityEstimation> {
+        self.wrapped_shard.estimate_segments_cardinality(filter)
+    }
+
+    pub fn term_counts(
+        &self,
+        field_name: PayloadKeyTypeRef,
+        filter: Option<&Filter>,
+    ) -> Option<HashMap<String, usize>> {
+        self.wrapped_shard.term_counts(field_name, filter)
+    }
+
+    pub fn numeric_bounds(
+        &self,
+        field_name: PayloadKeyTypeRef,
+        filter: Option<&Filter>,
+    ) -> Option<Option<ValueBounds>> {
+        self.wrapped_shard.numeric_bounds(field_name, filter)
+    }
+
+    pub fn numeric_histogram(
+        &self,
+        field_name: PayloadKeyTypeRef,
+        buckets: &HistogramBuckets,
+        filter: Option<&Filter>,
+    ) -> Option<Vec<usize>> {
+        self.wrapped_shard
+            .numeric_histogram(field_name, buckets, filter)
+    }
+
+    pub fn nearest_points(
+        &self,
+        field_name: PayloadKeyTypeRef,
+        origin: &GeoPoint,
+        limit: usize,
+        filter: Option<&Filter>,
+    ) -> Option<Vec<(PointIdType, f64)>> {
+        self.wrapped_shard
+            .nearest_points(field_name, origin, limit, filter)
+    }
+
+    pub fn ordered_points(
+        &self,
+        field_name: PayloadKeyTypeRef,
+        direction: Direction,
+        start_from: Option<f64>,
+        offset: Option<PointIdType>,
+        limit: usize,
+        filter: Option<&Filter>,
+    ) -> Option<Vec<(PointIdType, f64)>> {
+        self.wrapped_shard
+            .ordered_points(field_name, direction, start_from, offset, limit, filter)
+    }
+
+    pub fn id_chunks(&self, chunks: usize) -> Vec<IdChunk> {
+        self.wrapped_shard.id_chunks(chunks)
+    }
+
+    pub fn point_versions(&self, ids: &[PointIdType]) -> HashMap<PointIdType, SeqNumberType> {
+        self.wrapped_shard.point_versions(ids)
+    }
+
+    pub async fn wait_applied(
+        &self,
+        version: SeqNumberType,
+        timeout: Duration,
+    ) -> CollectionResult<()> {
+        self.wrapped_shard.wait_applied(version, timeout).await
+    }
+
+    pub async fn vacuum(
+        &self,
+        shard_id: ShardId,
+        deleted_threshold: f64,
+    ) -> CollectionResult<(Vec<SegmentDeletedInfo>, usize)> {
+        self.wrapped_shard.vacuum(shard_id, deleted_threshold).await
+    }
+
+    pub async fn flush(&self) -> CollectionResult<SeqNumberType> {
+        self.wrapped_shard.flush().await
+    }
+
+    pub fn original_segments(&self) -> Vec<LockedSegment> {
+        self.wrapped_shard.original_segments()
+    }
+
+    pub async fn memory_usage(&self) -> CollectionResult<MemoryUsage> {
+        self.wrapped_shard.memory_usage().await
+    }
+
+    pub async fn truncate_wal(&self) -> CollectionResult<(SeqNumberType, WalTelemetry)> {
+        self.wrapped_shard.truncate_wal().await
+    }
+
+    pub async fn orphaned_files(&self) -> CollectionResult<Option<Vec<PathBuf>>> {
+        self.wrapped_shard.orphaned_files().await
+    }
+
     /// Forward `before_drop` to `wrapped_shard`
     pub async fn before_drop(&mut self) {
         self.wrapped_shard.before_drop().await
@@ -172,10 +336,20 @@ impl ShardOperation for ForwardProxyShard {
         with_payload_interface: &WithPayloadInterface,
         with_vector: &WithVector,
         filter: Option<&Filter>,
+        sample_seed: Option<u64>,
+        planner_hint: Option<PlannerHint>,
     ) -> CollectionResult<Vec<Record>> {
         let local_shard = &self.wrapped_shard;
         local_shard
-            .scroll_by(offset, limit, with_payload_interface, with_vector, filter)
+            .scroll_by(
+                offset,
+                limit,
+                with_payload_interface,
+                with_vector,
+                filter,
+                sample_seed,
+                planner_hint,
+            )
             .await
     }
 
@@ -1,22 +1,29 @@
 use std::collections::hash_map::Entry;
-use std::collections::{BTreeSet, HashMap};
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::ops::Deref;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::thread;
+use std::time::Duration;
 
 use arc_swap::ArcSwap;
 use indicatif::{ProgressBar, ProgressStyle};
 use itertools::Itertools;
 use parking_lot::{Mutex as ParkingMutex, RwLock};
+use segment::common::log_context::LogContext;
+use segment::data_types::distinct::DistinctValues;
+use segment::data_types::histogram::{HistogramBuckets, ValueBounds};
+use segment::data_types::order_by::Direction;
 use segment::entry::entry_point::SegmentEntry;
 use segment::index::field_index::CardinalityEstimation;
 use segment::segment::Segment;
-use segment::segment_constructor::{build_segment, load_segment};
+use segment::segment_constructor::{build_segment, is_segment_building, load_segment};
 use segment::types::{
-    Filter, PayloadIndexInfo, PayloadKeyType, PayloadStorageType, PointIdType, SegmentConfig,
-    SegmentType,
+    Filter, GeoPoint, MemoryUsage, PayloadFieldSchema, PayloadIndexInfo, PayloadKeyType,
+    PayloadKeyTypeRef, PayloadStorageType, PlannerHint, PointIdType, PointMetadata, SegmentConfig,
+    SegmentType, SeqNumberType,
 };
+use segment::vector_storage::scoring_benchmark::ScoringMeasurement;
 use tokio::fs::{copy, create_dir_all, remove_dir_all};
 use tokio::runtime::Handle;
 use tokio::sync::mpsc::Sender;
@@ -26,18 +33,25 @@ use wal::{Wal, WalOptions};
 use crate::collection_manager::collection_updater::CollectionUpdater;
 use crate::collection_manager::holders::segment_holder::{LockedSegment, SegmentHolder};
 use crate::config::CollectionConfig;
+use crate::operations::cardinality_ops::SegmentCardinalityEstimation;
+use crate::operations::id_range_ops::{split_into_chunks, IdChunk};
+use crate::operations::recall_ops::{
+    estimate_segment_recall, RecallEstimationRequest, SegmentRecall,
+};
 use crate::operations::shared_storage_config::SharedStorageConfig;
 use crate::operations::types::{
     CollectionError, CollectionInfo, CollectionResult, CollectionStatus, OptimizersStatus,
 };
+use crate::operations::vacuum_ops::SegmentDeletedInfo;
 use crate::operations::CollectionUpdateOperations;
-use crate::optimizers_builder::build_optimizers;
+use crate::optimizers_builder::{build_optimizers, build_vacuum_optimizer};
 use crate::shards::shard::ShardId;
 use crate::shards::shard_config::{ShardConfig, SHARD_CONFIG_FILE};
-use crate::shards::telemetry::{LocalShardTelemetry, OptimizerTelemetry};
+use crate::shards::telemetry::{LocalShardTelemetry, OptimizerTelemetry, WalTelemetry};
 use crate::shards::CollectionId;
 use crate::update_handler::{Optimizer, UpdateHandler, UpdateSignal};
 use crate::wal::SerdeWal;
+use crate::wal_archive::WalArchive;
 
 pub type LockedWal = Arc<ParkingMutex<SerdeWal<CollectionUpdateOperations>>>;
 
@@ -103,6 +117,7 @@ impl LocalShard {
         optimizers: Arc<Vec<Arc<Optimizer>>>,
         shard_path: &Path,
         update_runtime: Handle,
+        log_context: LogContext,
     ) -> Self {
         let segment_holder = Arc::new(RwLock::new(segment_holder));
         let config = collection_config.read().await;
@@ -116,6 +131,7 @@ impl LocalShard {
             locked_wal.clone(),
             config.optimizer_config.flush_interval_sec,
             config.optimizer_config.max_optimization_threads,
+            log_context,
         );
 
         let (update_sender, update_receiver) =
@@ -156,11 +172,12 @@ impl LocalShard {
         let segments_path = Self::segments_path(shard_path);
         let mut segment_holder = SegmentHolder::default();
 
-        let wal: SerdeWal<CollectionUpdateOperations> = SerdeWal::new(
+        let mut wal: SerdeWal<CollectionUpdateOperations> = SerdeWal::new(
             wal_path.to_str().unwrap(),
             (&collection_config_read.wal_config).into(),
         )
         .map_err(|e| CollectionError::service_error(format!("Wal error: {e}")))?;
+        Self::set_wal_archive(&mut wal, &shared_storage_config, &collection_id, id)?;
 
         let segment_dirs = std::fs::read_dir(&segments_path).map_err(|err| {
             CollectionError::service_error(format!(
@@ -170,6 +187,7 @@ impl LocalShard {
             ))
         })?;
 
+        let log_context = LogContext::new(&collection_id, Some(id));
         let mut load_handlers = vec![];
 
         for entry in segment_dirs {
@@ -183,10 +201,12 @@ impl LocalShard {
                 })?;
                 continue;
             }
+            let log_context = log_context.clone();
             load_handlers.push(
                 thread::Builder::new()
                     .name(format!("shard-load-{collection_id}-{id}"))
                     .spawn(move || {
+                        let _log_context = log_context.enter();
                         let mut res = load_segment(&segments_path)?;
                         if let Some(segment) = &mut res {
                             segment.check_consistency_and_repair()?;
@@ -231,10 +251,12 @@ impl LocalShard {
             optimizers,
             shard_path,
             update_runtime,
+            log_context.clone(),
         )
         .await;
 
-        collection.load_from_wal(collection_id);
+        let _log_context = log_context.enter();
+        collection.load_from_wal(collection_id)?;
 
         Ok(collection)
     }
@@ -251,6 +273,10 @@ impl LocalShard {
         shard_path.join("segments")
     }
 
+    pub fn temp_segments_path(shard_path: &Path) -> PathBuf {
+        shard_path.join("temp_segments")
+    }
+
     pub async fn build_local(
         id: ShardId,
         collection_id: CollectionId,
@@ -309,8 +335,10 @@ impl LocalShard {
             .get_all_vector_params(&config.hnsw_config, config.quantization_config.as_ref())?;
         let segment_number = config.optimizer_config.get_number_segments();
 
+        let log_context = LogContext::new(&collection_id, Some(id));
         for _sid in 0..segment_number {
             let path_clone = segments_path.clone();
+            let segment_log_context = log_context.clone();
             let segment_config = SegmentConfig {
                 vector_data: vector_params.clone(),
                 index: Default::default(),
@@ -320,10 +348,14 @@ impl LocalShard {
                     false => PayloadStorageType::InMemory,
                 },
                 quantization_config: Default::default(),
+                sparse_vector_data: config.params.get_all_sparse_vector_params(),
             };
             let segment = thread::Builder::new()
                 .name(format!("shard-build-{collection_id}-{id}"))
-                .spawn(move || build_segment(&path_clone, &segment_config))
+                .spawn(move || {
+                    let _log_context = segment_log_context.enter();
+                    build_segment(&path_clone, &segment_config)
+                })
                 .unwrap();
             build_handlers.push(segment);
         }
@@ -347,8 +379,9 @@ impl LocalShard {
             segment_holder.add(segment);
         }
 
-        let wal: SerdeWal<CollectionUpdateOperations> =
+        let mut wal: SerdeWal<CollectionUpdateOperations> =
             SerdeWal::new(wal_path.to_str().unwrap(), (&config.wal_config).into())?;
+        Self::set_wal_archive(&mut wal, &shared_storage_config, &collection_id, id)?;
 
         let optimizers = build_optimizers(
             shard_path,
@@ -368,6 +401,7 @@ impl LocalShard {
             optimizers,
             shard_path,
             update_runtime,
+            log_context,
         )
         .await;
 
@@ -384,10 +418,24 @@ impl LocalShard {
         update_handler.wait_workers_stops().await
     }
 
+    /// Archive records of the shard WAL before truncation, if it is configured for the node
+    fn set_wal_archive(
+        wal: &mut SerdeWal<CollectionUpdateOperations>,
+        shared_storage_config: &SharedStorageConfig,
+        collection_id: &CollectionId,
+        shard_id: ShardId,
+    ) -> CollectionResult<()> {
+        if let Some(archive_config) = &shared_storage_config.wal_archive {
+            let archive = WalArchive::new(archive_config, collection_id, shard_id)?;
+            wal.set_archive(archive);
+        }
+        Ok(())
+    }
+
     /// Loads latest collection operations from WAL
-    pub fn load_from_wal(&self, collection_id: CollectionId) {
+    pub fn load_from_wal(&self, collection_id: CollectionId) -> CollectionResult<()> {
         let wal = self.wal.lock();
-        let bar = ProgressBar::new(wal.len());
+        let bar = ProgressBar::new(wal.len()?);
 
         let progress_style = ProgressStyle::default_bar()
             .template("{msg} [{elapsed_precise}] {wide_bar} {pos}/{len} (eta:{eta})")
@@ -397,7 +445,7 @@ impl LocalShard {
         bar.set_message(format!("Recovering collection {collection_id}"));
         let segments = self.segments();
         // ToDo: Start from minimal applied version
-        for (op_num, update) in wal.read_all() {
+        for (op_num, update) in wal.read_all()? {
             // Panic only in case of internal error. If wrong formatting - skip
             if let Err(CollectionError::ServiceError { error, backtrace }) =
                 CollectionUpdater::update(segments, op_num, update)
@@ -413,6 +461,7 @@ impl LocalShard {
 
         self.segments.read().flush_all(true).unwrap();
         bar.finish();
+        Ok(())
     }
 
     pub async fn on_optimizer_config_update(&self) -> CollectionResult<()> {
@@ -427,6 +476,7 @@ impl LocalShard {
         update_handler.stop_flush_worker();
 
         update_handler.wait_workers_stops().await?;
+        self.wal.lock().reopen((&config.wal_config).into())?;
         let new_optimizers = build_optimizers(
             &self.path,
             &config.params,
@@ -442,6 +492,43 @@ impl LocalShard {
         Ok(())
     }
 
+    /// Adds an appendable segment, which stores all vectors of the collection,
+    /// and restarts optimizers with new params.
+    ///
+    /// Existing segments keep their vectors, points are moved into the new segment on upsert.
+    pub async fn on_vectors_added(&self) -> CollectionResult<()> {
+        {
+            let config = self.collection_config.read().await;
+            let segment_config = SegmentConfig {
+                vector_data: config.params.get_all_vector_params(
+                    &config.hnsw_config,
+                    config.quantization_config.as_ref(),
+                )?,
+                index: Default::default(),
+                storage_type: Default::default(),
+                payload_storage_type: match config.params.on_disk_payload {
+                    true => PayloadStorageType::OnDisk,
+                    false => PayloadStorageType::InMemory,
+                },
+                quantization_config: Default::default(),
+                sparse_vector_data: config.params.get_all_sparse_vector_params(),
+            };
+            let mut segment = build_segment(&Self::segments_path(&self.path), &segment_config)?;
+
+            let mut segments = self.segments.write();
+            let indexed_fields = segments
+                .iter()
+                .next()
+                .map(|(_, segment)| segment.get().read().get_indexed_fields())
+                .unwrap_or_default();
+            for (field_name, field_schema) in indexed_fields {
+                segment.create_field_index(0, &field_name, Some(&field_schema))?;
+            }
+            segments.add(segment);
+        }
+        self.on_optimizer_config_update().await
+    }
+
     pub async fn before_drop(&mut self) {
         // Finishes update tasks right before destructor stuck to do so with runtime
         if let Err(err) = self.update_sender.load().send(UpdateSignal::Stop).await {
@@ -532,7 +619,7 @@ impl LocalShard {
     pub fn snapshot_empty_wal(wal: LockedWal, snapshot_shard_path: &Path) -> CollectionResult<()> {
         let (segment_capacity, latest_op_num) = {
             let wal_guard = wal.lock();
-            (wal_guard.segment_capacity(), wal_guard.last_index())
+            (wal_guard.segment_capacity(), wal_guard.last_index()?)
         };
 
         let target_path = Self::wal_path(snapshot_shard_path);
@@ -603,6 +690,7 @@ impl LocalShard {
     pub fn read_filtered<'a>(
         &'a self,
         filter: Option<&'a Filter>,
+        planner_hint: Option<PlannerHint>,
     ) -> CollectionResult<BTreeSet<PointIdType>> {
         let segments = self.segments().read();
         let some_segment = segments.iter().next();
@@ -612,11 +700,510 @@ impl LocalShard {
         }
         let all_points: BTreeSet<_> = segments
             .iter()
-            .flat_map(|(_id, segment)| segment.get().read().read_filtered(None, None, filter))
+            .flat_map(|(_id, segment)| {
+                segment
+                    .get()
+                    .read()
+                    .read_filtered(None, None, filter, planner_hint)
+            })
             .collect();
         Ok(all_points)
     }
 
+    /// Segments of the shard, which are not being optimized at the moment
+    pub fn original_segments(&self) -> Vec<LockedSegment> {
+        self.segments()
+            .read()
+            .iter()
+            .filter(|(_id, segment)| matches!(segment, LockedSegment::Original(_)))
+            .map(|(_id, segment)| segment.clone())
+            .collect()
+    }
+
+    /// Estimate memory, occupied by all segments of the shard
+    pub async fn memory_usage(&self) -> CollectionResult<MemoryUsage> {
+        let segments = self.segments.clone();
+        let usage = tokio::task::spawn_blocking(move || {
+            segments
+                .read()
+                .iter()
+                .map(|(_id, segment)| segment.get().read().memory_usage())
+                .sum()
+        })
+        .await?;
+        Ok(usage)
+    }
+
+    /// Flush WAL and all segments to disk.
+    ///
+    /// Returns version of the last operation, which is persisted in all segments.
+    pub async fn flush(&self) -> CollectionResult<SeqNumberType> {
+        let segments = self.segments.clone();
+        let wal = self.wal.clone();
+        tokio::task::spawn_blocking(move || -> CollectionResult<SeqNumberType> {
+            wal.lock().flush()?;
+            let confirmed_version = UpdateHandler::flush_segments(segments, true)?;
+            wal.lock().ack(confirmed_version)?;
+            Ok(confirmed_version)
+        })
+        .await?
+    }
+
+    /// Report deleted points of each segment and start rebuilding segments,
+    /// which have the fraction of deleted points above `deleted_threshold`.
+    ///
+    /// Returns segments info and number of started optimizations.
+    pub async fn vacuum(
+        &self,
+        shard_id: ShardId,
+        deleted_threshold: f64,
+    ) -> CollectionResult<(Vec<SegmentDeletedInfo>, usize)> {
+        let segments_info = {
+            let segments = self.segments().read();
+            segments
+                .iter()
+                .map(|(segment_id, segment)| {
+                    let segment = segment.get();
+                    let read_segment = segment.read();
+                    let points_count = read_segment.points_count();
+                    let deleted_count = read_segment.deleted_count();
+                    let segment_type = read_segment.segment_type();
+                    // Same condition as in vacuum optimizer, without division by zero
+                    let is_littered =
+                        deleted_count as f64 > points_count as f64 * deleted_threshold;
+                    SegmentDeletedInfo {
+                        shard_id,
+                        segment_id: *segment_id,
+                        segment_type,
+                        points_count,
+                        deleted_count,
+                        deleted_ratio: (points_count > 0)
+                            .then(|| deleted_count as f64 / points_count as f64),
+                        vacuum: segment_type != SegmentType::Special && is_littered,
+                    }
+                })
+                .collect_vec()
+        };
+
+        if !segments_info.iter().any(|info| info.vacuum) {
+            return Ok((segments_info, 0));
+        }
+
+        let vacuum_optimizer = {
+            let config = self.collection_config.read().await;
+            build_vacuum_optimizer(
+                &self.path,
+                &config.params,
+                &config.optimizer_config,
+                &config.hnsw_config,
+                &config.quantization_config,
+                deleted_threshold,
+            )
+        };
+        let optimizations_started = self
+            .update_handler
+            .lock()
+            .await
+            .launch_extra_optimization(Arc::new(vec![vacuum_optimizer]))
+            .await;
+
+        Ok((segments_info, optimizations_started))
+    }
+
+    /// Files and directories in the segment directories of the shard, which are not used by any segment.
+    /// Segments, which are being built, are skipped.
+    ///
+    /// Returns `None` if optimizations are running, as segments of running optimizations
+    /// are not in the segment holder, and their temporary segments are in use.
+    ///
+    /// WAL and snapshot directories are not checked: WAL files are removed by the WAL itself,
+    /// and snapshots are managed with the snapshot API.
+    pub async fn orphaned_files(&self) -> CollectionResult<Option<Vec<PathBuf>>> {
+        if self.has_running_optimizations().await {
+            return Ok(None);
+        }
+
+        let paths = {
+            // Directories are listed under the lock, so segments can't be added or removed
+            // between the listing and collecting the used paths
+            let segments = self.segments().read();
+            let used_paths: HashSet<_> = segments
+                .iter()
+                .map(|(_, segment)| segment.get().read().data_path())
+                .collect();
+
+            let mut paths = Vec::new();
+            for dir in [
+                Self::segments_path(&self.path),
+                Self::temp_segments_path(&self.path),
+            ] {
+                if !dir.is_dir() {
+                    continue;
+                }
+                for entry in std::fs::read_dir(&dir)? {
+                    let path = entry?.path();
+                    if !is_segment_building(&path) && !used_paths.contains(&path) {
+                        paths.push(path);
+                    }
+                }
+            }
+            paths
+        };
+
+        // Optimization might have started during the listing
+        if self.has_running_optimizations().await {
+            return Ok(None);
+        }
+        Ok(Some(paths))
+    }
+
+    async fn has_running_optimizations(&self) -> bool {
+        self.update_handler
+            .lock()
+            .await
+            .has_running_optimizations()
+            .await
+    }
+
+    /// Estimate recall of approximate search in each segment of the shard.
+    ///
+    /// Searches are blocking, so they run on the search runtime.
+    pub async fn estimate_recall(
+        &self,
+        shard_id: ShardId,
+        vector_name: &str,
+        request: &RecallEstimationRequest,
+        search_runtime_handle: &Handle,
+    ) -> CollectionResult<Vec<SegmentRecall>> {
+        let segments = self.segments.clone();
+        let vector_name = vector_name.to_owned();
+        let request = request.clone();
+        search_runtime_handle
+            .spawn_blocking(move || {
+                let segments = segments.read();
+                let mut recalls = Vec::new();
+                for (segment_id, segment) in segments.iter() {
+                    let segment_recall = estimate_segment_recall(
+                        segment.get().read().deref(),
+                        shard_id,
+                        *segment_id,
+                        &vector_name,
+                        &request,
+                    )?;
+                    recalls.extend(segment_recall);
+                }
+                Ok(recalls)
+            })
+            .await?
+    }
+
+    /// Measure scoring throughput of the vector in each segment of the shard
+    pub fn benchmark_scoring(
+        &self,
+        vector_name: &str,
+        points_limit: usize,
+    ) -> CollectionResult<Vec<ScoringMeasurement>> {
+        let segments = self.segments().read();
+        let mut measurements = Vec::new();
+        for (_segment_id, segment) in segments.iter() {
+            let segment = segment.get();
+            let segment = segment.read();
+            // Segments, created before the vector was added, don't store it
+            if !segment.vector_dims().contains_key(vector_name) {
+                continue;
+            }
+            measurements.extend(segment.benchmark_scoring(vector_name, points_limit)?);
+        }
+        Ok(measurements)
+    }
+
+    /// Schema of payload fields, indexed in any segment of the shard
+    pub fn indexed_fields(&self) -> HashMap<PayloadKeyType, PayloadFieldSchema> {
+        let segments = self.segments().read();
+        let mut indexed_fields = HashMap::new();
+        for (_, segment) in segments.iter() {
+            indexed_fields.extend(segment.get().read().get_indexed_fields());
+        }
+        indexed_fields
+    }
+
+    /// Estimate the number of points, which satisfy the filter, in each segment of the shard
+    pub fn estimate_segments_cardinality(
+        &self,
+        filter: Option<&Filter>,
+    ) -> Vec<SegmentCardinalityEstimation> {
+        let segments = self.segments().read();
+        segments
+            .iter()
+            .map(|(_, segment)| {
+                let segment = segment.get();
+                let segment = segment.read();
+                SegmentCardinalityEstimation::new(
+                    segment.segment_type(),
+                    segment.points_count(),
+                    segment.estimate_points_count(filter),
+                )
+            })
+            .collect()
+    }
+
+    /// Number of points, which satisfy the filter, for each term of the keyword or full-text index
+    /// of the field in all segments of the shard.
+    /// Returns `None` if no segment has such index of the field.
+    pub fn term_counts(
+        &self,
+        field_name: PayloadKeyTypeRef,
+        filter: Option<&Filter>,
+    ) -> Option<HashMap<String, usize>> {
+        let segments = self.segments().read();
+        let mut term_counts: Option<HashMap<String, usize>> = None;
+        for (_, segment) in segments.iter() {
+            let segment_counts = match segment.get().read().term_counts(field_name, filter) {
+                Some(segment_counts) => segment_counts,
+                None => continue,
+            };
+            let term_counts = term_counts.get_or_insert_with(HashMap::new);
+            for (term, count) in segment_counts {
+                *term_counts.entry(term).or_default() += count;
+            }
+        }
+        term_counts
+    }
+
+    /// Distinct values of the keyword or integer index of the field among points, which satisfy
+    /// the filter, in all segments of the shard.
+    /// Returns `None` if no segment has such index of the field.
+    pub fn distinct_values(
+        &self,
+        field_name: PayloadKeyTypeRef,
+        filter: Option<&Filter>,
+    ) -> Option<DistinctValues> {
+        let segments = self.segments().read();
+        let mut distinct_values: Option<DistinctValues> = None;
+        for (_, segment) in segments.iter() {
+            let segment_values = match segment.get().read().distinct_values(field_name, filter) {
+                Some(segment_values) => segment_values,
+                None => continue,
+            };
+            distinct_values
+                .get_or_insert_with(DistinctValues::default)
+                .merge(segment_values);
+        }
+        distinct_values
+    }
+
+    /// Min and max values of the integer or float index of the field in all segments of the shard.
+    /// Returns `None` if no segment has numeric index of the field.
+    pub fn numeric_bounds(
+        &self,
+        field_name: PayloadKeyTypeRef,
+        filter: Option<&Filter>,
+    ) -> Option<Option<ValueBounds>> {
+        let segments = self.segments().read();
+        let mut bounds: Option<Option<ValueBounds>> = None;
+        for (_, segment) in segments.iter() {
+            let segment_bounds = match segment.get().read().numeric_bounds(field_name, filter) {
+                Some(segment_bounds) => segment_bounds,
+                None => continue,
+            };
+            let bounds = bounds.get_or_insert(None);
+            *bounds = ValueBounds::merge_opt(*bounds, segment_bounds);
+        }
+        bounds
+    }
+
+    /// Number of points, which satisfy the filter, with values of the integer or float index
+    /// of the field in each of the buckets, in all segments of the shard.
+    /// Returns `None` if no segment has numeric index of the field.
+    pub fn numeric_histogram(
+        &self,
+        field_name: PayloadKeyTypeRef,
+        buckets: &HistogramBuckets,
+        filter: Option<&Filter>,
+    ) -> Option<Vec<usize>> {
+        let segments = self.segments().read();
+        let mut counts: Option<Vec<usize>> = None;
+        for (_, segment) in segments.iter() {
+            let segment_counts = match segment
+                .get()
+                .read()
+                .numeric_histogram(field_name, buckets, filter)
+            {
+                Some(segment_counts) => segment_counts,
+                None => continue,
+            };
+            let counts = counts.get_or_insert_with(|| vec![0; buckets.count]);
+            for (count, segment_count) in counts.iter_mut().zip(segment_counts) {
+                *count += segment_count;
+            }
+        }
+        counts
+    }
+
+    /// Points of the shard, which satisfy the filter, nearest to the `origin` according to
+    /// the geo index of the field, with distances in meters, ordered from the nearest.
+    /// Returns `None` if no segment has geo index of the field.
+    pub fn nearest_points(
+        &self,
+        field_name: PayloadKeyTypeRef,
+        origin: &GeoPoint,
+        limit: usize,
+        filter: Option<&Filter>,
+    ) -> Option<Vec<(PointIdType, f64)>> {
+        let segments = self.segments().read();
+        let mut nearest: Option<Vec<(PointIdType, f64)>> = None;
+        for (_, segment) in segments.iter() {
+            let segment_nearest = match segment
+                .get()
+                .read()
+                .nearest_points(field_name, origin, limit, filter)
+            {
+                Some(segment_nearest) => segment_nearest,
+                None => continue,
+            };
+            nearest.get_or_insert_with(Vec::new).extend(segment_nearest);
+        }
+        nearest.map(|nearest| {
+            // The same point may be present in several segments during optimization
+            nearest
+                .into_iter()
+                .sorted_by(|(id_a, distance_a), (id_b, distance_b)| {
+                    distance_a.total_cmp(distance_b).then(id_a.cmp(id_b))
+                })
+                .unique_by(|(point_id, _)| *point_id)
+                .take(limit)
+                .collect()
+        })
+    }
+
+    /// Points of the shard, which satisfy the filter, ordered by values of the integer or float
+    /// index of the field, starting from the `start_from` value and `offset` id.
+    /// Returns `None` if no segment has integer or float range index of the field.
+    pub fn ordered_points(
+        &self,
+        field_name: PayloadKeyTypeRef,
+        direction: Direction,
+        start_from: Option<f64>,
+        offset: Option<PointIdType>,
+        limit: usize,
+        filter: Option<&Filter>,
+    ) -> Option<Vec<(PointIdType, f64)>> {
+        let segments = self.segments().read();
+        let mut ordered: Option<Vec<(PointIdType, f64)>> = None;
+        for (_, segment) in segments.iter() {
+            let segment_ordered = match segment
+                .get()
+                .read()
+                .ordered_points(field_name, direction, start_from, offset, limit, filter)
+            {
+                Some(segment_ordered) => segment_ordered,
+                None => continue,
+            };
+            ordered.get_or_insert_with(Vec::new).extend(segment_ordered);
+        }
+        ordered.map(|ordered| {
+            // The same point may be present in several segments during optimization
+            ordered
+                .into_iter()
+                .sorted_by(|(id_a, value_a), (id_b, value_b)| {
+                    direction.cmp(*value_a, *value_b).then(id_a.cmp(id_b))
+                })
+                .unique_by(|(point_id, _)| *point_id)
+                .take(limit)
+                .collect()
+        })
+    }
+
+    /// Split points of the shard into chunks of consecutive ids with about the same number of points
+    pub fn id_chunks(&self, chunks: usize) -> Vec<IdChunk> {
+        let mut ids = Vec::new();
+        for (_, segment) in self.segments().read().iter() {
+            ids.extend(segment.get().read().iter_points());
+        }
+        // The same point may be present in several segments during optimization
+        ids.sort_unstable();
+        ids.dedup();
+        split_into_chunks(&ids, chunks)
+    }
+
+    /// Latest operation, applied to the segments of the shard
+    pub fn applied_version(&self) -> SeqNumberType {
+        self.segments()
+            .read()
+            .iter()
+            .map(|(_, segment)| segment.get().read().version())
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Wait until the operation with the given number in the WAL is applied to the segments
+    pub async fn wait_applied(
+        &self,
+        version: SeqNumberType,
+        timeout: Duration,
+    ) -> CollectionResult<()> {
+        if self.applied_version() >= version {
+            return Ok(());
+        }
+        // The operation may not change any segment, so wait until the update queue processes
+        // everything submitted so far instead.
+        let (tx, rx) = oneshot::channel();
+        let plunger = UpdateSignal::Plunger(tx);
+        self.update_sender.load().send(plunger).await?;
+        match tokio::time::timeout(timeout, rx).await {
+            Ok(res) => Ok(res?),
+            Err(_) => Err(CollectionError::service_error(format!(
+                "Timeout of {}s while waiting for operation {version} to be applied",
+                timeout.as_secs()
+            ))),
+        }
+    }
+
+    /// Versions of the points of the shard, which exist among the given ids.
+    /// Only the latest version is reported, if a point is present in several segments.
+    pub fn point_versions(&self, ids: &[PointIdType]) -> HashMap<PointIdType, SeqNumberType> {
+        let mut versions: HashMap<PointIdType, SeqNumberType> = HashMap::new();
+        for (_, segment) in self.segments().read().iter() {
+            let segment_arc = segment.get();
+            let read_segment = segment_arc.read();
+            for &id in ids {
+                if !read_segment.has_point(id) {
+                    continue;
+                }
+                if let Some(version) = read_segment.point_version(id) {
+                    let latest = versions.entry(id).or_insert(version);
+                    *latest = (*latest).max(version);
+                }
+            }
+        }
+        versions
+    }
+
+    /// Service metadata of the points of the shard, which exist among the given ids and have it
+    pub fn points_metadata(
+        &self,
+        ids: &[PointIdType],
+    ) -> CollectionResult<HashMap<PointIdType, PointMetadata>> {
+        let mut points_metadata = HashMap::new();
+        self.segments().read().read_points(ids, |id, segment| {
+            let metadata = segment.point_metadata(id)?;
+            if !metadata.is_empty() {
+                points_metadata.insert(id, metadata);
+            }
+            Ok(true)
+        })?;
+        Ok(points_metadata)
+    }
+
+    /// Some segments of the shard don't store original values of vectors,
+    /// only quantized ones are kept
+    pub fn has_quantized_only_segments(&self) -> bool {
+        self.segments()
+            .read()
+            .iter()
+            .any(|(_id, segment)| segment.get().read().config().has_quantized_only_vectors())
+    }
+
     pub fn get_telemetry_data(&self) -> LocalShardTelemetry {
         let segments_read_guard = self.segments.read();
         let segments: Vec<_> = segments_read_guard
@@ -642,9 +1229,49 @@ impl LocalShard {
                 status: optimizer_status,
                 optimizations,
             },
+            wal: self
+                .wal_telemetry()
+                .map_err(|err| log::warn!("Can't read WAL of shard {}: {err}", self.path.display()))
+                .ok(),
         }
     }
 
+    fn wal_telemetry(&self) -> CollectionResult<WalTelemetry> {
+        let wal = self.wal.lock();
+        let size_bytes = match wal.disk_size() {
+            Ok(size) => Some(size),
+            Err(err) => {
+                log::warn!(
+                    "Can't read WAL size of shard {}: {err}",
+                    self.path.display()
+                );
+                None
+            }
+        };
+        Ok(WalTelemetry {
+            size_bytes,
+            num_entries: wal.len()?,
+            first_op_num: wal.first_index()?,
+            last_op_num: wal.last_index()?,
+        })
+    }
+
+    /// Acknowledge operations, persisted in all segments, and truncate WAL up to them.
+    ///
+    /// Returns version of the last acknowledged operation and state of WAL after truncation.
+    pub async fn truncate_wal(&self) -> CollectionResult<(SeqNumberType, WalTelemetry)> {
+        let segments = self.segments.clone();
+        let wal = self.wal.clone();
+        let confirmed_version =
+            tokio::task::spawn_blocking(move || -> CollectionResult<SeqNumberType> {
+                let confirmed_version = UpdateHandler::flush_segments(segments, false)?;
+                wal.lock().ack(confirmed_version)?;
+                Ok(confirmed_version)
+            })
+            .await??;
+        Ok((confirmed_version, self.wal_telemetry()?))
+    }
+
     fn assert_before_drop_called(&self) {
         if !self.before_drop_called {
             // Panic is used to get fast feedback in unit and integration tests
@@ -697,7 +1324,7 @@ impl LocalShard {
             for (key, val) in segment_info.index_schema {
                 match schema.entry(key) {
                     Entry::Occupied(o) => {
-                        o.into_mut().points += val.points;
+                        o.into_mut().merge(&val);
                     }
                     Entry::Vacant(v) => {
                         v.insert(val);
@@ -2,16 +2,19 @@ use std::sync::Arc;
 
 use async_trait::async_trait;
 use itertools::Itertools;
+use segment::common::utils::sampling_key;
+use segment::data_types::distinct::DistinctValues;
 use segment::types::{
-    ExtendedPointId, Filter, ScoredPoint, WithPayload, WithPayloadInterface, WithVector,
+    ExtendedPointId, Filter, PlannerHint, ScoredPoint, WithPayload, WithPayloadInterface,
+    WithVector,
 };
 use tokio::runtime::Handle;
 use tokio::sync::oneshot;
 
 use crate::collection_manager::segments_searcher::SegmentsSearcher;
 use crate::operations::types::{
-    CollectionInfo, CollectionResult, CountRequest, CountResult, PointRequest, Record,
-    SearchRequestBatch, UpdateResult, UpdateStatus,
+    CollectionError, CollectionInfo, CollectionResult, CountRequest, CountResult, PointRequest,
+    Record, SearchRequestBatch, UpdateResult, UpdateStatus,
 };
 use crate::operations::CollectionUpdateOperations;
 use crate::shards::local_shard::LocalShard;
@@ -53,11 +56,17 @@ impl ShardOperation for LocalShard {
             Ok(UpdateResult {
                 operation_id,
                 status: UpdateStatus::Completed,
+                replication: None,
+                generated_ids: None,
+                consistency_token: None,
             })
         } else {
             Ok(UpdateResult {
                 operation_id,
                 status: UpdateStatus::Acknowledged,
+                replication: None,
+                generated_ids: None,
+                consistency_token: None,
             })
         }
     }
@@ -69,9 +78,31 @@ impl ShardOperation for LocalShard {
         with_payload_interface: &WithPayloadInterface,
         with_vector: &WithVector,
         filter: Option<&Filter>,
+        sample_seed: Option<u64>,
+        planner_hint: Option<PlannerHint>,
     ) -> CollectionResult<Vec<Record>> {
-        // ToDo: Make faster points selection with a set
         let segments = self.segments();
+
+        if let Some(seed) = sample_seed {
+            let point_ids = segments
+                .read()
+                .iter()
+                .flat_map(|(_, segment)| segment.get().read().sample_filtered(limit, filter, seed))
+                .sorted_by_key(|point_id| (sampling_key(seed, *point_id), *point_id))
+                .dedup()
+                .take(limit)
+                .collect_vec();
+
+            let with_payload = WithPayload::from(with_payload_interface);
+            let mut points =
+                SegmentsSearcher::retrieve(segments, &point_ids, &with_payload, with_vector)
+                    .await?;
+            points.sort_by_key(|point| (sampling_key(seed, point.id), point.id));
+
+            return Ok(points);
+        }
+
+        // ToDo: Make faster points selection with a set
         let point_ids = segments
             .read()
             .iter()
@@ -79,7 +110,7 @@ impl ShardOperation for LocalShard {
                 segment
                     .get()
                     .read()
-                    .read_filtered(offset, Some(limit), filter)
+                    .read_filtered(offset, Some(limit), filter, planner_hint)
             })
             .sorted()
             .dedup()
@@ -146,12 +177,27 @@ impl ShardOperation for LocalShard {
 
     async fn count(&self, request: Arc<CountRequest>) -> CollectionResult<CountResult> {
         let total_count = if request.exact {
-            let all_points = self.read_filtered(request.filter.as_ref())?;
+            let all_points = self.read_filtered(request.filter.as_ref(), request.planner_hint)?;
             all_points.len()
         } else {
             self.estimate_cardinality(request.filter.as_ref())?.exp
         };
-        Ok(CountResult { count: total_count })
+        let distinct_values = match &request.distinct {
+            None => None,
+            Some(key) => Some(
+                self.distinct_values(key, request.filter.as_ref())
+                    .ok_or_else(|| CollectionError::BadRequest {
+                        description: format!(
+                            "Keyword or integer index of field {key} is required to count values"
+                        ),
+                    })?,
+            ),
+        };
+        Ok(CountResult {
+            count: total_count,
+            distinct: distinct_values.as_ref().map(DistinctValues::count),
+            distinct_values,
+        })
     }
 
     async fn retrieve(
@@ -6,6 +6,7 @@ pub mod local_shard;
 pub mod local_shard_operations;
 pub mod proxy_shard;
 pub mod remote_shard;
+pub mod replica_selection;
 #[allow(dead_code)]
 pub mod replica_set;
 pub mod resolve;
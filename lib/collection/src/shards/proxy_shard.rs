@@ -1,29 +1,39 @@
-use std::collections::HashSet;
-use std::path::Path;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
 use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
 use std::time::Duration;
 
 use async_trait::async_trait;
+use segment::data_types::histogram::{HistogramBuckets, ValueBounds};
+use segment::data_types::order_by::Direction;
 use segment::types::{
-    ExtendedPointId, Filter, PointIdType, ScoredPoint, WithPayload, WithPayloadInterface,
-    WithVector,
+    ExtendedPointId, Filter, GeoPoint, MemoryUsage, PayloadFieldSchema, PayloadKeyType,
+    PayloadKeyTypeRef, PlannerHint, PointIdType, ScoredPoint, SeqNumberType, WithPayload,
+    WithPayloadInterface, WithVector,
 };
+use segment::vector_storage::scoring_benchmark::ScoringMeasurement;
 use tokio::runtime::Handle;
 use tokio::sync::{oneshot, RwLock};
 use tokio::time::timeout;
 
+use crate::collection_manager::holders::segment_holder::LockedSegment;
+use crate::operations::cardinality_ops::SegmentCardinalityEstimation;
+use crate::operations::id_range_ops::IdChunk;
 use crate::operations::operation_effect::{
     EstimateOperationEffectArea, OperationEffectArea, PointsOperationEffect,
 };
+use crate::operations::recall_ops::{RecallEstimationRequest, SegmentRecall};
 use crate::operations::types::{
     CollectionError, CollectionInfo, CollectionResult, CountRequest, CountResult, PointRequest,
     Record, SearchRequestBatch, UpdateResult,
 };
+use crate::operations::vacuum_ops::SegmentDeletedInfo;
 use crate::operations::CollectionUpdateOperations;
 use crate::shards::local_shard::LocalShard;
+use crate::shards::shard::ShardId;
 use crate::shards::shard_trait::ShardOperation;
-use crate::shards::telemetry::LocalShardTelemetry;
+use crate::shards::telemetry::{LocalShardTelemetry, WalTelemetry};
 use crate::update_handler::UpdateSignal;
 
 type ChangedPointsSet = Arc<RwLock<HashSet<PointIdType>>>;
@@ -76,6 +86,10 @@ impl ProxyShard {
         self.wrapped_shard.on_optimizer_config_update().await
     }
 
+    pub async fn on_vectors_added(&self) -> CollectionResult<()> {
+        self.wrapped_shard.on_vectors_added().await
+    }
+
     pub async fn reinit_changelog(&self) -> CollectionResult<()> {
         // Blocks updates in the wrapped shard.
         let mut changed_points_guard = self.changed_points.write().await;
@@ -123,6 +137,132 @@ impl ProxyShard {
     pub fn get_telemetry_data(&self) -> LocalShardTelemetry {
         self.wrapped_shard.get_telemetry_data()
     }
+
+    pub async fn estimate_recall(
+        &self,
+        shard_id: ShardId,
+        vector_name: &str,
+        request: &RecallEstimationRequest,
+        search_runtime_handle: &Handle,
+    ) -> CollectionResult<Vec<SegmentRecall>> {
+        self.wrapped_shard
+            .estimate_recall(shard_id, vector_name, request, search_runtime_handle)
+            .await
+    }
+
+    pub fn benchmark_scoring(
+        &self,
+        vector_name: &str,
+        points_limit: usize,
+    ) -> CollectionResult<Vec<ScoringMeasurement>> {
+        self.wrapped_shard
+            .benchmark_scoring(vector_name, points_limit)
+    }
+
+    pub fn indexed_fields(&self) -> HashMap<PayloadKeyType, PayloadFieldSchema> {
+        self.wrapped_shard.indexed_fields()
+    }
+
+    pub fn estimate_segments_cardinality(
+        &self,
+        filter: Option<&Filter>,
+    ) -> Vec<SegmentCardinalityEstimation> {
+        self.wrapped_shard.estimate_segments_cardinality(filter)
+    }
+
+    pub fn term_counts(
+        &self,
+        field_name: PayloadKeyTypeRef,
+        filter: Option<&Filter>,
+    ) -> Option<HashMap<String, usize>> {
+        self.wrapped_shard.term_counts(field_name, filter)
+    }
+
+    pub fn numeric_bounds(
+        &self,
+        field_name: PayloadKeyTypeRef,
+        filter: Option<&Filter>,
+    ) -> Option<Option<ValueBounds>> {
+        self.wrapped_shard.numeric_bounds(field_name, filter)
+    }
+
+    pub fn numeric_histogram(
+        &self,
+        field_name: PayloadKeyTypeRef,
+        buckets: &HistogramBuckets,
+        filter: Option<&Filter>,
+    ) -> Option<Vec<usize>> {
+        self.wrapped_shard
+            .numeric_histogram(field_name, buckets, filter)
+    }
+
+    pub fn nearest_points(
+        &self,
+        field_name: PayloadKeyTypeRef,
+        origin: &GeoPoint,
+        limit: usize,
+        filter: Option<&Filter>,
+    ) -> Option<Vec<(PointIdType, f64)>> {
+        self.wrapped_shard
+            .nearest_points(field_name, origin, limit, filter)
+    }
+
+    pub fn ordered_points(
+        &self,
+        field_name: PayloadKeyTypeRef,
+        direction: Direction,
+        start_from: Option<f64>,
+        offset: Option<PointIdType>,
+        limit: usize,
+        filter: Option<&Filter>,
+    ) -> Option<Vec<(PointIdType, f64)>> {
+        self.wrapped_shard
+            .ordered_points(field_name, direction, start_from, offset, limit, filter)
+    }
+
+    pub fn id_chunks(&self, chunks: usize) -> Vec<IdChunk> {
+        self.wrapped_shard.id_chunks(chunks)
+    }
+
+    pub fn point_versions(&self, ids: &[PointIdType]) -> HashMap<PointIdType, SeqNumberType> {
+        self.wrapped_shard.point_versions(ids)
+    }
+
+    pub async fn wait_applied(
+        &self,
+        version: SeqNumberType,
+        timeout: Duration,
+    ) -> CollectionResult<()> {
+        self.wrapped_shard.wait_applied(version, timeout).await
+    }
+
+    pub async fn vacuum(
+        &self,
+        shard_id: ShardId,
+        deleted_threshold: f64,
+    ) -> CollectionResult<(Vec<SegmentDeletedInfo>, usize)> {
+        self.wrapped_shard.vacuum(shard_id, deleted_threshold).await
+    }
+
+    pub async fn flush(&self) -> CollectionResult<SeqNumberType> {
+        self.wrapped_shard.flush().await
+    }
+
+    pub fn original_segments(&self) -> Vec<LockedSegment> {
+        self.wrapped_shard.original_segments()
+    }
+
+    pub async fn memory_usage(&self) -> CollectionResult<MemoryUsage> {
+        self.wrapped_shard.memory_usage().await
+    }
+
+    pub async fn truncate_wal(&self) -> CollectionResult<(SeqNumberType, WalTelemetry)> {
+        self.wrapped_shard.truncate_wal().await
+    }
+
+    pub async fn orphaned_files(&self) -> CollectionResult<Option<Vec<PathBuf>>> {
+        self.wrapped_shard.orphaned_files().await
+    }
 }
 
 #[async_trait]
@@ -144,7 +284,7 @@ impl ShardOperation for ProxyShard {
                 if cardinality.max > MAX_CHANGES_TRACKED_COUNT {
                     PointsOperationEffect::Many
                 } else {
-                    let points = local_shard.read_filtered(Some(&filter))?;
+                    let points = local_shard.read_filtered(Some(&filter), None)?;
                     PointsOperationEffect::Some(points.into_iter().collect())
                 }
             }
@@ -179,10 +319,20 @@ impl ShardOperation for ProxyShard {
         with_payload_interface: &WithPayloadInterface,
         with_vector: &WithVector,
         filter: Option<&Filter>,
+        sample_seed: Option<u64>,
+        planner_hint: Option<PlannerHint>,
     ) -> CollectionResult<Vec<Record>> {
         let local_shard = &self.wrapped_shard;
         local_shard
-            .scroll_by(offset, limit, with_payload_interface, with_vector, filter)
+            .scroll_by(
+                offset,
+                limit,
+                with_payload_interface,
+                with_vector,
+                filter,
+                sample_seed,
+                planner_hint,
+            )
             .await
     }
 
@@ -1,13 +1,16 @@
+use std::collections::HashMap;
 use std::future::Future;
 use std::path::Path;
 use std::sync::Arc;
+use std::time::Duration;
 
 use api::grpc::qdrant::collections_internal_client::CollectionsInternalClient;
 use api::grpc::qdrant::points_internal_client::PointsInternalClient;
 use api::grpc::qdrant::{
     CollectionOperationResponse, CountPoints, CountPointsInternal, GetCollectionInfoRequest,
-    GetCollectionInfoRequestInternal, GetPoints, GetPointsInternal, InitiateShardTransferRequest,
-    ScrollPoints, ScrollPointsInternal, SearchBatchPointsInternal,
+    GetCollectionInfoRequestInternal, GetPoints, GetPointsInternal, IdChunksInternal,
+    InitiateShardTransferRequest, PointVersionsInternal, ScrollPoints, ScrollPointsInternal,
+    SearchBatchPointsInternal, WaitAppliedInternal,
 };
 use async_trait::async_trait;
 use parking_lot::Mutex;
@@ -15,13 +18,15 @@ use segment::common::operation_time_statistics::{
     OperationDurationsAggregator, ScopeDurationMeasurer,
 };
 use segment::types::{
-    ExtendedPointId, Filter, ScoredPoint, WithPayload, WithPayloadInterface, WithVector,
+    ExtendedPointId, Filter, PlannerHint, PointIdType, ScoredPoint, SeqNumberType, WithPayload,
+    WithPayloadInterface, WithVector,
 };
 use tokio::runtime::Handle;
 use tonic::transport::{Channel, Uri};
 use tonic::Status;
 
 use crate::operations::conversions::try_record_from_grpc;
+use crate::operations::id_range_ops::IdChunk;
 use crate::operations::payload_ops::PayloadOps;
 use crate::operations::point_ops::{PointOperations, WriteOrdering};
 use crate::operations::types::{
@@ -34,7 +39,7 @@ use crate::shards::conversions::{
     internal_clear_payload, internal_clear_payload_by_filter, internal_create_index,
     internal_delete_index, internal_delete_payload, internal_delete_points,
     internal_delete_points_by_filter, internal_set_payload, internal_sync_points,
-    internal_upsert_points, try_scored_point_from_grpc,
+    internal_update_vectors, internal_upsert_points, try_scored_point_from_grpc,
 };
 use crate::shards::shard::{PeerId, ShardId};
 use crate::shards::shard_trait::ShardOperation;
@@ -139,6 +144,80 @@ impl RemoteShard {
         Ok(res)
     }
 
+    /// Chunks of consecutive point ids of the replica on the remote peer
+    pub async fn id_chunks(&self, chunks: usize) -> CollectionResult<Vec<IdChunk>> {
+        let request = &IdChunksInternal {
+            collection_name: self.collection_id.clone(),
+            chunks: chunks as u32,
+            shard_id: Some(self.id),
+        };
+        let response = self
+            .with_points_client(|mut client| async move {
+                client.id_chunks(tonic::Request::new(request.clone())).await
+            })
+            .await?
+            .into_inner();
+        let chunks = response
+            .result
+            .into_iter()
+            .map(IdChunk::try_from)
+            .collect::<Result<_, Status>>()?;
+        Ok(chunks)
+    }
+
+    /// Versions of the points, which exist in the replica on the remote peer, among the given ids
+    pub async fn point_versions(
+        &self,
+        ids: &[PointIdType],
+    ) -> CollectionResult<HashMap<PointIdType, SeqNumberType>> {
+        let request = &PointVersionsInternal {
+            collection_name: self.collection_id.clone(),
+            ids: ids.iter().map(|id| (*id).into()).collect(),
+            shard_id: Some(self.id),
+        };
+        let response = self
+            .with_points_client(|mut client| async move {
+                client
+                    .point_versions(tonic::Request::new(request.clone()))
+                    .await
+            })
+            .await?
+            .into_inner();
+        let versions = response
+            .result
+            .into_iter()
+            .map(|point_version| {
+                let id = point_version.id.ok_or_else(|| {
+                    Status::invalid_argument("Malformed PointVersion: id is missing")
+                })?;
+                let id: PointIdType = id.try_into()?;
+                Ok((id, point_version.version))
+            })
+            .collect::<Result<_, Status>>()?;
+        Ok(versions)
+    }
+
+    /// Wait until the replica on the remote peer applies the operation with the given number
+    pub async fn wait_applied(
+        &self,
+        version: SeqNumberType,
+        timeout: Duration,
+    ) -> CollectionResult<()> {
+        let request = &WaitAppliedInternal {
+            collection_name: self.collection_id.clone(),
+            version,
+            timeout: timeout.as_secs(),
+            shard_id: Some(self.id),
+        };
+        self.with_points_client(|mut client| async move {
+            client
+                .wait_applied(tonic::Request::new(request.clone()))
+                .await
+        })
+        .await?;
+        Ok(())
+    }
+
     pub async fn forward_update(
         &self,
         operation: CollectionUpdateOperations,
@@ -220,6 +299,17 @@ impl RemoteShard {
                     .await?
                     .into_inner()
                 }
+                PointOperations::UpdateVectors { points } => {
+                    let request =
+                        &internal_update_vectors(shard_id, collection_name, points, wait, ordering);
+                    self.with_points_client(|mut client| async move {
+                        client
+                            .update_vectors(tonic::Request::new(request.clone()))
+                            .await
+                    })
+                    .await?
+                    .into_inner()
+                }
             },
             CollectionUpdateOperations::PayloadOperation(payload_ops) => match payload_ops {
                 PayloadOps::SetPayload(set_payload) => {
@@ -367,7 +457,13 @@ impl ShardOperation for RemoteShard {
         with_payload_interface: &WithPayloadInterface,
         with_vector: &WithVector,
         filter: Option<&Filter>,
+        sample_seed: Option<u64>,
+        planner_hint: Option<PlannerHint>,
     ) -> CollectionResult<Vec<Record>> {
+        let order = match sample_seed {
+            None => api::grpc::qdrant::ScrollOrder::Id,
+            Some(_) => api::grpc::qdrant::ScrollOrder::Random,
+        };
         let scroll_points = ScrollPoints {
             collection_name: self.collection_id.clone(),
             filter: filter.map(|f| f.clone().into()),
@@ -376,6 +472,13 @@ impl ShardOperation for RemoteShard {
             with_payload: Some(with_payload_interface.clone().into()),
             with_vectors: Some(with_vector.clone().into()),
             read_consistency: None,
+            order: Some(order as i32),
+            seed: sample_seed,
+            planner_hint: planner_hint
+                .map(|hint| api::grpc::qdrant::PlannerHint::from(hint) as i32),
+            geo_order: None,
+            order_by: None,
+            timeout: None,
         };
         let request = &ScrollPointsInternal {
             scroll_points: Some(scroll_points),
@@ -474,6 +577,10 @@ impl ShardOperation for RemoteShard {
             collection_name: self.collection_id.clone(),
             filter: request.filter.clone().map(|f| f.into()),
             exact: Some(request.exact),
+            planner_hint: request
+                .planner_hint
+                .map(|hint| api::grpc::qdrant::PlannerHint::from(hint) as i32),
+            distinct: request.distinct.clone(),
         };
 
         let request = &CountPointsInternal {
@@ -492,7 +599,7 @@ impl ShardOperation for RemoteShard {
                     "Unexpected empty CountResult".to_string(),
                 ))
             },
-            |count_result| Ok(count_result.into()),
+            |count_result| Ok(count_result.try_into()?),
         )
     }
 
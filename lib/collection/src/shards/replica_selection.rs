@@ -0,0 +1,153 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use parking_lot::Mutex;
+use rand::seq::SliceRandom;
+
+use crate::operations::types::ReadPreference;
+use crate::shards::shard::PeerId;
+
+/// Weight of the latest observation in the moving average of the replica latency
+const LATENCY_SMOOTHING: f64 = 0.2;
+
+#[derive(Debug, Default, Clone, Copy)]
+struct ReplicaReadStats {
+    /// Exponential moving average of successful read latency
+    latency: Option<Duration>,
+    /// Number of reads failed in a row, reset by the first successful read
+    consecutive_failures: usize,
+}
+
+impl ReplicaReadStats {
+    fn is_healthy(&self) -> bool {
+        self.consecutive_failures == 0
+    }
+}
+
+/// Selects the order, in which replicas of the shard are queried by read operations.
+/// Tracks health and latency of reads from each replica, observed by this peer.
+#[derive(Debug, Default)]
+pub struct ReplicaSelector {
+    stats: Mutex<HashMap<PeerId, ReplicaReadStats>>,
+    round_robin_counter: AtomicUsize,
+}
+
+impl ReplicaSelector {
+    pub fn record_success(&self, peer_id: PeerId, latency: Duration) {
+        let mut stats = self.stats.lock();
+        let peer_stats = stats.entry(peer_id).or_default();
+        peer_stats.consecutive_failures = 0;
+        peer_stats.latency = Some(match peer_stats.latency {
+            None => latency,
+            Some(average) => {
+                average.mul_f64(1.0 - LATENCY_SMOOTHING) + latency.mul_f64(LATENCY_SMOOTHING)
+            }
+        });
+    }
+
+    pub fn record_failure(&self, peer_id: PeerId) {
+        let mut stats = self.stats.lock();
+        stats.entry(peer_id).or_default().consecutive_failures += 1;
+    }
+
+    /// Order replicas according to the read preference.
+    /// Replicas, which failed the last read, are moved to the end regardless of the preference,
+    /// except for the leader in `LeaderOnly` mode.
+    ///
+    /// `leader` - replica, designated for `LeaderOnly` reads
+    pub fn order_replicas<T>(
+        &self,
+        preference: ReadPreference,
+        this_peer_id: PeerId,
+        leader: Option<PeerId>,
+        replicas: &mut [(PeerId, T)],
+    ) {
+        if replicas.is_empty() {
+            return;
+        }
+
+        replicas.shuffle(&mut rand::thread_rng());
+
+        let stats = self.stats.lock();
+        let peer_stats = |peer_id: &PeerId| stats.get(peer_id).copied().unwrap_or_default();
+
+        match preference {
+            ReadPreference::PreferLocal | ReadPreference::LeaderOnly => {
+                replicas.sort_by_key(|(peer_id, _)| *peer_id != this_peer_id);
+            }
+            ReadPreference::RoundRobin => {
+                replicas.sort_by_key(|(peer_id, _)| *peer_id);
+                let shift = self.round_robin_counter.fetch_add(1, Ordering::Relaxed);
+                replicas.rotate_left(shift % replicas.len());
+            }
+            ReadPreference::LowestLatency => {
+                // Replicas without measurements go first, so each of them is probed eventually
+                replicas
+                    .sort_by_key(|(peer_id, _)| peer_stats(peer_id).latency.unwrap_or_default());
+            }
+        }
+
+        replicas.sort_by_key(|(peer_id, _)| !peer_stats(peer_id).is_healthy());
+
+        if preference == ReadPreference::LeaderOnly {
+            replicas.sort_by_key(|(peer_id, _)| Some(*peer_id) != leader);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ordered(
+        selector: &ReplicaSelector,
+        preference: ReadPreference,
+        leader: Option<PeerId>,
+    ) -> Vec<PeerId> {
+        let mut replicas: Vec<_> = (1..=4).map(|peer_id| (peer_id, ())).collect();
+        selector.order_replicas(preference, 1, leader, &mut replicas);
+        replicas.into_iter().map(|(peer_id, _)| peer_id).collect()
+    }
+
+    #[test]
+    fn test_replica_ordering() {
+        let selector = ReplicaSelector::default();
+
+        assert_eq!(ordered(&selector, ReadPreference::PreferLocal, None)[0], 1);
+        assert_eq!(
+            ordered(&selector, ReadPreference::LeaderOnly, Some(4))[0],
+            4
+        );
+
+        let first = ordered(&selector, ReadPreference::RoundRobin, None);
+        let second = ordered(&selector, ReadPreference::RoundRobin, None);
+        assert_eq!(first, vec![1, 2, 3, 4]);
+        assert_eq!(second, vec![2, 3, 4, 1]);
+
+        for peer_id in 1..=4 {
+            selector.record_success(peer_id, Duration::from_millis(10 * (5 - peer_id)));
+        }
+        assert_eq!(
+            ordered(&selector, ReadPreference::LowestLatency, None),
+            vec![4, 3, 2, 1],
+        );
+
+        selector.record_failure(4);
+        assert_eq!(
+            ordered(&selector, ReadPreference::LowestLatency, None),
+            vec![3, 2, 1, 4],
+        );
+        assert_eq!(ordered(&selector, ReadPreference::PreferLocal, None)[3], 4);
+        assert_eq!(
+            ordered(&selector, ReadPreference::LeaderOnly, Some(4))[0],
+            4
+        );
+
+        selector.record_success(4, Duration::from_millis(10));
+        assert_eq!(
+            ordered(&selector, ReadPreference::LowestLatency, None)[0],
+            4
+        );
+    }
+}
@@ -1,22 +1,27 @@
 use std::cmp;
 use std::cmp::max;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::future::Future;
 use std::ops::Deref;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
-use futures::future::{join, join_all};
+use futures::future::{join, join_all, try_join_all};
 use futures::stream::FuturesUnordered;
-use futures::{FutureExt, StreamExt};
+use futures::StreamExt;
 use itertools::Itertools;
 use rand::seq::SliceRandom;
 use schemars::JsonSchema;
+use segment::common::log_context::{with_log_context, LogContext};
+use segment::data_types::histogram::{HistogramBuckets, ValueBounds};
+use segment::data_types::order_by::Direction;
 use segment::types::{
-    ExtendedPointId, Filter, PointIdType, ScoredPoint, WithPayload, WithPayloadInterface,
-    WithVector,
+    ExtendedPointId, Filter, GeoPoint, MemoryUsage, PayloadFieldSchema, PayloadKeyType,
+    PayloadKeyTypeRef, PlannerHint, PointIdType, ScoredPoint, SeqNumberType, WithPayload,
+    WithPayloadInterface, WithVector,
 };
+use segment::vector_storage::scoring_benchmark::ScoringMeasurement;
 use serde::{Deserialize, Serialize};
 use tokio::runtime::Handle;
 use tokio::sync::{Mutex, RwLock};
@@ -25,18 +30,27 @@ use super::local_shard::LocalShard;
 use super::remote_shard::RemoteShard;
 use super::resolve::{Resolve, ResolveCondition};
 use super::{create_shard_dir, CollectionId};
+use crate::collection_manager::holders::segment_holder::LockedSegment;
 use crate::config::CollectionConfig;
-use crate::operations::consistency_params::{ReadConsistency, ReadConsistencyType};
+use crate::operations::cardinality_ops::SegmentCardinalityEstimation;
+use crate::operations::consistency_params::{
+    ConsistencyToken, ReadConsistency, ReadConsistencyType,
+};
+use crate::operations::id_range_ops::IdChunk;
 use crate::operations::point_ops::WriteOrdering;
+use crate::operations::recall_ops::{RecallEstimationRequest, SegmentRecall};
 use crate::operations::shared_storage_config::SharedStorageConfig;
 use crate::operations::types::{
     CollectionError, CollectionInfo, CollectionResult, CountRequest, CountResult, PointRequest,
-    Record, SearchRequestBatch, UpdateResult,
+    ReadPreference, Record, ReplicaWriteFailure, ReplicationReport, SearchRequestBatch,
+    ShardFlushResult, ShardWalTruncateResult, UpdateResult,
 };
+use crate::operations::vacuum_ops::SegmentDeletedInfo;
 use crate::operations::CollectionUpdateOperations;
 use crate::save_on_disk::SaveOnDisk;
 use crate::shards::channel_service::ChannelService;
 use crate::shards::forward_proxy_shard::ForwardProxyShard;
+use crate::shards::replica_selection::ReplicaSelector;
 use crate::shards::shard::Shard::{ForwardProxy, Local};
 use crate::shards::shard::{PeerId, Shard, ShardId};
 use crate::shards::shard_config::ShardConfig;
@@ -144,7 +158,7 @@ impl ReplicaSetState {
 
 /// A set of shard replicas.
 /// Handles operations so that the state is consistent across all the replicas of the shard.
-/// Prefers local shard for read-only operations.
+/// Selects replicas for read-only operations according to the read preference of the node.
 /// Perform updates on all replicas and report error if there is at least one failure.
 ///
 pub struct ShardReplicaSet {
@@ -169,6 +183,8 @@ pub struct ShardReplicaSet {
     update_runtime: Handle,
     /// Lock to serialized write operations on the replicaset when a write ordering is used.
     write_ordering_lock: Mutex<()>,
+    /// Order of replicas for read operations, based on the read preference and replica health
+    replica_selector: ReplicaSelector,
 }
 
 impl ShardReplicaSet {
@@ -189,6 +205,11 @@ impl ShardReplicaSet {
         self.replica_state.read().this_peer_id
     }
 
+    /// Context of log lines, emitted while operating on the shard
+    pub fn log_context(&self) -> LogContext {
+        LogContext::new(&self.collection_id, Some(self.shard_id))
+    }
+
     pub fn highest_replica_peer_id(&self) -> Option<PeerId> {
         self.replica_state.read().peers.keys().max().cloned()
     }
@@ -221,6 +242,18 @@ impl ShardReplicaSet {
             .collect()
     }
 
+    /// Check that the replica on the given remote peer responds and serves the shard
+    pub async fn check_remote_replica(&self, peer_id: PeerId) -> CollectionResult<()> {
+        let remotes = self.remotes.read().await;
+        match remotes.iter().find(|remote| remote.peer_id == peer_id) {
+            Some(remote) => remote.info().await.map(|_| ()),
+            None => Err(CollectionError::service_error(format!(
+                "Peer {peer_id} has no replica of shard {}",
+                self.shard_id
+            ))),
+        }
+    }
+
     fn init_remote_shards(
         shard_id: ShardId,
         collection_id: CollectionId,
@@ -313,6 +346,7 @@ impl ShardReplicaSet {
             shared_storage_config,
             update_runtime,
             write_ordering_lock: Mutex::new(()),
+            replica_selector: Default::default(),
         })
     }
 
@@ -507,6 +541,7 @@ impl ShardReplicaSet {
             shared_storage_config,
             update_runtime,
             write_ordering_lock: Mutex::new(()),
+            replica_selector: Default::default(),
         }
     }
 
@@ -663,6 +698,23 @@ impl ShardReplicaSet {
         local: &'a Option<Shard>,
         remotes: &'a [RemoteShard],
     ) -> CollectionResult<Res>
+    where
+        F: Fn(&'a (dyn ShardOperation + Send + Sync)) -> Fut,
+        Fut: Future<Output = CollectionResult<Res>>,
+    {
+        with_log_context(
+            Some(self.log_context()),
+            self.execute_read_operation_in_context(read_operation, local, remotes),
+        )
+        .await
+    }
+
+    async fn execute_read_operation_in_context<'a, F, Fut, Res>(
+        &self,
+        read_operation: F,
+        local: &'a Option<Shard>,
+        remotes: &'a [RemoteShard],
+    ) -> CollectionResult<Res>
     where
         F: Fn(&'a (dyn ShardOperation + Send + Sync)) -> Fut,
         Fut: Future<Output = CollectionResult<Res>>,
@@ -770,6 +822,9 @@ impl ShardReplicaSet {
         captured_error.expect("at this point `captured_error` must be defined by construction")
     }
 
+    /// Execute read op. on replicas in order, selected by the read preference,
+    /// until enough responses are collected to satisfy the read consistency.
+    /// Latency and failures of each replica are tracked to inform the following selections.
     pub async fn execute_and_resolve_read_operation<'a, F, Fut, Res>(
         &self,
         read_operation: F,
@@ -777,6 +832,30 @@ impl ShardReplicaSet {
         remotes: &'a [RemoteShard],
         read_consistency: ReadConsistency,
     ) -> CollectionResult<Res>
+    where
+        F: Fn(&'a (dyn ShardOperation + Send + Sync)) -> Fut,
+        Fut: Future<Output = CollectionResult<Res>>,
+        Res: Resolve,
+    {
+        with_log_context(
+            Some(self.log_context()),
+            self.execute_and_resolve_read_operation_in_context(
+                read_operation,
+                local,
+                remotes,
+                read_consistency,
+            ),
+        )
+        .await
+    }
+
+    async fn execute_and_resolve_read_operation_in_context<'a, F, Fut, Res>(
+        &self,
+        read_operation: F,
+        local: &'a Option<Shard>,
+        remotes: &'a [RemoteShard],
+        read_consistency: ReadConsistency,
+    ) -> CollectionResult<Res>
     where
         F: Fn(&'a (dyn ShardOperation + Send + Sync)) -> Fut,
         Fut: Future<Output = CollectionResult<Res>>,
@@ -823,22 +902,48 @@ impl ShardReplicaSet {
             )));
         }
 
-        let mut active_remotes: Vec<_> = active_remotes_iter.collect();
-        active_remotes.shuffle(&mut rand::thread_rng());
+        let this_peer_id = self.this_peer_id();
+        let read_preference = self.shared_storage_config.read_preference;
 
-        let local_operations = active_local
+        let mut replicas: Vec<(PeerId, &'a (dyn ShardOperation + Send + Sync))> = active_local
             .into_iter()
-            .map(|local| read_operation(local.get()).left_future());
+            .map(|local| (this_peer_id, local.get()))
+            .chain(active_remotes_iter.map(|remote| (remote.peer_id, remote as _)))
+            .collect();
 
-        let remote_operations = active_remotes
-            .into_iter()
-            .map(|remote| read_operation(remote).right_future());
+        let leader = self.highest_alive_replica_peer_id();
+
+        self.replica_selector
+            .order_replicas(read_preference, this_peer_id, leader, &mut replicas);
 
-        let mut operations = local_operations.chain(remote_operations);
+        if read_preference == ReadPreference::LeaderOnly && factor == 1 {
+            // Other replicas are only queried, if read consistency requires multiple responses
+            replicas.retain(|(peer_id, _)| Some(*peer_id) == leader);
 
-        let required_reads = if active_local_count > 0 {
-            // If there is a local shard, we can ignore fan-out `read_remote_replicas` param,
-            // as we already know that the local peer is working.
+            if replicas.is_empty() {
+                return Err(CollectionError::service_error(format!(
+                    "The replica set for shard {} on peer {} does not have an active leader replica",
+                    self.shard_id, this_peer_id,
+                )));
+            }
+        }
+
+        let local_is_first = replicas
+            .first()
+            .map_or(false, |(peer_id, _)| *peer_id == this_peer_id);
+
+        let mut operations = replicas.into_iter().map(|(peer_id, replica)| {
+            let operation = read_operation(replica);
+            async move {
+                let start = Instant::now();
+                let result = operation.await;
+                (peer_id, start.elapsed(), result)
+            }
+        });
+
+        let required_reads = if local_is_first {
+            // If the local shard is queried first, we can ignore fan-out `read_remote_replicas`
+            // param, as we already know that the local peer is working.
             factor
         } else {
             max(factor, usize::try_from(self.read_remote_replicas).unwrap())
@@ -849,9 +954,12 @@ impl ShardReplicaSet {
 
         let mut responses = Vec::new();
 
-        while let Some(result) = pending_operations.next().await {
+        while let Some((peer_id, latency, result)) = pending_operations.next().await {
             match result {
-                Ok(resp) => responses.push(resp),
+                Ok(resp) => {
+                    self.replica_selector.record_success(peer_id, latency);
+                    responses.push(resp);
+                }
 
                 Err(err) => {
                     let is_transient = matches!(
@@ -860,6 +968,7 @@ impl ShardReplicaSet {
                     );
 
                     if is_transient {
+                        self.replica_selector.record_failure(peer_id);
                         log::debug!("Read operation failed: {err}");
                     } else {
                         return Err(err);
@@ -905,6 +1014,15 @@ impl ShardReplicaSet {
         }
     }
 
+    pub(crate) async fn on_vectors_added(&self) -> CollectionResult<()> {
+        let read_local = self.local.read().await;
+        if let Some(shard) = &*read_local {
+            shard.on_vectors_added().await
+        } else {
+            Ok(())
+        }
+    }
+
     pub(crate) async fn before_drop(&mut self) {
         let mut write_local = self.local.write().await;
         if let Some(shard) = &mut *write_local {
@@ -912,6 +1030,360 @@ impl ShardReplicaSet {
         }
     }
 
+    /// Flush the local replica, if any
+    pub(crate) async fn flush(&self) -> CollectionResult<Option<ShardFlushResult>> {
+        let read_local = self.local.read().await;
+        match &*read_local {
+            Some(shard) => Ok(Some(ShardFlushResult {
+                shard_id: self.shard_id,
+                operation_id: shard.flush().await?,
+            })),
+            None => Ok(None),
+        }
+    }
+
+    /// Segments of the local replica, which are not being optimized at the moment
+    pub(crate) async fn local_original_segments(&self) -> Vec<LockedSegment> {
+        let read_local = self.local.read().await;
+        match &*read_local {
+            Some(shard) => shard.original_segments(),
+            None => vec![],
+        }
+    }
+
+    /// Estimate memory, occupied by the local replica, if any
+    pub(crate) async fn memory_usage(&self) -> CollectionResult<Option<MemoryUsage>> {
+        let read_local = self.local.read().await;
+        match &*read_local {
+            Some(shard) => Ok(Some(shard.memory_usage().await?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Truncate WAL of the local replica, if any
+    pub(crate) async fn truncate_wal(&self) -> CollectionResult<Option<ShardWalTruncateResult>> {
+        let read_local = self.local.read().await;
+        match &*read_local {
+            Some(shard) => {
+                let (operation_id, wal) = shard.truncate_wal().await?;
+                Ok(Some(ShardWalTruncateResult {
+                    shard_id: self.shard_id,
+                    operation_id,
+                    wal,
+                }))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Orphaned files of the local replica, if any.
+    /// Returns `None` if there is no local replica or orphaned files can't be determined now.
+    pub(crate) async fn orphaned_files(&self) -> CollectionResult<Option<Vec<PathBuf>>> {
+        let read_local = self.local.read().await;
+        match &*read_local {
+            Some(shard) => shard.orphaned_files().await,
+            None => Ok(None),
+        }
+    }
+
+    /// Vacuum segments of the local replica, if any
+    pub(crate) async fn vacuum(
+        &self,
+        deleted_threshold: f64,
+    ) -> CollectionResult<(Vec<SegmentDeletedInfo>, usize)> {
+        let read_local = self.local.read().await;
+        match &*read_local {
+            Some(shard) => shard.vacuum(self.shard_id, deleted_threshold).await,
+            None => Ok((vec![], 0)),
+        }
+    }
+
+    /// Estimate recall of the local replica, if any
+    pub(crate) async fn estimate_recall(
+        &self,
+        vector_name: &str,
+        request: &RecallEstimationRequest,
+        search_runtime_handle: &Handle,
+    ) -> CollectionResult<Vec<SegmentRecall>> {
+        let read_local = self.local.read().await;
+        match &*read_local {
+            Some(shard) => {
+                shard
+                    .estimate_recall(self.shard_id, vector_name, request, search_runtime_handle)
+                    .await
+            }
+            None => Ok(vec![]),
+        }
+    }
+
+    /// Measure scoring throughput of the local replica, if any
+    pub(crate) async fn benchmark_scoring(
+        &self,
+        vector_name: &str,
+        points_limit: usize,
+    ) -> CollectionResult<Vec<ScoringMeasurement>> {
+        let read_local = self.local.read().await;
+        match &*read_local {
+            Some(shard) => shard.benchmark_scoring(vector_name, points_limit),
+            None => Ok(vec![]),
+        }
+    }
+
+    /// Schema of payload fields, indexed in the local replica, if any
+    pub(crate) async fn local_indexed_fields(&self) -> HashMap<PayloadKeyType, PayloadFieldSchema> {
+        let read_local = self.local.read().await;
+        match &*read_local {
+            Some(shard) => shard.indexed_fields(),
+            None => HashMap::new(),
+        }
+    }
+
+    /// Cardinality estimations of segments of the local replica, if any
+    pub(crate) async fn local_cardinality_estimation(
+        &self,
+        filter: Option<&Filter>,
+    ) -> Option<Vec<SegmentCardinalityEstimation>> {
+        let read_local = self.local.read().await;
+        read_local
+            .as_ref()
+            .map(|shard| shard.estimate_segments_cardinality(filter))
+    }
+
+    /// Term counts of the field index in the local replica.
+    /// Returns `None` if the local replica has no keyword or full-text index of the field.
+    ///
+    /// Fails if there is no active local replica: terms are counted in the index directly.
+    pub(crate) async fn local_term_counts(
+        &self,
+        field_name: PayloadKeyTypeRef<'_>,
+        filter: Option<&Filter>,
+    ) -> CollectionResult<Option<HashMap<String, usize>>> {
+        let read_local = self.local.read().await;
+        match &*read_local {
+            Some(shard) if self.peer_is_active(&self.this_peer_id()) => {
+                Ok(shard.term_counts(field_name, filter))
+            }
+            _ => Err(CollectionError::service_error(format!(
+                "No active local replica of shard {} to count terms of payload field",
+                self.shard_id
+            ))),
+        }
+    }
+
+    /// Min and max values of the numeric field index in the local replica.
+    /// Returns `None` if the local replica has no integer or float index of the field.
+    ///
+    /// Fails if there is no active local replica: values are read from the index directly.
+    pub(crate) async fn local_numeric_bounds(
+        &self,
+        field_name: PayloadKeyTypeRef<'_>,
+        filter: Option<&Filter>,
+    ) -> CollectionResult<Option<Option<ValueBounds>>> {
+        let read_local = self.local.read().await;
+        match &*read_local {
+            Some(shard) if self.peer_is_active(&self.this_peer_id()) => {
+                Ok(shard.numeric_bounds(field_name, filter))
+            }
+            _ => Err(CollectionError::service_error(format!(
+                "No active local replica of shard {} to build a histogram of payload field",
+                self.shard_id
+            ))),
+        }
+    }
+
+    /// Histogram of the numeric field index in the local replica.
+    /// Returns `None` if the local replica has no integer or float index of the field.
+    ///
+    /// Fails if there is no active local replica: values are read from the index directly.
+    pub(crate) async fn local_numeric_histogram(
+        &self,
+        field_name: PayloadKeyTypeRef<'_>,
+        buckets: &HistogramBuckets,
+        filter: Option<&Filter>,
+    ) -> CollectionResult<Option<Vec<usize>>> {
+        let read_local = self.local.read().await;
+        match &*read_local {
+            Some(shard) if self.peer_is_active(&self.this_peer_id()) => {
+                Ok(shard.numeric_histogram(field_name, buckets, filter))
+            }
+            _ => Err(CollectionError::service_error(format!(
+                "No active local replica of shard {} to build a histogram of payload field",
+                self.shard_id
+            ))),
+        }
+    }
+
+    /// Points of the local replica nearest to the `origin` according to the geo index of the field.
+    /// Returns `None` if the local replica has no geo index of the field.
+    ///
+    /// Fails if there is no active local replica: ordering by geo distance reads the index directly.
+    pub(crate) async fn local_nearest_points(
+        &self,
+        field_name: PayloadKeyTypeRef<'_>,
+        origin: &GeoPoint,
+        limit: usize,
+        filter: Option<&Filter>,
+    ) -> CollectionResult<Option<Vec<(PointIdType, f64)>>> {
+        let read_local = self.local.read().await;
+        match &*read_local {
+            Some(shard) if self.peer_is_active(&self.this_peer_id()) => {
+                Ok(shard.nearest_points(field_name, origin, limit, filter))
+            }
+            _ => Err(CollectionError::service_error(format!(
+                "No active local replica of shard {} to order points by geo distance",
+                self.shard_id
+            ))),
+        }
+    }
+
+    /// Points of the local replica ordered by values of the integer or float index of the field.
+    /// Returns `None` if the local replica has no integer or float range index of the field.
+    ///
+    /// Fails if there is no active local replica: ordering by payload field reads the index directly.
+    pub(crate) async fn local_ordered_points(
+        &self,
+        field_name: PayloadKeyTypeRef<'_>,
+        direction: Direction,
+        start_from: Option<f64>,
+        offset: Option<PointIdType>,
+        limit: usize,
+        filter: Option<&Filter>,
+    ) -> CollectionResult<Option<Vec<(PointIdType, f64)>>> {
+        let read_local = self.local.read().await;
+        match &*read_local {
+            Some(shard) if self.peer_is_active(&self.this_peer_id()) => {
+                Ok(shard.ordered_points(field_name, direction, start_from, offset, limit, filter))
+            }
+            _ => Err(CollectionError::service_error(format!(
+                "No active local replica of shard {} to order points by payload field",
+                self.shard_id
+            ))),
+        }
+    }
+
+    /// Chunks of consecutive point ids of the local replica, if there is one
+    pub(crate) async fn local_id_chunks(&self, chunks: usize) -> Option<Vec<IdChunk>> {
+        let read_local = self.local.read().await;
+        read_local.as_ref().map(|shard| shard.id_chunks(chunks))
+    }
+
+    /// Chunks of consecutive point ids of the shard.
+    /// Read from the local replica, if it is active, otherwise from active remote replicas.
+    pub(crate) async fn id_chunks(&self, chunks: usize) -> CollectionResult<Vec<IdChunk>> {
+        if self.peer_is_active(&self.this_peer_id()) {
+            if let Some(local_chunks) = self.local_id_chunks(chunks).await {
+                return Ok(local_chunks);
+            }
+        }
+        let remotes = self.remotes.read().await;
+        let mut last_error = None;
+        for remote in remotes
+            .iter()
+            .filter(|remote| self.peer_is_active(&remote.peer_id))
+        {
+            match remote.id_chunks(chunks).await {
+                Ok(remote_chunks) => return Ok(remote_chunks),
+                Err(err) => last_error = Some(err),
+            }
+        }
+        Err(last_error.unwrap_or_else(|| {
+            CollectionError::service_error(format!(
+                "No active replica of shard {} to read point ids",
+                self.shard_id
+            ))
+        }))
+    }
+
+    /// Versions of the points of the local replica, if there is one, which exist among the given ids
+    pub(crate) async fn local_point_versions(
+        &self,
+        ids: &[PointIdType],
+    ) -> Option<HashMap<PointIdType, SeqNumberType>> {
+        let read_local = self.local.read().await;
+        read_local.as_ref().map(|shard| shard.point_versions(ids))
+    }
+
+    /// Versions of the points of the shard, which exist among the given ids.
+    /// Read from the local replica, if it is active, otherwise from active remote replicas.
+    pub(crate) async fn point_versions(
+        &self,
+        ids: &[PointIdType],
+    ) -> CollectionResult<HashMap<PointIdType, SeqNumberType>> {
+        if self.peer_is_active(&self.this_peer_id()) {
+            if let Some(local_versions) = self.local_point_versions(ids).await {
+                return Ok(local_versions);
+            }
+        }
+        let remotes = self.remotes.read().await;
+        let mut last_error = None;
+        for remote in remotes
+            .iter()
+            .filter(|remote| self.peer_is_active(&remote.peer_id))
+        {
+            match remote.point_versions(ids).await {
+                Ok(remote_versions) => return Ok(remote_versions),
+                Err(err) => last_error = Some(err),
+            }
+        }
+        Err(last_error.unwrap_or_else(|| {
+            CollectionError::service_error(format!(
+                "No active replica of shard {} to read point versions",
+                self.shard_id
+            ))
+        }))
+    }
+
+    /// Wait until the local replica, if there is one, applies the operation with the given number
+    pub(crate) async fn local_wait_applied(
+        &self,
+        version: SeqNumberType,
+        timeout: Duration,
+    ) -> CollectionResult<()> {
+        let read_local = self.local.read().await;
+        match &*read_local {
+            Some(shard) => shard.wait_applied(version, timeout).await,
+            None => Err(CollectionError::service_error(format!(
+                "Shard {} has no local replica",
+                self.shard_id
+            ))),
+        }
+    }
+
+    /// Wait until active replicas of the shard apply the writes of a consistency token.
+    /// `peer_versions` - number of the operation in the WAL of each replica, which acknowledged it
+    pub(crate) async fn wait_for_writes(
+        &self,
+        peer_versions: &BTreeMap<PeerId, SeqNumberType>,
+        timeout: Duration,
+    ) -> CollectionResult<()> {
+        let local = self.local.read().await;
+        let remotes = self.remotes.read().await;
+        let this_peer_id = self.this_peer_id();
+
+        let waits = peer_versions
+            .iter()
+            .filter(|(peer_id, _)| self.peer_is_active(peer_id))
+            .map(|(&peer_id, &version)| {
+                let local = &local;
+                let remotes = &remotes;
+                async move {
+                    if peer_id == this_peer_id {
+                        match local.as_ref() {
+                            Some(shard) => shard.wait_applied(version, timeout).await,
+                            None => Ok(()),
+                        }
+                    } else {
+                        match remotes.iter().find(|remote| remote.peer_id == peer_id) {
+                            Some(remote) => remote.wait_applied(version, timeout).await,
+                            None => Ok(()),
+                        }
+                    }
+                }
+            });
+        try_join_all(waits).await?;
+        Ok(())
+    }
+
     pub(crate) async fn get_telemetry_data(&self) -> ReplicaSetTelemetry {
         let local_shard = self.local.read().await;
         let local = local_shard
@@ -1150,12 +1622,15 @@ impl ShardReplicaSet {
         }
     }
 
+    /// Deactivate failed replicas. Returns deactivated peers and whether the update should wait
+    /// for their deactivation.
     fn handle_failed_replicas(
         &self,
         failures: &Vec<(PeerId, CollectionError)>,
         state: &ReplicaSetState,
-    ) -> bool {
+    ) -> (bool, Vec<PeerId>) {
         let mut wait_for_deactivation = false;
+        let mut deactivated = Vec::new();
         for (peer_id, err) in failures {
             log::warn!(
                 "Failed to update shard {}:{} on peer {}, error: {}",
@@ -1184,9 +1659,10 @@ impl ShardReplicaSet {
                 );
                 self.locally_disabled_peers.write().insert(*peer_id);
                 self.notify_peer_failure(*peer_id);
+                deactivated.push(*peer_id);
             }
         }
-        wait_for_deactivation
+        (wait_for_deactivation, deactivated)
     }
 
     // Make sure that locally disabled peers do not contradict the consensus
@@ -1223,6 +1699,19 @@ impl ShardReplicaSet {
         operation: CollectionUpdateOperations,
         wait: bool,
         ordering: WriteOrdering,
+    ) -> CollectionResult<UpdateResult> {
+        with_log_context(
+            Some(self.log_context()),
+            self.update_with_consistency_in_context(operation, wait, ordering),
+        )
+        .await
+    }
+
+    async fn update_with_consistency_in_context(
+        &self,
+        operation: CollectionUpdateOperations,
+        wait: bool,
+        ordering: WriteOrdering,
     ) -> CollectionResult<UpdateResult> {
         match self.leader_peer_for_update(ordering) {
             None => Err(CollectionError::service_error(format!(
@@ -1237,11 +1726,25 @@ impl ShardReplicaSet {
                         WriteOrdering::Weak => None, // no locking required
                         WriteOrdering::Medium | WriteOrdering::Strong => Some(self.write_ordering_lock.lock().await), // one request at a time
                     };
-                    self.update(operation, wait).await
+                    self.update(operation, wait, ordering).await
                 } else {
                     // forward the update to the designated leader
+                    let write_consistency_factor = self.write_consistency_factor().await;
                     self.forward_update(leader_peer, operation, wait, ordering)
                         .await
+                        .map(|mut result| {
+                            result.replication = Some(vec![ReplicationReport {
+                                shard_id: self.shard_id,
+                                ordering,
+                                leader_peer,
+                                forwarded: true,
+                                write_consistency_factor,
+                                acknowledged: vec![],
+                                failed: vec![],
+                                deactivated: vec![],
+                            }]);
+                            result
+                        })
                         .map_err(|err| {
                             match err {
                                 CollectionError::ServiceError { .. } | CollectionError::Cancelled { .. } => {
@@ -1294,10 +1797,22 @@ impl ShardReplicaSet {
         }
     }
 
+    async fn write_consistency_factor(&self) -> u32 {
+        self.collection_config
+            .read()
+            .await
+            .params
+            .write_consistency_factor
+            .get()
+    }
+
+    /// Send the update to all replicas, which can receive updates.
+    /// `ordering` is only used to report the write of the update.
     pub async fn update(
         &self,
         operation: CollectionUpdateOperations,
         wait: bool,
+        ordering: WriteOrdering,
     ) -> CollectionResult<UpdateResult> {
         let all_res: Vec<Result<_, _>> = {
             let local = self.local.read().await;
@@ -1328,6 +1843,7 @@ impl ShardReplicaSet {
                     remote
                         .update(op, wait)
                         .await
+                        .map(|res| (remote.peer_id, res))
                         .map_err(|err| (remote.peer_id, err))
                 });
             }
@@ -1346,6 +1862,7 @@ impl ShardReplicaSet {
                             .get()
                             .update(operation.clone(), local_wait)
                             .await
+                            .map(|res| (this_peer_id, res))
                             .map_err(|err| {
                                 let peer_id = err.remote_peer_id().unwrap_or(this_peer_id);
 
@@ -1356,7 +1873,7 @@ impl ShardReplicaSet {
 
                     // run local and remote shards read concurrently
                     let (mut remote_res, local_res): (
-                        Vec<Result<UpdateResult, (PeerId, CollectionError)>>,
+                        Vec<Result<(PeerId, UpdateResult), (PeerId, CollectionError)>>,
                         _,
                     ) = join(remote_updates, local_update).await;
                     // return both remote and local results
@@ -1375,63 +1892,97 @@ impl ShardReplicaSet {
         // 1. There is at least one success, otherwise it might be a problem of sending node
         // 2. ???
 
-        if !successes.is_empty() {
-            let wait_for_deactivation =
-                self.handle_failed_replicas(&failures, &self.replica_state.read());
-            // report all failing peers to consensus
-            if wait && wait_for_deactivation && !failures.is_empty() {
-                // ToDo: allow timeout configuration in API
-                let timeout = DEFAULT_SHARD_DEACTIVATION_TIMEOUT;
-
-                let replica_state = self.replica_state.clone();
-                let peer_ids: Vec<_> = failures.iter().map(|(peer_id, _)| *peer_id).collect();
-
-                let shards_disabled = tokio::task::spawn_blocking(move || {
-                    replica_state.wait_for(
-                        |state| {
-                            peer_ids.iter().all(|peer_id| {
-                                state
-                                    .peers
-                                    .get(peer_id)
-                                    .map(|state| state != &ReplicaState::Active)
-                                    .unwrap_or(true) // not found means that peer is dead
-                            })
-                        },
-                        DEFAULT_SHARD_DEACTIVATION_TIMEOUT,
-                    )
+        let (wait_for_deactivation, deactivated) = if successes.is_empty() {
+            (false, Vec::new())
+        } else {
+            self.handle_failed_replicas(&failures, &self.replica_state.read())
+        };
+
+        let report = ReplicationReport {
+            shard_id: self.shard_id,
+            ordering,
+            leader_peer: self.this_peer_id(),
+            forwarded: false,
+            write_consistency_factor: self.write_consistency_factor().await,
+            acknowledged: successes.iter().map(|(peer_id, _)| *peer_id).collect(),
+            failed: failures
+                .iter()
+                .map(|(peer_id, err)| ReplicaWriteFailure {
+                    peer_id: *peer_id,
+                    error: err.to_string(),
                 })
-                .await?;
+                .collect(),
+            deactivated,
+        };
+        if report.failed.is_empty() {
+            log::debug!(
+                "Update of shard {}:{} is written to replicas: {report:?}",
+                self.collection_id,
+                self.shard_id
+            );
+        } else {
+            log::warn!(
+                "Update of shard {}:{} is written to replicas with failures: {report:?}",
+                self.collection_id,
+                self.shard_id
+            );
+        }
 
-                if !shards_disabled {
-                    return Err(CollectionError::service_error(format!(
-                        "Some replica of shard {} failed to apply operation and deactivation \
-                         timed out after {} seconds. Consistency of this update is not guaranteed. Please retry.",
-                        self.shard_id, timeout.as_secs()
-                    )));
-                }
+        // report all failing peers to consensus
+        if wait && wait_for_deactivation && !failures.is_empty() {
+            // ToDo: allow timeout configuration in API
+            let timeout = DEFAULT_SHARD_DEACTIVATION_TIMEOUT;
+
+            let replica_state = self.replica_state.clone();
+            let peer_ids: Vec<_> = failures.iter().map(|(peer_id, _)| *peer_id).collect();
+
+            let shards_disabled = tokio::task::spawn_blocking(move || {
+                replica_state.wait_for(
+                    |state| {
+                        peer_ids.iter().all(|peer_id| {
+                            state
+                                .peers
+                                .get(peer_id)
+                                .map(|state| state != &ReplicaState::Active)
+                                .unwrap_or(true) // not found means that peer is dead
+                        })
+                    },
+                    DEFAULT_SHARD_DEACTIVATION_TIMEOUT,
+                )
+            })
+            .await?;
+
+            if !shards_disabled {
+                return Err(CollectionError::service_error(format!(
+                    "Some replica of shard {} failed to apply operation and deactivation \
+                     timed out after {} seconds. Consistency of this update is not guaranteed. Please retry.",
+                    self.shard_id, timeout.as_secs()
+                )));
             }
         }
 
         if !failures.is_empty() {
-            let write_consistency_factor = self
-                .collection_config
-                .read()
-                .await
-                .params
-                .write_consistency_factor
-                .get() as usize;
-            let minimal_success_count = write_consistency_factor.min(total_results);
+            let minimal_success_count =
+                (report.write_consistency_factor as usize).min(total_results);
             if successes.len() < minimal_success_count {
                 // completely failed - report error to user
                 let (_peer_id, err) = failures.into_iter().next().expect("failures is not empty");
                 return Err(err);
             }
         }
+        let consistency_token = ConsistencyToken::for_shard(
+            self.shard_id,
+            successes
+                .iter()
+                .map(|(peer_id, res)| (*peer_id, res.operation_id)),
+        );
         // there are enough successes, return the first one
-        let res = successes
+        let (_peer_id, mut res) = successes
             .into_iter()
             .next()
             .expect("successes is not empty");
+        res.replication = Some(vec![report]);
+        res.consistency_token = Some(consistency_token);
         Ok(res)
     }
 
@@ -1443,13 +1994,25 @@ impl ShardReplicaSet {
         with_payload_interface: &WithPayloadInterface,
         with_vector: &WithVector,
         filter: Option<&Filter>,
+        sample_seed: Option<u64>,
+        planner_hint: Option<PlannerHint>,
         read_consistency: Option<ReadConsistency>,
     ) -> CollectionResult<Vec<Record>> {
         let local = self.local.read().await;
         let remotes = self.remotes.read().await;
 
         self.execute_and_resolve_read_operation(
-            |shard| shard.scroll_by(offset, limit, with_payload_interface, with_vector, filter),
+            |shard| {
+                shard.scroll_by(
+                    offset,
+                    limit,
+                    with_payload_interface,
+                    with_vector,
+                    filter,
+                    sample_seed,
+                    planner_hint,
+                )
+            },
             &local,
             &remotes,
             read_consistency.unwrap_or_default(),
@@ -1543,13 +2106,15 @@ mod tests {
         indexing_threshold: 50_000,
         flush_interval_sec: 30,
         max_optimization_threads: 2,
+        target_segment_count: None,
+        max_segment_size_mb: None,
     };
 
     pub fn dummy_on_replica_failure() -> ChangePeerState {
         Arc::new(move |_peer_id, _shard_id| {})
     }
 
-    async fn new_shard_replica_set(collection_dir: &TempDir) -> ShardReplicaSet {
+    async fn new_shard_replica_set(collection_dir: &TempDir, local: bool) -> ShardReplicaSet {
         let update_runtime = Handle::current();
         let wal_config = WalConfig {
             wal_capacity_mb: 1,
@@ -1562,11 +2127,21 @@ mod tests {
                 distance: Distance::Dot,
                 hnsw_config: None,
                 quantization_config: None,
+                projection: None,
             }),
             shard_number: NonZeroU32::new(4).unwrap(),
             replication_factor: NonZeroU32::new(3).unwrap(),
             write_consistency_factor: NonZeroU32::new(2).unwrap(),
             on_disk_payload: false,
+            vector_aliases: Default::default(),
+            default_vector: None,
+            defrag_key: None,
+            deduplicate_upserts: false,
+            max_concurrent_searches: 0,
+            acl_key: None,
+            default_write_ordering: None,
+            default_read_consistency: None,
+            sparse_vectors: Default::default(),
         };
 
         let config = CollectionConfig {
@@ -1575,6 +2150,8 @@ mod tests {
             wal_config,
             hnsw_config: Default::default(),
             quantization_config: None,
+            lifecycle_config: None,
+            created_at: None,
         };
 
         let shared_config = Arc::new(RwLock::new(config.clone()));
@@ -1583,7 +2160,7 @@ mod tests {
             1,
             "test_collection".to_string(),
             1,
-            false,
+            local,
             remotes,
             dummy_on_replica_failure(),
             collection_dir.path(),
@@ -1599,7 +2176,7 @@ mod tests {
     #[tokio::test]
     async fn test_highest_replica_peer_id() {
         let collection_dir = Builder::new().prefix("test_collection").tempdir().unwrap();
-        let rs = new_shard_replica_set(&collection_dir).await;
+        let rs = new_shard_replica_set(&collection_dir, false).await;
 
         assert_eq!(rs.highest_replica_peer_id(), Some(5));
         // at build time the replicas are all dead, they need to be activated
@@ -1613,4 +2190,39 @@ mod tests {
         assert_eq!(rs.highest_replica_peer_id(), Some(5));
         assert_eq!(rs.highest_alive_replica_peer_id(), Some(4));
     }
+
+    #[tokio::test]
+    async fn test_read_without_consistency_stays_local() {
+        let collection_dir = Builder::new().prefix("test_collection").tempdir().unwrap();
+        let rs = new_shard_replica_set(&collection_dir, true).await;
+        rs.set_replica_state(&1, ReplicaState::Active).unwrap();
+        rs.collection_config
+            .write()
+            .await
+            .params
+            .default_read_consistency = Some(ReadConsistency::Type(ReadConsistencyType::All));
+
+        let request = Arc::new(PointRequest {
+            ids: vec![1.into()],
+            with_payload: None,
+            with_vector: false.into(),
+        });
+        let with_payload = WithPayload::from(false);
+        let with_vector = WithVector::from(false);
+
+        // Requests of other peers don't specify consistency and are served by the local replica,
+        // regardless of the default consistency of the collection
+        let records = rs
+            .retrieve(request.clone(), &with_payload, &with_vector, None)
+            .await
+            .unwrap();
+        assert!(records.is_empty());
+
+        // Remote replicas are not active, so reading from all replicas fails
+        let all = Some(ReadConsistency::Type(ReadConsistencyType::All));
+        assert!(rs
+            .retrieve(request, &with_payload, &with_vector, all)
+            .await
+            .is_err());
+    }
 }
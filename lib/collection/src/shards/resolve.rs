@@ -1,7 +1,9 @@
+use std::borrow::Cow;
 use std::collections::{HashMap, HashSet};
 use std::hash;
 
-use segment::types::{Payload, ScoredPoint};
+use segment::entry::entry_point::OperationResult;
+use segment::types::{Payload, RecordPayload, ScoredPoint};
 use tinyvec::TinyVec;
 
 use crate::operations::types::Record;
@@ -59,17 +61,32 @@ fn transpose<T>(vec: Vec<Vec<T>>) -> Vec<Vec<T>> {
 }
 
 fn record_eq(this: &Record, other: &Record) -> bool {
-    this.id == other.id && this.vector == other.vector && payload_eq(&this.payload, &other.payload)
+    if this.id != other.id || this.vector != other.vector {
+        return false;
+    }
+    // Payload, which can't be read, doesn't match anything
+    match (record_payload(this), record_payload(other)) {
+        (Ok(this), Ok(other)) => payload_eq(this.as_deref(), other.as_deref()),
+        _ => false,
+    }
+}
+
+fn record_payload(record: &Record) -> OperationResult<Option<Cow<Payload>>> {
+    record
+        .payload
+        .as_ref()
+        .map(RecordPayload::to_payload)
+        .transpose()
 }
 
 fn scored_point_eq(this: &ScoredPoint, other: &ScoredPoint) -> bool {
     this.id == other.id
         && this.score == other.score
         && this.vector == other.vector
-        && payload_eq(&this.payload, &other.payload)
+        && payload_eq(this.payload.as_ref(), other.payload.as_ref())
 }
 
-fn payload_eq(this: &Option<Payload>, other: &Option<Payload>) -> bool {
+fn payload_eq(this: Option<&Payload>, other: Option<&Payload>) -> bool {
     match (this, other) {
         (Some(payload), None) | (None, Some(payload)) => payload.is_empty(),
         (this, other) => this == other,
@@ -251,6 +268,8 @@ mod test {
             score,
             payload: None,
             vector: None,
+            highlights: None,
+            geo_distance: None,
         }
     }
 
@@ -1,12 +1,28 @@
 use core::marker::{Send, Sync};
-use std::path::Path;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
+use segment::data_types::histogram::{HistogramBuckets, ValueBounds};
+use segment::data_types::order_by::Direction;
+use segment::types::{
+    Filter, GeoPoint, MemoryUsage, PayloadFieldSchema, PayloadKeyType, PayloadKeyTypeRef,
+    PointIdType, SeqNumberType,
+};
+use segment::vector_storage::scoring_benchmark::ScoringMeasurement;
+use tokio::runtime::Handle;
+
+use crate::collection_manager::holders::segment_holder::LockedSegment;
+use crate::operations::cardinality_ops::SegmentCardinalityEstimation;
+use crate::operations::id_range_ops::IdChunk;
+use crate::operations::recall_ops::{RecallEstimationRequest, SegmentRecall};
 use crate::operations::types::CollectionResult;
+use crate::operations::vacuum_ops::SegmentDeletedInfo;
 use crate::shards::forward_proxy_shard::ForwardProxyShard;
 use crate::shards::local_shard::LocalShard;
 use crate::shards::proxy_shard::ProxyShard;
 use crate::shards::shard_trait::ShardOperation;
-use crate::shards::telemetry::LocalShardTelemetry;
+use crate::shards::telemetry::{LocalShardTelemetry, WalTelemetry};
 
 pub type ShardId = u32;
 
@@ -79,4 +95,234 @@ impl Shard {
             Shard::ForwardProxy(proxy_shard) => proxy_shard.on_optimizer_config_update().await,
         }
     }
+
+    pub async fn on_vectors_added(&self) -> CollectionResult<()> {
+        match self {
+            Shard::Local(local_shard) => local_shard.on_vectors_added().await,
+            Shard::Proxy(proxy_shard) => proxy_shard.on_vectors_added().await,
+            Shard::ForwardProxy(proxy_shard) => proxy_shard.on_vectors_added().await,
+        }
+    }
+
+    pub async fn estimate_recall(
+        &self,
+        shard_id: ShardId,
+        vector_name: &str,
+        request: &RecallEstimationRequest,
+        search_runtime_handle: &Handle,
+    ) -> CollectionResult<Vec<SegmentRecall>> {
+        match self {
+            Shard::Local(local_shard) => {
+                local_shard
+                    .estimate_recall(shard_id, vector_name, request, search_runtime_handle)
+                    .await
+            }
+            Shard::Proxy(proxy_shard) => {
+                proxy_shard
+                    .estimate_recall(shard_id, vector_name, request, search_runtime_handle)
+                    .await
+            }
+            Shard::ForwardProxy(proxy_shard) => {
+                proxy_shard
+                    .estimate_recall(shard_id, vector_name, request, search_runtime_handle)
+                    .await
+            }
+        }
+    }
+
+    pub fn benchmark_scoring(
+        &self,
+        vector_name: &str,
+        points_limit: usize,
+    ) -> CollectionResult<Vec<ScoringMeasurement>> {
+        match self {
+            Shard::Local(local_shard) => local_shard.benchmark_scoring(vector_name, points_limit),
+            Shard::Proxy(proxy_shard) => proxy_shard.benchmark_scoring(vector_name, points_limit),
+            Shard::ForwardProxy(proxy_shard) => {
+                proxy_shard.benchmark_scoring(vector_name, points_limit)
+            }
+        }
+    }
+
+    pub fn indexed_fields(&self) -> HashMap<PayloadKeyType, PayloadFieldSchema> {
+        match self {
+            Shard::Local(local_shard) => local_shard.indexed_fields(),
+            Shard::Proxy(proxy_shard) => proxy_shard.indexed_fields(),
+            Shard::ForwardProxy(proxy_shard) => proxy_shard.indexed_fields(),
+        }
+    }
+
+    pub fn estimate_segments_cardinality(
+        &self,
+        filter: Option<&Filter>,
+    ) -> Vec<SegmentCardinalityEstimation> {
+        match self {
+            Shard::Local(local_shard) => local_shard.estimate_segments_cardinality(filter),
+            Shard::Proxy(proxy_shard) => proxy_shard.estimate_segments_cardinality(filter),
+            Shard::ForwardProxy(proxy_shard) => proxy_shard.estimate_segments_cardinality(filter),
+        }
+    }
+
+    pub fn term_counts(
+        &self,
+        field_name: PayloadKeyTypeRef,
+        filter: Option<&Filter>,
+    ) -> Option<HashMap<String, usize>> {
+        match self {
+            Shard::Local(local_shard) => local_shard.term_counts(field_name, filter),
+            Shard::Proxy(proxy_shard) => proxy_shard.term_counts(field_name, filter),
+            Shard::ForwardProxy(proxy_shard) => proxy_shard.term_counts(field_name, filter),
+        }
+    }
+
+    pub fn numeric_bounds(
+        &self,
+        field_name: PayloadKeyTypeRef,
+        filter: Option<&Filter>,
+    ) -> Option<Option<ValueBounds>> {
+        match self {
+            Shard::Local(local_shard) => local_shard.numeric_bounds(field_name, filter),
+            Shard::Proxy(proxy_shard) => proxy_shard.numeric_bounds(field_name, filter),
+            Shard::ForwardProxy(proxy_shard) => proxy_shard.numeric_bounds(field_name, filter),
+        }
+    }
+
+    pub fn numeric_histogram(
+        &self,
+        field_name: PayloadKeyTypeRef,
+        buckets: &HistogramBuckets,
+        filter: Option<&Filter>,
+    ) -> Option<Vec<usize>> {
+        match self {
+            Shard::Local(local_shard) => local_shard.numeric_histogram(field_name, buckets, filter),
+            Shard::Proxy(proxy_shard) => proxy_shard.numeric_histogram(field_name, buckets, filter),
+            Shard::ForwardProxy(proxy_shard) => {
+                proxy_shard.numeric_histogram(field_name, buckets, filter)
+            }
+        }
+    }
+
+    pub fn nearest_points(
+        &self,
+        field_name: PayloadKeyTypeRef,
+        origin: &GeoPoint,
+        limit: usize,
+        filter: Option<&Filter>,
+    ) -> Option<Vec<(PointIdType, f64)>> {
+        match self {
+            Shard::Local(local_shard) => {
+                local_shard.nearest_points(field_name, origin, limit, filter)
+            }
+            Shard::Proxy(proxy_shard) => {
+                proxy_shard.nearest_points(field_name, origin, limit, filter)
+            }
+            Shard::ForwardProxy(proxy_shard) => {
+                proxy_shard.nearest_points(field_name, origin, limit, filter)
+            }
+        }
+    }
+
+    pub fn ordered_points(
+        &self,
+        field_name: PayloadKeyTypeRef,
+        direction: Direction,
+        start_from: Option<f64>,
+        offset: Option<PointIdType>,
+        limit: usize,
+        filter: Option<&Filter>,
+    ) -> Option<Vec<(PointIdType, f64)>> {
+        match self {
+            Shard::Local(local_shard) => {
+                local_shard.ordered_points(field_name, direction, start_from, offset, limit, filter)
+            }
+            Shard::Proxy(proxy_shard) => {
+                proxy_shard.ordered_points(field_name, direction, start_from, offset, limit, filter)
+            }
+            Shard::ForwardProxy(proxy_shard) => {
+                proxy_shard.ordered_points(field_name, direction, start_from, offset, limit, filter)
+            }
+        }
+    }
+
+    pub fn id_chunks(&self, chunks: usize) -> Vec<IdChunk> {
+        match self {
+            Shard::Local(local_shard) => local_shard.id_chunks(chunks),
+            Shard::Proxy(proxy_shard) => proxy_shard.id_chunks(chunks),
+            Shard::ForwardProxy(proxy_shard) => proxy_shard.id_chunks(chunks),
+        }
+    }
+
+    pub fn point_versions(&self, ids: &[PointIdType]) -> HashMap<PointIdType, SeqNumberType> {
+        match self {
+            Shard::Local(local_shard) => local_shard.point_versions(ids),
+            Shard::Proxy(proxy_shard) => proxy_shard.point_versions(ids),
+            Shard::ForwardProxy(proxy_shard) => proxy_shard.point_versions(ids),
+        }
+    }
+
+    pub async fn wait_applied(
+        &self,
+        version: SeqNumberType,
+        timeout: Duration,
+    ) -> CollectionResult<()> {
+        match self {
+            Shard::Local(local_shard) => local_shard.wait_applied(version, timeout).await,
+            Shard::Proxy(proxy_shard) => proxy_shard.wait_applied(version, timeout).await,
+            Shard::ForwardProxy(proxy_shard) => proxy_shard.wait_applied(version, timeout).await,
+        }
+    }
+
+    pub async fn vacuum(
+        &self,
+        shard_id: ShardId,
+        deleted_threshold: f64,
+    ) -> CollectionResult<(Vec<SegmentDeletedInfo>, usize)> {
+        match self {
+            Shard::Local(local_shard) => local_shard.vacuum(shard_id, deleted_threshold).await,
+            Shard::Proxy(proxy_shard) => proxy_shard.vacuum(shard_id, deleted_threshold).await,
+            Shard::ForwardProxy(proxy_shard) => {
+                proxy_shard.vacuum(shard_id, deleted_threshold).await
+            }
+        }
+    }
+
+    pub async fn flush(&self) -> CollectionResult<SeqNumberType> {
+        match self {
+            Shard::Local(local_shard) => local_shard.flush().await,
+            Shard::Proxy(proxy_shard) => proxy_shard.flush().await,
+            Shard::ForwardProxy(proxy_shard) => proxy_shard.flush().await,
+        }
+    }
+
+    pub fn original_segments(&self) -> Vec<LockedSegment> {
+        match self {
+            Shard::Local(local_shard) => local_shard.original_segments(),
+            Shard::Proxy(proxy_shard) => proxy_shard.original_segments(),
+            Shard::ForwardProxy(proxy_shard) => proxy_shard.original_segments(),
+        }
+    }
+
+    pub async fn memory_usage(&self) -> CollectionResult<MemoryUsage> {
+        match self {
+            Shard::Local(local_shard) => local_shard.memory_usage().await,
+            Shard::Proxy(proxy_shard) => proxy_shard.memory_usage().await,
+            Shard::ForwardProxy(proxy_shard) => proxy_shard.memory_usage().await,
+        }
+    }
+
+    pub async fn truncate_wal(&self) -> CollectionResult<(SeqNumberType, WalTelemetry)> {
+        match self {
+            Shard::Local(local_shard) => local_shard.truncate_wal().await,
+            Shard::Proxy(proxy_shard) => proxy_shard.truncate_wal().await,
+            Shard::ForwardProxy(proxy_shard) => proxy_shard.truncate_wal().await,
+        }
+    }
+
+    pub async fn orphaned_files(&self) -> CollectionResult<Option<Vec<PathBuf>>> {
+        match self {
+            Shard::Local(local_shard) => local_shard.orphaned_files().await,
+            Shard::Proxy(proxy_shard) => proxy_shard.orphaned_files().await,
+            Shard::ForwardProxy(proxy_shard) => proxy_shard.orphaned_files().await,
+        }
+    }
 }
@@ -4,6 +4,7 @@ use std::sync::Arc;
 
 use futures::stream::FuturesUnordered;
 use futures::StreamExt;
+use segment::types::PointIdType;
 use tokio::runtime::Handle;
 use tokio::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
 
@@ -11,7 +12,7 @@ use crate::config::CollectionConfig;
 use crate::hash_ring::HashRing;
 use crate::operations::shared_storage_config::SharedStorageConfig;
 use crate::operations::types::{CollectionResult, ShardTransferInfo};
-use crate::operations::{OperationToShard, SplitByShard};
+use crate::operations::{point_to_shard, OperationToShard, SplitByShard};
 use crate::save_on_disk::SaveOnDisk;
 use crate::shards::channel_service::ChannelService;
 use crate::shards::local_shard::LocalShard;
@@ -91,6 +92,11 @@ impl ShardHolder {
         self.shards.values()
     }
 
+    /// Shard, which stores the point
+    pub fn point_shard(&self, point_id: PointIdType) -> ShardId {
+        point_to_shard(point_id, &self.ring)
+    }
+
     pub fn split_by_shard<O: SplitByShard + Clone>(
         &self,
         operation: O,
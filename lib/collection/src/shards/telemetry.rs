@@ -32,6 +32,21 @@ pub struct LocalShardTelemetry {
     pub variant_name: Option<String>,
     pub segments: Vec<SegmentTelemetry>,
     pub optimizations: OptimizerTelemetry,
+    /// None, if WAL is not opened
+    pub wal: Option<WalTelemetry>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, JsonSchema)]
+pub struct WalTelemetry {
+    /// Size of WAL files on disk, including preallocated segments.
+    /// None, if WAL directory can't be read
+    pub size_bytes: Option<u64>,
+    /// Number of operations stored in WAL
+    pub num_entries: u64,
+    /// Sequential number of the oldest operation stored in WAL
+    pub first_op_num: u64,
+    /// Sequential number of the newest operation stored in WAL
+    pub last_op_num: u64,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, JsonSchema, Default)]
@@ -60,12 +75,19 @@ impl Anonymize for OptimizerTelemetry {
     }
 }
 
+impl Anonymize for WalTelemetry {
+    fn anonymize(&self) -> Self {
+        self.clone()
+    }
+}
+
 impl Anonymize for LocalShardTelemetry {
     fn anonymize(&self) -> Self {
         LocalShardTelemetry {
             variant_name: self.variant_name.clone(),
             segments: self.segments.anonymize(),
             optimizations: self.optimizations.anonymize(),
+            wal: self.wal.anonymize(),
         }
     }
 }
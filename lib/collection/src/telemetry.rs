@@ -13,6 +13,67 @@ pub struct CollectionTelemetry {
     pub config: CollectionConfig,
     pub shards: Vec<ReplicaSetTelemetry>,
     pub transfers: Vec<ShardTransferInfo>,
+    pub search_recall: SearchRecallTelemetry,
+}
+
+/// Recall of approximate search, estimated by comparing sampled searches with exact search
+#[derive(Serialize, Deserialize, Clone, Default, Debug, JsonSchema)]
+pub struct SearchRecallTelemetry {
+    /// Number of searches compared with exact search
+    pub samples: usize,
+
+    /// Average recall over the latest samples
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub avg_recall: Option<f64>,
+
+    /// Lowest recall over all samples
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub min_recall: Option<f64>,
+}
+
+const RECALL_WINDOW_LEN: usize = 128;
+
+pub struct SearchRecallAggregator {
+    samples: usize,
+    recalls: [f64; RECALL_WINDOW_LEN],
+    min_recall: Option<f64>,
+}
+
+impl Default for SearchRecallAggregator {
+    fn default() -> Self {
+        Self {
+            samples: 0,
+            recalls: [0.; RECALL_WINDOW_LEN],
+            min_recall: None,
+        }
+    }
+}
+
+impl SearchRecallAggregator {
+    /// Record recall of a single search: share of exact results found by the approximate search
+    pub fn add(&mut self, recall: f64) {
+        self.recalls[self.samples % RECALL_WINDOW_LEN] = recall;
+        self.samples += 1;
+        self.min_recall = Some(match self.min_recall {
+            Some(min_recall) => min_recall.min(recall),
+            None => recall,
+        });
+    }
+
+    pub fn get_statistics(&self) -> SearchRecallTelemetry {
+        let window = self.samples.min(RECALL_WINDOW_LEN);
+        SearchRecallTelemetry {
+            samples: self.samples,
+            avg_recall: if window > 0 {
+                Some(self.recalls[..window].iter().sum::<f64>() / window as f64)
+            } else {
+                None
+            },
+            min_recall: self.min_recall,
+        }
+    }
 }
 
 impl CollectionTelemetry {
@@ -34,6 +95,7 @@ impl Anonymize for CollectionTelemetry {
             init_time_ms: self.init_time_ms,
             shards: self.shards.anonymize(),
             transfers: vec![],
+            search_recall: self.search_recall.clone(),
         }
     }
 }
@@ -42,10 +104,39 @@ impl Anonymize for CollectionConfig {
     fn anonymize(&self) -> Self {
         CollectionConfig {
             params: self.params.clone(),
-            hnsw_config: self.hnsw_config,
+            hnsw_config: self.hnsw_config.clone(),
             optimizer_config: self.optimizer_config.clone(),
             wal_config: self.wal_config.clone(),
             quantization_config: self.quantization_config.clone(),
+            lifecycle_config: self.lifecycle_config.clone(),
+            created_at: self.created_at,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_search_recall_aggregator() {
+        let mut aggregator = SearchRecallAggregator::default();
+        assert!(aggregator.get_statistics().avg_recall.is_none());
+
+        aggregator.add(1.0);
+        aggregator.add(0.5);
+        let stats = aggregator.get_statistics();
+        assert_eq!(stats.samples, 2);
+        assert_eq!(stats.avg_recall, Some(0.75));
+        assert_eq!(stats.min_recall, Some(0.5));
+
+        // Only the latest samples are averaged
+        for _ in 0..RECALL_WINDOW_LEN {
+            aggregator.add(1.0);
         }
+        let stats = aggregator.get_statistics();
+        assert_eq!(stats.samples, RECALL_WINDOW_LEN + 2);
+        assert_eq!(stats.avg_recall, Some(1.0));
+        assert_eq!(stats.min_recall, Some(0.5));
     }
 }
@@ -7,6 +7,7 @@ use std::time::Duration;
 use futures::future::join_all;
 use itertools::Itertools;
 use parking_lot::RwLock;
+use segment::common::version::VERSION_FILE;
 use tempfile::Builder;
 use tokio::time::{sleep, Instant};
 
@@ -15,6 +16,7 @@ use crate::collection_manager::fixtures::{
     get_indexing_optimizer, get_merge_optimizer, random_segment,
 };
 use crate::collection_manager::holders::segment_holder::{LockedSegment, SegmentHolder, SegmentId};
+use crate::common::optimization_budget::OptimizationBudget;
 use crate::update_handler::{Optimizer, UpdateHandler};
 
 #[tokio::test]
@@ -46,14 +48,21 @@ async fn test_optimization_process() {
     let optimizers = Arc::new(vec![merge_optimizer, indexing_optimizer]);
 
     let segments: Arc<RwLock<_>> = Arc::new(RwLock::new(holder));
-    let handles = UpdateHandler::launch_optimization(optimizers.clone(), segments.clone(), |_| {});
+    // Optimizations are executed one by one
+    let budget = Arc::new(OptimizationBudget::new(1));
+    let handles = UpdateHandler::launch_optimization(
+        optimizers.clone(),
+        segments.clone(),
+        budget.clone(),
+        |_| {},
+    );
 
     assert_eq!(handles.len(), 2);
 
     let join_res = join_all(handles.into_iter().map(|x| x.join_handle).collect_vec()).await;
 
     let handles_2 =
-        UpdateHandler::launch_optimization(optimizers.clone(), segments.clone(), |_| {});
+        UpdateHandler::launch_optimization(optimizers.clone(), segments.clone(), budget, |_| {});
 
     assert_eq!(handles_2.len(), 0);
 
@@ -91,7 +100,12 @@ async fn test_cancel_optimization() {
     let now = Instant::now();
 
     let segments: Arc<RwLock<_>> = Arc::new(RwLock::new(holder));
-    let handles = UpdateHandler::launch_optimization(optimizers.clone(), segments.clone(), |_| {});
+    let handles = UpdateHandler::launch_optimization(
+        optimizers.clone(),
+        segments.clone(),
+        Default::default(),
+        |_| {},
+    );
 
     sleep(Duration::from_millis(100)).await;
 
@@ -138,3 +152,34 @@ fn check_version_upgrade() {
         &"0.4.2".parse().unwrap()
     ));
 }
+
+#[test]
+fn check_version_migrate() {
+    assert!(Collection::can_migrate_storage(
+        &"0.4.0".parse().unwrap(),
+        &"0.4.2".parse().unwrap()
+    ));
+    assert!(!Collection::can_migrate_storage(
+        &"0.3.1".parse().unwrap(),
+        &"0.4.0".parse().unwrap()
+    ));
+    assert!(!Collection::can_migrate_storage(
+        &"0.4.2".parse().unwrap(),
+        &"1.4.2".parse().unwrap()
+    ));
+    assert!(!Collection::can_migrate_storage(
+        &"0.4.2".parse().unwrap(),
+        &"0.4.1".parse().unwrap()
+    ));
+}
+
+#[test]
+fn test_migrate_unsupported_version_is_refused() {
+    let dir = Builder::new().prefix("collection_dir").tempdir().unwrap();
+    let version_file = dir.path().join(VERSION_FILE);
+    std::fs::write(&version_file, "0.0.1").unwrap();
+
+    assert!(Collection::migrate_storage(dir.path()).is_err());
+    // Version is not restamped, so the collection is still recognized as an old one
+    assert_eq!(std::fs::read_to_string(&version_file).unwrap(), "0.0.1");
+}
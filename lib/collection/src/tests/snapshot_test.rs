@@ -23,6 +23,8 @@ pub const TEST_OPTIMIZERS_CONFIG: OptimizersConfig = OptimizersConfig {
     indexing_threshold: 50_000,
     flush_interval_sec: 30,
     max_optimization_threads: 2,
+    target_segment_count: None,
+    max_segment_size_mb: None,
 };
 
 pub fn dummy_on_replica_failure() -> ChangePeerState {
@@ -45,11 +47,21 @@ async fn _test_snapshot_collection(node_type: NodeType) {
             distance: Distance::Dot,
             hnsw_config: None,
             quantization_config: None,
+            projection: None,
         }),
         shard_number: NonZeroU32::new(4).unwrap(),
         replication_factor: NonZeroU32::new(3).unwrap(),
         write_consistency_factor: NonZeroU32::new(2).unwrap(),
         on_disk_payload: false,
+        vector_aliases: Default::default(),
+        default_vector: None,
+        defrag_key: None,
+        deduplicate_upserts: false,
+        max_concurrent_searches: 0,
+        acl_key: None,
+        default_write_ordering: None,
+        default_read_consistency: None,
+        sparse_vectors: Default::default(),
     };
 
     let config = CollectionConfig {
@@ -58,6 +70,8 @@ async fn _test_snapshot_collection(node_type: NodeType) {
         wal_config,
         hnsw_config: Default::default(),
         quantization_config: Default::default(),
+        lifecycle_config: None,
+        created_at: None,
     };
 
     let snapshots_path = Builder::new().prefix("test_snapshots").tempdir().unwrap();
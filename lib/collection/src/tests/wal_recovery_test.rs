@@ -26,11 +26,21 @@ fn create_collection_config() -> CollectionConfig {
             distance: Distance::Dot,
             hnsw_config: None,
             quantization_config: None,
+            projection: None,
         }),
         shard_number: NonZeroU32::new(1).unwrap(),
         replication_factor: NonZeroU32::new(1).unwrap(),
         write_consistency_factor: NonZeroU32::new(1).unwrap(),
         on_disk_payload: false,
+        vector_aliases: Default::default(),
+        default_vector: None,
+        defrag_key: None,
+        deduplicate_upserts: false,
+        max_concurrent_searches: 0,
+        acl_key: None,
+        default_write_ordering: None,
+        default_read_consistency: None,
+        sparse_vectors: Default::default(),
     };
 
     let mut optimizer_config = TEST_OPTIMIZERS_CONFIG.clone();
@@ -44,6 +54,8 @@ fn create_collection_config() -> CollectionConfig {
         wal_config,
         hnsw_config: Default::default(),
         quantization_config: Default::default(),
+        lifecycle_config: None,
+        created_at: None,
     }
 }
 
@@ -56,6 +68,8 @@ fn upsert_operation() -> CollectionUpdateOperations {
                 payload: Some(
                     serde_json::from_str(r#"{ "location": { "lat": 10.12, "lon": 32.12  } }"#).unwrap(),
                 ),
+                sequence: None,
+                content_hash: None,
             },
             PointStruct {
                 id: 2.into(),
@@ -63,6 +77,8 @@ fn upsert_operation() -> CollectionUpdateOperations {
                 payload: Some(
                     serde_json::from_str(r#"{ "location": { "lat": 11.12, "lon": 34.82  } }"#).unwrap(),
                 ),
+                sequence: None,
+                content_hash: None,
             },
             PointStruct {
                 id: 3.into(),
@@ -70,6 +86,8 @@ fn upsert_operation() -> CollectionUpdateOperations {
                 payload: Some(
                     serde_json::from_str(r#"{ "location": [ { "lat": 12.12, "lon": 34.82  }, { "lat": 12.2, "lon": 12.82  }] }"#).unwrap(),
                 ),
+                sequence: None,
+                content_hash: None,
             },
             PointStruct {
                 id: 4.into(),
@@ -77,6 +95,8 @@ fn upsert_operation() -> CollectionUpdateOperations {
                 payload: Some(
                     serde_json::from_str(r#"{ "location": { "lat": 13.12, "lon": 34.82  } }"#).unwrap(),
                 ),
+                sequence: None,
+                content_hash: None,
             },
             PointStruct {
                 id: 5.into(),
@@ -84,6 +104,8 @@ fn upsert_operation() -> CollectionUpdateOperations {
                 payload: Some(
                     serde_json::from_str(r#"{ "location": { "lat": 14.12, "lon": 32.12  } }"#).unwrap(),
                 ),
+                sequence: None,
+                content_hash: None,
             },
 
         ]
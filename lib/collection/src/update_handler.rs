@@ -4,6 +4,7 @@ use std::sync::Arc;
 
 use itertools::Itertools;
 use log::{debug, error, info, trace, warn};
+use segment::common::log_context::{with_log_context, LogContext};
 use segment::entry::entry_point::OperationResult;
 use segment::types::SeqNumberType;
 use tokio::runtime::Handle;
@@ -15,6 +16,8 @@ use tokio::time::Duration;
 use crate::collection_manager::collection_updater::CollectionUpdater;
 use crate::collection_manager::holders::segment_holder::LockedSegmentHolder;
 use crate::collection_manager::optimizers::segment_optimizer::SegmentOptimizer;
+use crate::common::fault_injection;
+use crate::common::optimization_budget::OptimizationBudget;
 use crate::common::stoppable_task::{spawn_stoppable, StoppableTaskHandle};
 use crate::operations::shared_storage_config::SharedStorageConfig;
 use crate::operations::types::{CollectionError, CollectionResult};
@@ -80,6 +83,8 @@ pub struct UpdateHandler {
     wal: LockedWal,
     optimization_handles: Arc<TokioMutex<Vec<StoppableTaskHandle<bool>>>>,
     max_optimization_threads: usize,
+    /// Context of log lines, emitted by the workers
+    log_context: LogContext,
 }
 
 impl UpdateHandler {
@@ -91,6 +96,7 @@ impl UpdateHandler {
         wal: LockedWal,
         flush_interval_sec: u64,
         max_optimization_threads: usize,
+        log_context: LogContext,
     ) -> UpdateHandler {
         UpdateHandler {
             shared_storage_config,
@@ -105,35 +111,70 @@ impl UpdateHandler {
             flush_interval_sec,
             optimization_handles: Arc::new(TokioMutex::new(vec![])),
             max_optimization_threads,
+            log_context,
         }
     }
 
     pub fn run_workers(&mut self, update_receiver: Receiver<UpdateSignal>) {
         let (tx, rx) = mpsc::channel(self.shared_storage_config.update_queue_size);
-        self.optimizer_worker = Some(self.runtime_handle.spawn(Self::optimization_worker_fn(
-            self.optimizers.clone(),
-            tx.clone(),
-            rx,
-            self.segments.clone(),
-            self.wal.clone(),
-            self.optimization_handles.clone(),
-            self.max_optimization_threads,
+        let log_context = Some(self.log_context.clone());
+        self.optimizer_worker = Some(self.runtime_handle.spawn(with_log_context(
+            log_context.clone(),
+            Self::optimization_worker_fn(
+                self.optimizers.clone(),
+                tx.clone(),
+                rx,
+                self.segments.clone(),
+                self.wal.clone(),
+                self.optimization_handles.clone(),
+                self.max_optimization_threads,
+                self.shared_storage_config.optimization_budget.clone(),
+            ),
         )));
-        self.update_worker = Some(self.runtime_handle.spawn(Self::update_worker_fn(
-            update_receiver,
-            tx,
-            self.segments.clone(),
+        self.update_worker = Some(self.runtime_handle.spawn(with_log_context(
+            log_context.clone(),
+            Self::update_worker_fn(update_receiver, tx, self.segments.clone()),
         )));
         let (flush_tx, flush_rx) = oneshot::channel();
-        self.flush_worker = Some(self.runtime_handle.spawn(Self::flush_worker(
-            self.segments.clone(),
-            self.wal.clone(),
-            self.flush_interval_sec,
-            flush_rx,
+        self.flush_worker = Some(self.runtime_handle.spawn(with_log_context(
+            log_context,
+            Self::flush_worker(
+                self.segments.clone(),
+                self.wal.clone(),
+                self.flush_interval_sec,
+                flush_rx,
+            ),
         )));
         self.flush_stop = Some(flush_tx);
     }
 
+    /// Run given optimizers once, in addition to the regular optimizers.
+    /// Optimizations are stopped along with the regular ones.
+    /// Returns number of started optimizations.
+    pub async fn launch_extra_optimization(&self, optimizers: Arc<Vec<Arc<Optimizer>>>) -> usize {
+        let log_context = self.log_context.clone().enter();
+        let mut new_handles = Self::launch_optimization(
+            optimizers,
+            self.segments.clone(),
+            self.shared_storage_config.optimization_budget.clone(),
+            |_| {},
+        );
+        drop(log_context);
+        let launched = new_handles.len();
+        let mut handles = self.optimization_handles.lock().await;
+        handles.append(&mut new_handles);
+        launched
+    }
+
+    /// Checks if any optimization is in progress
+    pub async fn has_running_optimizations(&self) -> bool {
+        self.optimization_handles
+            .lock()
+            .await
+            .iter()
+            .any(|handle| !handle.is_finished())
+    }
+
     pub fn stop_flush_worker(&mut self) {
         if let Some(flush_stop) = self.flush_stop.take() {
             if let Err(()) = flush_stop.send(()) {
@@ -178,7 +219,7 @@ impl UpdateHandler {
             None => {}
             Some(first_failed_op) => {
                 let wal_lock = wal.lock();
-                for (op_num, operation) in wal_lock.read(first_failed_op) {
+                for (op_num, operation) in wal_lock.read(first_failed_op)? {
                     CollectionUpdater::update(&segments, op_num, operation)?;
                 }
             }
@@ -187,11 +228,12 @@ impl UpdateHandler {
     }
 
     /// Checks conditions for all optimizers until there is no suggested segment
-    /// Starts a task for each optimization
+    /// Starts a task for each optimization, which waits for the node-wide `budget` first
     /// Returns handles for started tasks
     pub(crate) fn launch_optimization<F>(
         optimizers: Arc<Vec<Arc<Optimizer>>>,
         segments: LockedSegmentHolder,
+        budget: Arc<OptimizationBudget>,
         callback: F,
     ) -> Vec<StoppableTaskHandle<bool>>
     where
@@ -215,8 +257,14 @@ impl UpdateHandler {
                         scheduled_segment_ids.insert(*sid);
                     }
                     let callback_cloned = callback.clone();
+                    let budget = budget.clone();
 
                     handles.push(spawn_stoppable(move |stopped| {
+                        let Some(_permit) = budget.acquire(stopped) else {
+                            log::debug!("Optimization cancelled while waiting for the budget");
+                            return false;
+                        };
+                        fault_injection::inject_optimizer_stall(stopped);
                         match optim.as_ref().optimize(segs.clone(), nsi, stopped) {
                             Ok(result) => {
                                 callback_cloned(result); // Perform some actions when optimization if finished
@@ -253,10 +301,12 @@ impl UpdateHandler {
         segments: LockedSegmentHolder,
         optimization_handles: Arc<TokioMutex<Vec<StoppableTaskHandle<bool>>>>,
         sender: Sender<OptimizerSignal>,
+        budget: Arc<OptimizationBudget>,
     ) {
         let mut new_handles = Self::launch_optimization(
             optimizers.clone(),
             segments.clone(),
+            budget,
             move |_optimization_result| {
                 // After optimization is finished, we still need to check if there are
                 // some further optimizations possible.
@@ -278,6 +328,7 @@ impl UpdateHandler {
         wal: LockedWal,
         optimization_handles: Arc<TokioMutex<Vec<StoppableTaskHandle<bool>>>>,
         max_handles: usize,
+        budget: Arc<OptimizationBudget>,
     ) {
         while let Some(signal) = receiver.recv().await {
             match signal {
@@ -302,6 +353,7 @@ impl UpdateHandler {
                         segments.clone(),
                         optimization_handles.clone(),
                         sender.clone(),
+                        budget.clone(),
                     )
                     .await;
                 }
@@ -387,7 +439,14 @@ impl UpdateHandler {
             };
 
             trace!("Attempting flushing");
-            let wal_flash_job = wal.lock().flush_async();
+            let wal_flash_job = match wal.lock().flush_async() {
+                Ok(job) => job,
+                Err(err) => {
+                    error!("Failed to flush wal: {err}");
+                    segments.write().report_optimizer_error(err);
+                    continue;
+                }
+            };
 
             if let Err(err) = wal_flash_job.join() {
                 error!("Failed to flush wal: {:?}", err);
@@ -399,7 +458,7 @@ impl UpdateHandler {
                 continue;
             }
 
-            let confirmed_version = Self::flush_segments(segments.clone());
+            let confirmed_version = Self::flush_segments(segments.clone(), false);
             let confirmed_version = match confirmed_version {
                 Ok(version) => version,
                 Err(err) => {
@@ -418,9 +477,13 @@ impl UpdateHandler {
     ///
     /// # Errors
     /// Returns an error on flush failure
-    fn flush_segments(segments: LockedSegmentHolder) -> OperationResult<SeqNumberType> {
+    pub(crate) fn flush_segments(
+        segments: LockedSegmentHolder,
+        sync: bool,
+    ) -> OperationResult<SeqNumberType> {
+        fault_injection::inject_flush_fault()?;
         let read_segments = segments.read();
-        let flushed_version = read_segments.flush_all(false)?;
+        let flushed_version = read_segments.flush_all(sync)?;
         Ok(match read_segments.failed_operation.iter().cloned().min() {
             None => flushed_version,
             Some(failed_operation) => min(failed_operation, flushed_version),
@@ -0,0 +1,193 @@
+use std::future::Future;
+
+use segment::data_types::vectors::{NamedVector, VectorElementType, DEFAULT_VECTOR_NAME};
+use segment::types::{Condition, Filter, HasIdCondition, ScoredPoint};
+use tokio::sync::RwLockReadGuard;
+
+use crate::collection::Collection;
+use crate::operations::consistency_params::ReadConsistency;
+use crate::operations::types::{
+    ArithmeticSearchRequest, CollectionError, CollectionResult, SearchRequest, SearchRequestBatch,
+    UsingVector,
+};
+use crate::recommendations::{example_vectors, retrieve_examples};
+
+/// Sum of the vectors, multiplied by their weights
+fn weighted_sum<'a>(
+    terms: impl Iterator<Item = (&'a [VectorElementType], f32)>,
+    dim: usize,
+) -> Vec<VectorElementType> {
+    let mut sum = vec![0.0; dim];
+    for (vector, weight) in terms {
+        for (item, value) in sum.iter_mut().zip(vector) {
+            *item += weight * value;
+        }
+    }
+    sum
+}
+
+/// Search points by the query vector, composed from the weighted examples.
+///
+/// Function works in following stages:
+///
+/// - Retrieves vectors of the examples, given by ids, from this or the lookup collection
+/// - Sums up the vectors of the examples, multiplied by their weights
+/// - Searches by the sum as a regular query vector, excluding the examples given by ids
+pub async fn search_by_arithmetic<'a, F, Fut>(
+    request: ArithmeticSearchRequest,
+    collection: &Collection,
+    collection_by_name: F,
+    read_consistency: Option<ReadConsistency>,
+) -> CollectionResult<Vec<ScoredPoint>>
+where
+    F: Fn(String) -> Fut,
+    Fut: Future<Output = Option<RwLockReadGuard<'a, Collection>>>,
+{
+    if request.limit == 0 {
+        return Ok(vec![]);
+    }
+    if request.terms.is_empty() {
+        return Err(CollectionError::bad_request(
+            "At least one term of the query required".to_owned(),
+        ));
+    }
+
+    let vector_name = match &request.using {
+        None => DEFAULT_VECTOR_NAME,
+        Some(UsingVector::Name(name)) => name,
+    };
+    let lookup_vector_name = match &request.lookup_from {
+        None => vector_name.to_owned(),
+        Some(lookup_from) => lookup_from
+            .vector
+            .clone()
+            .unwrap_or_else(|| DEFAULT_VECTOR_NAME.to_owned()),
+    };
+
+    let example_ids: Vec<_> = request
+        .terms
+        .iter()
+        .filter_map(|term| term.example.as_point_id())
+        .collect();
+
+    let records = retrieve_examples(
+        collection,
+        collection_by_name,
+        request.lookup_from.as_ref(),
+        example_ids.clone(),
+        &lookup_vector_name,
+        read_consistency,
+    )
+    .await?;
+
+    let vector_params = {
+        let collection_params = &collection.collection_config.read().await.params;
+        collection_params
+            .get_vector_params(&collection_params.resolve_vector_name(vector_name))
+            .map_err(|_| {
+                CollectionError::bad_request(format!(
+                    "Vector arithmetic is not supported by vector {vector_name}"
+                ))
+            })?
+    };
+
+    let vectors = request
+        .terms
+        .iter()
+        .map(|term| {
+            example_vectors(
+                [&term.example],
+                |point_id| records.get(&point_id),
+                &lookup_vector_name,
+                Some(&vector_params),
+            )?
+            .into_iter()
+            .next()
+            .ok_or_else(|| {
+                CollectionError::bad_request(format!(
+                    "Example point has no vector {lookup_vector_name}"
+                ))
+            })
+        })
+        .collect::<CollectionResult<Vec<_>>>()?;
+
+    let dim = vector_params.size.get() as usize;
+    if let Some(vector) = vectors.iter().find(|vector| vector.len() != dim) {
+        return Err(CollectionError::bad_request(format!(
+            "Wrong dimension of the example vector: expected {dim}, got {}",
+            vector.len()
+        )));
+    }
+
+    let sum = weighted_sum(
+        vectors
+            .iter()
+            .map(Vec::as_slice)
+            .zip(request.terms.iter().map(|term| term.weight)),
+        dim,
+    );
+    // Sum of normalized vectors is not normalized, so it is preprocessed as any query vector
+    let query_vector = vector_params
+        .distance
+        .preprocess_vector(&sum)
+        .unwrap_or(sum);
+
+    let filter = Filter {
+        should: None,
+        must: request.filter.map(|filter| vec![Condition::Filter(filter)]),
+        must_not: Some(vec![Condition::HasId(HasIdCondition {
+            has_id: example_ids.into_iter().collect(),
+        })]),
+    };
+
+    let search = SearchRequest {
+        vector: NamedVector {
+            name: vector_name.to_string(),
+            vector: query_vector,
+        }
+        .into(),
+        filter: Some(filter),
+        with_payload: request.with_payload,
+        with_vector: request.with_vector,
+        params: request.params,
+        limit: request.limit,
+        score_threshold: request.score_threshold,
+        offset: request.offset,
+        with_highlights: None,
+        with_geo_distance: None,
+    };
+
+    let results = collection
+        .search_batch_by_stored_vectors(
+            SearchRequestBatch {
+                searches: vec![search],
+            },
+            read_consistency,
+            None,
+            None,
+        )
+        .await?;
+    Ok(results.into_iter().next().unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_weighted_sum() {
+        let a = vec![1.0, 0.0, 0.0];
+        let b = vec![0.0, 1.0, 0.0];
+        let c = vec![0.0, 0.0, 1.0];
+        let sum = weighted_sum(
+            [
+                (a.as_slice(), 0.5),
+                (b.as_slice(), 2.0),
+                (c.as_slice(), -1.0),
+            ]
+            .into_iter(),
+            3,
+        );
+        assert_eq!(sum, vec![0.5, 2.0, -1.0]);
+    }
+}
@@ -1,6 +1,6 @@
 use std::fmt::Debug;
 use std::marker::PhantomData;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::result;
 use std::thread::JoinHandle;
 
@@ -9,6 +9,8 @@ use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use wal::{Wal, WalOptions};
 
+use crate::wal_archive::WalArchive;
+
 #[allow(clippy::enum_variant_names)]
 #[derive(Error, Debug)]
 #[error("{0}")]
@@ -19,6 +21,10 @@ pub enum WalError {
     WriteWalError(String),
     #[error("Can't truncate WAL: {0}")]
     TruncateWalError(String),
+    #[error("Can't archive WAL: {0}")]
+    ArchiveWalError(String),
+    #[error("WAL is not opened: {0}")]
+    ClosedWalError(String),
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -44,6 +50,12 @@ enum TestRecord {
 
 type Result<T> = result::Result<T, WalError>;
 
+enum WalState {
+    Opened(Wal),
+    /// WAL could not be reopened, contains the reason
+    Failed(String),
+}
+
 /// Write-Ahead-Log wrapper with built-in type parsing.
 /// Stores sequences of records of type `R` in binary files.
 ///
@@ -52,8 +64,11 @@ type Result<T> = result::Result<T, WalError>;
 /// for removing old, no longer required, records.
 pub struct SerdeWal<R> {
     record: PhantomData<R>,
-    wal: Wal,
+    wal: WalState,
+    path: PathBuf,
     options: WalOptions,
+    /// If set, records are archived before they are removed from the WAL
+    archive: Option<WalArchive>,
 }
 
 impl<'s, R: DeserializeOwned + Serialize + Debug> SerdeWal<R> {
@@ -62,74 +77,167 @@ impl<'s, R: DeserializeOwned + Serialize + Debug> SerdeWal<R> {
             .map_err(|err| WalError::InitWalError(format!("{err:?}")))?;
         Ok(SerdeWal {
             record: PhantomData,
-            wal,
+            wal: WalState::Opened(wal),
+            path: PathBuf::from(dir),
             options: wal_options,
+            archive: None,
         })
     }
 
+    pub fn set_archive(&mut self, archive: WalArchive) {
+        self.archive = Some(archive);
+    }
+
+    fn wal(&self) -> Result<&Wal> {
+        match &self.wal {
+            WalState::Opened(wal) => Ok(wal),
+            WalState::Failed(reason) => Err(WalError::ClosedWalError(reason.clone())),
+        }
+    }
+
+    fn wal_mut(&mut self) -> Result<&mut Wal> {
+        match &mut self.wal {
+            WalState::Opened(wal) => Ok(wal),
+            WalState::Failed(reason) => Err(WalError::ClosedWalError(reason.clone())),
+        }
+    }
+
+    /// Reopen WAL with new options.
+    /// Stored records are preserved, new options only apply to segments created afterwards.
+    ///
+    /// If the WAL can't be opened with new options, it is reopened with the previous ones.
+    /// If that fails too, the WAL stays closed and all operations on it return an error,
+    /// until it is successfully reopened.
+    pub fn reopen(&mut self, wal_options: WalOptions) -> Result<()> {
+        if let WalState::Opened(wal) = &mut self.wal {
+            if wal_options.segment_capacity == self.options.segment_capacity
+                && wal_options.segment_queue_len == self.options.segment_queue_len
+            {
+                return Ok(());
+            }
+            wal.flush_open_segment()
+                .map_err(|err| WalError::WriteWalError(format!("{err:?}")))?;
+        }
+        // WAL holds an exclusive lock on its directory, so it must be closed before reopening
+        self.wal = WalState::Failed("WAL is being reopened".to_string());
+        match Wal::with_options(&self.path, &wal_options) {
+            Ok(wal) => {
+                self.wal = WalState::Opened(wal);
+                self.options = wal_options;
+                Ok(())
+            }
+            Err(err) => {
+                let err = WalError::InitWalError(format!("{err:?}"));
+                match Wal::with_options(&self.path, &self.options) {
+                    Ok(wal) => self.wal = WalState::Opened(wal),
+                    Err(fallback_err) => {
+                        self.wal = WalState::Failed(format!(
+                            "can't reopen WAL at {}: {fallback_err:?}",
+                            self.path.display()
+                        ))
+                    }
+                }
+                Err(err)
+            }
+        }
+    }
+
     /// Write a record to the WAL but does guarantee durability.
     pub fn write(&mut self, entity: &R) -> Result<u64> {
         // ToDo: Replace back to faster rmp, once this https://github.com/serde-rs/serde/issues/2055 solved
         let binary_entity = serde_cbor::to_vec(&entity).unwrap();
-        self.wal
+        self.wal_mut()?
             .append(&binary_entity)
             .map_err(|err| WalError::WriteWalError(format!("{err:?}")))
     }
 
-    pub fn read_all(&'s self) -> impl Iterator<Item = (u64, R)> + 's {
-        self.read(self.wal.first_index())
+    pub fn read_all(&'s self) -> Result<impl Iterator<Item = (u64, R)> + 's> {
+        self.read(self.first_index()?)
     }
 
-    pub fn is_empty(&self) -> bool {
-        self.len() == 0
+    pub fn is_empty(&self) -> Result<bool> {
+        Ok(self.len()? == 0)
     }
 
-    pub fn len(&self) -> u64 {
-        self.wal.num_entries()
+    pub fn len(&self) -> Result<u64> {
+        Ok(self.wal()?.num_entries())
     }
 
-    pub fn read(&'s self, start_from: u64) -> impl Iterator<Item = (u64, R)> + 's {
-        let first_index = self.wal.first_index();
-        let num_entries = self.wal.num_entries();
+    pub fn read(&'s self, start_from: u64) -> Result<impl Iterator<Item = (u64, R)> + 's> {
+        let wal = self.wal()?;
+        let first_index = wal.first_index();
+        let num_entries = wal.num_entries();
 
-        (start_from..(first_index + num_entries)).map(move |idx| {
-            let record_bin = self.wal.entry(idx).expect("Can't read entry from WAL");
-            let record: R = serde_cbor::from_slice(&record_bin)
-                .or_else(|_err| rmp_serde::from_slice(&record_bin))
-                .expect("Can't deserialize entry, probably corrupted WAL on version mismatch");
-            (idx, record)
-        })
+        Ok((start_from..(first_index + num_entries)).map(move |idx| (idx, Self::entry(wal, idx))))
+    }
+
+    fn entry(wal: &Wal, idx: u64) -> R {
+        let record_bin = wal.entry(idx).expect("Can't read entry from WAL");
+        serde_cbor::from_slice(&record_bin)
+            .or_else(|_err| rmp_serde::from_slice(&record_bin))
+            .expect("Can't deserialize entry, probably corrupted WAL on version mismatch")
     }
 
     /// Inform WAL, that records older than `until_index` are no longer required.
     /// If it is possible, WAL will remove unused files.
+    /// If the archive is set, records are archived first, and WAL is not truncated if it fails.
     ///
     /// # Arguments
     ///
     /// * `until_index` - the newest no longer required record sequence number
     ///
     pub fn ack(&mut self, until_index: u64) -> Result<()> {
-        self.wal
+        if let Some(next_index) = self.archive.as_ref().map(WalArchive::next_index) {
+            let wal = self.wal()?;
+            let first_index = wal.first_index();
+            let end_index = (first_index + wal.num_entries()).min(until_index + 1);
+            let records: Vec<_> = (next_index.max(first_index)..end_index)
+                .map(|idx| (idx, Self::entry(wal, idx)))
+                .collect();
+            if let Some(archive) = &mut self.archive {
+                archive
+                    .archive(&records)
+                    .map_err(|err| WalError::ArchiveWalError(err.to_string()))?;
+            }
+        }
+        self.wal_mut()?
             .prefix_truncate(until_index)
             .map_err(|err| WalError::TruncateWalError(format!("{err:?}")))
     }
 
     pub fn flush(&mut self) -> Result<()> {
-        self.wal
+        self.wal_mut()?
             .flush_open_segment()
             .map_err(|err| WalError::WriteWalError(format!("{err:?}")))
     }
 
-    pub fn flush_async(&mut self) -> JoinHandle<std::io::Result<()>> {
-        self.wal.flush_open_segment_async()
+    pub fn flush_async(&mut self) -> Result<JoinHandle<std::io::Result<()>>> {
+        Ok(self.wal_mut()?.flush_open_segment_async())
     }
 
     pub fn path(&self) -> &Path {
-        self.wal.path()
+        &self.path
     }
 
-    pub fn last_index(&self) -> u64 {
-        self.wal.last_index()
+    pub fn first_index(&self) -> Result<u64> {
+        Ok(self.wal()?.first_index())
+    }
+
+    pub fn last_index(&self) -> Result<u64> {
+        Ok(self.wal()?.last_index())
+    }
+
+    /// Total size of WAL segment files on disk, in bytes.
+    /// Includes preallocated space of open segments.
+    pub fn disk_size(&self) -> std::io::Result<u64> {
+        let mut size = 0;
+        for entry in std::fs::read_dir(self.path())? {
+            let metadata = entry?.metadata()?;
+            if metadata.is_file() {
+                size += metadata.len();
+            }
+        }
+        Ok(size)
     }
 
     pub fn segment_capacity(&self) -> usize {
@@ -171,7 +279,7 @@ mod tests {
             assert_eq!(metadata.size() as usize, capacity);
         };
 
-        for (_idx, rec) in serde_wal.read(0) {
+        for (_idx, rec) in serde_wal.read(0).unwrap() {
             println!("{rec:?}");
         }
 
@@ -179,7 +287,7 @@ mod tests {
 
         serde_wal.write(&record).expect("Can't write");
 
-        let mut read_iterator = serde_wal.read(0);
+        let mut read_iterator = serde_wal.read(0).unwrap();
 
         let (idx1, record1) = read_iterator.next().unwrap();
         let (idx2, record2) = read_iterator.next().unwrap();
@@ -200,4 +308,73 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_wal_reopen() {
+        let dir = Builder::new().prefix("wal_test").tempdir().unwrap();
+        let wal_options = WalOptions {
+            segment_capacity: 1024 * 1024,
+            segment_queue_len: 0,
+        };
+
+        let mut serde_wal: SerdeWal<TestRecord> =
+            SerdeWal::new(dir.path().to_str().unwrap(), wal_options).unwrap();
+
+        let record = TestRecord::Struct1(TestInternalStruct1 { data: 10 });
+        serde_wal.write(&record).expect("Can't write");
+
+        let new_capacity = 2 * 1024 * 1024;
+        serde_wal
+            .reopen(WalOptions {
+                segment_capacity: new_capacity,
+                segment_queue_len: 1,
+            })
+            .unwrap();
+        assert_eq!(serde_wal.segment_capacity(), new_capacity);
+
+        let record = TestRecord::Struct2(TestInternalStruct2 { a: 12, b: 13 });
+        serde_wal.write(&record).expect("Can't write");
+
+        let records: Vec<_> = serde_wal.read(0).unwrap().map(|(idx, _)| idx).collect();
+        assert_eq!(records, vec![0, 1]);
+    }
+
+    #[test]
+    fn test_wal_failed_reopen_returns_errors() {
+        let dir = Builder::new().prefix("wal_test").tempdir().unwrap();
+        let wal_options = WalOptions {
+            segment_capacity: 1024 * 1024,
+            segment_queue_len: 0,
+        };
+
+        let mut serde_wal: SerdeWal<TestRecord> =
+            SerdeWal::new(dir.path().to_str().unwrap(), wal_options).unwrap();
+
+        let record = TestRecord::Struct1(TestInternalStruct1 { data: 10 });
+        serde_wal.write(&record).expect("Can't write");
+        serde_wal.flush().unwrap();
+
+        // Simulate failure of both reopen attempts
+        serde_wal.wal = WalState::Failed("test".to_string());
+
+        assert!(matches!(
+            serde_wal.write(&record),
+            Err(WalError::ClosedWalError(_))
+        ));
+        assert!(serde_wal.len().is_err());
+        assert!(serde_wal.read(0).is_err());
+        assert!(serde_wal.flush().is_err());
+        assert!(serde_wal.ack(0).is_err());
+        assert_eq!(serde_wal.path(), dir.path());
+
+        // Reopening recovers the WAL, even with unchanged options
+        serde_wal
+            .reopen(WalOptions {
+                segment_capacity: 1024 * 1024,
+                segment_queue_len: 0,
+            })
+            .unwrap();
+        let records: Vec<_> = serde_wal.read(0).unwrap().map(|(idx, _)| idx).collect();
+        assert_eq!(records, vec![0]);
+    }
 }
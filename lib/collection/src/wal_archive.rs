@@ -0,0 +1,260 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::thread::{self, JoinHandle};
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::shards::shard::ShardId;
+
+const ARCHIVE_FILE_EXTENSION: &str = "wal";
+
+/// Archiving of WAL records before the WAL is truncated.
+/// Archived records can be replayed on top of a snapshot to recover the collection
+/// to a later point in time.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
+pub struct WalArchiveConfig {
+    /// Directory for archive files.
+    /// Records of each shard are archived into `<path>/<collection_name>/<shard_id>`
+    pub path: String,
+    /// Program with arguments to upload an archive file into the external storage,
+    /// e.g. S3-compatible one. The program is executed directly, not through a shell.
+    /// In arguments, `{file}` is replaced with the path of the file,
+    /// `{key}` - with the path of the file relative to the archive directory.
+    /// Uploaded files are removed from the archive directory.
+    /// If not specified - files are kept in the archive directory.
+    #[serde(default)]
+    pub upload_command: Option<Vec<String>>,
+}
+
+/// Archive of WAL records of a single shard
+pub struct WalArchive {
+    root: PathBuf,
+    /// Directory of the shard, relative to the root
+    shard_dir: PathBuf,
+    upload_command: Option<Vec<String>>,
+    /// Records with lower sequence numbers are already archived
+    next_index: u64,
+    /// Background upload of pending archive files
+    upload: Option<JoinHandle<()>>,
+}
+
+impl WalArchive {
+    pub fn new(
+        config: &WalArchiveConfig,
+        collection_name: &str,
+        shard_id: ShardId,
+    ) -> io::Result<Self> {
+        let root = PathBuf::from(&config.path);
+        let shard_dir = Path::new(collection_name).join(shard_id.to_string());
+        fs::create_dir_all(root.join(&shard_dir))?;
+        Ok(WalArchive {
+            root,
+            shard_dir,
+            upload_command: config.upload_command.clone(),
+            next_index: 0,
+            upload: None,
+        })
+    }
+
+    pub fn next_index(&self) -> u64 {
+        self.next_index
+    }
+
+    /// Write sequential records into a new archive file and start upload of pending files.
+    /// Files are uploaded in background, so only writing of the local file may fail.
+    /// If upload fails, the file is kept and uploaded with the next one.
+    pub fn archive<R: Serialize>(&mut self, records: &[(u64, R)]) -> io::Result<()> {
+        let (first, last) = match (records.first(), records.last()) {
+            (Some((first, _)), Some((last, _))) => (*first, *last),
+            _ => return Ok(()),
+        };
+
+        let binary = serde_cbor::to_vec(&records)
+            .map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
+        let file_name = format!("{first:020}-{last:020}.{ARCHIVE_FILE_EXTENSION}");
+        let file_path = self.root.join(&self.shard_dir).join(file_name);
+        // Write into a temporary file first, so incomplete files are never uploaded
+        let tmp_path = file_path.with_extension("tmp");
+        fs::write(&tmp_path, binary)?;
+        fs::rename(&tmp_path, &file_path)?;
+
+        self.next_index = last + 1;
+        self.start_upload();
+        Ok(())
+    }
+
+    /// Start background upload of pending files, unless the previous one is still running.
+    /// Files, archived during a running upload, are picked up by the next one.
+    fn start_upload(&mut self) {
+        let command = match &self.upload_command {
+            Some(command) => command.clone(),
+            None => return,
+        };
+        if self
+            .upload
+            .as_ref()
+            .map_or(false, |upload| !upload.is_finished())
+        {
+            return;
+        }
+
+        let root = self.root.clone();
+        let shard_dir = self.shard_dir.clone();
+        let upload = thread::Builder::new()
+            .name("wal-archive-upload".to_string())
+            .spawn(move || {
+                if let Err(err) = upload_pending(&root, &shard_dir, &command) {
+                    log::warn!(
+                        "Failed to upload WAL archive of {}: {err}",
+                        shard_dir.display(),
+                    );
+                }
+            });
+        match upload {
+            Ok(upload) => self.upload = Some(upload),
+            Err(err) => log::warn!("Failed to start WAL archive upload: {err}"),
+        }
+    }
+
+    /// Wait until the running upload is finished
+    pub fn wait_upload(&mut self) {
+        if let Some(upload) = self.upload.take() {
+            if upload.join().is_err() {
+                log::warn!("WAL archive upload panicked");
+            }
+        }
+    }
+}
+
+/// Upload archive files of the shard directory in order, stop on the first failure
+fn upload_pending(root: &Path, shard_dir: &Path, command: &[String]) -> io::Result<()> {
+    let (program, args) = command
+        .split_first()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "Upload command is empty"))?;
+    for file_path in archived_files(&root.join(shard_dir))? {
+        let key = shard_dir.join(file_path.file_name().unwrap_or_default());
+        // Paths are passed as separate arguments, so they are never interpreted by a shell
+        let args: Vec<_> = args
+            .iter()
+            .map(|arg| {
+                arg.replace("{file}", &file_path.to_string_lossy())
+                    .replace("{key}", &key.to_string_lossy())
+            })
+            .collect();
+        let status = Command::new(program).args(&args).status()?;
+        if !status.success() {
+            return Err(io::Error::new(
+                io::ErrorKind::Other,
+                format!(
+                    "Upload command `{program}` failed for {} with {status}",
+                    key.display(),
+                ),
+            ));
+        }
+        fs::remove_file(&file_path)?;
+    }
+    Ok(())
+}
+
+/// Archive files of the shard directory, ordered by sequence numbers of their records
+pub fn archived_files(shard_dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for entry in fs::read_dir(shard_dir)? {
+        let path = entry?.path();
+        if path.is_file()
+            && path
+                .extension()
+                .map_or(false, |ext| ext == ARCHIVE_FILE_EXTENSION)
+        {
+            files.push(path);
+        }
+    }
+    // File names start with zero-padded sequence numbers
+    files.sort();
+    Ok(files)
+}
+
+pub fn read_archived_file<R: DeserializeOwned>(path: &Path) -> io::Result<Vec<(u64, R)>> {
+    let binary = fs::read(path)?;
+    serde_cbor::from_slice(&binary).map_err(|err| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("Can't read WAL archive file {}: {err}", path.display()),
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::Builder;
+
+    use super::*;
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_wal_archive() {
+        let dir = Builder::new().prefix("wal_archive").tempdir().unwrap();
+        let config = WalArchiveConfig {
+            path: dir.path().join("archive").to_string_lossy().into_owned(),
+            upload_command: Some(vec![
+                "install".to_string(),
+                "-D".to_string(),
+                "{file}".to_string(),
+                format!("{}/{{key}}", dir.path().join("uploaded").display()),
+            ]),
+        };
+
+        let mut archive = WalArchive::new(&config, "test", 0).unwrap();
+        archive
+            .archive(&[(1, "a".to_string()), (2, "b".to_string())])
+            .unwrap();
+        archive.wait_upload();
+        archive.archive::<String>(&[]).unwrap();
+        archive.archive(&[(3, "c".to_string())]).unwrap();
+        archive.wait_upload();
+        assert_eq!(archive.next_index(), 4);
+
+        let local_files = archived_files(&dir.path().join("archive/test/0")).unwrap();
+        assert!(local_files.is_empty());
+
+        let uploaded_files = archived_files(&dir.path().join("uploaded/test/0")).unwrap();
+        assert_eq!(uploaded_files.len(), 2);
+        let records: Vec<(u64, String)> = uploaded_files
+            .iter()
+            .flat_map(|path| read_archived_file(path).unwrap())
+            .collect();
+        assert_eq!(
+            records,
+            vec![
+                (1, "a".to_string()),
+                (2, "b".to_string()),
+                (3, "c".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_wal_archive_upload_is_not_shell() {
+        let dir = Builder::new().prefix("wal_archive").tempdir().unwrap();
+        let marker = dir.path().join("marker");
+        let config = WalArchiveConfig {
+            path: dir.path().join("archive").to_string_lossy().into_owned(),
+            upload_command: Some(vec!["false".to_string(), "{key}".to_string()]),
+        };
+
+        // Collection name with shell syntax must not be executed
+        let collection_name = format!("x;touch {}", marker.display());
+        let mut archive = WalArchive::new(&config, &collection_name, 0).unwrap();
+        archive.archive(&[(1, "a".to_string())]).unwrap();
+        archive.wait_upload();
+
+        assert!(!marker.exists());
+        // Failed upload keeps the file for the next attempt
+        let shard_dir = dir.path().join("archive").join(&collection_name).join("0");
+        assert_eq!(archived_files(&shard_dir).unwrap().len(), 1);
+    }
+}
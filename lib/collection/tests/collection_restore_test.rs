@@ -1,7 +1,5 @@
-use collection::operations::point_ops::{
-    Batch, PointInsertOperations, PointOperations, WriteOrdering,
-};
-use collection::operations::types::ScrollRequest;
+use collection::operations::point_ops::{Batch, PointInsertOperations, PointOperations};
+use collection::operations::types::{ScrollOrder, ScrollRequest};
 use collection::operations::CollectionUpdateOperations;
 use itertools::Itertools;
 use segment::types::{PayloadSelectorExclude, WithPayloadInterface};
@@ -41,7 +39,7 @@ async fn test_collection_reloading_with_shards(shard_number: u32) {
             })),
         );
         collection
-            .update_from_client(insert_points, true, WriteOrdering::default())
+            .update_from_client(insert_points, true, None)
             .await
             .unwrap();
         collection.before_drop().await;
@@ -76,7 +74,7 @@ async fn test_collection_payload_reloading_with_shards(shard_number: u32) {
             })),
         );
         collection
-            .update_from_client(insert_points, true, WriteOrdering::default())
+            .update_from_client(insert_points, true, None)
             .await
             .unwrap();
         collection.before_drop().await;
@@ -97,6 +95,11 @@ async fn test_collection_payload_reloading_with_shards(shard_number: u32) {
                 filter: None,
                 with_payload: Some(WithPayloadInterface::Bool(true)),
                 with_vector: true.into(),
+                order: ScrollOrder::Id,
+                seed: None,
+                planner_hint: None,
+                geo_order: None,
+                order_by: None,
             },
             None,
             None,
@@ -110,6 +113,8 @@ async fn test_collection_payload_reloading_with_shards(shard_number: u32) {
         .payload
         .as_ref()
         .expect("has payload")
+        .to_payload()
+        .unwrap()
         .get_value("k")
         .next()
         .expect("has value")
@@ -120,7 +125,13 @@ async fn test_collection_payload_reloading_with_shards(shard_number: u32) {
 
     eprintln!(
         "res = {:#?}",
-        res.points[0].payload.as_ref().unwrap().get_value("k")
+        res.points[0]
+            .payload
+            .as_ref()
+            .unwrap()
+            .to_payload()
+            .unwrap()
+            .get_value("k")
     );
     collection.before_drop().await;
 }
@@ -146,7 +157,7 @@ async fn test_collection_payload_custom_payload_with_shards(shard_number: u32) {
             })),
         );
         collection
-            .update_from_client(insert_points, true, WriteOrdering::default())
+            .update_from_client(insert_points, true, None)
             .await
             .unwrap();
         collection.before_drop().await;
@@ -169,6 +180,11 @@ async fn test_collection_payload_custom_payload_with_shards(shard_number: u32) {
                 filter: None,
                 with_payload: Some(WithPayloadInterface::Fields(vec![String::from("k2")])),
                 with_vector: true.into(),
+                order: ScrollOrder::Id,
+                seed: None,
+                planner_hint: None,
+                geo_order: None,
+                order_by: None,
             },
             None,
             None,
@@ -179,12 +195,16 @@ async fn test_collection_payload_custom_payload_with_shards(shard_number: u32) {
         .payload
         .as_ref()
         .expect("has payload")
+        .to_payload()
+        .unwrap()
         .is_empty());
 
     match res_with_custom_payload.points[1]
         .payload
         .as_ref()
         .expect("has payload")
+        .to_payload()
+        .unwrap()
         .get_value("k2")
         .next()
         .expect("has value")
@@ -202,6 +222,11 @@ async fn test_collection_payload_custom_payload_with_shards(shard_number: u32) {
                 filter: None,
                 with_payload: Some(PayloadSelectorExclude::new(vec!["k1".to_string()]).into()),
                 with_vector: false.into(),
+                order: ScrollOrder::Id,
+                seed: None,
+                planner_hint: None,
+                geo_order: None,
+                order_by: None,
             },
             None,
             None,
@@ -212,6 +237,8 @@ async fn test_collection_payload_custom_payload_with_shards(shard_number: u32) {
         .payload
         .as_ref()
         .expect("has payload")
+        .to_payload()
+        .unwrap()
         .is_empty());
 
     assert_eq!(
@@ -219,6 +246,8 @@ async fn test_collection_payload_custom_payload_with_shards(shard_number: u32) {
             .payload
             .as_ref()
             .expect("has payload")
+            .to_payload()
+            .unwrap()
             .len(),
         2
     );
@@ -227,6 +256,8 @@ async fn test_collection_payload_custom_payload_with_shards(shard_number: u32) {
         .payload
         .as_ref()
         .expect("has payload")
+        .to_payload()
+        .unwrap()
         .get_value("k3")
         .next()
         .expect("has value")
@@ -1,20 +1,42 @@
 use std::collections::HashSet;
-
+use std::num::{NonZeroU32, NonZeroU64};
+use std::time::Duration;
+
+use collection::discovery::discover_by;
+use collection::operations::cardinality_ops::{CardinalityEstimationRequest, FilteringStrategy};
+use collection::operations::config_diff::CollectionParamsDiff;
+use collection::operations::facet_ops::FacetRequest;
+use collection::operations::filter_validation::FilterValidation;
+use collection::operations::histogram_ops::HistogramRequest;
+use collection::operations::index_rebuild_ops::IndexRebuildStatus;
 use collection::operations::payload_ops::{PayloadOps, SetPayload};
 use collection::operations::point_ops::{Batch, PointOperations, PointStruct, WriteOrdering};
+use collection::operations::recall_ops::RecallEstimationRequest;
+use collection::operations::terms_ops::TermsRequest;
 use collection::operations::types::{
-    CountRequest, PointRequest, RecommendRequest, ScrollRequest, SearchRequest, UpdateStatus,
+    ArithmeticSearchRequest, CollectionError, ContextExamplePair, CountRequest, DiscoverRequest,
+    OrderBy, PointRequest, RecommendExample, RecommendRequest, RecommendStrategy, ScrollOrder,
+    ScrollRequest, SearchRequest, SearchRequestBatch, UpdateStatus, VectorParams, VectorProjection,
+    WeightedExample,
 };
-use collection::operations::CollectionUpdateOperations;
+use collection::operations::vacuum_ops::VacuumRequest;
+use collection::operations::{CollectionUpdateOperations, CreateIndex, FieldIndexOperations};
 use collection::recommendations::recommend_by;
+use collection::shards::transfer::shard_transfer::ShardTransfer;
+use collection::vector_arithmetic::search_by_arithmetic;
 use itertools::Itertools;
+use segment::data_types::order_by::Direction;
 use segment::data_types::vectors::VectorStruct;
 use segment::types::{
-    Condition, FieldCondition, Filter, HasIdCondition, Payload, PointIdType, WithPayloadInterface,
+    Condition, Distance, FieldCondition, Filter, HasIdCondition, Payload, PayloadFieldSchema,
+    PayloadSchemaType, PointIdType, ScalarQuantizationConfig, WithPayloadInterface,
 };
 use tempfile::Builder;
 
-use crate::common::{load_local_collection, simple_collection_fixture, N_SHARDS};
+use crate::common::{
+    create_field_index, load_local_collection, new_local_collection, payload_collection_fixture,
+    simple_collection_config, simple_collection_fixture, N_SHARDS,
+};
 
 mod common;
 
@@ -49,7 +71,7 @@ async fn test_collection_updater_with_shards(shard_number: u32) {
     );
 
     let insert_result = collection
-        .update_from_client(insert_points, true, WriteOrdering::default())
+        .update_from_client(insert_points, true, None)
         .await;
 
     match insert_result {
@@ -68,6 +90,8 @@ async fn test_collection_updater_with_shards(shard_number: u32) {
         limit: 3,
         offset: 0,
         score_threshold: None,
+        with_highlights: None,
+        with_geo_distance: None,
     };
 
     let search_res = collection.search(search_request, None, None).await;
@@ -107,7 +131,7 @@ async fn test_collection_search_with_payload_and_vector_with_shards(shard_number
     );
 
     let insert_result = collection
-        .update_from_client(insert_points, true, WriteOrdering::default())
+        .update_from_client(insert_points, true, None)
         .await;
 
     match insert_result {
@@ -126,6 +150,8 @@ async fn test_collection_search_with_payload_and_vector_with_shards(shard_number
         limit: 3,
         offset: 0,
         score_threshold: None,
+        with_highlights: None,
+        with_geo_distance: None,
     };
 
     let search_res = collection.search(search_request, None, None).await;
@@ -153,6 +179,8 @@ async fn test_collection_search_with_payload_and_vector_with_shards(shard_number
             values_count: None,
         }))),
         exact: true,
+        planner_hint: None,
+        distinct: None,
     };
 
     let count_res = collection.count(count_request, None).await.unwrap();
@@ -193,7 +221,7 @@ async fn test_collection_loading_with_shards(shard_number: u32) {
         );
 
         collection
-            .update_from_client(insert_points, true, WriteOrdering::default())
+            .update_from_client(insert_points, true, None)
             .await
             .unwrap();
 
@@ -207,7 +235,7 @@ async fn test_collection_loading_with_shards(shard_number: u32) {
             }));
 
         collection
-            .update_from_client(assign_payload, true, WriteOrdering::default())
+            .update_from_client(assign_payload, true, None)
             .await
             .unwrap();
         collection.before_drop().await;
@@ -234,7 +262,7 @@ async fn test_collection_loading_with_shards(shard_number: u32) {
 
     for record in retrieved {
         if record.id == 2.into() {
-            let non_empty_payload = record.payload.unwrap();
+            let non_empty_payload = record.payload.unwrap().into_payload().unwrap();
 
             assert_eq!(non_empty_payload.len(), 1)
         }
@@ -270,11 +298,15 @@ fn test_deserialization2() {
                 id: 0.into(),
                 vector: vec![1.0, 0.0, 1.0, 1.0].into(),
                 payload: None,
+                sequence: None,
+                content_hash: None,
             },
             PointStruct {
                 id: 1.into(),
                 vector: vec![1.0, 0.0, 1.0, 0.0].into(),
                 payload: None,
+                sequence: None,
+                content_hash: None,
             },
         ]
         .into(),
@@ -324,7 +356,7 @@ async fn test_recommendation_api_with_shards(shard_number: u32) {
     );
 
     collection
-        .update_from_client(insert_points, true, WriteOrdering::default())
+        .update_from_client(insert_points, true, None)
         .await
         .unwrap();
     let result = recommend_by(
@@ -344,6 +376,243 @@ async fn test_recommendation_api_with_shards(shard_number: u32) {
     let top1 = &result[0];
 
     assert!(top1.id == 5.into() || top1.id == 6.into());
+
+    // Raw vectors are used as examples without lookup
+    let result = recommend_by(
+        RecommendRequest {
+            positive: vec![RecommendExample::Vector(vec![0.0, 0.0, 1.0, 0.0])],
+            negative: vec![8.into()],
+            limit: 5,
+            ..Default::default()
+        },
+        &collection,
+        |_name| async { unreachable!("Should not be called in this test") },
+        None,
+    )
+    .await
+    .unwrap();
+    assert!(result[0].id == 5.into() || result[0].id == 6.into());
+
+    let wrong_dimension = recommend_by(
+        RecommendRequest {
+            positive: vec![RecommendExample::Vector(vec![0.0, 1.0])],
+            limit: 5,
+            ..Default::default()
+        },
+        &collection,
+        |_name| async { unreachable!("Should not be called in this test") },
+        None,
+    )
+    .await;
+    assert!(wrong_dimension.is_err());
+
+    let result = recommend_by(
+        RecommendRequest {
+            positive: vec![0.into()],
+            negative: vec![8.into()],
+            strategy: Some(RecommendStrategy::BestScore),
+            limit: 5,
+            ..Default::default()
+        },
+        &collection,
+        |_name| async { unreachable!("Should not be called in this test") },
+        None,
+    )
+    .await
+    .unwrap();
+    assert_eq!(result.len(), 5);
+    let top1 = &result[0];
+    assert!(top1.id == 5.into() || top1.id == 6.into());
+    assert!(top1.score > 0.0);
+    // Point 7 is as close to the negative example as to the positive one
+    assert!(result
+        .iter()
+        .filter(|point| point.id == 7.into())
+        .all(|point| point.score < 0.0));
+
+    // The heavier example pulls the results towards itself
+    for strategy in [
+        RecommendStrategy::AverageVector,
+        RecommendStrategy::BestScore,
+    ] {
+        let result = recommend_by(
+            RecommendRequest {
+                positive: vec![1.into(), 3.into()],
+                positive_weights: Some(vec![1.0, 3.0]),
+                strategy: Some(strategy),
+                limit: 1,
+                ..Default::default()
+            },
+            &collection,
+            |_name| async { unreachable!("Should not be called in this test") },
+            None,
+        )
+        .await
+        .unwrap();
+        assert_eq!(result[0].id, 4.into());
+    }
+
+    let wrong_weights = recommend_by(
+        RecommendRequest {
+            positive: vec![1.into(), 3.into()],
+            positive_weights: Some(vec![1.0]),
+            limit: 1,
+            ..Default::default()
+        },
+        &collection,
+        |_name| async { unreachable!("Should not be called in this test") },
+        None,
+    )
+    .await;
+    assert!(wrong_weights.is_err());
+
+    collection.before_drop().await;
+}
+
+#[tokio::test]
+async fn test_discovery_api() {
+    test_discovery_api_with_shards(1).await;
+    test_discovery_api_with_shards(N_SHARDS).await;
+}
+
+async fn test_discovery_api_with_shards(shard_number: u32) {
+    let collection_dir = Builder::new().prefix("collection").tempdir().unwrap();
+    let mut collection = simple_collection_fixture(collection_dir.path(), shard_number).await;
+
+    let insert_points = CollectionUpdateOperations::PointOperation(
+        Batch {
+            ids: vec![0, 1, 2, 3, 4, 5, 6, 7, 8]
+                .into_iter()
+                .map(|x| x.into())
+                .collect_vec(),
+            vectors: vec![
+                vec![0.0, 0.0, 1.0, 1.0],
+                vec![1.0, 0.0, 0.0, 0.0],
+                vec![1.0, 0.0, 0.0, 0.0],
+                vec![0.0, 1.0, 0.0, 0.0],
+                vec![0.0, 1.0, 0.0, 0.0],
+                vec![0.0, 0.0, 1.0, 0.0],
+                vec![0.0, 0.0, 1.0, 0.0],
+                vec![0.0, 0.0, 0.0, 1.0],
+                vec![0.0, 0.0, 0.0, 1.0],
+            ]
+            .into(),
+            payloads: None,
+        }
+        .into(),
+    );
+
+    collection
+        .update_from_client(insert_points, true, None)
+        .await
+        .unwrap();
+    let result = discover_by(
+        DiscoverRequest {
+            target: 5.into(),
+            context: vec![ContextExamplePair {
+                positive: RecommendExample::Vector(vec![0.0, 0.0, 0.0, 1.0]),
+                negative: RecommendExample::Vector(vec![0.0, 1.0, 0.0, 0.0]),
+            }],
+            filter: None,
+            params: None,
+            limit: 3,
+            offset: 0,
+            with_payload: None,
+            with_vector: None,
+            using: None,
+            lookup_from: None,
+        },
+        &collection,
+        |_name| async { unreachable!("Should not be called in this test") },
+        None,
+    )
+    .await
+    .unwrap();
+    assert_eq!(result.len(), 3);
+    // Point 6 is the closest to the target, but not closer to the positive context example
+    assert_eq!(result[0].id, 0.into());
+    assert!(result[0].score > 0.0);
+    assert!(result.iter().all(|point| point.id != 5.into()));
+    assert!(result
+        .iter()
+        .filter(|point| point.id == 6.into())
+        .all(|point| point.score < 0.0));
+    collection.before_drop().await;
+}
+
+#[tokio::test]
+async fn test_vector_arithmetic_api() {
+    test_vector_arithmetic_api_with_shards(1).await;
+    test_vector_arithmetic_api_with_shards(N_SHARDS).await;
+}
+
+async fn test_vector_arithmetic_api_with_shards(shard_number: u32) {
+    let collection_dir = Builder::new().prefix("collection").tempdir().unwrap();
+    let mut collection = simple_collection_fixture(collection_dir.path(), shard_number).await;
+
+    let insert_points = CollectionUpdateOperations::PointOperation(
+        Batch {
+            ids: vec![0, 1, 2, 3, 4, 5, 6, 7, 8]
+                .into_iter()
+                .map(|x| x.into())
+                .collect_vec(),
+            vectors: vec![
+                vec![0.0, 0.0, 1.0, 1.0],
+                vec![1.0, 0.0, 0.0, 0.0],
+                vec![1.0, 0.0, 0.0, 0.0],
+                vec![0.0, 1.0, 0.0, 0.0],
+                vec![0.0, 1.0, 0.0, 0.0],
+                vec![0.0, 0.0, 1.0, 0.0],
+                vec![0.0, 0.0, 1.0, 0.0],
+                vec![0.0, 0.0, 0.0, 1.0],
+                vec![0.0, 0.0, 0.0, 1.0],
+            ]
+            .into(),
+            payloads: None,
+        }
+        .into(),
+    );
+
+    collection
+        .update_from_client(insert_points, true, None)
+        .await
+        .unwrap();
+    let result = search_by_arithmetic(
+        ArithmeticSearchRequest {
+            terms: vec![
+                WeightedExample {
+                    example: 1.into(),
+                    weight: 1.0,
+                },
+                WeightedExample {
+                    example: 3.into(),
+                    weight: -0.5,
+                },
+                WeightedExample {
+                    example: RecommendExample::Vector(vec![0.0, 0.0, 0.0, 1.0]),
+                    weight: 0.8,
+                },
+            ],
+            filter: None,
+            params: None,
+            limit: 3,
+            offset: 0,
+            with_payload: None,
+            with_vector: None,
+            score_threshold: None,
+            using: None,
+            lookup_from: None,
+        },
+        &collection,
+        |_name| async { unreachable!("Should not be called in this test") },
+        None,
+    )
+    .await
+    .unwrap();
+    assert_eq!(result.len(), 3);
+    assert_eq!(result[0].id, 2.into());
+    assert_eq!(result[0].score, 1.0);
+    assert!(result[1..].iter().all(|point| point.score == 0.8));
     collection.before_drop().await;
 }
 
@@ -381,7 +650,7 @@ async fn test_read_api_with_shards(shard_number: u32) {
     ));
 
     collection
-        .update_from_client(insert_points, true, WriteOrdering::default())
+        .update_from_client(insert_points, true, None)
         .await
         .unwrap();
 
@@ -393,6 +662,11 @@ async fn test_read_api_with_shards(shard_number: u32) {
                 filter: None,
                 with_payload: Some(WithPayloadInterface::Bool(true)),
                 with_vector: false.into(),
+                order: ScrollOrder::Id,
+                seed: None,
+                planner_hint: None,
+                geo_order: None,
+                order_by: None,
             },
             None,
             None,
@@ -436,7 +710,7 @@ async fn test_collection_delete_points_by_filter_with_shards(shard_number: u32)
     );
 
     let insert_result = collection
-        .update_from_client(insert_points, true, WriteOrdering::default())
+        .update_from_client(insert_points, true, None)
         .await;
 
     match insert_result {
@@ -459,7 +733,7 @@ async fn test_collection_delete_points_by_filter_with_shards(shard_number: u32)
     );
 
     let delete_result = collection
-        .update_from_client(delete_points, true, WriteOrdering::default())
+        .update_from_client(delete_points, true, None)
         .await;
 
     match delete_result {
@@ -477,6 +751,11 @@ async fn test_collection_delete_points_by_filter_with_shards(shard_number: u32)
                 filter: None,
                 with_payload: Some(WithPayloadInterface::Bool(false)),
                 with_vector: false.into(),
+                order: ScrollOrder::Id,
+                seed: None,
+                planner_hint: None,
+                geo_order: None,
+                order_by: None,
             },
             None,
             None,
@@ -491,3 +770,1058 @@ async fn test_collection_delete_points_by_filter_with_shards(shard_number: u32)
     assert_eq!(result.points.get(2).unwrap().id, 4.into());
     collection.before_drop().await;
 }
+
+#[tokio::test]
+async fn test_collection_recall_estimation() {
+    let collection_dir = Builder::new().prefix("collection").tempdir().unwrap();
+
+    let mut collection = simple_collection_fixture(collection_dir.path(), N_SHARDS).await;
+
+    let insert_points = CollectionUpdateOperations::PointOperation(
+        Batch {
+            ids: (0..20).map(|x: u64| x.into()).collect_vec(),
+            vectors: (0..20)
+                .map(|x| vec![x as f32, 1.0, 0.0, (x % 3) as f32])
+                .collect_vec()
+                .into(),
+            payloads: None,
+        }
+        .into(),
+    );
+
+    collection
+        .update_from_client(insert_points, true, None)
+        .await
+        .unwrap();
+
+    let result = collection
+        .estimate_recall(RecallEstimationRequest {
+            vector: None,
+            samples: Some(5),
+            limit: Some(3),
+            params: None,
+        })
+        .await
+        .unwrap();
+
+    assert_eq!(result.limit, 3);
+    assert!(!result.segments.is_empty());
+    for segment in result.segments.iter().filter(|segment| segment.samples > 0) {
+        // Segments are not indexed, so approximate search is the same as exact search
+        assert_eq!(segment.recall, Some(1.0));
+        assert!(segment.samples <= 5);
+    }
+
+    let unknown_vector = collection
+        .estimate_recall(RecallEstimationRequest {
+            vector: Some("unknown".to_string()),
+            ..Default::default()
+        })
+        .await;
+    assert!(unknown_vector.is_err());
+
+    collection.before_drop().await;
+}
+
+#[tokio::test]
+async fn test_collection_vacuum() {
+    let collection_dir = Builder::new().prefix("collection").tempdir().unwrap();
+
+    let mut collection = simple_collection_fixture(collection_dir.path(), 1).await;
+
+    let insert_points = CollectionUpdateOperations::PointOperation(
+        Batch {
+            ids: (0..10).map(|x: u64| x.into()).collect_vec(),
+            vectors: (0..10)
+                .map(|x| vec![x as f32, 1.0, 0.0, 1.0])
+                .collect_vec()
+                .into(),
+            payloads: None,
+        }
+        .into(),
+    );
+    collection
+        .update_from_client(insert_points, true, None)
+        .await
+        .unwrap();
+
+    // Nothing is deleted, so nothing is rebuilt
+    let result = collection
+        .vacuum(VacuumRequest {
+            deleted_threshold: Some(0.1),
+        })
+        .await
+        .unwrap();
+    assert_eq!(result.deleted_threshold, 0.1);
+    assert_eq!(result.optimizations_started, 0);
+    assert!(result.segments.iter().all(|segment| !segment.vacuum));
+
+    let delete_points = CollectionUpdateOperations::PointOperation(PointOperations::DeletePoints {
+        ids: (0..5).map(|x: u64| x.into()).collect_vec(),
+    });
+    collection
+        .update_from_client(delete_points, true, None)
+        .await
+        .unwrap();
+
+    let result = collection
+        .vacuum(VacuumRequest {
+            deleted_threshold: Some(0.1),
+        })
+        .await
+        .unwrap();
+
+    let deleted_count: usize = result
+        .segments
+        .iter()
+        .map(|segment| segment.deleted_count)
+        .sum();
+    assert_eq!(deleted_count, 5);
+    assert!(result.segments.iter().any(|segment| segment.vacuum));
+
+    collection.before_drop().await;
+}
+
+#[tokio::test]
+async fn test_collection_rebuild_field_index() {
+    let collection_dir = Builder::new().prefix("collection").tempdir().unwrap();
+
+    let mut collection = payload_collection_fixture(
+        collection_dir.path(),
+        1,
+        r#"[{ "k": "v1" }, { "k": "v2" }]"#,
+    )
+    .await;
+
+    // Field is not indexed yet
+    assert!(collection
+        .rebuild_field_index("k".to_string())
+        .await
+        .is_err());
+
+    create_field_index(&collection, "k", PayloadSchemaType::Keyword).await;
+
+    let info = collection
+        .rebuild_field_index("k".to_string())
+        .await
+        .unwrap();
+    assert!(info.segments_total > 0);
+
+    let info = loop {
+        let info = collection
+            .field_index_rebuild_info(&"k".to_string())
+            .unwrap();
+        if info.status != IndexRebuildStatus::Running {
+            break info;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    };
+    assert_eq!(info.status, IndexRebuildStatus::Completed);
+    assert_eq!(info.segments_rebuilt, info.segments_total);
+
+    let count_request = CountRequest {
+        filter: Some(Filter::new_must(Condition::Field(FieldCondition {
+            key: "k".to_string(),
+            r#match: Some(serde_json::from_str(r#"{ "value": "v2" }"#).unwrap()),
+            range: None,
+            geo_bounding_box: None,
+            geo_radius: None,
+            values_count: None,
+        }))),
+        exact: true,
+        planner_hint: None,
+        distinct: None,
+    };
+    let count_res = collection.count(count_request, None).await.unwrap();
+    assert_eq!(count_res.count, 1);
+
+    let collection_info = collection.info(None).await.unwrap();
+    assert!(collection_info.payload_schema.contains_key("k"));
+
+    collection.before_drop().await;
+}
+
+#[tokio::test]
+async fn test_collection_field_terms() {
+    let collection_dir = Builder::new().prefix("collection").tempdir().unwrap();
+
+    let mut collection = payload_collection_fixture(
+        collection_dir.path(),
+        1,
+        r#"[{ "k": ["a", "b"], "n": 1 }, { "k": "b", "n": 2 }, { "k": "c", "n": 2 }]"#,
+    )
+    .await;
+
+    // Field is not indexed yet
+    assert!(collection
+        .field_terms("k".to_string(), TermsRequest::default())
+        .await
+        .is_err());
+
+    create_field_index(&collection, "k", PayloadSchemaType::Keyword).await;
+
+    let terms = collection
+        .field_terms("k".to_string(), TermsRequest::default())
+        .await
+        .unwrap()
+        .terms;
+    let terms: Vec<_> = terms
+        .iter()
+        .map(|term| (term.term.as_str(), term.count))
+        .collect();
+    assert_eq!(terms, vec![("b", 2), ("a", 1), ("c", 1)]);
+
+    let request = TermsRequest {
+        filter: Some(Filter::new_must(Condition::Field(FieldCondition {
+            key: "n".to_string(),
+            r#match: Some(serde_json::from_str(r#"{ "value": 2 }"#).unwrap()),
+            range: None,
+            geo_bounding_box: None,
+            geo_radius: None,
+            values_count: None,
+        }))),
+        limit: Some(1),
+    };
+    let terms = collection
+        .field_terms("k".to_string(), request)
+        .await
+        .unwrap()
+        .terms;
+    assert_eq!(terms.len(), 1);
+    assert_eq!(terms[0].term, "b");
+    assert_eq!(terms[0].count, 1);
+
+    collection.before_drop().await;
+}
+
+#[tokio::test]
+async fn test_collection_facet() {
+    let collection_dir = Builder::new().prefix("collection").tempdir().unwrap();
+
+    let mut collection = payload_collection_fixture(
+        collection_dir.path(),
+        1,
+        r#"[{ "k": ["a", "b"], "n": 1 }, { "k": "b", "n": 2 }, { "k": "c", "n": 2 }]"#,
+    )
+    .await;
+
+    let facet_request = |key: &str, filter| FacetRequest {
+        key: key.to_string(),
+        filter,
+        limit: None,
+    };
+
+    // Field is not indexed yet
+    assert!(collection.facet(facet_request("k", None)).await.is_err());
+
+    create_field_index(&collection, "k", PayloadSchemaType::Keyword).await;
+    create_field_index(&collection, "n", PayloadSchemaType::Integer).await;
+
+    // Only keyword index can be used
+    assert!(collection.facet(facet_request("n", None)).await.is_err());
+
+    let hits = collection
+        .facet(facet_request("k", None))
+        .await
+        .unwrap()
+        .hits;
+    let hits: Vec<_> = hits
+        .iter()
+        .map(|hit| (hit.value.as_str(), hit.count))
+        .collect();
+    assert_eq!(hits, vec![("b", 2), ("a", 1), ("c", 1)]);
+
+    let filter = Filter::new_must(Condition::Field(FieldCondition {
+        key: "n".to_string(),
+        r#match: Some(serde_json::from_str(r#"{ "value": 2 }"#).unwrap()),
+        range: None,
+        geo_bounding_box: None,
+        geo_radius: None,
+        values_count: None,
+    }));
+    let hits = collection
+        .facet(facet_request("k", Some(filter)))
+        .await
+        .unwrap()
+        .hits;
+    let hits: Vec<_> = hits
+        .iter()
+        .map(|hit| (hit.value.as_str(), hit.count))
+        .collect();
+    assert_eq!(hits, vec![("b", 1), ("c", 1)]);
+
+    collection.before_drop().await;
+}
+
+#[tokio::test]
+async fn test_collection_count_distinct() {
+    let collection_dir = Builder::new().prefix("collection").tempdir().unwrap();
+
+    let mut collection = payload_collection_fixture(
+        collection_dir.path(),
+        1,
+        r#"[{"k": ["a", "b"], "n": 1}, {"k": "b", "n": 2}, {"k": "c", "n": 2}, {"n": 3}]"#,
+    )
+    .await;
+
+    let count_request = |distinct: &str, filter| CountRequest {
+        filter,
+        exact: true,
+        planner_hint: None,
+        distinct: Some(distinct.to_string()),
+    };
+
+    // Field is not indexed yet
+    assert!(collection
+        .count(count_request("k", None), None)
+        .await
+        .is_err());
+
+    create_field_index(&collection, "k", PayloadSchemaType::Keyword).await;
+    create_field_index(&collection, "n", PayloadSchemaType::Integer).await;
+
+    let count_res = collection
+        .count(count_request("k", None), None)
+        .await
+        .unwrap();
+    assert_eq!(count_res.count, 4);
+    assert_eq!(count_res.distinct, Some(3));
+
+    let count_res = collection
+        .count(count_request("n", None), None)
+        .await
+        .unwrap();
+    assert_eq!(count_res.distinct, Some(3));
+
+    let filter = Filter::new_must(Condition::Field(FieldCondition {
+        key: "n".to_string(),
+        r#match: Some(serde_json::from_str(r#"{ "value": 2 }"#).unwrap()),
+        range: None,
+        geo_bounding_box: None,
+        geo_radius: None,
+        values_count: None,
+    }));
+    let count_res = collection
+        .count(count_request("k", Some(filter)), None)
+        .await
+        .unwrap();
+    assert_eq!(count_res.count, 2);
+    assert_eq!(count_res.distinct, Some(2));
+
+    collection.before_drop().await;
+}
+
+#[tokio::test]
+async fn test_collection_field_histogram() {
+    let collection_dir = Builder::new().prefix("collection").tempdir().unwrap();
+
+    let mut collection = payload_collection_fixture(
+        collection_dir.path(),
+        1,
+        r#"[{ "n": [0, 1, 10] }, { "n": 4 }, { "n": 6, "k": "a" }, { "k": "a" }]"#,
+    )
+    .await;
+
+    // Field is not indexed yet
+    assert!(collection
+        .field_histogram("n".to_string(), HistogramRequest::default())
+        .await
+        .is_err());
+
+    create_field_index(&collection, "n", PayloadSchemaType::Integer).await;
+
+    let request = HistogramRequest {
+        filter: None,
+        buckets: Some(2),
+    };
+    let buckets = collection
+        .field_histogram("n".to_string(), request)
+        .await
+        .unwrap()
+        .buckets;
+    let buckets: Vec<_> = buckets
+        .iter()
+        .map(|bucket| (bucket.from, bucket.to, bucket.count))
+        .collect();
+    // Values 0 and 1 of the first point fall into the same bucket and are counted once
+    assert_eq!(buckets, vec![(0.0, 5.0, 2), (5.0, 10.0, 2)]);
+
+    let request = HistogramRequest {
+        filter: Some(Filter::new_must(Condition::Field(FieldCondition {
+            key: "k".to_string(),
+            r#match: Some(serde_json::from_str(r#"{ "value": "a" }"#).unwrap()),
+            range: None,
+            geo_bounding_box: None,
+            geo_radius: None,
+            values_count: None,
+        }))),
+        buckets: Some(3),
+    };
+    let buckets = collection
+        .field_histogram("n".to_string(), request)
+        .await
+        .unwrap()
+        .buckets;
+    assert_eq!(buckets.len(), 3);
+    assert_eq!(buckets[0].from, 6.0);
+    assert_eq!(buckets[0].count, 1);
+    assert_eq!(buckets[2].to, 6.0);
+
+    collection.before_drop().await;
+}
+
+#[tokio::test]
+async fn test_scroll_order_by() {
+    let collection_dir = Builder::new().prefix("collection").tempdir().unwrap();
+
+    let mut collection = payload_collection_fixture(
+        collection_dir.path(),
+        N_SHARDS,
+        r#"[{ "n": 5 }, { "n": [2, 9] }, { "n": 5 }, { "n": 7 }, { "k": "a" }, { "n": 1 }]"#,
+    )
+    .await;
+
+    let order_by = OrderBy {
+        key: "n".to_string(),
+        direction: Direction::Asc,
+        start_from: None,
+    };
+    let request = ScrollRequest {
+        order_by: Some(order_by.clone()),
+        ..Default::default()
+    };
+    // Field is not indexed yet
+    assert!(collection.scroll_by(request, None, None).await.is_err());
+
+    create_field_index(&collection, "n", PayloadSchemaType::Integer).await;
+
+    // Read all pages, continuing from the value and id of the first point of the next page
+    let mut pages = vec![];
+    let mut offset = None;
+    let mut start_from = None;
+    loop {
+        let request = ScrollRequest {
+            offset,
+            limit: Some(2),
+            order_by: Some(OrderBy {
+                start_from,
+                ..order_by.clone()
+            }),
+            ..Default::default()
+        };
+        let result = collection.scroll_by(request, None, None).await.unwrap();
+        pages.push(
+            result
+                .points
+                .iter()
+                .map(|point| point.id)
+                .collect::<Vec<_>>(),
+        );
+        if result.next_page_offset.is_none() {
+            assert!(result.next_page_order_value.is_none());
+            break;
+        }
+        offset = result.next_page_offset;
+        start_from = result.next_page_order_value;
+    }
+    // Point with several values is ordered by the smallest one, points with equal values by id
+    assert_eq!(
+        pages,
+        vec![
+            vec![5.into(), 1.into()],
+            vec![0.into(), 2.into()],
+            vec![3.into()],
+        ]
+    );
+
+    let request = ScrollRequest {
+        limit: Some(3),
+        order_by: Some(OrderBy {
+            direction: Direction::Desc,
+            ..order_by.clone()
+        }),
+        ..Default::default()
+    };
+    let result = collection.scroll_by(request, None, None).await.unwrap();
+    let ids: Vec<_> = result.points.iter().map(|point| point.id).collect();
+    assert_eq!(ids, vec![1.into(), 3.into(), 0.into()]);
+    assert_eq!(result.next_page_offset, Some(2.into()));
+    assert_eq!(result.next_page_order_value, Some(5.0));
+
+    collection.before_drop().await;
+}
+
+#[tokio::test]
+async fn test_collection_estimate_cardinality() {
+    let collection_dir = Builder::new().prefix("collection").tempdir().unwrap();
+
+    let mut collection = simple_collection_fixture(collection_dir.path(), 1).await;
+
+    let insert_points = CollectionUpdateOperations::PointOperation(
+        Batch {
+            ids: vec![0.into(), 1.into(), 2.into()],
+            vectors: vec![
+                vec![1.0, 0.0, 1.0, 1.0],
+                vec![1.0, 0.0, 1.0, 0.0],
+                vec![1.0, 1.0, 1.0, 1.0],
+            ]
+            .into(),
+            payloads: serde_json::from_str(
+                r#"[{ "k": "a", "n": 1 }, { "k": "b", "n": 2 }, { "k": "a", "n": 3 }]"#,
+            )
+            .unwrap(),
+        }
+        .into(),
+    );
+    collection
+        .update_from_client(insert_points, true, None)
+        .await
+        .unwrap();
+
+    let create_index = CollectionUpdateOperations::FieldIndexOperation(
+        FieldIndexOperations::CreateIndex(CreateIndex {
+            field_name: "k".to_string(),
+            field_schema: Some(PayloadFieldSchema::FieldType(PayloadSchemaType::Keyword)),
+        }),
+    );
+    collection
+        .update_from_client(create_index, true, None)
+        .await
+        .unwrap();
+
+    let request = |json: &str| CardinalityEstimationRequest {
+        filter: Some(serde_json::from_str(json).unwrap()),
+    };
+
+    let indexed = collection
+        .estimate_cardinality(request(
+            r#"{ "must": [{ "key": "k", "match": { "value": "a" } }] }"#,
+        ))
+        .await
+        .unwrap();
+    assert_eq!(indexed.exp, 2);
+    assert_eq!(indexed.shards.len(), 1);
+    assert!(indexed.shards[0]
+        .segments
+        .iter()
+        .any(|segment| segment.strategy == FilteringStrategy::Index));
+
+    let not_indexed = collection
+        .estimate_cardinality(request(
+            r#"{ "must": [{ "key": "n", "range": { "gt": 1 } }] }"#,
+        ))
+        .await
+        .unwrap();
+    assert_eq!(not_indexed.max, 3);
+    assert!(not_indexed.shards[0]
+        .segments
+        .iter()
+        .all(|segment| segment.strategy == FilteringStrategy::FullScan));
+
+    collection.before_drop().await;
+}
+
+#[tokio::test]
+async fn test_collection_check_filter() {
+    let collection_dir = Builder::new().prefix("collection").tempdir().unwrap();
+
+    let mut collection = simple_collection_fixture(collection_dir.path(), 1).await;
+
+    let insert_points = CollectionUpdateOperations::PointOperation(
+        Batch {
+            ids: vec![0.into(), 1.into()],
+            vectors: vec![vec![1.0, 0.0, 1.0, 1.0], vec![1.0, 0.0, 1.0, 0.0]].into(),
+            payloads: serde_json::from_str(r#"[{ "k": "a", "n": 1 }, { "k": "b" }]"#).unwrap(),
+        }
+        .into(),
+    );
+    collection
+        .update_from_client(insert_points, true, None)
+        .await
+        .unwrap();
+
+    let create_index = CollectionUpdateOperations::FieldIndexOperation(
+        FieldIndexOperations::CreateIndex(CreateIndex {
+            field_name: "k".to_string(),
+            field_schema: Some(PayloadFieldSchema::FieldType(PayloadSchemaType::Keyword)),
+        }),
+    );
+    collection
+        .update_from_client(create_index, true, None)
+        .await
+        .unwrap();
+
+    let filter = |json: &str| -> Filter { serde_json::from_str(json).unwrap() };
+    let strict = Some(FilterValidation::Strict);
+
+    let valid = filter(
+        r#"{ "must": [{ "key": "k", "match": { "value": "a" } }, { "key": "n", "range": { "gt": 0 } }] }"#,
+    );
+    assert!(collection.check_filter(Some(&valid), strict).await.is_ok());
+
+    let unknown_key = filter(r#"{ "must": [{ "key": "color", "match": { "value": "red" } }] }"#);
+    assert!(collection
+        .check_filter(Some(&unknown_key), strict)
+        .await
+        .is_err());
+    // Problems are only reported, if the validation is not strict
+    assert!(collection
+        .check_filter(Some(&unknown_key), Some(FilterValidation::Warn))
+        .await
+        .is_ok());
+    assert!(collection
+        .check_filter(Some(&unknown_key), None)
+        .await
+        .is_ok());
+
+    let mismatched_type = filter(r#"{ "must": [{ "key": "k", "range": { "gt": 0 } }] }"#);
+    assert!(collection
+        .check_filter(Some(&mismatched_type), strict)
+        .await
+        .is_err());
+
+    collection.before_drop().await;
+}
+
+#[tokio::test]
+async fn test_collection_flush() {
+    let collection_dir = Builder::new().prefix("collection").tempdir().unwrap();
+
+    let mut collection = simple_collection_fixture(collection_dir.path(), 1).await;
+
+    let insert_points = CollectionUpdateOperations::PointOperation(
+        Batch {
+            ids: vec![0, 1, 2].into_iter().map(|x| x.into()).collect_vec(),
+            vectors: vec![
+                vec![1.0, 0.0, 1.0, 1.0],
+                vec![1.0, 0.0, 1.0, 0.0],
+                vec![1.0, 1.0, 1.0, 1.0],
+            ]
+            .into(),
+            payloads: None,
+        }
+        .into(),
+    );
+    let insert_result = collection
+        .update_from_client(insert_points, true, None)
+        .await
+        .unwrap();
+
+    let flush_result = collection.flush().await.unwrap();
+
+    assert_eq!(flush_result.shards.len(), 1);
+    assert_eq!(
+        flush_result.shards[0].operation_id,
+        insert_result.operation_id
+    );
+
+    collection.before_drop().await;
+}
+
+#[tokio::test]
+async fn test_collection_truncate_wal() {
+    let collection_dir = Builder::new().prefix("collection").tempdir().unwrap();
+
+    let mut collection = simple_collection_fixture(collection_dir.path(), 1).await;
+
+    let insert_points = CollectionUpdateOperations::PointOperation(
+        Batch {
+            ids: vec![0, 1, 2].into_iter().map(|x| x.into()).collect_vec(),
+            vectors: vec![
+                vec![1.0, 0.0, 1.0, 1.0],
+                vec![1.0, 0.0, 1.0, 0.0],
+                vec![1.0, 1.0, 1.0, 1.0],
+            ]
+            .into(),
+            payloads: None,
+        }
+        .into(),
+    );
+    let insert_result = collection
+        .update_from_client(insert_points, true, None)
+        .await
+        .unwrap();
+
+    collection.flush().await.unwrap();
+    let truncate_result = collection.truncate_wal().await.unwrap();
+
+    assert_eq!(truncate_result.shards.len(), 1);
+    let shard_result = &truncate_result.shards[0];
+    assert_eq!(shard_result.operation_id, insert_result.operation_id);
+    assert!(shard_result.wal.size_bytes.is_some());
+
+    collection.before_drop().await;
+}
+
+#[tokio::test]
+async fn test_collection_update_with_shard_key() {
+    let collection_dir = Builder::new().prefix("collection").tempdir().unwrap();
+
+    let mut collection = simple_collection_fixture(collection_dir.path(), N_SHARDS).await;
+
+    let cluster_info = collection.cluster_info(0, true).await.unwrap();
+    let routing = cluster_info.routing.unwrap();
+    assert_eq!(routing.len(), N_SHARDS as usize);
+
+    let insert_points = || {
+        CollectionUpdateOperations::PointOperation(
+            Batch {
+                ids: (0..10).map(|x: u64| x.into()).collect_vec(),
+                vectors: vec![vec![1.0, 0.0, 1.0, 1.0]; 10].into(),
+                payloads: None,
+            }
+            .into(),
+        )
+    };
+
+    // Points are distributed across all shards, so single shard hint is outdated
+    let result = collection
+        .update_from_client_with_shard_key(insert_points(), Some(routing[0].shard_id), true, None)
+        .await;
+    assert!(result.is_err());
+
+    let result = collection
+        .update_from_client_with_shard_key(insert_points(), None, true, None)
+        .await
+        .unwrap();
+    assert_eq!(result.status, UpdateStatus::Completed);
+
+    collection.before_drop().await;
+}
+
+#[tokio::test]
+async fn test_collection_search_with_shard_key() {
+    let collection_dir = Builder::new().prefix("collection").tempdir().unwrap();
+    let snapshot_path = collection_dir.path().join("snapshots");
+
+    // Stored vectors are the 2-dimensional input vectors, padded with zeros
+    let mut config = simple_collection_config(1);
+    config.params.vectors = VectorParams {
+        size: NonZeroU64::new(4).unwrap(),
+        distance: Distance::Dot,
+        hnsw_config: None,
+        quantization_config: None,
+        projection: Some(VectorProjection {
+            matrix: vec![
+                vec![1.0, 0.0],
+                vec![0.0, 1.0],
+                vec![0.0, 0.0],
+                vec![0.0, 0.0],
+            ],
+            mean: None,
+        }),
+    }
+    .into();
+    let mut collection = new_local_collection(
+        "test".to_string(),
+        collection_dir.path(),
+        &snapshot_path,
+        &config,
+    )
+    .await
+    .unwrap();
+
+    let insert_points = CollectionUpdateOperations::PointOperation(
+        Batch {
+            ids: (0..5).map(|x: u64| x.into()).collect_vec(),
+            vectors: (0..5).map(|x| vec![x as f32, 1.0]).collect_vec().into(),
+            payloads: None,
+        }
+        .into(),
+    );
+    collection
+        .update_from_client(insert_points, true, None)
+        .await
+        .unwrap();
+
+    let request = SearchRequest {
+        vector: vec![1.0, 0.0].into(),
+        filter: None,
+        params: None,
+        limit: 2,
+        offset: 1,
+        with_payload: None,
+        with_vector: None,
+        score_threshold: None,
+        with_highlights: None,
+        with_geo_distance: None,
+    };
+
+    // Query vector of the client is projected and the offset is applied once
+    let result = collection
+        .search_with_shard_key(request.clone(), None, Some(0))
+        .await
+        .unwrap();
+    let ids = result.iter().map(|point| point.id).collect_vec();
+    assert_eq!(ids, vec![3.into(), 2.into()]);
+
+    let result = collection
+        .search_batch_with_shard_key(
+            SearchRequestBatch {
+                searches: vec![request.clone()],
+            },
+            None,
+            Some(0),
+        )
+        .await
+        .unwrap();
+    let ids = result[0].iter().map(|point| point.id).collect_vec();
+    assert_eq!(ids, vec![3.into(), 2.into()]);
+
+    // Peers send already projected vectors and apply the offset after merging the shards
+    let mut peer_request = request;
+    peer_request.vector = vec![1.0, 0.0, 0.0, 0.0].into();
+    let result = collection
+        .search(peer_request, None, Some(0))
+        .await
+        .unwrap();
+    assert_eq!(result.len(), 3);
+
+    collection.before_drop().await;
+}
+
+#[tokio::test]
+async fn test_collection_max_concurrent_searches() {
+    let collection_dir = Builder::new().prefix("collection").tempdir().unwrap();
+
+    let mut collection = simple_collection_fixture(collection_dir.path(), 1).await;
+
+    assert!(collection.acquire_search_permit().await.unwrap().is_none());
+
+    collection
+        .update_params_from_diff(CollectionParamsDiff {
+            replication_factor: None,
+            write_consistency_factor: None,
+            vector_aliases: None,
+            default_vector: None,
+            defrag_key: None,
+            deduplicate_upserts: None,
+            max_concurrent_searches: Some(1),
+            acl_key: None,
+            default_write_ordering: None,
+            default_read_consistency: None,
+        })
+        .await
+        .unwrap();
+
+    let permit = collection.acquire_search_permit().await.unwrap();
+    assert!(permit.is_some());
+
+    // Second search waits until the first one releases the permit
+    let waiting = tokio::time::timeout(
+        Duration::from_millis(100),
+        collection.acquire_search_permit(),
+    )
+    .await;
+    assert!(waiting.is_err());
+
+    drop(permit);
+    assert!(collection.acquire_search_permit().await.unwrap().is_some());
+
+    collection.before_drop().await;
+}
+
+#[tokio::test]
+async fn test_collection_default_write_ordering() {
+    let collection_dir = Builder::new().prefix("collection").tempdir().unwrap();
+
+    let mut collection = simple_collection_fixture(collection_dir.path(), 1).await;
+
+    collection
+        .update_params_from_diff(CollectionParamsDiff {
+            replication_factor: None,
+            write_consistency_factor: None,
+            vector_aliases: None,
+            default_vector: None,
+            defrag_key: None,
+            deduplicate_upserts: None,
+            max_concurrent_searches: None,
+            acl_key: None,
+            default_write_ordering: Some(WriteOrdering::Medium),
+            default_read_consistency: None,
+        })
+        .await
+        .unwrap();
+
+    let insert_points = || {
+        CollectionUpdateOperations::PointOperation(
+            Batch {
+                ids: (0..10).map(|x: u64| x.into()).collect_vec(),
+                vectors: vec![vec![1.0, 0.0, 1.0, 1.0]; 10].into(),
+                payloads: None,
+            }
+            .into(),
+        )
+    };
+
+    let result = collection
+        .update_from_client(insert_points(), true, None)
+        .await
+        .unwrap();
+    assert_eq!(
+        result.replication.unwrap()[0].ordering,
+        WriteOrdering::Medium
+    );
+
+    // Ordering of the request overrides the default of the collection
+    let result = collection
+        .update_from_client(insert_points(), true, Some(WriteOrdering::Weak))
+        .await
+        .unwrap();
+    assert_eq!(result.replication.unwrap()[0].ordering, WriteOrdering::Weak);
+
+    collection.before_drop().await;
+}
+
+#[tokio::test]
+async fn test_collection_point_versions() {
+    let collection_dir = Builder::new().prefix("collection").tempdir().unwrap();
+
+    let mut collection = simple_collection_fixture(collection_dir.path(), N_SHARDS).await;
+
+    let insert_points = CollectionUpdateOperations::PointOperation(
+        Batch {
+            ids: vec![0.into(), 1.into(), 2.into()],
+            vectors: vec![
+                vec![1.0, 0.0, 1.0, 1.0],
+                vec![1.0, 0.0, 1.0, 0.0],
+                vec![1.0, 1.0, 1.0, 1.0],
+            ]
+            .into(),
+            payloads: None,
+        }
+        .into(),
+    );
+    collection
+        .update_from_client(insert_points, true, None)
+        .await
+        .unwrap();
+
+    let ids: Vec<PointIdType> = vec![1.into(), 2.into(), 5.into()];
+    let versions = collection.point_versions(&ids, None).await.unwrap();
+    assert_eq!(versions.len(), 2);
+    let version = versions[&PointIdType::from(1)];
+
+    let assign_payload =
+        CollectionUpdateOperations::PayloadOperation(PayloadOps::SetPayload(SetPayload {
+            payload: serde_json::from_str(r#"{"color":"red"}"#).unwrap(),
+            points: Some(vec![1.into()]),
+            filter: None,
+        }));
+    collection
+        .update_from_client(assign_payload, true, None)
+        .await
+        .unwrap();
+
+    let versions = collection.point_versions(&ids, None).await.unwrap();
+    assert!(versions[&PointIdType::from(1)] > version);
+    assert!(!versions.contains_key(&PointIdType::from(5)));
+
+    collection.before_drop().await;
+}
+
+#[tokio::test]
+async fn test_collection_quantized_only() {
+    let collection_dir = Builder::new().prefix("collection").tempdir().unwrap();
+
+    let mut collection = simple_collection_fixture(collection_dir.path(), 1).await;
+
+    collection
+        .update_quantization_config(
+            ScalarQuantizationConfig {
+                r#type: Default::default(),
+                quantile: None,
+                always_ram: None,
+                quantized_only: Some(true),
+            }
+            .into(),
+        )
+        .await
+        .unwrap();
+
+    // Replicas can't be recovered without original vectors
+    let result = collection
+        .update_params_from_diff(CollectionParamsDiff {
+            replication_factor: NonZeroU32::new(2),
+            write_consistency_factor: None,
+            vector_aliases: None,
+            default_vector: None,
+            defrag_key: None,
+            deduplicate_upserts: None,
+            max_concurrent_searches: None,
+            acl_key: None,
+            default_write_ordering: None,
+            default_read_consistency: None,
+        })
+        .await;
+    assert!(matches!(result, Err(CollectionError::BadRequest { .. })));
+    assert_eq!(collection.config().await.params.replication_factor.get(), 1);
+
+    let transfer = ShardTransfer {
+        shard_id: 0,
+        from: 0,
+        to: 1,
+        sync: false,
+    };
+    let result = collection
+        .start_shard_transfer(transfer, async {}, async {})
+        .await;
+    assert!(matches!(result, Err(CollectionError::BadRequest { .. })));
+
+    let insert_points = CollectionUpdateOperations::PointOperation(
+        Batch {
+            ids: vec![1.into(), 2.into()],
+            vectors: vec![vec![1.0, 0.0, 1.0, 1.0]; 2].into(),
+            payloads: None,
+        }
+        .into(),
+    );
+    collection
+        .update_from_client(insert_points, true, None)
+        .await
+        .unwrap();
+
+    // Vectors of appendable segments are kept, so they are still returned
+    let request = PointRequest {
+        ids: vec![1.into(), 2.into()],
+        with_payload: None,
+        with_vector: true.into(),
+    };
+    let retrieved = collection.retrieve(request, None, None).await.unwrap();
+    assert_eq!(retrieved.len(), 2);
+    assert!(retrieved.iter().all(|record| record.vector.is_some()));
+
+    collection.before_drop().await;
+}
+
+#[tokio::test]
+async fn test_collection_remove_orphaned_files() {
+    let collection_dir = Builder::new().prefix("collection").tempdir().unwrap();
+
+    let mut collection = simple_collection_fixture(collection_dir.path(), 1).await;
+
+    let segments_path = collection_dir.path().join("0").join("segments");
+    let orphaned_path = segments_path.join("orphaned");
+    let building_path = segments_path.join("new.building");
+    std::fs::create_dir_all(&orphaned_path).unwrap();
+    std::fs::create_dir_all(&building_path).unwrap();
+
+    // Files found for the first time are kept even without grace period
+    let result = collection
+        .remove_orphaned_files(Duration::ZERO)
+        .await
+        .unwrap();
+    assert!(result.removed.is_empty());
+    assert_eq!(result.pending, vec![orphaned_path.display().to_string()]);
+
+    let result = collection
+        .remove_orphaned_files(Duration::ZERO)
+        .await
+        .unwrap();
+    assert_eq!(result.removed, vec![orphaned_path.display().to_string()]);
+    assert!(!orphaned_path.exists());
+    // Segments, which are being built, are not orphaned
+    assert!(building_path.exists());
+
+    collection.before_drop().await;
+}
@@ -6,13 +6,15 @@ use std::sync::Arc;
 
 use collection::collection::{Collection, RequestShardTransfer};
 use collection::config::{CollectionConfig, CollectionParams, WalConfig};
+use collection::operations::point_ops::Batch;
 use collection::operations::types::{CollectionError, VectorParams};
+use collection::operations::{CollectionUpdateOperations, CreateIndex, FieldIndexOperations};
 use collection::optimizers_builder::OptimizersConfig;
 use collection::shards::channel_service::ChannelService;
 use collection::shards::collection_shard_distribution::CollectionShardDistribution;
 use collection::shards::replica_set::{ChangePeerState, ReplicaState};
 use collection::shards::CollectionId;
-use segment::types::Distance;
+use segment::types::{Distance, Payload, PayloadFieldSchema, PayloadSchemaType};
 
 /// Test collections for this upper bound of shards.
 /// Testing with more shards is problematic due to `number of open files problem`
@@ -29,11 +31,76 @@ pub const TEST_OPTIMIZERS_CONFIG: OptimizersConfig = OptimizersConfig {
     indexing_threshold: 50_000,
     flush_interval_sec: 30,
     max_optimization_threads: 2,
+    target_segment_count: None,
+    max_segment_size_mb: None,
 };
 
 #[cfg(test)]
 #[allow(dead_code)]
 pub async fn simple_collection_fixture(collection_path: &Path, shard_number: u32) -> Collection {
+    let collection_config = simple_collection_config(shard_number);
+
+    let snapshot_path = collection_path.join("snapshots");
+
+    // Default to a collection with all the shards local
+    new_local_collection(
+        "test".to_string(),
+        collection_path,
+        &snapshot_path,
+        &collection_config,
+    )
+    .await
+    .unwrap()
+}
+
+/// Collection of `simple_collection_fixture` with a point for each of the JSON `payloads`.
+/// Points have consecutive ids from 0 and the same vector.
+#[allow(dead_code)]
+pub async fn payload_collection_fixture(
+    collection_path: &Path,
+    shard_number: u32,
+    payloads: &str,
+) -> Collection {
+    let collection = simple_collection_fixture(collection_path, shard_number).await;
+
+    let payloads: Vec<Option<Payload>> = serde_json::from_str(payloads).unwrap();
+    let insert_points = CollectionUpdateOperations::PointOperation(
+        Batch {
+            ids: (0..payloads.len() as u64).map(|id| id.into()).collect(),
+            vectors: vec![vec![1.0, 0.0, 1.0, 1.0]; payloads.len()].into(),
+            payloads: Some(payloads),
+        }
+        .into(),
+    );
+    collection
+        .update_from_client(insert_points, true, None)
+        .await
+        .unwrap();
+
+    collection
+}
+
+#[allow(dead_code)]
+pub async fn create_field_index(
+    collection: &Collection,
+    field_name: &str,
+    field_schema: PayloadSchemaType,
+) {
+    let create_index = CollectionUpdateOperations::FieldIndexOperation(
+        FieldIndexOperations::CreateIndex(CreateIndex {
+            field_name: field_name.to_string(),
+            field_schema: Some(PayloadFieldSchema::FieldType(field_schema)),
+        }),
+    );
+    collection
+        .update_from_client(create_index, true, None)
+        .await
+        .unwrap();
+}
+
+/// Config of a collection with a single 4-dimensional vector with dot product distance
+#[allow(dead_code)]
+pub fn simple_collection_config(shard_number: u32) -> CollectionConfig {
     let wal_config = WalConfig {
         wal_capacity_mb: 1,
         wal_segments_ahead: 0,
@@ -45,33 +112,33 @@ pub async fn simple_collection_fixture(collection_path: &Path, shard_number: u32
             distance: Distance::Dot,
             hnsw_config: None,
             quantization_config: None,
+            projection: None,
         }
         .into(),
         shard_number: NonZeroU32::new(shard_number).expect("Shard number can not be zero"),
         replication_factor: NonZeroU32::new(1).unwrap(),
         write_consistency_factor: NonZeroU32::new(1).unwrap(),
         on_disk_payload: false,
+        vector_aliases: Default::default(),
+        default_vector: None,
+        defrag_key: None,
+        deduplicate_upserts: false,
+        max_concurrent_searches: 0,
+        acl_key: None,
+        default_write_ordering: None,
+        default_read_consistency: None,
+        sparse_vectors: Default::default(),
     };
 
-    let collection_config = CollectionConfig {
+    CollectionConfig {
         params: collection_params,
         optimizer_config: TEST_OPTIMIZERS_CONFIG.clone(),
         wal_config,
         hnsw_config: Default::default(),
         quantization_config: Default::default(),
-    };
-
-    let snapshot_path = collection_path.join("snapshots");
-
-    // Default to a collection with all the shards local
-    new_local_collection(
-        "test".to_string(),
-        collection_path,
-        &snapshot_path,
-        &collection_config,
-    )
-    .await
-    .unwrap()
+        lifecycle_config: None,
+        created_at: None,
+    }
 }
 
 pub fn dummy_on_replica_failure() -> ChangePeerState {
@@ -6,9 +6,10 @@ use std::path::Path;
 
 use collection::collection::Collection;
 use collection::config::{CollectionConfig, CollectionParams, WalConfig};
-use collection::operations::point_ops::{
-    PointInsertOperations, PointOperations, PointStruct, WriteOrdering,
+use collection::operations::backfill_ops::{
+    BackfillBatch, BackfillPoint, BackfillPointsRequest, CreateBackfillJob,
 };
+use collection::operations::point_ops::{PointInsertOperations, PointOperations, PointStruct};
 use collection::operations::types::{
     CollectionError, PointRequest, RecommendRequest, SearchRequest, VectorParams, VectorsConfig,
 };
@@ -44,12 +45,14 @@ pub async fn multi_vec_collection_fixture(collection_path: &Path, shard_number:
         distance: Distance::Dot,
         hnsw_config: None,
         quantization_config: None,
+        projection: None,
     };
     let vector_params2 = VectorParams {
         size: NonZeroU64::new(4).unwrap(),
         distance: Distance::Dot,
         hnsw_config: None,
         quantization_config: None,
+        projection: None,
     };
 
     let mut vectors_config = BTreeMap::new();
@@ -63,6 +66,15 @@ pub async fn multi_vec_collection_fixture(collection_path: &Path, shard_number:
         replication_factor: NonZeroU32::new(1).unwrap(),
         write_consistency_factor: NonZeroU32::new(1).unwrap(),
         on_disk_payload: false,
+        vector_aliases: Default::default(),
+        default_vector: None,
+        defrag_key: None,
+        deduplicate_upserts: false,
+        max_concurrent_searches: 0,
+        acl_key: None,
+        default_write_ordering: None,
+        default_read_consistency: None,
+        sparse_vectors: Default::default(),
     };
 
     let collection_config = CollectionConfig {
@@ -71,6 +83,8 @@ pub async fn multi_vec_collection_fixture(collection_path: &Path, shard_number:
         wal_config,
         hnsw_config: Default::default(),
         quantization_config: Default::default(),
+        lifecycle_config: None,
+        created_at: None,
     };
 
     let snapshot_path = collection_path.join("snapshots");
@@ -105,13 +119,15 @@ async fn test_multi_vec_with_shards(shard_number: u32) {
             id: i.into(),
             vector: vectors.into(),
             payload: Some(serde_json::from_str(r#"{"number": "John Doe"}"#).unwrap()),
+            sequence: None,
+            content_hash: None,
         });
     }
     let insert_points = CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
         PointInsertOperations::PointsList(points),
     ));
     collection
-        .update_from_client(insert_points, true, WriteOrdering::default())
+        .update_from_client(insert_points, true, None)
         .await
         .unwrap();
 
@@ -130,6 +146,8 @@ async fn test_multi_vec_with_shards(shard_number: u32) {
         with_vector: Some(true.into()),
         params: None,
         score_threshold: None,
+        with_highlights: None,
+        with_geo_distance: None,
     };
 
     let result = collection
@@ -158,6 +176,8 @@ async fn test_multi_vec_with_shards(shard_number: u32) {
         with_vector: Some(true.into()),
         params: None,
         score_threshold: None,
+        with_highlights: None,
+        with_geo_distance: None,
     };
 
     let result = collection.search(failed_search_request, None, None).await;
@@ -180,6 +200,8 @@ async fn test_multi_vec_with_shards(shard_number: u32) {
         with_vector: Some(true.into()),
         params: None,
         score_threshold: None,
+        with_highlights: None,
+        with_geo_distance: None,
     };
 
     let result = collection
@@ -271,3 +293,160 @@ async fn test_multi_vec_with_shards(shard_number: u32) {
 
     collection.before_drop().await;
 }
+
+#[tokio::test]
+async fn test_backfill_added_vector() {
+    let collection_dir = Builder::new()
+        .prefix("test_backfill_added_vector")
+        .tempdir()
+        .unwrap();
+
+    let mut collection = multi_vec_collection_fixture(collection_dir.path(), N_SHARDS).await;
+
+    let points = (0..100)
+        .map(|i| {
+            let mut vectors = NamedVectors::default();
+            vectors.insert(VEC_NAME1.to_string(), vec![i as f32, 0.0, 0.0, 0.0]);
+            vectors.insert(VEC_NAME2.to_string(), vec![0.0, i as f32, 0.0, 0.0]);
+            PointStruct {
+                id: i.into(),
+                vector: vectors.into(),
+                payload: Some(serde_json::from_str(r#"{"number": 1}"#).unwrap()),
+                sequence: None,
+                content_hash: None,
+            }
+        })
+        .collect();
+    let insert_points = CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
+        PointInsertOperations::PointsList(points),
+    ));
+    collection
+        .update_from_client(insert_points, true, None)
+        .await
+        .unwrap();
+
+    let new_vector = "vec3".to_string();
+    collection
+        .add_vectors(BTreeMap::from([(
+            new_vector.clone(),
+            VectorParams {
+                size: NonZeroU64::new(4).unwrap(),
+                distance: Distance::Dot,
+                hnsw_config: None,
+                quantization_config: None,
+                projection: None,
+            },
+        )]))
+        .await
+        .unwrap();
+
+    let job = collection
+        .create_backfill_job(CreateBackfillJob {
+            vector: new_vector.clone(),
+        })
+        .await
+        .unwrap();
+    assert_eq!(job.shards.len(), N_SHARDS as usize);
+
+    loop {
+        let request = BackfillPointsRequest {
+            limit: Some(30),
+            with_payload: None,
+        };
+        let result = collection
+            .backfill_points(job.job_id, request)
+            .await
+            .unwrap();
+        assert!(result.points.len() <= 30);
+
+        if !result.points.is_empty() {
+            // Client writes to the points, while they are backfilled, must be kept
+            let client_points = result
+                .points
+                .iter()
+                .map(|point| {
+                    let mut vectors = NamedVectors::default();
+                    vectors.insert(VEC_NAME1.to_string(), vec![1.0, 1.0, 1.0, 1.0]);
+                    vectors.insert(VEC_NAME2.to_string(), vec![0.0, 1.0, 0.0, 0.0]);
+                    PointStruct {
+                        id: point.id,
+                        vector: vectors.into(),
+                        payload: Some(serde_json::from_str(r#"{"number": 2}"#).unwrap()),
+                        sequence: None,
+                        content_hash: None,
+                    }
+                })
+                .collect();
+            collection
+                .update_from_client(
+                    CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
+                        PointInsertOperations::PointsList(client_points),
+                    )),
+                    true,
+                    None,
+                )
+                .await
+                .unwrap();
+
+            let points = result
+                .points
+                .iter()
+                .map(|point| BackfillPoint {
+                    id: point.id,
+                    vector: vec![0.0, 0.0, 1.0, 0.0],
+                })
+                .collect();
+            collection
+                .backfill_update(job.job_id, BackfillBatch { points }, true, None)
+                .await
+                .unwrap();
+        }
+        if result.completed {
+            break;
+        }
+    }
+
+    let job = collection.backfill_job_info(job.job_id).await.unwrap();
+    assert_eq!(
+        job.shards.iter().map(|shard| shard.missing).sum::<usize>(),
+        100
+    );
+    assert_eq!(
+        job.shards
+            .iter()
+            .map(|shard| shard.backfilled)
+            .sum::<usize>(),
+        100
+    );
+
+    let records = collection
+        .retrieve(
+            PointRequest {
+                ids: vec![1.into(), 50.into()],
+                with_payload: Some(WithPayloadInterface::Bool(true)),
+                with_vector: true.into(),
+            },
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(records.len(), 2);
+    for record in records {
+        let vectors = record.vector.unwrap();
+        assert_eq!(vectors.get(VEC_NAME1), Some(&vec![1.0, 1.0, 1.0, 1.0]));
+        assert_eq!(vectors.get(&new_vector), Some(&vec![0.0, 0.0, 1.0, 0.0]));
+        assert_eq!(
+            record
+                .payload
+                .unwrap()
+                .into_payload()
+                .unwrap()
+                .0
+                .get("number"),
+            Some(&serde_json::json!(2))
+        );
+    }
+
+    collection.before_drop().await;
+}
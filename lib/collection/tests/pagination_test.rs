@@ -1,6 +1,4 @@
-use collection::operations::point_ops::{
-    PointInsertOperations, PointOperations, PointStruct, WriteOrdering,
-};
+use collection::operations::point_ops::{PointInsertOperations, PointOperations, PointStruct};
 use collection::operations::types::SearchRequest;
 use collection::operations::CollectionUpdateOperations;
 use segment::types::WithPayloadInterface;
@@ -31,13 +29,15 @@ async fn test_collection_paginated_search_with_shards(shard_number: u32) {
             id: i.into(),
             vector: vec![i as f32, 0.0, 0.0, 0.0].into(),
             payload: Some(serde_json::from_str(r#"{"number": "John Doe"}"#).unwrap()),
+            sequence: None,
+            content_hash: None,
         });
     }
     let insert_points = CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
         PointInsertOperations::PointsList(points),
     ));
     collection
-        .update_from_client(insert_points, true, WriteOrdering::default())
+        .update_from_client(insert_points, true, None)
         .await
         .unwrap();
 
@@ -52,6 +52,8 @@ async fn test_collection_paginated_search_with_shards(shard_number: u32) {
         with_vector: None,
         params: None,
         score_threshold: None,
+        with_highlights: None,
+        with_geo_distance: None,
     };
 
     let reference_result = collection
@@ -75,6 +77,8 @@ async fn test_collection_paginated_search_with_shards(shard_number: u32) {
         with_vector: None,
         params: None,
         score_threshold: None,
+        with_highlights: None,
+        with_geo_distance: None,
     };
 
     let page_1_result = collection.search(page_1_request, None, None).await.unwrap();
@@ -94,6 +98,8 @@ async fn test_collection_paginated_search_with_shards(shard_number: u32) {
         with_vector: None,
         params: None,
         score_threshold: None,
+        with_highlights: None,
+        with_geo_distance: None,
     };
 
     let page_9_result = collection.search(page_9_request, None, None).await.unwrap();
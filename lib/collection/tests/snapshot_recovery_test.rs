@@ -3,9 +3,7 @@ use std::sync::Arc;
 
 use collection::collection::Collection;
 use collection::config::{CollectionConfig, CollectionParams, WalConfig};
-use collection::operations::point_ops::{
-    PointInsertOperations, PointOperations, PointStruct, WriteOrdering,
-};
+use collection::operations::point_ops::{PointInsertOperations, PointOperations, PointStruct};
 use collection::operations::shared_storage_config::SharedStorageConfig;
 use collection::operations::types::{NodeType, SearchRequest, VectorParams, VectorsConfig};
 use collection::operations::CollectionUpdateOperations;
@@ -33,11 +31,21 @@ async fn _test_snapshot_and_recover_collection(node_type: NodeType) {
             distance: Distance::Dot,
             hnsw_config: None,
             quantization_config: None,
+            projection: None,
         }),
         shard_number: NonZeroU32::new(1).unwrap(),
         replication_factor: NonZeroU32::new(1).unwrap(),
         write_consistency_factor: NonZeroU32::new(1).unwrap(),
         on_disk_payload: false,
+        vector_aliases: Default::default(),
+        default_vector: None,
+        defrag_key: None,
+        deduplicate_upserts: false,
+        max_concurrent_searches: 0,
+        acl_key: None,
+        default_write_ordering: None,
+        default_read_consistency: None,
+        sparse_vectors: Default::default(),
     };
 
     let config = CollectionConfig {
@@ -46,6 +54,8 @@ async fn _test_snapshot_and_recover_collection(node_type: NodeType) {
         wal_config,
         hnsw_config: Default::default(),
         quantization_config: Default::default(),
+        lifecycle_config: None,
+        created_at: None,
     };
 
     let snapshots_path = Builder::new().prefix("test_snapshots").tempdir().unwrap();
@@ -100,13 +110,15 @@ async fn _test_snapshot_and_recover_collection(node_type: NodeType) {
             id: i.into(),
             vector: vec![i as f32, 0.0, 0.0, 0.0].into(),
             payload: Some(serde_json::from_str(r#"{"number": "John Doe"}"#).unwrap()),
+            sequence: None,
+            content_hash: None,
         });
     }
     let insert_points = CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
         PointInsertOperations::PointsList(points),
     ));
     collection
-        .update_from_client(insert_points, true, WriteOrdering::default())
+        .update_from_client(insert_points, true, None)
         .await
         .unwrap();
 
@@ -118,14 +130,41 @@ async fn _test_snapshot_and_recover_collection(node_type: NodeType) {
         .await
         .unwrap();
 
-    if let Err(err) = Collection::restore_snapshot(
+    let inspect_dir = Builder::new()
+        .prefix("test_collection_inspect")
+        .tempdir()
+        .unwrap();
+    match Collection::inspect_snapshot(
+        &snapshots_path.path().join(&snapshot_description.name),
+        inspect_dir.path(),
+    ) {
+        Ok(inspection) => {
+            assert_eq!(inspection.version, env!("CARGO_PKG_VERSION"));
+            assert_eq!(inspection.config, config);
+            assert_eq!(inspection.points_count, 100);
+            assert_eq!(inspection.shards.len(), 1);
+            assert!(inspection.shards[0].has_local_data);
+        }
+        Err(err) => {
+            collection.before_drop().await;
+            panic!("Failed to inspect snapshot: {err}")
+        }
+    }
+
+    match Collection::restore_snapshot(
         &snapshots_path.path().join(snapshot_description.name),
         recover_dir.path(),
         0,
         false,
     ) {
-        collection.before_drop().await;
-        panic!("Failed to restore snapshot: {err}")
+        Ok(verification) => {
+            assert!(verification.is_ok(), "{:?}", verification.problems);
+            assert_eq!(verification.points_count, 100);
+        }
+        Err(err) => {
+            collection.before_drop().await;
+            panic!("Failed to restore snapshot: {err}")
+        }
     }
 
     let mut recovered_collection = Collection::load(
@@ -153,6 +192,8 @@ async fn _test_snapshot_and_recover_collection(node_type: NodeType) {
         with_vector: Some(WithVector::Bool(true)),
         params: None,
         score_threshold: None,
+        with_highlights: None,
+        with_geo_distance: None,
     };
 
     let reference_result = collection
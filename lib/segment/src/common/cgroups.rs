@@ -0,0 +1,183 @@
+//! Detection of resource limits, imposed on the process by Linux control groups.
+//! Containers usually limit CPU and memory with cgroups, while the number of CPUs and the amount
+//! of memory reported by the system are those of the whole host.
+#![cfg_attr(not(target_os = "linux"), allow(dead_code))]
+
+#[cfg(target_os = "linux")]
+use std::fs;
+#[cfg(target_os = "linux")]
+use std::path::{Path, PathBuf};
+
+#[cfg(target_os = "linux")]
+const CGROUP_ROOT: &str = "/sys/fs/cgroup";
+
+/// Limits above this value are used by cgroups v1 to represent "no limit"
+const UNLIMITED_MEMORY_THRESHOLD: u64 = 1 << 62;
+
+/// Number of CPUs the process may use according to the CPU quota of its cgroup, rounded up.
+/// Returns `None` if there is no quota or cgroups are not available.
+#[cfg(target_os = "linux")]
+pub fn cpu_quota_limit() -> Option<usize> {
+    if is_cgroup_v2() {
+        cgroup_dirs(None)
+            .into_iter()
+            .filter_map(|dir| fs::read_to_string(dir.join("cpu.max")).ok())
+            .filter_map(|cpu_max| parse_cpu_max(&cpu_max))
+            .min()
+    } else {
+        cgroup_dirs(Some("cpu"))
+            .into_iter()
+            .filter_map(|dir| {
+                let quota = fs::read_to_string(dir.join("cpu.cfs_quota_us")).ok()?;
+                let period = fs::read_to_string(dir.join("cpu.cfs_period_us")).ok()?;
+                cpu_quota_to_cpus(quota.trim().parse().ok()?, period.trim().parse().ok()?)
+            })
+            .min()
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn cpu_quota_limit() -> Option<usize> {
+    None
+}
+
+/// Memory limit of the process cgroup in bytes.
+/// Returns `None` if there is no limit or cgroups are not available.
+#[cfg(target_os = "linux")]
+pub fn memory_limit() -> Option<usize> {
+    let (controller, limit_file) = if is_cgroup_v2() {
+        (None, "memory.max")
+    } else {
+        (Some("memory"), "memory.limit_in_bytes")
+    };
+    cgroup_dirs(controller)
+        .into_iter()
+        .filter_map(|dir| fs::read_to_string(dir.join(limit_file)).ok())
+        .filter_map(|limit| parse_memory_limit(&limit))
+        .min()
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn memory_limit() -> Option<usize> {
+    None
+}
+
+#[cfg(target_os = "linux")]
+fn is_cgroup_v2() -> bool {
+    Path::new(CGROUP_ROOT).join("cgroup.controllers").exists()
+}
+
+/// Directories of the process cgroup and all of its ancestors, which are visible to the process.
+/// Limits of the ancestors apply to the process as well, so the tightest one should be used.
+///
+/// `controller` - name of the cgroups v1 controller, `None` for the unified cgroups v2 hierarchy
+#[cfg(target_os = "linux")]
+fn cgroup_dirs(controller: Option<&str>) -> Vec<PathBuf> {
+    let root = match controller {
+        Some(controller) => Path::new(CGROUP_ROOT).join(controller),
+        None => PathBuf::from(CGROUP_ROOT),
+    };
+    let cgroup_path = fs::read_to_string("/proc/self/cgroup")
+        .ok()
+        .and_then(|content| parse_cgroup_path(&content, controller))
+        .unwrap_or_default();
+
+    // Without cgroup namespace, the path of the cgroup is relative to the host hierarchy,
+    // while only the own cgroup of the container is mounted. Such paths do not exist
+    // and are skipped, so the mount root is used instead.
+    root.join(cgroup_path.trim_start_matches('/'))
+        .ancestors()
+        .take_while(|dir| dir.starts_with(&root))
+        .filter(|dir| dir.is_dir())
+        .map(Path::to_path_buf)
+        .collect()
+}
+
+/// Find the path of the process cgroup in the content of `/proc/self/cgroup`.
+/// Lines have the format `hierarchy-ID:controller-list:cgroup-path`,
+/// the cgroups v2 hierarchy has ID 0 and an empty controller list.
+fn parse_cgroup_path(content: &str, controller: Option<&str>) -> Option<String> {
+    content.lines().find_map(|line| {
+        let mut parts = line.splitn(3, ':');
+        let hierarchy_id = parts.next()?;
+        let controllers = parts.next()?;
+        let path = parts.next()?;
+        let matches = match controller {
+            Some(controller) => controllers.split(',').any(|name| name == controller),
+            None => hierarchy_id == "0" && controllers.is_empty(),
+        };
+        matches.then(|| path.to_string())
+    })
+}
+
+/// Parse `cpu.max` file of cgroups v2, which contains `$MAX $PERIOD` or `max $PERIOD`
+fn parse_cpu_max(content: &str) -> Option<usize> {
+    let mut parts = content.split_whitespace();
+    let quota = parts.next()?;
+    let period = parts.next()?;
+    if quota == "max" {
+        return None;
+    }
+    cpu_quota_to_cpus(quota.parse().ok()?, period.parse().ok()?)
+}
+
+/// Convert CPU quota per period into the number of CPUs, rounded up.
+/// Negative quota means there is no limit.
+fn cpu_quota_to_cpus(quota: i64, period: u64) -> Option<usize> {
+    if quota <= 0 || period == 0 {
+        return None;
+    }
+    let quota = quota as u64;
+    Some(((quota + period - 1) / period) as usize)
+}
+
+/// Parse `memory.max` file of cgroups v2 or `memory.limit_in_bytes` file of cgroups v1
+fn parse_memory_limit(content: &str) -> Option<usize> {
+    let limit = content.trim().parse::<u64>().ok()?;
+    if limit >= UNLIMITED_MEMORY_THRESHOLD {
+        return None;
+    }
+    usize::try_from(limit).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_cgroup_path() {
+        let v2 = "0::/system.slice/docker-1234.scope\n";
+        assert_eq!(
+            parse_cgroup_path(v2, None).as_deref(),
+            Some("/system.slice/docker-1234.scope"),
+        );
+        assert_eq!(parse_cgroup_path(v2, Some("memory")), None);
+
+        let v1 =
+            "12:memory:/docker/1234\n4:cpu,cpuacct:/docker/1234\n1:name=systemd:/docker/1234\n";
+        assert_eq!(
+            parse_cgroup_path(v1, Some("cpu")).as_deref(),
+            Some("/docker/1234"),
+        );
+        assert_eq!(
+            parse_cgroup_path(v1, Some("memory")).as_deref(),
+            Some("/docker/1234"),
+        );
+        assert_eq!(parse_cgroup_path(v1, None), None);
+    }
+
+    #[test]
+    fn test_parse_limits() {
+        assert_eq!(parse_cpu_max("max 100000\n"), None);
+        assert_eq!(parse_cpu_max("200000 100000\n"), Some(2));
+        assert_eq!(parse_cpu_max("150000 100000\n"), Some(2));
+        assert_eq!(parse_cpu_max("50000 100000\n"), Some(1));
+
+        assert_eq!(cpu_quota_to_cpus(-1, 100000), None);
+        assert_eq!(cpu_quota_to_cpus(400000, 100000), Some(4));
+
+        assert_eq!(parse_memory_limit("max\n"), None);
+        assert_eq!(parse_memory_limit("9223372036854771712\n"), None);
+        assert_eq!(parse_memory_limit("1073741824\n"), Some(1 << 30));
+    }
+}
@@ -1,5 +1,7 @@
+use crate::common::cgroups;
+
 /// Try to read number of CPUs from environment variable `QDRANT_NUM_CPUS`.
-/// If it is not set, use `num_cpus::get()`.
+/// If it is not set, use `num_cpus::get()`, limited by the CPU quota of the cgroup.
 pub fn get_num_cpus() -> usize {
     match std::env::var("QDRANT_NUM_CPUS") {
         Ok(val) => {
@@ -7,9 +9,17 @@ pub fn get_num_cpus() -> usize {
             if num_cpus > 0 {
                 num_cpus
             } else {
-                num_cpus::get()
+                detect_num_cpus()
             }
         }
-        Err(_) => num_cpus::get(),
+        Err(_) => detect_num_cpus(),
+    }
+}
+
+fn detect_num_cpus() -> usize {
+    let num_cpus = num_cpus::get();
+    match cgroups::cpu_quota_limit() {
+        Some(quota) => num_cpus.min(quota).max(1),
+        None => num_cpus,
     }
 }
@@ -0,0 +1,129 @@
+use std::cell::RefCell;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+thread_local! {
+    static CURRENT_LOG_CONTEXT: RefCell<Option<LogContext>> = RefCell::new(None);
+}
+
+/// Collection, shard and API request, which the current thread is working on.
+/// Attached by the logger to all log lines, emitted while the context is entered.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct LogContext {
+    pub collection: Option<Arc<str>>,
+    pub shard_id: Option<u32>,
+    pub request_id: Option<Arc<str>>,
+}
+
+impl LogContext {
+    /// Context of the collection. Request of the current context, if any, is kept
+    pub fn new(collection: &str, shard_id: Option<u32>) -> Self {
+        LogContext {
+            collection: Some(Arc::from(collection)),
+            shard_id,
+            request_id: LogContext::current().and_then(|context| context.request_id),
+        }
+    }
+
+    pub fn request(request_id: &str) -> Self {
+        LogContext {
+            request_id: Some(Arc::from(request_id)),
+            ..Default::default()
+        }
+    }
+
+    pub fn current() -> Option<LogContext> {
+        CURRENT_LOG_CONTEXT.with(|current| current.borrow().clone())
+    }
+
+    /// Enter the context on the current thread, until the guard is dropped
+    pub fn enter(self) -> LogContextGuard {
+        enter(Some(self))
+    }
+}
+
+/// Restores the previous context of the thread on drop
+pub struct LogContextGuard {
+    previous: Option<LogContext>,
+}
+
+impl Drop for LogContextGuard {
+    fn drop(&mut self) {
+        let previous = self.previous.take();
+        CURRENT_LOG_CONTEXT.with(|current| *current.borrow_mut() = previous);
+    }
+}
+
+/// Enter the context on the current thread. `None` clears the context until the guard is dropped
+pub fn enter(context: Option<LogContext>) -> LogContextGuard {
+    let previous = CURRENT_LOG_CONTEXT.with(|current| current.replace(context));
+    LogContextGuard { previous }
+}
+
+/// Future, which enters the context each time it is polled.
+/// Async tasks may move between threads, so the context can't be entered only once.
+pub struct WithLogContext<F> {
+    context: Option<LogContext>,
+    inner: Pin<Box<F>>,
+}
+
+impl<F: Future> Future for WithLogContext<F> {
+    type Output = F::Output;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let _guard = enter(self.context.clone());
+        self.inner.as_mut().poll(cx)
+    }
+}
+
+pub fn with_log_context<F: Future>(context: Option<LogContext>, future: F) -> WithLogContext<F> {
+    WithLogContext {
+        context,
+        inner: Box::pin(future),
+    }
+}
+
+/// Keep the context of the current thread in a future, which is spawned as a separate task
+pub fn in_current_log_context<F: Future>(future: F) -> WithLogContext<F> {
+    with_log_context(LogContext::current(), future)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_log_context_guard() {
+        assert_eq!(LogContext::current(), None);
+        {
+            let _outer = LogContext::new("outer", None).enter();
+            {
+                let _inner = LogContext::new("inner", Some(1)).enter();
+                let current = LogContext::current().unwrap();
+                assert_eq!(current.collection.as_deref(), Some("inner"));
+                assert_eq!(current.shard_id, Some(1));
+
+                let _cleared = enter(None);
+                assert_eq!(LogContext::current(), None);
+            }
+            assert_eq!(
+                LogContext::current().unwrap().collection.as_deref(),
+                Some("outer")
+            );
+
+            let context = std::thread::spawn(LogContext::current).join().unwrap();
+            assert_eq!(context, None);
+        }
+        assert_eq!(LogContext::current(), None);
+    }
+
+    #[test]
+    fn test_log_context_keeps_request() {
+        let _request = LogContext::request("42").enter();
+        let context = LogContext::new("collection", Some(0));
+        assert_eq!(context.collection.as_deref(), Some("collection"));
+        assert_eq!(context.request_id.as_deref(), Some("42"));
+    }
+}
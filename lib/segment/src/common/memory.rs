@@ -0,0 +1,30 @@
+use crate::common::cgroups;
+
+/// Total amount of memory available to the process in bytes:
+/// physical memory of the host, limited by the memory limit of the cgroup.
+/// Returns `None` if it can't be determined on the platform.
+pub fn get_total_memory() -> Option<usize> {
+    match (host_total_memory(), cgroups::memory_limit()) {
+        (Some(total), Some(limit)) => Some(total.min(limit)),
+        (total, limit) => total.or(limit),
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn host_total_memory() -> Option<usize> {
+    let meminfo = std::fs::read_to_string("/proc/meminfo").ok()?;
+    let total_kb = meminfo
+        .lines()
+        .find_map(|line| line.strip_prefix("MemTotal:"))?
+        .trim()
+        .strip_suffix("kB")?
+        .trim()
+        .parse::<usize>()
+        .ok()?;
+    Some(total_kb.saturating_mul(1024))
+}
+
+#[cfg(not(target_os = "linux"))]
+fn host_total_memory() -> Option<usize> {
+    None
+}
@@ -1,10 +1,14 @@
 pub mod anonymize;
 pub mod arc_atomic_ref_cell_iterator;
+pub mod cgroups;
 pub mod cpu;
 pub mod error_logging;
 pub mod file_operations;
+pub mod log_context;
+pub mod memory;
 pub mod operation_time_statistics;
 pub mod rocksdb_wrapper;
+pub mod transcode;
 pub mod utils;
 pub mod version;
 
@@ -23,6 +27,18 @@ pub fn check_vector_name(vector_name: &str, segment_config: &SegmentConfig) -> O
     Ok(())
 }
 
+pub fn check_sparse_vector_name(
+    vector_name: &str,
+    segment_config: &SegmentConfig,
+) -> OperationResult<()> {
+    if !segment_config.sparse_vector_data.contains_key(vector_name) {
+        return Err(OperationError::VectorNameNotExists {
+            received_name: vector_name.to_owned(),
+        });
+    }
+    Ok(())
+}
+
 pub fn check_vectors_set(
     vectors: &NamedVectors,
     segment_config: &SegmentConfig,
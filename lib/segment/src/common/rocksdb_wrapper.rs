@@ -14,9 +14,18 @@ const DB_MAX_LOG_SIZE: usize = 1024 * 1024; // 1 mb
 const DB_MAX_OPEN_FILES: usize = 256;
 
 pub const DB_VECTOR_CF: &str = "vector";
+pub const DB_SPARSE_VECTOR_CF: &str = "sparse_vector";
 pub const DB_PAYLOAD_CF: &str = "payload";
 pub const DB_MAPPING_CF: &str = "mapping";
 pub const DB_VERSIONS_CF: &str = "version";
+pub const DB_POINT_METADATA_CF: &str = "point_metadata";
+
+/// Properties of column families, which report memory held by RocksDB
+const DB_MEMORY_PROPERTIES: [&str; 3] = [
+    "rocksdb.cur-size-all-mem-tables",
+    "rocksdb.estimate-table-readers-mem",
+    "rocksdb.block-cache-usage",
+];
 
 pub struct DatabaseColumnWrapper {
     pub database: Arc<RwLock<DB>>,
@@ -54,7 +63,12 @@ pub fn open_db<T: AsRef<str>>(
     path: &Path,
     vector_pathes: &[T],
 ) -> Result<Arc<RwLock<DB>>, rocksdb::Error> {
-    let mut column_families = vec![DB_PAYLOAD_CF, DB_MAPPING_CF, DB_VERSIONS_CF];
+    let mut column_families = vec![
+        DB_PAYLOAD_CF,
+        DB_MAPPING_CF,
+        DB_VERSIONS_CF,
+        DB_POINT_METADATA_CF,
+    ];
     for vector_path in vector_pathes {
         column_families.push(vector_path.as_ref());
     }
@@ -77,6 +91,21 @@ pub fn open_db_with_existing_cf(path: &Path) -> Result<Arc<RwLock<DB>>, rocksdb:
     Ok(Arc::new(RwLock::new(db)))
 }
 
+/// Estimated memory, used by memtables, table readers and block caches of all column families
+pub fn db_memory_usage(db: &DB) -> usize {
+    let column_families = DB::list_cf(&db_options(), db.path()).unwrap_or_default();
+    column_families
+        .iter()
+        .filter_map(|name| db.cf_handle(name))
+        .flat_map(|cf| {
+            DB_MEMORY_PROPERTIES
+                .iter()
+                .map(move |property| db.property_int_value_cf(cf, property))
+        })
+        .filter_map(|value| value.ok().flatten())
+        .sum::<u64>() as usize
+}
+
 pub fn db_write_options() -> WriteOptions {
     let mut write_options = WriteOptions::default();
     write_options.set_sync(false);
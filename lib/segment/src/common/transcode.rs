@@ -0,0 +1,163 @@
+use std::cell::RefCell;
+use std::fmt;
+
+use serde::de::{DeserializeSeed, MapAccess, SeqAccess, Visitor};
+use serde::ser::{Error as _, SerializeMap, SerializeSeq};
+use serde::{de, Deserializer, Serialize, Serializer};
+
+/// Serializes everything the deserializer produces, without building an intermediate value.
+///
+/// Used to write stored payloads into responses directly from their serialized form.
+/// Only the data model of JSON values is supported: no bytes, enums or structs.
+pub struct Transcoder<D>(RefCell<Option<D>>);
+
+impl<D> Transcoder<D> {
+    pub fn new(deserializer: D) -> Self {
+        Transcoder(RefCell::new(Some(deserializer)))
+    }
+}
+
+impl<'de, D> Serialize for Transcoder<D>
+where
+    D: Deserializer<'de>,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let deserializer = self
+            .0
+            .borrow_mut()
+            .take()
+            .ok_or_else(|| S::Error::custom("Transcoder can only be serialized once"))?;
+        deserializer
+            .deserialize_any(TranscodeVisitor(serializer))
+            .map_err(S::Error::custom)
+    }
+}
+
+fn to_de_error<E: fmt::Display, F: de::Error>(error: E) -> F {
+    F::custom(error)
+}
+
+struct TranscodeVisitor<S>(S);
+
+impl<'de, S: Serializer> Visitor<'de> for TranscodeVisitor<S> {
+    type Value = S::Ok;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a JSON value")
+    }
+
+    fn visit_bool<E: de::Error>(self, v: bool) -> Result<S::Ok, E> {
+        self.0.serialize_bool(v).map_err(to_de_error)
+    }
+
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<S::Ok, E> {
+        self.0.serialize_i64(v).map_err(to_de_error)
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<S::Ok, E> {
+        self.0.serialize_u64(v).map_err(to_de_error)
+    }
+
+    fn visit_f64<E: de::Error>(self, v: f64) -> Result<S::Ok, E> {
+        self.0.serialize_f64(v).map_err(to_de_error)
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<S::Ok, E> {
+        self.0.serialize_str(v).map_err(to_de_error)
+    }
+
+    fn visit_none<E: de::Error>(self) -> Result<S::Ok, E> {
+        self.0.serialize_none().map_err(to_de_error)
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<S::Ok, D::Error> {
+        self.0
+            .serialize_some(&Transcoder::new(deserializer))
+            .map_err(to_de_error)
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<S::Ok, E> {
+        self.0.serialize_unit().map_err(to_de_error)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<S::Ok, A::Error> {
+        let mut serializer = self.0.serialize_seq(seq.size_hint()).map_err(to_de_error)?;
+        while seq
+            .next_element_seed(ElementSeed(&mut serializer))?
+            .is_some()
+        {}
+        serializer.end().map_err(to_de_error)
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<S::Ok, A::Error> {
+        let mut serializer = self.0.serialize_map(map.size_hint()).map_err(to_de_error)?;
+        while map.next_key_seed(KeySeed(&mut serializer))?.is_some() {
+            map.next_value_seed(ValueSeed(&mut serializer))?;
+        }
+        serializer.end().map_err(to_de_error)
+    }
+}
+
+struct ElementSeed<'a, S>(&'a mut S);
+
+impl<'a, 'de, S: SerializeSeq> DeserializeSeed<'de> for ElementSeed<'a, S> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        self.0
+            .serialize_element(&Transcoder::new(deserializer))
+            .map_err(to_de_error)
+    }
+}
+
+struct KeySeed<'a, S>(&'a mut S);
+
+impl<'a, 'de, S: SerializeMap> DeserializeSeed<'de> for KeySeed<'a, S> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        self.0
+            .serialize_key(&Transcoder::new(deserializer))
+            .map_err(to_de_error)
+    }
+}
+
+struct ValueSeed<'a, S>(&'a mut S);
+
+impl<'a, 'de, S: SerializeMap> DeserializeSeed<'de> for ValueSeed<'a, S> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        self.0
+            .serialize_value(&Transcoder::new(deserializer))
+            .map_err(to_de_error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_transcode_cbor_to_json() {
+        let value = json!({
+            "name": "John Doe",
+            "age": 43,
+            "balance": -12.5,
+            "tags": ["a", "b", null, true],
+            "address": { "city": "Berlin", "zip": 10115 },
+            "empty": {}
+        });
+        let cbor = serde_cbor::to_vec(&value).unwrap();
+
+        let mut deserializer = serde_cbor::Deserializer::from_slice(&cbor);
+        let json = serde_json::to_string(&Transcoder::new(&mut deserializer)).unwrap();
+        assert_eq!(json, serde_json::to_string(&value).unwrap());
+
+        let truncated = &cbor[..cbor.len() - 3];
+        let mut deserializer = serde_cbor::Deserializer::from_slice(truncated);
+        assert!(serde_json::to_string(&Transcoder::new(&mut deserializer)).is_err());
+    }
+}
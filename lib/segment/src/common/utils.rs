@@ -1,9 +1,12 @@
 use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
 
+use seahash::SeaHasher;
 use serde_json::Value;
 
 use crate::data_types::named_vectors::NamedVectors;
 use crate::data_types::vectors::VectorElementType;
+use crate::types::PointIdType;
 
 /// Avoids allocating Vec with a single element
 #[derive(Debug)]
@@ -109,6 +112,16 @@ impl<T> Iterator for MultiValue<T> {
     }
 }
 
+/// Deterministic pseudo-random key of the point for the given `seed`.
+///
+/// Sampling takes points with the smallest keys, which is equivalent to reservoir sampling,
+/// but produces the same sample regardless of how points are distributed across segments and shards.
+pub fn sampling_key(seed: u64, point_id: PointIdType) -> u64 {
+    let mut hasher = SeaHasher::with_seeds(seed, seed, seed, seed);
+    point_id.hash(&mut hasher);
+    hasher.finish()
+}
+
 pub fn rev_range(a: usize, b: usize) -> impl Iterator<Item = usize> {
     (b + 1..=a).rev()
 }
@@ -293,6 +306,57 @@ pub fn remove_value_from_json_map(
     }
 }
 
+/// Copy values found by the `path` from `src` into `dst`, preserving their nesting.
+///
+/// Arrays of objects on the path are projected element-wise,
+/// so `{"a": [{"b": 1, "c": 2}]}` projected by `a[].b` becomes `{"a": [{"b": 1}]}`.
+pub fn copy_value_by_json_path(
+    path: &str,
+    src: &serde_json::Map<String, Value>,
+    dst: &mut serde_json::Map<String, Value>,
+) {
+    // Keys, which contain dots themselves, take precedence over nested paths
+    if let Some(value) = src.get(path) {
+        dst.insert(path.to_string(), value.clone());
+        return;
+    }
+
+    // Do not produce empty intermediate objects for missing values
+    if get_value_from_json_map(path, src).next().is_none() {
+        return;
+    }
+
+    let (element, rest_path) = match path.split_once('.') {
+        Some(split) => split,
+        None => return,
+    };
+    let key = parse_array_path(element).map_or(element, |(key, _)| key);
+
+    match src.get(key) {
+        Some(Value::Object(src_map)) => {
+            let entry = dst
+                .entry(key.to_string())
+                .or_insert_with(|| Value::Object(Default::default()));
+            if let Value::Object(dst_map) = entry {
+                copy_value_by_json_path(rest_path, src_map, dst_map);
+            }
+        }
+        Some(Value::Array(src_array)) => {
+            let entry = dst.entry(key.to_string()).or_insert_with(|| {
+                Value::Array(vec![Value::Object(Default::default()); src_array.len()])
+            });
+            if let Value::Array(dst_array) = entry {
+                for (src_item, dst_item) in src_array.iter().zip(dst_array.iter_mut()) {
+                    if let (Value::Object(src_map), Value::Object(dst_map)) = (src_item, dst_item) {
+                        copy_value_by_json_path(rest_path, src_map, dst_map);
+                    }
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
 pub fn transpose_map_into_named_vector(
     map: HashMap<String, Vec<Vec<VectorElementType>>>,
 ) -> Vec<NamedVectors<'static>> {
@@ -310,6 +374,47 @@ pub fn transpose_map_into_named_vector(
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_copy_value_by_json_path() {
+        let map = serde_json::from_str::<serde_json::Map<String, Value>>(
+            r#"
+            {
+                "a": {
+                    "b": 1,
+                    "c": { "d": 2, "e": 3 }
+                },
+                "f": [ { "g": 4, "h": 5 }, { "h": 6 } ],
+                "i.j": 7
+            }
+            "#,
+        )
+        .unwrap();
+
+        let mut projection = serde_json::Map::new();
+        copy_value_by_json_path("a.c.d", &map, &mut projection);
+        copy_value_by_json_path("a.b", &map, &mut projection);
+        copy_value_by_json_path("f[].g", &map, &mut projection);
+        copy_value_by_json_path("i.j", &map, &mut projection);
+        copy_value_by_json_path("a.missing", &map, &mut projection);
+        copy_value_by_json_path("missing.key", &map, &mut projection);
+
+        let expected = serde_json::from_str::<serde_json::Map<String, Value>>(
+            r#"
+            {
+                "a": {
+                    "b": 1,
+                    "c": { "d": 2 }
+                },
+                "f": [ { "g": 4 }, {} ],
+                "i.j": 7
+            }
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(projection, expected);
+    }
+
     #[test]
     fn test_get_nested_value_from_json_map() {
         let map = serde_json::from_str::<serde_json::Map<String, Value>>(
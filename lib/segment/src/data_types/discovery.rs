@@ -0,0 +1,83 @@
+use crate::data_types::vectors::VectorElementType;
+use crate::spaces::tools::sigmoid;
+use crate::types::{Distance, ScoreType};
+
+/// Pair of examples, which splits the space into the preferred half, closer to the positive
+/// example, and the avoided one, closer to the negative example
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContextPair {
+    pub positive: Vec<VectorElementType>,
+    pub negative: Vec<VectorElementType>,
+}
+
+impl ContextPair {
+    /// 0 if the vector is in the preferred half of the space, -1 otherwise
+    pub fn rank(&self, vector: &[VectorElementType], distance: Distance) -> i32 {
+        let positive = distance.similarity(vector, &self.positive);
+        let negative = distance.similarity(vector, &self.negative);
+        if positive > negative {
+            0
+        } else {
+            -1
+        }
+    }
+}
+
+/// Look for vectors close to the target, which are in the preferred half of the space of as many
+/// context pairs as possible.
+///
+/// All vectors are expected to be preprocessed for the distance.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiscoveryQuery {
+    pub target: Vec<VectorElementType>,
+    pub pairs: Vec<ContextPair>,
+}
+
+impl DiscoveryQuery {
+    /// Integer part of the score is the negated number of violated context pairs,
+    /// fractional part is the similarity to the target, mapped into range from 0 to 1.
+    /// So the vector, which violates fewer pairs, is always scored higher.
+    pub fn score(&self, vector: &[VectorElementType], distance: Distance) -> ScoreType {
+        let rank: i32 = self
+            .pairs
+            .iter()
+            .map(|pair| pair.rank(vector, distance))
+            .sum();
+        rank as ScoreType + sigmoid(distance.similarity(vector, &self.target))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_discovery_score() {
+        let query = DiscoveryQuery {
+            target: vec![1.0, 0.0],
+            pairs: vec![ContextPair {
+                positive: vec![0.0, 1.0],
+                negative: vec![0.0, -1.0],
+            }],
+        };
+        let distance = Distance::Dot;
+
+        // Close to the target, but in the avoided half of the context
+        let violating = query.score(&[1.0, -0.1], distance);
+        // Further from the target, but in the preferred half
+        let satisfying = query.score(&[0.5, 0.5], distance);
+        let exact_on_border = query.score(&[1.0, 0.0], distance);
+
+        assert!(violating < 0.0);
+        assert!(satisfying > 0.0);
+        assert!(satisfying > violating);
+        // Equal similarity to both examples doesn't satisfy the pair
+        assert!(exact_on_border < 0.0);
+
+        let no_context = DiscoveryQuery {
+            target: vec![1.0, 0.0],
+            pairs: vec![],
+        };
+        assert!(no_context.score(&[1.0, 0.0], distance) > no_context.score(&[0.5, 0.5], distance));
+    }
+}
@@ -0,0 +1,179 @@
+use std::collections::HashSet;
+use std::hash::{Hash, Hasher};
+
+use seahash::SeaHasher;
+
+/// Number of distinct values, up to which they are counted exactly
+pub const DISTINCT_EXACT_THRESHOLD: usize = 10_000;
+
+/// Number of bits of the hash, which select the register of HyperLogLog.
+/// Standard error of the estimation is `1.04 / sqrt(2^HLL_PRECISION)`, about 0.8%.
+const HLL_PRECISION: u32 = 14;
+const HLL_REGISTERS: usize = 1 << HLL_PRECISION;
+
+/// Hash of the value, which is the same on all nodes, so values of different shards can be merged
+pub fn value_hash<T: Hash + ?Sized>(value: &T) -> u64 {
+    let mut hasher = SeaHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Distinct values of a field, identified by hashes.
+/// Hashes are kept while there are few of them, then they are replaced with a HyperLogLog sketch.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DistinctValues {
+    Exact(HashSet<u64>),
+    Approximate(HyperLogLog),
+}
+
+impl Default for DistinctValues {
+    fn default() -> Self {
+        DistinctValues::Exact(HashSet::new())
+    }
+}
+
+impl DistinctValues {
+    pub fn insert(&mut self, hash: u64) {
+        match self {
+            DistinctValues::Exact(hashes) => {
+                hashes.insert(hash);
+                if hashes.len() > DISTINCT_EXACT_THRESHOLD {
+                    let mut sketch = HyperLogLog::default();
+                    hashes.drain().for_each(|hash| sketch.insert(hash));
+                    *self = DistinctValues::Approximate(sketch);
+                }
+            }
+            DistinctValues::Approximate(sketch) => sketch.insert(hash),
+        }
+    }
+
+    pub fn merge(&mut self, other: DistinctValues) {
+        match other {
+            DistinctValues::Exact(hashes) => hashes.into_iter().for_each(|hash| self.insert(hash)),
+            DistinctValues::Approximate(mut sketch) => {
+                match self {
+                    DistinctValues::Exact(hashes) => {
+                        hashes.drain().for_each(|hash| sketch.insert(hash))
+                    }
+                    DistinctValues::Approximate(self_sketch) => sketch.merge(self_sketch),
+                }
+                *self = DistinctValues::Approximate(sketch);
+            }
+        }
+    }
+
+    pub fn is_exact(&self) -> bool {
+        matches!(self, DistinctValues::Exact(_))
+    }
+
+    pub fn count(&self) -> usize {
+        match self {
+            DistinctValues::Exact(hashes) => hashes.len(),
+            DistinctValues::Approximate(sketch) => sketch.estimate(),
+        }
+    }
+}
+
+/// HyperLogLog sketch of a set of hashes
+#[derive(Debug, Clone, PartialEq)]
+pub struct HyperLogLog {
+    registers: Vec<u8>,
+}
+
+impl Default for HyperLogLog {
+    fn default() -> Self {
+        Self {
+            registers: vec![0; HLL_REGISTERS],
+        }
+    }
+}
+
+impl HyperLogLog {
+    /// Restore the sketch from its registers.
+    /// Returns `None` if the number of registers doesn't match the precision of the sketch.
+    pub fn from_registers(registers: Vec<u8>) -> Option<Self> {
+        (registers.len() == HLL_REGISTERS).then_some(Self { registers })
+    }
+
+    pub fn registers(&self) -> &[u8] {
+        &self.registers
+    }
+
+    pub fn into_registers(self) -> Vec<u8> {
+        self.registers
+    }
+
+    pub fn insert(&mut self, hash: u64) {
+        let register = (hash >> (64 - HLL_PRECISION)) as usize;
+        // Position of the first set bit in the rest of the hash, the marker bit limits it
+        let rest = (hash << HLL_PRECISION) | (1 << (HLL_PRECISION - 1));
+        let rank = rest.leading_zeros() as u8 + 1;
+        let value = &mut self.registers[register];
+        *value = (*value).max(rank);
+    }
+
+    pub fn merge(&mut self, other: &HyperLogLog) {
+        for (value, &other_value) in self.registers.iter_mut().zip(&other.registers) {
+            *value = (*value).max(other_value);
+        }
+    }
+
+    pub fn estimate(&self) -> usize {
+        let registers = HLL_REGISTERS as f64;
+        let alpha = 0.7213 / (1.0 + 1.079 / registers);
+        let sum: f64 = self
+            .registers
+            .iter()
+            .map(|&value| 2f64.powi(-(value as i32)))
+            .sum();
+        let estimate = alpha * registers * registers / sum;
+
+        // Linear counting is more precise for small sets
+        let empty_registers = self.registers.iter().filter(|&&value| value == 0).count();
+        if estimate <= 2.5 * registers && empty_registers > 0 {
+            return (registers * (registers / empty_registers as f64).ln()).round() as usize;
+        }
+        estimate.round() as usize
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn distinct_values(values: impl Iterator<Item = usize>) -> DistinctValues {
+        let mut distinct = DistinctValues::default();
+        values.for_each(|value| distinct.insert(value_hash(&value)));
+        distinct
+    }
+
+    #[test]
+    fn test_exact_distinct_values() {
+        let mut distinct = distinct_values((0..100).map(|value| value % 30));
+        assert!(distinct.is_exact());
+        assert_eq!(distinct.count(), 30);
+
+        distinct.merge(distinct_values(20..50));
+        assert!(distinct.is_exact());
+        assert_eq!(distinct.count(), 50);
+    }
+
+    #[test]
+    fn test_approximate_distinct_values() {
+        let total = 100_000;
+        let mut distinct = distinct_values(0..total / 2);
+        assert!(!distinct.is_exact());
+
+        // Values of the other half intersect with the first one
+        distinct.merge(distinct_values(total / 4..total));
+        let error = (distinct.count() as f64 - total as f64).abs() / total as f64;
+        assert!(error < 0.05, "error: {error}");
+
+        let mut exact = distinct_values(0..DISTINCT_EXACT_THRESHOLD);
+        assert!(exact.is_exact());
+        exact.merge(distinct);
+        assert!(!exact.is_exact());
+        let error = (exact.count() as f64 - total as f64).abs() / total as f64;
+        assert!(error < 0.05, "error: {error}");
+    }
+}
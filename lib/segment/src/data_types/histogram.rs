@@ -0,0 +1,99 @@
+/// Min and max of numeric values
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ValueBounds {
+    pub min: f64,
+    pub max: f64,
+}
+
+impl ValueBounds {
+    pub fn new(value: f64) -> Self {
+        Self {
+            min: value,
+            max: value,
+        }
+    }
+
+    pub fn merge(self, other: ValueBounds) -> Self {
+        Self {
+            min: self.min.min(other.min),
+            max: self.max.max(other.max),
+        }
+    }
+
+    /// Merge optional bounds, where `None` means there are no values
+    pub fn merge_opt(a: Option<ValueBounds>, b: Option<ValueBounds>) -> Option<ValueBounds> {
+        match (a, b) {
+            (Some(a), Some(b)) => Some(a.merge(b)),
+            (bounds, None) | (None, bounds) => bounds,
+        }
+    }
+}
+
+/// Buckets of equal width, which split the bounds of numeric values
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HistogramBuckets {
+    pub bounds: ValueBounds,
+    pub count: usize,
+}
+
+impl HistogramBuckets {
+    pub fn width(&self) -> f64 {
+        (self.bounds.max - self.bounds.min) / self.count as f64
+    }
+
+    /// Index of the bucket, which contains the value.
+    /// The last bucket includes the max value, values out of the bounds have no bucket.
+    pub fn bucket(&self, value: f64) -> Option<usize> {
+        let ValueBounds { min, max } = self.bounds;
+        if !(min..=max).contains(&value) || self.count == 0 {
+            return None;
+        }
+        if max == min {
+            return Some(0);
+        }
+        let bucket = ((value - min) / (max - min) * self.count as f64) as usize;
+        Some(bucket.min(self.count - 1))
+    }
+
+    /// Lower border of the bucket, the upper one is the lower border of the next bucket
+    pub fn bucket_start(&self, bucket: usize) -> f64 {
+        if bucket == self.count {
+            return self.bounds.max;
+        }
+        self.bounds.min + self.width() * bucket as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_histogram_buckets() {
+        let buckets = HistogramBuckets {
+            bounds: ValueBounds::new(0.0).merge(ValueBounds::new(10.0)),
+            count: 4,
+        };
+        assert_eq!(buckets.width(), 2.5);
+        assert_eq!(buckets.bucket(0.0), Some(0));
+        assert_eq!(buckets.bucket(2.5), Some(1));
+        assert_eq!(buckets.bucket(7.4), Some(2));
+        assert_eq!(buckets.bucket(10.0), Some(3));
+        assert_eq!(buckets.bucket(10.1), None);
+        assert_eq!(buckets.bucket(-0.1), None);
+        assert_eq!(buckets.bucket_start(1), 2.5);
+        assert_eq!(buckets.bucket_start(4), 10.0);
+
+        let single_value = HistogramBuckets {
+            bounds: ValueBounds::new(3.0),
+            count: 4,
+        };
+        assert_eq!(single_value.bucket(3.0), Some(0));
+
+        assert_eq!(
+            ValueBounds::merge_opt(None, Some(ValueBounds::new(1.0))),
+            Some(ValueBounds::new(1.0))
+        );
+        assert_eq!(ValueBounds::merge_opt(None, None), None);
+    }
+}
@@ -1,4 +1,9 @@
+pub mod discovery;
+pub mod distinct;
+pub mod histogram;
 pub mod named_vectors;
+pub mod order_by;
+pub mod sparse_vector;
 pub mod text_index;
 pub mod tiny_map;
 pub mod vectors;
@@ -0,0 +1,44 @@
+use std::cmp::Ordering;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Direction of the order by values of the payload field
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum Direction {
+    /// Smallest values first
+    #[default]
+    Asc,
+    /// Largest values first
+    Desc,
+}
+
+impl Direction {
+    /// Compare values, so that the value, which goes first in this direction, is less
+    pub fn cmp(self, a: f64, b: f64) -> Ordering {
+        match self {
+            Direction::Asc => a.total_cmp(&b),
+            Direction::Desc => b.total_cmp(&a),
+        }
+    }
+
+    /// Value of the point with several values, by which it is ordered:
+    /// the smallest one in ascending order and the largest one in descending order
+    pub fn order_value(self, values: impl IntoIterator<Item = f64>) -> Option<f64> {
+        values.into_iter().min_by(|&a, &b| self.cmp(a, b))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_order_value() {
+        let values = [3.0, -1.0, 7.5];
+        assert_eq!(Direction::Asc.order_value(values), Some(-1.0));
+        assert_eq!(Direction::Desc.order_value(values), Some(7.5));
+        assert_eq!(Direction::Asc.order_value([]), None);
+    }
+}
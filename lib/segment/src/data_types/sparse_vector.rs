@@ -0,0 +1,95 @@
+use std::collections::{HashMap, HashSet};
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use validator::{Validate, ValidationError, ValidationErrors};
+
+use super::vectors::VectorElementType;
+use crate::types::ScoreType;
+
+/// Index of a dimension of a sparse vector
+pub type DimId = u32;
+
+/// Sparse vector structure: values of non-zero dimensions along with their indices
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub struct SparseVector {
+    /// Indices of non-zero dimensions, must be unique
+    pub indices: Vec<DimId>,
+    /// Values of non-zero dimensions, must have the same length as `indices`
+    pub values: Vec<VectorElementType>,
+}
+
+/// Sparse vectors of a single point, by vector name
+pub type SparseVectors = HashMap<String, SparseVector>;
+
+impl SparseVector {
+    pub fn new(indices: Vec<DimId>, values: Vec<VectorElementType>) -> Self {
+        SparseVector { indices, values }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.indices.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (DimId, VectorElementType)> + '_ {
+        self.indices
+            .iter()
+            .copied()
+            .zip(self.values.iter().copied())
+    }
+
+    /// Dot product with another sparse vector
+    pub fn score(&self, other: &SparseVector) -> ScoreType {
+        let other_values: HashMap<DimId, VectorElementType> = other.iter().collect();
+        self.iter()
+            .filter_map(|(idx, value)| other_values.get(&idx).map(|other| value * other))
+            .sum()
+    }
+}
+
+impl Validate for SparseVector {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+        if self.indices.len() != self.values.len() {
+            let mut error = ValidationError::new("values");
+            error.message = Some("must have the same length as indices".into());
+            errors.add("values", error);
+        }
+        let unique_indices: HashSet<_> = self.indices.iter().collect();
+        if unique_indices.len() != self.indices.len() {
+            let mut error = ValidationError::new("indices");
+            error.message = Some("must be unique".into());
+            errors.add("indices", error);
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sparse_vector_validation() {
+        assert!(SparseVector::new(vec![1, 3], vec![0.5, 1.0])
+            .validate()
+            .is_ok());
+        assert!(SparseVector::new(vec![1, 3], vec![0.5]).validate().is_err());
+        assert!(SparseVector::new(vec![1, 1], vec![0.5, 1.0])
+            .validate()
+            .is_err());
+    }
+
+    #[test]
+    fn test_sparse_vector_score() {
+        let a = SparseVector::new(vec![1, 3, 7], vec![1.0, 2.0, 3.0]);
+        let b = SparseVector::new(vec![7, 2, 3], vec![0.5, 4.0, 1.0]);
+        assert_eq!(a.score(&b), 2.0 + 1.5);
+        assert_eq!(a.score(&SparseVector::default()), 0.0);
+    }
+}
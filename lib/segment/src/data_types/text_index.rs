@@ -1,3 +1,5 @@
+use std::collections::{BTreeMap, BTreeSet};
+
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -40,4 +42,34 @@ pub struct TextIndexParams {
     #[serde(skip_serializing_if = "Option::is_none")]
     /// If true, lowercase all tokens. Default: true
     pub lowercase: Option<bool>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Tokens to exclude from both documents and queries.
+    /// Compared with tokens after lowercasing, if it is enabled.
+    pub stopwords: Option<BTreeSet<String>>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Query-time expansion of tokens: a query token also matches documents with any of its synonyms.
+    /// Keys and synonyms are single tokens, compared after lowercasing, if it is enabled.
+    pub synonyms: Option<BTreeMap<String, BTreeSet<String>>>,
+}
+
+impl TextIndexParams {
+    pub fn is_stopword(&self, token: &str) -> bool {
+        self.stopwords
+            .as_ref()
+            .map_or(false, |stopwords| stopwords.contains(token))
+    }
+
+    /// Query token together with its synonyms
+    pub fn token_alternatives(&self, token: &str) -> BTreeSet<String> {
+        let mut alternatives = self
+            .synonyms
+            .as_ref()
+            .and_then(|synonyms| synonyms.get(token))
+            .cloned()
+            .unwrap_or_default();
+        alternatives.insert(token.to_owned());
+        alternatives
+    }
 }
@@ -10,15 +10,20 @@ use rayon::ThreadPoolBuildError;
 use thiserror::Error;
 
 use crate::common::file_operations::FileStorageError;
+use crate::data_types::distinct::DistinctValues;
+use crate::data_types::histogram::{HistogramBuckets, ValueBounds};
 use crate::data_types::named_vectors::NamedVectors;
+use crate::data_types::order_by::Direction;
+use crate::data_types::sparse_vector::{SparseVector, SparseVectors};
 use crate::data_types::vectors::VectorElementType;
 use crate::index::field_index::CardinalityEstimation;
 use crate::telemetry::SegmentTelemetry;
 use crate::types::{
-    Filter, Payload, PayloadFieldSchema, PayloadKeyType, PayloadKeyTypeRef, PointIdType,
-    ScoredPoint, SearchParams, SegmentConfig, SegmentInfo, SegmentType, SeqNumberType, WithPayload,
-    WithVector,
+    Filter, GeoPoint, MemoryUsage, Payload, PayloadFieldSchema, PayloadKeyType, PayloadKeyTypeRef,
+    PlannerHint, PointIdType, PointMetadata, RecordPayload, ScoredPoint, SearchParams,
+    SegmentConfig, SegmentInfo, SegmentType, SeqNumberType, WithPayload, WithVector,
 };
+use crate::vector_storage::scoring_benchmark::ScoringMeasurement;
 
 #[derive(Error, Debug, Clone)]
 #[error("{0}")]
@@ -28,6 +33,8 @@ pub enum OperationError {
         expected_dim: usize,
         received_dim: usize,
     },
+    #[error("Wrong sparse vector: {description}")]
+    WrongSparseVector { description: String },
     #[error("Not existing vector name error: {received_name}")]
     VectorNameNotExists { received_name: String },
     #[error("Missed vector name error: {received_name}")]
@@ -41,6 +48,8 @@ pub enum OperationError {
     },
     #[error("Unable to infer type for the field '{field_name}'. Please specify `field_type`")]
     TypeInferenceError { field_name: PayloadKeyType },
+    #[error("Original vectors are not stored for vector name {vector_name}, only quantized vectors are kept")]
+    VectorsNotStored { vector_name: String },
     /// Service Error prevents further update of the collection until it is fixed.
     /// Should only be used for hardware, data corruption, IO, or other unexpected internal errors.
     #[error("Service runtime error: {description}")]
@@ -181,6 +190,8 @@ pub trait SegmentEntry {
         params: Option<&SearchParams>,
     ) -> OperationResult<Vec<ScoredPoint>>;
 
+    /// Search for the batch of vectors with the same parameters.
+    /// Returns `Cancelled` error if `is_stopped` is set before the search is complete.
     #[allow(clippy::too_many_arguments)]
     fn search_batch(
         &self,
@@ -191,6 +202,7 @@ pub trait SegmentEntry {
         filter: Option<&Filter>,
         top: usize,
         params: Option<&SearchParams>,
+        is_stopped: &AtomicBool,
     ) -> OperationResult<Vec<Vec<ScoredPoint>>>;
 
     fn upsert_vector(
@@ -200,6 +212,27 @@ pub trait SegmentEntry {
         vectors: &NamedVectors,
     ) -> OperationResult<bool>;
 
+    /// Search points by the sparse vector, using dot product as a score
+    #[allow(clippy::too_many_arguments)]
+    fn search_sparse(
+        &self,
+        vector_name: &str,
+        vector: &SparseVector,
+        with_payload: &WithPayload,
+        with_vector: &WithVector,
+        filter: Option<&Filter>,
+        top: usize,
+    ) -> OperationResult<Vec<ScoredPoint>>;
+
+    /// Replace sparse vectors of the existing point.
+    /// Sparse vectors, which are not present in `vectors`, are removed from the point.
+    fn update_sparse_vectors(
+        &mut self,
+        op_num: SeqNumberType,
+        point_id: PointIdType,
+        vectors: &SparseVectors,
+    ) -> OperationResult<bool>;
+
     fn delete_point(
         &mut self,
         op_num: SeqNumberType,
@@ -233,6 +266,14 @@ pub trait SegmentEntry {
         point_id: PointIdType,
     ) -> OperationResult<bool>;
 
+    /// Replace service metadata of the point, which is stored apart from its payload
+    fn set_point_metadata(
+        &mut self,
+        op_num: SeqNumberType,
+        point_id: PointIdType,
+        metadata: &PointMetadata,
+    ) -> OperationResult<bool>;
+
     fn vector(
         &self,
         vector_name: &str,
@@ -241,16 +282,37 @@ pub trait SegmentEntry {
 
     fn all_vectors(&self, point_id: PointIdType) -> OperationResult<NamedVectors>;
 
+    /// Sparse vectors of the point. Names without vector for the point are omitted.
+    fn sparse_vectors(&self, point_id: PointIdType) -> OperationResult<SparseVectors>;
+
     fn payload(&self, point_id: PointIdType) -> OperationResult<Payload>;
 
+    /// Payload of the point, as it is stored.
+    /// Might be kept serialized, so it could be written into a response without decoding.
+    fn raw_payload(&self, point_id: PointIdType) -> OperationResult<RecordPayload>;
+
+    /// Service metadata of the point, empty if none is stored
+    fn point_metadata(&self, point_id: PointIdType) -> OperationResult<PointMetadata>;
+
     fn iter_points(&self) -> Box<dyn Iterator<Item = PointIdType> + '_>;
 
     /// Paginate over points which satisfies filtering condition starting with `offset` id including.
+    /// `planner_hint` overrides the reading strategy, selected by the cardinality of the filter.
     fn read_filtered<'a>(
         &'a self,
         offset: Option<PointIdType>,
         limit: Option<usize>,
         filter: Option<&'a Filter>,
+        planner_hint: Option<PlannerHint>,
+    ) -> Vec<PointIdType>;
+
+    /// Pseudo-random sample of at most `limit` points, which satisfy filtering condition.
+    /// Sample depends only on the `seed`, points are ordered by their sampling key.
+    fn sample_filtered<'a>(
+        &'a self,
+        limit: usize,
+        filter: Option<&'a Filter>,
+        seed: u64,
     ) -> Vec<PointIdType>;
 
     /// Read points in [from; to) range
@@ -265,6 +327,62 @@ pub trait SegmentEntry {
     /// Estimate points count in this segment for given filter.
     fn estimate_points_count<'a>(&'a self, filter: Option<&'a Filter>) -> CardinalityEstimation;
 
+    /// Number of points, which satisfy filtering condition, for each term of the keyword or
+    /// full-text index of the field. Returns `None` if the field has no such index.
+    fn term_counts<'a>(
+        &'a self,
+        key: PayloadKeyTypeRef,
+        filter: Option<&'a Filter>,
+    ) -> Option<HashMap<String, usize>>;
+
+    /// Distinct values of the keyword or integer index of the field among points, which satisfy
+    /// filtering condition. Returns `None` if the field has no such index.
+    fn distinct_values<'a>(
+        &'a self,
+        key: PayloadKeyTypeRef,
+        filter: Option<&'a Filter>,
+    ) -> Option<DistinctValues>;
+
+    /// Min and max values of the integer or float index of the field among points, which satisfy
+    /// filtering condition. Returns `None` if the field has no numeric index.
+    fn numeric_bounds<'a>(
+        &'a self,
+        key: PayloadKeyTypeRef,
+        filter: Option<&'a Filter>,
+    ) -> Option<Option<ValueBounds>>;
+
+    /// Number of points, which satisfy filtering condition, with values of the integer or float
+    /// index of the field in each of the buckets. Returns `None` if the field has no numeric index.
+    fn numeric_histogram<'a>(
+        &'a self,
+        key: PayloadKeyTypeRef,
+        buckets: &HistogramBuckets,
+        filter: Option<&'a Filter>,
+    ) -> Option<Vec<usize>>;
+
+    /// Points, which satisfy filtering condition, nearest to the `origin` according to the geo
+    /// index of the field, with distances in meters. Returns `None` if the field has no geo index.
+    fn nearest_points<'a>(
+        &'a self,
+        key: PayloadKeyTypeRef,
+        origin: &GeoPoint,
+        limit: usize,
+        filter: Option<&'a Filter>,
+    ) -> Option<Vec<(PointIdType, f64)>>;
+
+    /// Points, which satisfy filtering condition, ordered by values of the integer or float index
+    /// of the field, starting from the `start_from` value and `offset` id, with the values, by which
+    /// they are ordered. Returns `None` if the field has no integer or float range index.
+    fn ordered_points<'a>(
+        &'a self,
+        key: PayloadKeyTypeRef,
+        direction: Direction,
+        start_from: Option<f64>,
+        offset: Option<PointIdType>,
+        limit: usize,
+        filter: Option<&'a Filter>,
+    ) -> Option<Vec<(PointIdType, f64)>>;
+
     fn vector_dim(&self, vector_name: &str) -> OperationResult<usize>;
 
     fn vector_dims(&self) -> HashMap<String, usize>;
@@ -331,4 +449,16 @@ pub trait SegmentEntry {
 
     // Get collected telemetry data of segment
     fn get_telemetry_data(&self) -> SegmentTelemetry;
+
+    /// Estimate memory, occupied by the segment components.
+    /// Iterates over indexed values, so it is more expensive than `info`.
+    fn memory_usage(&self) -> MemoryUsage;
+
+    /// Measure scoring throughput of the stored vectors of `vector_name`.
+    /// Scores a random query against at most `points_limit` vectors.
+    fn benchmark_scoring(
+        &self,
+        vector_name: &str,
+        points_limit: usize,
+    ) -> OperationResult<Vec<ScoringMeasurement>>;
 }
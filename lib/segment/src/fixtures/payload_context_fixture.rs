@@ -18,7 +18,7 @@ use crate::index::PayloadIndex;
 use crate::payload_storage::in_memory_payload_storage::InMemoryPayloadStorage;
 use crate::payload_storage::query_checker::SimpleConditionChecker;
 use crate::payload_storage::PayloadStorage;
-use crate::types::{PayloadSchemaType, PointIdType, PointOffsetType, SeqNumberType};
+use crate::types::{PayloadSchemaType, PointIdType, PointMetadata, PointOffsetType, SeqNumberType};
 
 /// Warn: Use for tests only
 ///
@@ -52,6 +52,18 @@ impl IdTracker for FixtureIdTracker {
         Ok(())
     }
 
+    fn point_metadata(&self, _internal_id: PointOffsetType) -> Option<&PointMetadata> {
+        None
+    }
+
+    fn set_point_metadata(
+        &mut self,
+        _internal_id: PointOffsetType,
+        _metadata: PointMetadata,
+    ) -> OperationResult<()> {
+        Ok(())
+    }
+
     fn internal_id(&self, external_id: PointIdType) -> Option<PointOffsetType> {
         Some(match external_id {
             PointIdType::NumId(id) => {
@@ -151,6 +163,10 @@ impl IdTracker for FixtureIdTracker {
     fn deleted_bitvec(&self) -> &BitVec {
         &self.deleted
     }
+
+    fn ram_usage_bytes(&self) -> usize {
+        0
+    }
 }
 
 /// Creates in-memory payload storage and fills it with random points
@@ -3,7 +3,7 @@ use rand::Rng;
 
 use crate::common::Flusher;
 use crate::entry::entry_point::OperationResult;
-use crate::types::{PointIdType, PointOffsetType, SeqNumberType};
+use crate::types::{PointIdType, PointMetadata, PointOffsetType, SeqNumberType};
 
 /// Trait for point ids tracker.
 ///
@@ -19,6 +19,16 @@ pub trait IdTracker {
         version: SeqNumberType,
     ) -> OperationResult<()>;
 
+    /// Service metadata of the point, if any is stored
+    fn point_metadata(&self, internal_id: PointOffsetType) -> Option<&PointMetadata>;
+
+    /// Replace service metadata of the point, empty metadata is removed
+    fn set_point_metadata(
+        &mut self,
+        internal_id: PointOffsetType,
+        metadata: PointMetadata,
+    ) -> OperationResult<()>;
+
     /// Returns internal ID of the point, which is used inside this segment
     fn internal_id(&self, external_id: PointIdType) -> Option<PointOffsetType>;
 
@@ -59,13 +69,16 @@ pub trait IdTracker {
     /// Flush id mapping to disk
     fn mapping_flusher(&self) -> Flusher;
 
-    /// Flush points versions to disk
+    /// Flush points versions and metadata to disk
     fn versions_flusher(&self) -> Flusher;
 
     fn deleted_bitvec(&self) -> &BitVec;
 
     fn is_deleted(&self, internal_id: PointOffsetType) -> bool;
 
+    /// Estimated size of id mappings and versions in RAM, without the overhead of containers
+    fn ram_usage_bytes(&self) -> usize;
+
     // Number of deleted points
     fn deleted_count(&self) -> usize {
         self.internal_size() - self.points_count()
@@ -1,4 +1,5 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap};
+use std::mem::size_of;
 use std::sync::Arc;
 
 use bincode;
@@ -8,11 +9,13 @@ use rocksdb::DB;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::common::rocksdb_wrapper::{DatabaseColumnWrapper, DB_MAPPING_CF, DB_VERSIONS_CF};
+use crate::common::rocksdb_wrapper::{
+    DatabaseColumnWrapper, DB_MAPPING_CF, DB_POINT_METADATA_CF, DB_VERSIONS_CF,
+};
 use crate::common::Flusher;
 use crate::entry::entry_point::OperationResult;
 use crate::id_tracker::IdTracker;
-use crate::types::{ExtendedPointId, PointIdType, PointOffsetType, SeqNumberType};
+use crate::types::{ExtendedPointId, PointIdType, PointMetadata, PointOffsetType, SeqNumberType};
 
 /// Point Id type used for storing ids internally
 /// Should be serializable by `bincode`, therefore is not untagged.
@@ -56,10 +59,13 @@ pub struct SimpleIdTracker {
     deleted: BitVec,
     internal_to_external: Vec<PointIdType>,
     internal_to_version: Vec<SeqNumberType>,
+    /// Only points with non-empty metadata are stored
+    internal_to_metadata: HashMap<PointOffsetType, PointMetadata>,
     external_to_internal_num: BTreeMap<u64, PointOffsetType>,
     external_to_internal_uuid: BTreeMap<Uuid, PointOffsetType>,
     mapping_db_wrapper: DatabaseColumnWrapper,
     versions_db_wrapper: DatabaseColumnWrapper,
+    metadata_db_wrapper: DatabaseColumnWrapper,
 }
 
 impl SimpleIdTracker {
@@ -114,7 +120,7 @@ impl SimpleIdTracker {
         }
 
         let mut internal_to_version: Vec<SeqNumberType> = Default::default();
-        let versions_db_wrapper = DatabaseColumnWrapper::new(store, DB_VERSIONS_CF);
+        let versions_db_wrapper = DatabaseColumnWrapper::new(store.clone(), DB_VERSIONS_CF);
         for (key, val) in versions_db_wrapper.lock_db().iter()? {
             let external_id = Self::restore_key(&key);
             let version: SeqNumberType = bincode::deserialize(&val).unwrap();
@@ -135,6 +141,25 @@ impl SimpleIdTracker {
             }
         }
 
+        let mut internal_to_metadata: HashMap<PointOffsetType, PointMetadata> = HashMap::new();
+        let metadata_db_wrapper = DatabaseColumnWrapper::new(store, DB_POINT_METADATA_CF);
+        for (key, val) in metadata_db_wrapper.lock_db().iter()? {
+            let external_id = Self::restore_key(&key);
+            let metadata: PointMetadata = bincode::deserialize(&val).unwrap();
+            let internal_id = match external_id {
+                PointIdType::NumId(idx) => external_to_internal_num.get(&idx).copied(),
+                PointIdType::Uuid(uuid) => external_to_internal_uuid.get(&uuid).copied(),
+            };
+            if let Some(internal_id) = internal_id {
+                internal_to_metadata.insert(internal_id, metadata);
+            } else {
+                log::debug!(
+                    "Found metadata without internal id, external id: {}",
+                    external_id
+                );
+            }
+        }
+
         #[cfg(debug_assertions)]
         {
             for (idx, id) in external_to_internal_num.iter() {
@@ -151,10 +176,12 @@ impl SimpleIdTracker {
             deleted,
             internal_to_external,
             internal_to_version,
+            internal_to_metadata,
             external_to_internal_num,
             external_to_internal_uuid,
             mapping_db_wrapper,
             versions_db_wrapper,
+            metadata_db_wrapper,
         })
     }
 
@@ -191,6 +218,32 @@ impl IdTracker for SimpleIdTracker {
         Ok(())
     }
 
+    fn point_metadata(&self, internal_id: PointOffsetType) -> Option<&PointMetadata> {
+        self.internal_to_metadata.get(&internal_id)
+    }
+
+    fn set_point_metadata(
+        &mut self,
+        internal_id: PointOffsetType,
+        metadata: PointMetadata,
+    ) -> OperationResult<()> {
+        if let Some(external_id) = self.external_id(internal_id) {
+            if metadata.is_empty() {
+                if self.internal_to_metadata.remove(&internal_id).is_some() {
+                    self.metadata_db_wrapper
+                        .remove(Self::store_key(&external_id))?;
+                }
+            } else {
+                self.metadata_db_wrapper.put(
+                    Self::store_key(&external_id),
+                    bincode::serialize(&metadata).unwrap(),
+                )?;
+                self.internal_to_metadata.insert(internal_id, metadata);
+            }
+        }
+        Ok(())
+    }
+
     fn internal_id(&self, external_id: PointIdType) -> Option<PointOffsetType> {
         match external_id {
             PointIdType::NumId(idx) => self.external_to_internal_num.get(&idx).copied(),
@@ -247,6 +300,10 @@ impl IdTracker for SimpleIdTracker {
         if let Some(internal_id) = internal_id {
             self.deleted.set(internal_id as usize, true);
             self.internal_to_external[internal_id as usize] = PointIdType::NumId(u64::MAX);
+            if self.internal_to_metadata.remove(&internal_id).is_some() {
+                self.metadata_db_wrapper
+                    .remove(Self::store_key(&external_id))?;
+            }
         }
         self.mapping_db_wrapper
             .remove(Self::store_key(&external_id))?;
@@ -342,7 +399,13 @@ impl IdTracker for SimpleIdTracker {
     }
 
     fn versions_flusher(&self) -> Flusher {
-        self.versions_db_wrapper.flusher()
+        // Metadata is flushed before versions, as presence of version means the point is persisted
+        let metadata_flusher = self.metadata_db_wrapper.flusher();
+        let versions_flusher = self.versions_db_wrapper.flusher();
+        Box::new(move || {
+            metadata_flusher()?;
+            versions_flusher()
+        })
     }
 
     fn is_deleted(&self, key: PointOffsetType) -> bool {
@@ -356,6 +419,18 @@ impl IdTracker for SimpleIdTracker {
     fn deleted_bitvec(&self) -> &BitVec {
         &self.deleted
     }
+
+    fn ram_usage_bytes(&self) -> usize {
+        self.deleted.capacity() / u8::BITS as usize
+            + self.internal_to_external.capacity() * size_of::<PointIdType>()
+            + self.internal_to_version.capacity() * size_of::<SeqNumberType>()
+            + self.internal_to_metadata.len()
+                * (size_of::<PointOffsetType>() + size_of::<PointMetadata>())
+            + self.external_to_internal_num.len()
+                * (size_of::<u64>() + size_of::<PointOffsetType>())
+            + self.external_to_internal_uuid.len()
+                * (size_of::<Uuid>() + size_of::<PointOffsetType>())
+    }
 }
 
 #[cfg(test)]
@@ -412,6 +487,35 @@ mod tests {
         assert_eq!(last.len(), 7);
     }
 
+    #[test]
+    fn test_point_metadata() {
+        let dir = Builder::new().prefix("storage_dir").tempdir().unwrap();
+        let db = open_db(dir.path(), &[DB_VECTOR_CF]).unwrap();
+
+        let mut id_tracker = SimpleIdTracker::open(db.clone()).unwrap();
+        id_tracker.set_link(100.into(), 0).unwrap();
+        id_tracker.set_link(200.into(), 1).unwrap();
+        let metadata = PointMetadata {
+            sequence: Some(10),
+            content_hash: Some("a".to_string()),
+        };
+        id_tracker.set_point_metadata(0, metadata.clone()).unwrap();
+        id_tracker.set_point_metadata(1, metadata.clone()).unwrap();
+        id_tracker
+            .set_point_metadata(1, PointMetadata::default())
+            .unwrap();
+        drop(id_tracker);
+
+        let mut id_tracker = SimpleIdTracker::open(db).unwrap();
+        assert_eq!(id_tracker.point_metadata(0), Some(&metadata));
+        assert_eq!(id_tracker.point_metadata(1), None);
+
+        // Metadata is removed along with the point
+        IdTracker::drop(&mut id_tracker, 100.into()).unwrap();
+        id_tracker.set_link(100.into(), 2).unwrap();
+        assert_eq!(id_tracker.point_metadata(2), None);
+    }
+
     #[test]
     fn test_mixed_types_iterator() {
         let dir = Builder::new().prefix("storage_dir").tempdir().unwrap();
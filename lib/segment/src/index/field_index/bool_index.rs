@@ -0,0 +1,319 @@
+use std::mem::size_of;
+use std::sync::Arc;
+
+use bitvec::prelude::BitVec;
+use parking_lot::RwLock;
+use rocksdb::DB;
+use serde_json::Value;
+
+use crate::common::rocksdb_wrapper::DatabaseColumnWrapper;
+use crate::common::Flusher;
+use crate::entry::entry_point::{OperationError, OperationResult};
+use crate::index::field_index::{
+    CardinalityEstimation, PayloadBlockCondition, PayloadFieldIndex, PrimaryCondition, ValueIndexer,
+};
+use crate::telemetry::PayloadIndexTelemetry;
+use crate::types::{
+    FieldCondition, Match, MatchValue, PayloadKeyType, PointOffsetType, ValueVariants,
+};
+
+const TRUE_FLAG: u8 = 0b01;
+const FALSE_FLAG: u8 = 0b10;
+
+/// Bitmap-based index of boolean values.
+/// A point may have both values, if the payload contains an array of booleans.
+pub struct BoolIndex {
+    trues: BitVec,
+    falses: BitVec,
+    trues_count: usize,
+    falses_count: usize,
+    /// Amount of point which have at least one indexed payload value
+    indexed_points: usize,
+    db_wrapper: DatabaseColumnWrapper,
+}
+
+impl BoolIndex {
+    pub fn new(db: Arc<RwLock<DB>>, field_name: &str) -> BoolIndex {
+        let store_cf_name = Self::storage_cf_name(field_name);
+        let db_wrapper = DatabaseColumnWrapper::new(db, &store_cf_name);
+        BoolIndex {
+            trues: BitVec::new(),
+            falses: BitVec::new(),
+            trues_count: 0,
+            falses_count: 0,
+            indexed_points: 0,
+            db_wrapper,
+        }
+    }
+
+    fn storage_cf_name(field: &str) -> String {
+        format!("{field}_bool")
+    }
+
+    fn store_key(id: PointOffsetType) -> Vec<u8> {
+        bincode::serialize(&id).unwrap()
+    }
+
+    fn restore_key(data: &[u8]) -> PointOffsetType {
+        bincode::deserialize(data).unwrap()
+    }
+
+    pub fn recreate(&self) -> OperationResult<()> {
+        self.db_wrapper.recreate_column_family()
+    }
+
+    fn bitmap(&self, value: bool) -> &BitVec {
+        if value {
+            &self.trues
+        } else {
+            &self.falses
+        }
+    }
+
+    fn value_count(&self, value: bool) -> usize {
+        if value {
+            self.trues_count
+        } else {
+            self.falses_count
+        }
+    }
+
+    /// Whether the point has the given value
+    pub fn check_value(&self, idx: PointOffsetType, value: bool) -> bool {
+        self.bitmap(value)
+            .get(idx as usize)
+            .map_or(false, |bit| *bit)
+    }
+
+    fn get_iterator(&self, value: bool) -> Box<dyn Iterator<Item = PointOffsetType> + '_> {
+        Box::new(
+            self.bitmap(value)
+                .iter_ones()
+                .map(|idx| idx as PointOffsetType),
+        )
+    }
+
+    fn point_flags(&self, idx: PointOffsetType) -> u8 {
+        let mut flags = 0;
+        if self.check_value(idx, true) {
+            flags |= TRUE_FLAG;
+        }
+        if self.check_value(idx, false) {
+            flags |= FALSE_FLAG;
+        }
+        flags
+    }
+
+    fn set_flags(&mut self, idx: PointOffsetType, flags: u8) {
+        if flags == 0 {
+            return;
+        }
+        let idx = idx as usize;
+        if self.trues.len() <= idx {
+            self.trues.resize(idx + 1, false);
+            self.falses.resize(idx + 1, false);
+        }
+        if flags & TRUE_FLAG != 0 {
+            self.trues.set(idx, true);
+            self.trues_count += 1;
+        }
+        if flags & FALSE_FLAG != 0 {
+            self.falses.set(idx, true);
+            self.falses_count += 1;
+        }
+        self.indexed_points += 1;
+    }
+
+    fn load(&mut self) -> OperationResult<bool> {
+        if !self.db_wrapper.has_column_family()? {
+            return Ok(false);
+        }
+        let mut stored_flags = vec![];
+        for (key, value) in self.db_wrapper.lock_db().iter()? {
+            let idx = Self::restore_key(&key);
+            let flags = value.first().copied().ok_or_else(|| {
+                OperationError::service_error("Index db parsing error: empty bool value")
+            })?;
+            stored_flags.push((idx, flags));
+        }
+        for (idx, flags) in stored_flags {
+            self.set_flags(idx, flags);
+        }
+        Ok(true)
+    }
+
+    pub fn get_telemetry_data(&self) -> PayloadIndexTelemetry {
+        PayloadIndexTelemetry {
+            field_name: None,
+            points_count: self.indexed_points,
+            points_values_count: self.trues_count + self.falses_count,
+            histogram_bucket_size: None,
+        }
+    }
+
+    fn remove_point(&mut self, idx: PointOffsetType) -> OperationResult<()> {
+        let flags = self.point_flags(idx);
+        if flags == 0 {
+            return Ok(());
+        }
+        if flags & TRUE_FLAG != 0 {
+            self.trues.set(idx as usize, false);
+            self.trues_count -= 1;
+        }
+        if flags & FALSE_FLAG != 0 {
+            self.falses.set(idx as usize, false);
+            self.falses_count -= 1;
+        }
+        self.indexed_points -= 1;
+        self.db_wrapper.remove(Self::store_key(idx))
+    }
+}
+
+impl PayloadFieldIndex for BoolIndex {
+    fn indexed_points(&self) -> usize {
+        self.indexed_points
+    }
+
+    fn load(&mut self) -> OperationResult<bool> {
+        BoolIndex::load(self)
+    }
+
+    fn clear(self) -> OperationResult<()> {
+        self.db_wrapper.recreate_column_family()
+    }
+
+    fn flusher(&self) -> Flusher {
+        self.db_wrapper.flusher()
+    }
+
+    fn filter(
+        &self,
+        condition: &FieldCondition,
+    ) -> Option<Box<dyn Iterator<Item = PointOffsetType> + '_>> {
+        match &condition.r#match {
+            Some(Match::Value(MatchValue {
+                value: ValueVariants::Bool(value),
+            })) => Some(self.get_iterator(*value)),
+            _ => None,
+        }
+    }
+
+    fn estimate_cardinality(&self, condition: &FieldCondition) -> Option<CardinalityEstimation> {
+        match &condition.r#match {
+            Some(Match::Value(MatchValue {
+                value: ValueVariants::Bool(value),
+            })) => {
+                let mut estimation = CardinalityEstimation::exact(self.value_count(*value));
+                estimation
+                    .primary_clauses
+                    .push(PrimaryCondition::Condition(condition.clone()));
+                Some(estimation)
+            }
+            _ => None,
+        }
+    }
+
+    fn payload_blocks(
+        &self,
+        threshold: usize,
+        key: PayloadKeyType,
+    ) -> Box<dyn Iterator<Item = PayloadBlockCondition> + '_> {
+        let iter = [true, false]
+            .into_iter()
+            .filter(move |value| self.value_count(*value) >= threshold)
+            .map(move |value| PayloadBlockCondition {
+                condition: FieldCondition::new_match(key.clone(), value.into()),
+                cardinality: self.value_count(value),
+            });
+        Box::new(iter)
+    }
+
+    fn count_indexed_points(&self) -> usize {
+        self.indexed_points
+    }
+
+    fn ram_usage_bytes(&self) -> usize {
+        (self.trues.capacity() + self.falses.capacity()) / 8 + 2 * size_of::<BitVec>()
+    }
+}
+
+impl ValueIndexer<bool> for BoolIndex {
+    fn add_many(&mut self, id: PointOffsetType, values: Vec<bool>) -> OperationResult<()> {
+        let flags = values.into_iter().fold(0, |flags, value| {
+            flags | if value { TRUE_FLAG } else { FALSE_FLAG }
+        });
+        if flags == 0 {
+            return Ok(());
+        }
+        self.set_flags(id, flags);
+        self.db_wrapper.put(Self::store_key(id), [flags])
+    }
+
+    fn get_value(&self, value: &Value) -> Option<bool> {
+        value.as_bool()
+    }
+
+    fn remove_point(&mut self, id: PointOffsetType) -> OperationResult<()> {
+        self.remove_point(id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::Builder;
+
+    use super::*;
+    use crate::common::rocksdb_wrapper::open_db_with_existing_cf;
+    use crate::common::utils::MultiValue;
+
+    const FIELD_NAME: &str = "test";
+
+    fn match_condition(value: bool) -> FieldCondition {
+        FieldCondition::new_match(FIELD_NAME.to_string(), value.into())
+    }
+
+    #[test]
+    fn test_bool_index() {
+        let payloads = [
+            serde_json::json!(true),
+            serde_json::json!(false),
+            serde_json::json!([true, false]),
+            serde_json::json!("true"),
+            serde_json::json!([false]),
+        ];
+
+        let tmp_dir = Builder::new().prefix("test_dir").tempdir().unwrap();
+        {
+            let db = open_db_with_existing_cf(tmp_dir.path()).unwrap();
+            let mut index = BoolIndex::new(db, FIELD_NAME);
+            index.recreate().unwrap();
+            for (idx, payload) in payloads.iter().enumerate() {
+                index
+                    .add_point(idx as PointOffsetType, &MultiValue::Single(Some(payload)))
+                    .unwrap();
+            }
+            assert_eq!(index.count_indexed_points(), 4);
+
+            index.remove_point(2).unwrap();
+            assert_eq!(index.count_indexed_points(), 3);
+            index.flusher()().unwrap();
+        }
+
+        let db = open_db_with_existing_cf(tmp_dir.path()).unwrap();
+        let mut index = BoolIndex::new(db, FIELD_NAME);
+        assert!(index.load().unwrap());
+        assert_eq!(index.count_indexed_points(), 3);
+
+        let trues: Vec<_> = index.filter(&match_condition(true)).unwrap().collect();
+        assert_eq!(trues, vec![0]);
+        let falses: Vec<_> = index.filter(&match_condition(false)).unwrap().collect();
+        assert_eq!(falses, vec![1, 4]);
+
+        let estimation = index.estimate_cardinality(&match_condition(false)).unwrap();
+        assert_eq!(estimation.exp, 2);
+
+        let blocks: Vec<_> = index.payload_blocks(2, FIELD_NAME.to_string()).collect();
+        assert_eq!(blocks.len(), 1);
+        assert_eq!(blocks[0].condition, match_condition(false));
+    }
+}
@@ -1,8 +1,13 @@
+use std::collections::BTreeSet;
+
 use serde_json::Value;
 
 use crate::common::utils::MultiValue;
 use crate::common::Flusher;
-use crate::entry::entry_point::OperationResult;
+use crate::data_types::distinct::value_hash;
+use crate::entry::entry_point::{OperationError, OperationResult};
+use crate::index::field_index::bool_index::BoolIndex;
+use crate::index::field_index::full_text_index::inverted_index::Document;
 use crate::index::field_index::full_text_index::text_index::FullTextIndex;
 use crate::index::field_index::geo_index::GeoMapIndex;
 use crate::index::field_index::map_index::MapIndex;
@@ -10,7 +15,7 @@ use crate::index::field_index::numeric_index::NumericIndex;
 use crate::index::field_index::{CardinalityEstimation, PayloadBlockCondition};
 use crate::telemetry::PayloadIndexTelemetry;
 use crate::types::{
-    FieldCondition, FloatPayloadType, IntPayloadType, PayloadKeyType, PointOffsetType,
+    FieldCondition, FloatPayloadType, GeoPoint, IntPayloadType, PayloadKeyType, PointOffsetType,
 };
 
 pub trait PayloadFieldIndex {
@@ -46,6 +51,10 @@ pub trait PayloadFieldIndex {
 
     /// Returns an amount of unique indexed points
     fn count_indexed_points(&self) -> usize;
+
+    /// Estimated size of the index in RAM.
+    /// Only stored values and point ids are accounted, overhead of the containers is not included.
+    fn ram_usage_bytes(&self) -> usize;
 }
 
 pub trait ValueIndexer<T> {
@@ -56,15 +65,11 @@ pub trait ValueIndexer<T> {
     /// Extract index-able value from payload `Value`
     fn get_value(&self, value: &Value) -> Option<T>;
 
-    /// Add point with payload to index
-    fn add_point(
-        &mut self,
-        id: PointOffsetType,
-        payload: &MultiValue<&Value>,
-    ) -> OperationResult<()> {
+    /// Extract index-able values of the point from the payload.
+    /// Returns `None` if the payload has nothing to index and the point should stay untouched.
+    fn get_values(&self, payload: &MultiValue<&Value>) -> Option<Vec<T>> {
         match payload {
             MultiValue::Multiple(values) => {
-                self.remove_point(id)?;
                 let mut flatten_values: Vec<_> = vec![];
 
                 for value in values {
@@ -79,21 +84,31 @@ pub trait ValueIndexer<T> {
                         }
                     }
                 }
-                self.add_many(id, flatten_values)
+                Some(flatten_values)
             }
             MultiValue::Single(Some(Value::Array(values))) => {
-                self.remove_point(id)?;
-                self.add_many(id, values.iter().flat_map(|x| self.get_value(x)).collect())
-            }
-            MultiValue::Single(Some(value)) => {
-                if let Some(x) = self.get_value(value) {
-                    self.remove_point(id)?;
-                    self.add_many(id, vec![x])
-                } else {
-                    Ok(())
-                }
+                Some(values.iter().flat_map(|x| self.get_value(x)).collect())
             }
-            MultiValue::Single(None) => Ok(()),
+            MultiValue::Single(Some(value)) => self.get_value(value).map(|x| vec![x]),
+            MultiValue::Single(None) => None,
+        }
+    }
+
+    /// Replace values of the point with the given ones
+    fn set_values(&mut self, id: PointOffsetType, values: Vec<T>) -> OperationResult<()> {
+        self.remove_point(id)?;
+        self.add_many(id, values)
+    }
+
+    /// Add point with payload to index
+    fn add_point(
+        &mut self,
+        id: PointOffsetType,
+        payload: &MultiValue<&Value>,
+    ) -> OperationResult<()> {
+        match self.get_values(payload) {
+            Some(values) => self.set_values(id, values),
+            None => Ok(()),
         }
     }
 
@@ -101,6 +116,18 @@ pub trait ValueIndexer<T> {
     fn remove_point(&mut self, id: PointOffsetType) -> OperationResult<()>;
 }
 
+/// Values of a single point, extracted from the payload and prepared for insertion into the index.
+/// Preparation doesn't modify the index, so points may be prepared in parallel.
+pub enum PreparedValues {
+    Int(Vec<IntPayloadType>),
+    Keyword(Vec<String>),
+    Float(Vec<FloatPayloadType>),
+    Geo(Vec<GeoPoint>),
+    /// Tokenized document, `None` if the point has no text values
+    Text(Option<Document>),
+    Bool(Vec<bool>),
+}
+
 /// Common interface for all possible types of field indexes
 /// Enables polymorphism on field indexes
 /// TODO: Rename with major release
@@ -112,6 +139,7 @@ pub enum FieldIndex {
     FloatIndex(NumericIndex<FloatPayloadType>),
     GeoIndex(GeoMapIndex),
     FullTextIndex(FullTextIndex),
+    BoolIndex(BoolIndex),
 }
 
 impl FieldIndex {
@@ -123,6 +151,7 @@ impl FieldIndex {
             FieldIndex::FloatIndex(payload_field_index) => payload_field_index,
             FieldIndex::GeoIndex(payload_field_index) => payload_field_index,
             FieldIndex::FullTextIndex(payload_field_index) => payload_field_index,
+            FieldIndex::BoolIndex(payload_field_index) => payload_field_index,
         }
     }
 
@@ -135,6 +164,7 @@ impl FieldIndex {
             FieldIndex::FloatIndex(ref mut payload_field_index) => payload_field_index,
             FieldIndex::GeoIndex(ref mut payload_field_index) => payload_field_index,
             FieldIndex::FullTextIndex(ref mut payload_field_index) => payload_field_index,
+            FieldIndex::BoolIndex(ref mut payload_field_index) => payload_field_index,
         }
     }
 
@@ -146,6 +176,7 @@ impl FieldIndex {
             FieldIndex::FloatIndex(ref mut payload_field_index) => payload_field_index.load(),
             FieldIndex::GeoIndex(ref mut payload_field_index) => payload_field_index.load(),
             FieldIndex::FullTextIndex(ref mut payload_field_index) => payload_field_index.load(),
+            FieldIndex::BoolIndex(ref mut payload_field_index) => payload_field_index.load(),
         }
     }
 
@@ -157,6 +188,7 @@ impl FieldIndex {
             FieldIndex::FloatIndex(index) => index.clear(),
             FieldIndex::GeoIndex(index) => index.clear(),
             FieldIndex::FullTextIndex(index) => index.clear(),
+            FieldIndex::BoolIndex(index) => index.clear(),
         }
     }
 
@@ -168,6 +200,7 @@ impl FieldIndex {
             FieldIndex::FloatIndex(index) => index.recreate(),
             FieldIndex::GeoIndex(index) => index.recreate(),
             FieldIndex::FullTextIndex(index) => index.recreate(),
+            FieldIndex::BoolIndex(index) => index.recreate(),
         }
     }
 
@@ -207,6 +240,10 @@ impl FieldIndex {
         self.get_payload_field_index().count_indexed_points()
     }
 
+    pub fn ram_usage_bytes(&self) -> usize {
+        self.get_payload_field_index().ram_usage_bytes()
+    }
+
     pub fn add_point(
         &mut self,
         id: PointOffsetType,
@@ -231,6 +268,73 @@ impl FieldIndex {
             FieldIndex::FullTextIndex(ref mut payload_field_index) => {
                 payload_field_index.add_point(id, payload)
             }
+            FieldIndex::BoolIndex(ref mut payload_field_index) => {
+                payload_field_index.add_point(id, payload)
+            }
+        }
+    }
+
+    /// Extract values of the point from the payload, without modifying the index.
+    /// Returns `None` if the payload has nothing to index.
+    pub fn prepare_point(&self, payload: &MultiValue<&Value>) -> Option<PreparedValues> {
+        match self {
+            FieldIndex::IntIndex(index) => {
+                ValueIndexer::get_values(index, payload).map(PreparedValues::Int)
+            }
+            FieldIndex::IntMapIndex(index) => {
+                ValueIndexer::get_values(index, payload).map(PreparedValues::Int)
+            }
+            FieldIndex::KeywordIndex(index) => {
+                ValueIndexer::get_values(index, payload).map(PreparedValues::Keyword)
+            }
+            FieldIndex::FloatIndex(index) => {
+                ValueIndexer::get_values(index, payload).map(PreparedValues::Float)
+            }
+            FieldIndex::GeoIndex(index) => {
+                ValueIndexer::get_values(index, payload).map(PreparedValues::Geo)
+            }
+            FieldIndex::FullTextIndex(index) => index
+                .get_values(payload)
+                .map(|values| PreparedValues::Text(index.tokenize_document(&values))),
+            FieldIndex::BoolIndex(index) => index.get_values(payload).map(PreparedValues::Bool),
+        }
+    }
+
+    /// Add point with values, prepared by [`FieldIndex::prepare_point`] of the same index
+    pub fn add_prepared(
+        &mut self,
+        id: PointOffsetType,
+        values: PreparedValues,
+    ) -> OperationResult<()> {
+        match (self, values) {
+            (FieldIndex::IntIndex(index), PreparedValues::Int(values)) => {
+                index.set_values(id, values)
+            }
+            (FieldIndex::IntMapIndex(index), PreparedValues::Int(values)) => {
+                index.set_values(id, values)
+            }
+            (FieldIndex::KeywordIndex(index), PreparedValues::Keyword(values)) => {
+                index.set_values(id, values)
+            }
+            (FieldIndex::FloatIndex(index), PreparedValues::Float(values)) => {
+                index.set_values(id, values)
+            }
+            (FieldIndex::GeoIndex(index), PreparedValues::Geo(values)) => {
+                index.set_values(id, values)
+            }
+            (FieldIndex::FullTextIndex(index), PreparedValues::Text(document)) => {
+                index.remove_point(id)?;
+                match document {
+                    Some(document) => index.add_document(id, document),
+                    None => Ok(()),
+                }
+            }
+            (FieldIndex::BoolIndex(index), PreparedValues::Bool(values)) => {
+                index.set_values(id, values)
+            }
+            _ => Err(OperationError::service_error(
+                "Prepared values don't match the type of the field index",
+            )),
         }
     }
 
@@ -242,6 +346,131 @@ impl FieldIndex {
             FieldIndex::FloatIndex(index) => index.remove_point(point_id),
             FieldIndex::GeoIndex(index) => index.remove_point(point_id),
             FieldIndex::FullTextIndex(index) => index.remove_point(point_id),
+            FieldIndex::BoolIndex(index) => index.remove_point(point_id),
+        }
+    }
+
+    /// Geo index, if this is one
+    pub fn as_geo_index(&self) -> Option<&GeoMapIndex> {
+        match self {
+            FieldIndex::GeoIndex(index) => Some(index),
+            FieldIndex::IntIndex(_)
+            | FieldIndex::IntMapIndex(_)
+            | FieldIndex::KeywordIndex(_)
+            | FieldIndex::FloatIndex(_)
+            | FieldIndex::FullTextIndex(_)
+            | FieldIndex::BoolIndex(_) => None,
+        }
+    }
+
+    /// Numeric values of the point in the integer or float index, converted to `f64`.
+    /// Returns `None` for other types of indexes.
+    pub fn numeric_values(&self, point_id: PointOffsetType) -> Option<Vec<f64>> {
+        let values = match self {
+            FieldIndex::IntIndex(index) => index
+                .get_values(point_id)
+                .map(|values| values.iter().map(|&value| value as f64).collect()),
+            FieldIndex::IntMapIndex(index) => index
+                .get_values(point_id)
+                .map(|values| values.iter().map(|&value| value as f64).collect()),
+            FieldIndex::FloatIndex(index) => index.get_values(point_id).cloned(),
+            FieldIndex::KeywordIndex(_)
+            | FieldIndex::GeoIndex(_)
+            | FieldIndex::FullTextIndex(_)
+            | FieldIndex::BoolIndex(_) => return None,
+        };
+        Some(values.unwrap_or_default())
+    }
+
+    pub fn is_numeric(&self) -> bool {
+        matches!(
+            self,
+            FieldIndex::IntIndex(_) | FieldIndex::IntMapIndex(_) | FieldIndex::FloatIndex(_)
+        )
+    }
+
+    /// Iterate over values of the integer or float range index, converted to `f64`,
+    /// together with points, which have them, ordered by value.
+    /// Iteration starts from the `from` value, inclusive, if it is given.
+    /// Returns `None` for other types of indexes.
+    #[allow(clippy::type_complexity)]
+    pub fn iter_ordered_values(
+        &self,
+        from: Option<f64>,
+        descending: bool,
+    ) -> Option<Box<dyn Iterator<Item = (f64, PointOffsetType)> + '_>> {
+        match self {
+            FieldIndex::IntIndex(index) => {
+                // Integers at or after the `from` value in the order
+                let from = from.map(|from| {
+                    if descending {
+                        from.floor() as IntPayloadType
+                    } else {
+                        from.ceil() as IntPayloadType
+                    }
+                });
+                Some(Box::new(
+                    index
+                        .iter_ordered(from, descending)
+                        .map(|(value, point_id)| (value as f64, point_id)),
+                ))
+            }
+            FieldIndex::FloatIndex(index) => Some(index.iter_ordered(from, descending)),
+            FieldIndex::IntMapIndex(_)
+            | FieldIndex::KeywordIndex(_)
+            | FieldIndex::GeoIndex(_)
+            | FieldIndex::FullTextIndex(_)
+            | FieldIndex::BoolIndex(_) => None,
+        }
+    }
+
+    /// Iterate over terms of the keyword or full-text index together with the points,
+    /// which have them. Returns `None` for other types of indexes.
+    #[allow(clippy::type_complexity)]
+    pub fn iter_terms(
+        &self,
+    ) -> Option<Box<dyn Iterator<Item = (&str, &BTreeSet<PointOffsetType>)> + '_>> {
+        match self {
+            FieldIndex::KeywordIndex(index) => Some(Box::new(
+                index
+                    .iter_values_with_points()
+                    .map(|(value, points)| (value.as_str(), points)),
+            )),
+            FieldIndex::FullTextIndex(index) => Some(Box::new(
+                index
+                    .iter_postings()
+                    .map(|(token, points)| (token.as_str(), points)),
+            )),
+            FieldIndex::IntIndex(_)
+            | FieldIndex::IntMapIndex(_)
+            | FieldIndex::FloatIndex(_)
+            | FieldIndex::GeoIndex(_)
+            | FieldIndex::BoolIndex(_) => None,
+        }
+    }
+
+    /// Iterate over hashes of values of the keyword or integer map index together with the points,
+    /// which have them. Returns `None` for other types of indexes.
+    #[allow(clippy::type_complexity)]
+    pub fn iter_value_hashes(
+        &self,
+    ) -> Option<Box<dyn Iterator<Item = (u64, &BTreeSet<PointOffsetType>)> + '_>> {
+        match self {
+            FieldIndex::KeywordIndex(index) => Some(Box::new(
+                index
+                    .iter_values_with_points()
+                    .map(|(value, points)| (value_hash(value.as_str()), points)),
+            )),
+            FieldIndex::IntMapIndex(index) => Some(Box::new(
+                index
+                    .iter_values_with_points()
+                    .map(|(value, points)| (value_hash(value), points)),
+            )),
+            FieldIndex::IntIndex(_)
+            | FieldIndex::FloatIndex(_)
+            | FieldIndex::GeoIndex(_)
+            | FieldIndex::FullTextIndex(_)
+            | FieldIndex::BoolIndex(_) => None,
         }
     }
 
@@ -253,6 +482,7 @@ impl FieldIndex {
             FieldIndex::FloatIndex(index) => index.get_telemetry_data(),
             FieldIndex::GeoIndex(index) => index.get_telemetry_data(),
             FieldIndex::FullTextIndex(index) => index.get_telemetry_data(),
+            FieldIndex::BoolIndex(index) => index.get_telemetry_data(),
         }
     }
 }
@@ -0,0 +1,99 @@
+use std::collections::BTreeSet;
+use std::ops::Range;
+
+use super::tokenizers::Tokenizer;
+use crate::data_types::text_index::{TextIndexParams, TokenizerType};
+
+/// Split the text into words the same way the tokenizer does.
+/// Returns words along with their ranges in characters.
+fn words(text: &str, tokenizer: TokenizerType) -> Vec<(Range<usize>, &str)> {
+    let is_separator = |c: char| match tokenizer {
+        TokenizerType::Whitespace => c.is_whitespace(),
+        TokenizerType::Word | TokenizerType::Prefix => !c.is_alphanumeric(),
+    };
+    let mut words = vec![];
+    // Positions of the current word start: in characters and in bytes
+    let mut word_start: Option<(usize, usize)> = None;
+    let mut chars_count = 0;
+    for (char_pos, (byte_pos, c)) in text.char_indices().enumerate() {
+        chars_count = char_pos + 1;
+        if is_separator(c) {
+            if let Some((start, byte_start)) = word_start.take() {
+                words.push((start..char_pos, &text[byte_start..byte_pos]));
+            }
+        } else if word_start.is_none() {
+            word_start = Some((char_pos, byte_pos));
+        }
+    }
+    if let Some((start, byte_start)) = word_start {
+        words.push((start..chars_count, &text[byte_start..]));
+    }
+    words
+}
+
+/// Ranges of the words in `text`, which match any token of the full-text `query`.
+/// Ranges are measured in characters, not bytes.
+///
+/// Words are compared the same way the full-text index compares them: with lowercasing,
+/// synonyms of the query tokens and, for the prefix tokenizer, by the prefix of the word.
+pub fn highlight_text(text: &str, query: &str, config: &TextIndexParams) -> Vec<Range<usize>> {
+    let mut query_tokens = BTreeSet::new();
+    Tokenizer::tokenize_query(query, config, |token| {
+        query_tokens.extend(config.token_alternatives(token));
+    });
+    if query_tokens.is_empty() {
+        return vec![];
+    }
+
+    words(text, config.tokenizer)
+        .into_iter()
+        .filter(|(_, word)| {
+            let word = if config.lowercase.unwrap_or(true) {
+                word.to_lowercase()
+            } else {
+                word.to_string()
+            };
+            match config.tokenizer {
+                TokenizerType::Prefix => query_tokens
+                    .iter()
+                    .any(|token| word.starts_with(token.as_str())),
+                TokenizerType::Whitespace | TokenizerType::Word => query_tokens.contains(&word),
+            }
+        })
+        .map(|(range, _)| range)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::*;
+
+    #[test]
+    fn test_highlight_text() {
+        let config = TextIndexParams {
+            synonyms: Some(BTreeMap::from([(
+                "car".to_owned(),
+                BTreeSet::from(["automobile".to_owned()]),
+            )])),
+            ..Default::default()
+        };
+        let text = "Моя car, an Automobile; not a cart";
+        assert_eq!(highlight_text(text, "car", &config), vec![4..7, 12..22]);
+        assert_eq!(highlight_text(text, "моя", &config), vec![0..3]);
+        assert!(highlight_text(text, "", &config).is_empty());
+
+        let config = TextIndexParams {
+            tokenizer: TokenizerType::Prefix,
+            ..Default::default()
+        };
+        assert_eq!(highlight_text(text, "car", &config), vec![4..7, 30..34]);
+
+        let config = TextIndexParams {
+            tokenizer: TokenizerType::Whitespace,
+            ..Default::default()
+        };
+        assert_eq!(highlight_text(text, "car,", &config), vec![4..8]);
+    }
+}
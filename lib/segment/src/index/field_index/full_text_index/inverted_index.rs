@@ -1,8 +1,11 @@
 use std::collections::{BTreeMap, BTreeSet};
+use std::mem::size_of;
 
 use serde::{Deserialize, Serialize};
 
-use crate::index::field_index::full_text_index::postings_iterator::intersect_btree_iterator;
+use crate::index::field_index::full_text_index::postings_iterator::{
+    intersect_alternatives_iterator, intersect_btree_iterator,
+};
 use crate::index::field_index::{CardinalityEstimation, PayloadBlockCondition, PrimaryCondition};
 use crate::types::{FieldCondition, Match, MatchText, PayloadKeyType, PointOffsetType};
 
@@ -19,16 +22,19 @@ impl Document {
     }
 }
 
+/// Tokens of the query, each one with its alternatives (the token itself and its synonyms)
 pub struct ParsedQuery {
-    pub tokens: BTreeSet<String>,
+    pub tokens: Vec<BTreeSet<String>>,
 }
 
 impl ParsedQuery {
     pub fn check_match(&self, document: &Document) -> bool {
-        // Check that all tokens are in document
-        self.tokens
-            .iter()
-            .all(|query_token| document.tokens.contains(query_token))
+        // Check that all tokens are in document, either directly or as a synonym
+        self.tokens.iter().all(|alternatives| {
+            alternatives
+                .iter()
+                .any(|query_token| document.tokens.contains(query_token))
+        })
     }
 }
 
@@ -47,6 +53,36 @@ impl InvertedIndex {
         }
     }
 
+    /// Estimated size of postings and documents in RAM, without the overhead of containers
+    pub fn ram_usage_bytes(&self) -> usize {
+        let token_size = |token: &String| size_of::<String>() + token.capacity();
+        let postings_size: usize = self
+            .postings
+            .iter()
+            .map(|(token, posting)| {
+                token_size(token) + posting.len() * size_of::<PointOffsetType>()
+            })
+            .sum();
+        let documents_size: usize = self
+            .point_to_docs
+            .iter()
+            .map(|document| {
+                size_of::<Option<Document>>()
+                    + document
+                        .iter()
+                        .flat_map(|document| document.tokens.iter())
+                        .map(token_size)
+                        .sum::<usize>()
+            })
+            .sum();
+        postings_size + documents_size
+    }
+
+    /// Iterate over tokens together with the points, which documents contain them
+    pub fn iter_postings(&self) -> impl Iterator<Item = (&String, &PostingList)> + '_ {
+        self.postings.iter()
+    }
+
     pub fn index_document(&mut self, idx: PointOffsetType, document: Document) {
         for token in &document.tokens {
             let posting = self
@@ -88,11 +124,20 @@ impl InvertedIndex {
         Some(removed_doc)
     }
 
+    /// Postings of all known alternatives of a query token, `None` if none of them is known
+    fn alternative_postings(&self, alternatives: &BTreeSet<String>) -> Option<Vec<&PostingList>> {
+        let postings: Vec<_> = alternatives
+            .iter()
+            .filter_map(|token| self.postings.get(token))
+            .collect();
+        (!postings.is_empty()).then_some(postings)
+    }
+
     pub fn filter(&self, query: &ParsedQuery) -> Box<dyn Iterator<Item = PointOffsetType> + '_> {
         let postings_opt: Option<Vec<_>> = query
             .tokens
             .iter()
-            .map(|token| self.postings.get(token))
+            .map(|alternatives| self.alternative_postings(alternatives))
             .collect();
         if postings_opt.is_none() {
             // There are unseen tokens -> no matches
@@ -103,7 +148,16 @@ impl InvertedIndex {
             // Empty request -> no matches
             return Box::new(vec![].into_iter());
         }
-        intersect_btree_iterator(postings)
+        if postings.iter().all(|alternatives| alternatives.len() == 1) {
+            // No synonyms matched -> plain intersection
+            return intersect_btree_iterator(
+                postings
+                    .into_iter()
+                    .map(|alternatives| alternatives[0])
+                    .collect(),
+            );
+        }
+        intersect_alternatives_iterator(postings)
     }
 
     pub fn estimate_cardinality(
@@ -114,7 +168,7 @@ impl InvertedIndex {
         let postings_opt: Option<Vec<_>> = query
             .tokens
             .iter()
-            .map(|token| self.postings.get(token))
+            .map(|alternatives| self.alternative_postings(alternatives))
             .collect();
         if postings_opt.is_none() {
             // There are unseen tokens -> no matches
@@ -135,20 +189,36 @@ impl InvertedIndex {
                 max: 0,
             };
         }
+        // Union of alternatives is at least as large as the largest one and at most their sum
+        let largest_alternative = |alternatives: &Vec<&PostingList>| {
+            alternatives
+                .iter()
+                .map(|posting| posting.len())
+                .max()
+                .unwrap()
+        };
+        let union_size = |alternatives: &Vec<&PostingList>| {
+            alternatives
+                .iter()
+                .map(|posting| posting.len())
+                .sum::<usize>()
+                .min(self.points_count)
+        };
+
         // Smallest posting is the largest possible cardinality
-        let smallest_posting = postings.iter().map(|posting| posting.len()).min().unwrap();
+        let smallest_posting = postings.iter().map(union_size).min().unwrap();
 
         return if postings.len() == 1 {
             CardinalityEstimation {
                 primary_clauses: vec![PrimaryCondition::Condition(condition.clone())],
-                min: smallest_posting,
+                min: largest_alternative(&postings[0]),
                 exp: smallest_posting,
                 max: smallest_posting,
             }
         } else {
             let expected_frac: f64 = postings
                 .iter()
-                .map(|posting| posting.len() as f64 / self.points_count as f64)
+                .map(|alternatives| union_size(alternatives) as f64 / self.points_count as f64)
                 .product();
             let exp = (expected_frac * self.points_count as f64) as usize;
             CardinalityEstimation {
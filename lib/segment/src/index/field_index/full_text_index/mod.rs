@@ -1,4 +1,5 @@
-mod inverted_index;
+pub mod highlight;
+pub mod inverted_index;
 mod postings_iterator;
 pub mod text_index;
 mod tokenizers;
@@ -19,6 +19,32 @@ pub fn intersect_btree_iterator<'a, T: Ord + Copy>(
     Box::new(and_iter)
 }
 
+/// Intersection of unions: an element is returned if every group has at least one posting with it
+pub fn intersect_alternatives_iterator<'a, T: Ord + Copy + 'a>(
+    mut groups: Vec<Vec<&'a BTreeSet<T>>>,
+) -> Box<dyn Iterator<Item = T> + 'a> {
+    let smallest_group_idx = groups
+        .iter()
+        .enumerate()
+        .min_by_key(|(_idx, group)| group.iter().map(|posting| posting.len()).sum::<usize>())
+        .map(|(idx, _group)| idx)
+        .unwrap();
+    let smallest_group: BTreeSet<T> = groups
+        .remove(smallest_group_idx)
+        .into_iter()
+        .flatten()
+        .copied()
+        .collect();
+
+    let and_iter = smallest_group.into_iter().filter(move |doc_id| {
+        groups
+            .iter()
+            .all(|group| group.iter().any(|posting| posting.contains(doc_id)))
+    });
+
+    Box::new(and_iter)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -36,4 +62,18 @@ mod tests {
 
         assert_eq!(res, vec![2, 5]);
     }
+
+    #[test]
+    fn test_alternatives_iterator() {
+        let v1: BTreeSet<_> = vec![1, 2, 3].into_iter().collect();
+        let v2: BTreeSet<_> = vec![7, 8].into_iter().collect();
+        let v3: BTreeSet<_> = vec![2, 4, 8].into_iter().collect();
+
+        let groups = vec![vec![&v1, &v2], vec![&v3]];
+        let merged = intersect_alternatives_iterator(groups);
+
+        let res = merged.collect::<Vec<_>>();
+
+        assert_eq!(res, vec![2, 8]);
+    }
 }
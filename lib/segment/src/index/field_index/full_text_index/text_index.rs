@@ -1,4 +1,4 @@
-use std::collections::HashSet;
+use std::collections::{BTreeSet, HashSet};
 use std::sync::Arc;
 
 use parking_lot::RwLock;
@@ -76,39 +76,42 @@ impl FullTextIndex {
         }
     }
 
-    pub fn recreate(&self) -> OperationResult<()> {
-        self.db_wrapper.recreate_column_family()
+    /// Iterate over tokens together with the points, which documents contain them
+    pub fn iter_postings(
+        &self,
+    ) -> impl Iterator<Item = (&String, &BTreeSet<PointOffsetType>)> + '_ {
+        self.inverted_index.iter_postings()
     }
 
-    pub fn parse_query(&self, text: &str) -> ParsedQuery {
-        let mut tokens = vec![];
-        Tokenizer::tokenize_query(text, &self.config, |token| {
-            tokens.push(token.to_owned());
-        });
-        ParsedQuery {
-            tokens: tokens.into_iter().collect(),
-        }
+    pub fn recreate(&self) -> OperationResult<()> {
+        self.db_wrapper.recreate_column_family()
     }
-}
 
-impl ValueIndexer<String> for FullTextIndex {
-    fn add_many(&mut self, idx: PointOffsetType, values: Vec<String>) -> OperationResult<()> {
+    /// Tokenize text values of the point into a document.
+    /// Returns `None` if there are no values.
+    pub fn tokenize_document(&self, values: &[String]) -> Option<Document> {
         if values.is_empty() {
-            return Ok(());
+            return None;
         }
 
         let mut tokens: HashSet<String> = HashSet::new();
 
         for value in values {
-            Tokenizer::tokenize_doc(&value, &self.config, |token| {
+            Tokenizer::tokenize_doc(value, &self.config, |token| {
                 tokens.insert(token.to_owned());
             });
         }
 
-        let document = Document {
+        Some(Document {
             tokens: tokens.into_iter().collect(),
-        };
+        })
+    }
 
+    pub fn add_document(
+        &mut self,
+        idx: PointOffsetType,
+        document: Document,
+    ) -> OperationResult<()> {
         self.inverted_index.index_document(idx, document);
 
         let db_idx = Self::store_key(&idx);
@@ -123,6 +126,25 @@ impl ValueIndexer<String> for FullTextIndex {
         Ok(())
     }
 
+    pub fn parse_query(&self, text: &str) -> ParsedQuery {
+        let mut tokens = vec![];
+        Tokenizer::tokenize_query(text, &self.config, |token| {
+            tokens.push(self.config.token_alternatives(token));
+        });
+        tokens.sort();
+        tokens.dedup();
+        ParsedQuery { tokens }
+    }
+}
+
+impl ValueIndexer<String> for FullTextIndex {
+    fn add_many(&mut self, idx: PointOffsetType, values: Vec<String>) -> OperationResult<()> {
+        match self.tokenize_document(&values) {
+            Some(document) => self.add_document(idx, document),
+            None => Ok(()),
+        }
+    }
+
     fn get_value(&self, value: &Value) -> Option<String> {
         if let Value::String(keyword) = value {
             return Some(keyword.to_owned());
@@ -203,6 +225,10 @@ impl PayloadFieldIndex for FullTextIndex {
     fn count_indexed_points(&self) -> usize {
         self.inverted_index.points_count
     }
+
+    fn ram_usage_bytes(&self) -> usize {
+        self.inverted_index.ram_usage_bytes()
+    }
 }
 
 #[cfg(test)]
@@ -248,6 +274,8 @@ mod tests {
             min_token_len: None,
             max_token_len: None,
             lowercase: None,
+            stopwords: None,
+            synonyms: None,
         };
 
         {
@@ -318,4 +346,66 @@ mod tests {
             assert_eq!(search_res, vec![0, 1, 3, 4]);
         }
     }
+
+    #[test]
+    fn test_stopwords_and_synonyms() {
+        let payloads: Vec<_> = vec![
+            serde_json::json!("The car is parked"),
+            serde_json::json!("An automobile in the garage"),
+            serde_json::json!("A bicycle in the garage"),
+        ];
+
+        let tmp_dir = Builder::new().prefix("test_dir").tempdir().unwrap();
+        let config = TextIndexParams {
+            r#type: TextIndexType::Text,
+            tokenizer: TokenizerType::Word,
+            min_token_len: None,
+            max_token_len: None,
+            lowercase: None,
+            stopwords: Some(["the", "a", "an"].map(String::from).into()),
+            synonyms: Some([("car".to_owned(), ["automobile"].map(String::from).into())].into()),
+        };
+
+        let db = open_db_with_existing_cf(&tmp_dir.path().join("test_db")).unwrap();
+        let mut index = FullTextIndex::new(db, config, "text");
+        index.recreate().unwrap();
+
+        for (idx, payload) in payloads.iter().enumerate() {
+            index
+                .add_point(idx as PointOffsetType, &MultiValue::one(payload))
+                .unwrap();
+        }
+
+        // Stop words are neither indexed nor required by the query
+        assert!(index
+            .filter(&filter_request("the"))
+            .unwrap()
+            .next()
+            .is_none());
+        let search_res: Vec<_> = index
+            .filter(&filter_request("the garage"))
+            .unwrap()
+            .collect();
+        assert_eq!(search_res, vec![1, 2]);
+
+        // Query token matches its synonyms
+        let search_res: Vec<_> = index.filter(&filter_request("car")).unwrap().collect();
+        assert_eq!(search_res, vec![0, 1]);
+        let search_res: Vec<_> = index
+            .filter(&filter_request("car garage"))
+            .unwrap()
+            .collect();
+        assert_eq!(search_res, vec![1]);
+
+        // Expansion is one-directional
+        let search_res: Vec<_> = index
+            .filter(&filter_request("automobile"))
+            .unwrap()
+            .collect();
+        assert_eq!(search_res, vec![1]);
+
+        let parsed_query = index.parse_query("car");
+        let document = index.get_doc(1).unwrap();
+        assert!(parsed_query.check_match(document));
+    }
 }
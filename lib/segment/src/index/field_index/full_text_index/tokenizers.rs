@@ -83,8 +83,11 @@ impl Tokenizer {
                 return;
             }
             if config.lowercase.unwrap_or(true) {
-                callback(&token.to_lowercase());
-            } else {
+                let token = token.to_lowercase();
+                if !config.is_stopword(&token) {
+                    callback(&token);
+                }
+            } else if !config.is_stopword(token) {
                 callback(token);
             }
         }
@@ -184,6 +187,8 @@ mod tests {
                 min_token_len: Some(1),
                 max_token_len: Some(4),
                 lowercase: Some(true),
+                stopwords: None,
+                synonyms: None,
             },
             |token| tokens.push(token.to_owned()),
         );
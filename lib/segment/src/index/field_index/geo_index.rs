@@ -1,5 +1,6 @@
 use std::cmp::{max, min};
 use std::collections::{BTreeMap, HashSet};
+use std::mem::size_of;
 use std::str::FromStr;
 use std::sync::Arc;
 
@@ -258,6 +259,14 @@ impl GeoMapIndex {
         self.point_to_values.get(idx as usize)
     }
 
+    /// Distance in meters from the `origin` to the closest geo point of the point
+    pub fn min_distance(&self, idx: PointOffsetType, origin: &GeoPoint) -> Option<f64> {
+        self.get_values(idx)?
+            .iter()
+            .map(|value| origin.distance(value))
+            .min_by(|a, b| a.total_cmp(b))
+    }
+
     pub fn check_radius(&self, idx: PointOffsetType, radius: &GeoRadius) -> bool {
         self.get_values(idx)
             .map(|values| values.iter().any(|x| radius.check_point(x.lon, x.lat)))
@@ -586,6 +595,27 @@ impl PayloadFieldIndex for GeoMapIndex {
     fn count_indexed_points(&self) -> usize {
         self.points_count
     }
+
+    fn ram_usage_bytes(&self) -> usize {
+        let hash_size = |hash: &GeoHash| size_of::<GeoHash>() + hash.capacity();
+        let counters_size: usize = self
+            .points_per_hash
+            .keys()
+            .chain(self.values_per_hash.keys())
+            .map(|hash| hash_size(hash) + size_of::<usize>())
+            .sum();
+        let points_map_size: usize = self
+            .points_map
+            .iter()
+            .map(|(hash, ids)| hash_size(hash) + ids.len() * size_of::<PointOffsetType>())
+            .sum();
+        let point_to_values_size: usize = self
+            .point_to_values
+            .iter()
+            .map(|values| size_of::<Vec<GeoPoint>>() + values.capacity() * size_of::<GeoPoint>())
+            .sum();
+        counters_size + points_map_size + point_to_values_size
+    }
 }
 
 #[cfg(test)]
@@ -2,6 +2,7 @@ use std::collections::{BTreeSet, HashMap};
 use std::fmt::Display;
 use std::hash::Hash;
 use std::iter;
+use std::mem::size_of;
 use std::str::FromStr;
 use std::sync::Arc;
 
@@ -49,6 +50,29 @@ impl<N: Hash + Eq + Clone + Display + FromStr> MapIndex<N> {
         format!("{field}_map")
     }
 
+    /// `value_heap_size` - size of the value data, allocated outside of the value itself
+    fn ram_usage_bytes_with(&self, value_heap_size: impl Fn(&N) -> usize) -> usize {
+        let value_size = |value: &N| size_of::<N>() + value_heap_size(value);
+        let map_size: usize = self
+            .map
+            .iter()
+            .map(|(value, ids)| value_size(value) + ids.len() * size_of::<PointOffsetType>())
+            .sum();
+        let point_to_values_size: usize = self
+            .point_to_values
+            .iter()
+            .map(|values| size_of::<Vec<N>>() + values.iter().map(value_size).sum::<usize>())
+            .sum();
+        map_size + point_to_values_size
+    }
+
+    /// Iterate over indexed values together with the points, which have them
+    pub fn iter_values_with_points(
+        &self,
+    ) -> impl Iterator<Item = (&N, &BTreeSet<PointOffsetType>)> + '_ {
+        self.map.iter()
+    }
+
     pub fn recreate(&self) -> OperationResult<()> {
         self.db_wrapper.recreate_column_family()
     }
@@ -255,6 +279,10 @@ impl PayloadFieldIndex for MapIndex<String> {
     fn count_indexed_points(&self) -> usize {
         self.indexed_points
     }
+
+    fn ram_usage_bytes(&self) -> usize {
+        self.ram_usage_bytes_with(|value| value.capacity())
+    }
 }
 
 impl PayloadFieldIndex for MapIndex<IntPayloadType> {
@@ -332,6 +360,10 @@ impl PayloadFieldIndex for MapIndex<IntPayloadType> {
     fn count_indexed_points(&self) -> usize {
         self.indexed_points
     }
+
+    fn ram_usage_bytes(&self) -> usize {
+        self.ram_usage_bytes_with(|_| 0)
+    }
 }
 
 impl ValueIndexer<String> for MapIndex<String> {
@@ -2,6 +2,7 @@ use std::collections::HashSet;
 
 use crate::types::{FieldCondition, IsEmptyCondition, IsNullCondition, PointOffsetType};
 
+pub mod bool_index;
 mod field_index_base;
 pub mod full_text_index;
 pub mod geo_hash;
@@ -1,5 +1,6 @@
 use std::cmp::{max, min};
 use std::collections::BTreeMap;
+use std::mem::size_of;
 use std::ops::Bound;
 use std::ops::Bound::{Excluded, Included, Unbounded};
 use std::sync::Arc;
@@ -167,6 +168,35 @@ impl<T: Encodable + Numericable> NumericIndex<T> {
         self.point_to_values.get(idx as usize)
     }
 
+    /// Iterate over values of the index together with points, which have them,
+    /// ordered by value and then by point offset.
+    /// Iteration starts from the `from` value, inclusive, if it is given.
+    pub fn iter_ordered(
+        &self,
+        from: Option<T>,
+        descending: bool,
+    ) -> Box<dyn Iterator<Item = (T, PointOffsetType)> + '_> {
+        let decode = |key: &Vec<u8>| {
+            let (idx, value) = T::decode_key(key);
+            (value, idx)
+        };
+        match (from, descending) {
+            (None, false) => Box::new(self.map.keys().map(decode)),
+            (None, true) => Box::new(self.map.keys().rev().map(decode)),
+            (Some(from), false) => Box::new(
+                self.map
+                    .range(from.encode_key(PointOffsetType::MIN)..)
+                    .map(move |(key, _)| decode(key)),
+            ),
+            (Some(from), true) => Box::new(
+                self.map
+                    .range(..=from.encode_key(PointOffsetType::MAX))
+                    .rev()
+                    .map(move |(key, _)| decode(key)),
+            ),
+        }
+    }
+
     #[allow(clippy::manual_clamp)] // false positive
     fn range_cardinality(&self, range: &Range) -> CardinalityEstimation {
         let lbound = if let Some(lte) = range.lte {
@@ -444,6 +474,20 @@ impl<T: Encodable + Numericable> PayloadFieldIndex for NumericIndex<T> {
     fn count_indexed_points(&self) -> usize {
         self.points_count
     }
+
+    fn ram_usage_bytes(&self) -> usize {
+        let map_size: usize = self
+            .map
+            .keys()
+            .map(|key| size_of::<Vec<u8>>() + key.capacity() + size_of::<u32>())
+            .sum();
+        let point_to_values_size: usize = self
+            .point_to_values
+            .iter()
+            .map(|values| size_of::<Vec<T>>() + values.capacity() * size_of::<T>())
+            .sum();
+        map_size + point_to_values_size
+    }
 }
 
 impl ValueIndexer<IntPayloadType> for NumericIndex<IntPayloadType> {
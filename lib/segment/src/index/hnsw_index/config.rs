@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 
 use serde::{Deserialize, Serialize};
@@ -5,10 +6,11 @@ use serde::{Deserialize, Serialize};
 use crate::common::cpu::get_num_cpus;
 use crate::common::file_operations::{atomic_save_json, read_json};
 use crate::entry::entry_point::OperationResult;
+use crate::types::PayloadKeyType;
 
 pub const HNSW_INDEX_CONFIG_FILE: &str = "hnsw_config.json";
 
-#[derive(Debug, Deserialize, Serialize, Copy, Clone, PartialEq, Eq)]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
 pub struct HnswGraphConfig {
     pub m: usize,
     /// Requested M
@@ -25,6 +27,20 @@ pub struct HnswGraphConfig {
     pub payload_m: Option<usize>,
     #[serde(default)]
     pub payload_m0: Option<usize>,
+    /// Fields to build additional payload-aware links for. If not set - all indexed fields
+    #[serde(default)]
+    pub payload_fields: Option<Vec<PayloadKeyType>>,
+    /// Sizes of payload-aware subgraphs per field, filled on index building
+    #[serde(default)]
+    pub payload_subgraphs: BTreeMap<PayloadKeyType, PayloadSubgraphStats>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Default, Copy, Clone, PartialEq, Eq)]
+pub struct PayloadSubgraphStats {
+    /// Number of payload blocks, for which subgraphs are built
+    pub blocks: usize,
+    /// Total number of points linked in subgraphs, including repeats
+    pub points: usize,
 }
 
 impl HnswGraphConfig {
@@ -34,6 +50,7 @@ impl HnswGraphConfig {
         indexing_threshold: usize,
         max_indexing_threads: usize,
         payload_m: Option<usize>,
+        payload_fields: Option<Vec<PayloadKeyType>>,
     ) -> Self {
         HnswGraphConfig {
             m,
@@ -44,6 +61,8 @@ impl HnswGraphConfig {
             max_indexing_threads,
             payload_m,
             payload_m0: payload_m.map(|v| v * 2),
+            payload_fields,
+            payload_subgraphs: BTreeMap::new(),
         }
     }
 
@@ -59,6 +78,13 @@ impl HnswGraphConfig {
         Ok(atomic_save_json(path, self)?)
     }
 
+    /// Whether additional payload-aware links should be built for the field
+    pub fn is_payload_field_linked(&self, field: &str) -> bool {
+        self.payload_fields
+            .as_ref()
+            .map_or(true, |fields| fields.iter().any(|f| f == field))
+    }
+
     pub fn max_rayon_threads(&self) -> usize {
         let max_threads = self.max_indexing_threads;
 
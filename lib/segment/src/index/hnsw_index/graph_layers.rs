@@ -83,6 +83,16 @@ pub trait GraphLayersBase {
                 }
             });
 
+            // Deleted points are skipped by the scorer, but their links are still followed
+            for &point_id in &points_ids {
+                if points_scorer.is_transit(point_id) {
+                    searcher.process_transit(ScoredPointOffset {
+                        idx: point_id,
+                        score: points_scorer.score_point(point_id),
+                    });
+                }
+            }
+
             let scores = points_scorer.score_points(&mut points_ids, limit);
             scores
                 .iter()
@@ -199,10 +209,10 @@ impl<TGraphLinks: GraphLinks> GraphLayers<TGraphLinks> {
         ef: usize,
         mut points_scorer: FilteredScorer,
     ) -> Vec<ScoredPointOffset> {
-        let entry_point = match self
-            .entry_points
-            .get_entry_point(|point_id| points_scorer.check_point(point_id))
-        {
+        let entry_point = match self.entry_points.get_entry_point(|point_id| {
+            // Deleted point is not returned, but the search can start from it
+            points_scorer.check_point(point_id) || points_scorer.is_transit(point_id)
+        }) {
             None => return vec![],
             Some(ep) => ep,
         };
@@ -216,7 +226,11 @@ impl<TGraphLinks: GraphLinks> GraphLayers<TGraphLinks> {
 
         let nearest =
             self.search_on_level(zero_level_entry, 0, max(top, ef), &mut points_scorer, &[]);
-        nearest.into_iter().take(top).collect_vec()
+        nearest
+            .into_iter()
+            .filter(|scored_point| !points_scorer.is_transit(scored_point.idx))
+            .take(top)
+            .collect_vec()
     }
 
     pub fn get_path(path: &Path) -> PathBuf {
@@ -486,4 +500,39 @@ mod tests {
         )
         .unwrap();
     }
+
+    #[test]
+    fn test_search_through_deleted() {
+        let num_vectors = 1000;
+        let dim = 8;
+        let top = 10;
+        let ef = 16;
+
+        let mut rng = StdRng::seed_from_u64(42);
+
+        let (mut vector_holder, graph_layers) = create_graph_layer_fixture::<CosineMetric, _>(
+            num_vectors,
+            M,
+            dim,
+            false,
+            &mut rng,
+            None,
+        );
+
+        // Most of the graph is deleted, only every 20th point is left
+        for point_id in 0..num_vectors {
+            vector_holder.deleted.set(point_id, point_id % 20 != 0);
+        }
+
+        let query = random_vector(&mut rng, dim);
+        let raw_scorer = vector_holder.get_raw_scorer(query);
+        let scorer =
+            FilteredScorer::with_deleted(raw_scorer.as_ref(), None, &vector_holder.deleted);
+        let result = graph_layers.search(top, ef, scorer);
+
+        assert_eq!(result.len(), top);
+        assert!(result
+            .iter()
+            .all(|scored_point| !vector_holder.deleted[scored_point.idx as usize]));
+    }
 }
@@ -339,6 +339,9 @@ pub trait GraphLinks: Default {
 
     fn num_points(&self) -> usize;
 
+    /// Estimated size of links, kept in RAM. Memory mapped links are not included
+    fn ram_usage_bytes(&self) -> usize;
+
     fn links(&self, point_id: PointOffsetType, level: usize) -> &[PointOffsetType] {
         if level == 0 {
             let links_range = self.get_links_range(point_id as usize);
@@ -463,6 +466,13 @@ impl GraphLinks for GraphLinksRam {
     fn num_points(&self) -> usize {
         self.reindex.len()
     }
+
+    fn ram_usage_bytes(&self) -> usize {
+        self.links.capacity() * size_of::<PointOffsetType>()
+            + self.offsets.capacity() * size_of::<u64>()
+            + self.level_offsets.capacity() * size_of::<u64>()
+            + self.reindex.capacity() * size_of::<PointOffsetType>()
+    }
 }
 
 #[derive(Default)]
@@ -557,6 +567,10 @@ impl GraphLinks for GraphLinksMmap {
     fn num_points(&self) -> usize {
         self.header.point_count as usize
     }
+
+    fn ram_usage_bytes(&self) -> usize {
+        self.level_offsets.capacity() * size_of::<u64>()
+    }
 }
 
 #[cfg(test)]
@@ -1,10 +1,12 @@
 use std::cmp::max;
+use std::collections::BTreeMap;
 use std::fs::create_dir_all;
 use std::path::{Path, PathBuf};
-use std::sync::atomic::AtomicBool;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 
 use atomic_refcell::AtomicRefCell;
+use bitvec::prelude::BitVec;
 use log::debug;
 use parking_lot::Mutex;
 use rand::thread_rng;
@@ -19,7 +21,7 @@ use crate::data_types::vectors::VectorElementType;
 use crate::entry::entry_point::{check_process_stopped, OperationError, OperationResult};
 use crate::id_tracker::IdTrackerSS;
 use crate::index::hnsw_index::build_condition_checker::BuildConditionChecker;
-use crate::index::hnsw_index::config::HnswGraphConfig;
+use crate::index::hnsw_index::config::{HnswGraphConfig, PayloadSubgraphStats};
 use crate::index::hnsw_index::graph_layers::GraphLayers;
 use crate::index::hnsw_index::graph_layers_builder::GraphLayersBuilder;
 use crate::index::hnsw_index::point_scorer::FilteredScorer;
@@ -27,11 +29,12 @@ use crate::index::sample_estimation::sample_check_cardinality;
 use crate::index::struct_payload_index::StructPayloadIndex;
 use crate::index::visited_pool::VisitedList;
 use crate::index::{PayloadIndex, VectorIndex};
-use crate::telemetry::VectorIndexSearchesTelemetry;
+use crate::spaces::tools::peek_top_largest_iterable;
+use crate::telemetry::{PayloadSubgraphTelemetry, VectorIndexSearchesTelemetry};
 use crate::types::Condition::Field;
 use crate::types::{
-    default_quantization_ignore_value, default_quantization_rescore_value, FieldCondition, Filter,
-    HnswConfig, QuantizationSearchParams, SearchParams, VECTOR_ELEMENT_SIZE,
+    default_quantization_ignore_value, FieldCondition, Filter, HnswConfig, PlannerHint,
+    QuantizationSearchParams, SearchParams, VECTOR_ELEMENT_SIZE,
 };
 use crate::vector_storage::quantized::quantized_vectors_base::QuantizedVectors;
 use crate::vector_storage::{new_raw_scorer, ScoredPointOffset, VectorStorage, VectorStorageEnum};
@@ -80,6 +83,7 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
                 indexing_threshold,
                 hnsw_config.max_indexing_threads,
                 hnsw_config.payload_m,
+                hnsw_config.payload_fields,
             )
         };
 
@@ -164,7 +168,7 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
                 .try_for_each(|block_point_id| {
                     check_process_stopped(stopped)?;
 
-                    let vector = vector_storage.get_vector(block_point_id).to_vec();
+                    let vector = vector_storage.get_vector(block_point_id)?.to_vec();
                     let raw_scorer =
                         if let Some(quantized_storage) = vector_storage.quantized_storage() {
                             quantized_storage.raw_scorer(&vector, id_tracker.deleted_bitvec())
@@ -199,11 +203,9 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
             .and_then(|params| params.hnsw_ef)
             .unwrap_or(self.config.ef);
 
-        // ef should always be bigger that required top
-        let ef = max(req_ef, top);
-
         let vector_storage = self.vector_storage.borrow();
         let id_tracker = self.id_tracker.borrow();
+        let quantization_params = params.and_then(|p| p.quantization).unwrap_or_default();
         let ignore_quantization = params
             .and_then(|p| p.quantization)
             .map(|q| q.ignore)
@@ -237,24 +239,31 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
 
         let filter_context = filter.map(|f| payload_index.filter_context(f));
 
-        let points_scorer = FilteredScorer::new(raw_scorer.as_ref(), filter_context.as_deref());
+        let points_scorer = FilteredScorer::with_deleted(
+            raw_scorer.as_ref(),
+            filter_context.as_deref(),
+            id_tracker.deleted_bitvec(),
+        );
 
         if let Some(graph) = &self.graph {
-            let mut search_result = graph.search(top, ef, points_scorer);
-            let if_rescore = params
-                .and_then(|p| p.quantization)
-                .map(|q| q.rescore)
-                .unwrap_or(default_quantization_rescore_value());
-            if quantized && if_rescore {
-                let raw_scorer = new_raw_scorer(
-                    vector.to_owned(),
+            let if_rescore = quantized && quantization_params.is_rescore_required();
+            // Oversampled candidates of the quantized search are narrowed down after re-scoring
+            let candidates_top = if if_rescore {
+                quantization_params.oversampled_top(top)
+            } else {
+                top
+            };
+            // ef should always be bigger that required top
+            let ef = max(req_ef, candidates_top);
+            let search_result = graph.search(candidates_top, ef, points_scorer);
+            if if_rescore {
+                Self::rescore_with_original(
+                    vector,
                     &vector_storage,
                     id_tracker.deleted_bitvec(),
-                );
-                search_result.iter_mut().for_each(|scored_point| {
-                    scored_point.score = raw_scorer.score_point(scored_point.idx);
-                });
-                search_result
+                    search_result,
+                    top,
+                )
             } else {
                 search_result
             }
@@ -263,16 +272,40 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
         }
     }
 
+    /// Score candidates, found with quantized vectors, with original vectors and select the best of them
+    fn rescore_with_original(
+        vector: &[VectorElementType],
+        vector_storage: &VectorStorageEnum,
+        deleted: &BitVec,
+        candidates: Vec<ScoredPointOffset>,
+        top: usize,
+    ) -> Vec<ScoredPointOffset> {
+        let raw_scorer = new_raw_scorer(vector.to_owned(), vector_storage, deleted);
+        let rescored = candidates
+            .into_iter()
+            .map(|scored_point| ScoredPointOffset {
+                idx: scored_point.idx,
+                score: raw_scorer.score_point(scored_point.idx),
+            });
+        peek_top_largest_iterable(rescored, top)
+    }
+
     fn search_vectors_with_graph(
         &self,
         vectors: &[&[VectorElementType]],
         filter: Option<&Filter>,
         top: usize,
         params: Option<&SearchParams>,
+        is_stopped: &AtomicBool,
     ) -> Vec<Vec<ScoredPointOffset>> {
         vectors
             .iter()
-            .map(|vector| self.search_with_graph(vector, filter, top, params))
+            .map(|vector| {
+                if is_stopped.load(Ordering::Relaxed) {
+                    return vec![];
+                }
+                self.search_with_graph(vector, filter, top, params)
+            })
             .collect()
     }
 
@@ -282,11 +315,14 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
         filter: &Filter,
         top: usize,
         params: Option<&SearchParams>,
+        is_stopped: &AtomicBool,
     ) -> Vec<Vec<ScoredPointOffset>> {
         let payload_index = self.payload_index.borrow();
         let vector_storage = self.vector_storage.borrow();
         let id_tracker = self.id_tracker.borrow();
-        let mut filtered_iter = payload_index.query_points(filter);
+        let mut filtered_iter = payload_index
+            .query_points(filter)
+            .take_while(|_| !is_stopped.load(Ordering::Relaxed));
         let ignore_quantization = params
             .and_then(|p| p.quantization)
             .map(|q| q.ignore)
@@ -300,7 +336,7 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
                         &vector_storage,
                         id_tracker.deleted_bitvec(),
                     )
-                    .peek_top_iter(filtered_iter.as_mut(), top)
+                    .peek_top_iter(&mut filtered_iter, top)
                 })
                 .collect()
         } else {
@@ -308,16 +344,33 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
                 .iter()
                 .map(|vector| {
                     if let Some(quantized_storage) = vector_storage.quantized_storage() {
-                        quantized_storage
+                        let quantization_params =
+                            params.and_then(|p| p.quantization).unwrap_or_default();
+                        if !quantization_params.is_rescore_required() {
+                            return quantized_storage
+                                .raw_scorer(vector, id_tracker.deleted_bitvec())
+                                .peek_top_iter(&mut filtered_iter, top);
+                        }
+                        let candidates = quantized_storage
                             .raw_scorer(vector, id_tracker.deleted_bitvec())
-                            .peek_top_iter(filtered_iter.as_mut(), top)
+                            .peek_top_iter(
+                                &mut filtered_iter,
+                                quantization_params.oversampled_top(top),
+                            );
+                        Self::rescore_with_original(
+                            vector,
+                            &vector_storage,
+                            id_tracker.deleted_bitvec(),
+                            candidates,
+                            top,
+                        )
                     } else {
                         new_raw_scorer(
                             vector.to_vec(),
                             &vector_storage,
                             id_tracker.deleted_bitvec(),
                         )
-                        .peek_top_iter(filtered_iter.as_mut(), top)
+                        .peek_top_iter(&mut filtered_iter, top)
                     }
                 })
                 .collect()
@@ -332,11 +385,14 @@ impl<TGraphLinks: GraphLinks> VectorIndex for HNSWIndex<TGraphLinks> {
         filter: Option<&Filter>,
         top: usize,
         params: Option<&SearchParams>,
+        is_stopped: &AtomicBool,
     ) -> Vec<Vec<ScoredPointOffset>> {
         let exact = params.map(|params| params.exact).unwrap_or(false);
+        let plain_search =
+            params.and_then(|params| params.planner_hint) == Some(PlannerHint::PlainSearch);
         match filter {
             None => {
-                if exact {
+                if exact || plain_search {
                     let _timer =
                         ScopeDurationMeasurer::new(&self.searches_telemetry.exact_unfiltered);
                     let vector_storage = self.vector_storage.borrow();
@@ -344,6 +400,9 @@ impl<TGraphLinks: GraphLinks> VectorIndex for HNSWIndex<TGraphLinks> {
                     vectors
                         .iter()
                         .map(|vector| {
+                            if is_stopped.load(Ordering::Relaxed) {
+                                return vec![];
+                            }
                             new_raw_scorer(
                                 vector.to_vec(),
                                 &vector_storage,
@@ -354,7 +413,7 @@ impl<TGraphLinks: GraphLinks> VectorIndex for HNSWIndex<TGraphLinks> {
                         .collect()
                 } else {
                     let _timer = ScopeDurationMeasurer::new(&self.searches_telemetry.unfiltered);
-                    self.search_vectors_with_graph(vectors, None, top, params)
+                    self.search_vectors_with_graph(vectors, None, top, params, is_stopped)
                 }
             }
             Some(query_filter) => {
@@ -369,6 +428,7 @@ impl<TGraphLinks: GraphLinks> VectorIndex for HNSWIndex<TGraphLinks> {
                         params.quantization = Some(QuantizationSearchParams {
                             ignore: true,
                             rescore: false,
+                            rescore_factor: None,
                         }); // disable quantization for exact search
                         params
                     });
@@ -379,6 +439,20 @@ impl<TGraphLinks: GraphLinks> VectorIndex for HNSWIndex<TGraphLinks> {
                         query_filter,
                         top,
                         exact_params.as_ref(),
+                        is_stopped,
+                    );
+                }
+
+                // plain search is forced by the planner hint
+                if plain_search {
+                    let _timer =
+                        ScopeDurationMeasurer::new(&self.searches_telemetry.small_cardinality);
+                    return self.search_vectors_plain(
+                        vectors,
+                        query_filter,
+                        top,
+                        params,
+                        is_stopped,
                     );
                 }
 
@@ -391,14 +465,21 @@ impl<TGraphLinks: GraphLinks> VectorIndex for HNSWIndex<TGraphLinks> {
                     // if cardinality is small - use plain index
                     let _timer =
                         ScopeDurationMeasurer::new(&self.searches_telemetry.small_cardinality);
-                    return self.search_vectors_plain(vectors, query_filter, top, params);
+                    return self.search_vectors_plain(
+                        vectors,
+                        query_filter,
+                        top,
+                        params,
+                        is_stopped,
+                    );
                 }
 
                 if query_cardinality.min > self.config.indexing_threshold {
                     // if cardinality is high enough - use HNSW index
                     let _timer =
                         ScopeDurationMeasurer::new(&self.searches_telemetry.large_cardinality);
-                    return self.search_vectors_with_graph(vectors, filter, top, params);
+                    return self
+                        .search_vectors_with_graph(vectors, filter, top, params, is_stopped);
                 }
 
                 let filter_context = payload_index.filter_context(query_filter);
@@ -414,12 +495,12 @@ impl<TGraphLinks: GraphLinks> VectorIndex for HNSWIndex<TGraphLinks> {
                     // if cardinality is high enough - use HNSW index
                     let _timer =
                         ScopeDurationMeasurer::new(&self.searches_telemetry.large_cardinality);
-                    self.search_vectors_with_graph(vectors, filter, top, params)
+                    self.search_vectors_with_graph(vectors, filter, top, params, is_stopped)
                 } else {
                     // if cardinality is small - use plain index
                     let _timer =
                         ScopeDurationMeasurer::new(&self.searches_telemetry.small_cardinality);
-                    self.search_vectors_plain(vectors, query_filter, top, params)
+                    self.search_vectors_plain(vectors, query_filter, top, params, is_stopped)
                 }
             }
         }
@@ -460,7 +541,7 @@ impl<TGraphLinks: GraphLinks> VectorIndex for HNSWIndex<TGraphLinks> {
             pool.install(|| {
                 ids.into_par_iter().try_for_each(|vector_id| {
                     check_process_stopped(stopped)?;
-                    let vector = vector_storage.get_vector(vector_id).to_vec();
+                    let vector = vector_storage.get_vector(vector_id)?.to_vec();
                     let raw_scorer =
                         if let Some(quantized_storage) = vector_storage.quantized_storage() {
                             quantized_storage.raw_scorer(&vector, id_tracker.deleted_bitvec())
@@ -489,10 +570,16 @@ impl<TGraphLinks: GraphLinks> VectorIndex for HNSWIndex<TGraphLinks> {
         let payload_index = self.payload_index.borrow();
 
         let payload_m = self.config.payload_m.unwrap_or(self.config.m);
+        let mut payload_subgraphs = BTreeMap::new();
 
         if payload_m > 0 {
             for (field, _) in payload_index.indexed_fields() {
+                if !self.config.is_payload_field_linked(&field) {
+                    debug!("skip additional index for field {}", &field);
+                    continue;
+                }
                 debug!("building additional index for field {}", &field);
+                let mut subgraph_stats = PayloadSubgraphStats::default();
 
                 // It is expected, that graph will become disconnected less than
                 // $1/m$ points left.
@@ -529,12 +616,17 @@ impl<TGraphLinks: GraphLinks> VectorIndex for HNSWIndex<TGraphLinks> {
                         &mut block_filter_list,
                     )?;
                     graph_layers_builder.merge_from_other(additional_graph);
+                    subgraph_stats.blocks += 1;
+                    subgraph_stats.points += payload_block.cardinality;
                 }
+                payload_subgraphs.insert(field, subgraph_stats);
             }
         } else {
             debug!("skip building additional HNSW links");
         }
 
+        self.config.payload_subgraphs = payload_subgraphs;
+
         let graph_links_path = GraphLayers::<TGraphLinks>::get_links_path(&self.path);
         self.graph = Some(graph_layers_builder.into_graph_layers(Some(&graph_links_path))?);
 
@@ -554,6 +646,16 @@ impl<TGraphLinks: GraphLinks> VectorIndex for HNSWIndex<TGraphLinks> {
             filtered_large_cardinality: tm.large_cardinality.lock().get_statistics(),
             filtered_exact: tm.exact_filtered.lock().get_statistics(),
             unfiltered_exact: tm.exact_unfiltered.lock().get_statistics(),
+            payload_subgraphs: self
+                .config
+                .payload_subgraphs
+                .iter()
+                .map(|(field, stats)| PayloadSubgraphTelemetry {
+                    field_name: Some(field.clone()),
+                    blocks: stats.blocks,
+                    points: stats.points,
+                })
+                .collect(),
         }
     }
 
@@ -567,4 +669,11 @@ impl<TGraphLinks: GraphLinks> VectorIndex for HNSWIndex<TGraphLinks> {
             vec![]
         }
     }
+
+    fn ram_usage_bytes(&self) -> usize {
+        self.graph
+            .as_ref()
+            .map(|graph| graph.links.ram_usage_bytes())
+            .unwrap_or(0)
+    }
 }
@@ -1,3 +1,5 @@
+use bitvec::vec::BitVec;
+
 use crate::payload_storage::FilterContext;
 use crate::types::{PointOffsetType, ScoreType};
 use crate::vector_storage::{RawScorer, ScoredPointOffset};
@@ -5,6 +7,8 @@ use crate::vector_storage::{RawScorer, ScoredPointOffset};
 pub struct FilteredScorer<'a> {
     pub raw_scorer: &'a dyn RawScorer,
     pub filter_context: Option<&'a dyn FilterContext>,
+    /// Deleted points, which are not scored as results, but are passed through during graph search
+    deleted: Option<&'a BitVec>,
     points_buffer: Vec<ScoredPointOffset>,
 }
 
@@ -16,10 +20,33 @@ impl<'a> FilteredScorer<'a> {
         FilteredScorer {
             raw_scorer,
             filter_context,
+            deleted: None,
             points_buffer: Vec::new(),
         }
     }
 
+    /// Scorer, which allows graph search to pass through deleted points.
+    /// Otherwise links of deleted points are lost and the graph may become disconnected.
+    pub fn with_deleted(
+        raw_scorer: &'a dyn RawScorer,
+        filter_context: Option<&'a dyn FilterContext>,
+        deleted: &'a BitVec,
+    ) -> Self {
+        FilteredScorer {
+            deleted: Some(deleted),
+            ..Self::new(raw_scorer, filter_context)
+        }
+    }
+
+    /// Check if the point is deleted, but can be passed through during graph search
+    pub fn is_transit(&self, point_id: PointOffsetType) -> bool {
+        self.deleted.map_or(false, |deleted| {
+            deleted
+                .get(point_id as usize)
+                .map_or(false, |is_deleted| *is_deleted)
+        })
+    }
+
     pub fn check_point(&self, point_id: PointOffsetType) -> bool {
         match self.filter_context {
             None => self.raw_scorer.check_point(point_id),
@@ -43,4 +43,12 @@ impl SearchContext {
             self.candidates.push(score_point);
         }
     }
+
+    /// Updates search context with the point, which can't be returned as a result (e.g. deleted),
+    /// but its links can lead to the nearest points.
+    pub fn process_transit(&mut self, score_point: ScoredPointOffset) {
+        if !self.nearest.is_full() || score_point.score > self.lower_bound() {
+            self.candidates.push(score_point);
+        }
+    }
 }
@@ -7,6 +7,7 @@ pub mod plain_payload_index;
 pub mod query_estimator;
 mod query_optimization;
 mod sample_estimation;
+pub mod sparse_vector_index;
 mod struct_filter_context;
 pub mod struct_payload_index;
 mod vector_index_base;
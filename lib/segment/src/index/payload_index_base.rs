@@ -9,7 +9,7 @@ use crate::index::field_index::{CardinalityEstimation, PayloadBlockCondition};
 use crate::payload_storage::FilterContext;
 use crate::types::{
     Filter, Payload, PayloadFieldSchema, PayloadKeyType, PayloadKeyTypeRef, PayloadSchemaType,
-    PointOffsetType,
+    PointOffsetType, RecordPayload,
 };
 
 pub trait PayloadIndex {
@@ -61,6 +61,11 @@ pub trait PayloadIndex {
     /// Get payload for point
     fn payload(&self, point_id: PointOffsetType) -> OperationResult<Payload>;
 
+    /// Get payload for point in the stored form, if the storage keeps it serialized
+    fn raw_payload(&self, point_id: PointOffsetType) -> OperationResult<RecordPayload> {
+        self.payload(point_id).map(RecordPayload::from)
+    }
+
     /// Delete payload by key
     fn delete(
         &mut self,
@@ -86,4 +91,7 @@ pub trait PayloadIndex {
     fn take_database_snapshot(&self, path: &Path) -> OperationResult<()>;
 
     fn files(&self) -> Vec<PathBuf>;
+
+    /// Estimated size of field indexes in RAM
+    fn ram_usage_bytes(&self) -> usize;
 }
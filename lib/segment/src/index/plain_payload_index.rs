@@ -1,7 +1,7 @@
 use std::collections::HashMap;
 use std::fs::create_dir_all;
 use std::path::{Path, PathBuf};
-use std::sync::atomic::AtomicBool;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 
 use atomic_refcell::AtomicRefCell;
@@ -191,6 +191,10 @@ impl PayloadIndex for PlainPayloadIndex {
     fn files(&self) -> Vec<PathBuf> {
         vec![self.config_path()]
     }
+
+    fn ram_usage_bytes(&self) -> usize {
+        0
+    }
 }
 
 pub struct PlainIndex {
@@ -224,6 +228,7 @@ impl VectorIndex for PlainIndex {
         filter: Option<&Filter>,
         top: usize,
         _params: Option<&SearchParams>,
+        is_stopped: &AtomicBool,
     ) -> Vec<Vec<ScoredPointOffset>> {
         match filter {
             Some(filter) => {
@@ -231,7 +236,10 @@ impl VectorIndex for PlainIndex {
                 let payload_index = self.payload_index.borrow();
                 let vector_storage = self.vector_storage.borrow();
                 let id_tracker = self.id_tracker.borrow();
-                let filtered_ids_vec: Vec<_> = payload_index.query_points(filter).collect();
+                let filtered_ids_vec: Vec<_> = payload_index
+                    .query_points(filter)
+                    .take_while(|_| !is_stopped.load(Ordering::Relaxed))
+                    .collect();
                 vectors
                     .iter()
                     .map(|vector| {
@@ -240,7 +248,13 @@ impl VectorIndex for PlainIndex {
                             &vector_storage,
                             id_tracker.deleted_bitvec(),
                         )
-                        .peek_top_iter(&mut filtered_ids_vec.iter().copied(), top)
+                        .peek_top_iter(
+                            &mut filtered_ids_vec
+                                .iter()
+                                .copied()
+                                .take_while(|_| !is_stopped.load(Ordering::Relaxed)),
+                            top,
+                        )
                     })
                     .collect()
             }
@@ -251,6 +265,9 @@ impl VectorIndex for PlainIndex {
                 vectors
                     .iter()
                     .map(|vector| {
+                        if is_stopped.load(Ordering::Relaxed) {
+                            return vec![];
+                        }
                         new_raw_scorer(
                             vector.to_vec(),
                             &vector_storage,
@@ -277,12 +294,17 @@ impl VectorIndex for PlainIndex {
             filtered_large_cardinality: OperationDurationStatistics::default(),
             filtered_exact: OperationDurationStatistics::default(),
             unfiltered_exact: OperationDurationStatistics::default(),
+            payload_subgraphs: vec![],
         }
     }
 
     fn files(&self) -> Vec<PathBuf> {
         vec![]
     }
+
+    fn ram_usage_bytes(&self) -> usize {
+        0
+    }
 }
 
 pub struct PlainFilterContext<'a> {
@@ -13,12 +13,19 @@ use crate::types::{
     MatchAny, MatchText, MatchValue, PointOffsetType, Range, ValueVariants,
 };
 
+/// Relative cost of a check by in-memory structures: field index or set of ids
+pub const INDEXED_CHECK_COST: f64 = 1.0;
+/// Relative cost of a check, which reads and deserializes the payload of the point
+pub const PAYLOAD_CHECK_COST: f64 = 50.0;
+
+/// Converts the condition into a checker function.
+/// Returns the checker with the relative cost of a single check.
 pub fn condition_converter<'a>(
     condition: &'a Condition,
     field_indexes: &'a IndexesMap,
     payload_provider: PayloadProvider,
     id_tracker: &IdTrackerSS,
-) -> ConditionCheckerFn<'a> {
+) -> (ConditionCheckerFn<'a>, f64) {
     match condition {
         Condition::Field(field_condition) => field_indexes
             .get(&field_condition.key)
@@ -28,26 +35,34 @@ pub fn condition_converter<'a>(
                     .filter_map(|index| field_condition_index(index, field_condition))
                     .next()
             })
+            .map(|checker| (checker, INDEXED_CHECK_COST))
             .unwrap_or_else(|| {
-                Box::new(move |point_id| {
-                    payload_provider.with_payload(point_id, |payload| {
+                let checker: ConditionCheckerFn = Box::new(move |point_id| {
+                    payload_provider.with_field_payload(point_id, &field_condition.key, |payload| {
                         check_field_condition(field_condition, &payload)
                     })
-                })
+                });
+                (checker, PAYLOAD_CHECK_COST)
             }),
         // ToDo: It might be possible to make this condition faster by using index to check
         //       if there is any value. But if value if not found,
         //       it does not mean that there are no values in payload
-        Condition::IsEmpty(is_empty) => Box::new(move |point_id| {
-            payload_provider.with_payload(point_id, |payload| {
-                check_is_empty_condition(is_empty, &payload)
-            })
-        }),
-        Condition::IsNull(is_null) => Box::new(move |point_id| {
-            payload_provider.with_payload(point_id, |payload| {
-                check_is_null_condition(is_null, &payload)
-            })
-        }),
+        Condition::IsEmpty(is_empty) => (
+            Box::new(move |point_id| {
+                payload_provider.with_field_payload(point_id, &is_empty.is_empty.key, |payload| {
+                    check_is_empty_condition(is_empty, &payload)
+                })
+            }),
+            PAYLOAD_CHECK_COST,
+        ),
+        Condition::IsNull(is_null) => (
+            Box::new(move |point_id| {
+                payload_provider.with_field_payload(point_id, &is_null.is_null.key, |payload| {
+                    check_is_null_condition(is_null, &payload)
+                })
+            }),
+            PAYLOAD_CHECK_COST,
+        ),
         // ToDo: It might be possible to make this condition faster by using `VisitedPool` instead of HashSet
         Condition::HasId(has_id) => {
             let segment_ids: HashSet<_> = has_id
@@ -55,7 +70,10 @@ pub fn condition_converter<'a>(
                 .iter()
                 .filter_map(|external_id| id_tracker.internal_id(*external_id))
                 .collect();
-            Box::new(move |point_id| segment_ids.contains(&point_id))
+            (
+                Box::new(move |point_id| segment_ids.contains(&point_id)),
+                INDEXED_CHECK_COST,
+            )
         }
         Condition::Filter(_) => unreachable!(),
     }
@@ -176,6 +194,11 @@ pub fn get_match_checkers(index: &FieldIndex, cond_match: Match) -> Option<Condi
                     }
                 }))
             }
+            (ValueVariants::Bool(value), FieldIndex::BoolIndex(index)) => {
+                Some(Box::new(move |point_id: PointOffsetType| {
+                    index.check_value(point_id, value)
+                }))
+            }
             _ => None,
         },
         Match::Text(MatchText { text }) => match index {
@@ -1,4 +1,3 @@
-use std::cmp::Reverse;
 use std::collections::HashMap;
 
 use itertools::Itertools;
@@ -21,7 +20,7 @@ pub type IndexesMap = HashMap<PayloadKeyType, Vec<FieldIndex>>;
 ///
 /// * Convert each condition into a checker function
 /// * Use column index, avoid reading Payload, if possible
-/// * Re-order operations using estimated cardinalities
+/// * Re-order operations using estimated cardinalities and costs of the checks
 ///
 /// ToDo: Add optimizations between clauses
 ///
@@ -43,65 +42,65 @@ pub fn optimize_filter<'a, F>(
     estimator: &F,
     total: usize,
 ) -> (OptimizedFilter<'a>, CardinalityEstimation)
+where
+    F: Fn(&Condition) -> CardinalityEstimation,
+{
+    let (optimized_filter, estimation, _) = optimize_filter_with_cost(
+        filter,
+        id_tracker,
+        field_indexes,
+        payload_provider,
+        estimator,
+        total,
+    );
+    (optimized_filter, estimation)
+}
+
+/// Same as `optimize_filter`, but also returns the worst case cost of the filter check
+fn optimize_filter_with_cost<'a, F>(
+    filter: &'a Filter,
+    id_tracker: &IdTrackerSS,
+    field_indexes: &'a IndexesMap,
+    payload_provider: PayloadProvider,
+    estimator: &F,
+    total: usize,
+) -> (OptimizedFilter<'a>, CardinalityEstimation, f64)
 where
     F: Fn(&Condition) -> CardinalityEstimation,
 {
     let mut filter_estimations: Vec<CardinalityEstimation> = vec![];
+    let mut filter_cost = 0.0;
 
-    let optimized_filter = OptimizedFilter {
-        should: filter.should.as_ref().and_then(|conditions| {
-            if !conditions.is_empty() {
-                let (optimized_conditions, estimation) = optimize_should(
-                    conditions,
-                    id_tracker,
-                    field_indexes,
-                    payload_provider.clone(),
-                    estimator,
-                    total,
-                );
-                filter_estimations.push(estimation);
-                Some(optimized_conditions)
-            } else {
-                None
-            }
-        }),
-        must: filter.must.as_ref().and_then(|conditions| {
-            if !conditions.is_empty() {
-                let (optimized_conditions, estimation) = optimize_must(
-                    conditions,
-                    id_tracker,
-                    field_indexes,
-                    payload_provider.clone(),
-                    estimator,
-                    total,
-                );
-                filter_estimations.push(estimation);
-                Some(optimized_conditions)
-            } else {
-                None
-            }
-        }),
-        must_not: filter.must_not.as_ref().and_then(|conditions| {
-            if !conditions.is_empty() {
-                let (optimized_conditions, estimation) = optimize_must_not(
-                    conditions,
-                    id_tracker,
-                    field_indexes,
-                    payload_provider.clone(),
-                    estimator,
-                    total,
-                );
-                filter_estimations.push(estimation);
-                Some(optimized_conditions)
-            } else {
-                None
+    let mut optimize = |conditions: &'a Option<Vec<Condition>>, clause: Clause| {
+        conditions.as_ref().and_then(|conditions| {
+            if conditions.is_empty() {
+                return None;
             }
-        }),
+            let (optimized_conditions, estimation, cost) = optimize_clause(
+                clause,
+                conditions,
+                id_tracker,
+                field_indexes,
+                payload_provider.clone(),
+                estimator,
+                total,
+            );
+            filter_estimations.push(estimation);
+            filter_cost += cost;
+            Some(optimized_conditions)
+        })
+    };
+
+    let optimized_filter = OptimizedFilter {
+        should: optimize(&filter.should, Clause::Should),
+        must: optimize(&filter.must, Clause::Must),
+        must_not: optimize(&filter.must_not, Clause::MustNot),
     };
 
     (
         optimized_filter,
         combine_must_estimations(&filter_estimations, total),
+        filter_cost,
     )
 }
 
@@ -112,7 +111,7 @@ fn convert_conditions<'a, F>(
     payload_provider: PayloadProvider,
     estimator: &F,
     total: usize,
-) -> Vec<(OptimizedCondition<'a>, CardinalityEstimation)>
+) -> Vec<(OptimizedCondition<'a>, CardinalityEstimation, f64)>
 where
     F: Fn(&Condition) -> CardinalityEstimation,
 {
@@ -120,7 +119,7 @@ where
         .iter()
         .map(|condition| match condition {
             Condition::Filter(filter) => {
-                let (optimized_filter, estimation) = optimize_filter(
+                let (optimized_filter, estimation, cost) = optimize_filter_with_cost(
                     filter,
                     id_tracker,
                     field_indexes,
@@ -128,82 +127,83 @@ where
                     estimator,
                     total,
                 );
-                (OptimizedCondition::Filter(optimized_filter), estimation)
+                (
+                    OptimizedCondition::Filter(optimized_filter),
+                    estimation,
+                    cost,
+                )
             }
             _ => {
                 let estimation = estimator(condition);
-                let condition_checker = condition_converter(
+                let (condition_checker, cost) = condition_converter(
                     condition,
                     field_indexes,
                     payload_provider.clone(),
                     id_tracker,
                 );
-                (OptimizedCondition::Checker(condition_checker), estimation)
+                (
+                    OptimizedCondition::Checker(condition_checker),
+                    estimation,
+                    cost,
+                )
             }
         })
         .collect()
 }
 
-fn optimize_should<'a, F>(
-    conditions: &'a [Condition],
-    id_tracker: &IdTrackerSS,
-    field_indexes: &'a IndexesMap,
-    payload_provider: PayloadProvider,
-    estimator: &F,
-    total: usize,
-) -> (Vec<OptimizedCondition<'a>>, CardinalityEstimation)
-where
-    F: Fn(&Condition) -> CardinalityEstimation,
-{
-    let mut converted = convert_conditions(
-        conditions,
-        id_tracker,
-        field_indexes,
-        payload_provider,
-        estimator,
-        total,
-    );
-    // More probable conditions first
-    converted.sort_by_key(|(_, estimation)| Reverse(estimation.exp));
-    let (conditions, estimations): (Vec<_>, Vec<_>) = converted.into_iter().unzip();
-
-    (conditions, combine_should_estimations(&estimations, total))
+/// Clause of the filter, which defines how results of its conditions are combined
+#[derive(Debug, Clone, Copy)]
+enum Clause {
+    Should,
+    Must,
+    MustNot,
 }
 
-fn optimize_must<'a, F>(
-    conditions: &'a [Condition],
-    id_tracker: &IdTrackerSS,
-    field_indexes: &'a IndexesMap,
-    payload_provider: PayloadProvider,
-    estimator: &F,
-    total: usize,
-) -> (Vec<OptimizedCondition<'a>>, CardinalityEstimation)
-where
-    F: Fn(&Condition) -> CardinalityEstimation,
-{
-    let mut converted = convert_conditions(
-        conditions,
-        id_tracker,
-        field_indexes,
-        payload_provider,
-        estimator,
-        total,
-    );
-    // Less probable conditions first
-    converted.sort_by_key(|(_, estimation)| estimation.exp);
-    let (conditions, estimations): (Vec<_>, Vec<_>) = converted.into_iter().unzip();
+impl Clause {
+    /// Expected cost of the check per short-circuit of the clause evaluation.
+    /// Conditions are checked in the ascending order of it, so cheap checks, which are likely
+    /// to decide the result of the clause, go first, and payload reading checks go last.
+    fn check_order(self, estimation: &CardinalityEstimation, cost: f64, total: usize) -> f64 {
+        // Smoothed, so it is never exactly 0 or 1
+        let match_probability = (estimation.exp as f64 + 1.0) / (total as f64 + 2.0);
+        match self {
+            // Any match decides the result
+            Clause::Should | Clause::MustNot => cost / match_probability,
+            // Any mismatch decides the result
+            Clause::Must => cost / (1.0 - match_probability),
+        }
+    }
 
-    (conditions, combine_must_estimations(&estimations, total))
+    fn combine_estimations(
+        self,
+        estimations: Vec<CardinalityEstimation>,
+        total: usize,
+    ) -> CardinalityEstimation {
+        match self {
+            Clause::Should => combine_should_estimations(&estimations, total),
+            Clause::Must => combine_must_estimations(&estimations, total),
+            Clause::MustNot => combine_must_estimations(
+                &estimations
+                    .iter()
+                    .map(|estimation| invert_estimation(estimation, total))
+                    .collect_vec(),
+                total,
+            ),
+        }
+    }
 }
 
-fn optimize_must_not<'a, F>(
+/// Converts conditions of the clause and orders them by the expected cost of the clause check.
+/// Returns the worst case cost of the clause check too.
+fn optimize_clause<'a, F>(
+    clause: Clause,
     conditions: &'a [Condition],
     id_tracker: &IdTrackerSS,
     field_indexes: &'a IndexesMap,
     payload_provider: PayloadProvider,
     estimator: &F,
     total: usize,
-) -> (Vec<OptimizedCondition<'a>>, CardinalityEstimation)
+) -> (Vec<OptimizedCondition<'a>>, CardinalityEstimation, f64)
 where
     F: Fn(&Condition) -> CardinalityEstimation,
 {
@@ -215,18 +215,73 @@ where
         estimator,
         total,
     );
-    // More probable conditions first, as it will be reverted
-    converted.sort_by_key(|(_, estimation)| estimation.exp);
-    let (conditions, estimations): (Vec<_>, Vec<_>) = converted.into_iter().unzip();
+    converted.sort_by(|(_, estimation_a, cost_a), (_, estimation_b, cost_b)| {
+        let order_a = clause.check_order(estimation_a, *cost_a, total);
+        let order_b = clause.check_order(estimation_b, *cost_b, total);
+        order_a.total_cmp(&order_b)
+    });
+
+    let mut clause_cost = 0.0;
+    let mut optimized_conditions = Vec::with_capacity(converted.len());
+    let mut estimations = Vec::with_capacity(converted.len());
+    for (condition, estimation, cost) in converted {
+        optimized_conditions.push(condition);
+        estimations.push(estimation);
+        clause_cost += cost;
+    }
 
     (
-        conditions,
-        combine_must_estimations(
-            &estimations
-                .into_iter()
-                .map(|estimation| invert_estimation(&estimation, total))
-                .collect_vec(),
-            total,
-        ),
+        optimized_conditions,
+        clause.combine_estimations(estimations, total),
+        clause_cost,
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::index::query_optimization::condition_converter::{
+        INDEXED_CHECK_COST, PAYLOAD_CHECK_COST,
+    };
+
+    const TOTAL: usize = 1000;
+
+    fn estimation(exp: usize) -> CardinalityEstimation {
+        CardinalityEstimation {
+            primary_clauses: vec![],
+            min: exp,
+            exp,
+            max: exp,
+        }
+    }
+
+    #[test]
+    fn test_check_order() {
+        let order = |clause: Clause, exp, cost| clause.check_order(&estimation(exp), cost, TOTAL);
+
+        // Same cost: the most likely to decide the result goes first
+        assert!(
+            order(Clause::Must, 10, INDEXED_CHECK_COST)
+                < order(Clause::Must, 900, INDEXED_CHECK_COST)
+        );
+        assert!(
+            order(Clause::Should, 900, INDEXED_CHECK_COST)
+                < order(Clause::Should, 10, INDEXED_CHECK_COST)
+        );
+        assert!(
+            order(Clause::MustNot, 900, INDEXED_CHECK_COST)
+                < order(Clause::MustNot, 10, INDEXED_CHECK_COST)
+        );
+
+        // Cheap indexed check goes before the payload one, even if it is less selective
+        assert!(
+            order(Clause::Must, 500, INDEXED_CHECK_COST)
+                < order(Clause::Must, 100, PAYLOAD_CHECK_COST)
+        );
+        // Unless the payload check is much more likely to decide the result
+        assert!(
+            order(Clause::Must, 999, INDEXED_CHECK_COST)
+                > order(Clause::Must, 0, PAYLOAD_CHECK_COST)
+        );
+    }
+}
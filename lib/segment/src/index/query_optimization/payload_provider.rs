@@ -1,15 +1,28 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
 use std::ops::Deref;
+use std::rc::Rc;
 use std::sync::Arc;
 
 use atomic_refcell::AtomicRefCell;
 
+use crate::payload_storage::payload_column::PayloadColumn;
 use crate::payload_storage::payload_storage_enum::PayloadStorageEnum;
-use crate::types::{OwnedPayloadRef, Payload, PointOffsetType};
+use crate::types::{OwnedPayloadRef, Payload, PayloadKeyType, PointOffsetType};
+
+/// Id and payload of the point, read from disk last
+type LastReadPayload = Option<(PointOffsetType, Rc<Payload>)>;
 
 #[derive(Clone)]
 pub struct PayloadProvider {
     payload_storage: Arc<AtomicRefCell<PayloadStorageEnum>>,
     empty_payload: Payload,
+    /// Payload of the last point, read from disk. Shared by all clones of the provider,
+    /// so conditions of one filter, checked one after another for the same point,
+    /// read and deserialize its payload only once.
+    last_read: Rc<RefCell<LastReadPayload>>,
+    /// Columns of the fields, stored separately from the whole payloads
+    columns: HashMap<PayloadKeyType, Arc<PayloadColumn>>,
 }
 
 impl PayloadProvider {
@@ -17,9 +30,33 @@ impl PayloadProvider {
         Self {
             payload_storage,
             empty_payload: Default::default(),
+            last_read: Default::default(),
+            columns: Default::default(),
         }
     }
 
+    pub fn with_columns(mut self, columns: HashMap<PayloadKeyType, Arc<PayloadColumn>>) -> Self {
+        self.columns = columns;
+        self
+    }
+
+    /// Same as `with_payload`, but only values of the `key` are required from the payload.
+    /// If the field has a column, the projection of the payload onto the field is read from it.
+    pub fn with_field_payload<F, G>(&self, point_id: PointOffsetType, key: &str, callback: F) -> G
+    where
+        F: FnOnce(OwnedPayloadRef) -> G,
+    {
+        let column = match self.columns.get(key) {
+            Some(column) => column,
+            None => return self.with_payload(point_id, callback),
+        };
+        // Same as for the whole payload, failed read means that the storage is corrupted
+        let projection = column
+            .get(point_id)
+            .unwrap_or_else(|err| panic!("Payload column is corrupted: {err}"));
+        callback(projection.into())
+    }
+
     pub fn with_payload<F, G>(&self, point_id: PointOffsetType, callback: F) -> G
     where
         F: FnOnce(OwnedPayloadRef) -> G,
@@ -32,23 +69,35 @@ impl PayloadProvider {
             PayloadStorageEnum::SimplePayloadStorage(s) => {
                 s.payload_ptr(point_id).map(|x| x.into())
             }
-            // Warn: Possible panic here
-            // Currently, it is possible that `read_payload` fails with Err,
-            // but it seems like a very rare possibility which might only happen
-            // if something is wrong with disk or storage is corrupted.
-            //
-            // In both cases it means that service can't be of use any longer.
-            // It is as good as dead. Therefore it is tolerable to just panic here.
-            // Downside is - API user won't be notified of the failure.
-            // It will just timeout.
-            //
-            // The alternative:
-            // Rewrite condition checking code to support error reporting.
-            // Which may lead to slowdown and assumes a lot of changes.
-            PayloadStorageEnum::OnDiskPayloadStorage(s) => s
-                .read_payload(point_id)
-                .unwrap_or_else(|err| panic!("Payload storage is corrupted: {err}"))
-                .map(|x| x.into()),
+            PayloadStorageEnum::OnDiskPayloadStorage(s) => {
+                let mut last_read = self.last_read.borrow_mut();
+                let payload = match last_read.as_ref() {
+                    Some((last_point_id, payload)) if *last_point_id == point_id => payload.clone(),
+                    _ => {
+                        // Warn: Possible panic here
+                        // Currently, it is possible that `read_payload` fails with Err,
+                        // but it seems like a very rare possibility which might only happen
+                        // if something is wrong with disk or storage is corrupted.
+                        //
+                        // In both cases it means that service can't be of use any longer.
+                        // It is as good as dead. Therefore it is tolerable to just panic here.
+                        // Downside is - API user won't be notified of the failure.
+                        // It will just timeout.
+                        //
+                        // The alternative:
+                        // Rewrite condition checking code to support error reporting.
+                        // Which may lead to slowdown and assumes a lot of changes.
+                        let payload = s
+                            .read_payload(point_id)
+                            .unwrap_or_else(|err| panic!("Payload storage is corrupted: {err}"))
+                            .unwrap_or_default();
+                        let payload = Rc::new(payload);
+                        *last_read = Some((point_id, payload.clone()));
+                        payload
+                    }
+                };
+                Some(OwnedPayloadRef::Owned(payload))
+            }
         };
 
         let payload = if let Some(payload_ptr) = payload_ptr_opt {
@@ -60,3 +109,37 @@ impl PayloadProvider {
         callback(payload)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use tempfile::Builder;
+
+    use super::*;
+    use crate::common::rocksdb_wrapper::{open_db, DB_VECTOR_CF};
+    use crate::payload_storage::on_disk_payload_storage::OnDiskPayloadStorage;
+    use crate::payload_storage::PayloadStorage;
+
+    #[test]
+    fn test_on_disk_payload_cache() {
+        let dir = Builder::new().prefix("storage_dir").tempdir().unwrap();
+        let db = open_db(dir.path(), &[DB_VECTOR_CF]).unwrap();
+
+        let mut storage: PayloadStorageEnum = OnDiskPayloadStorage::open(db).unwrap().into();
+        let payload_a: Payload = serde_json::from_str(r#"{ "name": "a" }"#).unwrap();
+        let payload_b: Payload = serde_json::from_str(r#"{ "name": "b" }"#).unwrap();
+        storage.assign(0, &payload_a).unwrap();
+        storage.assign(1, &payload_b).unwrap();
+
+        let provider = PayloadProvider::new(Arc::new(AtomicRefCell::new(storage)));
+        let clone = provider.clone();
+        let read = |provider: &PayloadProvider, point_id| {
+            provider.with_payload(point_id, |payload| payload.deref().clone())
+        };
+
+        assert_eq!(read(&provider, 0), payload_a);
+        assert_eq!(read(&clone, 0), payload_a);
+        assert_eq!(read(&clone, 1), payload_b);
+        assert_eq!(read(&provider, 0), payload_a);
+        assert_eq!(read(&provider, 2), Payload::default());
+    }
+}
@@ -0,0 +1,199 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use atomic_refcell::AtomicRefCell;
+
+use crate::data_types::sparse_vector::{DimId, SparseVector};
+use crate::data_types::vectors::VectorElementType;
+use crate::id_tracker::IdTrackerSS;
+use crate::index::struct_payload_index::StructPayloadIndex;
+use crate::index::PayloadIndex;
+use crate::spaces::tools::peek_top_largest_iterable;
+use crate::types::{Filter, PointOffsetType, ScoreType};
+use crate::vector_storage::sparse_vector_storage::SparseVectorStorage;
+use crate::vector_storage::ScoredPointOffset;
+
+/// Element of the posting list: point, which has non-zero value in the dimension
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct PostingElement {
+    id: PointOffsetType,
+    weight: VectorElementType,
+}
+
+/// Inverted index over sparse vectors
+///
+/// Keeps a posting list of points for each non-zero dimension, sorted by point offset.
+/// Score of a point is a dot product of the query and the point vector,
+/// accumulated over posting lists of the query dimensions only.
+/// Posting lists are built in RAM from the vector storage on segment load.
+pub struct SparseVectorIndex {
+    id_tracker: Arc<AtomicRefCell<IdTrackerSS>>,
+    payload_index: Arc<AtomicRefCell<StructPayloadIndex>>,
+    postings: HashMap<DimId, Vec<PostingElement>>,
+}
+
+impl SparseVectorIndex {
+    pub fn open(
+        id_tracker: Arc<AtomicRefCell<IdTrackerSS>>,
+        vector_storage: Arc<AtomicRefCell<SparseVectorStorage>>,
+        payload_index: Arc<AtomicRefCell<StructPayloadIndex>>,
+    ) -> Self {
+        let mut index = SparseVectorIndex {
+            id_tracker,
+            payload_index,
+            postings: HashMap::new(),
+        };
+        // Storage iterates in the order of offsets, so posting lists are sorted by construction
+        for (id, vector) in vector_storage.borrow().iter_vectors() {
+            for (dim, weight) in vector.iter() {
+                index
+                    .postings
+                    .entry(dim)
+                    .or_default()
+                    .push(PostingElement { id, weight });
+            }
+        }
+        index
+    }
+
+    /// Number of dimensions with at least one point
+    pub fn indexed_dims(&self) -> usize {
+        self.postings.len()
+    }
+
+    /// Replace `old` vector of the point with the `new` one in posting lists
+    pub fn update(
+        &mut self,
+        id: PointOffsetType,
+        old: Option<&SparseVector>,
+        new: Option<&SparseVector>,
+    ) {
+        if let Some(old) = old {
+            for dim in &old.indices {
+                if let Some(posting) = self.postings.get_mut(dim) {
+                    if let Ok(pos) = posting.binary_search_by_key(&id, |element| element.id) {
+                        posting.remove(pos);
+                    }
+                    if posting.is_empty() {
+                        self.postings.remove(dim);
+                    }
+                }
+            }
+        }
+        if let Some(new) = new {
+            for (dim, weight) in new.iter() {
+                let posting = self.postings.entry(dim).or_default();
+                match posting.binary_search_by_key(&id, |element| element.id) {
+                    Ok(pos) => posting[pos].weight = weight,
+                    Err(pos) => posting.insert(pos, PostingElement { id, weight }),
+                }
+            }
+        }
+    }
+
+    /// Find `top` points with the largest dot product with the `vector`
+    ///
+    /// Points which have no common non-zero dimensions with the query are never returned.
+    pub fn search(
+        &self,
+        vector: &SparseVector,
+        filter: Option<&Filter>,
+        top: usize,
+    ) -> Vec<ScoredPointOffset> {
+        let mut scores: HashMap<PointOffsetType, ScoreType> = HashMap::new();
+        for (dim, value) in vector.iter() {
+            if let Some(posting) = self.postings.get(&dim) {
+                for element in posting {
+                    *scores.entry(element.id).or_default() += value * element.weight;
+                }
+            }
+        }
+
+        let id_tracker = self.id_tracker.borrow();
+        let payload_index = self.payload_index.borrow();
+        let filter_context = filter.map(|filter| payload_index.filter_context(filter));
+        let candidates = scores
+            .into_iter()
+            .filter(|(id, _)| !id_tracker.is_deleted(*id))
+            .filter(|(id, _)| {
+                filter_context
+                    .as_ref()
+                    .map_or(true, |context| context.check(*id))
+            })
+            .map(|(idx, score)| ScoredPointOffset { idx, score });
+        peek_top_largest_iterable(candidates, top)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::Builder;
+
+    use super::*;
+    use crate::common::rocksdb_wrapper::{open_db, DB_SPARSE_VECTOR_CF};
+    use crate::id_tracker::simple_id_tracker::SimpleIdTracker;
+    use crate::id_tracker::IdTracker;
+    use crate::payload_storage::simple_payload_storage::SimplePayloadStorage;
+    use crate::vector_storage::sparse_vector_storage::open_sparse_vector_storage;
+
+    #[test]
+    fn test_sparse_index_search() {
+        let dir = Builder::new().prefix("db_dir").tempdir().unwrap();
+        let db = open_db(dir.path(), &[DB_SPARSE_VECTOR_CF]).unwrap();
+        let id_tracker = Arc::new(AtomicRefCell::new(
+            SimpleIdTracker::open(db.clone()).unwrap(),
+        ));
+        let payload_storage = Arc::new(AtomicRefCell::new(
+            SimplePayloadStorage::open(db.clone()).unwrap().into(),
+        ));
+        let payload_index = Arc::new(AtomicRefCell::new(
+            StructPayloadIndex::open(
+                payload_storage,
+                id_tracker.clone(),
+                &dir.path().join("payload_index"),
+            )
+            .unwrap(),
+        ));
+        let storage = open_sparse_vector_storage(db, DB_SPARSE_VECTOR_CF).unwrap();
+
+        let vectors = [
+            SparseVector::new(vec![0, 5], vec![1.0, 1.0]),
+            SparseVector::new(vec![5, 9], vec![2.0, 1.0]),
+            SparseVector::new(vec![9], vec![2.5]),
+        ];
+        for (id, vector) in vectors.iter().enumerate() {
+            let id = id as PointOffsetType;
+            id_tracker
+                .borrow_mut()
+                .set_link((id as u64).into(), id)
+                .unwrap();
+            storage
+                .borrow_mut()
+                .insert_vector(id, Some(vector))
+                .unwrap();
+        }
+
+        let mut index = SparseVectorIndex::open(id_tracker.clone(), storage.clone(), payload_index);
+        assert_eq!(index.indexed_dims(), 3);
+
+        let query = SparseVector::new(vec![5, 9], vec![1.0, 1.0]);
+        let result = index.search(&query, None, 10);
+        let ids: Vec<_> = result.iter().map(|point| point.idx).collect();
+        assert_eq!(ids, vec![1, 2, 0]);
+        assert_eq!(result[0].score, 3.0);
+
+        // Point without common dimensions is not found
+        let result = index.search(&SparseVector::new(vec![0], vec![1.0]), None, 10);
+        assert_eq!(result.len(), 1);
+
+        let new_vector = SparseVector::new(vec![0], vec![1.0]);
+        index.update(1, Some(&vectors[1]), Some(&new_vector));
+        let result = index.search(&query, None, 10);
+        let ids: Vec<_> = result.iter().map(|point| point.idx).collect();
+        assert_eq!(ids, vec![2, 0]);
+
+        id_tracker.borrow_mut().drop(2.into()).unwrap();
+        let result = index.search(&query, None, 10);
+        assert_eq!(result.len(), 1);
+    }
+}
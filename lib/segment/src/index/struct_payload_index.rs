@@ -1,19 +1,29 @@
-use std::collections::{HashMap, HashSet};
+use std::cell::Cell;
+use std::cmp::{max, Reverse};
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::fs::{create_dir_all, remove_file};
 use std::ops::Deref;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use atomic_refcell::AtomicRefCell;
+use bitvec::vec::BitVec;
+use itertools::Itertools;
 use log::debug;
-use parking_lot::RwLock;
+use ordered_float::OrderedFloat;
+use parking_lot::{Mutex, RwLock};
+use rayon::prelude::*;
 use rocksdb::DB;
 use schemars::_serde_json::Value;
 
 use crate::common::arc_atomic_ref_cell_iterator::ArcAtomicRefCellIterator;
+use crate::common::cpu::get_num_cpus;
 use crate::common::rocksdb_wrapper::open_db_with_existing_cf;
 use crate::common::utils::MultiValue;
 use crate::common::Flusher;
+use crate::data_types::distinct::DistinctValues;
+use crate::data_types::histogram::{HistogramBuckets, ValueBounds};
+use crate::data_types::order_by::Direction;
 use crate::entry::entry_point::{OperationError, OperationResult};
 use crate::id_tracker::IdTrackerSS;
 use crate::index::field_index::index_selector::index_selector;
@@ -27,17 +37,53 @@ use crate::index::query_optimization::payload_provider::PayloadProvider;
 use crate::index::struct_filter_context::StructFilterContext;
 use crate::index::visited_pool::VisitedPool;
 use crate::index::PayloadIndex;
+use crate::payload_storage::payload_column::PayloadColumn;
 use crate::payload_storage::payload_storage_enum::PayloadStorageEnum;
 use crate::payload_storage::{FilterContext, PayloadStorage};
+use crate::spaces::tools::peek_top_smallest_iterable;
 use crate::telemetry::PayloadIndexTelemetry;
 use crate::types::{
-    infer_collection_value_type, infer_value_type, Condition, FieldCondition, Filter,
-    IsEmptyCondition, IsNullCondition, Payload, PayloadFieldSchema, PayloadKeyType,
-    PayloadKeyTypeRef, PayloadSchemaType, PointOffsetType,
+    infer_collection_value_type, infer_value_type, Condition, FieldCondition, Filter, GeoPoint,
+    IsEmptyCondition, IsNullCondition, Payload, PayloadFieldSchema, PayloadIndexInfo,
+    PayloadIndexStatus, PayloadKeyType, PayloadKeyTypeRef, PayloadSchemaType, PointIdType,
+    PointOffsetType, RecordPayload,
 };
 
 pub const PAYLOAD_FIELD_INDEX_PATH: &str = "fields";
 
+/// Number of points, which payload is read and prepared for the index build by a single thread at once
+const BUILD_CHUNK_SIZE: usize = 1000;
+
+/// Progress of the field index build, which is not applied to the payload index yet
+#[derive(Debug, Clone)]
+struct IndexBuildProgress {
+    payload_schema: PayloadFieldSchema,
+    status: PayloadIndexStatus,
+    processed_points: usize,
+    total_points: usize,
+}
+
+fn count_check(checks: Option<&Cell<usize>>) {
+    if let Some(checks) = checks {
+        checks.set(checks.get() + 1);
+    }
+}
+
+impl IndexBuildProgress {
+    fn info(&self) -> PayloadIndexInfo {
+        let mut info = PayloadIndexInfo::new(self.payload_schema.clone(), self.processed_points);
+        info.status = self.status;
+        if self.status == PayloadIndexStatus::Building {
+            let percent = match self.total_points {
+                0 => 100,
+                total_points => (self.processed_points * 100 / total_points).min(100),
+            };
+            info.progress_percent = Some(percent as u8);
+        }
+        info
+    }
+}
+
 /// `PayloadIndex` implementation, which actually uses index structures for providing faster search
 pub struct StructPayloadIndex {
     /// Payload storage
@@ -50,6 +96,13 @@ pub struct StructPayloadIndex {
     path: PathBuf,
     visited_pool: VisitedPool,
     db: Arc<RwLock<DB>>,
+    /// Builds of field indexes, which are in progress or failed.
+    /// Builds may run concurrently with reads, so the progress is behind a lock.
+    index_builds: Mutex<HashMap<PayloadKeyType, IndexBuildProgress>>,
+    /// Values of indexed fields, stored separately from the whole payloads.
+    /// Only maintained for the on-disk payload storage, so checks of conditions,
+    /// which can't be answered by the index, read only values of the field.
+    columns: HashMap<PayloadKeyType, Arc<PayloadColumn>>,
 }
 
 impl StructPayloadIndex {
@@ -104,12 +157,54 @@ impl StructPayloadIndex {
 
     fn load_all_fields(&mut self) -> OperationResult<()> {
         let mut field_indexes: IndexesMap = Default::default();
+        let mut columns: HashMap<PayloadKeyType, Arc<PayloadColumn>> = Default::default();
 
         for (field, payload_schema) in &self.config.indexed_fields {
             let field_index = self.load_from_db(field, payload_schema.to_owned())?;
             field_indexes.insert(field.clone(), field_index);
+            if let Some(column) = self.load_column(field)? {
+                columns.insert(field.clone(), Arc::new(column));
+            }
         }
         self.field_indexes = field_indexes;
+        self.columns = columns;
+        Ok(())
+    }
+
+    /// Whether values of indexed fields are stored in columns.
+    /// In-memory payload storages don't benefit from reading of separate fields.
+    fn is_columnar(&self) -> bool {
+        matches!(
+            &*self.payload.borrow(),
+            PayloadStorageEnum::OnDiskPayloadStorage(_)
+        )
+    }
+
+    fn load_column(&self, field: PayloadKeyTypeRef) -> OperationResult<Option<PayloadColumn>> {
+        if !self.is_columnar() {
+            return Ok(None);
+        }
+        let column = PayloadColumn::new(self.db.clone(), field);
+        if !column.exists()? {
+            debug!("Column for `{field}` was not found. Building...");
+            column.recreate()?;
+            self.payload.borrow().iter(|point_id, payload| {
+                column.set(point_id, payload)?;
+                Ok(true)
+            })?;
+        }
+        Ok(Some(column))
+    }
+
+    /// Update columns of the point from its stored payload
+    fn update_columns(&self, point_id: PointOffsetType) -> OperationResult<()> {
+        if self.columns.is_empty() {
+            return Ok(());
+        }
+        let payload = self.payload.borrow().payload(point_id)?;
+        for column in self.columns.values() {
+            column.set(point_id, &payload)?;
+        }
         Ok(())
     }
 
@@ -130,6 +225,7 @@ impl StructPayloadIndex {
         if !is_loaded {
             debug!("Index for `{field}` was not loaded. Building...");
             indexes = self.build_field_indexes(field, payload_schema)?;
+            self.index_builds.lock().remove(field);
         }
 
         Ok(indexes)
@@ -159,6 +255,8 @@ impl StructPayloadIndex {
             path: path.to_owned(),
             visited_pool: Default::default(),
             db,
+            index_builds: Default::default(),
+            columns: Default::default(),
         };
 
         if !index.config_path().exists() {
@@ -171,45 +269,413 @@ impl StructPayloadIndex {
         Ok(index)
     }
 
+    /// Build indexes of the field from the stored payload.
+    /// Progress of the build is tracked until the indexes are applied, or kept if the build fails.
     pub fn build_field_indexes(
         &self,
         field: PayloadKeyTypeRef,
         payload_schema: PayloadFieldSchema,
+    ) -> OperationResult<Vec<FieldIndex>> {
+        self.index_builds.lock().insert(
+            field.to_owned(),
+            IndexBuildProgress {
+                payload_schema: payload_schema.clone(),
+                status: PayloadIndexStatus::Building,
+                processed_points: 0,
+                total_points: self.total_points(),
+            },
+        );
+
+        let result = self.build_field_indexes_tracked(field, payload_schema);
+        if result.is_err() {
+            if let Some(progress) = self.index_builds.lock().get_mut(field) {
+                progress.status = PayloadIndexStatus::Failed;
+            }
+        }
+        result
+    }
+
+    fn build_field_indexes_tracked(
+        &self,
+        field: PayloadKeyTypeRef,
+        payload_schema: PayloadFieldSchema,
     ) -> OperationResult<Vec<FieldIndex>> {
         let payload_storage = self.payload.borrow();
         let mut field_indexes = index_selector(field, &payload_schema, self.db.clone());
         for index in &field_indexes {
             index.recreate()?;
         }
+        let column = if self.is_columnar() {
+            let column = PayloadColumn::new(self.db.clone(), field);
+            column.recreate()?;
+            Some(column)
+        } else {
+            None
+        };
+
+        let payload_storage: &PayloadStorageEnum = &payload_storage;
+        let num_threads = max(1, get_num_cpus() - 1);
+        let pool = rayon::ThreadPoolBuilder::new()
+            .thread_name(|idx| format!("field-index-build-{idx}"))
+            .num_threads(num_threads)
+            .build()?;
 
-        payload_storage.iter(|point_id, point_payload| {
-            let field_value = &point_payload.get_value(field);
-            for field_index in field_indexes.iter_mut() {
-                field_index.add_point(point_id, field_value)?;
+        let id_tracker = self.id_tracker.borrow();
+        let mut point_ids = id_tracker.iter_ids();
+        let mut processed_points = 0;
+        loop {
+            let batch: Vec<PointOffsetType> = point_ids
+                .by_ref()
+                .take(num_threads * BUILD_CHUNK_SIZE)
+                .collect();
+            if batch.is_empty() {
+                break;
             }
-            Ok(true)
-        })?;
+
+            // Reading of payloads and extraction of values is the heavy part of the build,
+            // so it is done in parallel for chunks of points.
+            // Insertion into the indexes is sequential, as it modifies the index structures.
+            let field_indexes_ref = &field_indexes;
+            let column_ref = column.as_ref();
+            let prepared_chunks = pool.install(|| {
+                batch
+                    .par_chunks(BUILD_CHUNK_SIZE)
+                    .map(|chunk| {
+                        chunk
+                            .iter()
+                            .map(|&point_id| {
+                                let point_payload = payload_storage.payload(point_id)?;
+                                let field_value = point_payload.get_value(field);
+                                let values: Vec<_> = field_indexes_ref
+                                    .iter()
+                                    .map(|field_index| field_index.prepare_point(&field_value))
+                                    .collect();
+                                let projection =
+                                    column_ref.map(|column| column.project(&point_payload));
+                                Ok((point_id, values, projection))
+                            })
+                            .collect::<OperationResult<Vec<_>>>()
+                    })
+                    .collect::<OperationResult<Vec<_>>>()
+            })?;
+
+            for (point_id, point_values, projection) in prepared_chunks.into_iter().flatten() {
+                for (field_index, values) in field_indexes.iter_mut().zip(point_values) {
+                    if let Some(values) = values {
+                        field_index.add_prepared(point_id, values)?;
+                    }
+                }
+                if let (Some(column), Some(projection)) = (&column, projection) {
+                    column.set_projection(point_id, &projection)?;
+                }
+            }
+
+            processed_points += batch.len();
+            if let Some(progress) = self.index_builds.lock().get_mut(field) {
+                progress.processed_points = processed_points;
+            }
+        }
         Ok(field_indexes)
     }
 
-    fn build_and_save(
+    /// Build indexes of the field, which is not indexed yet, without modifying the payload index.
+    /// Returns `None` if the field is already indexed.
+    pub fn build_index(
+        &self,
+        field: PayloadKeyTypeRef,
+        payload_schema: &PayloadFieldSchema,
+    ) -> OperationResult<Option<Vec<FieldIndex>>> {
+        if self.config.indexed_fields.contains_key(field) {
+            return Ok(None);
+        }
+        self.build_field_indexes(field, payload_schema.clone())
+            .map(Some)
+    }
+
+    /// Start using indexes, built by [`StructPayloadIndex::build_index`], for the field
+    pub fn apply_index(
         &mut self,
         field: PayloadKeyTypeRef,
         payload_schema: PayloadFieldSchema,
+        field_indexes: Vec<FieldIndex>,
     ) -> OperationResult<()> {
-        let field_indexes = self.build_field_indexes(field, payload_schema)?;
+        self.config
+            .indexed_fields
+            .insert(field.to_owned(), payload_schema);
+        self.save_config()?;
         self.field_indexes.insert(field.into(), field_indexes);
+        if self.is_columnar() {
+            // Column is filled by the build of the indexes
+            let column = PayloadColumn::new(self.db.clone(), field);
+            self.columns.insert(field.into(), Arc::new(column));
+        }
+        self.index_builds.lock().remove(field);
         Ok(())
     }
 
+    /// Info about indexes of fields, which are being built or failed to build
+    pub fn index_builds_info(&self) -> HashMap<PayloadKeyType, PayloadIndexInfo> {
+        self.index_builds
+            .lock()
+            .iter()
+            .map(|(field, progress)| (field.clone(), progress.info()))
+            .collect()
+    }
+
+    /// Count points, which have each term of the keyword or full-text index of the field.
+    /// Only points, which satisfy the filter, are counted, terms without such points are omitted.
+    ///
+    /// Returns `None` if the field has no keyword or full-text index.
+    pub fn term_counts(
+        &self,
+        field: PayloadKeyTypeRef,
+        filter: Option<&Filter>,
+    ) -> Option<HashMap<String, usize>> {
+        let terms = self
+            .field_indexes
+            .get(field)?
+            .iter()
+            .find_map(|field_index| field_index.iter_terms())?;
+
+        let filter_context = filter.map(|filter| self.filter_context(filter));
+        let term_counts = terms
+            .filter_map(|(term, points)| {
+                let count = match &filter_context {
+                    None => points.len(),
+                    Some(filter_context) => points
+                        .iter()
+                        .filter(|&&point_id| filter_context.check(point_id))
+                        .count(),
+                };
+                (count > 0).then(|| (term.to_owned(), count))
+            })
+            .collect();
+        Some(term_counts)
+    }
+
+    /// Distinct values of the keyword or integer index of the field among points,
+    /// which satisfy the filter.
+    ///
+    /// Returns `None` if the field has no keyword or integer index.
+    pub fn distinct_values(
+        &self,
+        field: PayloadKeyTypeRef,
+        filter: Option<&Filter>,
+    ) -> Option<DistinctValues> {
+        let values = self
+            .field_indexes
+            .get(field)?
+            .iter()
+            .find_map(|field_index| field_index.iter_value_hashes())?;
+
+        let filter_context = filter.map(|filter| self.filter_context(filter));
+        let mut distinct_values = DistinctValues::default();
+        for (hash, points) in values {
+            let has_points = match &filter_context {
+                None => !points.is_empty(),
+                Some(filter_context) => points
+                    .iter()
+                    .any(|&point_id| filter_context.check(point_id)),
+            };
+            if has_points {
+                distinct_values.insert(hash);
+            }
+        }
+        Some(distinct_values)
+    }
+
+    /// Points, which satisfy the filter, nearest to the `origin` according to the geo index
+    /// of the field, along with distances in meters to their closest geo points.
+    /// Points are ordered from the nearest to the farthest.
+    ///
+    /// Returns `None` if the field has no geo index.
+    pub fn nearest_points(
+        &self,
+        field: PayloadKeyTypeRef,
+        origin: &GeoPoint,
+        limit: usize,
+        filter: Option<&Filter>,
+    ) -> Option<Vec<(PointOffsetType, f64)>> {
+        let geo_index = self
+            .field_indexes
+            .get(field)?
+            .iter()
+            .find_map(|field_index| field_index.as_geo_index())?;
+
+        let filter_context = filter.map(|filter| self.filter_context(filter));
+        let id_tracker = self.id_tracker.borrow();
+        let distances = id_tracker
+            .iter_ids()
+            .filter(|&point_id| {
+                filter_context
+                    .as_ref()
+                    .map_or(true, |filter_context| filter_context.check(point_id))
+            })
+            .filter_map(|point_id| {
+                let distance = geo_index.min_distance(point_id, origin)?;
+                Some((OrderedFloat(distance), point_id))
+            });
+        let nearest = peek_top_smallest_iterable(distances, limit)
+            .into_iter()
+            .map(|(distance, point_id)| (point_id, distance.into_inner()))
+            .collect();
+        Some(nearest)
+    }
+
+    /// Points, which satisfy the filter, ordered by values of the integer or float index
+    /// of the field, along with the values, by which they are ordered.
+    /// Points with several values are ordered by the first of them in the `direction`,
+    /// points with equal values - by external id.
+    ///
+    /// If `start_from` is given, only points with values at or after it are returned,
+    /// and if `offset` is given too, points with exactly the `start_from` value
+    /// are returned starting from the `offset` id.
+    ///
+    /// Returns `None` if the field has no integer or float range index.
+    pub fn ordered_points(
+        &self,
+        field: PayloadKeyTypeRef,
+        direction: Direction,
+        start_from: Option<f64>,
+        offset: Option<PointIdType>,
+        limit: usize,
+        filter: Option<&Filter>,
+    ) -> Option<Vec<(PointOffsetType, f64)>> {
+        let (field_index, values) = self.field_indexes.get(field)?.iter().find_map(|index| {
+            let values = index.iter_ordered_values(start_from, direction == Direction::Desc)?;
+            Some((index, values))
+        })?;
+
+        let filter_context = filter.map(|filter| self.filter_context(filter));
+        let id_tracker = self.id_tracker.borrow();
+        let mut points: Vec<(f64, PointIdType, PointOffsetType)> = Vec::new();
+        for (value, point_id) in values {
+            // Points with the same value as the last one are still read, so they are ordered by id
+            let page_is_full = points.len() >= limit
+                && points
+                    .last()
+                    .map_or(true, |&(last_value, ..)| last_value != value);
+            if page_is_full {
+                break;
+            }
+            // Point is ordered by only one of its values, others are skipped
+            let order_value =
+                direction.order_value(field_index.numeric_values(point_id).unwrap_or_default());
+            if order_value != Some(value) {
+                continue;
+            }
+            let external_id = match id_tracker.external_id(point_id) {
+                Some(external_id) => external_id,
+                None => continue,
+            };
+            let before_offset =
+                Some(value) == start_from && offset.map_or(false, |offset| external_id < offset);
+            if before_offset {
+                continue;
+            }
+            let satisfies_filter = filter_context
+                .as_ref()
+                .map_or(true, |filter_context| filter_context.check(point_id));
+            if satisfies_filter {
+                points.push((value, external_id, point_id));
+            }
+        }
+
+        let ordered = points
+            .into_iter()
+            .sorted_by(|(value_a, id_a, _), (value_b, id_b, _)| {
+                direction.cmp(*value_a, *value_b).then(id_a.cmp(id_b))
+            })
+            .take(limit)
+            .map(|(value, _, point_id)| (point_id, value))
+            .collect();
+        Some(ordered)
+    }
+
+    /// Min and max values of the integer or float index of the field among points,
+    /// which satisfy the filter.
+    ///
+    /// Returns `None` if the field has no numeric index,
+    /// `Some(None)` if no point, which satisfies the filter, has values.
+    pub fn numeric_bounds(
+        &self,
+        field: PayloadKeyTypeRef,
+        filter: Option<&Filter>,
+    ) -> Option<Option<ValueBounds>> {
+        let field_index = self.numeric_field_index(field)?;
+        let mut bounds = None;
+        self.for_each_filtered_point(filter, |point_id| {
+            for value in field_index.numeric_values(point_id).unwrap_or_default() {
+                bounds = ValueBounds::merge_opt(bounds, Some(ValueBounds::new(value)));
+            }
+        });
+        Some(bounds)
+    }
+
+    /// Count points, which satisfy the filter, with values of the integer or float index
+    /// of the field in each of the buckets.
+    /// Points with several values in the same bucket are counted once.
+    ///
+    /// Returns `None` if the field has no numeric index.
+    pub fn numeric_histogram(
+        &self,
+        field: PayloadKeyTypeRef,
+        buckets: &HistogramBuckets,
+        filter: Option<&Filter>,
+    ) -> Option<Vec<usize>> {
+        let field_index = self.numeric_field_index(field)?;
+        let mut counts = vec![0; buckets.count];
+        self.for_each_filtered_point(filter, |point_id| {
+            let point_buckets: BTreeSet<_> = field_index
+                .numeric_values(point_id)
+                .unwrap_or_default()
+                .into_iter()
+                .filter_map(|value| buckets.bucket(value))
+                .collect();
+            for bucket in point_buckets {
+                counts[bucket] += 1;
+            }
+        });
+        Some(counts)
+    }
+
+    fn numeric_field_index(&self, field: PayloadKeyTypeRef) -> Option<&FieldIndex> {
+        self.field_indexes
+            .get(field)?
+            .iter()
+            .find(|field_index| field_index.is_numeric())
+    }
+
+    fn for_each_filtered_point(&self, filter: Option<&Filter>, mut f: impl FnMut(PointOffsetType)) {
+        let filter_context = filter.map(|filter| self.filter_context(filter));
+        let id_tracker = self.id_tracker.borrow();
+        id_tracker
+            .iter_ids()
+            .filter(|&point_id| {
+                filter_context
+                    .as_ref()
+                    .map_or(true, |filter_context| filter_context.check(point_id))
+            })
+            .for_each(&mut f);
+    }
+
     pub fn total_points(&self) -> usize {
         self.id_tracker.borrow().points_count()
     }
 
+    /// Iterate over all stored payloads, until the callback returns `false`
+    pub fn iter_payloads<F>(&self, callback: F) -> OperationResult<()>
+    where
+        F: FnMut(PointOffsetType, &Payload) -> OperationResult<bool>,
+    {
+        self.payload.borrow().iter(callback)
+    }
+
     fn struct_filtered_context<'a>(&'a self, filter: &'a Filter) -> StructFilterContext<'a> {
         let estimator = |condition: &Condition| self.condition_cardinality(condition);
         let id_tracker = self.id_tracker.borrow();
-        let payload_provider = PayloadProvider::new(self.payload.clone());
+        let payload_provider =
+            PayloadProvider::new(self.payload.clone()).with_columns(self.columns.clone());
         StructFilterContext::new(
             filter,
             id_tracker.deref(),
@@ -316,56 +782,62 @@ impl StructPayloadIndex {
     ) -> OperationResult<()> {
         crate::rocksdb_backup::restore(snapshot_path, &segment_path.join("payload_index"))
     }
-}
-
-impl PayloadIndex for StructPayloadIndex {
-    fn indexed_fields(&self) -> HashMap<PayloadKeyType, PayloadFieldSchema> {
-        self.config.indexed_fields.clone()
-    }
-
-    fn set_indexed(
-        &mut self,
-        field: PayloadKeyTypeRef,
-        payload_schema: PayloadFieldSchema,
-    ) -> OperationResult<()> {
-        if self
-            .config
-            .indexed_fields
-            .insert(field.to_owned(), payload_schema.clone())
-            .is_none()
-        {
-            self.save_config()?;
-            self.build_and_save(field, payload_schema)?;
-        }
-
-        Ok(())
-    }
 
-    fn drop_index(&mut self, field: PayloadKeyTypeRef) -> OperationResult<()> {
-        self.config.indexed_fields.remove(field);
-        self.save_config()?;
-        self.field_indexes.remove(field);
+    /// Points, which match any of the top-level `must_not` conditions of the query by the indexes.
+    /// Such points never satisfy the query, so a full scan might skip them without a check.
+    ///
+    /// Only conditions, which indexes select exactly, are used. Conditions with more expected
+    /// matches go first, so the exclusion stops early, once it covers all points of the segment.
+    /// Returns `None`, if no point is excluded.
+    fn must_not_exclusion(&self, query: &Filter) -> Option<BitVec> {
+        let conditions = query.must_not.as_ref()?;
+        let id_tracker = self.id_tracker.borrow();
+        let total_points = id_tracker.points_count();
 
-        let field_index_path = Self::get_field_index_path(&self.path, field);
+        let clauses = conditions
+            .iter()
+            .filter(|condition| matches!(condition, Condition::Field(_) | Condition::HasId(_)))
+            .map(|condition| self.condition_cardinality(condition))
+            .filter(|estimation| !estimation.primary_clauses.is_empty())
+            .sorted_by_key(|estimation| Reverse(estimation.exp))
+            .flat_map(|estimation| estimation.primary_clauses);
 
-        if field_index_path.exists() {
-            remove_file(&field_index_path)?;
+        let mut excluded = BitVec::repeat(false, id_tracker.internal_size());
+        let mut excluded_count = 0;
+        for clause in clauses {
+            let points: Box<dyn Iterator<Item = PointOffsetType> + '_> = match &clause {
+                PrimaryCondition::Condition(field_condition) => {
+                    match self.query_field(field_condition) {
+                        Some(points) => points,
+                        None => continue,
+                    }
+                }
+                PrimaryCondition::Ids(ids) => Box::new(ids.iter().copied()),
+                // Not indexed, so these points would be read from the payload anyway
+                PrimaryCondition::IsEmpty(_) | PrimaryCondition::IsNull(_) => continue,
+            };
+            for point in points {
+                if let Some(mut bit) = excluded.get_mut(point as usize) {
+                    if !*bit {
+                        *bit = true;
+                        excluded_count += 1;
+                    }
+                }
+            }
+            if excluded_count >= total_points {
+                break;
+            }
         }
 
-        Ok(())
-    }
-
-    fn estimate_cardinality(&self, query: &Filter) -> CardinalityEstimation {
-        let total_points = self.total_points();
-
-        let estimator = |condition: &Condition| self.condition_cardinality(condition);
-
-        estimate_filter(&estimator, query, total_points)
+        (excluded_count > 0).then_some(excluded)
     }
 
-    fn query_points<'a>(
+    /// Same as `query_points`, but also counts the number of points, checked against the filter.
+    /// Checks are counted lazily, while the returned iterator is consumed.
+    pub fn query_points_with_checks<'a>(
         &'a self,
         query: &'a Filter,
+        checks: Option<&'a Cell<usize>>,
     ) -> Box<dyn Iterator<Item = PointOffsetType> + 'a> {
         // Assume query is already estimated to be small enough so we can iterate over all matched ids
 
@@ -377,9 +849,19 @@ impl PayloadIndex for StructPayloadIndex {
                 });
 
             let struct_filtered_context = self.struct_filtered_context(query);
+            let excluded_points = self.must_not_exclusion(query);
             // Worst case: query expected to return few matches, but index can't be used
-            let matched_points =
-                full_scan_iterator.filter(move |i| struct_filtered_context.check(*i));
+            let matched_points = full_scan_iterator
+                .filter(move |&i| {
+                    // Excluded points can't match the query, so they are skipped without a check
+                    excluded_points.as_ref().map_or(true, |excluded| {
+                        !excluded.get(i as usize).map_or(false, |bit| *bit)
+                    })
+                })
+                .filter(move |i| {
+                    count_check(checks);
+                    struct_filtered_context.check(*i)
+                });
 
             Box::new(matched_points)
         } else {
@@ -407,7 +889,10 @@ impl PayloadIndex for StructPayloadIndex {
                     }
                 })
                 .filter(|&id| !visited_list.check_and_update_visited(id))
-                .filter(move |&i| struct_filtered_context.check(i))
+                .filter(move |&i| {
+                    count_check(checks);
+                    struct_filtered_context.check(i)
+                })
                 .collect();
 
             self.visited_pool.return_back(visited_list);
@@ -416,6 +901,57 @@ impl PayloadIndex for StructPayloadIndex {
             Box::new(matched_points_iter)
         };
     }
+}
+
+impl PayloadIndex for StructPayloadIndex {
+    fn indexed_fields(&self) -> HashMap<PayloadKeyType, PayloadFieldSchema> {
+        self.config.indexed_fields.clone()
+    }
+
+    fn set_indexed(
+        &mut self,
+        field: PayloadKeyTypeRef,
+        payload_schema: PayloadFieldSchema,
+    ) -> OperationResult<()> {
+        if let Some(field_indexes) = self.build_index(field, &payload_schema)? {
+            self.apply_index(field, payload_schema, field_indexes)?;
+        }
+
+        Ok(())
+    }
+
+    fn drop_index(&mut self, field: PayloadKeyTypeRef) -> OperationResult<()> {
+        self.config.indexed_fields.remove(field);
+        self.save_config()?;
+        self.field_indexes.remove(field);
+        self.index_builds.lock().remove(field);
+        if let Some(column) = self.columns.remove(field) {
+            column.clear()?;
+        }
+
+        let field_index_path = Self::get_field_index_path(&self.path, field);
+
+        if field_index_path.exists() {
+            remove_file(&field_index_path)?;
+        }
+
+        Ok(())
+    }
+
+    fn estimate_cardinality(&self, query: &Filter) -> CardinalityEstimation {
+        let total_points = self.total_points();
+
+        let estimator = |condition: &Condition| self.condition_cardinality(condition);
+
+        estimate_filter(&estimator, query, total_points)
+    }
+
+    fn query_points<'a>(
+        &'a self,
+        query: &'a Filter,
+    ) -> Box<dyn Iterator<Item = PointOffsetType> + 'a> {
+        self.query_points_with_checks(query, None)
+    }
 
     fn indexed_points(&self, field: PayloadKeyTypeRef) -> usize {
         self.field_indexes.get(field).map_or(0, |indexes| {
@@ -457,13 +993,18 @@ impl PayloadIndex for StructPayloadIndex {
                 index.add_point(point_id, field_value)?;
             }
         }
-        self.payload.borrow_mut().assign(point_id, payload)
+        self.payload.borrow_mut().assign(point_id, payload)?;
+        self.update_columns(point_id)
     }
 
     fn payload(&self, point_id: PointOffsetType) -> OperationResult<Payload> {
         self.payload.borrow().payload(point_id)
     }
 
+    fn raw_payload(&self, point_id: PointOffsetType) -> OperationResult<RecordPayload> {
+        self.payload.borrow().raw_payload(point_id)
+    }
+
     fn delete(
         &mut self,
         point_id: PointOffsetType,
@@ -474,7 +1015,9 @@ impl PayloadIndex for StructPayloadIndex {
                 index.remove_point(point_id)?;
             }
         }
-        self.payload.borrow_mut().delete(point_id, key)
+        let deleted = self.payload.borrow_mut().delete(point_id, key)?;
+        self.update_columns(point_id)?;
+        Ok(deleted)
     }
 
     fn drop(&mut self, point_id: PointOffsetType) -> OperationResult<Option<Payload>> {
@@ -483,6 +1026,9 @@ impl PayloadIndex for StructPayloadIndex {
                 index.remove_point(point_id)?;
             }
         }
+        for column in self.columns.values() {
+            column.remove(point_id)?;
+        }
         self.payload.borrow_mut().drop(point_id)
     }
 
@@ -493,6 +1039,9 @@ impl PayloadIndex for StructPayloadIndex {
                 index.clear()?;
             }
         }
+        for (_, column) in self.columns.drain() {
+            column.clear()?;
+        }
         self.load_all_fields()
     }
 
@@ -503,6 +1052,9 @@ impl PayloadIndex for StructPayloadIndex {
                 flushers.push(index.flusher());
             }
         }
+        for column in self.columns.values() {
+            flushers.push(column.flusher());
+        }
         flushers.push(self.payload.borrow().flusher());
         Box::new(move || {
             for flusher in flushers {
@@ -537,4 +1089,12 @@ impl PayloadIndex for StructPayloadIndex {
     fn files(&self) -> Vec<PathBuf> {
         vec![self.config_path()]
     }
+
+    fn ram_usage_bytes(&self) -> usize {
+        self.field_indexes
+            .values()
+            .flatten()
+            .map(|index| index.ram_usage_bytes())
+            .sum()
+    }
 }
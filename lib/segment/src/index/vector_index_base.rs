@@ -13,12 +13,15 @@ use crate::vector_storage::ScoredPointOffset;
 /// Trait for vector searching
 pub trait VectorIndex {
     /// Return list of Ids with fitting
+    ///
+    /// The search is interrupted once `is_stopped` is set, results are incomplete in that case.
     fn search(
         &self,
         vectors: &[&[VectorElementType]],
         filter: Option<&Filter>,
         top: usize,
         params: Option<&SearchParams>,
+        is_stopped: &AtomicBool,
     ) -> Vec<Vec<ScoredPointOffset>>;
 
     /// Force internal index rebuild.
@@ -27,6 +30,9 @@ pub trait VectorIndex {
     fn get_telemetry_data(&self) -> VectorIndexSearchesTelemetry;
 
     fn files(&self) -> Vec<PathBuf>;
+
+    /// Estimated size of the index structures, kept in RAM
+    fn ram_usage_bytes(&self) -> usize;
 }
 
 pub enum VectorIndexEnum {
@@ -42,11 +48,16 @@ impl VectorIndex for VectorIndexEnum {
         filter: Option<&Filter>,
         top: usize,
         params: Option<&SearchParams>,
+        is_stopped: &AtomicBool,
     ) -> Vec<Vec<ScoredPointOffset>> {
         match self {
-            VectorIndexEnum::Plain(index) => index.search(vectors, filter, top, params),
-            VectorIndexEnum::HnswRam(index) => index.search(vectors, filter, top, params),
-            VectorIndexEnum::HnswMmap(index) => index.search(vectors, filter, top, params),
+            VectorIndexEnum::Plain(index) => index.search(vectors, filter, top, params, is_stopped),
+            VectorIndexEnum::HnswRam(index) => {
+                index.search(vectors, filter, top, params, is_stopped)
+            }
+            VectorIndexEnum::HnswMmap(index) => {
+                index.search(vectors, filter, top, params, is_stopped)
+            }
         }
     }
 
@@ -73,4 +84,12 @@ impl VectorIndex for VectorIndexEnum {
             VectorIndexEnum::HnswMmap(index) => index.files(),
         }
     }
+
+    fn ram_usage_bytes(&self) -> usize {
+        match self {
+            VectorIndexEnum::Plain(index) => index.ram_usage_bytes(),
+            VectorIndexEnum::HnswRam(index) => index.ram_usage_bytes(),
+            VectorIndexEnum::HnswMmap(index) => index.ram_usage_bytes(),
+        }
+    }
 }
@@ -8,7 +8,7 @@ use crate::common::rocksdb_wrapper::{DatabaseColumnWrapper, DB_PAYLOAD_CF};
 use crate::common::Flusher;
 use crate::entry::entry_point::{OperationError, OperationResult};
 use crate::payload_storage::PayloadStorage;
-use crate::types::{Payload, PayloadKeyTypeRef, PointOffsetType};
+use crate::types::{Payload, PayloadKeyTypeRef, PointOffsetType, RecordPayload};
 
 /// On-disk implementation of `PayloadStorage`.
 /// Persists all changes to disk using `store`, does not keep payload in memory
@@ -88,6 +88,15 @@ impl PayloadStorage for OnDiskPayloadStorage {
         }
     }
 
+    fn raw_payload(&self, point_id: PointOffsetType) -> OperationResult<RecordPayload> {
+        let key = serde_cbor::to_vec(&point_id).unwrap();
+        let bytes = self.db_wrapper.get_pinned(&key, |raw| raw.to_vec())?;
+        Ok(match bytes {
+            Some(bytes) => RecordPayload::Cbor(bytes),
+            None => RecordPayload::Payload(Default::default()),
+        })
+    }
+
     fn delete(
         &mut self,
         point_id: PointOffsetType,
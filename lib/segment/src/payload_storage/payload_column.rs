@@ -0,0 +1,125 @@
+use std::sync::Arc;
+
+use parking_lot::RwLock;
+use rocksdb::DB;
+
+use crate::common::rocksdb_wrapper::DatabaseColumnWrapper;
+use crate::common::utils::copy_value_by_json_path;
+use crate::common::Flusher;
+use crate::entry::entry_point::{OperationError, OperationResult};
+use crate::types::{Payload, PayloadKeyType, PayloadKeyTypeRef, PointOffsetType};
+
+/// Values of a single payload field, stored separately from the whole payloads of points.
+///
+/// Each point is stored as a projection of its payload onto the field, so conditions on the field
+/// are checked against the projection in the same way as against the whole payload,
+/// while only the values of the field are read from disk and deserialized.
+pub struct PayloadColumn {
+    field: PayloadKeyType,
+    db_wrapper: DatabaseColumnWrapper,
+}
+
+impl PayloadColumn {
+    pub fn new(db: Arc<RwLock<DB>>, field: PayloadKeyTypeRef) -> Self {
+        let db_wrapper = DatabaseColumnWrapper::new(db, &Self::storage_cf_name(field));
+        Self {
+            field: field.to_owned(),
+            db_wrapper,
+        }
+    }
+
+    fn storage_cf_name(field: &str) -> String {
+        format!("{field}_column")
+    }
+
+    pub fn exists(&self) -> OperationResult<bool> {
+        self.db_wrapper.has_column_family()
+    }
+
+    pub fn recreate(&self) -> OperationResult<()> {
+        self.db_wrapper.recreate_column_family()
+    }
+
+    pub fn clear(&self) -> OperationResult<()> {
+        self.db_wrapper.remove_column_family()
+    }
+
+    /// Projection of the whole payload onto the field of the column
+    pub fn project(&self, payload: &Payload) -> Payload {
+        let mut projection = serde_json::Map::new();
+        copy_value_by_json_path(&self.field, &payload.0, &mut projection);
+        projection.into()
+    }
+
+    /// Store values of the field, taken from the whole payload of the point
+    pub fn set(&self, point_id: PointOffsetType, payload: &Payload) -> OperationResult<()> {
+        self.set_projection(point_id, &self.project(payload))
+    }
+
+    pub fn set_projection(
+        &self,
+        point_id: PointOffsetType,
+        projection: &Payload,
+    ) -> OperationResult<()> {
+        if projection.is_empty() {
+            return self.remove(point_id);
+        }
+        self.db_wrapper.put(
+            serde_cbor::to_vec(&point_id).unwrap(),
+            serde_cbor::to_vec(projection).unwrap(),
+        )
+    }
+
+    pub fn remove(&self, point_id: PointOffsetType) -> OperationResult<()> {
+        self.db_wrapper
+            .remove(serde_cbor::to_vec(&point_id).unwrap())
+    }
+
+    /// Projection of the payload of the point onto the field, empty if the point has no values
+    pub fn get(&self, point_id: PointOffsetType) -> OperationResult<Payload> {
+        let key = serde_cbor::to_vec(&point_id).unwrap();
+        let projection = self
+            .db_wrapper
+            .get_pinned(&key, |raw| serde_cbor::from_slice(raw))?
+            .transpose()
+            .map_err(OperationError::from)?;
+        Ok(projection.unwrap_or_default())
+    }
+
+    pub fn flusher(&self) -> Flusher {
+        self.db_wrapper.flusher()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::Builder;
+
+    use super::*;
+    use crate::common::rocksdb_wrapper::{open_db, DB_VECTOR_CF};
+
+    #[test]
+    fn test_payload_column() {
+        let dir = Builder::new().prefix("storage_dir").tempdir().unwrap();
+        let db = open_db(dir.path(), &[DB_VECTOR_CF]).unwrap();
+
+        let column = PayloadColumn::new(db, "location.city");
+        assert!(!column.exists().unwrap());
+        column.recreate().unwrap();
+
+        let payload: Payload = serde_json::from_str(
+            r#"{ "name": "John Doe", "location": { "city": "Berlin", "country": "Germany" } }"#,
+        )
+        .unwrap();
+        column.set(0, &payload).unwrap();
+        column.set(1, &Payload::default()).unwrap();
+
+        let expected: Payload =
+            serde_json::from_str(r#"{ "location": { "city": "Berlin" } }"#).unwrap();
+        assert_eq!(column.get(0).unwrap(), expected);
+        assert_eq!(column.get(1).unwrap(), Payload::default());
+
+        column.remove(0).unwrap();
+        assert_eq!(column.get(0).unwrap(), Payload::default());
+    }
+}
@@ -2,7 +2,7 @@ use serde_json::Value;
 
 use crate::common::Flusher;
 use crate::entry::entry_point::OperationResult;
-use crate::types::{Filter, Payload, PayloadKeyTypeRef, PointOffsetType};
+use crate::types::{Filter, Payload, PayloadKeyTypeRef, PointOffsetType, RecordPayload};
 
 /// Trait for payload data storage. Should allow filter checks
 pub trait PayloadStorage {
@@ -19,6 +19,11 @@ pub trait PayloadStorage {
     /// Get payload for point
     fn payload(&self, point_id: PointOffsetType) -> OperationResult<Payload>;
 
+    /// Get payload for point in the stored form, if the storage keeps it serialized
+    fn raw_payload(&self, point_id: PointOffsetType) -> OperationResult<RecordPayload> {
+        self.payload(point_id).map(RecordPayload::from)
+    }
+
     /// Delete payload by key
     fn delete(
         &mut self,
@@ -6,7 +6,7 @@ use crate::payload_storage::in_memory_payload_storage::InMemoryPayloadStorage;
 use crate::payload_storage::on_disk_payload_storage::OnDiskPayloadStorage;
 use crate::payload_storage::simple_payload_storage::SimplePayloadStorage;
 use crate::payload_storage::PayloadStorage;
-use crate::types::{Payload, PayloadKeyTypeRef, PointOffsetType};
+use crate::types::{Payload, PayloadKeyTypeRef, PointOffsetType, RecordPayload};
 
 pub enum PayloadStorageEnum {
     InMemoryPayloadStorage(InMemoryPayloadStorage),
@@ -62,6 +62,14 @@ impl PayloadStorage for PayloadStorageEnum {
         }
     }
 
+    fn raw_payload(&self, point_id: PointOffsetType) -> OperationResult<RecordPayload> {
+        match self {
+            PayloadStorageEnum::InMemoryPayloadStorage(s) => s.raw_payload(point_id),
+            PayloadStorageEnum::SimplePayloadStorage(s) => s.raw_payload(point_id),
+            PayloadStorageEnum::OnDiskPayloadStorage(s) => s.raw_payload(point_id),
+        }
+    }
+
     fn delete(
         &mut self,
         point_id: PointOffsetType,
@@ -188,4 +196,27 @@ mod tests {
             eprintln!("res = {res:#?}");
         }
     }
+
+    #[test]
+    fn test_on_disk_raw_payload() {
+        let dir = Builder::new().prefix("storage_dir").tempdir().unwrap();
+        let db = open_db(dir.path(), &[DB_VECTOR_CF]).unwrap();
+
+        let mut storage: PayloadStorageEnum = OnDiskPayloadStorage::open(db).unwrap().into();
+        let payload: Payload =
+            serde_json::from_str(r#"{ "name": "John Doe", "tags": ["a", "b"], "age": 52 }"#)
+                .unwrap();
+        storage.assign(100, &payload).unwrap();
+
+        let raw = storage.raw_payload(100).unwrap();
+        assert!(matches!(raw, RecordPayload::Cbor(_)));
+        assert_eq!(
+            serde_json::to_value(&raw).unwrap(),
+            serde_json::to_value(&payload).unwrap()
+        );
+        assert_eq!(raw.into_payload().unwrap(), payload);
+
+        let missing = storage.raw_payload(200).unwrap();
+        assert_eq!(missing.into_payload().unwrap(), Payload::default());
+    }
 }
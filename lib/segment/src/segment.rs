@@ -1,41 +1,58 @@
+use std::cell::Cell;
 use std::cmp::max;
 use std::collections::{HashMap, HashSet};
 use std::fs::{self, File};
 use std::path::{Path, PathBuf};
+use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
 use std::thread::JoinHandle;
 
 use atomic_refcell::AtomicRefCell;
 use parking_lot::{Mutex, RwLock};
 use rocksdb::DB;
+use serde::{Deserialize, Serialize};
 use tar::Builder;
 use uuid::Uuid;
+use validator::Validate;
 
 use crate::common::file_operations::{atomic_save_json, read_json};
+use crate::common::rocksdb_wrapper::db_memory_usage;
+use crate::common::utils::sampling_key;
 use crate::common::version::{StorageVersion, VERSION_FILE};
-use crate::common::{check_vector_name, check_vectors_set};
+use crate::common::{check_sparse_vector_name, check_vector_name, check_vectors_set};
+use crate::data_types::distinct::DistinctValues;
+use crate::data_types::histogram::{HistogramBuckets, ValueBounds};
 use crate::data_types::named_vectors::NamedVectors;
-use crate::data_types::vectors::VectorElementType;
+use crate::data_types::order_by::Direction;
+use crate::data_types::sparse_vector::{SparseVector, SparseVectors};
+use crate::data_types::vectors::{VectorElementType, VectorStruct};
 use crate::entry::entry_point::OperationError::TypeInferenceError;
 use crate::entry::entry_point::{
-    get_service_error, OperationError, OperationResult, SegmentEntry, SegmentFailedState,
+    check_process_stopped, get_service_error, OperationError, OperationResult, SegmentEntry,
+    SegmentFailedState,
 };
 use crate::id_tracker::IdTrackerSS;
-use crate::index::field_index::CardinalityEstimation;
+use crate::index::field_index::{CardinalityEstimation, FieldIndex};
+use crate::index::sparse_vector_index::SparseVectorIndex;
 use crate::index::struct_payload_index::StructPayloadIndex;
 use crate::index::{PayloadIndex, VectorIndex, VectorIndexEnum};
 use crate::spaces::tools::peek_top_smallest_iterable;
-use crate::telemetry::SegmentTelemetry;
+use crate::telemetry::{ReadFilteredTelemetry, SegmentTelemetry};
 use crate::types::{
-    Filter, Payload, PayloadFieldSchema, PayloadIndexInfo, PayloadKeyType, PayloadKeyTypeRef,
-    PayloadSchemaType, PointIdType, PointOffsetType, ScoredPoint, SearchParams, SegmentConfig,
-    SegmentInfo, SegmentState, SegmentType, SeqNumberType, WithPayload, WithVector,
+    Filter, GeoPoint, MemoryUsage, Payload, PayloadFieldSchema, PayloadIndexInfo, PayloadKeyType,
+    PayloadKeyTypeRef, PayloadSchemaType, PlannerHint, PointIdType, PointMetadata, PointOffsetType,
+    RecordPayload, ScoredPoint, SearchParams, SegmentConfig, SegmentInfo, SegmentState,
+    SegmentType, SegmentVerification, SeqNumberType, VectorSlice, WithPayload, WithVector,
 };
 use crate::utils;
+use crate::vector_storage::scoring_benchmark::{benchmark_scoring, ScoringMeasurement};
+use crate::vector_storage::sparse_vector_storage::SparseVectorStorage;
 use crate::vector_storage::{ScoredPointOffset, VectorStorage, VectorStorageEnum};
 
 pub const SEGMENT_STATE_FILE: &str = "segment.json";
 
+pub const SEGMENT_MANIFEST_FILE: &str = "manifest.json";
+
 const SNAPSHOT_PATH: &str = "snapshot";
 
 // Sub-directories of `SNAPSHOT_PATH`:
@@ -51,6 +68,36 @@ impl StorageVersion for SegmentVersion {
     }
 }
 
+/// Manifest of a fully built segment.
+///
+/// It is saved into the segment directory before the directory is moved into its final place,
+/// so a segment directory without manifest is incomplete.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
+pub struct SegmentManifest {
+    /// Version of the application, which has built the segment
+    pub version: String,
+}
+
+impl SegmentManifest {
+    pub fn current() -> Self {
+        SegmentManifest {
+            version: SegmentVersion::current(),
+        }
+    }
+
+    pub fn check_exists(path: &Path) -> bool {
+        path.join(SEGMENT_MANIFEST_FILE).exists()
+    }
+
+    pub fn load(path: &Path) -> OperationResult<Self> {
+        Ok(read_json(&path.join(SEGMENT_MANIFEST_FILE))?)
+    }
+
+    pub fn save(&self, path: &Path) -> OperationResult<()> {
+        Ok(atomic_save_json(&path.join(SEGMENT_MANIFEST_FILE), self)?)
+    }
+}
+
 /// Segment - an object which manages an independent group of points.
 ///
 /// - Provides storage, indexing and managing operations for points (vectors + payload)
@@ -68,6 +115,7 @@ pub struct Segment {
     /// Component for mapping external ids to internal and also keeping track of point versions
     pub id_tracker: Arc<AtomicRefCell<IdTrackerSS>>,
     pub vector_data: HashMap<String, VectorData>,
+    pub sparse_vector_data: HashMap<String, SparseVectorData>,
     pub payload_index: Arc<AtomicRefCell<StructPayloadIndex>>,
     /// Shows if it is possible to insert more points into this segment
     pub appendable_flag: bool,
@@ -79,6 +127,8 @@ pub struct Segment {
     pub error_status: Option<SegmentFailedState>,
     pub database: Arc<RwLock<DB>>,
     pub flush_thread: Mutex<Option<JoinHandle<OperationResult<SeqNumberType>>>>,
+    /// Statistics of the strategies, selected for reading filtered points
+    pub read_filtered_telemetry: Mutex<ReadFilteredTelemetry>,
 }
 
 pub struct VectorData {
@@ -86,6 +136,11 @@ pub struct VectorData {
     pub vector_storage: Arc<AtomicRefCell<VectorStorageEnum>>,
 }
 
+pub struct SparseVectorData {
+    pub vector_index: Arc<AtomicRefCell<SparseVectorIndex>>,
+    pub vector_storage: Arc<AtomicRefCell<SparseVectorStorage>>,
+}
+
 impl Segment {
     /// Change vector in-place.
     /// WARN: Available for appendable segments only
@@ -271,7 +326,7 @@ impl Segment {
                 vector_data
                     .vector_storage
                     .borrow()
-                    .get_vector(point_offset)
+                    .get_vector(point_offset)?
                     .to_vec(),
             ))
         } else {
@@ -279,22 +334,59 @@ impl Segment {
         }
     }
 
-    fn all_vectors_by_offset(
+    /// Vectors of the found point, requested by `with_vector`.
+    ///
+    /// Original values of quantized-only vectors are not stored, so they are omitted.
+    /// Returns `None` if none of the requested vectors are stored.
+    fn found_vectors_by_offset(
         &self,
+        with_vector: &WithVector,
         point_offset: PointOffsetType,
-    ) -> OperationResult<NamedVectors> {
-        let mut vectors = NamedVectors::default();
-        for (vector_name, vector_data) in &self.vector_data {
-            vectors.insert(
-                vector_name.clone(),
-                vector_data
-                    .vector_storage
-                    .borrow()
-                    .get_vector(point_offset)
-                    .to_vec(),
-            );
+    ) -> OperationResult<Option<VectorStruct>> {
+        let requested: Vec<(&str, Option<&VectorSlice>)> = match with_vector {
+            WithVector::Bool(false) => return Ok(None),
+            WithVector::Bool(true) => self
+                .vector_data
+                .keys()
+                .map(|vector_name| (vector_name.as_str(), None))
+                .collect(),
+            WithVector::Selector(vector_names) => vector_names
+                .iter()
+                .map(|vector_name| (vector_name.as_str(), None))
+                .collect(),
+            WithVector::Slices(slices) => slices
+                .iter()
+                .map(|slice| (slice.name.as_str(), Some(slice)))
+                .collect(),
+        };
+
+        let mut result = NamedVectors::default();
+        let mut omitted = false;
+        for (vector_name, slice) in requested {
+            let vector = match self.vector_by_offset(vector_name, point_offset) {
+                Ok(Some(vector)) => vector,
+                Ok(None) => {
+                    return Err(OperationError::service_error(format!(
+                        "Vector {vector_name} not found at offset {point_offset}"
+                    )))
+                }
+                Err(OperationError::VectorsNotStored { .. }) => {
+                    omitted = true;
+                    continue;
+                }
+                Err(err) => return Err(err),
+            };
+            let vector = match slice {
+                Some(slice) => slice.apply(vector),
+                None => vector,
+            };
+            result.insert(vector_name.to_owned(), vector);
+        }
+
+        if omitted && result.is_empty() {
+            return Ok(None);
         }
-        Ok(vectors)
+        Ok(Some(result.into()))
     }
 
     /// Retrieve payload by internal ID
@@ -315,6 +407,58 @@ impl Segment {
         payload_index.infer_payload_type(key)
     }
 
+    /// Build index of the payload field without modifying the segment, so it can be done
+    /// while the segment is read by other threads. The build progress is reported in segment info.
+    /// The index is applied to the segment by [`Segment::apply_field_index`].
+    ///
+    /// Returns `None` if there is nothing to build: the operation is already applied
+    /// or the field is already indexed.
+    pub fn build_field_index(
+        &self,
+        op_num: SeqNumberType,
+        key: PayloadKeyTypeRef,
+        field_type: Option<&PayloadFieldSchema>,
+    ) -> OperationResult<Option<(PayloadFieldSchema, Vec<FieldIndex>)>> {
+        if self.version.unwrap_or(0) > op_num {
+            return Ok(None);
+        }
+
+        let field_schema = match field_type {
+            Some(schema) => schema.clone(),
+            None => match self.infer_from_payload_data(key)? {
+                None => {
+                    return Err(TypeInferenceError {
+                        field_name: key.to_string(),
+                    })
+                }
+                Some(schema_type) => schema_type.into(),
+            },
+        };
+
+        let field_index = self
+            .payload_index
+            .borrow()
+            .build_index(key, &field_schema)?;
+        Ok(field_index.map(|field_index| (field_schema, field_index)))
+    }
+
+    /// Apply index of the payload field, built by [`Segment::build_field_index`]
+    pub fn apply_field_index(
+        &mut self,
+        op_num: SeqNumberType,
+        key: PayloadKeyTypeRef,
+        field_schema: PayloadFieldSchema,
+        field_index: Vec<FieldIndex>,
+    ) -> OperationResult<bool> {
+        self.handle_version_and_failure(op_num, None, |segment| {
+            segment
+                .payload_index
+                .borrow_mut()
+                .apply_index(key, field_schema, field_index)?;
+            Ok((true, None))
+        })
+    }
+
     pub fn restore_snapshot(snapshot_path: &Path, segment_id: &str) -> OperationResult<()> {
         let segment_path = snapshot_path.parent().unwrap().join(segment_id);
 
@@ -423,36 +567,11 @@ impl Segment {
                 })?;
                 let payload = if with_payload.enable {
                     let initial_payload = self.payload_by_offset(point_offset)?;
-                    let processed_payload = if let Some(i) = &with_payload.payload_selector {
-                        i.process(initial_payload)
-                    } else {
-                        initial_payload
-                    };
-                    Some(processed_payload)
+                    Some(with_payload.process(initial_payload))
                 } else {
                     None
                 };
-                let vector = match with_vector {
-                    WithVector::Bool(false) => None,
-                    WithVector::Bool(true) => {
-                        Some(self.all_vectors_by_offset(point_offset)?.into())
-                    }
-                    WithVector::Selector(vectors) => {
-                        let mut result = NamedVectors::default();
-                        for vector_name in vectors {
-                            let vector_opt = self.vector_by_offset(vector_name, point_offset)?;
-                            match vector_opt {
-                                None => {
-                                    return Err(OperationError::service_error(
-                                        "Vector {vector_name} not found at offset {point_offset}",
-                                    ))
-                                }
-                                Some(vector) => result.insert(vector_name.clone(), vector),
-                            }
-                        }
-                        Some(result.into())
-                    }
-                };
+                let vector = self.found_vectors_by_offset(with_vector, point_offset)?;
 
                 Ok(ScoredPoint {
                     id: point_id,
@@ -460,6 +579,8 @@ impl Segment {
                     score: scored_point_offset.score,
                     payload,
                     vector,
+                    highlights: None,
+                    geo_distance: None,
                 })
             })
             .collect()
@@ -470,12 +591,22 @@ impl Segment {
         offset: Option<PointIdType>,
         limit: Option<usize>,
         condition: &Filter,
+    ) -> Vec<PointIdType> {
+        self.filtered_read_by_index_with_checks(offset, limit, condition, None)
+    }
+
+    fn filtered_read_by_index_with_checks(
+        &self,
+        offset: Option<PointIdType>,
+        limit: Option<usize>,
+        condition: &Filter,
+        checks: Option<&Cell<usize>>,
     ) -> Vec<PointIdType> {
         let payload_index = self.payload_index.borrow();
         let id_tracker = self.id_tracker.borrow();
 
         let ids_iterator = payload_index
-            .query_points(condition)
+            .query_points_with_checks(condition, checks)
             .filter_map(|internal_id| {
                 let external_id = id_tracker.external_id(internal_id);
                 match external_id {
@@ -500,18 +631,157 @@ impl Segment {
         offset: Option<PointIdType>,
         limit: Option<usize>,
         condition: &Filter,
+    ) -> Vec<PointIdType> {
+        self.filtered_read_by_id_stream_with_checks(offset, limit, condition, None)
+    }
+
+    fn filtered_read_by_id_stream_with_checks(
+        &self,
+        offset: Option<PointIdType>,
+        limit: Option<usize>,
+        condition: &Filter,
+        checks: Option<&Cell<usize>>,
     ) -> Vec<PointIdType> {
         let payload_index = self.payload_index.borrow();
         let filter_context = payload_index.filter_context(condition);
         self.id_tracker
             .borrow()
             .iter_from(offset)
-            .filter(move |(_, internal_id)| filter_context.check(*internal_id))
+            .filter(move |(_, internal_id)| {
+                if let Some(checks) = checks {
+                    checks.set(checks.get() + 1);
+                }
+                filter_context.check(*internal_id)
+            })
             .map(|(external_id, _)| external_id)
             .take(limit.unwrap_or(usize::MAX))
             .collect()
     }
 
+    /// Select the reading strategy by the expected number of filter checks
+    fn filtered_read_by_estimation(
+        &self,
+        offset: Option<PointIdType>,
+        limit: Option<usize>,
+        condition: &Filter,
+    ) -> Vec<PointIdType> {
+        let query_cardinality = {
+            let payload_index = self.payload_index.borrow();
+            payload_index.estimate_cardinality(condition)
+        };
+
+        // Calculate expected number of condition checks required for
+        // this scroll request with is stream strategy.
+        // Example:
+        //  - cardinality = 1000
+        //  - limit = 10
+        //  - total = 10000
+        //  - point filter prob = 1000 / 10000 = 0.1
+        //  - expected_checks = 10 / 0.1  = 100
+        //  -------------------------------
+        //  - cardinality = 10
+        //  - limit = 10
+        //  - total = 10000
+        //  - point filter prob = 10 / 10000 = 0.001
+        //  - expected_checks = 10 / 0.001  = 10000
+
+        let total_points = self.points_count() + 1 /* + 1 for division-by-zero */;
+        // Expected number of successful checks per point
+        let check_probability =
+            (query_cardinality.exp as f64 + 1.0/* protect from zero */) / total_points as f64;
+        let exp_stream_checks = (limit.unwrap_or(total_points) as f64 / check_probability) as usize;
+
+        // Assume it would require about `query cardinality` checks.
+        // We are interested in approximate number of checks, so we can
+        // use `query cardinality` as a starting point.
+        let exp_index_checks = query_cardinality.max;
+
+        let checks = Cell::new(0);
+        if exp_stream_checks > exp_index_checks {
+            let points =
+                self.filtered_read_by_index_with_checks(offset, limit, condition, Some(&checks));
+            self.read_filtered_telemetry
+                .lock()
+                .index
+                .add(exp_index_checks, checks.get());
+            points
+        } else {
+            let points = self.filtered_read_by_id_stream_with_checks(
+                offset,
+                limit,
+                condition,
+                Some(&checks),
+            );
+            self.read_filtered_telemetry
+                .lock()
+                .stream
+                .add(exp_stream_checks, checks.get());
+            points
+        }
+    }
+
+    /// Cross-check the id tracker, vector storages and payload storage of the segment,
+    /// and read a random sample of `samples` vectors, without modifying the segment.
+    pub fn verify(&self, samples: usize) -> OperationResult<SegmentVerification> {
+        let id_tracker = self.id_tracker.borrow();
+        let mut verification = SegmentVerification {
+            points_count: id_tracker.points_count(),
+            ..Default::default()
+        };
+
+        let vector_storages: Vec<_> = self
+            .vector_data
+            .values()
+            .map(|vector_data| vector_data.vector_storage.borrow())
+            .collect();
+
+        for internal_id in id_tracker.iter_ids() {
+            if id_tracker.external_id(internal_id).is_none() {
+                verification.unmapped_points += 1;
+            }
+            let has_vectors = vector_storages
+                .iter()
+                .all(|storage| (internal_id as usize) < storage.total_vector_count());
+            if !has_vectors {
+                verification.missing_vectors += 1;
+            }
+        }
+
+        self.payload_index
+            .borrow()
+            .iter_payloads(|internal_id, _payload| {
+                if id_tracker.is_deleted(internal_id)
+                    || id_tracker.external_id(internal_id).is_none()
+                {
+                    verification.dangling_payloads += 1;
+                }
+                Ok(true)
+            })?;
+
+        // Sampling never ends if there are no points
+        if verification.points_count > 0 {
+            for internal_id in id_tracker.sample_ids().take(samples) {
+                for storage in &vector_storages {
+                    // Original vectors of quantized-only storage can't be read
+                    if storage.is_quantized_only()
+                        || internal_id as usize >= storage.total_vector_count()
+                    {
+                        continue;
+                    }
+                    let vector = storage.get_vector(internal_id)?;
+                    verification.sampled_vectors += 1;
+                    if vector.len() != storage.vector_dim()
+                        || !vector.iter().all(|value| value.is_finite())
+                    {
+                        verification.corrupted_vectors += 1;
+                    }
+                }
+            }
+        }
+
+        Ok(verification)
+    }
+
     /// Check consistency of the segment's data and repair it if possible.
     pub fn check_consistency_and_repair(&mut self) -> OperationResult<()> {
         let mut internal_ids_to_delete = HashSet::new();
@@ -580,11 +850,13 @@ impl SegmentEntry for Segment {
             });
         }
 
-        let internal_result =
-            &vector_data
-                .vector_index
-                .borrow()
-                .search(&[vector], filter, top, params)[0];
+        let internal_result = &vector_data.vector_index.borrow().search(
+            &[vector],
+            filter,
+            top,
+            params,
+            &AtomicBool::new(false),
+        )[0];
 
         self.process_search_result(internal_result, with_payload, with_vector)
     }
@@ -598,6 +870,7 @@ impl SegmentEntry for Segment {
         filter: Option<&Filter>,
         top: usize,
         params: Option<&SearchParams>,
+        is_stopped: &AtomicBool,
     ) -> OperationResult<Vec<Vec<ScoredPoint>>> {
         check_vector_name(vector_name, &self.segment_config)?;
         let vector_data = &self.vector_data[vector_name];
@@ -614,7 +887,8 @@ impl SegmentEntry for Segment {
         let internal_results = vector_data
             .vector_index
             .borrow()
-            .search(vectors, filter, top, params);
+            .search(vectors, filter, top, params, is_stopped);
+        check_process_stopped(is_stopped)?;
 
         let res = internal_results
             .iter()
@@ -626,6 +900,60 @@ impl SegmentEntry for Segment {
         res
     }
 
+    fn search_sparse(
+        &self,
+        vector_name: &str,
+        vector: &SparseVector,
+        with_payload: &WithPayload,
+        with_vector: &WithVector,
+        filter: Option<&Filter>,
+        top: usize,
+    ) -> OperationResult<Vec<ScoredPoint>> {
+        check_sparse_vector_name(vector_name, &self.segment_config)?;
+        let internal_result = self.sparse_vector_data[vector_name]
+            .vector_index
+            .borrow()
+            .search(vector, filter, top);
+
+        self.process_search_result(&internal_result, with_payload, with_vector)
+    }
+
+    fn update_sparse_vectors(
+        &mut self,
+        op_num: SeqNumberType,
+        point_id: PointIdType,
+        vectors: &SparseVectors,
+    ) -> OperationResult<bool> {
+        for (vector_name, vector) in vectors {
+            check_sparse_vector_name(vector_name, &self.segment_config)?;
+            vector
+                .validate()
+                .map_err(|err| OperationError::WrongSparseVector {
+                    description: err.to_string(),
+                })?;
+        }
+        let internal_id = self.id_tracker.borrow().internal_id(point_id);
+        self.handle_version_and_failure(op_num, internal_id, |segment| match internal_id {
+            Some(internal_id) => {
+                for (vector_name, sparse_vector_data) in &segment.sparse_vector_data {
+                    let new_vector = vectors.get(vector_name);
+                    let mut vector_storage = sparse_vector_data.vector_storage.borrow_mut();
+                    let old_vector = vector_storage.get_vector(internal_id).cloned();
+                    vector_storage.insert_vector(internal_id, new_vector)?;
+                    sparse_vector_data.vector_index.borrow_mut().update(
+                        internal_id,
+                        old_vector.as_ref(),
+                        new_vector,
+                    );
+                }
+                Ok((true, Some(internal_id)))
+            }
+            None => Err(OperationError::PointIdError {
+                missed_point_id: point_id,
+            }),
+        })
+    }
+
     fn upsert_vector(
         &mut self,
         op_num: SeqNumberType,
@@ -780,6 +1108,27 @@ impl SegmentEntry for Segment {
         })
     }
 
+    fn set_point_metadata(
+        &mut self,
+        op_num: SeqNumberType,
+        point_id: PointIdType,
+        metadata: &PointMetadata,
+    ) -> OperationResult<bool> {
+        let internal_id = self.id_tracker.borrow().internal_id(point_id);
+        self.handle_version_and_failure(op_num, internal_id, |segment| match internal_id {
+            Some(internal_id) => {
+                segment
+                    .id_tracker
+                    .borrow_mut()
+                    .set_point_metadata(internal_id, metadata.clone())?;
+                Ok((true, Some(internal_id)))
+            }
+            None => Err(OperationError::PointIdError {
+                missed_point_id: point_id,
+            }),
+        })
+    }
+
     fn vector(
         &self,
         vector_name: &str,
@@ -805,11 +1154,41 @@ impl SegmentEntry for Segment {
         Ok(result)
     }
 
+    fn sparse_vectors(&self, point_id: PointIdType) -> OperationResult<SparseVectors> {
+        let internal_id = self.lookup_internal_id(point_id)?;
+        let mut result = SparseVectors::new();
+        for (vector_name, sparse_vector_data) in &self.sparse_vector_data {
+            if let Some(vector) = sparse_vector_data
+                .vector_storage
+                .borrow()
+                .get_vector(internal_id)
+            {
+                result.insert(vector_name.clone(), vector.clone());
+            }
+        }
+        Ok(result)
+    }
+
     fn payload(&self, point_id: PointIdType) -> OperationResult<Payload> {
         let internal_id = self.lookup_internal_id(point_id)?;
         self.payload_by_offset(internal_id)
     }
 
+    fn raw_payload(&self, point_id: PointIdType) -> OperationResult<RecordPayload> {
+        let internal_id = self.lookup_internal_id(point_id)?;
+        self.payload_index.borrow().raw_payload(internal_id)
+    }
+
+    fn point_metadata(&self, point_id: PointIdType) -> OperationResult<PointMetadata> {
+        let internal_id = self.lookup_internal_id(point_id)?;
+        Ok(self
+            .id_tracker
+            .borrow()
+            .point_metadata(internal_id)
+            .cloned()
+            .unwrap_or_default())
+    }
+
     fn iter_points(&self) -> Box<dyn Iterator<Item = PointIdType> + '_> {
         // Sorry for that, but I didn't find any way easier.
         // If you try simply return iterator - it won't work because AtomicRef should exist
@@ -823,6 +1202,7 @@ impl SegmentEntry for Segment {
         offset: Option<PointIdType>,
         limit: Option<usize>,
         filter: Option<&'a Filter>,
+        planner_hint: Option<PlannerHint>,
     ) -> Vec<PointIdType> {
         match filter {
             None => self
@@ -832,50 +1212,55 @@ impl SegmentEntry for Segment {
                 .map(|x| x.0)
                 .take(limit.unwrap_or(usize::MAX))
                 .collect(),
-            Some(condition) => {
-                let query_cardinality = {
-                    let payload_index = self.payload_index.borrow();
-                    payload_index.estimate_cardinality(condition)
-                };
-
-                // ToDo: Add telemetry for this heuristics
-
-                // Calculate expected number of condition checks required for
-                // this scroll request with is stream strategy.
-                // Example:
-                //  - cardinality = 1000
-                //  - limit = 10
-                //  - total = 10000
-                //  - point filter prob = 1000 / 10000 = 0.1
-                //  - expected_checks = 10 / 0.1  = 100
-                //  -------------------------------
-                //  - cardinality = 10
-                //  - limit = 10
-                //  - total = 10000
-                //  - point filter prob = 10 / 10000 = 0.001
-                //  - expected_checks = 10 / 0.001  = 10000
-
-                let total_points = self.points_count() + 1 /* + 1 for division-by-zero */;
-                // Expected number of successful checks per point
-                let check_probability = (query_cardinality.exp as f64 + 1.0/* protect from zero */)
-                    / total_points as f64;
-                let exp_stream_checks =
-                    (limit.unwrap_or(total_points) as f64 / check_probability) as usize;
-
-                // Assume it would require about `query cardinality` checks.
-                // We are interested in approximate number of checks, so we can
-                // use `query cardinality` as a starting point.
-                let exp_index_checks = query_cardinality.max;
-
-                if exp_stream_checks > exp_index_checks {
+            Some(condition) => match planner_hint {
+                Some(PlannerHint::IndexScan) => {
                     self.filtered_read_by_index(offset, limit, condition)
-                } else {
+                }
+                Some(PlannerHint::StreamScan) => {
                     self.filtered_read_by_id_stream(offset, limit, condition)
                 }
-            }
+                Some(PlannerHint::PlainSearch) | None => {
+                    self.filtered_read_by_estimation(offset, limit, condition)
+                }
+            },
         }
     }
 
+    fn sample_filtered<'a>(
+        &'a self,
+        limit: usize,
+        filter: Option<&'a Filter>,
+        seed: u64,
+    ) -> Vec<PointIdType> {
+        let id_tracker = self.id_tracker.borrow();
+        let keyed_points = id_tracker
+            .iter_from(None)
+            .map(|(external_id, internal_id)| {
+                (sampling_key(seed, external_id), external_id, internal_id)
+            });
+
+        let sample = match filter {
+            None => peek_top_smallest_iterable(
+                keyed_points.map(|(key, external_id, _)| (key, external_id)),
+                limit,
+            ),
+            Some(condition) => {
+                let payload_index = self.payload_index.borrow();
+                let filter_context = payload_index.filter_context(condition);
+                peek_top_smallest_iterable(
+                    keyed_points
+                        .filter(|(_, _, internal_id)| filter_context.check(*internal_id))
+                        .map(|(key, external_id, _)| (key, external_id)),
+                    limit,
+                )
+            }
+        };
+        sample
+            .into_iter()
+            .map(|(_, external_id)| external_id)
+            .collect()
+    }
+
     fn read_range(&self, from: Option<PointIdType>, to: Option<PointIdType>) -> Vec<PointIdType> {
         let id_tracker = self.id_tracker.borrow();
         let iterator = id_tracker.iter_from(from).map(|x| x.0);
@@ -893,6 +1278,83 @@ impl SegmentEntry for Segment {
         self.id_tracker.borrow().points_count()
     }
 
+    fn term_counts<'a>(
+        &'a self,
+        key: PayloadKeyTypeRef,
+        filter: Option<&'a Filter>,
+    ) -> Option<HashMap<String, usize>> {
+        self.payload_index.borrow().term_counts(key, filter)
+    }
+
+    fn distinct_values<'a>(
+        &'a self,
+        key: PayloadKeyTypeRef,
+        filter: Option<&'a Filter>,
+    ) -> Option<DistinctValues> {
+        self.payload_index.borrow().distinct_values(key, filter)
+    }
+
+    fn numeric_bounds<'a>(
+        &'a self,
+        key: PayloadKeyTypeRef,
+        filter: Option<&'a Filter>,
+    ) -> Option<Option<ValueBounds>> {
+        self.payload_index.borrow().numeric_bounds(key, filter)
+    }
+
+    fn numeric_histogram<'a>(
+        &'a self,
+        key: PayloadKeyTypeRef,
+        buckets: &HistogramBuckets,
+        filter: Option<&'a Filter>,
+    ) -> Option<Vec<usize>> {
+        self.payload_index
+            .borrow()
+            .numeric_histogram(key, buckets, filter)
+    }
+
+    fn nearest_points<'a>(
+        &'a self,
+        key: PayloadKeyTypeRef,
+        origin: &GeoPoint,
+        limit: usize,
+        filter: Option<&'a Filter>,
+    ) -> Option<Vec<(PointIdType, f64)>> {
+        let nearest = self
+            .payload_index
+            .borrow()
+            .nearest_points(key, origin, limit, filter)?;
+        let id_tracker = self.id_tracker.borrow();
+        let nearest = nearest
+            .into_iter()
+            .filter_map(|(internal_id, distance)| {
+                Some((id_tracker.external_id(internal_id)?, distance))
+            })
+            .collect();
+        Some(nearest)
+    }
+
+    fn ordered_points<'a>(
+        &'a self,
+        key: PayloadKeyTypeRef,
+        direction: Direction,
+        start_from: Option<f64>,
+        offset: Option<PointIdType>,
+        limit: usize,
+        filter: Option<&'a Filter>,
+    ) -> Option<Vec<(PointIdType, f64)>> {
+        let ordered = self
+            .payload_index
+            .borrow()
+            .ordered_points(key, direction, start_from, offset, limit, filter)?;
+        let id_tracker = self.id_tracker.borrow();
+        let ordered = ordered
+            .into_iter()
+            .filter_map(|(internal_id, value)| Some((id_tracker.external_id(internal_id)?, value)))
+            .collect();
+        Some(ordered)
+    }
+
     fn estimate_points_count<'a>(&'a self, filter: Option<&'a Filter>) -> CardinalityEstimation {
         match filter {
             None => {
@@ -921,7 +1383,7 @@ impl SegmentEntry for Segment {
 
     fn info(&self) -> SegmentInfo {
         let payload_index = self.payload_index.borrow();
-        let schema = payload_index
+        let mut schema: HashMap<_, _> = payload_index
             .indexed_fields()
             .into_iter()
             .map(|(key, index_schema)| {
@@ -929,6 +1391,8 @@ impl SegmentEntry for Segment {
                 (key, PayloadIndexInfo::new(index_schema, points_count))
             })
             .collect();
+        // Fields, which are being indexed or failed to index
+        schema.extend(payload_index.index_builds_info());
 
         SegmentInfo {
             segment_type: self.segment_type,
@@ -975,6 +1439,11 @@ impl SegmentEntry for Segment {
             .vector_data
             .values()
             .map(|v| v.vector_storage.borrow().flusher())
+            .chain(
+                self.sparse_vector_data
+                    .values()
+                    .map(|v| v.vector_storage.borrow().flusher()),
+            )
             .collect();
         let state = self.get_state();
         let current_path = self.current_path.clone();
@@ -1106,26 +1575,16 @@ impl SegmentEntry for Segment {
         key: PayloadKeyTypeRef,
         field_type: Option<&PayloadFieldSchema>,
     ) -> OperationResult<bool> {
-        self.handle_version_and_failure(op_num, None, |segment| match field_type {
-            Some(schema) => {
+        self.handle_version_and_failure(op_num, None, |segment| {
+            if let Some((field_schema, field_index)) =
+                segment.build_field_index(op_num, key, field_type)?
+            {
                 segment
                     .payload_index
                     .borrow_mut()
-                    .set_indexed(key, schema.clone())?;
-                Ok((true, None))
+                    .apply_index(key, field_schema, field_index)?;
             }
-            None => match segment.infer_from_payload_data(key)? {
-                None => Err(TypeInferenceError {
-                    field_name: key.to_string(),
-                }),
-                Some(schema_type) => {
-                    segment
-                        .payload_index
-                        .borrow_mut()
-                        .set_indexed(key, schema_type.into())?;
-                    Ok((true, None))
-                }
-            },
+            Ok((true, None))
         })
     }
 
@@ -1143,7 +1602,7 @@ impl SegmentEntry for Segment {
         filter: &'a Filter,
     ) -> OperationResult<usize> {
         let mut deleted_points = 0;
-        for point_id in self.read_filtered(None, None, Some(filter)) {
+        for point_id in self.read_filtered(None, None, Some(filter), None) {
             deleted_points += self.delete_point(op_num, point_id)? as usize;
         }
 
@@ -1264,6 +1723,12 @@ impl SegmentEntry for Segment {
             &files.join(VERSION_FILE),
         )?;
 
+        utils::tar::append_file(
+            &mut builder,
+            &self.current_path.join(SEGMENT_MANIFEST_FILE),
+            &files.join(SEGMENT_MANIFEST_FILE),
+        )?;
+
         builder.finish()?;
 
         // remove tmp directory in background
@@ -1297,8 +1762,43 @@ impl SegmentEntry for Segment {
             config: self.config(),
             vector_index_searches,
             payload_field_indices: self.payload_index.borrow().get_telemetry_data(),
+            read_filtered: self.read_filtered_telemetry.lock().clone(),
         }
     }
+
+    fn memory_usage(&self) -> MemoryUsage {
+        let mut usage = MemoryUsage {
+            payload_indexes: self.payload_index.borrow().ram_usage_bytes(),
+            id_trackers: self.id_tracker.borrow().ram_usage_bytes(),
+            rocksdb: db_memory_usage(&self.database.read()),
+            ..Default::default()
+        };
+        for vector_data in self.vector_data.values() {
+            let vector_storage = vector_data.vector_storage.borrow();
+            usage.vector_storages += vector_storage.ram_usage_bytes();
+            usage.quantized_vectors += vector_storage
+                .quantized_storage()
+                .map(|quantized| quantized.ram_usage_bytes())
+                .unwrap_or(0);
+            usage.hnsw_links += vector_data.vector_index.borrow().ram_usage_bytes();
+        }
+        usage
+    }
+
+    fn benchmark_scoring(
+        &self,
+        vector_name: &str,
+        points_limit: usize,
+    ) -> OperationResult<Vec<ScoringMeasurement>> {
+        check_vector_name(vector_name, &self.segment_config)?;
+        let vector_storage = self.vector_data[vector_name].vector_storage.borrow();
+        let id_tracker = self.id_tracker.borrow();
+        Ok(benchmark_scoring(
+            &vector_storage,
+            id_tracker.deleted_bitvec(),
+            points_limit,
+        ))
+    }
 }
 
 impl Drop for Segment {
@@ -1315,7 +1815,9 @@ mod tests {
     use crate::data_types::vectors::{only_default_vector, DEFAULT_VECTOR_NAME};
     use crate::entry::entry_point::OperationError::PointIdError;
     use crate::segment_constructor::{build_segment, load_segment};
-    use crate::types::{Distance, Indexes, SegmentConfig, StorageType, VectorDataConfig};
+    use crate::types::{
+        Distance, Indexes, PayloadIndexStatus, SegmentConfig, StorageType, VectorDataConfig,
+    };
 
     // no longer valid since users are now allowed to store arbitrary json objects.
     // TODO(gvelo): add tests for invalid payload types on indexed fields.
@@ -1404,6 +1906,7 @@ mod tests {
                 None,
                 10,
                 None,
+                &false.into(),
             )
             .unwrap();
         eprintln!("search_batch_result = {search_batch_result:#?}");
@@ -1641,6 +2144,7 @@ mod tests {
             storage_type: StorageType::InMemory,
             payload_storage_type: Default::default(),
             quantization_config: None,
+            sparse_vector_data: Default::default(),
         };
         let mut segment = build_segment(dir.path(), &config).unwrap();
 
@@ -1719,4 +2223,359 @@ mod tests {
             Ok(None)
         );
     }
+
+    #[test]
+    fn test_sample_filtered() {
+        let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+        let dim = 4;
+        let config = SegmentConfig {
+            vector_data: HashMap::from([(
+                DEFAULT_VECTOR_NAME.to_owned(),
+                VectorDataConfig {
+                    size: dim,
+                    distance: Distance::Dot,
+                    hnsw_config: None,
+                    quantization_config: None,
+                },
+            )]),
+            index: Indexes::Plain {},
+            storage_type: StorageType::InMemory,
+            payload_storage_type: Default::default(),
+            quantization_config: None,
+            sparse_vector_data: Default::default(),
+        };
+        let mut segment = build_segment(dir.path(), &config).unwrap();
+
+        for i in 0..100 {
+            segment
+                .upsert_vector(i, i.into(), &only_default_vector(&[1.0, 0.0, 1.0, 1.0]))
+                .unwrap();
+        }
+
+        let sample = segment.sample_filtered(10, None, 42);
+        assert_eq!(sample.len(), 10);
+        assert_eq!(sample, segment.sample_filtered(10, None, 42));
+        assert_ne!(sample, segment.sample_filtered(10, None, 43));
+
+        // Smaller sample is a prefix of a larger one
+        assert_eq!(sample[..5], segment.sample_filtered(5, None, 42));
+
+        let keys = sample
+            .iter()
+            .map(|point_id| sampling_key(42, *point_id))
+            .collect::<Vec<_>>();
+        assert!(keys.windows(2).all(|pair| pair[0] <= pair[1]));
+    }
+
+    #[test]
+    fn test_read_filtered_telemetry() {
+        let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+        let dim = 4;
+        let config = SegmentConfig {
+            vector_data: HashMap::from([(
+                DEFAULT_VECTOR_NAME.to_owned(),
+                VectorDataConfig {
+                    size: dim,
+                    distance: Distance::Dot,
+                    hnsw_config: None,
+                    quantization_config: None,
+                },
+            )]),
+            index: Indexes::Plain {},
+            storage_type: StorageType::InMemory,
+            payload_storage_type: Default::default(),
+            quantization_config: None,
+            sparse_vector_data: Default::default(),
+        };
+        let mut segment = build_segment(dir.path(), &config).unwrap();
+
+        for i in 0..100 {
+            segment
+                .upsert_vector(i, i.into(), &only_default_vector(&[1.0, 0.0, 1.0, 1.0]))
+                .unwrap();
+            let payload: Payload = serde_json::json!({ "num": i % 10 }).into();
+            segment.set_full_payload(i, i.into(), &payload).unwrap();
+        }
+
+        let filter: Filter =
+            serde_json::from_str(r#"{ "must": [{ "key": "num", "match": { "value": 3 } }] }"#)
+                .unwrap();
+
+        let points = segment.read_filtered(None, Some(5), Some(&filter), None);
+        assert_eq!(points.len(), 5);
+
+        let telemetry = segment.get_telemetry_data().read_filtered;
+        assert_eq!(telemetry.index.count + telemetry.stream.count, 1);
+        assert!(telemetry.index.actual_checks + telemetry.stream.actual_checks >= 5);
+
+        // Reads with a planner hint are not accounted
+        segment.read_filtered(None, Some(5), Some(&filter), Some(PlannerHint::IndexScan));
+        let telemetry = segment.get_telemetry_data().read_filtered;
+        assert_eq!(telemetry.index.count + telemetry.stream.count, 1);
+    }
+
+    #[test]
+    fn test_verify() {
+        let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+        let config = SegmentConfig {
+            vector_data: HashMap::from([(
+                DEFAULT_VECTOR_NAME.to_owned(),
+                VectorDataConfig {
+                    size: 4,
+                    distance: Distance::Dot,
+                    hnsw_config: None,
+                    quantization_config: None,
+                },
+            )]),
+            index: Indexes::Plain {},
+            storage_type: StorageType::InMemory,
+            payload_storage_type: Default::default(),
+            quantization_config: None,
+            sparse_vector_data: Default::default(),
+        };
+        let mut segment = build_segment(dir.path(), &config).unwrap();
+
+        for i in 0..10 {
+            segment
+                .upsert_vector(i, i.into(), &only_default_vector(&[1.0, 0.0, 1.0, 1.0]))
+                .unwrap();
+            let payload: Payload = serde_json::json!({ "num": i }).into();
+            segment.set_full_payload(i, i.into(), &payload).unwrap();
+        }
+        segment.delete_point(11, 0.into()).unwrap();
+
+        let verification = segment.verify(5).unwrap();
+        assert!(verification.is_ok(), "{:?}", verification.problems());
+        assert_eq!(verification.points_count, 9);
+        assert_eq!(verification.sampled_vectors, 5);
+
+        // Payload is left, if the point is removed from the id tracker only
+        segment.id_tracker.borrow_mut().drop(1.into()).unwrap();
+        let verification = segment.verify(5).unwrap();
+        assert_eq!(verification.dangling_payloads, 1);
+        assert!(!verification.is_ok());
+    }
+
+    #[test]
+    fn test_memory_usage() {
+        let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+        let config = SegmentConfig {
+            vector_data: HashMap::from([(
+                DEFAULT_VECTOR_NAME.to_owned(),
+                VectorDataConfig {
+                    size: 4,
+                    distance: Distance::Dot,
+                    hnsw_config: None,
+                    quantization_config: None,
+                },
+            )]),
+            index: Indexes::Plain {},
+            storage_type: StorageType::InMemory,
+            payload_storage_type: Default::default(),
+            quantization_config: None,
+            sparse_vector_data: Default::default(),
+        };
+        let mut segment = build_segment(dir.path(), &config).unwrap();
+        let empty_usage = segment.memory_usage();
+
+        for i in 0..100 {
+            segment
+                .upsert_vector(i, i.into(), &only_default_vector(&[1.0, 0.0, 1.0, 1.0]))
+                .unwrap();
+        }
+
+        let usage = segment.memory_usage();
+        assert!(usage.vector_storages >= 100 * 4 * std::mem::size_of::<f32>());
+        assert!(usage.id_trackers > empty_usage.id_trackers);
+        assert_eq!(usage.hnsw_links, 0);
+        assert_eq!(usage.quantized_vectors, 0);
+        assert!(usage.total() >= usage.vector_storages + usage.id_trackers);
+    }
+
+    #[test]
+    fn test_sparse_vectors() {
+        let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+        let config = SegmentConfig {
+            vector_data: HashMap::from([(
+                DEFAULT_VECTOR_NAME.to_owned(),
+                VectorDataConfig {
+                    size: 2,
+                    distance: Distance::Dot,
+                    hnsw_config: None,
+                    quantization_config: None,
+                },
+            )]),
+            index: Indexes::Plain {},
+            storage_type: StorageType::InMemory,
+            payload_storage_type: Default::default(),
+            quantization_config: None,
+            sparse_vector_data: HashMap::from([("text".to_owned(), Default::default())]),
+        };
+        let mut segment = build_segment(dir.path(), &config).unwrap();
+
+        for i in 1..=3 {
+            segment
+                .upsert_vector(i, i.into(), &only_default_vector(&[1.0, 0.0]))
+                .unwrap();
+        }
+        let sparse = |indices: Vec<u32>, values: Vec<f32>| {
+            SparseVectors::from([("text".to_owned(), SparseVector::new(indices, values))])
+        };
+        segment
+            .update_sparse_vectors(4, 1.into(), &sparse(vec![1, 2], vec![1.0, 1.0]))
+            .unwrap();
+        segment
+            .update_sparse_vectors(5, 2.into(), &sparse(vec![2, 3], vec![2.0, 1.0]))
+            .unwrap();
+
+        // Sparse vectors can't be set for unknown names or points
+        let unknown_name = SparseVectors::from([("other".to_owned(), SparseVector::default())]);
+        assert!(segment
+            .update_sparse_vectors(6, 1.into(), &unknown_name)
+            .is_err());
+        assert!(segment
+            .update_sparse_vectors(6, 10.into(), &sparse(vec![1], vec![1.0]))
+            .is_err());
+        assert!(segment
+            .update_sparse_vectors(6, 1.into(), &sparse(vec![1, 1], vec![1.0, 1.0]))
+            .is_err());
+
+        let ids = |ids: &[u64]| -> Vec<PointIdType> { ids.iter().map(|&id| id.into()).collect() };
+        let query = SparseVector::new(vec![2], vec![1.0]);
+        let search = |segment: &Segment| -> Vec<PointIdType> {
+            segment
+                .search_sparse(
+                    "text",
+                    &query,
+                    &WithPayload::default(),
+                    &false.into(),
+                    None,
+                    10,
+                )
+                .unwrap()
+                .into_iter()
+                .map(|point| point.id)
+                .collect()
+        };
+        assert_eq!(search(&segment), ids(&[2, 1]));
+        assert!(segment.sparse_vectors(3.into()).unwrap().is_empty());
+
+        // Missing name removes the sparse vector of the point
+        segment
+            .update_sparse_vectors(7, 2.into(), &SparseVectors::new())
+            .unwrap();
+        assert_eq!(search(&segment), ids(&[1]));
+
+        segment.flush(true).unwrap();
+        let segment_path = segment.current_path.clone();
+        drop(segment);
+
+        let segment = load_segment(&segment_path).unwrap().unwrap();
+        assert_eq!(search(&segment), ids(&[1]));
+        assert_eq!(
+            segment.sparse_vectors(1.into()).unwrap(),
+            sparse(vec![1, 2], vec![1.0, 1.0])
+        );
+    }
+
+    #[test]
+    fn test_field_index_build_status() {
+        let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+        let config = SegmentConfig {
+            vector_data: HashMap::from([(
+                DEFAULT_VECTOR_NAME.to_owned(),
+                VectorDataConfig {
+                    size: 2,
+                    distance: Distance::Dot,
+                    hnsw_config: None,
+                    quantization_config: None,
+                },
+            )]),
+            index: Indexes::Plain {},
+            storage_type: StorageType::InMemory,
+            ..Default::default()
+        };
+        let mut segment = build_segment(dir.path(), &config).unwrap();
+
+        for i in 0..10 {
+            segment
+                .upsert_vector(i, i.into(), &only_default_vector(&[1.0, 1.0]))
+                .unwrap();
+            let payload: Payload = serde_json::from_str(r#"{"color": "red"}"#).unwrap();
+            segment.set_full_payload(i, i.into(), &payload).unwrap();
+        }
+
+        let schema: PayloadFieldSchema = PayloadSchemaType::Keyword.into();
+        let (field_schema, field_index) = segment
+            .build_field_index(10, "color", Some(&schema))
+            .unwrap()
+            .unwrap();
+
+        // The index is built, but not applied yet
+        let info = segment.info().index_schema["color"].clone();
+        assert_eq!(info.status, PayloadIndexStatus::Building);
+        assert_eq!(info.progress_percent, Some(100));
+
+        segment
+            .apply_field_index(10, "color", field_schema, field_index)
+            .unwrap();
+
+        let info = segment.info().index_schema["color"].clone();
+        assert_eq!(info.status, PayloadIndexStatus::Ready);
+        assert_eq!(info.progress_percent, None);
+        assert_eq!(info.points, 10);
+
+        // Nothing to build for the indexed field
+        assert!(segment
+            .build_field_index(11, "color", Some(&schema))
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_load_incomplete_segment() {
+        let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+        let config = SegmentConfig {
+            vector_data: HashMap::from([(
+                DEFAULT_VECTOR_NAME.to_owned(),
+                VectorDataConfig {
+                    size: 2,
+                    distance: Distance::Dot,
+                    hnsw_config: None,
+                    quantization_config: None,
+                },
+            )]),
+            index: Indexes::Plain {},
+            storage_type: StorageType::InMemory,
+            ..Default::default()
+        };
+        let segment = build_segment(dir.path(), &config).unwrap();
+        let segment_path = segment.current_path.clone();
+        drop(segment);
+
+        // Only committed segment is left in the directory
+        let entries: Vec<_> = fs::read_dir(dir.path()).unwrap().collect();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(
+            SegmentManifest::load(&segment_path).unwrap(),
+            SegmentManifest::current()
+        );
+
+        // Segment without manifest, but with version file, is built by older version
+        fs::remove_file(segment_path.join(SEGMENT_MANIFEST_FILE)).unwrap();
+        assert!(load_segment(&segment_path).unwrap().is_some());
+        assert!(SegmentManifest::check_exists(&segment_path));
+
+        // Crashed during build: not moved into place, or without any commit markers
+        let building_path = dir.path().join("segment.building");
+        fs::create_dir(&building_path).unwrap();
+        SegmentManifest::current().save(&building_path).unwrap();
+        let partial_path = dir.path().join("partial");
+        fs::create_dir(&partial_path).unwrap();
+
+        assert!(load_segment(&building_path).unwrap().is_none());
+        assert!(load_segment(&partial_path).unwrap().is_none());
+        assert!(!building_path.exists());
+        assert!(!partial_path.exists());
+    }
 }
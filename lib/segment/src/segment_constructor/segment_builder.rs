@@ -3,15 +3,21 @@ use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::sync::atomic::AtomicBool;
 
-use super::get_vector_storage_path;
+use itertools::Itertools;
+
+use super::{get_vector_name_with_prefix, get_vector_storage_path};
 use crate::common::error_logging::LogError;
+use crate::common::rocksdb_wrapper::{DatabaseColumnWrapper, DB_VECTOR_CF};
 use crate::entry::entry_point::{
     check_process_stopped, OperationError, OperationResult, SegmentEntry,
 };
 use crate::index::{PayloadIndex, VectorIndex};
 use crate::segment::Segment;
 use crate::segment_constructor::{build_segment, load_segment};
-use crate::types::{PayloadFieldSchema, PayloadKeyType, SegmentConfig};
+use crate::types::{
+    PayloadFieldSchema, PayloadKeyType, PointOffsetType, SegmentConfig, StorageType,
+};
+use crate::vector_storage::memmap_vector_storage::VECTORS_PATH;
 use crate::vector_storage::VectorStorage;
 
 /// Structure for constructing segment out of several other segments
@@ -20,6 +26,10 @@ pub struct SegmentBuilder {
     pub destination_path: PathBuf,
     pub temp_path: PathBuf,
     pub indexed_fields: HashMap<PayloadKeyType, PayloadFieldSchema>,
+    /// Payload key to group points by.
+    /// Points with the same value of the key are stored next to each other,
+    /// which improves locality of filtered search on mmap storage.
+    pub defrag_key: Option<PayloadKeyType>,
 }
 
 impl SegmentBuilder {
@@ -38,12 +48,16 @@ impl SegmentBuilder {
             destination_path,
             temp_path,
             indexed_fields: Default::default(),
+            defrag_key: None,
         })
     }
 
     /// Update current segment builder with all (not deleted) vectors and payload form `other` segment
     /// Perform index building at the end of update
     ///
+    /// If `defrag_key` is set, points of `other` segment are added in order of the key values.
+    /// Use `update_from_segments` to order points of several segments together.
+    ///
     /// # Arguments
     ///
     /// * `other` - segment to add into construction
@@ -53,6 +67,64 @@ impl SegmentBuilder {
     /// * `bool` - if `true` - data successfully added, if `false` - process was interrupted
     ///
     pub fn update_from(&mut self, other: &Segment, stopped: &AtomicBool) -> OperationResult<bool> {
+        self.update_from_segments(&[other], stopped)
+    }
+
+    /// Update current segment builder with all (not deleted) vectors and payload from `others` segments
+    ///
+    /// If `defrag_key` is set, points of all `others` segments are added in order of the key values,
+    /// so points with the same value are stored next to each other regardless of their source segment.
+    pub fn update_from_segments(
+        &mut self,
+        others: &[&Segment],
+        stopped: &AtomicBool,
+    ) -> OperationResult<bool> {
+        let Some(defrag_key) = self.defrag_key.clone() else {
+            for other in others {
+                let other_internal_ids: Vec<_> = other.id_tracker.borrow().iter_ids().collect();
+                self.copy_points(other, &other_internal_ids, stopped)?;
+            }
+            return Ok(true);
+        };
+
+        let mut keyed_ids = Vec::new();
+        for (segment_idx, other) in others.iter().enumerate() {
+            let other_id_tracker = other.id_tracker.borrow();
+            let other_payload_index = other.payload_index.borrow();
+            for internal_id in other_id_tracker.iter_ids() {
+                check_process_stopped(stopped)?;
+                let key_values: Vec<_> = other_payload_index
+                    .payload(internal_id)?
+                    .get_value(&defrag_key)
+                    .values()
+                    .into_iter()
+                    .map(|value| value.to_string())
+                    .collect();
+                keyed_ids.push((key_values, segment_idx, internal_id));
+            }
+        }
+        // Stable sort keeps original order of points within a group
+        keyed_ids.sort_by(|(a, _, _), (b, _, _)| a.cmp(b));
+
+        // Consecutive points of the same source segment are copied together
+        for (segment_idx, points) in &keyed_ids
+            .iter()
+            .group_by(|(_, segment_idx, _)| *segment_idx)
+        {
+            let other_internal_ids: Vec<_> =
+                points.map(|(_, _, internal_id)| *internal_id).collect();
+            self.copy_points(others[segment_idx], &other_internal_ids, stopped)?;
+        }
+        Ok(true)
+    }
+
+    /// Append points of `other` segment with the given internal ids, in the given order
+    fn copy_points(
+        &mut self,
+        other: &Segment,
+        other_internal_ids: &[PointOffsetType],
+        stopped: &AtomicBool,
+    ) -> OperationResult<()> {
         match &mut self.segment {
             None => Err(OperationError::service_error(
                 "Segment building error: created segment not found",
@@ -101,7 +173,7 @@ impl SegmentBuilder {
                     let other_vector_storage = other_vector_storage.unwrap();
                     let internal_range = vector_storage.update_from(
                         other_vector_storage,
-                        &mut other_id_tracker.iter_ids(),
+                        &mut other_internal_ids.iter().copied(),
                         stopped,
                     )?;
                     match new_internal_range.clone() {
@@ -116,8 +188,40 @@ impl SegmentBuilder {
                     }
                 }
 
+                let sparse_start = match &new_internal_range {
+                    Some(new_internal_range) => new_internal_range.start,
+                    None => id_tracker.internal_size() as PointOffsetType,
+                };
+                for (vector_name, sparse_vector_data) in &self_segment.sparse_vector_data {
+                    check_process_stopped(stopped)?;
+                    let other_sparse_vector_data =
+                        other.sparse_vector_data.get(vector_name).ok_or_else(|| {
+                            OperationError::service_error(format!(
+                                "Cannot update from other segment because of missing sparse vector name {vector_name}"
+                            ))
+                        })?;
+                    let internal_range =
+                        sparse_vector_data.vector_storage.borrow_mut().update_from(
+                            &other_sparse_vector_data.vector_storage.borrow(),
+                            &mut other_internal_ids.iter().copied(),
+                            sparse_start,
+                            stopped,
+                        )?;
+                    match new_internal_range.clone() {
+                        Some(new_internal_range) => {
+                            if new_internal_range != internal_range {
+                                return Err(OperationError::service_error(
+                                    "Internal ids range mismatch between self segment vectors and other segment sparse vectors",
+                                ));
+                            }
+                        }
+                        None => new_internal_range = Some(internal_range),
+                    }
+                }
+
                 if let Some(new_internal_range) = new_internal_range {
-                    let internal_id_iter = new_internal_range.zip(other_id_tracker.iter_ids());
+                    let internal_id_iter =
+                        new_internal_range.zip(other_internal_ids.iter().copied());
 
                     for (new_internal_id, old_internal_id) in internal_id_iter {
                         check_process_stopped(stopped)?;
@@ -138,6 +242,12 @@ impl SegmentBuilder {
                                 // New point, just insert
                                 id_tracker.set_link(external_id, new_internal_id)?;
                                 id_tracker.set_internal_version(new_internal_id, other_version)?;
+                                if let Some(metadata) =
+                                    other_id_tracker.point_metadata(old_internal_id)
+                                {
+                                    id_tracker
+                                        .set_point_metadata(new_internal_id, metadata.clone())?;
+                                }
                                 payload_index.assign(
                                     new_internal_id,
                                     &other_payload_index.payload(old_internal_id)?,
@@ -154,6 +264,14 @@ impl SegmentBuilder {
                                     id_tracker.set_link(external_id, new_internal_id)?;
                                     id_tracker
                                         .set_internal_version(new_internal_id, other_version)?;
+                                    if let Some(metadata) =
+                                        other_id_tracker.point_metadata(old_internal_id)
+                                    {
+                                        id_tracker.set_point_metadata(
+                                            new_internal_id,
+                                            metadata.clone(),
+                                        )?;
+                                    }
                                     payload_index.drop(existing_internal_id)?;
                                     payload_index.assign(
                                         new_internal_id,
@@ -169,7 +287,7 @@ impl SegmentBuilder {
                     self.indexed_fields.insert(field, payload_schema);
                 }
 
-                Ok(true)
+                Ok(())
             }
         }
     }
@@ -193,6 +311,8 @@ impl SegmentBuilder {
             }
 
             segment.flush(true)?;
+
+            Self::remove_quantized_only_originals(&segment)?;
             // Now segment is going to be evicted from RAM
         }
 
@@ -225,4 +345,30 @@ impl SegmentBuilder {
         }
         Ok(())
     }
+
+    /// Remove original vectors, which are replaced by quantized ones.
+    /// Quantized vectors and index are already built, so originals are not needed anymore.
+    fn remove_quantized_only_originals(segment: &Segment) -> OperationResult<()> {
+        let config = segment.config();
+        for vector_name in segment.vector_data.keys() {
+            if !config.is_quantized_only(vector_name) {
+                continue;
+            }
+            match config.storage_type {
+                StorageType::InMemory => {
+                    let column_name = get_vector_name_with_prefix(DB_VECTOR_CF, vector_name);
+                    DatabaseColumnWrapper::new(segment.database.clone(), &column_name)
+                        .recreate_column_family()?;
+                }
+                StorageType::Mmap => {
+                    let segment_path = segment.current_path.as_path();
+                    let vectors_path =
+                        get_vector_storage_path(segment_path, vector_name).join(VECTORS_PATH);
+                    std::fs::remove_file(&vectors_path)
+                        .describe("Removing original vectors of quantized-only storage")?;
+                }
+            }
+        }
+        Ok(())
+    }
 }
@@ -11,7 +11,8 @@ use semver::Version;
 use serde::Deserialize;
 use uuid::Uuid;
 
-use crate::common::rocksdb_wrapper::{open_db, DB_VECTOR_CF};
+use crate::common::error_logging::LogError;
+use crate::common::rocksdb_wrapper::{open_db, DB_SPARSE_VECTOR_CF, DB_VECTOR_CF};
 use crate::common::version::StorageVersion;
 use crate::data_types::vectors::DEFAULT_VECTOR_NAME;
 use crate::entry::entry_point::{OperationError, OperationResult};
@@ -19,28 +20,37 @@ use crate::id_tracker::simple_id_tracker::SimpleIdTracker;
 use crate::index::hnsw_index::graph_links::{GraphLinksMmap, GraphLinksRam};
 use crate::index::hnsw_index::hnsw::HNSWIndex;
 use crate::index::plain_payload_index::PlainIndex;
+use crate::index::sparse_vector_index::SparseVectorIndex;
 use crate::index::struct_payload_index::StructPayloadIndex;
 use crate::index::VectorIndexEnum;
 use crate::payload_storage::on_disk_payload_storage::OnDiskPayloadStorage;
 use crate::payload_storage::simple_payload_storage::SimplePayloadStorage;
-use crate::segment::{Segment, SegmentVersion, VectorData, SEGMENT_STATE_FILE};
+use crate::segment::{
+    Segment, SegmentManifest, SegmentVersion, SparseVectorData, VectorData, SEGMENT_STATE_FILE,
+};
 use crate::types::{
     Distance, Indexes, PayloadStorageType, SegmentConfig, SegmentState, SegmentType, SeqNumberType,
     StorageType, VectorDataConfig,
 };
 use crate::vector_storage::memmap_vector_storage::open_memmap_vector_storage;
+use crate::vector_storage::quantized::quantized_vectors_base::QuantizedVectorsStorage;
+use crate::vector_storage::quantized_only_vector_storage::open_quantized_only_vector_storage;
 use crate::vector_storage::simple_vector_storage::open_simple_vector_storage;
+use crate::vector_storage::sparse_vector_storage::open_sparse_vector_storage;
 use crate::vector_storage::VectorStorage;
 
 pub const PAYLOAD_INDEX_PATH: &str = "payload_index";
 pub const VECTOR_STORAGE_PATH: &str = "vector_storage";
 pub const VECTOR_INDEX_PATH: &str = "vector_index";
 
+/// Suffix of the directory, in which a new segment is created before it is moved into its place
+const SEGMENT_BUILD_SUFFIX: &str = ".building";
+
 fn sp<T>(t: T) -> Arc<AtomicRefCell<T>> {
     Arc::new(AtomicRefCell::new(t))
 }
 
-fn get_vector_name_with_prefix(prefix: &str, vector_name: &str) -> String {
+pub(crate) fn get_vector_name_with_prefix(prefix: &str, vector_name: &str) -> String {
     if !vector_name.is_empty() {
         format!("{prefix}-{vector_name}")
     } else {
@@ -68,6 +78,12 @@ fn create_segment(
         .vector_data
         .keys()
         .map(|vector_name| get_vector_name_with_prefix(DB_VECTOR_CF, vector_name))
+        .chain(
+            config
+                .sparse_vector_data
+                .keys()
+                .map(|vector_name| get_vector_name_with_prefix(DB_SPARSE_VECTOR_CF, vector_name)),
+        )
         .collect();
     let database = open_db(segment_path, &vector_db_names)
         .map_err(|err| OperationError::service_error(format!("RocksDB open error: {err}")))?;
@@ -91,7 +107,18 @@ fn create_segment(
         let vector_storage_path = get_vector_storage_path(segment_path, vector_name);
         let vector_index_path = get_vector_index_path(segment_path, vector_name);
 
+        // Original vectors are removed on segment build, if only quantized vectors are kept
+        let quantized_only = config.is_quantized_only(vector_name)
+            && QuantizedVectorsStorage::check_exists(&vector_storage_path);
+
         let vector_storage = match config.storage_type {
+            _ if quantized_only => open_quantized_only_vector_storage(
+                &vector_storage_path,
+                vector_name,
+                vector_config.size,
+                vector_config.distance,
+                config.storage_type == StorageType::Mmap,
+            )?,
             StorageType::InMemory => {
                 let db_column_name = get_vector_name_with_prefix(DB_VECTOR_CF, vector_name);
                 open_simple_vector_storage(
@@ -117,14 +144,17 @@ fn create_segment(
                 .load_quantization(&quantized_data_path)?;
         }
 
-        let vector_index: Arc<AtomicRefCell<VectorIndexEnum>> = match config.index {
+        let vector_index: Arc<AtomicRefCell<VectorIndexEnum>> = match &config.index {
             Indexes::Plain {} => sp(VectorIndexEnum::Plain(PlainIndex::new(
                 id_tracker.clone(),
                 vector_storage.clone(),
                 payload_index.clone(),
             ))),
             Indexes::Hnsw(collection_hnsw_config) => {
-                let hnsw_config = vector_config.hnsw_config.unwrap_or(collection_hnsw_config);
+                let hnsw_config = vector_config
+                    .hnsw_config
+                    .clone()
+                    .unwrap_or_else(|| collection_hnsw_config.clone());
                 sp(if hnsw_config.on_disk == Some(true) {
                     VectorIndexEnum::HnswMmap(HNSWIndex::<GraphLinksMmap>::open(
                         &vector_index_path,
@@ -154,6 +184,24 @@ fn create_segment(
         );
     }
 
+    let mut sparse_vector_data = HashMap::new();
+    for vector_name in config.sparse_vector_data.keys() {
+        let db_column_name = get_vector_name_with_prefix(DB_SPARSE_VECTOR_CF, vector_name);
+        let vector_storage = open_sparse_vector_storage(database.clone(), &db_column_name)?;
+        let vector_index = sp(SparseVectorIndex::open(
+            id_tracker.clone(),
+            vector_storage.clone(),
+            payload_index.clone(),
+        ));
+        sparse_vector_data.insert(
+            vector_name.to_owned(),
+            SparseVectorData {
+                vector_storage,
+                vector_index,
+            },
+        );
+    }
+
     let segment_type = match config.index {
         Indexes::Plain {} => SegmentType::Plain,
         Indexes::Hnsw { .. } => SegmentType::Indexed,
@@ -168,6 +216,7 @@ fn create_segment(
         current_path: segment_path.to_owned(),
         id_tracker,
         vector_data,
+        sparse_vector_data,
         segment_type,
         appendable_flag,
         payload_index,
@@ -175,52 +224,102 @@ fn create_segment(
         error_status: None,
         database,
         flush_thread: Mutex::new(None),
+        read_filtered_telemetry: Mutex::new(Default::default()),
     })
 }
 
+/// Check if the directory is used to build a new segment.
+/// Builds, which are not finished because of a crash, are removed on load.
+pub fn is_segment_building(path: &Path) -> bool {
+    path.file_name().map_or(false, |name| {
+        name.to_string_lossy().ends_with(SEGMENT_BUILD_SUFFIX)
+    })
+}
+
+/// Check if the directory contains a fully built segment.
+///
+/// Segments built by older versions have no manifest, they are complete if the version file exists.
+pub fn is_segment_committed(path: &Path) -> bool {
+    !is_segment_building(path)
+        && (SegmentManifest::check_exists(path) || SegmentVersion::check_exists(path))
+}
+
 pub fn load_segment(path: &Path) -> OperationResult<Option<Segment>> {
-    if !SegmentVersion::check_exists(path) {
-        // Assume segment was not properly saved.
-        // Server might have crashed before saving the segment fully.
-        log::warn!(
-            "Segment version file not found, skipping: {}",
-            path.display()
-        );
+    if !is_segment_committed(path) {
+        if path.is_dir() {
+            // Server might have crashed before the segment was fully built.
+            // Nothing references the incomplete segment, so it is safe to remove it.
+            log::warn!("Removing incomplete segment: {}", path.display());
+            std::fs::remove_dir_all(path).describe("Removing incomplete segment")?;
+        }
         return Ok(None);
     }
 
-    let stored_version: Version = SegmentVersion::load(path)?.parse()?;
-    let app_version: Version = SegmentVersion::current().parse()?;
+    if !SegmentManifest::check_exists(path) {
+        // Segment was built before manifests were introduced
+        let manifest = SegmentManifest {
+            version: SegmentVersion::load(path)?,
+        };
+        manifest.save(path)?;
+    }
 
-    if stored_version != app_version {
-        info!("Migrating segment {} -> {}", stored_version, app_version,);
+    migrate_segment(path)?;
 
-        if stored_version > app_version {
-            return Err(OperationError::service_error(format!(
-                "Data version {stored_version} is newer than application version {app_version}. \
-                Please upgrade the application. Compatibility is not guaranteed."
-            )));
-        }
+    let segment_state = Segment::load_state(path)?;
 
-        if stored_version.major == 0 && stored_version.minor < 3 {
-            return Err(OperationError::service_error(format!(
-                "Segment version({stored_version}) is not compatible with current version({app_version})"
-            )));
-        }
+    let segment = create_segment(segment_state.version, path, &segment_state.config)?;
 
-        if stored_version.major == 0 && stored_version.minor == 3 {
-            let segment_state = load_segment_state_v3(path)?;
-            Segment::save_state(&segment_state, path)?;
-        }
+    Ok(Some(segment))
+}
 
-        SegmentVersion::save(path)?
+fn check_segment_version(stored_version: &Version, app_version: &Version) -> OperationResult<()> {
+    if stored_version > app_version {
+        return Err(OperationError::service_error(format!(
+            "Data version {stored_version} is newer than application version {app_version}. \
+            Please upgrade the application. Compatibility is not guaranteed."
+        )));
     }
 
-    let segment_state = Segment::load_state(path)?;
+    if stored_version.major == 0 && stored_version.minor < 3 {
+        return Err(OperationError::service_error(format!(
+            "Segment version({stored_version}) is not compatible with current version({app_version})"
+        )));
+    }
 
-    let segment = create_segment(segment_state.version, path, &segment_state.config)?;
+    Ok(())
+}
 
-    Ok(Some(segment))
+/// Check that the segment can be loaded by the current version without modifying it.
+/// Segment must have a version file.
+pub fn check_segment_compatibility(path: &Path) -> OperationResult<()> {
+    let stored_version: Version = SegmentVersion::load(path)?.parse()?;
+    let app_version: Version = SegmentVersion::current().parse()?;
+    check_segment_version(&stored_version, &app_version)
+}
+
+/// Upgrade on-disk format of the segment to the current version, if required.
+/// Segment must have a version file.
+///
+/// Returns `true` if the segment was migrated.
+pub fn migrate_segment(path: &Path) -> OperationResult<bool> {
+    let stored_version: Version = SegmentVersion::load(path)?.parse()?;
+    let app_version: Version = SegmentVersion::current().parse()?;
+
+    if stored_version == app_version {
+        return Ok(false);
+    }
+
+    info!("Migrating segment {} -> {}", stored_version, app_version,);
+
+    check_segment_version(&stored_version, &app_version)?;
+
+    if stored_version.major == 0 && stored_version.minor == 3 {
+        let segment_state = load_segment_state_v3(path)?;
+        Segment::save_state(&segment_state, path)?;
+    }
+
+    SegmentVersion::save(path)?;
+    Ok(true)
 }
 
 /// Build segment instance using given configuration.
@@ -233,18 +332,25 @@ pub fn load_segment(path: &Path) -> OperationResult<Option<Segment>> {
 ///
 ///
 pub fn build_segment(path: &Path, config: &SegmentConfig) -> OperationResult<Segment> {
-    let segment_path = path.join(Uuid::new_v4().to_string());
+    let segment_id = Uuid::new_v4().to_string();
+    let segment_path = path.join(&segment_id);
+    let build_path = path.join(format!("{segment_id}{SEGMENT_BUILD_SUFFIX}"));
+
+    std::fs::create_dir_all(&build_path)?;
 
-    std::fs::create_dir_all(&segment_path)?;
+    {
+        let segment = create_segment(None, &build_path, config)?;
+        segment.save_current_state()?;
+        // Segment storages are closed on drop, before the directory is moved
+    }
 
-    let segment = create_segment(None, &segment_path, config)?;
-    segment.save_current_state()?;
+    SegmentVersion::save(&build_path)?;
+    SegmentManifest::current().save(&build_path)?;
 
-    // Version is the last file to save, as it will be used to check if segment was built correctly.
-    // If it is not saved, segment will be skipped.
-    SegmentVersion::save(&segment_path)?;
+    // Segment is committed by the rename, so it is either fully built or removed on load
+    std::fs::rename(&build_path, &segment_path).describe("Committing built segment")?;
 
-    Ok(segment)
+    create_segment(None, &segment_path, config)
 }
 
 fn load_segment_state_v3(segment_path: &Path) -> OperationResult<SegmentState> {
@@ -294,6 +400,7 @@ fn load_segment_state_v3(segment_path: &Path) -> OperationResult<SegmentState> {
                     storage_type: state.config.storage_type,
                     payload_storage_type: state.config.payload_storage_type,
                     quantization_config: None,
+                    sparse_vector_data: Default::default(),
                 },
             }
         })
@@ -6,6 +6,8 @@ use std::vec::IntoIter as VecIntoIter;
 
 use serde::{Deserialize, Serialize};
 
+use crate::types::ScoreType;
+
 /// This is a MinHeap by default - it will keep the largest elements, pop smallest
 #[derive(Deserialize, Serialize, Clone, Debug)]
 pub struct FixedLengthPriorityQueue<T: Ord> {
@@ -59,6 +61,10 @@ impl<T: Ord> FixedLengthPriorityQueue<T> {
         self.heap.len()
     }
 
+    pub fn is_full(&self) -> bool {
+        self.heap.len() >= self.length
+    }
+
     /// Checks if the queue is empty
     pub fn is_empty(&self) -> bool {
         self.heap.is_empty()
@@ -149,6 +155,11 @@ pub fn peek_top_scores<E: Ord + Clone>(scores: &[E], top: usize) -> Vec<E> {
     peek_top_largest_iterable(scores.iter().cloned(), top)
 }
 
+/// Logistic function, maps similarity of any distance into range from 0 to 1
+pub fn sigmoid(x: ScoreType) -> ScoreType {
+    1.0 / (1.0 + (-x).exp())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -3,7 +3,9 @@ use serde::{Deserialize, Serialize};
 
 use crate::common::anonymize::Anonymize;
 use crate::common::operation_time_statistics::OperationDurationStatistics;
-use crate::types::{PayloadIndexInfo, SegmentConfig, SegmentInfo, VectorDataConfig};
+use crate::types::{
+    PayloadIndexInfo, SegmentConfig, SegmentInfo, SparseVectorDataConfig, VectorDataConfig,
+};
 
 #[derive(Serialize, Deserialize, Clone, Debug, JsonSchema)]
 pub struct VectorIndexesTelemetry {
@@ -16,6 +18,34 @@ pub struct SegmentTelemetry {
     pub config: SegmentConfig,
     pub vector_index_searches: Vec<VectorIndexSearchesTelemetry>,
     pub payload_field_indices: Vec<PayloadIndexTelemetry>,
+    #[serde(default)]
+    pub read_filtered: ReadFilteredTelemetry,
+}
+
+/// Decisions of the query planner between reading filtered points by the payload index
+/// and by the stream of point ids. Reads with a planner hint are not included.
+#[derive(Serialize, Deserialize, Clone, Debug, JsonSchema, Default)]
+pub struct ReadFilteredTelemetry {
+    pub index: ReadFilteredStrategyTelemetry,
+    pub stream: ReadFilteredStrategyTelemetry,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, JsonSchema, Default)]
+pub struct ReadFilteredStrategyTelemetry {
+    /// Number of reads, for which the strategy was selected
+    pub count: usize,
+    /// Total number of filter checks, expected by the cost model
+    pub expected_checks: usize,
+    /// Total number of filter checks, actually performed
+    pub actual_checks: usize,
+}
+
+impl ReadFilteredStrategyTelemetry {
+    pub fn add(&mut self, expected_checks: usize, actual_checks: usize) {
+        self.count += 1;
+        self.expected_checks = self.expected_checks.saturating_add(expected_checks);
+        self.actual_checks = self.actual_checks.saturating_add(actual_checks);
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, JsonSchema)]
@@ -61,6 +91,19 @@ pub struct VectorIndexSearchesTelemetry {
 
     #[serde(skip_serializing_if = "OperationDurationStatistics::is_empty")]
     pub unfiltered_exact: OperationDurationStatistics,
+
+    /// Additional payload-aware subgraphs of HNSW index, per payload field
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub payload_subgraphs: Vec<PayloadSubgraphTelemetry>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, JsonSchema, Default)]
+pub struct PayloadSubgraphTelemetry {
+    pub field_name: Option<String>,
+    /// Number of payload blocks, for which subgraphs are built
+    pub blocks: usize,
+    /// Total number of points linked in subgraphs of the field
+    pub points: usize,
 }
 
 impl Anonymize for SegmentTelemetry {
@@ -70,6 +113,26 @@ impl Anonymize for SegmentTelemetry {
             config: self.config.anonymize(),
             vector_index_searches: self.vector_index_searches.anonymize(),
             payload_field_indices: self.payload_field_indices.anonymize(),
+            read_filtered: self.read_filtered.anonymize(),
+        }
+    }
+}
+
+impl Anonymize for ReadFilteredTelemetry {
+    fn anonymize(&self) -> Self {
+        ReadFilteredTelemetry {
+            index: self.index.anonymize(),
+            stream: self.stream.anonymize(),
+        }
+    }
+}
+
+impl Anonymize for ReadFilteredStrategyTelemetry {
+    fn anonymize(&self) -> Self {
+        ReadFilteredStrategyTelemetry {
+            count: self.count.anonymize(),
+            expected_checks: self.expected_checks.anonymize(),
+            actual_checks: self.actual_checks.anonymize(),
         }
     }
 }
@@ -99,6 +162,8 @@ impl Anonymize for PayloadIndexInfo {
             data_type: self.data_type,
             params: self.params.clone(),
             points: self.points.anonymize(),
+            status: self.status,
+            progress_percent: self.progress_percent,
         }
     }
 }
@@ -107,14 +172,21 @@ impl Anonymize for SegmentConfig {
     fn anonymize(&self) -> Self {
         SegmentConfig {
             vector_data: self.vector_data.anonymize(),
-            index: self.index,
+            index: self.index.clone(),
             storage_type: self.storage_type,
             payload_storage_type: self.payload_storage_type,
             quantization_config: self.quantization_config.clone(),
+            sparse_vector_data: self.sparse_vector_data.anonymize(),
         }
     }
 }
 
+impl Anonymize for SparseVectorDataConfig {
+    fn anonymize(&self) -> Self {
+        self.clone()
+    }
+}
+
 impl Anonymize for VectorDataConfig {
     fn anonymize(&self) -> Self {
         VectorDataConfig {
@@ -137,6 +209,17 @@ impl Anonymize for VectorIndexSearchesTelemetry {
             filtered_large_cardinality: self.filtered_large_cardinality.anonymize(),
             filtered_exact: self.filtered_exact.anonymize(),
             unfiltered_exact: self.filtered_exact.anonymize(),
+            payload_subgraphs: self.payload_subgraphs.anonymize(),
+        }
+    }
+}
+
+impl Anonymize for PayloadSubgraphTelemetry {
+    fn anonymize(&self) -> Self {
+        PayloadSubgraphTelemetry {
+            field_name: None,
+            blocks: self.blocks.anonymize(),
+            points: self.points.anonymize(),
         }
     }
 }
@@ -1,8 +1,10 @@
+use std::borrow::Cow;
 use std::cmp::Ordering;
 use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fmt::Formatter;
+use std::iter::Sum;
 use std::mem::size_of;
-use std::ops::Deref;
+use std::ops::{AddAssign, Deref};
 use std::rc::Rc;
 use std::str::FromStr;
 
@@ -10,16 +12,20 @@ use geo::prelude::HaversineDistance;
 use geo::Point;
 use itertools::Itertools;
 use ordered_float::OrderedFloat;
+use schemars::gen::SchemaGenerator;
+use schemars::schema::Schema;
 use schemars::JsonSchema;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use serde_json::{Map, Value};
 use uuid::Uuid;
 use validator::{Validate, ValidationErrors};
 
+use crate::common::transcode::Transcoder;
 use crate::common::utils;
 use crate::common::utils::MultiValue;
 use crate::data_types::text_index::TextIndexParams;
 use crate::data_types::vectors::{VectorElementType, VectorStruct};
+use crate::entry::entry_point::OperationResult;
 use crate::spaces::metric::Metric;
 use crate::spaces::simple::{CosineMetric, DotProductMetric, EuclidMetric};
 
@@ -141,6 +147,15 @@ impl Distance {
         }
     }
 
+    /// Greater the value - closer the vectors. Vectors are expected to be preprocessed.
+    pub fn similarity(&self, v1: &[VectorElementType], v2: &[VectorElementType]) -> ScoreType {
+        match self {
+            Distance::Cosine => CosineMetric::similarity(v1, v2),
+            Distance::Euclid => EuclidMetric::similarity(v1, v2),
+            Distance::Dot => DotProductMetric::similarity(v1, v2),
+        }
+    }
+
     pub fn distance_order(&self) -> Order {
         match self {
             Distance::Cosine | Distance::Dot => Order::LargeBetter,
@@ -175,6 +190,28 @@ pub struct ScoredPoint {
     pub payload: Option<Payload>,
     /// Vector of the point
     pub vector: Option<VectorStruct>,
+    /// Words of the payload text, which matched the full-text conditions of the filter
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub highlights: Option<Vec<Highlight>>,
+    /// Distance in meters from the center of the geo condition of the filter
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub geo_distance: Option<f64>,
+}
+
+/// Position of a matched word in the text value of the payload field
+#[derive(Deserialize, Serialize, JsonSchema, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub struct Highlight {
+    /// Payload field, which contains the text
+    pub key: PayloadKeyType,
+    /// Position of the text among the values of the field, if it holds an array of texts
+    pub index: usize,
+    /// Position of the first character of the word, counted in characters
+    pub start: usize,
+    /// Position after the last character of the word, counted in characters
+    pub end: usize,
 }
 
 impl Eq for ScoredPoint {}
@@ -209,6 +246,22 @@ pub enum SegmentType {
     Special,
 }
 
+/// State of the payload field index.
+/// Ordered by severity, so the state of the whole collection is the maximum of segment states.
+#[derive(
+    Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum PayloadIndexStatus {
+    /// Index is built and used for filtering
+    #[default]
+    Ready,
+    /// Index is being built, filtering by the field is not accelerated yet
+    Building,
+    /// Index build failed
+    Failed,
+}
+
 /// Display payload field type & index information
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
@@ -219,25 +272,42 @@ pub struct PayloadIndexInfo {
     pub params: Option<PayloadSchemaParams>,
     /// Number of points indexed with this index
     pub points: usize,
+    /// State of the index build
+    #[serde(default)]
+    pub status: PayloadIndexStatus,
+    /// Percent of points processed by the index build, while the index is being built
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub progress_percent: Option<u8>,
 }
 
 impl PayloadIndexInfo {
     pub fn new(field_type: PayloadFieldSchema, points_count: usize) -> Self {
-        match field_type {
-            PayloadFieldSchema::FieldType(data_type) => PayloadIndexInfo {
-                data_type,
-                params: None,
-                points: points_count,
-            },
+        let (data_type, params) = match field_type {
+            PayloadFieldSchema::FieldType(data_type) => (data_type, None),
             PayloadFieldSchema::FieldParams(schema_params) => match schema_params {
-                PayloadSchemaParams::Text(_) => PayloadIndexInfo {
-                    data_type: PayloadSchemaType::Text,
-                    params: Some(schema_params),
-                    points: points_count,
-                },
+                PayloadSchemaParams::Text(_) => (PayloadSchemaType::Text, Some(schema_params)),
             },
+        };
+        PayloadIndexInfo {
+            data_type,
+            params,
+            points: points_count,
+            status: PayloadIndexStatus::Ready,
+            progress_percent: None,
         }
     }
+
+    /// Combine info about the index of the same field in another segment or shard.
+    /// The least progress of the builds is reported, as the index is not usable until all of them finish.
+    pub fn merge(&mut self, other: &PayloadIndexInfo) {
+        self.points += other.points;
+        self.status = self.status.max(other.status);
+        self.progress_percent = match (self.progress_percent, other.progress_percent) {
+            (Some(progress), Some(other_progress)) => Some(progress.min(other_progress)),
+            (progress, other_progress) => progress.or(other_progress),
+        };
+    }
 }
 
 /// Aggregated information about segment
@@ -254,8 +324,96 @@ pub struct SegmentInfo {
     pub index_schema: HashMap<PayloadKeyType, PayloadIndexInfo>,
 }
 
+/// Result of cross-checking components of the segment
+#[derive(Debug, Default, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub struct SegmentVerification {
+    /// Number of points in the id tracker
+    pub points_count: usize,
+    /// Points without external id
+    pub unmapped_points: usize,
+    /// Points, which are missing in some of the vector storages
+    pub missing_vectors: usize,
+    /// Payloads of points, which are deleted or missing in the id tracker
+    pub dangling_payloads: usize,
+    /// Number of vectors, read from the vector storages
+    pub sampled_vectors: usize,
+    /// Read vectors with wrong dimension or non-finite values
+    pub corrupted_vectors: usize,
+}
+
+impl SegmentVerification {
+    pub fn is_ok(&self) -> bool {
+        self.problems().is_empty()
+    }
+
+    /// Descriptions of found inconsistencies
+    pub fn problems(&self) -> Vec<String> {
+        [
+            (self.unmapped_points, "points without external id"),
+            (self.missing_vectors, "points without vectors"),
+            (self.dangling_payloads, "payloads of missing points"),
+            (self.corrupted_vectors, "corrupted vectors"),
+        ]
+        .into_iter()
+        .filter(|(count, _)| *count > 0)
+        .map(|(count, description)| format!("{count} {description}"))
+        .collect()
+    }
+}
+
+/// Estimated memory, occupied by data structures of segment components, in bytes.
+/// Memory mapped files are not included, as their pages are managed by the OS page cache.
+#[derive(Debug, Default, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub struct MemoryUsage {
+    /// Original vectors, stored in RAM
+    pub vector_storages: usize,
+    /// Quantized vectors, stored in RAM
+    pub quantized_vectors: usize,
+    /// Links of HNSW graphs
+    pub hnsw_links: usize,
+    /// Indexes of payload fields
+    pub payload_indexes: usize,
+    /// Mappings of point ids and point versions
+    pub id_trackers: usize,
+    /// RocksDB memtables, table readers and block caches
+    pub rocksdb: usize,
+}
+
+impl MemoryUsage {
+    pub fn total(&self) -> usize {
+        self.vector_storages
+            + self.quantized_vectors
+            + self.hnsw_links
+            + self.payload_indexes
+            + self.id_trackers
+            + self.rocksdb
+    }
+}
+
+impl AddAssign for MemoryUsage {
+    fn add_assign(&mut self, other: Self) {
+        self.vector_storages += other.vector_storages;
+        self.quantized_vectors += other.quantized_vectors;
+        self.hnsw_links += other.hnsw_links;
+        self.payload_indexes += other.payload_indexes;
+        self.id_trackers += other.id_trackers;
+        self.rocksdb += other.rocksdb;
+    }
+}
+
+impl Sum for MemoryUsage {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::default(), |mut total, usage| {
+            total += usage;
+            total
+        })
+    }
+}
+
 /// Additional parameters of the search
-#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Default)]
 #[serde(rename_all = "snake_case")]
 pub struct QuantizationSearchParams {
     /// If true, quantized vectors are ignored. Default is false.
@@ -267,6 +425,30 @@ pub struct QuantizationSearchParams {
     /// Default is false.
     #[serde(default = "default_quantization_rescore_value")]
     pub rescore: bool,
+
+    /// Oversampling factor of the re-scoring.
+    /// If set, `limit * rescore_factor` candidates are selected with quantized vectors in each segment
+    /// and re-scored with original vectors, the best `limit` of them are returned.
+    /// Implies `rescore`, values not greater than 1 don't oversample. Default: no oversampling
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rescore_factor: Option<f64>,
+}
+
+impl Eq for QuantizationSearchParams {}
+
+impl QuantizationSearchParams {
+    /// Candidates, selected with quantized vectors, are re-scored with original vectors
+    pub fn is_rescore_required(&self) -> bool {
+        self.rescore || self.rescore_factor.is_some()
+    }
+
+    /// Number of candidates to select with quantized vectors for re-scoring
+    pub fn oversampled_top(&self, top: usize) -> usize {
+        match self.rescore_factor {
+            Some(factor) if factor > 1.0 => (top as f64 * factor).ceil() as usize,
+            _ => top,
+        }
+    }
 }
 
 pub fn default_quantization_ignore_value() -> bool {
@@ -292,10 +474,28 @@ pub struct SearchParams {
     /// Quantization params
     #[serde(default)]
     pub quantization: Option<QuantizationSearchParams>,
+
+    /// Override the strategy, selected by the query planner from the filter cardinality.
+    /// Default: strategy is selected automatically
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub planner_hint: Option<PlannerHint>,
+}
+
+/// Strategy of the query planner, which should be used instead of the one,
+/// selected by the cardinality estimation of the filter
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum PlannerHint {
+    /// Read filtered points using the payload index
+    IndexScan,
+    /// Read points in order of their ids and check each of them against the filter
+    StreamScan,
+    /// Search without the HNSW graph, score all points, which satisfy the filter
+    PlainSearch,
 }
 
 /// Vector index configuration of the segment
-#[derive(Debug, Deserialize, Serialize, JsonSchema, Copy, Clone, PartialEq, Eq)]
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
 #[serde(tag = "type", content = "options")]
 pub enum Indexes {
@@ -308,7 +508,7 @@ pub enum Indexes {
 }
 
 /// Config of HNSW index
-#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Copy, Clone, PartialEq, Eq)]
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
 pub struct HnswConfig {
     /// Number of edges per node in the index graph. Larger the value - more accurate the search, more space required.
@@ -333,6 +533,11 @@ pub struct HnswConfig {
     #[serde(default)]
     #[serde(skip_serializing_if = "Option::is_none")] // Better backward compatibility
     pub payload_m: Option<usize>,
+    /// Payload fields, for which additional payload-aware links are built.
+    /// If not set, links are built for all indexed payload fields.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")] // Better backward compatibility
+    pub payload_fields: Option<Vec<PayloadKeyType>>,
 }
 
 fn default_max_indexing_threads() -> usize {
@@ -359,6 +564,13 @@ pub struct ScalarQuantizationConfig {
     /// If true - quantized vectors always will be stored in RAM, ignoring the config of main storage
     #[serde(skip_serializing_if = "Option::is_none")]
     pub always_ram: Option<bool>,
+    /// If true - original vectors are removed from optimized segments and only quantized vectors are kept.
+    /// Search can't be rescored or made exact, retrieved points and search results don't include these vectors.
+    /// Collection can't be replicated, and its shards can't be transferred to other peers.
+    /// Optimized segments are not rebuilt anymore, points are moved out of them on upsert.
+    /// Intended for workloads, which tolerate lower precision in exchange for much less disk usage.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quantized_only: Option<bool>,
 }
 
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq, Eq, Hash)]
@@ -371,6 +583,7 @@ impl PartialEq for ScalarQuantizationConfig {
     fn eq(&self, other: &Self) -> bool {
         self.quantile == other.quantile
             && self.always_ram == other.always_ram
+            && self.quantized_only == other.quantized_only
             && self.r#type == other.r#type
     }
 }
@@ -378,23 +591,77 @@ impl PartialEq for ScalarQuantizationConfig {
 impl std::hash::Hash for ScalarQuantizationConfig {
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
         self.always_ram.hash(state);
+        self.quantized_only.hash(state);
         self.r#type.hash(state);
     }
 }
 
 impl Eq for ScalarQuantizationConfig {}
 
+/// Compression ratio of product quantization, compared to the original `float32` vectors
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum CompressionRatio {
+    X4,
+    X8,
+    X16,
+    X32,
+    X64,
+}
+
+impl CompressionRatio {
+    /// Number of vector dimensions, encoded with a single byte
+    pub fn bucket_size(&self) -> usize {
+        match self {
+            CompressionRatio::X4 => 1,
+            CompressionRatio::X8 => 2,
+            CompressionRatio::X16 => 4,
+            CompressionRatio::X32 => 8,
+            CompressionRatio::X64 => 16,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub struct ProductQuantizationConfig {
+    /// Compression ratio of the original vectors.
+    /// Higher ratio - less memory, lower precision of the search
+    pub compression: CompressionRatio,
+    /// If true - quantized vectors always will be stored in RAM, ignoring the config of main storage
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub always_ram: Option<bool>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq, Eq, Hash)]
+pub struct ProductQuantization {
+    #[validate]
+    pub product: ProductQuantizationConfig,
+}
+
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Eq, Hash)]
 #[serde(rename_all = "snake_case")]
 #[serde(untagged)]
 pub enum QuantizationConfig {
     Scalar(ScalarQuantization),
+    Product(ProductQuantization),
+}
+
+impl QuantizationConfig {
+    /// Original vectors are not kept, only quantized ones
+    pub fn is_quantized_only(&self) -> bool {
+        match self {
+            QuantizationConfig::Scalar(scalar) => scalar.scalar.quantized_only.unwrap_or(false),
+            QuantizationConfig::Product(_) => false,
+        }
+    }
 }
 
 impl Validate for QuantizationConfig {
     fn validate(&self) -> Result<(), ValidationErrors> {
         match self {
             QuantizationConfig::Scalar(scalar) => scalar.validate(),
+            QuantizationConfig::Product(product) => product.validate(),
         }
     }
 }
@@ -405,6 +672,12 @@ impl From<ScalarQuantizationConfig> for QuantizationConfig {
     }
 }
 
+impl From<ProductQuantizationConfig> for QuantizationConfig {
+    fn from(config: ProductQuantizationConfig) -> Self {
+        QuantizationConfig::Product(ProductQuantization { product: config })
+    }
+}
+
 pub const DEFAULT_HNSW_EF_CONSTRUCT: usize = 100;
 
 impl Default for HnswConfig {
@@ -416,6 +689,7 @@ impl Default for HnswConfig {
             max_indexing_threads: 0,
             on_disk: Some(false),
             payload_m: None,
+            payload_fields: None,
         }
     }
 }
@@ -472,6 +746,9 @@ pub enum PayloadStorageType {
 #[serde(rename_all = "snake_case")]
 pub struct SegmentConfig {
     pub vector_data: HashMap<String, VectorDataConfig>,
+    /// Sparse vectors, stored in the segment along with dense ones
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub sparse_vector_data: HashMap<String, SparseVectorDataConfig>,
     /// Type of index used for search
     pub index: Indexes,
     /// Type of vector storage
@@ -497,6 +774,27 @@ impl SegmentConfig {
             .and_then(|v| v.quantization_config.as_ref())
             .or(self.quantization_config.as_ref())
     }
+
+    /// Original vectors of the given vector name are removed from the segment,
+    /// only quantized vectors are stored.
+    ///
+    /// Vectors of appendable segments are always kept.
+    pub fn is_quantized_only(&self, vector_name: &str) -> bool {
+        let appendable =
+            self.index == Indexes::Plain {} && self.storage_type == StorageType::InMemory;
+        !appendable
+            && self
+                .quantization_config(vector_name)
+                .map_or(false, QuantizationConfig::is_quantized_only)
+    }
+
+    /// Segment doesn't store original vectors for some of its vector names,
+    /// so it can't be used as a source for building new segments.
+    pub fn has_quantized_only_vectors(&self) -> bool {
+        self.vector_data
+            .keys()
+            .any(|vector_name| self.is_quantized_only(vector_name))
+    }
 }
 
 /// Config of single vector data storage
@@ -515,6 +813,13 @@ pub struct VectorDataConfig {
     pub quantization_config: Option<QuantizationConfig>,
 }
 
+/// Config of single sparse vector data storage
+///
+/// Sparse vectors are always stored in RAM and searched with the inverted index by dot product.
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub struct SparseVectorDataConfig {}
+
 /// Default value based on <https://github.com/google-research/google-research/blob/master/scann/docs/algorithms.md>
 pub const DEFAULT_FULL_SCAN_THRESHOLD: usize = 20_000;
 
@@ -526,6 +831,23 @@ pub struct SegmentState {
     pub config: SegmentConfig,
 }
 
+/// Service metadata of the point, which controls further updates of it.
+/// Stored apart from the payload, so payload operations keep it and it is never returned to clients.
+#[derive(Debug, Deserialize, Serialize, Clone, Default, PartialEq, Eq)]
+pub struct PointMetadata {
+    /// External sequence number of the latest applied update of the point
+    pub sequence: Option<SeqNumberType>,
+    /// Hash of the point content, provided with the latest upsert.
+    /// Removed once the content is changed otherwise, e.g. by a payload update
+    pub content_hash: Option<String>,
+}
+
+impl PointMetadata {
+    pub fn is_empty(&self) -> bool {
+        self.sequence.is_none() && self.content_hash.is_none()
+    }
+}
+
 /// Geo point payload schema
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
 #[serde(try_from = "GeoPointShadow")]
@@ -569,6 +891,11 @@ impl GeoPoint {
         Self::validate(lon, lat)?;
         Ok(GeoPoint { lon, lat })
     }
+
+    /// Distance to the other point in meters
+    pub fn distance(&self, other: &GeoPoint) -> f64 {
+        Point::new(self.lon, self.lat).haversine_distance(&Point::new(other.lon, other.lat))
+    }
 }
 
 impl TryFrom<GeoPointShadow> for GeoPoint {
@@ -652,6 +979,77 @@ impl From<Map<String, Value>> for Payload {
     }
 }
 
+/// Payload of the retrieved point.
+///
+/// Payload of the storage, which keeps it serialized, is returned in the stored form:
+/// it is decoded only if the values are required, and is written into responses directly
+/// from the stored bytes, without building the JSON values of the payload.
+#[derive(Clone, Debug)]
+pub enum RecordPayload {
+    Payload(Payload),
+    /// Payload, serialized with CBOR
+    Cbor(Vec<u8>),
+}
+
+impl RecordPayload {
+    pub fn into_payload(self) -> OperationResult<Payload> {
+        match self {
+            RecordPayload::Payload(payload) => Ok(payload),
+            RecordPayload::Cbor(bytes) => Ok(serde_cbor::from_slice(&bytes)?),
+        }
+    }
+
+    pub fn to_payload(&self) -> OperationResult<Cow<Payload>> {
+        match self {
+            RecordPayload::Payload(payload) => Ok(Cow::Borrowed(payload)),
+            RecordPayload::Cbor(bytes) => Ok(Cow::Owned(serde_cbor::from_slice(bytes)?)),
+        }
+    }
+}
+
+impl From<Payload> for RecordPayload {
+    fn from(payload: Payload) -> Self {
+        RecordPayload::Payload(payload)
+    }
+}
+
+impl PartialEq for RecordPayload {
+    fn eq(&self, other: &Self) -> bool {
+        match (self.to_payload(), other.to_payload()) {
+            (Ok(this), Ok(other)) => this == other,
+            _ => false,
+        }
+    }
+}
+
+impl Serialize for RecordPayload {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            RecordPayload::Payload(payload) => payload.serialize(serializer),
+            RecordPayload::Cbor(bytes) => {
+                let mut deserializer = serde_cbor::Deserializer::from_slice(bytes);
+                Transcoder::new(&mut deserializer).serialize(serializer)
+            }
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for RecordPayload {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Payload::deserialize(deserializer).map(RecordPayload::Payload)
+    }
+}
+
+impl JsonSchema for RecordPayload {
+    fn schema_name() -> String {
+        Payload::schema_name()
+    }
+
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        Payload::json_schema(gen)
+    }
+}
+
 #[derive(Clone)]
 pub enum OwnedPayloadRef<'a> {
     Ref(&'a Payload),
@@ -737,6 +1135,7 @@ pub enum PayloadSchemaType {
     Float,
     Geo,
     Text,
+    Bool,
 }
 
 /// Payload type with parameters
@@ -780,7 +1179,7 @@ impl TryFrom<PayloadIndexInfo> for PayloadFieldSchema {
 pub fn value_type(value: &Value) -> Option<PayloadSchemaType> {
     match value {
         Value::Null => None,
-        Value::Bool(_) => None,
+        Value::Bool(_) => Some(PayloadSchemaType::Bool),
         Value::Number(num) => {
             if num.is_i64() {
                 Some(PayloadSchemaType::Integer)
@@ -1006,6 +1405,14 @@ impl GeoBoundingBox {
             && (self.bottom_right.lat < lat)
             && (lat < self.top_left.lat)
     }
+
+    /// Center of the area rectangle
+    pub fn center(&self) -> GeoPoint {
+        GeoPoint {
+            lon: (self.top_left.lon + self.bottom_right.lon) / 2.0,
+            lat: (self.top_left.lat + self.bottom_right.lat) / 2.0,
+        }
+    }
 }
 
 /// Geo filter request
@@ -1161,6 +1568,17 @@ pub enum WithPayloadInterface {
     Fields(Vec<String>),
     /// Specify included or excluded fields
     Selector(PayloadSelector),
+    /// Return payload statistics instead of the payload itself
+    Mode(PayloadMode),
+}
+
+/// Alternative representations of the payload in the response
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PayloadMode {
+    /// Return only number of top-level payload keys and size of the payload in bytes:
+    /// `{"keys": 3, "bytes": 120}`
+    Count,
 }
 
 /// Options for specifying which vector to include
@@ -1173,6 +1591,37 @@ pub enum WithVector {
     Bool(bool),
     /// Specify which vector to return
     Selector(Vec<String>),
+    /// Specify which dimensions of which vectors to return
+    Slices(Vec<VectorSlice>),
+}
+
+/// Range of dimensions of a named vector to return
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+#[serde(rename_all = "snake_case")]
+pub struct VectorSlice {
+    /// Name of the vector. Default vector if not specified
+    #[serde(default)]
+    pub name: String,
+    /// Index of the first dimension to return. Default: 0
+    #[serde(default)]
+    pub offset: usize,
+    /// Number of dimensions to return. Default: all dimensions starting from `offset`
+    pub limit: Option<usize>,
+}
+
+impl VectorSlice {
+    /// Cut selected range of dimensions from the vector, out of range dimensions are ignored
+    pub fn apply(&self, mut vector: Vec<VectorElementType>) -> Vec<VectorElementType> {
+        let start = self.offset.min(vector.len());
+        let end = match self.limit {
+            Some(limit) => start.saturating_add(limit).min(vector.len()),
+            None => vector.len(),
+        };
+        vector.truncate(end);
+        vector.drain(..start);
+        vector
+    }
 }
 
 impl WithVector {
@@ -1180,6 +1629,7 @@ impl WithVector {
         match self {
             WithVector::Bool(b) => *b,
             WithVector::Selector(_) => true,
+            WithVector::Slices(_) => true,
         }
     }
 }
@@ -1210,6 +1660,7 @@ impl From<bool> for WithPayload {
         WithPayload {
             enable: x,
             payload_selector: None,
+            count: false,
         }
     }
 }
@@ -1220,14 +1671,22 @@ impl From<&WithPayloadInterface> for WithPayload {
             WithPayloadInterface::Bool(x) => WithPayload {
                 enable: *x,
                 payload_selector: None,
+                count: false,
             },
             WithPayloadInterface::Fields(x) => WithPayload {
                 enable: true,
                 payload_selector: Some(PayloadSelector::new_include(x.clone())),
+                count: false,
             },
             WithPayloadInterface::Selector(x) => WithPayload {
                 enable: true,
                 payload_selector: Some(x.clone()),
+                count: false,
+            },
+            WithPayloadInterface::Mode(PayloadMode::Count) => WithPayload {
+                enable: true,
+                payload_selector: None,
+                count: true,
             },
         }
     }
@@ -1237,7 +1696,8 @@ impl From<&WithPayloadInterface> for WithPayload {
 #[serde(deny_unknown_fields)]
 #[serde(rename_all = "snake_case")]
 pub struct PayloadSelectorInclude {
-    /// Only include this payload keys
+    /// Only include this payload keys.
+    /// Nested keys could be selected with a path, e.g. `metadata.title`
     pub include: Vec<PayloadKeyType>,
 }
 
@@ -1251,7 +1711,8 @@ impl PayloadSelectorInclude {
 #[serde(deny_unknown_fields)]
 #[serde(rename_all = "snake_case")]
 pub struct PayloadSelectorExclude {
-    /// Exclude this fields from returning payload
+    /// Exclude this fields from returning payload.
+    /// Nested keys could be excluded with a path, e.g. `metadata.body`
     pub exclude: Vec<PayloadKeyType>,
 }
 
@@ -1300,9 +1761,24 @@ impl PayloadSelector {
     }
 
     pub fn process(&self, x: Payload) -> Payload {
-        let map: serde_json::Map<String, Value> =
-            x.into_iter().filter(|(key, _)| self.check(key)).collect();
-        map.into()
+        match self {
+            PayloadSelector::Include(selector) => {
+                let mut projection = serde_json::Map::new();
+                for path in &selector.include {
+                    utils::copy_value_by_json_path(path, &x.0, &mut projection);
+                }
+                projection.into()
+            }
+            PayloadSelector::Exclude(selector) => {
+                let mut payload = x;
+                for path in &selector.exclude {
+                    if payload.0.remove(path).is_none() {
+                        payload.remove(path);
+                    }
+                }
+                payload
+            }
+        }
     }
 }
 
@@ -1314,6 +1790,33 @@ pub struct WithPayload {
     pub enable: bool,
     /// Filter include and exclude payloads
     pub payload_selector: Option<PayloadSelector>,
+    /// Return only number of payload keys and payload size instead of the payload itself
+    #[serde(default)]
+    pub count: bool,
+}
+
+impl WithPayload {
+    /// Whether the stored payload of the point is returned as is
+    pub fn is_unchanged(&self) -> bool {
+        self.payload_selector.is_none() && !self.count
+    }
+
+    /// Convert stored payload of the point into the payload of the response
+    pub fn process(&self, payload: Payload) -> Payload {
+        let payload = match &self.payload_selector {
+            Some(selector) => selector.process(payload),
+            None => payload,
+        };
+        if self.count {
+            let bytes = serde_json::to_vec(&payload).map_or(0, |bytes| bytes.len());
+            let mut stats = Map::new();
+            stats.insert("keys".to_string(), payload.0.len().into());
+            stats.insert("bytes".to_string(), bytes.into());
+            Payload(stats)
+        } else {
+            payload
+        }
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
@@ -1397,6 +1900,48 @@ mod tests {
         eprintln!("{json}")
     }
 
+    #[test]
+    fn test_vector_slice_selector() {
+        let with_vector: WithVector =
+            serde_json::from_str(r#"[{"name": "image", "offset": 1, "limit": 2}, {}]"#).unwrap();
+        let slices = match with_vector {
+            WithVector::Slices(slices) => slices,
+            _ => panic!("Slices selector expected"),
+        };
+
+        assert_eq!(slices[0].name, "image");
+        assert_eq!(slices[0].apply(vec![1.0, 2.0, 3.0, 4.0]), vec![2.0, 3.0]);
+
+        assert_eq!(slices[1].name, "");
+        assert_eq!(slices[1].apply(vec![1.0, 2.0]), vec![1.0, 2.0]);
+
+        let out_of_range = VectorSlice {
+            name: "".to_string(),
+            offset: 3,
+            limit: Some(10),
+        };
+        assert_eq!(out_of_range.apply(vec![1.0, 2.0, 3.0, 4.0]), vec![4.0]);
+        assert!(out_of_range.apply(vec![1.0]).is_empty());
+
+        let with_vector: WithVector = serde_json::from_str(r#"["image"]"#).unwrap();
+        assert_eq!(with_vector, WithVector::Selector(vec!["image".to_string()]));
+    }
+
+    #[test]
+    fn test_with_payload_count() {
+        let interface: WithPayloadInterface = serde_json::from_str(r#""count""#).unwrap();
+        assert!(matches!(
+            interface,
+            WithPayloadInterface::Mode(PayloadMode::Count)
+        ));
+
+        let payload: Payload = serde_json::from_str(r#"{"a": 1, "b": [1, 2]}"#).unwrap();
+        let bytes = serde_json::to_vec(&payload).unwrap().len();
+        let stats = WithPayload::from(&interface).process(payload);
+        assert_eq!(stats.0.get("keys"), Some(&Value::from(2)));
+        assert_eq!(stats.0.get("bytes"), Some(&Value::from(bytes)));
+    }
+
     #[test]
     fn test_deny_unknown_fields() {
         let query1 = r#"
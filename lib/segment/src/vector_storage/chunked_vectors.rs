@@ -40,6 +40,15 @@ impl<T: Copy + Clone + Default> ChunkedVectors<T> {
         self.len == 0
     }
 
+    /// Size of allocated chunks in bytes
+    pub fn ram_usage_bytes(&self) -> usize {
+        self.chunks
+            .iter()
+            .map(|chunk| chunk.capacity())
+            .sum::<usize>()
+            * mem::size_of::<T>()
+    }
+
     pub fn get<TKey>(&self, key: TKey) -> &[T]
     where
         TKey: num_traits::cast::AsPrimitive<usize>,
@@ -16,7 +16,9 @@ use crate::entry::entry_point::{check_process_stopped, OperationResult};
 use crate::types::{Distance, PointOffsetType, QuantizationConfig};
 use crate::vector_storage::mmap_vectors::MmapVectors;
 use crate::vector_storage::quantized::quantized_vectors_base::QuantizedVectors;
-use crate::vector_storage::VectorStorage;
+use crate::vector_storage::{DenseVectorStorage, VectorStorage};
+
+pub const VECTORS_PATH: &str = "matrix.dat";
 
 fn vf_to_u8<T>(v: &[T]) -> &[u8] {
     unsafe { std::slice::from_raw_parts(v.as_ptr() as *const u8, v.len() * size_of::<T>()) }
@@ -41,7 +43,7 @@ pub fn open_memmap_vector_storage(
 ) -> OperationResult<Arc<AtomicRefCell<VectorStorageEnum>>> {
     create_dir_all(path)?;
 
-    let vectors_path = path.join("matrix.dat");
+    let vectors_path = path.join(VECTORS_PATH);
     let mmap_store = MmapVectors::open(&vectors_path, dim)?;
 
     Ok(Arc::new(AtomicRefCell::new(VectorStorageEnum::Memmap(
@@ -53,6 +55,12 @@ pub fn open_memmap_vector_storage(
     ))))
 }
 
+impl DenseVectorStorage for MemmapVectorStorage {
+    fn get_dense(&self, key: PointOffsetType) -> &[VectorElementType] {
+        self.mmap_store.as_ref().unwrap().get_vector(key)
+    }
+}
+
 impl VectorStorage for MemmapVectorStorage {
     fn vector_dim(&self) -> usize {
         self.mmap_store.as_ref().unwrap().dim
@@ -66,8 +74,8 @@ impl VectorStorage for MemmapVectorStorage {
         self.mmap_store.as_ref().unwrap().num_vectors
     }
 
-    fn get_vector(&self, key: PointOffsetType) -> &[VectorElementType] {
-        self.mmap_store.as_ref().unwrap().get_vector(key)
+    fn get_vector(&self, key: PointOffsetType) -> OperationResult<&[VectorElementType]> {
+        Ok(self.get_dense(key))
     }
 
     fn insert_vector(
@@ -101,7 +109,7 @@ impl VectorStorage for MemmapVectorStorage {
 
             for id in other_ids {
                 check_process_stopped(stopped)?;
-                let vector = other.get_vector(id);
+                let vector = other.get_vector(id)?;
                 let raw_bites = vf_to_u8(vector);
                 file.write_all(raw_bites)?;
                 end_index += 1;
@@ -146,6 +154,10 @@ impl VectorStorage for MemmapVectorStorage {
         }
         files
     }
+
+    fn ram_usage_bytes(&self) -> usize {
+        0
+    }
 }
 
 #[cfg(test)]
@@ -196,7 +208,7 @@ mod tests {
 
         assert_eq!(borrowed_storage.total_vector_count(), 3);
 
-        let vector = borrowed_storage.get_vector(1).to_vec();
+        let vector = borrowed_storage.get_vector(1).unwrap().to_vec();
 
         assert_eq!(points[1], vector);
 
@@ -359,6 +371,7 @@ mod tests {
             r#type: Default::default(),
             quantile: None,
             always_ram: None,
+            quantized_only: None,
         }
         .into();
 
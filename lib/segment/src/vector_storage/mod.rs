@@ -2,8 +2,11 @@ pub mod chunked_vectors;
 pub mod memmap_vector_storage;
 mod mmap_vectors;
 pub mod quantized;
+pub mod quantized_only_vector_storage;
 pub mod raw_scorer;
+pub mod scoring_benchmark;
 pub mod simple_vector_storage;
+pub mod sparse_vector_storage;
 mod vector_storage_base;
 
 pub use raw_scorer::*;
@@ -1,3 +1,4 @@
+mod product_quantized;
 pub mod quantized_vectors_base;
 mod scalar_quantized;
 mod scalar_quantized_mmap_storage;
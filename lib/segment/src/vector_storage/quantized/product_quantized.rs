@@ -0,0 +1,490 @@
+use std::path::{Path, PathBuf};
+
+use bitvec::prelude::BitVec;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::common::file_operations::{atomic_save_json, read_json};
+use crate::data_types::vectors::VectorElementType;
+use crate::entry::entry_point::OperationResult;
+use crate::spaces::tools::peek_top_largest_iterable;
+use crate::types::{Distance, PointOffsetType, ProductQuantizationConfig, ScoreType};
+use crate::vector_storage::chunked_vectors::ChunkedVectors;
+use crate::vector_storage::quantized::quantized_vectors_base::QuantizedVectors;
+use crate::vector_storage::quantized::scalar_quantized::{
+    QUANTIZED_DATA_PATH, QUANTIZED_META_PATH,
+};
+use crate::vector_storage::quantized::scalar_quantized_mmap_storage::{
+    QuantizedMmapStorage, QuantizedMmapStorageBuilder,
+};
+use crate::vector_storage::{RawScorer, ScoredPointOffset};
+
+/// Max number of centroids per bucket, so that each centroid index fits into a byte
+const CENTROIDS_COUNT: usize = 256;
+
+/// Max number of vectors used to train codebooks
+const TRAINING_SAMPLE_SIZE: usize = 10_000;
+
+const KMEANS_ITERATIONS: usize = 10;
+
+const KMEANS_SEED: u64 = 42;
+
+/// Codebooks of the product quantization.
+/// Vector is split into buckets of `bucket_size` dimensions,
+/// each bucket is encoded with the index of the closest centroid of this bucket.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct ProductQuantizedMeta {
+    dim: usize,
+    bucket_size: usize,
+    /// Flattened centroids of each bucket
+    centroids: Vec<Vec<VectorElementType>>,
+}
+
+impl ProductQuantizedMeta {
+    fn buckets_count(&self) -> usize {
+        buckets_count(self.dim, self.bucket_size)
+    }
+
+    fn bucket_dim(&self, bucket: usize) -> usize {
+        bucket_dim(self.dim, self.bucket_size, bucket)
+    }
+
+    fn centroid(&self, bucket: usize, code: u8) -> &[VectorElementType] {
+        let bucket_dim = self.bucket_dim(bucket);
+        let offset = code as usize * bucket_dim;
+        &self.centroids[bucket][offset..offset + bucket_dim]
+    }
+
+    fn encode(&self, vector: &[VectorElementType]) -> Vec<u8> {
+        (0..self.buckets_count())
+            .map(|bucket| {
+                let bucket_dim = self.bucket_dim(bucket);
+                let start = bucket * self.bucket_size;
+                nearest_centroid(
+                    &vector[start..start + bucket_dim],
+                    &self.centroids[bucket],
+                    bucket_dim,
+                ) as u8
+            })
+            .collect()
+    }
+}
+
+fn buckets_count(dim: usize, bucket_size: usize) -> usize {
+    (dim + bucket_size - 1) / bucket_size
+}
+
+/// Size of the bucket, the last bucket might be shorter than others
+fn bucket_dim(dim: usize, bucket_size: usize, bucket: usize) -> usize {
+    bucket_size.min(dim - bucket * bucket_size)
+}
+
+fn squared_distance(a: &[VectorElementType], b: &[VectorElementType]) -> ScoreType {
+    a.iter().zip(b).map(|(a, b)| (a - b) * (a - b)).sum()
+}
+
+/// Similarity of the parts of vectors, sum of similarities of all parts gives the similarity of whole vectors
+fn partial_similarity(
+    distance: Distance,
+    a: &[VectorElementType],
+    b: &[VectorElementType],
+) -> ScoreType {
+    match distance {
+        Distance::Cosine | Distance::Dot => a.iter().zip(b).map(|(a, b)| a * b).sum(),
+        Distance::Euclid => -squared_distance(a, b),
+    }
+}
+
+fn nearest_centroid(
+    vector: &[VectorElementType],
+    centroids: &[VectorElementType],
+    dim: usize,
+) -> usize {
+    centroids
+        .chunks_exact(dim)
+        .map(|centroid| squared_distance(vector, centroid))
+        .enumerate()
+        .min_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map(|(idx, _)| idx)
+        .unwrap_or_default()
+}
+
+/// K-means clustering of the given bucket of sample vectors, returns flattened centroids
+fn train_bucket(samples: &[Vec<VectorElementType>], start: usize, dim: usize) -> Vec<f32> {
+    let points: Vec<&[VectorElementType]> =
+        samples.iter().map(|v| &v[start..start + dim]).collect();
+    let centroids_count = CENTROIDS_COUNT.min(points.len());
+
+    let mut rng = StdRng::seed_from_u64(KMEANS_SEED);
+    let mut centroids: Vec<VectorElementType> =
+        rand::seq::index::sample(&mut rng, points.len(), centroids_count)
+            .into_iter()
+            .flat_map(|idx| points[idx].iter().copied())
+            .collect();
+
+    for _ in 0..KMEANS_ITERATIONS {
+        let mut sums = vec![0.0; centroids.len()];
+        let mut counts = vec![0usize; centroids_count];
+        for point in &points {
+            let closest = nearest_centroid(point, &centroids, dim);
+            counts[closest] += 1;
+            for (sum, value) in sums[closest * dim..(closest + 1) * dim]
+                .iter_mut()
+                .zip(point.iter())
+            {
+                *sum += value;
+            }
+        }
+        // Empty clusters keep the centroid from the previous iteration
+        for (idx, count) in counts.into_iter().enumerate() {
+            if count > 0 {
+                for (centroid, sum) in centroids[idx * dim..(idx + 1) * dim]
+                    .iter_mut()
+                    .zip(&sums[idx * dim..(idx + 1) * dim])
+                {
+                    *centroid = sum / count as f32;
+                }
+            }
+        }
+    }
+    centroids
+}
+
+fn train<'a>(
+    vectors: impl IntoIterator<Item = &'a [VectorElementType]>,
+    config: &ProductQuantizationConfig,
+    dim: usize,
+    count: usize,
+) -> ProductQuantizedMeta {
+    let bucket_size = config.compression.bucket_size();
+    // Take evenly spaced vectors, so that the sample covers all the segment
+    let step = ((count + TRAINING_SAMPLE_SIZE - 1) / TRAINING_SAMPLE_SIZE).max(1);
+    let samples: Vec<Vec<VectorElementType>> = vectors
+        .into_iter()
+        .step_by(step)
+        .take(TRAINING_SAMPLE_SIZE)
+        .map(|vector| vector.to_vec())
+        .collect();
+
+    let centroids = if samples.is_empty() {
+        vec![vec![0.0; bucket_size]; buckets_count(dim, bucket_size)]
+    } else {
+        (0..buckets_count(dim, bucket_size))
+            .into_par_iter()
+            .map(|bucket| {
+                train_bucket(
+                    &samples,
+                    bucket * bucket_size,
+                    bucket_dim(dim, bucket_size, bucket),
+                )
+            })
+            .collect()
+    };
+
+    ProductQuantizedMeta {
+        dim,
+        bucket_size,
+        centroids,
+    }
+}
+
+pub struct ProductQuantizedRawScorer<'a, TStorage: quantization::EncodedStorage> {
+    /// Similarity of the query to each centroid of each bucket, `CENTROIDS_COUNT` values per bucket
+    lookup_table: Vec<ScoreType>,
+    deleted: &'a BitVec,
+    quantized_data: &'a ProductQuantizedVectors<TStorage>,
+}
+
+impl<TStorage: quantization::EncodedStorage + Send + Sync> RawScorer
+    for ProductQuantizedRawScorer<'_, TStorage>
+{
+    fn score_points(&self, points: &[PointOffsetType], scores: &mut [ScoredPointOffset]) -> usize {
+        let mut size: usize = 0;
+        for point_id in points.iter().copied() {
+            if !self.check_point(point_id) {
+                continue;
+            }
+            scores[size] = ScoredPointOffset {
+                idx: point_id,
+                score: self.score_point(point_id),
+            };
+            size += 1;
+            if size == scores.len() {
+                return size;
+            }
+        }
+        size
+    }
+
+    fn check_point(&self, point: PointOffsetType) -> bool {
+        (point as usize) < self.deleted.len() && !self.deleted[point as usize]
+    }
+
+    fn score_point(&self, point: PointOffsetType) -> ScoreType {
+        self.quantized_data
+            .codes(point)
+            .iter()
+            .enumerate()
+            .map(|(bucket, code)| self.lookup_table[bucket * CENTROIDS_COUNT + *code as usize])
+            .sum()
+    }
+
+    fn score_internal(&self, point_a: PointOffsetType, point_b: PointOffsetType) -> ScoreType {
+        self.quantized_data.score_internal(point_a, point_b)
+    }
+
+    fn peek_top_iter(
+        &self,
+        points: &mut dyn Iterator<Item = PointOffsetType>,
+        top: usize,
+    ) -> Vec<ScoredPointOffset> {
+        let scores = points.filter(|idx| self.check_point(*idx)).map(|idx| {
+            let score = self.score_point(idx);
+            ScoredPointOffset { idx, score }
+        });
+        peek_top_largest_iterable(scores, top)
+    }
+
+    fn peek_top_all(&self, top: usize) -> Vec<ScoredPointOffset> {
+        let scores = (0..self.deleted.len() as PointOffsetType)
+            .filter(|idx| self.check_point(*idx))
+            .map(|idx| {
+                let score = self.score_point(idx);
+                ScoredPointOffset { idx, score }
+            });
+        peek_top_largest_iterable(scores, top)
+    }
+}
+
+pub struct ProductQuantizedVectors<TStorage: quantization::EncodedStorage> {
+    storage: TStorage,
+    meta: ProductQuantizedMeta,
+    distance: Distance,
+}
+
+impl<TStorage: quantization::EncodedStorage + Send + Sync> ProductQuantizedVectors<TStorage> {
+    /// Train codebooks on the given vectors and encode all of them
+    fn encode<'a>(
+        vectors: impl IntoIterator<Item = &'a [VectorElementType]> + Clone,
+        mut storage_builder: impl quantization::EncodedStorageBuilder<TStorage>,
+        config: &ProductQuantizationConfig,
+        dim: usize,
+        count: usize,
+        distance: Distance,
+    ) -> Self {
+        let meta = train(vectors.clone(), config, dim, count);
+        for vector in vectors {
+            storage_builder.push_vector_data(&meta.encode(vector));
+        }
+        Self {
+            storage: storage_builder.build(),
+            meta,
+            distance,
+        }
+    }
+
+    fn load(path: &Path, count: usize, distance: Distance) -> OperationResult<Self> {
+        let meta: ProductQuantizedMeta = read_json(&path.join(QUANTIZED_META_PATH))?;
+        let storage =
+            TStorage::from_file(&path.join(QUANTIZED_DATA_PATH), meta.buckets_count(), count)?;
+        Ok(Self {
+            storage,
+            meta,
+            distance,
+        })
+    }
+
+    fn codes(&self, point: PointOffsetType) -> &[u8] {
+        self.storage
+            .get_vector_data(point as usize, self.meta.buckets_count())
+    }
+
+    fn score_internal(&self, point_a: PointOffsetType, point_b: PointOffsetType) -> ScoreType {
+        self.codes(point_a)
+            .iter()
+            .zip(self.codes(point_b))
+            .enumerate()
+            .map(|(bucket, (code_a, code_b))| {
+                partial_similarity(
+                    self.distance,
+                    self.meta.centroid(bucket, *code_a),
+                    self.meta.centroid(bucket, *code_b),
+                )
+            })
+            .sum()
+    }
+
+    /// Size of the single encoded vector in bytes
+    pub fn encoded_vector_size(&self) -> usize {
+        self.meta.buckets_count()
+    }
+
+    /// Size of the single encoded vector in bytes, for the given config
+    pub fn quantized_vector_size(config: &ProductQuantizationConfig, dim: usize) -> usize {
+        buckets_count(dim, config.compression.bucket_size())
+    }
+}
+
+impl<TStorage> QuantizedVectors for ProductQuantizedVectors<TStorage>
+where
+    TStorage: quantization::EncodedStorage + Send + Sync,
+{
+    fn raw_scorer<'a>(
+        &'a self,
+        query: &[VectorElementType],
+        deleted: &'a BitVec,
+    ) -> Box<dyn RawScorer + 'a> {
+        let query = self
+            .distance
+            .preprocess_vector(query)
+            .unwrap_or_else(|| query.to_vec());
+
+        // Asymmetric distance: the query is not quantized,
+        // its similarity to every centroid is calculated once per search
+        let mut lookup_table = vec![0.0; self.meta.buckets_count() * CENTROIDS_COUNT];
+        for bucket in 0..self.meta.buckets_count() {
+            let bucket_dim = self.meta.bucket_dim(bucket);
+            let start = bucket * self.meta.bucket_size;
+            let query_part = &query[start..start + bucket_dim];
+            for (code, centroid) in self.meta.centroids[bucket]
+                .chunks_exact(bucket_dim)
+                .enumerate()
+            {
+                lookup_table[bucket * CENTROIDS_COUNT + code] =
+                    partial_similarity(self.distance, query_part, centroid);
+            }
+        }
+
+        Box::new(ProductQuantizedRawScorer {
+            lookup_table,
+            deleted,
+            quantized_data: self,
+        })
+    }
+
+    fn save_to(&self, path: &Path) -> OperationResult<()> {
+        self.storage.save_to_file(&path.join(QUANTIZED_DATA_PATH))?;
+        atomic_save_json(&path.join(QUANTIZED_META_PATH), &self.meta)?;
+        Ok(())
+    }
+
+    fn files(&self) -> Vec<PathBuf> {
+        vec![QUANTIZED_DATA_PATH.into(), QUANTIZED_META_PATH.into()]
+    }
+}
+
+pub fn create_product_quantized_vectors_ram<'a>(
+    vectors: impl IntoIterator<Item = &'a [f32]> + Clone,
+    config: &ProductQuantizationConfig,
+    vector_parameters: &quantization::VectorParameters,
+    distance: Distance,
+) -> OperationResult<ProductQuantizedVectors<ChunkedVectors<u8>>> {
+    let quantized_vector_size =
+        ProductQuantizedVectors::<ChunkedVectors<u8>>::quantized_vector_size(
+            config,
+            vector_parameters.dim,
+        );
+    let storage_builder = ChunkedVectors::<u8>::new(quantized_vector_size);
+    Ok(ProductQuantizedVectors::encode(
+        vectors,
+        storage_builder,
+        config,
+        vector_parameters.dim,
+        vector_parameters.count,
+        distance,
+    ))
+}
+
+pub fn create_product_quantized_vectors_mmap<'a>(
+    vectors: impl IntoIterator<Item = &'a [f32]> + Clone,
+    config: &ProductQuantizationConfig,
+    vector_parameters: &quantization::VectorParameters,
+    data_path: &Path,
+    distance: Distance,
+) -> OperationResult<ProductQuantizedVectors<QuantizedMmapStorage>> {
+    let quantized_vector_size =
+        ProductQuantizedVectors::<QuantizedMmapStorage>::quantized_vector_size(
+            config,
+            vector_parameters.dim,
+        );
+    let storage_builder = QuantizedMmapStorageBuilder::new(
+        data_path.join(QUANTIZED_DATA_PATH).as_path(),
+        vector_parameters.count,
+        quantized_vector_size,
+    )?;
+    Ok(ProductQuantizedVectors::encode(
+        vectors,
+        storage_builder,
+        config,
+        vector_parameters.dim,
+        vector_parameters.count,
+        distance,
+    ))
+}
+
+pub fn load_product_quantized_vectors_ram(
+    path: &Path,
+    vector_parameters: &quantization::VectorParameters,
+    distance: Distance,
+) -> OperationResult<ProductQuantizedVectors<ChunkedVectors<u8>>> {
+    ProductQuantizedVectors::load(path, vector_parameters.count, distance)
+}
+
+pub fn load_product_quantized_vectors_mmap(
+    path: &Path,
+    vector_parameters: &quantization::VectorParameters,
+    distance: Distance,
+) -> OperationResult<ProductQuantizedVectors<QuantizedMmapStorage>> {
+    ProductQuantizedVectors::load(path, vector_parameters.count, distance)
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+
+    use super::*;
+    use crate::spaces::metric::Metric;
+    use crate::spaces::simple::{DotProductMetric, EuclidMetric};
+    use crate::types::CompressionRatio;
+
+    #[test]
+    fn test_product_quantization_scores() {
+        let dim = 10;
+        let count = 300;
+        let mut rng = StdRng::seed_from_u64(1);
+        let vectors: Vec<Vec<f32>> = (0..count)
+            .map(|_| (0..dim).map(|_| rng.gen_range(-1.0..1.0)).collect())
+            .collect();
+        let config = ProductQuantizationConfig {
+            compression: CompressionRatio::X8,
+            always_ram: None,
+        };
+        let deleted = BitVec::repeat(false, count);
+        let query: Vec<f32> = (0..dim).map(|_| rng.gen_range(-1.0..1.0)).collect();
+
+        for distance in [Distance::Dot, Distance::Euclid] {
+            let quantized = ProductQuantizedVectors::encode(
+                vectors.iter().map(|v| v.as_slice()),
+                ChunkedVectors::<u8>::new(buckets_count(dim, 2)),
+                &config,
+                dim,
+                count,
+                distance,
+            );
+            let scorer = quantized.raw_scorer(&query, &deleted);
+            for (idx, vector) in vectors.iter().enumerate() {
+                let expected = match distance {
+                    Distance::Euclid => EuclidMetric::similarity(&query, vector),
+                    _ => DotProductMetric::similarity(&query, vector),
+                };
+                let score = scorer.score_point(idx as PointOffsetType);
+                assert!(
+                    (score - expected).abs() < 0.5,
+                    "{distance:?}: {score} != {expected}"
+                );
+            }
+        }
+    }
+}
@@ -6,8 +6,13 @@ use serde::{Deserialize, Serialize};
 use crate::common::file_operations::{atomic_save_json, read_json};
 use crate::data_types::vectors::VectorElementType;
 use crate::entry::entry_point::OperationResult;
-use crate::types::{Distance, QuantizationConfig, ScalarQuantization, ScalarQuantizationConfig};
+use crate::types::{Distance, ProductQuantization, QuantizationConfig, ScalarQuantization};
 use crate::vector_storage::chunked_vectors::ChunkedVectors;
+use crate::vector_storage::quantized::product_quantized::{
+    create_product_quantized_vectors_mmap, create_product_quantized_vectors_ram,
+    load_product_quantized_vectors_mmap, load_product_quantized_vectors_ram,
+    ProductQuantizedVectors,
+};
 use crate::vector_storage::quantized::scalar_quantized::ScalarQuantizedVectors;
 use crate::vector_storage::quantized::scalar_quantized_mmap_storage::{
     create_scalar_quantized_vectors_mmap, load_scalar_quantized_vectors_mmap, QuantizedMmapStorage,
@@ -28,6 +33,8 @@ pub struct QuantizedVectorsConfig {
 pub enum QuantizedVectorStorageImpl {
     ScalarRam(ScalarQuantizedVectors<ChunkedVectors<u8>>),
     ScalarMmap(ScalarQuantizedVectors<QuantizedMmapStorage>),
+    ProductRam(ProductQuantizedVectors<ChunkedVectors<u8>>),
+    ProductMmap(ProductQuantizedVectors<QuantizedMmapStorage>),
 }
 
 pub struct QuantizedVectorsStorage {
@@ -58,6 +65,8 @@ impl QuantizedVectors for QuantizedVectorsStorage {
         match &self.storage_impl {
             QuantizedVectorStorageImpl::ScalarRam(storage) => storage.raw_scorer(query, deleted),
             QuantizedVectorStorageImpl::ScalarMmap(storage) => storage.raw_scorer(query, deleted),
+            QuantizedVectorStorageImpl::ProductRam(storage) => storage.raw_scorer(query, deleted),
+            QuantizedVectorStorageImpl::ProductMmap(storage) => storage.raw_scorer(query, deleted),
         }
     }
 
@@ -65,6 +74,8 @@ impl QuantizedVectors for QuantizedVectorsStorage {
         match &self.storage_impl {
             QuantizedVectorStorageImpl::ScalarRam(storage) => storage.save_to(path),
             QuantizedVectorStorageImpl::ScalarMmap(storage) => storage.save_to(path),
+            QuantizedVectorStorageImpl::ProductRam(storage) => storage.save_to(path),
+            QuantizedVectorStorageImpl::ProductMmap(storage) => storage.save_to(path),
         }
     }
 
@@ -73,6 +84,8 @@ impl QuantizedVectors for QuantizedVectorsStorage {
         let storage_files = match &self.storage_impl {
             QuantizedVectorStorageImpl::ScalarRam(storage) => storage.files(),
             QuantizedVectorStorageImpl::ScalarMmap(storage) => storage.files(),
+            QuantizedVectorStorageImpl::ProductRam(storage) => storage.files(),
+            QuantizedVectorStorageImpl::ProductMmap(storage) => storage.files(),
         };
 
         result.extend(storage_files.into_iter().map(|file| self.path.join(file)));
@@ -82,10 +95,10 @@ impl QuantizedVectors for QuantizedVectorsStorage {
 
 impl QuantizedVectorsStorage {
     fn check_use_ram_quantization_storage(
-        config: &ScalarQuantizationConfig,
+        always_ram: Option<bool>,
         on_disk_vector_storage: bool,
     ) -> bool {
-        !on_disk_vector_storage || config.always_ram == Some(true)
+        !on_disk_vector_storage || always_ram == Some(true)
     }
 
     fn construct_vector_parameters(
@@ -120,8 +133,10 @@ impl QuantizedVectorsStorage {
             QuantizationConfig::Scalar(ScalarQuantization {
                 scalar: scalar_config,
             }) => {
-                let in_ram =
-                    Self::check_use_ram_quantization_storage(scalar_config, on_disk_vector_storage);
+                let in_ram = Self::check_use_ram_quantization_storage(
+                    scalar_config.always_ram,
+                    on_disk_vector_storage,
+                );
                 if in_ram {
                     let storage = create_scalar_quantized_vectors_ram(
                         vectors,
@@ -141,6 +156,32 @@ impl QuantizedVectorsStorage {
                     QuantizedVectorStorageImpl::ScalarMmap(storage)
                 }
             }
+            QuantizationConfig::Product(ProductQuantization {
+                product: product_config,
+            }) => {
+                let in_ram = Self::check_use_ram_quantization_storage(
+                    product_config.always_ram,
+                    on_disk_vector_storage,
+                );
+                if in_ram {
+                    let storage = create_product_quantized_vectors_ram(
+                        vectors,
+                        product_config,
+                        &vector_parameters,
+                        distance,
+                    )?;
+                    QuantizedVectorStorageImpl::ProductRam(storage)
+                } else {
+                    let storage = create_product_quantized_vectors_mmap(
+                        vectors,
+                        product_config,
+                        &vector_parameters,
+                        path,
+                        distance,
+                    )?;
+                    QuantizedVectorStorageImpl::ProductMmap(storage)
+                }
+            }
         };
 
         let quantized_vectors_config = QuantizedVectorsConfig {
@@ -174,7 +215,7 @@ impl QuantizedVectorsStorage {
                 scalar: scalar_u8_config,
             }) => {
                 let is_ram = Self::check_use_ram_quantization_storage(
-                    scalar_u8_config,
+                    scalar_u8_config.always_ram,
                     on_disk_vector_storage,
                 );
                 if is_ram {
@@ -193,6 +234,29 @@ impl QuantizedVectorsStorage {
                     QuantizedVectorStorageImpl::ScalarMmap(storage)
                 }
             }
+            QuantizationConfig::Product(ProductQuantization {
+                product: product_config,
+            }) => {
+                let is_ram = Self::check_use_ram_quantization_storage(
+                    product_config.always_ram,
+                    on_disk_vector_storage,
+                );
+                if is_ram {
+                    let storage = load_product_quantized_vectors_ram(
+                        data_path,
+                        &config.vector_parameters,
+                        distance,
+                    )?;
+                    QuantizedVectorStorageImpl::ProductRam(storage)
+                } else {
+                    let storage = load_product_quantized_vectors_mmap(
+                        data_path,
+                        &config.vector_parameters,
+                        distance,
+                    )?;
+                    QuantizedVectorStorageImpl::ProductMmap(storage)
+                }
+            }
         };
 
         Ok(QuantizedVectorsStorage {
@@ -201,4 +265,28 @@ impl QuantizedVectorsStorage {
             path: data_path.to_path_buf(),
         })
     }
+
+    /// Number of quantized vectors, including deleted
+    pub fn vectors_count(&self) -> usize {
+        self.config.vector_parameters.count
+    }
+
+    /// Estimated size of quantized vectors, kept in RAM
+    pub fn ram_usage_bytes(&self) -> usize {
+        match &self.storage_impl {
+            QuantizedVectorStorageImpl::ScalarRam(_) => {
+                let vector_parameters = &self.config.vector_parameters;
+                let quantized_vector_size =
+                    quantization::EncodedVectorsU8::<ChunkedVectors<u8>>::get_quantized_vector_size(
+                        vector_parameters,
+                    );
+                vector_parameters.count * quantized_vector_size
+            }
+            QuantizedVectorStorageImpl::ProductRam(storage) => {
+                self.config.vector_parameters.count * storage.encoded_vector_size()
+            }
+            QuantizedVectorStorageImpl::ScalarMmap(_)
+            | QuantizedVectorStorageImpl::ProductMmap(_) => 0,
+        }
+    }
 }
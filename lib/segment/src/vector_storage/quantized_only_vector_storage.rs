@@ -0,0 +1,116 @@
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+
+use atomic_refcell::AtomicRefCell;
+
+use super::quantized::quantized_vectors_base::{QuantizedVectors, QuantizedVectorsStorage};
+use super::{VectorStorage, VectorStorageEnum};
+use crate::common::Flusher;
+use crate::data_types::vectors::VectorElementType;
+use crate::entry::entry_point::{OperationError, OperationResult};
+use crate::types::{Distance, PointOffsetType, QuantizationConfig};
+
+/// Stores only quantized vectors, original vectors are removed
+///
+/// Scoring is always performed with quantized vectors, original vectors can't be read.
+/// Storage can only be opened from quantized vectors of an optimized segment,
+/// it is not possible to insert or update vectors.
+pub struct QuantizedOnlyVectorStorage {
+    vector_name: String,
+    dim: usize,
+    distance: Distance,
+    quantized_vectors: QuantizedVectorsStorage,
+}
+
+pub fn open_quantized_only_vector_storage(
+    path: &Path,
+    vector_name: &str,
+    dim: usize,
+    distance: Distance,
+    on_disk_vector_storage: bool,
+) -> OperationResult<Arc<AtomicRefCell<VectorStorageEnum>>> {
+    let quantized_vectors = QuantizedVectorsStorage::load(path, on_disk_vector_storage, distance)?;
+
+    Ok(Arc::new(AtomicRefCell::new(
+        VectorStorageEnum::QuantizedOnly(QuantizedOnlyVectorStorage {
+            vector_name: vector_name.to_owned(),
+            dim,
+            distance,
+            quantized_vectors,
+        }),
+    )))
+}
+
+impl VectorStorage for QuantizedOnlyVectorStorage {
+    fn vector_dim(&self) -> usize {
+        self.dim
+    }
+
+    fn distance(&self) -> Distance {
+        self.distance
+    }
+
+    fn total_vector_count(&self) -> usize {
+        self.quantized_vectors.vectors_count()
+    }
+
+    fn get_vector(&self, _key: PointOffsetType) -> OperationResult<&[VectorElementType]> {
+        Err(OperationError::VectorsNotStored {
+            vector_name: self.vector_name.clone(),
+        })
+    }
+
+    fn insert_vector(
+        &mut self,
+        _key: PointOffsetType,
+        _vector: &[VectorElementType],
+    ) -> OperationResult<()> {
+        Err(OperationError::VectorsNotStored {
+            vector_name: self.vector_name.clone(),
+        })
+    }
+
+    fn update_from(
+        &mut self,
+        _other: &VectorStorageEnum,
+        _other_ids: &mut dyn Iterator<Item = PointOffsetType>,
+        _stopped: &AtomicBool,
+    ) -> OperationResult<Range<PointOffsetType>> {
+        Err(OperationError::service_error(
+            "Can't update vectors of quantized-only storage",
+        ))
+    }
+
+    fn flusher(&self) -> Flusher {
+        Box::new(|| Ok(()))
+    }
+
+    fn quantize(
+        &mut self,
+        _data_path: &Path,
+        _quantization_config: &QuantizationConfig,
+    ) -> OperationResult<()> {
+        Err(OperationError::service_error(
+            "Can't quantize vectors of quantized-only storage, original vectors are removed",
+        ))
+    }
+
+    fn load_quantization(&mut self, _data_path: &Path) -> OperationResult<()> {
+        // Quantized vectors are loaded on open
+        Ok(())
+    }
+
+    fn quantized_storage(&self) -> Option<&QuantizedVectorsStorage> {
+        Some(&self.quantized_vectors)
+    }
+
+    fn files(&self) -> Vec<PathBuf> {
+        self.quantized_vectors.files()
+    }
+
+    fn ram_usage_bytes(&self) -> usize {
+        0
+    }
+}
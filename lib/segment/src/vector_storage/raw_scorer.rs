@@ -2,7 +2,8 @@ use std::marker::PhantomData;
 
 use bitvec::vec::BitVec;
 
-use super::{ScoredPointOffset, VectorStorage, VectorStorageEnum};
+use super::quantized::quantized_vectors_base::QuantizedVectors;
+use super::{DenseVectorStorage, ScoredPointOffset, VectorStorage, VectorStorageEnum};
 use crate::data_types::vectors::VectorElementType;
 use crate::spaces::metric::Metric;
 use crate::spaces::simple::{CosineMetric, DotProductMetric, EuclidMetric};
@@ -33,7 +34,7 @@ pub trait RawScorer {
     fn peek_top_all(&self, top: usize) -> Vec<ScoredPointOffset>;
 }
 
-pub struct RawScorerImpl<'a, TMetric: Metric, TVectorStorage: VectorStorage> {
+pub struct RawScorerImpl<'a, TMetric: Metric, TVectorStorage: DenseVectorStorage> {
     pub points_count: PointOffsetType,
     pub query: Vec<VectorElementType>,
     pub vector_storage: &'a TVectorStorage,
@@ -53,10 +54,15 @@ pub fn new_raw_scorer<'a>(
         VectorStorageEnum::Memmap(vector_storage) => {
             raw_scorer_impl(vector, vector_storage.as_ref(), deleted)
         }
+        // Original vectors are not available, score with quantized ones
+        VectorStorageEnum::QuantizedOnly(vector_storage) => vector_storage
+            .quantized_storage()
+            .unwrap()
+            .raw_scorer(&vector, deleted),
     }
 }
 
-fn raw_scorer_impl<'a, TVectorStorage: VectorStorage>(
+fn raw_scorer_impl<'a, TVectorStorage: DenseVectorStorage>(
     vector: Vec<VectorElementType>,
     vector_storage: &'a TVectorStorage,
     deleted: &'a BitVec,
@@ -90,7 +96,7 @@ fn raw_scorer_impl<'a, TVectorStorage: VectorStorage>(
 impl<'a, TMetric, TVectorStorage> RawScorer for RawScorerImpl<'a, TMetric, TVectorStorage>
 where
     TMetric: Metric,
-    TVectorStorage: VectorStorage,
+    TVectorStorage: DenseVectorStorage,
 {
     fn score_points(&self, points: &[PointOffsetType], scores: &mut [ScoredPointOffset]) -> usize {
         let mut size: usize = 0;
@@ -98,7 +104,7 @@ where
             if !self.check_point(point_id) {
                 continue;
             }
-            let other_vector = self.vector_storage.get_vector(point_id);
+            let other_vector = self.vector_storage.get_dense(point_id);
             scores[size] = ScoredPointOffset {
                 idx: point_id,
                 score: TMetric::similarity(&self.query, other_vector),
@@ -119,13 +125,13 @@ where
     }
 
     fn score_point(&self, point: PointOffsetType) -> ScoreType {
-        let other_vector = self.vector_storage.get_vector(point);
+        let other_vector = self.vector_storage.get_dense(point);
         TMetric::similarity(&self.query, other_vector)
     }
 
     fn score_internal(&self, point_a: PointOffsetType, point_b: PointOffsetType) -> ScoreType {
-        let vector_a = self.vector_storage.get_vector(point_a);
-        let vector_b = self.vector_storage.get_vector(point_b);
+        let vector_a = self.vector_storage.get_dense(point_a);
+        let vector_b = self.vector_storage.get_dense(point_b);
         TMetric::similarity(vector_a, vector_b)
     }
 
@@ -137,7 +143,7 @@ where
         let scores = points
             .filter(|point_id| self.check_point(*point_id))
             .map(|point_id| {
-                let other_vector = self.vector_storage.get_vector(point_id);
+                let other_vector = self.vector_storage.get_dense(point_id);
                 ScoredPointOffset {
                     idx: point_id,
                     score: TMetric::similarity(&self.query, other_vector),
@@ -151,7 +157,7 @@ where
             .filter(|point_id| self.check_point(*point_id))
             .map(|point_id| {
                 let point_id = point_id as PointOffsetType;
-                let other_vector = &self.vector_storage.get_vector(point_id);
+                let other_vector = &self.vector_storage.get_dense(point_id);
                 ScoredPointOffset {
                     idx: point_id,
                     score: TMetric::similarity(&self.query, other_vector),
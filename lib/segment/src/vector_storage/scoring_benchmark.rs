@@ -0,0 +1,137 @@
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use bitvec::prelude::BitVec;
+use rand::Rng;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use super::quantized::quantized_vectors_base::QuantizedVectors;
+use super::{DenseVectorStorage, ScoredPointOffset, VectorStorage, VectorStorageEnum};
+use crate::data_types::vectors::VectorElementType;
+use crate::spaces::metric::Metric;
+use crate::spaces::simple::{CosineMetric, DotProductMetric, EuclidMetric};
+use crate::types::{Distance, PointOffsetType, ScoreType};
+
+const BENCHMARK_DISTANCES: [Distance; 3] = [Distance::Cosine, Distance::Euclid, Distance::Dot];
+
+/// Kind of vectors, scored by the benchmark
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum ScoringStorageType {
+    /// Original vectors, kept in RAM
+    InMemory,
+    /// Original vectors, stored in memory mapped file
+    Mmap,
+    /// Quantized vectors
+    Quantized,
+}
+
+/// Scoring throughput of a single storage with a single distance metric
+#[derive(Debug, Clone)]
+pub struct ScoringMeasurement {
+    pub storage_type: ScoringStorageType,
+    pub distance: Distance,
+    /// Number of scored vectors
+    pub scored: usize,
+    pub elapsed: Duration,
+}
+
+fn similarity(distance: Distance, v1: &[VectorElementType], v2: &[VectorElementType]) -> ScoreType {
+    match distance {
+        Distance::Cosine => CosineMetric::similarity(v1, v2),
+        Distance::Euclid => EuclidMetric::similarity(v1, v2),
+        Distance::Dot => DotProductMetric::similarity(v1, v2),
+    }
+}
+
+/// Score a random query against up to `points_limit` stored vectors.
+///
+/// Original vectors are scored with every distance metric, regardless of the configured one,
+/// quantized vectors are scored with the configured metric only.
+pub fn benchmark_scoring(
+    vector_storage: &VectorStorageEnum,
+    deleted: &BitVec,
+    points_limit: usize,
+) -> Vec<ScoringMeasurement> {
+    let count = vector_storage.total_vector_count().min(points_limit);
+    if count == 0 {
+        return vec![];
+    }
+    let ids: Vec<PointOffsetType> = (0..count as PointOffsetType).collect();
+    let mut rng = rand::thread_rng();
+    let query: Vec<VectorElementType> = (0..vector_storage.vector_dim())
+        .map(|_| rng.gen_range(-1.0..1.0))
+        .collect();
+
+    let mut measurements = Vec::new();
+
+    let original_storage: Option<(ScoringStorageType, &dyn DenseVectorStorage)> =
+        match vector_storage {
+            VectorStorageEnum::Simple(storage) => Some((ScoringStorageType::InMemory, storage)),
+            VectorStorageEnum::Memmap(storage) => {
+                Some((ScoringStorageType::Mmap, storage.as_ref()))
+            }
+            VectorStorageEnum::QuantizedOnly(_) => None,
+        };
+    if let Some((storage_type, original_storage)) = original_storage {
+        for distance in BENCHMARK_DISTANCES {
+            let timer = Instant::now();
+            for &id in &ids {
+                black_box(similarity(distance, &query, original_storage.get_dense(id)));
+            }
+            measurements.push(ScoringMeasurement {
+                storage_type,
+                distance,
+                scored: ids.len(),
+                elapsed: timer.elapsed(),
+            });
+        }
+    }
+
+    if let Some(quantized_storage) = vector_storage.quantized_storage() {
+        let raw_scorer = quantized_storage.raw_scorer(&query, deleted);
+        let mut scores = vec![ScoredPointOffset::default(); ids.len()];
+        let timer = Instant::now();
+        let scored = raw_scorer.score_points(&ids, &mut scores);
+        black_box(&scores);
+        measurements.push(ScoringMeasurement {
+            storage_type: ScoringStorageType::Quantized,
+            distance: vector_storage.distance(),
+            scored,
+            elapsed: timer.elapsed(),
+        });
+    }
+
+    measurements
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::Builder;
+
+    use super::*;
+    use crate::common::rocksdb_wrapper::{open_db, DB_VECTOR_CF};
+    use crate::vector_storage::simple_vector_storage::open_simple_vector_storage;
+
+    #[test]
+    fn test_benchmark_scoring() {
+        let dir = Builder::new().prefix("storage_dir").tempdir().unwrap();
+        let db = open_db(dir.path(), &[DB_VECTOR_CF]).unwrap();
+        let storage = open_simple_vector_storage(db, DB_VECTOR_CF, 4, Distance::Dot).unwrap();
+        let mut borrowed_storage = storage.borrow_mut();
+        for id in 0..10 {
+            borrowed_storage
+                .insert_vector(id, &[id as f32, 1.0, 0.0, 1.0])
+                .unwrap();
+        }
+
+        let deleted = BitVec::repeat(false, 10);
+        let measurements = benchmark_scoring(&borrowed_storage, &deleted, 5);
+        assert_eq!(measurements.len(), BENCHMARK_DISTANCES.len());
+        for measurement in measurements {
+            assert_eq!(measurement.storage_type, ScoringStorageType::InMemory);
+            assert_eq!(measurement.scored, 5);
+        }
+    }
+}
@@ -11,7 +11,7 @@ use rocksdb::DB;
 use serde::{Deserialize, Serialize};
 
 use super::chunked_vectors::ChunkedVectors;
-use super::vector_storage_base::VectorStorage;
+use super::vector_storage_base::{DenseVectorStorage, VectorStorage};
 use super::VectorStorageEnum;
 use crate::common::rocksdb_wrapper::DatabaseColumnWrapper;
 use crate::common::Flusher;
@@ -91,6 +91,12 @@ impl SimpleVectorStorage {
     }
 }
 
+impl DenseVectorStorage for SimpleVectorStorage {
+    fn get_dense(&self, key: PointOffsetType) -> &[VectorElementType] {
+        self.vectors.get(key)
+    }
+}
+
 impl VectorStorage for SimpleVectorStorage {
     fn vector_dim(&self) -> usize {
         self.dim
@@ -104,8 +110,8 @@ impl VectorStorage for SimpleVectorStorage {
         self.vectors.len()
     }
 
-    fn get_vector(&self, key: PointOffsetType) -> &[VectorElementType] {
-        self.vectors.get(key)
+    fn get_vector(&self, key: PointOffsetType) -> OperationResult<&[VectorElementType]> {
+        Ok(self.get_dense(key))
     }
 
     fn insert_vector(
@@ -128,7 +134,7 @@ impl VectorStorage for SimpleVectorStorage {
         for point_id in other_ids {
             check_process_stopped(stopped)?;
             // Do not perform preprocessing - vectors should be already processed
-            let other_vector = other.get_vector(point_id);
+            let other_vector = other.get_vector(point_id)?;
             let new_id = self.vectors.push(other_vector);
             self.update_stored(new_id, other_vector)?;
         }
@@ -177,6 +183,10 @@ impl VectorStorage for SimpleVectorStorage {
             vec![]
         }
     }
+
+    fn ram_usage_bytes(&self) -> usize {
+        self.vectors.ram_usage_bytes()
+    }
 }
 
 #[cfg(test)]
@@ -303,6 +313,7 @@ mod tests {
             r#type: Default::default(),
             quantile: None,
             always_ram: None,
+            quantized_only: None,
         }
         .into();
 
@@ -0,0 +1,113 @@
+use std::ops::Range;
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+
+use atomic_refcell::AtomicRefCell;
+use log::debug;
+use parking_lot::RwLock;
+use rocksdb::DB;
+
+use crate::common::rocksdb_wrapper::DatabaseColumnWrapper;
+use crate::common::Flusher;
+use crate::data_types::sparse_vector::SparseVector;
+use crate::entry::entry_point::{check_process_stopped, OperationError, OperationResult};
+use crate::types::PointOffsetType;
+
+/// In-memory storage of sparse vectors with on-update persistence using `store`
+///
+/// Vectors are addressed by the same internal offsets as dense vectors of the segment.
+/// Points without sparse vector of this name have no value.
+pub struct SparseVectorStorage {
+    vectors: Vec<Option<SparseVector>>,
+    db_wrapper: DatabaseColumnWrapper,
+}
+
+pub fn open_sparse_vector_storage(
+    database: Arc<RwLock<DB>>,
+    database_column_name: &str,
+) -> OperationResult<Arc<AtomicRefCell<SparseVectorStorage>>> {
+    let mut vectors: Vec<Option<SparseVector>> = vec![];
+
+    let db_wrapper = DatabaseColumnWrapper::new(database, database_column_name);
+    for (key, value) in db_wrapper.lock_db().iter()? {
+        let point_id: PointOffsetType = bincode::deserialize(&key)
+            .map_err(|_| OperationError::service_error("cannot deserialize point id from db"))?;
+        let vector: SparseVector = bincode::deserialize(&value).map_err(|_| {
+            OperationError::service_error("cannot deserialize sparse vector from db")
+        })?;
+        let point_id = point_id as usize;
+        if vectors.len() <= point_id {
+            vectors.resize(point_id + 1, None);
+        }
+        vectors[point_id] = Some(vector);
+    }
+
+    debug!("Segment sparse vectors: {}", vectors.len());
+
+    Ok(Arc::new(AtomicRefCell::new(SparseVectorStorage {
+        vectors,
+        db_wrapper,
+    })))
+}
+
+impl SparseVectorStorage {
+    /// Number of offsets, allocated in the storage. Includes points without vector.
+    pub fn total_vector_count(&self) -> usize {
+        self.vectors.len()
+    }
+
+    pub fn get_vector(&self, key: PointOffsetType) -> Option<&SparseVector> {
+        self.vectors.get(key as usize).and_then(Option::as_ref)
+    }
+
+    pub fn iter_vectors(&self) -> impl Iterator<Item = (PointOffsetType, &SparseVector)> + '_ {
+        self.vectors
+            .iter()
+            .enumerate()
+            .filter_map(|(key, vector)| Some((key as PointOffsetType, vector.as_ref()?)))
+    }
+
+    /// Set or remove vector of the point
+    pub fn insert_vector(
+        &mut self,
+        key: PointOffsetType,
+        vector: Option<&SparseVector>,
+    ) -> OperationResult<()> {
+        let idx = key as usize;
+        if self.vectors.len() <= idx {
+            self.vectors.resize(idx + 1, None);
+        }
+        self.vectors[idx] = vector.cloned();
+        match vector {
+            Some(vector) => self.db_wrapper.put(
+                bincode::serialize(&key).unwrap(),
+                bincode::serialize(vector).unwrap(),
+            )?,
+            None => self.db_wrapper.remove(bincode::serialize(&key).unwrap())?,
+        }
+        Ok(())
+    }
+
+    /// Copy vectors of `other_ids` from `other` storage, placing them starting from `start` offset
+    ///
+    /// Returns the range of new offsets, which is aligned with the ranges of dense vector storages.
+    pub fn update_from(
+        &mut self,
+        other: &SparseVectorStorage,
+        other_ids: &mut dyn Iterator<Item = PointOffsetType>,
+        start: PointOffsetType,
+        stopped: &AtomicBool,
+    ) -> OperationResult<Range<PointOffsetType>> {
+        let mut new_id = start;
+        for point_id in other_ids {
+            check_process_stopped(stopped)?;
+            self.insert_vector(new_id, other.get_vector(point_id))?;
+            new_id += 1;
+        }
+        Ok(start..new_id)
+    }
+
+    pub fn flusher(&self) -> Flusher {
+        self.db_wrapper.flusher()
+    }
+}
@@ -7,6 +7,7 @@ use ordered_float::OrderedFloat;
 
 use super::memmap_vector_storage::MemmapVectorStorage;
 use super::quantized::quantized_vectors_base::QuantizedVectorsStorage;
+use super::quantized_only_vector_storage::QuantizedOnlyVectorStorage;
 use super::simple_vector_storage::SimpleVectorStorage;
 use crate::common::Flusher;
 use crate::data_types::vectors::VectorElementType;
@@ -44,8 +45,9 @@ pub trait VectorStorage {
     /// Number of vectors, marked as deleted but still stored
     fn total_vector_count(&self) -> usize;
 
-    /// Number of all stored vectors including deleted
-    fn get_vector(&self, key: PointOffsetType) -> &[VectorElementType];
+    /// Original vector under the given internal id.
+    /// Fails, if the storage doesn't keep original vectors.
+    fn get_vector(&self, key: PointOffsetType) -> OperationResult<&[VectorElementType]>;
 
     fn insert_vector(
         &mut self,
@@ -75,11 +77,29 @@ pub trait VectorStorage {
     fn quantized_storage(&self) -> Option<&QuantizedVectorsStorage>;
 
     fn files(&self) -> Vec<PathBuf>;
+
+    /// Estimated size of original vectors, kept in RAM.
+    /// Quantized vectors and memory mapped files are not included.
+    fn ram_usage_bytes(&self) -> usize;
+}
+
+/// Vector storage, which always keeps original vectors.
+/// Allows to read vectors without error handling, e.g. on the scoring hot path.
+pub trait DenseVectorStorage: VectorStorage {
+    fn get_dense(&self, key: PointOffsetType) -> &[VectorElementType];
 }
 
 pub enum VectorStorageEnum {
     Simple(SimpleVectorStorage),
     Memmap(Box<MemmapVectorStorage>),
+    QuantizedOnly(QuantizedOnlyVectorStorage),
+}
+
+impl VectorStorageEnum {
+    /// Original vectors are removed, only quantized vectors are stored
+    pub fn is_quantized_only(&self) -> bool {
+        matches!(self, VectorStorageEnum::QuantizedOnly(_))
+    }
 }
 
 impl VectorStorage for VectorStorageEnum {
@@ -87,6 +107,7 @@ impl VectorStorage for VectorStorageEnum {
         match self {
             VectorStorageEnum::Simple(v) => v.vector_dim(),
             VectorStorageEnum::Memmap(v) => v.vector_dim(),
+            VectorStorageEnum::QuantizedOnly(v) => v.vector_dim(),
         }
     }
 
@@ -94,6 +115,7 @@ impl VectorStorage for VectorStorageEnum {
         match self {
             VectorStorageEnum::Simple(v) => v.distance(),
             VectorStorageEnum::Memmap(v) => v.distance(),
+            VectorStorageEnum::QuantizedOnly(v) => v.distance(),
         }
     }
 
@@ -101,13 +123,15 @@ impl VectorStorage for VectorStorageEnum {
         match self {
             VectorStorageEnum::Simple(v) => v.total_vector_count(),
             VectorStorageEnum::Memmap(v) => v.total_vector_count(),
+            VectorStorageEnum::QuantizedOnly(v) => v.total_vector_count(),
         }
     }
 
-    fn get_vector(&self, key: PointOffsetType) -> &[VectorElementType] {
+    fn get_vector(&self, key: PointOffsetType) -> OperationResult<&[VectorElementType]> {
         match self {
             VectorStorageEnum::Simple(v) => v.get_vector(key),
             VectorStorageEnum::Memmap(v) => v.get_vector(key),
+            VectorStorageEnum::QuantizedOnly(v) => v.get_vector(key),
         }
     }
 
@@ -119,6 +143,7 @@ impl VectorStorage for VectorStorageEnum {
         match self {
             VectorStorageEnum::Simple(v) => v.insert_vector(key, vector),
             VectorStorageEnum::Memmap(v) => v.insert_vector(key, vector),
+            VectorStorageEnum::QuantizedOnly(v) => v.insert_vector(key, vector),
         }
     }
 
@@ -131,6 +156,7 @@ impl VectorStorage for VectorStorageEnum {
         match self {
             VectorStorageEnum::Simple(v) => v.update_from(other, other_ids, stopped),
             VectorStorageEnum::Memmap(v) => v.update_from(other, other_ids, stopped),
+            VectorStorageEnum::QuantizedOnly(v) => v.update_from(other, other_ids, stopped),
         }
     }
 
@@ -138,6 +164,7 @@ impl VectorStorage for VectorStorageEnum {
         match self {
             VectorStorageEnum::Simple(v) => v.flusher(),
             VectorStorageEnum::Memmap(v) => v.flusher(),
+            VectorStorageEnum::QuantizedOnly(v) => v.flusher(),
         }
     }
 
@@ -149,6 +176,7 @@ impl VectorStorage for VectorStorageEnum {
         match self {
             VectorStorageEnum::Simple(v) => v.quantize(data_path, quantization_config),
             VectorStorageEnum::Memmap(v) => v.quantize(data_path, quantization_config),
+            VectorStorageEnum::QuantizedOnly(v) => v.quantize(data_path, quantization_config),
         }
     }
 
@@ -156,6 +184,7 @@ impl VectorStorage for VectorStorageEnum {
         match self {
             VectorStorageEnum::Simple(v) => v.load_quantization(data_path),
             VectorStorageEnum::Memmap(v) => v.load_quantization(data_path),
+            VectorStorageEnum::QuantizedOnly(v) => v.load_quantization(data_path),
         }
     }
 
@@ -163,6 +192,7 @@ impl VectorStorage for VectorStorageEnum {
         match self {
             VectorStorageEnum::Simple(v) => v.quantized_storage(),
             VectorStorageEnum::Memmap(v) => v.quantized_storage(),
+            VectorStorageEnum::QuantizedOnly(v) => v.quantized_storage(),
         }
     }
 
@@ -170,6 +200,15 @@ impl VectorStorage for VectorStorageEnum {
         match self {
             VectorStorageEnum::Simple(v) => v.files(),
             VectorStorageEnum::Memmap(v) => v.files(),
+            VectorStorageEnum::QuantizedOnly(v) => v.files(),
+        }
+    }
+
+    fn ram_usage_bytes(&self) -> usize {
+        match self {
+            VectorStorageEnum::Simple(v) => v.ram_usage_bytes(),
+            VectorStorageEnum::Memmap(v) => v.ram_usage_bytes(),
+            VectorStorageEnum::QuantizedOnly(v) => v.ram_usage_bytes(),
         }
     }
 }
@@ -14,8 +14,8 @@ mod tests {
     use segment::segment_constructor::build_segment;
     use segment::types::{
         Condition, Distance, FieldCondition, Filter, HnswConfig, Indexes, Payload,
-        PayloadSchemaType, PointOffsetType, Range, SearchParams, SegmentConfig, SeqNumberType,
-        StorageType, VectorDataConfig,
+        PayloadSchemaType, PlannerHint, PointOffsetType, Range, SearchParams, SegmentConfig,
+        SeqNumberType, StorageType, VectorDataConfig,
     };
     use serde_json::json;
     use tempfile::Builder;
@@ -82,6 +82,7 @@ mod tests {
             max_indexing_threads: 2,
             on_disk: Some(false),
             payload_m: None,
+            payload_fields: None,
         };
 
         let mut hnsw_index = HNSWIndex::<GraphLinksRam>::open(
@@ -151,11 +152,12 @@ mod tests {
                     exact: true,
                     ..Default::default()
                 }),
+                &false.into(),
             );
             let plain_result = segment.vector_data[DEFAULT_VECTOR_NAME]
                 .vector_index
                 .borrow()
-                .search(&[&query], None, top, None);
+                .search(&[&query], None, top, None, &false.into());
 
             assert_eq!(
                 index_result, plain_result,
@@ -186,16 +188,34 @@ mod tests {
                     exact: true,
                     ..Default::default()
                 }),
+                &false.into(),
             );
             let plain_result = segment.vector_data[DEFAULT_VECTOR_NAME]
                 .vector_index
                 .borrow()
-                .search(&[&query], filter_query, top, None);
+                .search(&[&query], filter_query, top, None, &false.into());
 
             assert_eq!(
                 index_result, plain_result,
                 "Exact search is not equal to plain search"
             );
+
+            let hinted_result = hnsw_index.search(
+                &[&query],
+                filter_query,
+                top,
+                Some(&SearchParams {
+                    hnsw_ef: Some(ef),
+                    planner_hint: Some(PlannerHint::PlainSearch),
+                    ..Default::default()
+                }),
+                &false.into(),
+            );
+
+            assert_eq!(
+                hinted_result, plain_result,
+                "Search with plain search hint is not equal to plain search"
+            );
         }
     }
 }
@@ -83,6 +83,7 @@ mod tests {
             max_indexing_threads: 2,
             on_disk: Some(false),
             payload_m: None,
+            payload_fields: None,
         };
 
         let mut hnsw_index = HNSWIndex::<GraphLinksRam>::open(
@@ -138,6 +139,14 @@ mod tests {
 
         hnsw_index.build_index(&stopped).unwrap();
 
+        let telemetry = hnsw_index.get_telemetry_data();
+        let subgraph = telemetry
+            .payload_subgraphs
+            .iter()
+            .find(|subgraph| subgraph.field_name.as_deref() == Some(int_key))
+            .expect("payload subgraph should be reported for the indexed field");
+        assert!(subgraph.blocks > 0);
+
         let top = 3;
         let mut hits = 0;
         let attempts = 100;
@@ -174,7 +183,7 @@ mod tests {
             let plain_result = segment.vector_data[DEFAULT_VECTOR_NAME]
                 .vector_index
                 .borrow()
-                .search(&[&query], filter_query, top, None);
+                .search(&[&query], filter_query, top, None, &false.into());
 
             if plain_result.get(0).unwrap() == &index_result {
                 hits += 1;
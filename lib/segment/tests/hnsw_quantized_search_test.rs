@@ -12,8 +12,8 @@ mod tests {
     use segment::index::VectorIndex;
     use segment::segment_constructor::build_segment;
     use segment::types::{
-        Distance, HnswConfig, Indexes, ScalarQuantizationConfig, SearchParams, SegmentConfig,
-        SeqNumberType, StorageType, VectorDataConfig,
+        Distance, HnswConfig, Indexes, QuantizationSearchParams, ScalarQuantizationConfig,
+        SearchParams, SegmentConfig, SeqNumberType, StorageType, VectorDataConfig,
     };
     use segment::vector_storage::{ScoredPointOffset, VectorStorage};
     use tempfile::Builder;
@@ -75,6 +75,7 @@ mod tests {
                         r#type: Default::default(),
                         quantile: None,
                         always_ram: None,
+                        quantized_only: None,
                     }
                     .into(),
                 )
@@ -88,6 +89,7 @@ mod tests {
             max_indexing_threads: 2,
             on_disk: Some(false),
             payload_m: None,
+            payload_fields: None,
         };
 
         let mut hnsw_index = HNSWIndex::<GraphLinksRam>::open(
@@ -117,12 +119,38 @@ mod tests {
                     hnsw_ef: Some(ef),
                     ..Default::default()
                 }),
+                &false.into(),
             );
             let plain_result = segment.vector_data[DEFAULT_VECTOR_NAME]
                 .vector_index
                 .borrow()
-                .search(&[&query], None, top, None);
+                .search(&[&query], None, top, None, &false.into());
             sames += sames_count(&index_result, &plain_result);
+
+            // Oversampled candidates are re-scored with original vectors
+            let rescored_result = hnsw_index.search(
+                &[&query],
+                None,
+                top,
+                Some(&SearchParams {
+                    hnsw_ef: Some(ef),
+                    quantization: Some(QuantizationSearchParams {
+                        rescore_factor: Some(3.0),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                }),
+                &false.into(),
+            );
+            assert_eq!(rescored_result[0].len(), top);
+            assert!(rescored_result[0]
+                .windows(2)
+                .all(|pair| pair[0].score >= pair[1].score));
+            // Candidates include all quantized results, so accuracy can't get worse
+            assert!(
+                sames_count(&rescored_result, &plain_result)
+                    >= sames_count(&index_result, &plain_result)
+            );
         }
         let acc = 100.0 * sames as f64 / (attempts * top) as f64;
         println!("sames = {sames}, attempts = {attempts}, top = {top}, acc = {acc}");
@@ -2,7 +2,7 @@ mod utils;
 
 #[cfg(test)]
 mod tests {
-    use std::collections::HashMap;
+    use std::collections::{HashMap, HashSet};
     use std::path::Path;
 
     use itertools::Itertools;
@@ -11,17 +11,17 @@ mod tests {
     use segment::data_types::vectors::{only_default_vector, DEFAULT_VECTOR_NAME};
     use segment::entry::entry_point::SegmentEntry;
     use segment::fixtures::payload_fixtures::{
-        generate_diverse_nested_payload, generate_diverse_payload, random_filter,
+        generate_diverse_nested_payload, generate_diverse_payload, random_filter, random_keyword,
         random_nested_filter, random_vector, FLICKING_KEY, GEO_KEY, INT_KEY, INT_KEY_2, LAT_RANGE,
         LON_RANGE, STR_KEY, STR_PROJ_KEY, TEXT_KEY,
     };
     use segment::index::PayloadIndex;
     use segment::segment::Segment;
-    use segment::segment_constructor::build_segment;
+    use segment::segment_constructor::{build_segment, load_segment};
     use segment::types::{
         Condition, Distance, FieldCondition, Filter, GeoPoint, GeoRadius, Indexes,
-        IsEmptyCondition, Payload, PayloadField, PayloadSchemaType, Range, SegmentConfig,
-        StorageType, VectorDataConfig, WithPayload,
+        IsEmptyCondition, Payload, PayloadField, PayloadSchemaType, PayloadStorageType, Range,
+        SegmentConfig, StorageType, ValuesCount, VectorDataConfig, WithPayload,
     };
     use tempfile::Builder;
 
@@ -400,6 +400,59 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_must_not_index_complement() {
+        // Points, excluded by indexed `must_not` conditions, are skipped without a check,
+        // so results should be the same as with the plain index
+        let dir1 = Builder::new().prefix("segment1_dir").tempdir().unwrap();
+        let dir2 = Builder::new().prefix("segment2_dir").tempdir().unwrap();
+
+        let mut rnd = StdRng::seed_from_u64(42);
+
+        let (struct_segment, plain_segment) = build_test_segments(dir1.path(), dir2.path());
+
+        for _ in 0..20 {
+            let keywords: Vec<_> = (0..50).map(|_| random_keyword(&mut rnd)).collect();
+            let has_id: HashSet<_> = (0..20).map(|_| rnd.gen_range(0..3000u64).into()).collect();
+            let from = rnd.gen_range(0..500) as f64;
+            let filter = Filter {
+                should: None,
+                must: None,
+                must_not: Some(vec![
+                    Condition::Field(FieldCondition::new_match(
+                        STR_KEY.to_owned(),
+                        keywords.into(),
+                    )),
+                    Condition::Field(FieldCondition::new_range(
+                        INT_KEY.to_owned(),
+                        Range {
+                            lt: None,
+                            gt: None,
+                            gte: Some(from),
+                            lte: Some(from + 100.),
+                        },
+                    )),
+                    Condition::HasId(has_id.into()),
+                ]),
+            };
+
+            let plain_result = plain_segment
+                .payload_index
+                .borrow()
+                .query_points(&filter)
+                .sorted()
+                .collect_vec();
+            let struct_result = struct_segment
+                .payload_index
+                .borrow()
+                .query_points(&filter)
+                .sorted()
+                .collect_vec();
+
+            assert_eq!(plain_result, struct_result, "filter: {filter:#?}");
+        }
+    }
+
     #[test]
     fn test_struct_payload_geo_index() {
         // Compare search with plain and struct indexes
@@ -517,6 +570,7 @@ mod tests {
                     &WithPayload {
                         enable: true,
                         payload_selector: None,
+                        count: false,
                     },
                     &false.into(),
                     Some(&query_filter),
@@ -531,6 +585,7 @@ mod tests {
                     &WithPayload {
                         enable: true,
                         payload_selector: None,
+                        count: false,
                     },
                     &false.into(),
                     Some(&query_filter),
@@ -561,4 +616,125 @@ mod tests {
                 });
         }
     }
+
+    #[test]
+    fn test_on_disk_payload_columns() {
+        // Conditions, which can't be answered by the index, are checked against columns
+        // of indexed fields. Results should be the same as with the whole payload.
+        let dir1 = Builder::new().prefix("segment1_dir").tempdir().unwrap();
+        let dir2 = Builder::new().prefix("segment2_dir").tempdir().unwrap();
+
+        let mut rnd = StdRng::seed_from_u64(42);
+        let dim = 5;
+
+        let config = SegmentConfig {
+            vector_data: HashMap::from([(
+                DEFAULT_VECTOR_NAME.to_owned(),
+                VectorDataConfig {
+                    size: dim,
+                    distance: Distance::Dot,
+                    hnsw_config: None,
+                    quantization_config: None,
+                },
+            )]),
+            index: Indexes::Plain {},
+            storage_type: StorageType::InMemory,
+            ..Default::default()
+        };
+        let on_disk_config = SegmentConfig {
+            payload_storage_type: PayloadStorageType::OnDisk,
+            ..config.clone()
+        };
+
+        let mut plain_segment = build_segment(dir1.path(), &config).unwrap();
+        let mut struct_segment = build_segment(dir2.path(), &on_disk_config).unwrap();
+
+        let num_points = 1000;
+        let mut opnum = 0;
+        struct_segment
+            .create_field_index(opnum, INT_KEY, Some(&PayloadSchemaType::Integer.into()))
+            .unwrap();
+
+        for n in 0..num_points {
+            opnum += 1;
+            let idx = n.into();
+            let vector = random_vector(&mut rnd, dim);
+            let payload: Payload = generate_diverse_payload(&mut rnd);
+            for segment in [&mut plain_segment, &mut struct_segment] {
+                segment
+                    .upsert_vector(opnum, idx, &only_default_vector(&vector))
+                    .unwrap();
+                segment.set_full_payload(opnum, idx, &payload).unwrap();
+            }
+        }
+
+        opnum += 1;
+        for key in [STR_KEY, FLICKING_KEY] {
+            struct_segment
+                .create_field_index(opnum, key, Some(&PayloadSchemaType::Keyword.into()))
+                .unwrap();
+        }
+
+        for _ in 0..200 {
+            opnum += 1;
+            let idx = rnd.gen_range(0..num_points).into();
+            let flicking: Payload =
+                serde_json::json!({ FLICKING_KEY: random_keyword(&mut rnd) }).into();
+            for segment in [&mut plain_segment, &mut struct_segment] {
+                segment.set_payload(opnum, idx, &flicking).unwrap();
+                segment.delete_payload(opnum, idx, INT_KEY).unwrap();
+            }
+        }
+
+        let values_count = |key: &str| {
+            Condition::Field(FieldCondition::new_values_count(
+                key.to_owned(),
+                ValuesCount {
+                    lt: None,
+                    gt: Some(1),
+                    gte: None,
+                    lte: None,
+                },
+            ))
+        };
+        let is_empty = |key: &str| {
+            Condition::IsEmpty(IsEmptyCondition {
+                is_empty: PayloadField {
+                    key: key.to_owned(),
+                },
+            })
+        };
+        let filters = [
+            Filter::new_must(values_count(INT_KEY)),
+            Filter::new_must(values_count(STR_KEY)),
+            Filter::new_must(is_empty(INT_KEY)),
+            Filter::new_must_not(is_empty(FLICKING_KEY)),
+        ];
+
+        let check = |struct_segment: &Segment| {
+            for filter in &filters {
+                let plain_result = plain_segment
+                    .payload_index
+                    .borrow()
+                    .query_points(filter)
+                    .sorted()
+                    .collect_vec();
+                let struct_result = struct_segment
+                    .payload_index
+                    .borrow()
+                    .query_points(filter)
+                    .sorted()
+                    .collect_vec();
+                assert!(!plain_result.is_empty(), "filter: {filter:#?}");
+                assert_eq!(plain_result, struct_result, "filter: {filter:#?}");
+            }
+        };
+
+        check(&struct_segment);
+
+        let segment_path = struct_segment.current_path.clone();
+        drop(struct_segment);
+        let loaded_segment = load_segment(&segment_path).unwrap().unwrap();
+        check(&loaded_segment);
+    }
 }
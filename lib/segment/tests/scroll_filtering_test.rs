@@ -2,8 +2,10 @@
 mod tests {
     use rand::prelude::StdRng;
     use rand::{Rng, SeedableRng};
+    use segment::entry::entry_point::SegmentEntry;
     use segment::fixtures::payload_fixtures::random_filter;
     use segment::fixtures::segment_fixtures::random_segment;
+    use segment::types::PlannerHint;
     use tempfile::Builder;
 
     const NUM_POINTS: usize = 2000;
@@ -31,4 +33,40 @@ mod tests {
             assert_eq!(read_by_index_res, read_by_stream_res, "filter: {filter:#?}");
         }
     }
+
+    #[test]
+    fn test_read_filtered_with_planner_hint() {
+        let seed = 42;
+        let mut rng = StdRng::seed_from_u64(seed);
+
+        let dir = Builder::new().prefix("storage_dir").tempdir().unwrap();
+
+        let segment = random_segment(dir.path(), NUM_POINTS);
+
+        for _ in 0..ATTEMPTS {
+            let filter = random_filter(&mut rng, 3);
+
+            let random_offset = rng.gen_range(0..10);
+            let read = |planner_hint| {
+                segment.read_filtered(
+                    Some(random_offset.into()),
+                    Some(10),
+                    Some(&filter),
+                    planner_hint,
+                )
+            };
+
+            let estimated_res = read(None);
+            assert_eq!(
+                read(Some(PlannerHint::IndexScan)),
+                estimated_res,
+                "filter: {filter:#?}"
+            );
+            assert_eq!(
+                read(Some(PlannerHint::StreamScan)),
+                estimated_res,
+                "filter: {filter:#?}"
+            );
+        }
+    }
 }
@@ -12,7 +12,13 @@ mod tests {
     use segment::entry::entry_point::{OperationError, SegmentEntry};
     use segment::segment::Segment;
     use segment::segment_constructor::segment_builder::SegmentBuilder;
-    use segment::types::{Indexes, SegmentConfig, VectorDataConfig};
+    use segment::segment_constructor::{get_vector_storage_path, load_segment};
+    use segment::types::{
+        Indexes, Payload, ScalarQuantizationConfig, SegmentConfig, StorageType, VectorDataConfig,
+        WithPayload, WithVector,
+    };
+    use segment::vector_storage::VectorStorage;
+    use serde_json::json;
     use tempfile::Builder;
 
     use crate::fixtures::segment::{build_segment_1, build_segment_2, empty_segment};
@@ -72,6 +78,194 @@ mod tests {
         assert_eq!(merged_segment.point_version(3.into()), Some(100));
     }
 
+    #[test]
+    fn test_building_segment_with_defrag_key() {
+        let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+        let temp_dir = Builder::new().prefix("segment_temp_dir").tempdir().unwrap();
+
+        let stopped = AtomicBool::new(false);
+
+        let mut segment = empty_segment(dir.path());
+        for idx in 0..30u64 {
+            let payload: Payload = json!({ "tenant": format!("tenant_{}", idx % 3) }).into();
+            segment
+                .upsert_vector(1, idx.into(), &only_default_vector(&[0., 0., 0., 0.]))
+                .unwrap();
+            segment.set_full_payload(1, idx.into(), &payload).unwrap();
+        }
+
+        let mut builder =
+            SegmentBuilder::new(dir.path(), temp_dir.path(), &segment.segment_config).unwrap();
+        builder.defrag_key = Some("tenant".to_string());
+        builder.update_from(&segment, &stopped).unwrap();
+        let defragmented_segment: Segment = builder.build(&stopped).unwrap();
+
+        assert_eq!(defragmented_segment.points_count(), 30);
+
+        // Points of each tenant occupy a continuous range of internal ids
+        let id_tracker = defragmented_segment.id_tracker.borrow();
+        for tenant in 0..3u64 {
+            let internal_ids = (0..30u64)
+                .filter(|idx| idx % 3 == tenant)
+                .map(|idx| id_tracker.internal_id(idx.into()).unwrap())
+                .sorted()
+                .collect_vec();
+            assert_eq!(
+                internal_ids.last().unwrap() - internal_ids.first().unwrap(),
+                internal_ids.len() as u32 - 1
+            );
+        }
+    }
+
+    #[test]
+    fn test_building_segment_with_defrag_key_from_several_segments() {
+        let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+        let temp_dir = Builder::new().prefix("segment_temp_dir").tempdir().unwrap();
+
+        let stopped = AtomicBool::new(false);
+
+        // Each segment has points of all tenants
+        let segments = (0..2u64)
+            .map(|segment_idx| {
+                let mut segment = empty_segment(dir.path());
+                for idx in (0..30u64).filter(|idx| idx % 2 == segment_idx) {
+                    let payload: Payload =
+                        json!({ "tenant": format!("tenant_{}", idx % 3) }).into();
+                    segment
+                        .upsert_vector(1, idx.into(), &only_default_vector(&[0., 0., 0., 0.]))
+                        .unwrap();
+                    segment.set_full_payload(1, idx.into(), &payload).unwrap();
+                }
+                segment
+            })
+            .collect_vec();
+
+        let mut builder =
+            SegmentBuilder::new(dir.path(), temp_dir.path(), &segments[0].segment_config).unwrap();
+        builder.defrag_key = Some("tenant".to_string());
+        builder
+            .update_from_segments(&segments.iter().collect_vec(), &stopped)
+            .unwrap();
+        let defragmented_segment: Segment = builder.build(&stopped).unwrap();
+
+        assert_eq!(defragmented_segment.points_count(), 30);
+
+        // Points of each tenant occupy a continuous range of internal ids across source segments
+        let id_tracker = defragmented_segment.id_tracker.borrow();
+        for tenant in 0..3u64 {
+            let internal_ids = (0..30u64)
+                .filter(|idx| idx % 3 == tenant)
+                .map(|idx| id_tracker.internal_id(idx.into()).unwrap())
+                .sorted()
+                .collect_vec();
+            assert_eq!(
+                internal_ids.last().unwrap() - internal_ids.first().unwrap(),
+                internal_ids.len() as u32 - 1
+            );
+        }
+    }
+
+    #[test]
+    fn test_building_quantized_only_segment() {
+        let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+        let temp_dir = Builder::new().prefix("segment_temp_dir").tempdir().unwrap();
+
+        let stopped = AtomicBool::new(false);
+
+        let segment = build_segment_1(dir.path());
+
+        let segment_config = SegmentConfig {
+            storage_type: StorageType::Mmap,
+            quantization_config: Some(
+                ScalarQuantizationConfig {
+                    r#type: Default::default(),
+                    quantile: None,
+                    always_ram: None,
+                    quantized_only: Some(true),
+                }
+                .into(),
+            ),
+            ..segment.segment_config.clone()
+        };
+
+        let mut builder =
+            SegmentBuilder::new(dir.path(), temp_dir.path(), &segment_config).unwrap();
+        builder.update_from(&segment, &stopped).unwrap();
+        let quantized_segment: Segment = builder.build(&stopped).unwrap();
+
+        let vector_storage_path =
+            get_vector_storage_path(&quantized_segment.current_path, DEFAULT_VECTOR_NAME);
+        assert!(!vector_storage_path.join("matrix.dat").exists());
+
+        let check_segment = |quantized_segment: &Segment| {
+            assert_eq!(quantized_segment.points_count(), segment.points_count());
+            assert!(quantized_segment.vector_data[DEFAULT_VECTOR_NAME]
+                .vector_storage
+                .borrow()
+                .is_quantized_only());
+
+            // Search is served by quantized vectors
+            let result = quantized_segment
+                .search(
+                    DEFAULT_VECTOR_NAME,
+                    &[1.0, 1.0, 1.0, 1.0],
+                    &WithPayload::default(),
+                    &WithVector::Bool(false),
+                    None,
+                    3,
+                    None,
+                )
+                .unwrap();
+            assert_eq!(result.len(), 3);
+
+            // Original vectors can't be read
+            let point_id = segment.iter_points().next().unwrap();
+            assert!(matches!(
+                quantized_segment.vector(DEFAULT_VECTOR_NAME, point_id),
+                Err(OperationError::VectorsNotStored { .. })
+            ));
+            assert!(matches!(
+                quantized_segment.all_vectors(point_id),
+                Err(OperationError::VectorsNotStored { .. })
+            ));
+            {
+                let mut vector_storage = quantized_segment.vector_data[DEFAULT_VECTOR_NAME]
+                    .vector_storage
+                    .borrow_mut();
+                assert!(matches!(
+                    vector_storage.get_vector(0),
+                    Err(OperationError::VectorsNotStored { .. })
+                ));
+                assert!(matches!(
+                    vector_storage.insert_vector(0, &[1.0, 1.0, 1.0, 1.0]),
+                    Err(OperationError::VectorsNotStored { .. })
+                ));
+            }
+
+            // Search results are returned without vectors, which are not stored
+            let result = quantized_segment
+                .search(
+                    DEFAULT_VECTOR_NAME,
+                    &[1.0, 1.0, 1.0, 1.0],
+                    &WithPayload::default(),
+                    &WithVector::Bool(true),
+                    None,
+                    3,
+                    None,
+                )
+                .unwrap();
+            assert_eq!(result.len(), 3);
+            assert!(result.iter().all(|point| point.vector.is_none()));
+        };
+
+        check_segment(&quantized_segment);
+
+        let segment_path = quantized_segment.current_path.clone();
+        drop(quantized_segment);
+        let loaded_segment = load_segment(&segment_path).unwrap().unwrap();
+        check_segment(&loaded_segment);
+    }
+
     fn estimate_build_time(segment: &Segment, stop_timeout_millis: u64) -> (u64, bool) {
         let stopped = Arc::new(AtomicBool::new(false));
 
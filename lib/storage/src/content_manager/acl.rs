@@ -0,0 +1,194 @@
+use std::collections::HashSet;
+
+use segment::types::{Condition, FieldCondition, Filter, HasIdCondition, PointIdType};
+
+use crate::content_manager::errors::StorageError;
+
+/// HTTP header and gRPC metadata key with comma separated identity claims of the caller.
+///
+/// The service does not authenticate clients itself: the header must be set by the
+/// authenticating proxy in front of the service, which also strips it from client requests.
+pub const ACL_CLAIMS_HEADER: &str = "x-acl-claims";
+
+/// Identity of the caller of a read request, used to enforce point-level ACL of collections
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReadAccess {
+    /// Request of another peer, which already restricted it according to the client's claims
+    Internal,
+    /// Request of a client with the claims of `ACL_CLAIMS_HEADER`, if the header is present
+    Client(Option<Vec<String>>),
+}
+
+impl ReadAccess {
+    pub fn from_claims_header(value: Option<&str>) -> Self {
+        ReadAccess::Client(value.map(|value| {
+            value
+                .split(',')
+                .map(str::trim)
+                .filter(|claim| !claim.is_empty())
+                .map(str::to_owned)
+                .collect()
+        }))
+    }
+
+    /// Claims of the client joined into a single string, used to attribute config changes
+    pub fn identity(&self) -> Option<String> {
+        match self {
+            ReadAccess::Client(Some(claims)) if !claims.is_empty() => Some(claims.join(",")),
+            _ => None,
+        }
+    }
+
+    /// Check that the caller may use endpoints, which expose data of the node or collections
+    /// without the point-level ACL: diagnostics, snapshots, etc.
+    ///
+    /// Callers with identity claims are end users with restricted reads, so they are rejected.
+    pub fn check_unrestricted(&self) -> Result<(), StorageError> {
+        match self {
+            ReadAccess::Client(Some(claims)) if !claims.is_empty() => {
+                Err(StorageError::Forbidden {
+                    description: "Endpoint is not available for callers with identity claims"
+                        .to_string(),
+                })
+            }
+            ReadAccess::Internal | ReadAccess::Client(_) => Ok(()),
+        }
+    }
+
+    /// Mandatory filter for reads of the collection with the given ACL key.
+    ///
+    /// Fails closed: clients without claims can't read collections with enabled ACL at all.
+    pub fn acl_filter(
+        &self,
+        collection_name: &str,
+        acl_key: &str,
+    ) -> Result<Option<Filter>, StorageError> {
+        match self {
+            ReadAccess::Internal => Ok(None),
+            ReadAccess::Client(Some(claims)) if !claims.is_empty() => {
+                Ok(Some(Filter::new_must(Condition::Field(
+                    FieldCondition::new_match(acl_key.to_owned(), claims.clone().into()),
+                ))))
+            }
+            ReadAccess::Client(_) => Err(StorageError::Forbidden {
+                description: format!(
+                    "Collection {collection_name} requires identity claims in the {ACL_CLAIMS_HEADER} header"
+                ),
+            }),
+        }
+    }
+}
+
+/// Intersect the filter of the request with the mandatory ACL filter.
+///
+/// The filter of the request is nested as a whole, so its `should` conditions can't
+/// widen the result beyond the ACL.
+pub fn restrict_filter(filter: Option<Filter>, acl_filter: Option<&Filter>) -> Option<Filter> {
+    let acl_filter = match acl_filter {
+        Some(acl_filter) => acl_filter.clone(),
+        None => return filter,
+    };
+    match filter {
+        None => Some(acl_filter),
+        Some(filter) => Some(Filter {
+            should: None,
+            must: Some(vec![
+                Condition::Filter(acl_filter),
+                Condition::Filter(filter),
+            ]),
+            must_not: None,
+        }),
+    }
+}
+
+/// Filter, which selects only readable points among the given ids
+pub fn readable_ids_filter(ids: &[PointIdType], acl_filter: Option<&Filter>) -> Option<Filter> {
+    let has_id: HashSet<_> = ids.iter().cloned().collect();
+    restrict_filter(
+        Some(Filter::new_must(Condition::HasId(HasIdCondition::from(
+            has_id,
+        )))),
+        acl_filter,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use segment::types::Match;
+
+    use super::*;
+
+    #[test]
+    fn test_claims_header() {
+        assert_eq!(
+            ReadAccess::from_claims_header(Some("team-a, user:42,,")),
+            ReadAccess::Client(Some(vec!["team-a".to_string(), "user:42".to_string()]))
+        );
+        assert_eq!(
+            ReadAccess::from_claims_header(None),
+            ReadAccess::Client(None)
+        );
+        assert_eq!(
+            ReadAccess::from_claims_header(Some("team-a, user:42")).identity(),
+            Some("team-a,user:42".to_string())
+        );
+        assert_eq!(ReadAccess::Internal.identity(), None);
+    }
+
+    #[test]
+    fn test_check_unrestricted() {
+        assert!(ReadAccess::Internal.check_unrestricted().is_ok());
+        assert!(ReadAccess::Client(None).check_unrestricted().is_ok());
+        assert!(ReadAccess::from_claims_header(Some("team-a"))
+            .check_unrestricted()
+            .is_err());
+    }
+
+    #[test]
+    fn test_acl_filter() {
+        let access = ReadAccess::from_claims_header(Some("team-a"));
+        let acl_filter = access.acl_filter("docs", "_acl").unwrap().unwrap();
+        let expected = Condition::Field(FieldCondition::new_match(
+            "_acl".to_string(),
+            Match::from(vec!["team-a".to_string()]),
+        ));
+        assert_eq!(acl_filter.must, Some(vec![expected]));
+
+        assert!(ReadAccess::Internal
+            .acl_filter("docs", "_acl")
+            .unwrap()
+            .is_none());
+        assert!(ReadAccess::Client(None).acl_filter("docs", "_acl").is_err());
+        assert!(ReadAccess::Client(Some(vec![]))
+            .acl_filter("docs", "_acl")
+            .is_err());
+    }
+
+    #[test]
+    fn test_restrict_filter() {
+        let acl_filter = ReadAccess::from_claims_header(Some("team-a"))
+            .acl_filter("docs", "_acl")
+            .unwrap();
+        let request_filter =
+            Filter::new_should(Condition::HasId(HasIdCondition::from(HashSet::from([
+                PointIdType::from(1),
+            ]))));
+
+        assert_eq!(restrict_filter(None, acl_filter.as_ref()), acl_filter);
+        assert_eq!(
+            restrict_filter(Some(request_filter.clone()), None),
+            Some(request_filter.clone())
+        );
+
+        let restricted =
+            restrict_filter(Some(request_filter.clone()), acl_filter.as_ref()).unwrap();
+        assert!(restricted.should.is_none());
+        assert_eq!(
+            restricted.must,
+            Some(vec![
+                Condition::Filter(acl_filter.unwrap()),
+                Condition::Filter(request_filter),
+            ])
+        );
+    }
+}
@@ -3,6 +3,7 @@ use std::fs;
 use std::io::Write;
 use std::path::{Path, PathBuf};
 
+use collection::operations::types::AliasDescription;
 use collection::shards::CollectionId;
 use segment::common::file_operations::{atomic_save_json, read_json};
 use serde::{Deserialize, Serialize};
@@ -10,6 +11,7 @@ use serde::{Deserialize, Serialize};
 use crate::content_manager::errors::StorageError;
 
 pub const ALIAS_MAPPING_CONFIG_FILE: &str = "data.json";
+pub const ALIAS_METADATA_FILE: &str = "metadata.json";
 
 type Alias = String;
 
@@ -26,6 +28,34 @@ impl AliasMapping {
     }
 }
 
+/// Optional information about the alias, set on creation
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq, Default)]
+pub struct AliasMetadata {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Unix timestamp of the alias creation, in seconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<u64>,
+    /// Identity of the user, who created the alias
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_by: Option<String>,
+}
+
+/// Metadata of aliases, stored separately from the mapping to keep its format.
+/// Aliases, created before metadata was recorded, don't have an entry.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq, Default)]
+pub struct AliasMetadataMapping(HashMap<Alias, AliasMetadata>);
+
+impl AliasMetadataMapping {
+    pub fn load(path: &Path) -> Result<Self, StorageError> {
+        Ok(read_json(path)?)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), StorageError> {
+        Ok(atomic_save_json(path, self)?)
+    }
+}
+
 /// Persists mapping between alias and collection name. The data is assumed to be relatively small.
 /// - Reads are served from memory.
 /// - Writes are durably saved.
@@ -33,6 +63,8 @@ impl AliasMapping {
 pub struct AliasPersistence {
     data_path: PathBuf,
     alias_mapping: AliasMapping,
+    metadata_path: PathBuf,
+    alias_metadata: AliasMetadataMapping,
 }
 
 impl AliasPersistence {
@@ -40,8 +72,7 @@ impl AliasPersistence {
         path.join(ALIAS_MAPPING_CONFIG_FILE)
     }
 
-    fn init_file(dir_path: &Path) -> Result<PathBuf, StorageError> {
-        let data_path = Self::get_config_path(dir_path);
+    fn init_file(data_path: PathBuf) -> Result<PathBuf, StorageError> {
         if !data_path.exists() {
             let mut file = fs::File::create(&data_path)?;
             let empty_json = "{}";
@@ -54,11 +85,15 @@ impl AliasPersistence {
         if !dir_path.exists() {
             fs::create_dir_all(&dir_path)?;
         }
-        let data_path = Self::init_file(&dir_path)?;
+        let data_path = Self::init_file(Self::get_config_path(&dir_path))?;
         let alias_mapping = AliasMapping::load(&data_path)?;
+        let metadata_path = Self::init_file(dir_path.join(ALIAS_METADATA_FILE))?;
+        let alias_metadata = AliasMetadataMapping::load(&metadata_path)?;
         Ok(AliasPersistence {
             data_path,
             alias_mapping,
+            metadata_path,
+            alias_metadata,
         })
     }
 
@@ -66,15 +101,32 @@ impl AliasPersistence {
         self.alias_mapping.0.get(alias).cloned()
     }
 
-    pub fn insert(&mut self, alias: String, collection_name: String) -> Result<(), StorageError> {
-        self.alias_mapping.0.insert(alias, collection_name);
+    pub fn metadata(&self, alias: &str) -> Option<&AliasMetadata> {
+        self.alias_metadata.0.get(alias)
+    }
+
+    /// Creates or replaces the alias, metadata of the replaced alias is dropped
+    pub fn insert(
+        &mut self,
+        alias: String,
+        collection_name: String,
+        metadata: AliasMetadata,
+    ) -> Result<(), StorageError> {
+        self.alias_mapping.0.insert(alias.clone(), collection_name);
         self.alias_mapping.save(&self.data_path)?;
+        if metadata == AliasMetadata::default() {
+            self.remove_metadata(&alias)?;
+        } else {
+            self.alias_metadata.0.insert(alias, metadata);
+            self.alias_metadata.save(&self.metadata_path)?;
+        }
         Ok(())
     }
 
     pub fn remove(&mut self, alias: &str) -> Result<Option<String>, StorageError> {
         let res = self.alias_mapping.0.remove(alias);
         self.alias_mapping.save(&self.data_path)?;
+        self.remove_metadata(alias)?;
         Ok(res)
     }
 
@@ -82,6 +134,18 @@ impl AliasPersistence {
     pub fn remove_collection(&mut self, collection_name: &str) -> Result<(), StorageError> {
         self.alias_mapping.0.retain(|_, v| v != collection_name);
         self.alias_mapping.save(&self.data_path)?;
+        let alias_mapping = &self.alias_mapping;
+        self.alias_metadata
+            .0
+            .retain(|alias, _| alias_mapping.0.contains_key(alias));
+        self.alias_metadata.save(&self.metadata_path)?;
+        Ok(())
+    }
+
+    fn remove_metadata(&mut self, alias: &str) -> Result<(), StorageError> {
+        if self.alias_metadata.0.remove(alias).is_some() {
+            self.alias_metadata.save(&self.metadata_path)?;
+        }
         Ok(())
     }
 
@@ -96,14 +160,38 @@ impl AliasPersistence {
             }),
             Some(collection_name) => {
                 self.alias_mapping.0.remove(old_alias_name);
-                self.alias_mapping.0.insert(new_alias_name, collection_name);
+                self.alias_mapping
+                    .0
+                    .insert(new_alias_name.clone(), collection_name);
                 // 'remove' & 'insert' saved atomically
                 self.alias_mapping.save(&self.data_path)?;
+                // Metadata is kept by the renamed alias
+                if let Some(metadata) = self.alias_metadata.0.remove(old_alias_name) {
+                    self.alias_metadata.0.insert(new_alias_name, metadata);
+                    self.alias_metadata.save(&self.metadata_path)?;
+                }
                 Ok(())
             }
         }
     }
 
+    /// Aliases of the collection with their metadata
+    pub fn collection_alias_descriptions(&self, collection_name: &str) -> Vec<AliasDescription> {
+        self.collection_aliases(collection_name)
+            .into_iter()
+            .map(|alias_name| {
+                let metadata = self.metadata(&alias_name).cloned().unwrap_or_default();
+                AliasDescription {
+                    alias_name,
+                    collection_name: collection_name.to_string(),
+                    description: metadata.description,
+                    created_at: metadata.created_at,
+                    created_by: metadata.created_by,
+                }
+            })
+            .collect()
+    }
+
     pub fn collection_aliases(&self, collection_name: &str) -> Vec<String> {
         let mut result = vec![];
         for (alias, target_collection) in self.alias_mapping.0.iter() {
@@ -118,9 +206,19 @@ impl AliasPersistence {
         &self.alias_mapping
     }
 
-    pub fn apply_state(&mut self, alias_mapping: AliasMapping) -> Result<(), StorageError> {
+    pub fn metadata_state(&self) -> &AliasMetadataMapping {
+        &self.alias_metadata
+    }
+
+    pub fn apply_state(
+        &mut self,
+        alias_mapping: AliasMapping,
+        alias_metadata: AliasMetadataMapping,
+    ) -> Result<(), StorageError> {
         self.alias_mapping = alias_mapping;
         self.alias_mapping.save(&self.data_path)?;
+        self.alias_metadata = alias_metadata;
+        self.alias_metadata.save(&self.metadata_path)?;
         Ok(())
     }
 
@@ -1,8 +1,11 @@
+use std::collections::BTreeMap;
+
 use collection::config::CollectionConfig;
 use collection::operations::config_diff::{
     CollectionParamsDiff, HnswConfigDiff, OptimizersConfigDiff, WalConfigDiff,
 };
-use collection::operations::types::VectorsConfig;
+use collection::operations::lifecycle_ops::LifecycleConfig;
+use collection::operations::types::{SparseVectorParams, VectorParams, VectorsConfig};
 use collection::shards::replica_set::ReplicaState;
 use collection::shards::shard::{PeerId, ShardId};
 use collection::shards::transfer::shard_transfer::{ShardTransfer, ShardTransferKey};
@@ -12,6 +15,7 @@ use segment::types::QuantizationConfig;
 use serde::{Deserialize, Serialize};
 use validator::Validate;
 
+use crate::content_manager::errors::StorageError;
 use crate::content_manager::shard_distribution::ShardDistributionProposal;
 
 // *Operation wrapper structure is only required for better OpenAPI generation
@@ -23,6 +27,17 @@ use crate::content_manager::shard_distribution::ShardDistributionProposal;
 pub struct CreateAlias {
     pub collection_name: String,
     pub alias_name: String,
+    /// Free-form description of the alias, e.g. the purpose of the switch
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Set by the service when the operation is submitted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(skip)]
+    pub created_at: Option<u64>,
+    /// Set by the service when the operation is submitted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(skip)]
+    pub created_by: Option<String>,
 }
 
 #[derive(Debug, Deserialize, Serialize, JsonSchema, PartialEq, Eq, Hash, Clone)]
@@ -142,6 +157,9 @@ pub struct CreateCollection {
     #[serde(default, alias = "quantization")]
     #[validate]
     pub quantization_config: Option<QuantizationConfig>,
+    /// Sparse vector data config, searched by dot product using the inverted index.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sparse_vectors: Option<BTreeMap<String, SparseVectorParams>>,
 }
 
 /// Operation for creating new collection and (optionally) specify index params
@@ -185,6 +203,93 @@ pub struct UpdateCollection {
     pub optimizers_config: Option<OptimizersConfigDiff>, // ToDo: Allow updates for other configuration params as well
     /// Collection base params.  If none - values from service configuration file are used.
     pub params: Option<CollectionParamsDiff>,
+    /// New named vectors to add to the collection. Existing vectors can't be changed.
+    /// Points, stored before the vector was added, don't have it until they are upserted with all vectors.
+    pub vectors: Option<BTreeMap<String, VectorParams>>,
+    /// Custom params for WAL. If none - current values are kept.
+    /// New params are applied to WAL segments created after the update.
+    #[validate]
+    pub wal_config: Option<WalConfigDiff>,
+    /// Quantization params of the collection. Replaces existing params.
+    /// Optimized segments are rebuilt in the background to apply new params.
+    #[serde(
+        default,
+        alias = "quantization",
+        skip_serializing_if = "Option::is_none"
+    )]
+    #[validate]
+    pub quantization_config: Option<QuantizationConfig>,
+    /// Lifecycle rules of the collection. Replaces existing rules, empty list removes them.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate]
+    pub lifecycle_config: Option<LifecycleConfig>,
+}
+
+impl UpdateCollection {
+    /// Update, which restores updatable params of the `current` config to the `target` ones.
+    ///
+    /// Vectors are not restored, as existing vectors can't be removed.
+    pub fn rollback_to(
+        current: &CollectionConfig,
+        target: &CollectionConfig,
+    ) -> Result<Self, StorageError> {
+        let unset = |name: &str, current: bool, target: bool| {
+            if current && !target {
+                Err(StorageError::bad_request(&format!(
+                    "Can't roll back, {name} can't be removed from the collection config"
+                )))
+            } else {
+                Ok(())
+            }
+        };
+        unset(
+            "quantization",
+            current.quantization_config.is_some(),
+            target.quantization_config.is_some(),
+        )?;
+        unset(
+            "default vector",
+            current.params.default_vector.is_some(),
+            target.params.default_vector.is_some(),
+        )?;
+        unset(
+            "defrag key",
+            current.params.defrag_key.is_some(),
+            target.params.defrag_key.is_some(),
+        )?;
+
+        let mut params = CollectionParamsDiff::from(target.params.clone());
+        params.vector_aliases = Some(target.params.vector_aliases.clone());
+        if current.params.acl_key.is_some() && target.params.acl_key.is_none() {
+            // Empty key disables ACL
+            params.acl_key = Some(String::new());
+        }
+        // Request defaults can't be removed, but setting them to the defaults of the service
+        // has the same effect
+        if current.params.default_write_ordering.is_some()
+            && params.default_write_ordering.is_none()
+        {
+            params.default_write_ordering = Some(target.params.resolve_write_ordering(None));
+        }
+        if current.params.default_read_consistency.is_some()
+            && params.default_read_consistency.is_none()
+        {
+            params.default_read_consistency = Some(target.params.resolve_read_consistency(None));
+        }
+
+        Ok(Self {
+            optimizers_config: Some(target.optimizer_config.clone().into()),
+            params: Some(params),
+            vectors: None,
+            wal_config: Some(target.wal_config.clone().into()),
+            // Replacing quantization rebuilds optimized segments, so only send it if changed
+            quantization_config: target
+                .quantization_config
+                .clone()
+                .filter(|config| current.quantization_config.as_ref() != Some(config)),
+            lifecycle_config: Some(target.lifecycle_config.clone().unwrap_or_default()),
+        })
+    }
 }
 
 /// Operation for updating parameters of the existing collection
@@ -194,6 +299,9 @@ pub struct UpdateCollectionOperation {
     pub collection_name: String,
     pub update_collection: UpdateCollection,
     shard_replica_changes: Option<Vec<replica_set::Change>>,
+    /// Identity of the user, who requested the update, recorded in the config history
+    #[serde(default)]
+    author: Option<String>,
 }
 
 impl UpdateCollectionOperation {
@@ -203,8 +311,13 @@ impl UpdateCollectionOperation {
             update_collection: UpdateCollection {
                 optimizers_config: None,
                 params: None,
+                vectors: None,
+                wal_config: None,
+                quantization_config: None,
+                lifecycle_config: None,
             },
             shard_replica_changes: None,
+            author: None,
         }
     }
 
@@ -213,9 +326,19 @@ impl UpdateCollectionOperation {
             collection_name,
             update_collection,
             shard_replica_changes: None,
+            author: None,
         }
     }
 
+    pub fn with_author(mut self, author: Option<String>) -> Self {
+        self.author = author;
+        self
+    }
+
+    pub fn take_author(&mut self) -> Option<String> {
+        self.author.take()
+    }
+
     // Returns `true` if there are replica changes associated with this operation
     pub fn have_replica_changes(&self) -> bool {
         self.shard_replica_changes
@@ -246,6 +369,31 @@ pub struct ChangeAliasesOperation {
     pub actions: Vec<AliasOperations>,
 }
 
+impl ChangeAliasesOperation {
+    /// Record the identity of the user, who creates the aliases
+    pub fn with_author(mut self, author: Option<String>) -> Self {
+        for create_alias in self.created_aliases() {
+            create_alias.created_by = author.clone();
+        }
+        self
+    }
+
+    /// Record the creation time of the aliases.
+    /// Set once on submission, so all peers store the same time.
+    pub fn set_created_at(&mut self, timestamp: u64) {
+        for create_alias in self.created_aliases() {
+            create_alias.created_at = Some(timestamp);
+        }
+    }
+
+    fn created_aliases(&mut self) -> impl Iterator<Item = &mut CreateAlias> {
+        self.actions.iter_mut().filter_map(|action| match action {
+            AliasOperations::CreateAlias(operation) => Some(&mut operation.create_alias),
+            AliasOperations::DeleteAlias(_) | AliasOperations::RenameAlias(_) => None,
+        })
+    }
+}
+
 /// Operation for deleting collection with given name
 #[derive(Debug, Deserialize, Serialize, PartialEq, Eq, Hash, Clone)]
 #[serde(rename_all = "snake_case")]
@@ -306,6 +454,7 @@ impl From<CollectionConfig> for CreateCollection {
             optimizers_config: Some(value.optimizer_config.into()),
             init_from: None,
             quantization_config: value.quantization_config,
+            sparse_vectors: Some(value.params.sparse_vectors),
         }
     }
 }
@@ -22,7 +22,7 @@ use tokio::sync::oneshot::Receiver;
 use tokio::time::error::Elapsed;
 use tonic::transport::Uri;
 
-use super::alias_mapping::AliasMapping;
+use super::alias_mapping::{AliasMapping, AliasMetadataMapping};
 use super::consensus_ops::{ConsensusOperations, SnapshotStatus};
 use super::errors::StorageError;
 use super::CollectionContainer;
@@ -54,6 +54,8 @@ pub struct SnapshotData {
 pub struct CollectionsSnapshot {
     pub collections: HashMap<CollectionId, collection_state::State>,
     pub aliases: AliasMapping,
+    #[serde(default)]
+    pub alias_metadata: AliasMetadataMapping,
 }
 
 impl TryFrom<&[u8]> for SnapshotData {
@@ -175,6 +177,15 @@ impl<C: CollectionContainer> ConsensusManager<C> {
         self.persistent.read().this_peer_id
     }
 
+    /// Whether this peer is the leader of the current consensus term
+    pub fn is_leader(&self) -> bool {
+        let this_peer_id = self.this_peer_id();
+        self.soft_state
+            .read()
+            .as_ref()
+            .map_or(false, |state| state.leader_id == this_peer_id)
+    }
+
     pub fn first_voter(&self) -> PeerId {
         match self.first_voter.read().as_ref() {
             Some(id) => *id,
@@ -10,6 +10,7 @@ use crate::content_manager::collection_meta_ops::{
     UpdateCollection, UpdateCollectionOperation,
 };
 use crate::content_manager::errors::StorageError;
+use crate::content_manager::search_queue::SearchPriority;
 
 pub fn error_to_status(error: StorageError) -> tonic::Status {
     let error_code = match &error {
@@ -18,10 +19,33 @@ pub fn error_to_status(error: StorageError) -> tonic::Status {
         StorageError::ServiceError { .. } => tonic::Code::Internal,
         StorageError::BadRequest { .. } => tonic::Code::InvalidArgument,
         StorageError::Locked { .. } => tonic::Code::FailedPrecondition,
+        StorageError::Overloaded { .. } => tonic::Code::ResourceExhausted,
+        StorageError::Forbidden { .. } => tonic::Code::PermissionDenied,
+        StorageError::Timeout { .. } => tonic::Code::DeadlineExceeded,
     };
     tonic::Status::new(error_code, format!("{error}"))
 }
 
+pub fn search_priority_from_proto(priority: Option<i32>) -> Result<SearchPriority, Status> {
+    let priority_parsed = match priority {
+        None => api::grpc::qdrant::SearchPriority::Normal,
+        Some(priority) => match api::grpc::qdrant::SearchPriority::from_i32(priority) {
+            None => {
+                return Err(Status::invalid_argument(format!(
+                    "cannot convert search priority: {priority}"
+                )))
+            }
+            Some(res) => res,
+        },
+    };
+
+    Ok(match priority_parsed {
+        api::grpc::qdrant::SearchPriority::Normal => SearchPriority::Normal,
+        api::grpc::qdrant::SearchPriority::Low => SearchPriority::Low,
+        api::grpc::qdrant::SearchPriority::High => SearchPriority::High,
+    })
+}
+
 impl TryFrom<api::grpc::qdrant::CreateCollection> for CollectionMetaOperations {
     type Error = Status;
 
@@ -66,6 +90,13 @@ impl TryFrom<api::grpc::qdrant::CreateCollection> for CollectionMetaOperations {
                         None
                     }
                 },
+                sparse_vectors: value.sparse_vectors_config.map(|config| {
+                    config
+                        .map
+                        .into_iter()
+                        .map(|(name, params)| (name, params.into()))
+                        .collect()
+                }),
             },
         )))
     }
@@ -80,6 +111,19 @@ impl TryFrom<api::grpc::qdrant::UpdateCollection> for CollectionMetaOperations {
             UpdateCollection {
                 optimizers_config: value.optimizers_config.map(Into::into),
                 params: value.params.map(TryInto::try_into).transpose()?,
+                vectors: value
+                    .vectors
+                    .map(|vectors| {
+                        vectors
+                            .map
+                            .into_iter()
+                            .map(|(name, params)| Ok((name, params.try_into()?)))
+                            .collect::<Result<_, Status>>()
+                    })
+                    .transpose()?,
+                wal_config: value.wal_config.map(Into::into),
+                quantization_config: None,
+                lifecycle_config: None,
             },
         )))
     }
@@ -101,6 +145,9 @@ impl From<api::grpc::qdrant::CreateAlias> for AliasOperations {
             create_alias: CreateAlias {
                 collection_name: value.collection_name,
                 alias_name: value.alias_name,
+                description: None,
+                created_at: None,
+                created_by: None,
             },
         })
     }
@@ -2,15 +2,15 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use collection::collection::Collection;
-use collection::operations::point_ops::{
-    PointInsertOperations, PointOperations, PointStruct, WriteOrdering,
+use collection::operations::point_ops::{PointInsertOperations, PointOperations, PointStruct};
+use collection::operations::types::{
+    CollectionError, CollectionResult, ScrollOrder, ScrollRequest,
 };
-use collection::operations::types::{CollectionError, CollectionResult, ScrollRequest};
 use collection::operations::{CollectionUpdateOperations, CreateIndex, FieldIndexOperations};
 use collection::shards::replica_set::ReplicaState;
 use collection::shards::shard::{PeerId, ShardId};
 use collection::shards::CollectionId;
-use segment::types::{WithPayloadInterface, WithVector};
+use segment::types::{RecordPayload, WithPayloadInterface, WithVector};
 use tokio::sync::RwLock;
 
 use crate::content_manager::collections_ops::Collections;
@@ -83,6 +83,11 @@ async fn replicate_shard_data(
             filter: None,
             with_payload: Some(WithPayloadInterface::Bool(true)),
             with_vector: WithVector::Bool(true),
+            order: ScrollOrder::Id,
+            seed: None,
+            planner_hint: None,
+            geo_order: None,
+            order_by: None,
         };
 
         let collections_read = collections.read().await;
@@ -103,12 +108,16 @@ async fn replicate_shard_data(
         let records = scroll_result
             .points
             .into_iter()
-            .map(|point| PointStruct {
-                id: point.id,
-                vector: point.vector.unwrap(),
-                payload: point.payload,
+            .map(|point| -> CollectionResult<_> {
+                Ok(PointStruct {
+                    id: point.id,
+                    vector: point.vector.unwrap(),
+                    payload: point.payload.map(RecordPayload::into_payload).transpose()?,
+                    sequence: None,
+                    content_hash: None,
+                })
             })
-            .collect();
+            .collect::<CollectionResult<_>>()?;
 
         let upsert_request = CollectionUpdateOperations::PointOperation(
             PointOperations::UpsertPoints(PointInsertOperations::PointsList(records)),
@@ -118,7 +127,7 @@ async fn replicate_shard_data(
             handle_get_collection(collections_read.get(target_collection_name))?;
 
         target_collection
-            .update_from_client(upsert_request, false, WriteOrdering::default())
+            .update_from_client(upsert_request, false, None)
             .await?;
 
         if offset.is_none() {
@@ -220,7 +229,7 @@ pub async fn transfer_indexes(
             }),
         );
         target_collection
-            .update_from_client(request, false, WriteOrdering::default())
+            .update_from_client(request, false, None)
             .await?;
     }
 
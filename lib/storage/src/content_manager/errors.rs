@@ -22,6 +22,12 @@ pub enum StorageError {
     BadRequest { description: String },
     #[error("Storage locked: {description}")]
     Locked { description: String },
+    #[error("Service overloaded: {description}")]
+    Overloaded { description: String },
+    #[error("Forbidden: {description}")]
+    Forbidden { description: String },
+    #[error("Timeout: {description}")]
+    Timeout { description: String },
 }
 
 impl StorageError {
@@ -0,0 +1,121 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use collection::operations::config_diff::OptimizersConfigDiff;
+use collection::operations::lifecycle_ops::LifecycleAction;
+
+use crate::content_manager::collection_meta_ops::{
+    CollectionMetaOperations, DeleteCollectionOperation, UpdateCollection,
+    UpdateCollectionOperation,
+};
+use crate::dispatcher::Dispatcher;
+use crate::StorageError;
+
+/// Memmap threshold, applied by the `on_disk` action. Lowest value, allowed in the config.
+const ON_DISK_MEMMAP_THRESHOLD_KB: usize = 1000;
+
+fn on_disk_optimizers_config() -> OptimizersConfigDiff {
+    OptimizersConfigDiff {
+        deleted_threshold: None,
+        vacuum_min_vector_number: None,
+        default_segment_number: None,
+        max_segment_size: None,
+        memmap_threshold: Some(ON_DISK_MEMMAP_THRESHOLD_KB),
+        indexing_threshold: None,
+        flush_interval_sec: None,
+        max_optimization_threads: None,
+        target_segment_count: None,
+        max_segment_size_mb: None,
+    }
+}
+
+/// Apply due lifecycle rules of the collection.
+///
+/// Actions are submitted as regular collection meta operations, so in distributed mode they are
+/// applied on all peers through consensus. Actions, which are already in effect, are skipped.
+async fn apply_lifecycle_rules(
+    dispatcher: &Dispatcher,
+    collection_name: &str,
+) -> Result<(), StorageError> {
+    let collection = dispatcher.toc().get_collection(collection_name).await?;
+    let config = collection.state().await.config;
+    let lifecycle_config = match &config.lifecycle_config {
+        Some(lifecycle_config) => lifecycle_config,
+        None => return Ok(()),
+    };
+    let age = collection.age().await?;
+    drop(collection);
+
+    let mut update = UpdateCollection {
+        optimizers_config: None,
+        params: None,
+        vectors: None,
+        wal_config: None,
+        quantization_config: None,
+        lifecycle_config: None,
+    };
+    for action in lifecycle_config.due_actions(age) {
+        match action {
+            LifecycleAction::OnDisk => {
+                if config.optimizer_config.memmap_threshold != Some(ON_DISK_MEMMAP_THRESHOLD_KB) {
+                    update.optimizers_config = Some(on_disk_optimizers_config());
+                }
+            }
+            LifecycleAction::Quantize(quantization_config) => {
+                if config.quantization_config.as_ref() != Some(quantization_config) {
+                    update.quantization_config = Some(quantization_config.clone());
+                }
+            }
+            LifecycleAction::Delete => {
+                log::info!(
+                    "Deleting collection {collection_name} according to its lifecycle rules"
+                );
+                dispatcher
+                    .submit_collection_meta_op(
+                        CollectionMetaOperations::DeleteCollection(DeleteCollectionOperation(
+                            collection_name.to_string(),
+                        )),
+                        None,
+                    )
+                    .await?;
+                return Ok(());
+            }
+        }
+    }
+
+    if update.optimizers_config.is_some() || update.quantization_config.is_some() {
+        log::info!("Updating collection {collection_name} according to its lifecycle rules");
+        dispatcher
+            .submit_collection_meta_op(
+                CollectionMetaOperations::UpdateCollection(UpdateCollectionOperation::new(
+                    collection_name.to_string(),
+                    update,
+                )),
+                None,
+            )
+            .await?;
+    }
+    Ok(())
+}
+
+/// Periodically apply lifecycle rules of all collections. If `interval_sec` is 0 - disabled.
+///
+/// In distributed mode rules are evaluated only by the consensus leader.
+pub async fn run_lifecycle_policies(dispatcher: Arc<Dispatcher>, interval_sec: u64) {
+    if interval_sec == 0 {
+        return;
+    }
+    loop {
+        tokio::time::sleep(Duration::from_secs(interval_sec)).await;
+        if let Some(consensus_state) = dispatcher.consensus_state() {
+            if !consensus_state.is_leader() {
+                continue;
+            }
+        }
+        for collection_name in dispatcher.toc().all_collections().await {
+            if let Err(err) = apply_lifecycle_rules(&dispatcher, &collection_name).await {
+                log::warn!("Can't apply lifecycle rules of collection {collection_name}: {err}");
+            }
+        }
+    }
+}
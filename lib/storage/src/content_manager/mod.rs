@@ -4,6 +4,7 @@ use self::collection_meta_ops::CollectionMetaOperations;
 use self::consensus_manager::CollectionsSnapshot;
 use self::errors::StorageError;
 
+pub mod acl;
 pub mod alias_mapping;
 pub mod collection_meta_ops;
 mod collections_ops;
@@ -12,6 +13,9 @@ pub mod consensus_manager;
 pub mod conversions;
 mod data_transfer;
 pub mod errors;
+pub mod lifecycle;
+pub mod reindex;
+pub mod search_queue;
 pub mod shard_distribution;
 pub mod snapshots;
 pub mod toc;
@@ -105,6 +109,10 @@ pub mod consensus_ops {
                 UpdateCollection {
                     optimizers_config: None,
                     params: None,
+                    vectors: None,
+                    wal_config: None,
+                    quantization_config: None,
+                    lifecycle_config: None,
                 },
             );
             operation
@@ -0,0 +1,578 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use collection::config::CollectionConfig;
+use collection::operations::config_diff::{
+    CollectionParamsDiff, DiffConfig, HnswConfigDiff, OptimizersConfigDiff, WalConfigDiff,
+};
+use collection::operations::point_ops::{PointInsertOperations, PointOperations, PointStruct};
+use collection::operations::types::{CollectionResult, ScrollOrder, ScrollRequest};
+use collection::operations::{CollectionUpdateOperations, CreateIndex, FieldIndexOperations};
+use collection::shards::CollectionId;
+use schemars::JsonSchema;
+use segment::types::{QuantizationConfig, RecordPayload, WithPayloadInterface, WithVector};
+use serde::{Deserialize, Serialize};
+use tokio::task::JoinHandle;
+use validator::Validate;
+
+use crate::content_manager::collection_meta_ops::{
+    ChangeAliasesOperation, CollectionMetaOperations, CreateAlias, CreateCollection,
+    CreateCollectionOperation, DeleteCollectionOperation, UpdateCollection,
+    UpdateCollectionOperation,
+};
+use crate::content_manager::toc::TableOfContent;
+use crate::dispatcher::Dispatcher;
+use crate::StorageError;
+
+const REINDEX_BATCH_SIZE: usize = 1000;
+const COLLECTION_INITIATION_TIMEOUT: Duration = Duration::from_secs(60);
+
+pub type ReindexJobId = u64;
+
+/// Rebuild the collection with a new config into a shadow collection and switch aliases to it.
+///
+/// Params, which are not specified, are copied from the source collection.
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, Default)]
+#[serde(rename_all = "snake_case")]
+pub struct ReindexRequest {
+    /// Name of the shadow collection. Default: `{collection_name}_reindex_{job_id}`
+    #[validate(length(min = 1, max = 255))]
+    pub target_collection: Option<String>,
+    /// Aliases to switch to the shadow collection on completion.
+    /// Default: all aliases of the source collection at the time of the switch
+    pub aliases: Option<Vec<String>>,
+    /// Delete the source collection after the aliases are switched. Default: false
+    #[serde(default)]
+    pub delete_source: bool,
+    #[validate(range(min = 1))]
+    pub shard_number: Option<u32>,
+    #[validate(range(min = 1))]
+    pub replication_factor: Option<u32>,
+    #[validate(range(min = 1))]
+    pub write_consistency_factor: Option<u32>,
+    pub on_disk_payload: Option<bool>,
+    /// Changes of the HNSW params of the source collection
+    #[validate]
+    pub hnsw_config: Option<HnswConfigDiff>,
+    /// Changes of the optimizer params of the source collection
+    #[validate]
+    pub optimizers_config: Option<OptimizersConfigDiff>,
+    /// Changes of the WAL params of the source collection
+    #[validate]
+    pub wal_config: Option<WalConfigDiff>,
+    /// Quantization params of the shadow collection. Replaces the params of the source collection
+    #[validate]
+    pub quantization_config: Option<QuantizationConfig>,
+}
+
+/// Stage of the reindex job
+///
+/// * `creating` - shadow collection is being created
+/// * `backfilling` - points are copied, updates of the source collection are written to both collections
+/// * `swapping` - aliases are being switched to the shadow collection
+/// * `completed` - aliases point to the shadow collection
+/// * `failed` - job is stopped because of the error, aliases are not changed
+/// * `cancelled` - job is cancelled by the user, aliases are not changed
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ReindexStatus {
+    Creating,
+    Backfilling,
+    Swapping,
+    Completed,
+    Failed,
+    Cancelled,
+}
+
+impl ReindexStatus {
+    pub fn is_finished(&self) -> bool {
+        matches!(
+            self,
+            ReindexStatus::Completed | ReindexStatus::Failed | ReindexStatus::Cancelled
+        )
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct ReindexJobInfo {
+    pub job_id: ReindexJobId,
+    pub collection_name: String,
+    pub target_collection: String,
+    pub status: ReindexStatus,
+    /// Number of points, copied from the source collection
+    pub copied_points: usize,
+    /// Number of updates of the source collection, written to the shadow collection
+    pub mirrored_operations: usize,
+    /// Unix timestamp of the job start, in seconds
+    pub started_at: u64,
+    /// Unix timestamp of the job end, in seconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub finished_at: Option<u64>,
+    /// Error, which stopped the job
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Reindex jobs, started on this peer.
+///
+/// Jobs are not persisted, an interrupted job leaves the shadow collection as is.
+/// Updates are written to the shadow collection only if they are received by this peer.
+#[derive(Default)]
+pub struct ReindexJobs {
+    last_job_id: ReindexJobId,
+    jobs: HashMap<ReindexJobId, ReindexJobInfo>,
+    tasks: HashMap<ReindexJobId, JoinHandle<()>>,
+}
+
+impl ReindexJobs {
+    pub fn list(&self) -> Vec<ReindexJobInfo> {
+        let mut jobs: Vec<_> = self.jobs.values().cloned().collect();
+        jobs.sort_by_key(|job| job.job_id);
+        jobs
+    }
+
+    pub fn get(&self, job_id: ReindexJobId) -> Result<&ReindexJobInfo, StorageError> {
+        self.jobs.get(&job_id).ok_or_else(|| job_not_found(job_id))
+    }
+
+    fn get_mut(&mut self, job_id: ReindexJobId) -> Option<&mut ReindexJobInfo> {
+        self.jobs.get_mut(&job_id)
+    }
+
+    fn active_job(&self, collection_name: &str) -> Option<&ReindexJobInfo> {
+        self.jobs
+            .values()
+            .find(|job| job.collection_name == collection_name && !job.status.is_finished())
+    }
+
+    /// Whether updates of the collection may need to be written to a shadow collection
+    pub fn is_reindexing(&self, collection_name: &str) -> bool {
+        self.active_job(collection_name).is_some()
+    }
+
+    /// Shadow collection, which receives updates of the collection
+    pub fn mirror_target(&self, collection_name: &str) -> Option<(ReindexJobId, CollectionId)> {
+        self.active_job(collection_name)
+            .filter(|job| job.status != ReindexStatus::Creating)
+            .map(|job| (job.job_id, job.target_collection.clone()))
+    }
+
+    pub fn record_mirrored(&mut self, job_id: ReindexJobId, result: Result<(), StorageError>) {
+        if let Some(job) = self.get_mut(job_id) {
+            match result {
+                Ok(()) => job.mirrored_operations += 1,
+                // Shadow collection misses the update, so it can't replace the source one
+                Err(err) => fail_job(
+                    job,
+                    format!("Can't write update to the shadow collection: {err}"),
+                ),
+            }
+        }
+    }
+
+    /// Returns `false` if the job is already finished, e.g. failed because of a dual-write
+    fn set_status(&mut self, job_id: ReindexJobId, status: ReindexStatus) -> bool {
+        match self.get_mut(job_id) {
+            Some(job) if !job.status.is_finished() => {
+                job.status = status;
+                if status.is_finished() {
+                    job.finished_at = Some(now());
+                }
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+fn fail_job(job: &mut ReindexJobInfo, error: String) {
+    if !job.status.is_finished() {
+        log::error!("Reindex job {} failed: {error}", job.job_id);
+        job.status = ReindexStatus::Failed;
+        job.error = Some(error);
+        job.finished_at = Some(now());
+    }
+}
+
+fn job_not_found(job_id: ReindexJobId) -> StorageError {
+    StorageError::NotFound {
+        description: format!("Reindex job {job_id} not found"),
+    }
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+/// Start the reindex job of the collection in the background
+pub async fn start_reindex(
+    dispatcher: Arc<Dispatcher>,
+    collection_name: &str,
+    request: ReindexRequest,
+) -> Result<ReindexJobInfo, StorageError> {
+    let toc = dispatcher.toc().clone();
+    // Aliases are resolved, the job works with the collection itself
+    let collection = toc.get_collection(collection_name).await?;
+    let collection_name = collection.name();
+    let config = collection.config().await;
+    drop(collection);
+
+    let job = {
+        let mut jobs = toc.reindex_jobs().lock();
+        if let Some(job) = jobs.active_job(&collection_name) {
+            return Err(StorageError::bad_request(&format!(
+                "Collection {collection_name} is already being reindexed by job {}",
+                job.job_id
+            )));
+        }
+        jobs.last_job_id += 1;
+        let job_id = jobs.last_job_id;
+        let job = ReindexJobInfo {
+            job_id,
+            target_collection: request
+                .target_collection
+                .clone()
+                .unwrap_or_else(|| format!("{collection_name}_reindex_{job_id}")),
+            collection_name,
+            status: ReindexStatus::Creating,
+            copied_points: 0,
+            mirrored_operations: 0,
+            started_at: now(),
+            finished_at: None,
+            error: None,
+        };
+        jobs.jobs.insert(job_id, job.clone());
+        job
+    };
+
+    let job_id = job.job_id;
+    let task = tokio::spawn({
+        let job = job.clone();
+        async move {
+            let toc = dispatcher.toc().clone();
+            if let Err(err) = run_reindex(&dispatcher, &job, config, request).await {
+                if let Some(job) = toc.reindex_jobs().lock().get_mut(job.job_id) {
+                    fail_job(job, err.to_string());
+                }
+            }
+        }
+    });
+    toc.reindex_jobs().lock().tasks.insert(job_id, task);
+    Ok(job)
+}
+
+/// Stop the job. The shadow collection is kept, aliases are not changed.
+pub fn cancel_reindex(
+    toc: &TableOfContent,
+    job_id: ReindexJobId,
+) -> Result<ReindexJobInfo, StorageError> {
+    let mut jobs = toc.reindex_jobs().lock();
+    let status = jobs.get(job_id)?.status;
+    if status.is_finished() {
+        return Err(StorageError::bad_request(&format!(
+            "Reindex job {job_id} is already finished"
+        )));
+    }
+    if status == ReindexStatus::Swapping {
+        return Err(StorageError::bad_request(&format!(
+            "Reindex job {job_id} is switching aliases and can't be cancelled"
+        )));
+    }
+    if let Some(task) = jobs.tasks.remove(&job_id) {
+        task.abort();
+    }
+    jobs.set_status(job_id, ReindexStatus::Cancelled);
+    Ok(jobs.get(job_id)?.clone())
+}
+
+fn shadow_collection(
+    config: &CollectionConfig,
+    request: &ReindexRequest,
+) -> Result<CreateCollection, StorageError> {
+    let mut create_collection = CreateCollection::from(config.clone());
+    if let Some(shard_number) = request.shard_number {
+        create_collection.shard_number = Some(shard_number);
+    }
+    if let Some(replication_factor) = request.replication_factor {
+        create_collection.replication_factor = Some(replication_factor);
+    }
+    if let Some(write_consistency_factor) = request.write_consistency_factor {
+        create_collection.write_consistency_factor = Some(write_consistency_factor);
+    }
+    if let Some(on_disk_payload) = request.on_disk_payload {
+        create_collection.on_disk_payload = Some(on_disk_payload);
+    }
+    if let Some(diff) = request.hnsw_config.clone() {
+        create_collection.hnsw_config = Some(diff.update(&config.hnsw_config)?.into());
+    }
+    if let Some(diff) = request.optimizers_config.clone() {
+        create_collection.optimizers_config = Some(diff.update(&config.optimizer_config)?.into());
+    }
+    if let Some(diff) = request.wal_config.clone() {
+        create_collection.wal_config = Some(diff.update(&config.wal_config)?.into());
+    }
+    if let Some(quantization_config) = request.quantization_config.clone() {
+        create_collection.quantization_config = Some(quantization_config);
+    }
+    Ok(create_collection)
+}
+
+/// Params of the source collection, which can't be set on creation.
+/// ACL key must be copied before any point is readable through the shadow collection.
+fn shadow_collection_params(config: &CollectionConfig) -> UpdateCollection {
+    let mut params = CollectionParamsDiff::from(config.params.clone());
+    params.replication_factor = None;
+    params.write_consistency_factor = None;
+    UpdateCollection {
+        optimizers_config: None,
+        params: Some(params),
+        vectors: None,
+        wal_config: None,
+        quantization_config: None,
+        lifecycle_config: config.lifecycle_config.clone(),
+    }
+}
+
+async fn run_reindex(
+    dispatcher: &Dispatcher,
+    job: &ReindexJobInfo,
+    config: CollectionConfig,
+    request: ReindexRequest,
+) -> Result<(), StorageError> {
+    let toc = dispatcher.toc();
+    let source = &job.collection_name;
+    let target = &job.target_collection;
+
+    dispatcher
+        .submit_collection_meta_op(
+            CollectionMetaOperations::CreateCollection(CreateCollectionOperation::new(
+                target.clone(),
+                shadow_collection(&config, &request)?,
+            )),
+            None,
+        )
+        .await?;
+    dispatcher
+        .submit_collection_meta_op(
+            CollectionMetaOperations::UpdateCollection(UpdateCollectionOperation::new(
+                target.clone(),
+                shadow_collection_params(&config),
+            )),
+            None,
+        )
+        .await?;
+    if !toc
+        .get_collection(target)
+        .await?
+        .wait_collection_initiated(COLLECTION_INITIATION_TIMEOUT)
+    {
+        return Err(StorageError::service_error(format!(
+            "Collection {target} was not initialized within {} sec timeout",
+            COLLECTION_INITIATION_TIMEOUT.as_secs()
+        )));
+    }
+
+    let payload_schema = toc
+        .get_collection(source)
+        .await?
+        .info(None)
+        .await?
+        .payload_schema;
+    for (field_name, schema) in payload_schema {
+        let operation = CollectionUpdateOperations::FieldIndexOperation(
+            FieldIndexOperations::CreateIndex(CreateIndex {
+                field_name,
+                field_schema: Some(schema.try_into().map_err(StorageError::service_error)?),
+            }),
+        );
+        toc.update(target, operation, None, true, None).await?;
+    }
+
+    // Updates of the source collection are written to both collections from now on
+    if !toc
+        .reindex_jobs()
+        .lock()
+        .set_status(job.job_id, ReindexStatus::Backfilling)
+    {
+        return Ok(());
+    }
+    backfill(toc, job).await?;
+
+    if !toc
+        .reindex_jobs()
+        .lock()
+        .set_status(job.job_id, ReindexStatus::Swapping)
+    {
+        return Ok(());
+    }
+    swap_aliases(dispatcher, job, request.aliases).await?;
+
+    if request.delete_source {
+        dispatcher
+            .submit_collection_meta_op(
+                CollectionMetaOperations::DeleteCollection(DeleteCollectionOperation(
+                    source.clone(),
+                )),
+                None,
+            )
+            .await?;
+    }
+
+    toc.reindex_jobs()
+        .lock()
+        .set_status(job.job_id, ReindexStatus::Completed);
+    log::info!("Reindex job {} switched {source} to {target}", job.job_id);
+    Ok(())
+}
+
+/// Copy all points of the source collection into the shadow collection.
+///
+/// Updates of the source collection are blocked while a batch is copied, so concurrent updates,
+/// which are written to the shadow collection directly, are not overwritten with stale points.
+async fn backfill(toc: &TableOfContent, job: &ReindexJobInfo) -> Result<(), StorageError> {
+    let mut offset = None;
+    loop {
+        let source = toc.get_collection(&job.collection_name).await?;
+        let target = toc.get_collection(&job.target_collection).await?;
+
+        let request = ScrollRequest {
+            offset,
+            limit: Some(REINDEX_BATCH_SIZE),
+            filter: None,
+            with_payload: Some(WithPayloadInterface::Bool(true)),
+            with_vector: WithVector::Bool(true),
+            order: ScrollOrder::Id,
+            seed: None,
+            planner_hint: None,
+            geo_order: None,
+            order_by: None,
+        };
+
+        let updates_guard = source.lock_updates().await;
+        let scroll_result = source.scroll_by(request, None, None).await?;
+        offset = scroll_result.next_page_offset;
+        let copied = scroll_result.points.len();
+        if copied > 0 {
+            let points = scroll_result
+                .points
+                .into_iter()
+                .map(|point| -> CollectionResult<_> {
+                    Ok(PointStruct {
+                        id: point.id,
+                        vector: point.vector.unwrap(),
+                        payload: point.payload.map(RecordPayload::into_payload).transpose()?,
+                        sequence: None,
+                        content_hash: None,
+                    })
+                })
+                .collect::<CollectionResult<_>>()?;
+            let operation = CollectionUpdateOperations::PointOperation(
+                PointOperations::UpsertPoints(PointInsertOperations::PointsList(points)),
+            );
+            target.update_from_client(operation, true, None).await?;
+        }
+        drop(updates_guard);
+
+        match toc.reindex_jobs().lock().get_mut(job.job_id) {
+            Some(job) if !job.status.is_finished() => job.copied_points += copied,
+            _ => return Ok(()),
+        }
+        if offset.is_none() {
+            return Ok(());
+        }
+    }
+}
+
+/// Switch aliases to the shadow collection in a single operation, keeping their descriptions
+async fn swap_aliases(
+    dispatcher: &Dispatcher,
+    job: &ReindexJobInfo,
+    aliases: Option<Vec<String>>,
+) -> Result<(), StorageError> {
+    let toc = dispatcher.toc();
+    let current_aliases = toc
+        .collection_alias_descriptions(&job.collection_name)
+        .await?;
+    let aliases = aliases.unwrap_or_else(|| {
+        current_aliases
+            .iter()
+            .map(|alias| alias.alias_name.clone())
+            .collect()
+    });
+    if aliases.is_empty() {
+        return Ok(());
+    }
+    let actions = aliases
+        .into_iter()
+        .map(|alias_name| {
+            let description = current_aliases
+                .iter()
+                .find(|alias| alias.alias_name == alias_name)
+                .and_then(|alias| alias.description.clone());
+            CreateAlias {
+                collection_name: job.target_collection.clone(),
+                alias_name,
+                description,
+                created_at: None,
+                created_by: None,
+            }
+            .into()
+        })
+        .collect();
+    dispatcher
+        .submit_collection_meta_op(
+            CollectionMetaOperations::ChangeAliases(ChangeAliasesOperation { actions }),
+            None,
+        )
+        .await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn job(job_id: ReindexJobId, status: ReindexStatus) -> ReindexJobInfo {
+        ReindexJobInfo {
+            job_id,
+            collection_name: "docs".to_string(),
+            target_collection: format!("docs_reindex_{job_id}"),
+            status,
+            copied_points: 0,
+            mirrored_operations: 0,
+            started_at: 0,
+            finished_at: None,
+            error: None,
+        }
+    }
+
+    #[test]
+    fn test_mirror_target() {
+        let mut jobs = ReindexJobs::default();
+        jobs.jobs.insert(1, job(1, ReindexStatus::Completed));
+        jobs.jobs.insert(2, job(2, ReindexStatus::Creating));
+        assert!(jobs.is_reindexing("docs"));
+        assert!(!jobs.is_reindexing("other"));
+        assert_eq!(jobs.mirror_target("docs"), None);
+
+        assert!(jobs.set_status(2, ReindexStatus::Backfilling));
+        assert_eq!(
+            jobs.mirror_target("docs"),
+            Some((2, "docs_reindex_2".to_string()))
+        );
+
+        jobs.record_mirrored(2, Ok(()));
+        assert_eq!(jobs.get(2).unwrap().mirrored_operations, 1);
+
+        // Failed dual-write stops the job before the aliases are switched
+        jobs.record_mirrored(2, Err(StorageError::service_error("unavailable")));
+        assert_eq!(jobs.get(2).unwrap().status, ReindexStatus::Failed);
+        assert!(!jobs.set_status(2, ReindexStatus::Swapping));
+        assert_eq!(jobs.mirror_target("docs"), None);
+    }
+}
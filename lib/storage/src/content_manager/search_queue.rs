@@ -0,0 +1,186 @@
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use tokio::sync::{Semaphore, SemaphorePermit};
+
+use crate::content_manager::errors::StorageError;
+
+/// Priority class of the search request.
+/// If the search queue is filling up, requests with lower priority are rejected first.
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum SearchPriority {
+    /// Rejected, if the queue is more than half full
+    Low,
+    /// Rejected, if the queue is more than 80% full
+    #[default]
+    Normal,
+    /// Rejected only if the queue is full
+    High,
+}
+
+impl SearchPriority {
+    /// Fraction of the queue, which requests of this priority are allowed to occupy
+    fn queue_share(self) -> f64 {
+        match self {
+            SearchPriority::Low => 0.5,
+            SearchPriority::Normal => 0.8,
+            SearchPriority::High => 1.0,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, JsonSchema, Clone, Copy)]
+pub struct SearchQueueStats {
+    /// Number of search requests being executed
+    pub running: usize,
+    /// Number of search requests waiting for execution
+    pub queued: usize,
+    /// Max number of search requests executed concurrently
+    pub max_concurrency: usize,
+    /// Max number of search requests waiting for execution
+    pub max_queue_size: usize,
+    /// Number of requests rejected because of the full queue, since the service start
+    pub rejected: usize,
+}
+
+impl fmt::Display for SearchQueueStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "running {}/{}, queued {}/{}, rejected {}",
+            self.running, self.max_concurrency, self.queued, self.max_queue_size, self.rejected,
+        )
+    }
+}
+
+/// Bounded queue in front of the search runtime.
+/// Limits the number of concurrently executed search requests and the number of requests
+/// waiting for execution, so load spikes are rejected instead of piling up unbounded tasks.
+pub struct SearchQueue {
+    permits: Semaphore,
+    max_concurrency: usize,
+    max_queue_size: usize,
+    queued: AtomicUsize,
+    rejected: AtomicUsize,
+}
+
+/// Keeps the place of the request in the queue until it is admitted or cancelled
+struct QueuedGuard<'a>(&'a AtomicUsize);
+
+impl Drop for QueuedGuard<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+impl SearchQueue {
+    pub fn new(max_concurrency: usize, max_queue_size: usize) -> Self {
+        Self {
+            permits: Semaphore::new(max_concurrency),
+            max_concurrency,
+            max_queue_size,
+            queued: AtomicUsize::new(0),
+            rejected: AtomicUsize::new(0),
+        }
+    }
+
+    /// Wait for the permit to execute the search request.
+    /// Returns `Overloaded` error if there is no room for the request of the given priority.
+    pub async fn admit(&self, priority: SearchPriority) -> Result<SemaphorePermit, StorageError> {
+        if let Ok(permit) = self.permits.try_acquire() {
+            return Ok(permit);
+        }
+
+        let queued = self.queued.fetch_add(1, Ordering::SeqCst);
+        let _queued_guard = QueuedGuard(&self.queued);
+
+        let queue_limit = (self.max_queue_size as f64 * priority.queue_share()).ceil() as usize;
+        if queued >= queue_limit {
+            self.rejected.fetch_add(1, Ordering::Relaxed);
+            return Err(StorageError::Overloaded {
+                description: format!(
+                    "search queue is full for {priority:?} priority requests: {}",
+                    self.stats()
+                ),
+            });
+        }
+
+        self.permits
+            .acquire()
+            .await
+            .map_err(|_| StorageError::service_error("Search queue is closed"))
+    }
+
+    pub fn stats(&self) -> SearchQueueStats {
+        SearchQueueStats {
+            running: self.max_concurrency - self.permits.available_permits(),
+            queued: self.queued.load(Ordering::SeqCst),
+            max_concurrency: self.max_concurrency,
+            max_queue_size: self.max_queue_size,
+            rejected: self.rejected.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use tokio::runtime::Runtime;
+
+    use super::*;
+
+    #[test]
+    fn test_search_queue_admission() {
+        let runtime = Runtime::new().unwrap();
+        runtime.block_on(async {
+            let queue = Arc::new(SearchQueue::new(1, 2));
+
+            let running = queue.admit(SearchPriority::Low).await.unwrap();
+
+            // Low priority requests may only take half of the queue
+            let waiting_queue = queue.clone();
+            let waiting = tokio::spawn(async move {
+                let _permit = waiting_queue.admit(SearchPriority::Low).await.unwrap();
+            });
+            while queue.stats().queued == 0 {
+                tokio::task::yield_now().await;
+            }
+            assert!(matches!(
+                queue.admit(SearchPriority::Low).await,
+                Err(StorageError::Overloaded { .. }),
+            ));
+
+            let waiting_high_queue = queue.clone();
+            let waiting_high = tokio::spawn(async move {
+                let _permit = waiting_high_queue
+                    .admit(SearchPriority::High)
+                    .await
+                    .unwrap();
+            });
+            while queue.stats().queued == 1 {
+                tokio::task::yield_now().await;
+            }
+            assert!(matches!(
+                queue.admit(SearchPriority::High).await,
+                Err(StorageError::Overloaded { .. }),
+            ));
+
+            let stats = queue.stats();
+            assert_eq!(stats.running, 1);
+            assert_eq!(stats.queued, 2);
+            assert_eq!(stats.rejected, 2);
+
+            drop(running);
+            waiting.await.unwrap();
+            waiting_high.await.unwrap();
+
+            let stats = queue.stats();
+            assert_eq!(stats.running, 0);
+            assert_eq!(stats.queued, 0);
+        });
+    }
+}
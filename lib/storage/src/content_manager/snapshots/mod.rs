@@ -4,13 +4,15 @@ pub mod recover;
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
+use collection::collection::Collection;
 use collection::operations::snapshot_ops::{
-    get_snapshot_description, list_snapshots_in_directory, SnapshotDescription,
+    get_snapshot_description, list_snapshots_in_directory, SnapshotDescription, SnapshotInspection,
 };
 use serde::{Deserialize, Serialize};
 use tar::Builder as TarBuilder;
 use tokio::io::AsyncWriteExt;
 
+use crate::content_manager::acl::ReadAccess;
 use crate::content_manager::toc::FULL_SNAPSHOT_FILE_NAME;
 use crate::dispatcher::Dispatcher;
 use crate::{StorageError, TableOfContent};
@@ -97,17 +99,55 @@ async fn _do_delete_collection_snapshot(
     Ok(true)
 }
 
+/// Describe content of the collection snapshot without recovering it
+pub async fn do_inspect_collection_snapshot(
+    toc: &TableOfContent,
+    collection_name: &str,
+    snapshot_name: &str,
+    access: &ReadAccess,
+) -> Result<SnapshotInspection, StorageError> {
+    let collection = toc.get_collection_for_read(collection_name, access).await?;
+    let snapshot_path = collection.get_snapshot_path(snapshot_name).await?;
+
+    let tmp_dir_path = Path::new(toc.storage_path()).join("tmp_collections");
+    tokio::fs::create_dir_all(&tmp_dir_path).await?;
+    let tmp_dir = tempfile::Builder::new()
+        .prefix(&format!("inspect-{collection_name}-"))
+        .tempdir_in(&tmp_dir_path)?;
+
+    log::debug!(
+        "Inspecting snapshot {} in {}",
+        snapshot_path.display(),
+        tmp_dir.path().display()
+    );
+
+    let tmp_dir_clone = tmp_dir.path().to_path_buf();
+    let inspection = tokio::task::spawn_blocking(move || {
+        Collection::inspect_snapshot(&snapshot_path, &tmp_dir_clone)
+    })
+    .await??;
+
+    // Remove unpacked snapshot in the blocking thread as well, segments may be large
+    tokio::task::spawn_blocking(move || tmp_dir.close()).await??;
+
+    Ok(inspection)
+}
+
 pub async fn do_list_full_snapshots(
     toc: &TableOfContent,
+    access: &ReadAccess,
 ) -> Result<Vec<SnapshotDescription>, StorageError> {
+    toc.check_unrestricted_read(access).await?;
     let snapshots_path = Path::new(toc.snapshots_path());
     Ok(list_snapshots_in_directory(snapshots_path).await?)
 }
 
 pub async fn do_create_full_snapshot(
     dispatcher: &Dispatcher,
+    access: &ReadAccess,
     wait: bool,
 ) -> Result<Option<SnapshotDescription>, StorageError> {
+    dispatcher.check_unrestricted_read(access).await?;
     let dispatcher = dispatcher.clone();
     let task = tokio::spawn(async move { _do_create_full_snapshot(&dispatcher).await });
     if wait {
@@ -1,10 +1,12 @@
 use std::cmp::max;
 use std::collections::{HashMap, HashSet};
 use std::fs::{create_dir_all, read_dir};
+use std::future::Future;
 use std::num::NonZeroU32;
 use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use collection::collection::{Collection, RequestShardTransfer};
 use collection::collection_state;
@@ -12,14 +14,21 @@ use collection::config::{
     default_replication_factor, default_write_consistency_factor, CollectionConfig,
     CollectionParams,
 };
+use collection::discovery::discover_by;
+use collection::grouping::group_by;
+use collection::hybrid_query::query_by;
 use collection::operations::config_diff::DiffConfig;
-use collection::operations::consistency_params::ReadConsistency;
+use collection::operations::consistency_params::{ConsistencyToken, ReadConsistency};
+use collection::operations::filter_validation::FilterValidation;
+use collection::operations::orphan_files_ops::OrphanFilesResult;
 use collection::operations::point_ops::WriteOrdering;
+use collection::operations::shared_storage_config::SharedStorageConfig;
 use collection::operations::snapshot_ops::SnapshotDescription;
 use collection::operations::types::{
-    AliasDescription, CollectionResult, CountRequest, CountResult, PointRequest, RecommendRequest,
-    RecommendRequestBatch, Record, ScrollRequest, ScrollResult, SearchRequest, SearchRequestBatch,
-    UpdateResult, VectorsConfig,
+    AliasDescription, ArithmeticSearchRequest, CollectionResult, CountRequest, CountResult,
+    DiscoverRequest, GroupsResult, PointRequest, QueryRequest, RecommendRequest,
+    RecommendRequestBatch, Record, ScrollRequest, ScrollResult, SearchGroupsRequest, SearchRequest,
+    SearchRequestBatch, UpdateResult, VectorsConfig,
 };
 use collection::operations::CollectionUpdateOperations;
 use collection::recommendations::{recommend_batch_by, recommend_by};
@@ -32,10 +41,11 @@ use collection::shards::transfer::shard_transfer::{
 };
 use collection::shards::{replica_set, CollectionId};
 use collection::telemetry::CollectionTelemetry;
+use collection::vector_arithmetic::search_by_arithmetic;
 use segment::common::cpu::get_num_cpus;
-use segment::types::ScoredPoint;
+use segment::types::{Filter, PointIdType, ScoredPoint, SeqNumberType};
 use tokio::runtime::Runtime;
-use tokio::sync::{RwLock, RwLockReadGuard, Semaphore};
+use tokio::sync::{OwnedSemaphorePermit, RwLock, RwLockReadGuard, Semaphore};
 use uuid::Uuid;
 
 use super::collection_meta_ops::{
@@ -43,7 +53,8 @@ use super::collection_meta_ops::{
     UpdateCollectionOperation,
 };
 use super::{consensus_manager, CollectionContainer};
-use crate::content_manager::alias_mapping::AliasPersistence;
+use crate::content_manager::acl::ReadAccess;
+use crate::content_manager::alias_mapping::{AliasMetadata, AliasPersistence};
 use crate::content_manager::collection_meta_ops::{
     AliasOperations, ChangeAliasesOperation, CollectionMetaOperations, CreateAlias,
     CreateAliasOperation, CreateCollection, DeleteAlias, DeleteAliasOperation, RenameAlias,
@@ -53,6 +64,8 @@ use crate::content_manager::collections_ops::{Checker, Collections};
 use crate::content_manager::consensus::operation_sender::OperationSender;
 use crate::content_manager::data_transfer::{populate_collection, transfer_indexes};
 use crate::content_manager::errors::StorageError;
+use crate::content_manager::reindex::{ReindexJobId, ReindexJobInfo, ReindexJobs};
+use crate::content_manager::search_queue::{SearchPriority, SearchQueue};
 use crate::content_manager::shard_distribution::ShardDistributionProposal;
 use crate::types::{PeerAddressById, StorageConfig};
 use crate::ConsensusOperations;
@@ -69,6 +82,9 @@ pub const DEFAULT_WRITE_LOCK_ERROR_MESSAGE: &str = "Write operations are forbidd
 pub struct TableOfContent {
     collections: Arc<RwLock<Collections>>,
     storage_config: Arc<StorageConfig>,
+    /// Node configuration, shared by all collections.
+    /// Budget of optimizations can be changed on config reload.
+    shared_storage_config: Arc<SharedStorageConfig>,
     search_runtime: Runtime,
     update_runtime: Runtime,
     general_runtime: Runtime,
@@ -84,7 +100,14 @@ pub struct TableOfContent {
     /// timings. For example, the health check timing and consensus timing.
     ///
     /// If not defined - no rate limiting is applied.
-    update_rate_limiter: Option<Semaphore>,
+    /// Can be changed on config reload, updates in progress keep permits of the previous limiter.
+    update_rate_limiter: parking_lot::RwLock<Option<Arc<Semaphore>>>,
+    /// Bounded queue of search requests, prevents spawning unbounded number of search tasks
+    /// under load spikes.
+    search_queue: SearchQueue,
+    /// Reindex jobs, started on this peer. Updates of reindexed collections are also
+    /// written to their shadow collections.
+    reindex_jobs: parking_lot::Mutex<ReindexJobs>,
 }
 
 impl TableOfContent {
@@ -104,6 +127,7 @@ impl TableOfContent {
         create_dir_all(&collections_path).expect("Can't create Collections directory");
         let collection_paths =
             read_dir(&collections_path).expect("Can't read Collections directory");
+        let shared_storage_config = Arc::new(storage_config.to_shared_storage_config());
         let mut collections: HashMap<String, Collection> = Default::default();
         for entry in collection_paths {
             let collection_path = entry
@@ -135,7 +159,7 @@ impl TableOfContent {
                 this_peer_id,
                 &collection_path,
                 &collection_snapshots_path,
-                storage_config.to_shared_storage_config().into(),
+                shared_storage_config.clone(),
                 channel_service.clone(),
                 Self::change_peer_state_callback(
                     consensus_proposal_sender.clone(),
@@ -157,27 +181,24 @@ impl TableOfContent {
         let alias_persistence =
             AliasPersistence::open(alias_path).expect("Can't open database by the provided config");
 
-        let rate_limiter = match storage_config.performance.update_rate_limit {
-            Some(limit) => Some(Semaphore::new(limit)),
-            None => {
-                if consensus_proposal_sender.is_some() {
-                    // Auto adjust the rate limit in distributed mode.
-                    // Select number of working threads as a guess.
-                    let limit = max(get_num_cpus(), 2);
-                    log::debug!(
-                        "Auto adjusting update rate limit to {} parallel update requests",
-                        limit
-                    );
-                    Some(Semaphore::new(limit))
-                } else {
-                    None
-                }
-            }
+        let rate_limiter = Self::update_rate_limiter(
+            storage_config.performance.update_rate_limit,
+            consensus_proposal_sender.is_some(),
+        );
+
+        let max_concurrent_searches = match storage_config.performance.max_concurrent_searches {
+            0 => max(get_num_cpus(), 2),
+            limit => limit,
         };
+        let search_queue = SearchQueue::new(
+            max_concurrent_searches,
+            storage_config.performance.search_queue_size,
+        );
 
         TableOfContent {
             collections: Arc::new(RwLock::new(collections)),
             storage_config: Arc::new(storage_config.clone()),
+            shared_storage_config,
             search_runtime,
             update_runtime,
             general_runtime,
@@ -187,10 +208,45 @@ impl TableOfContent {
             consensus_proposal_sender,
             is_write_locked: AtomicBool::new(false),
             lock_error_message: parking_lot::Mutex::new(None),
-            update_rate_limiter: rate_limiter,
+            update_rate_limiter: parking_lot::RwLock::new(rate_limiter),
+            search_queue,
+            reindex_jobs: Default::default(),
         }
     }
 
+    fn update_rate_limiter(limit: Option<usize>, is_distributed: bool) -> Option<Arc<Semaphore>> {
+        match limit {
+            Some(limit) => Some(Arc::new(Semaphore::new(limit))),
+            None => {
+                if is_distributed {
+                    // Auto adjust the rate limit in distributed mode.
+                    // Select number of working threads as a guess.
+                    let limit = max(get_num_cpus(), 2);
+                    log::debug!(
+                        "Auto adjusting update rate limit to {} parallel update requests",
+                        limit
+                    );
+                    Some(Arc::new(Semaphore::new(limit)))
+                } else {
+                    None
+                }
+            }
+        }
+    }
+
+    /// Replace the limit of concurrent update requests
+    pub fn set_update_rate_limit(&self, limit: Option<usize>) {
+        *self.update_rate_limiter.write() = Self::update_rate_limiter(limit, self.is_distributed());
+    }
+
+    /// Replace the limit of concurrent optimizations of all collections.
+    /// Running optimizations are not interrupted, if the limit is lowered.
+    pub fn set_max_optimization_threads(&self, limit: usize) {
+        self.shared_storage_config
+            .optimization_budget
+            .set_limit(limit);
+    }
+
     /// Return `true` if service is working in distributed mode.
     pub fn is_distributed(&self) -> bool {
         self.consensus_proposal_sender.is_some()
@@ -285,6 +341,7 @@ impl TableOfContent {
             write_consistency_factor,
             init_from,
             quantization_config,
+            sparse_vectors,
         } = operation;
 
         self.collections
@@ -342,7 +399,17 @@ impl TableOfContent {
                     description: "`write_consistency_factor` cannot be 0".to_string(),
                 },
             )?,
+            vector_aliases: Default::default(),
+            default_vector: None,
+            defrag_key: None,
+            deduplicate_upserts: false,
+            max_concurrent_searches: 0,
+            acl_key: None,
+            default_write_ordering: None,
+            default_read_consistency: None,
+            sparse_vectors: sparse_vectors.unwrap_or_default(),
         };
+        collection_params.check_sparse_vectors()?;
         let wal_config = match wal_config_diff {
             None => self.storage_config.wal.clone(),
             Some(diff) => diff.update(&self.storage_config.wal)?,
@@ -354,7 +421,7 @@ impl TableOfContent {
         };
 
         let hnsw_config = match hnsw_config_diff {
-            None => self.storage_config.hnsw_index,
+            None => self.storage_config.hnsw_index.clone(),
             Some(diff) => diff.update(&self.storage_config.hnsw_index)?,
         };
 
@@ -369,14 +436,20 @@ impl TableOfContent {
             optimizer_config: optimizers_config,
             hnsw_config,
             quantization_config,
+            lifecycle_config: None,
+            created_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .ok()
+                .map(|since_epoch| since_epoch.as_secs()),
         };
+        collection_config.check_quantized_only_replication()?;
         let collection = Collection::new(
             collection_name.to_string(),
             self.this_peer_id,
             &collection_path,
             &snapshots_path,
             &collection_config,
-            self.storage_config.to_shared_storage_config().into(),
+            self.shared_storage_config.clone(),
             collection_shard_distribution,
             self.channel_service.clone(),
             Self::change_peer_state_callback(
@@ -707,17 +780,40 @@ impl TableOfContent {
         mut operation: UpdateCollectionOperation,
     ) -> Result<bool, StorageError> {
         let replica_changes = operation.take_shard_replica_changes();
+        let author = operation.take_author();
         let UpdateCollection {
             optimizers_config,
             params,
+            vectors,
+            wal_config,
+            quantization_config,
+            lifecycle_config,
         } = operation.update_collection;
         let collection = self.get_collection(&operation.collection_name).await?;
+        let previous_config = collection.config().await;
         if let Some(diff) = optimizers_config {
             collection.update_optimizer_params_from_diff(diff).await?
         }
         if let Some(diff) = params {
             collection.update_params_from_diff(diff).await?;
         }
+        if let Some(vectors) = vectors {
+            collection.add_vectors(vectors).await?;
+        }
+        if let Some(diff) = wal_config {
+            collection.update_wal_params_from_diff(diff).await?;
+        }
+        if let Some(quantization_config) = quantization_config {
+            collection
+                .update_quantization_config(quantization_config)
+                .await?;
+        }
+        if let Some(lifecycle_config) = lifecycle_config {
+            collection.update_lifecycle_config(lifecycle_config).await?;
+        }
+        collection
+            .record_config_change(previous_config, author)
+            .await?;
         if let Some(changes) = replica_changes {
             collection.handle_replica_changes(changes).await?;
         }
@@ -779,6 +875,9 @@ impl TableOfContent {
                         CreateAlias {
                             collection_name,
                             alias_name,
+                            description,
+                            created_at,
+                            created_by,
                         },
                 }) => {
                     collection_lock
@@ -788,7 +887,12 @@ impl TableOfContent {
                         .validate_collection_not_exists(&alias_name)
                         .await?;
 
-                    alias_lock.insert(alias_name, collection_name)?;
+                    let metadata = AliasMetadata {
+                        description,
+                        created_at,
+                        created_by,
+                    };
+                    alias_lock.insert(alias_name, collection_name, metadata)?;
                 }
                 AliasOperations::DeleteAlias(DeleteAliasOperation {
                     delete_alias: DeleteAlias { alias_name },
@@ -1005,6 +1109,58 @@ impl TableOfContent {
         }))
     }
 
+    /// Collection for reads, which expose data of points without the point-level ACL:
+    /// diagnostics, snapshots, WAL archive, etc.
+    ///
+    /// Only requests of other peers can read collections with enabled ACL this way,
+    /// clients are rejected regardless of their claims.
+    pub async fn get_collection_for_read(
+        &self,
+        collection_name: &str,
+        access: &ReadAccess,
+    ) -> Result<RwLockReadGuard<Collection>, StorageError> {
+        let collection = self.get_collection(collection_name).await?;
+        if *access != ReadAccess::Internal && collection.acl_key().await.is_some() {
+            return Err(StorageError::Forbidden {
+                description: format!(
+                    "Endpoint is not available for collection {collection_name} with ACL"
+                ),
+            });
+        }
+        Ok(collection)
+    }
+
+    /// Check that the caller may read data of all collections without the point-level ACL,
+    /// e.g. to download a full snapshot of the node
+    pub async fn check_unrestricted_read(&self, access: &ReadAccess) -> Result<(), StorageError> {
+        access.check_unrestricted()?;
+        for collection_name in self.all_collections().await {
+            match self.get_collection_for_read(&collection_name, access).await {
+                // Collection was removed in the meantime
+                Ok(_) | Err(StorageError::NotFound { .. }) => {}
+                Err(err) => return Err(err),
+            }
+        }
+        Ok(())
+    }
+
+    /// Mandatory filter for reads of the collection with the given access,
+    /// if point-level ACL is enabled for it
+    pub async fn read_acl_filter(
+        &self,
+        collection_name: &str,
+        access: &ReadAccess,
+    ) -> Result<Option<Filter>, StorageError> {
+        if *access == ReadAccess::Internal {
+            return Ok(None);
+        }
+        let collection = self.get_collection(collection_name).await?;
+        match collection.acl_key().await {
+            Some(acl_key) => access.acl_filter(collection_name, &acl_key),
+            None => Ok(None),
+        }
+    }
+
     /// Initiate receiving shard.
     ///
     /// Fails if the collection does not exist
@@ -1029,6 +1185,8 @@ impl TableOfContent {
     ///
     /// * `collection_name` - for what collection do we recommend
     /// * `request` - [`RecommendRequest`]
+    /// * `priority` - priority of the request in the search queue
+    /// * `timeout` - max duration of the request, including the wait in the search queue
     ///
     /// # Result
     ///
@@ -1038,16 +1196,23 @@ impl TableOfContent {
         collection_name: &str,
         request: RecommendRequest,
         read_consistency: Option<ReadConsistency>,
+        priority: SearchPriority,
+        timeout: Option<Duration>,
     ) -> Result<Vec<ScoredPoint>, StorageError> {
-        let collection = self.get_collection(collection_name).await?;
-        recommend_by(
-            request,
-            &collection,
-            |name| self.get_collection_opt(name),
-            read_consistency,
-        )
+        with_timeout(timeout, async {
+            let collection = self.get_collection(collection_name).await?;
+            let _collection_permit = collection.acquire_search_permit().await?;
+            let _permit = self.search_queue.admit(priority).await?;
+            recommend_by(
+                request,
+                &collection,
+                |name| self.get_collection_opt(name),
+                read_consistency,
+            )
+            .await
+            .map_err(|err| err.into())
+        })
         .await
-        .map_err(|err| err.into())
     }
 
     /// Recommend points in a batchig fashion using positive and negative example from the request
@@ -1056,6 +1221,8 @@ impl TableOfContent {
     ///
     /// * `collection_name` - for what collection do we recommend
     /// * `request` - [`RecommendRequestBatch`]
+    /// * `priority` - priority of the request in the search queue
+    /// * `timeout` - max duration of the request, including the wait in the search queue
     ///
     /// # Result
     ///
@@ -1065,9 +1232,47 @@ impl TableOfContent {
         collection_name: &str,
         request: RecommendRequestBatch,
         read_consistency: Option<ReadConsistency>,
+        priority: SearchPriority,
+        timeout: Option<Duration>,
     ) -> Result<Vec<Vec<ScoredPoint>>, StorageError> {
+        with_timeout(timeout, async {
+            let collection = self.get_collection(collection_name).await?;
+            let _collection_permit = collection.acquire_search_permit().await?;
+            let _permit = self.search_queue.admit(priority).await?;
+            recommend_batch_by(
+                request,
+                &collection,
+                |name| self.get_collection_opt(name),
+                read_consistency,
+            )
+            .await
+            .map_err(|err| err.into())
+        })
+        .await
+    }
+
+    /// Search points close to the target example, respecting the context pairs of examples
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_name` - in what collection do we search
+    /// * `request` - [`DiscoverRequest`]
+    /// * `priority` - priority of the request in the search queue
+    ///
+    /// # Result
+    ///
+    /// Points with discovery score
+    pub async fn discover(
+        &self,
+        collection_name: &str,
+        request: DiscoverRequest,
+        read_consistency: Option<ReadConsistency>,
+        priority: SearchPriority,
+    ) -> Result<Vec<ScoredPoint>, StorageError> {
         let collection = self.get_collection(collection_name).await?;
-        recommend_batch_by(
+        let _collection_permit = collection.acquire_search_permit().await?;
+        let _permit = self.search_queue.admit(priority).await?;
+        discover_by(
             request,
             &collection,
             |name| self.get_collection_opt(name),
@@ -1077,6 +1282,78 @@ impl TableOfContent {
         .map_err(|err| err.into())
     }
 
+    /// Search points by the weighted sum of the vectors of the examples
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_name` - in what collection do we search
+    /// * `request` - [`ArithmeticSearchRequest`]
+    /// * `priority` - priority of the request in the search queue
+    ///
+    /// # Result
+    ///
+    /// Points with search score
+    pub async fn search_arithmetic(
+        &self,
+        collection_name: &str,
+        request: ArithmeticSearchRequest,
+        read_consistency: Option<ReadConsistency>,
+        priority: SearchPriority,
+    ) -> Result<Vec<ScoredPoint>, StorageError> {
+        let collection = self.get_collection(collection_name).await?;
+        let _collection_permit = collection.acquire_search_permit().await?;
+        let _permit = self.search_queue.admit(priority).await?;
+        search_by_arithmetic(
+            request,
+            &collection,
+            |name| self.get_collection_opt(name),
+            read_consistency,
+        )
+        .await
+        .map_err(|err| err.into())
+    }
+
+    /// Search points by several prefetch queries and fuse their results into a single ranking
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_name` - in what collection do we search
+    /// * `request` - [`QueryRequest`]
+    /// * `priority` - priority of the request in the search queue
+    ///
+    /// # Result
+    ///
+    /// Points with fused score
+    pub async fn query(
+        &self,
+        collection_name: &str,
+        request: QueryRequest,
+        read_consistency: Option<ReadConsistency>,
+        priority: SearchPriority,
+    ) -> Result<Vec<ScoredPoint>, StorageError> {
+        let collection = self.get_collection(collection_name).await?;
+        let _collection_permit = collection.acquire_search_permit().await?;
+        let _permit = self.search_queue.admit(priority).await?;
+        query_by(request, &collection, read_consistency)
+            .await
+            .map_err(|err| err.into())
+    }
+
+    /// Collection limit is acquired before the search queue, so searches waiting for the
+    /// collection don't occupy the search pool.
+    /// Requests from other peers are already limited by the peer, which coordinates the search.
+    /// Waiting for the local limit there could block peers waiting for each other.
+    async fn acquire_collection_search_permit(
+        &self,
+        collection: &Collection,
+        shard_selection: Option<ShardId>,
+    ) -> Result<Option<OwnedSemaphorePermit>, StorageError> {
+        match shard_selection {
+            None => Ok(collection.acquire_search_permit().await?),
+            Some(_) => Ok(None),
+        }
+    }
+
     /// Search for the closest points using vector similarity with given restrictions defined
     /// in the request
     ///
@@ -1085,6 +1362,8 @@ impl TableOfContent {
     /// * `collection_name` - in what collection do we search
     /// * `request` - [`SearchRequest`]
     /// * `shard_selection` - which local shard to use
+    /// * `priority` - priority of the request in the search queue
+    /// * `timeout` - max duration of the request, including the wait in the search queue
     /// # Result
     ///
     /// Points with search score
@@ -1094,12 +1373,43 @@ impl TableOfContent {
         request: SearchRequest,
         read_consistency: Option<ReadConsistency>,
         shard_selection: Option<ShardId>,
+        priority: SearchPriority,
+        timeout: Option<Duration>,
     ) -> Result<Vec<ScoredPoint>, StorageError> {
-        let collection = self.get_collection(collection_name).await?;
-        collection
-            .search(request, read_consistency, shard_selection)
-            .await
-            .map_err(|err| err.into())
+        with_timeout(timeout, async {
+            let collection = self.get_collection(collection_name).await?;
+            let _collection_permit = self
+                .acquire_collection_search_permit(&collection, shard_selection)
+                .await?;
+            let _permit = self.search_queue.admit(priority).await?;
+            collection
+                .search(request, read_consistency, shard_selection)
+                .await
+                .map_err(|err| err.into())
+        })
+        .await
+    }
+
+    /// Search of a client, which expects results only from the points stored in `shard_key`
+    pub async fn search_with_shard_key(
+        &self,
+        collection_name: &str,
+        request: SearchRequest,
+        read_consistency: Option<ReadConsistency>,
+        shard_key: Option<ShardId>,
+        priority: SearchPriority,
+        timeout: Option<Duration>,
+    ) -> Result<Vec<ScoredPoint>, StorageError> {
+        with_timeout(timeout, async {
+            let collection = self.get_collection(collection_name).await?;
+            let _collection_permit = collection.acquire_search_permit().await?;
+            let _permit = self.search_queue.admit(priority).await?;
+            collection
+                .search_with_shard_key(request, read_consistency, shard_key)
+                .await
+                .map_err(|err| err.into())
+        })
+        .await
     }
 
     /// Search in a batching fashion for the closest points using vector similarity with given restrictions defined
@@ -1110,6 +1420,8 @@ impl TableOfContent {
     /// * `collection_name` - in what collection do we search
     /// * `request` - [`SearchRequestBatch`]
     /// * `shard_selection` - which local shard to use
+    /// * `priority` - priority of the request in the search queue
+    /// * `timeout` - max duration of the request, including the wait in the search queue
     /// # Result
     ///
     /// Points with search score
@@ -1119,10 +1431,93 @@ impl TableOfContent {
         request: SearchRequestBatch,
         read_consistency: Option<ReadConsistency>,
         shard_selection: Option<ShardId>,
+        priority: SearchPriority,
+        timeout: Option<Duration>,
     ) -> Result<Vec<Vec<ScoredPoint>>, StorageError> {
+        with_timeout(timeout, async {
+            let collection = self.get_collection(collection_name).await?;
+            let _collection_permit = self
+                .acquire_collection_search_permit(&collection, shard_selection)
+                .await?;
+            let _permit = self.search_queue.admit(priority).await?;
+            collection
+                .search_batch(request, read_consistency, shard_selection)
+                .await
+                .map_err(|err| err.into())
+        })
+        .await
+    }
+
+    /// Batch search of a client, which expects results only from the points stored in `shard_key`
+    pub async fn search_batch_with_shard_key(
+        &self,
+        collection_name: &str,
+        request: SearchRequestBatch,
+        read_consistency: Option<ReadConsistency>,
+        shard_key: Option<ShardId>,
+        priority: SearchPriority,
+        timeout: Option<Duration>,
+    ) -> Result<Vec<Vec<ScoredPoint>>, StorageError> {
+        with_timeout(timeout, async {
+            let collection = self.get_collection(collection_name).await?;
+            let _collection_permit = collection.acquire_search_permit().await?;
+            let _permit = self.search_queue.admit(priority).await?;
+            collection
+                .search_batch_with_shard_key(request, read_consistency, shard_key)
+                .await
+                .map_err(|err| err.into())
+        })
+        .await
+    }
+
+    /// Search for the closest points and group them by a payload field
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_name` - in what collection do we search
+    /// * `request` - [`SearchGroupsRequest`]
+    /// * `priority` - priority of the request in the search queue
+    ///
+    /// # Result
+    ///
+    /// Groups of points with search score, best groups first
+    pub async fn search_groups(
+        &self,
+        collection_name: &str,
+        request: SearchGroupsRequest,
+        read_consistency: Option<ReadConsistency>,
+        priority: SearchPriority,
+    ) -> Result<GroupsResult, StorageError> {
+        let collection = self.get_collection(collection_name).await?;
+        let _collection_permit = collection.acquire_search_permit().await?;
+        let _permit = self.search_queue.admit(priority).await?;
+        group_by(
+            request,
+            &collection,
+            |name| self.get_collection_opt(name),
+            read_consistency,
+        )
+        .await
+        .map_err(|err| err.into())
+    }
+
+    /// Check the filter of a read request against payload keys of the collection.
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_name` - in what collection the filter is applied
+    /// * `filter` - filter of the request, if any
+    /// * `validation` - overrides the default filter validation mode of the node
+    ///
+    pub async fn check_filter(
+        &self,
+        collection_name: &str,
+        filter: Option<&Filter>,
+        validation: Option<FilterValidation>,
+    ) -> Result<(), StorageError> {
         let collection = self.get_collection(collection_name).await?;
         collection
-            .search_batch(request, read_consistency, shard_selection)
+            .check_filter(filter, validation)
             .await
             .map_err(|err| err.into())
     }
@@ -1152,6 +1547,20 @@ impl TableOfContent {
             .map_err(|err| err.into())
     }
 
+    /// Count of a client, which expects only the points stored in `shard_key`
+    pub async fn count_with_shard_key(
+        &self,
+        collection_name: &str,
+        request: CountRequest,
+        shard_key: Option<ShardId>,
+    ) -> Result<CountResult, StorageError> {
+        let collection = self.get_collection(collection_name).await?;
+        collection
+            .count_with_shard_key(request, shard_key)
+            .await
+            .map_err(|err| err.into())
+    }
+
     /// Return specific points by IDs
     ///
     /// # Arguments
@@ -1177,6 +1586,55 @@ impl TableOfContent {
             .map_err(|err| err.into())
     }
 
+    /// Retrieve of a client, which expects only the points stored in `shard_key`
+    pub async fn retrieve_with_shard_key(
+        &self,
+        collection_name: &str,
+        request: PointRequest,
+        read_consistency: Option<ReadConsistency>,
+        shard_key: Option<ShardId>,
+    ) -> Result<Vec<Record>, StorageError> {
+        let collection = self.get_collection(collection_name).await?;
+        collection
+            .retrieve_with_shard_key(request, read_consistency, shard_key)
+            .await
+            .map_err(|err| err.into())
+    }
+
+    /// Versions of the points, which exist in the collection among the given ids
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_name` - check points of this collection
+    /// * `ids` - ids of the points to check
+    /// * `shard_selection` - which local shard to use
+    pub async fn point_versions(
+        &self,
+        collection_name: &str,
+        ids: &[PointIdType],
+        shard_selection: Option<ShardId>,
+    ) -> Result<HashMap<PointIdType, SeqNumberType>, StorageError> {
+        let collection = self.get_collection(collection_name).await?;
+        collection
+            .point_versions(ids, shard_selection)
+            .await
+            .map_err(|err| err.into())
+    }
+
+    /// Wait until the writes of the consistency token are applied, so the following reads of the
+    /// collection observe them
+    pub async fn wait_for_writes(
+        &self,
+        collection_name: &str,
+        token: &ConsistencyToken,
+    ) -> Result<(), StorageError> {
+        let collection = self.get_collection(collection_name).await?;
+        collection
+            .wait_for_writes(token)
+            .await
+            .map_err(|err| err.into())
+    }
+
     /// List of all collections
     pub async fn all_collections(&self) -> Vec<String> {
         self.collections.read().await.keys().cloned().collect()
@@ -1191,6 +1649,48 @@ impl TableOfContent {
             .collect()
     }
 
+    /// Remove files of the collection, which stay orphaned for longer than the grace period.
+    /// If not specified, the grace period of the storage config is used.
+    pub async fn remove_orphaned_files(
+        &self,
+        collection_name: &str,
+        grace_period_sec: Option<u64>,
+    ) -> Result<OrphanFilesResult, StorageError> {
+        let grace_period_sec =
+            grace_period_sec.unwrap_or(self.storage_config.orphan_files.grace_period_sec);
+        let collection = self.get_collection(collection_name).await?;
+        Ok(collection
+            .remove_orphaned_files(Duration::from_secs(grace_period_sec))
+            .await?)
+    }
+
+    /// Periodically remove orphaned files of all collections, if enabled in the storage config
+    pub async fn run_orphan_files_gc(toc: Arc<Self>) {
+        let interval_sec = toc.storage_config.orphan_files.gc_interval_sec;
+        if interval_sec == 0 {
+            return;
+        }
+        loop {
+            tokio::time::sleep(Duration::from_secs(interval_sec)).await;
+            for collection_name in toc.all_collections().await {
+                match toc.remove_orphaned_files(&collection_name, None).await {
+                    Ok(result) => {
+                        if !result.removed.is_empty() {
+                            log::info!(
+                                "Removed {} orphaned files of collection {collection_name}, reclaimed {} bytes",
+                                result.removed.len(),
+                                result.reclaimed_bytes,
+                            );
+                        }
+                    }
+                    Err(err) => log::warn!(
+                        "Can't remove orphaned files of collection {collection_name}: {err}"
+                    ),
+                }
+            }
+        }
+    }
+
     /// List of all aliases for a given collection
     pub async fn collection_aliases(
         &self,
@@ -1204,17 +1704,26 @@ impl TableOfContent {
         Ok(result)
     }
 
+    /// List of all aliases for a given collection with their metadata
+    pub async fn collection_alias_descriptions(
+        &self,
+        collection_name: &str,
+    ) -> Result<Vec<AliasDescription>, StorageError> {
+        let result = self
+            .alias_persistence
+            .read()
+            .await
+            .collection_alias_descriptions(collection_name);
+        Ok(result)
+    }
+
     /// List of all aliases across all collections
     pub async fn list_aliases(&self) -> Result<Vec<AliasDescription>, StorageError> {
         let all_collections = self.all_collections().await;
+        let alias_persistence = self.alias_persistence.read().await;
         let mut aliases: Vec<AliasDescription> = Default::default();
         for collection_name in &all_collections {
-            for alias in self.collection_aliases(collection_name).await? {
-                aliases.push(AliasDescription {
-                    alias_name: alias.to_string(),
-                    collection_name: collection_name.to_string(),
-                });
-            }
+            aliases.extend(alias_persistence.collection_alias_descriptions(collection_name));
         }
 
         Ok(aliases)
@@ -1227,6 +1736,7 @@ impl TableOfContent {
     /// * `collection_name` - which collection to use
     /// * `request` - [`ScrollRequest`]
     /// * `shard_selection` - which local shard to use
+    /// * `timeout` - max duration of the request
     ///
     /// # Result
     ///
@@ -1237,12 +1747,35 @@ impl TableOfContent {
         request: ScrollRequest,
         read_consistency: Option<ReadConsistency>,
         shard_selection: Option<ShardId>,
+        timeout: Option<Duration>,
     ) -> Result<ScrollResult, StorageError> {
-        let collection = self.get_collection(collection_name).await?;
-        collection
-            .scroll_by(request, read_consistency, shard_selection)
-            .await
-            .map_err(|err| err.into())
+        with_timeout(timeout, async {
+            let collection = self.get_collection(collection_name).await?;
+            collection
+                .scroll_by(request, read_consistency, shard_selection)
+                .await
+                .map_err(|err| err.into())
+        })
+        .await
+    }
+
+    /// Scroll of a client, which expects only the points stored in `shard_key`
+    pub async fn scroll_with_shard_key(
+        &self,
+        collection_name: &str,
+        request: ScrollRequest,
+        read_consistency: Option<ReadConsistency>,
+        shard_key: Option<ShardId>,
+        timeout: Option<Duration>,
+    ) -> Result<ScrollResult, StorageError> {
+        with_timeout(timeout, async {
+            let collection = self.get_collection(collection_name).await?;
+            collection
+                .scroll_by_with_shard_key(request, read_consistency, shard_key)
+                .await
+                .map_err(|err| err.into())
+        })
+        .await
     }
 
     pub async fn update(
@@ -1251,29 +1784,94 @@ impl TableOfContent {
         operation: CollectionUpdateOperations,
         shard_selection: Option<ShardId>,
         wait: bool,
-        ordering: WriteOrdering,
+        ordering: Option<WriteOrdering>,
     ) -> Result<UpdateResult, StorageError> {
-        let collection = self.get_collection(collection_name).await?;
-        let result = match shard_selection {
+        match shard_selection {
             Some(shard_selection) => {
-                collection
+                let collection = self.get_collection(collection_name).await?;
+                let result = collection
                     .update_from_peer(operation, shard_selection, wait)
-                    .await
+                    .await;
+                result.map_err(|err| err.into())
             }
             None => {
-                let _rate_limit = match &self.update_rate_limiter {
-                    None => None,
-                    Some(rate_limiter) => Some(rate_limiter.acquire().await),
-                };
-                if operation.is_write_operation() {
-                    self.check_write_lock()?;
-                }
-                collection
-                    .update_from_client(operation, wait, ordering)
+                self.update_with_shard_key(collection_name, operation, None, wait, ordering)
                     .await
             }
+        }
+    }
+
+    /// Update from client, which expects all points of the operation to be stored in `shard_key`
+    pub async fn update_with_shard_key(
+        &self,
+        collection_name: &str,
+        operation: CollectionUpdateOperations,
+        shard_key: Option<ShardId>,
+        wait: bool,
+        ordering: Option<WriteOrdering>,
+    ) -> Result<UpdateResult, StorageError> {
+        let collection = self.get_collection(collection_name).await?;
+        let rate_limiter = self.update_rate_limiter.read().clone();
+        let _rate_limit = match rate_limiter {
+            None => None,
+            Some(rate_limiter) => Some(rate_limiter.acquire_owned().await),
+        };
+        if operation.is_write_operation() {
+            self.check_write_lock()?;
+        }
+        // Operation is copied in advance, as the shadow collection may start receiving updates
+        // while the operation is applied
+        let collection_name = collection.name();
+        let mirrored_operation = self
+            .reindex_jobs
+            .lock()
+            .is_reindexing(&collection_name)
+            .then(|| operation.clone());
+        let result = collection
+            .update_from_client_with_shard_key(operation, shard_key, wait, ordering)
+            .await?;
+        drop(collection);
+        if let Some(operation) = mirrored_operation {
+            self.mirror_update(&collection_name, operation, wait, ordering)
+                .await;
+        }
+        Ok(result)
+    }
+
+    /// Write the update of the reindexed collection into its shadow collection.
+    /// Shard key is not passed, as the shadow collection may have a different sharding.
+    async fn mirror_update(
+        &self,
+        collection_name: &str,
+        operation: CollectionUpdateOperations,
+        wait: bool,
+        ordering: Option<WriteOrdering>,
+    ) {
+        let (job_id, target) = match self.reindex_jobs.lock().mirror_target(collection_name) {
+            Some(mirror) => mirror,
+            None => return,
+        };
+        let result = match self.get_collection(&target).await {
+            Ok(target) => target
+                .update_from_client_with_shard_key(operation, None, wait, ordering)
+                .await
+                .map(|_| ())
+                .map_err(StorageError::from),
+            Err(err) => Err(err),
         };
-        result.map_err(|err| err.into())
+        self.reindex_jobs.lock().record_mirrored(job_id, result);
+    }
+
+    pub(crate) fn reindex_jobs(&self) -> &parking_lot::Mutex<ReindexJobs> {
+        &self.reindex_jobs
+    }
+
+    pub fn list_reindex_jobs(&self) -> Vec<ReindexJobInfo> {
+        self.reindex_jobs.lock().list()
+    }
+
+    pub fn reindex_job(&self, job_id: ReindexJobId) -> Result<ReindexJobInfo, StorageError> {
+        Ok(self.reindex_jobs.lock().get(job_id)?.clone())
     }
 
     fn this_peer_id(&self) -> PeerId {
@@ -1293,9 +1891,11 @@ impl TableOfContent {
         for (id, collection) in self.collections.read().await.iter() {
             collections.insert(id.clone(), collection.state().await);
         }
+        let alias_persistence = self.alias_persistence.read().await;
         consensus_manager::CollectionsSnapshot {
             collections,
-            aliases: self.alias_persistence.read().await.state().clone(),
+            aliases: alias_persistence.state().clone(),
+            alias_metadata: alias_persistence.metadata_state().clone(),
         }
     }
 
@@ -1321,7 +1921,7 @@ impl TableOfContent {
                         &collection_path,
                         &snapshots_path,
                         &state.config,
-                        self.storage_config.to_shared_storage_config().into(),
+                        self.shared_storage_config.clone(),
                         shard_distribution,
                         self.channel_service.clone(),
                         Self::change_peer_state_callback(
@@ -1405,7 +2005,7 @@ impl TableOfContent {
             self.alias_persistence
                 .write()
                 .await
-                .apply_state(data.aliases)?;
+                .apply_state(data.aliases, data.alias_metadata)?;
 
             Ok(())
         })
@@ -1634,3 +2234,26 @@ impl Drop for TableOfContent {
         });
     }
 }
+
+/// Fail the read with `Timeout` error, if it is not complete within the `timeout`.
+///
+/// The read is dropped on timeout. Searches in segments, spawned for the read, are stopped along
+/// with it, so they don't keep occupying the search runtime.
+async fn with_timeout<T>(
+    timeout: Option<Duration>,
+    read: impl Future<Output = Result<T, StorageError>>,
+) -> Result<T, StorageError> {
+    match timeout {
+        None => read.await,
+        Some(timeout) => {
+            tokio::time::timeout(timeout, read)
+                .await
+                .map_err(|_| StorageError::Timeout {
+                    description: format!(
+                        "operation is not completed within {:.3} sec",
+                        timeout.as_secs_f64()
+                    ),
+                })?
+        }
+    }
+}
@@ -1,7 +1,7 @@
 use std::num::NonZeroU32;
 use std::ops::Deref;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use crate::{
     ClusterStatus, CollectionMetaOperations, ConsensusOperations, ConsensusStateRef, StorageError,
@@ -43,6 +43,18 @@ impl Dispatcher {
         operation: CollectionMetaOperations,
         wait_timeout: Option<Duration>,
     ) -> Result<bool, StorageError> {
+        let operation = match operation {
+            CollectionMetaOperations::ChangeAliases(mut op) => {
+                let now = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_secs();
+                op.set_created_at(now);
+                CollectionMetaOperations::ChangeAliases(op)
+            }
+            op => op,
+        };
+
         // if distributed deployment is enabled
         if let Some(state) = self.consensus_state.as_ref() {
             // List of operations to await for collection to be operational
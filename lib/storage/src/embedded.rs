@@ -0,0 +1,102 @@
+//! Embedded mode - the storage, running inside the process of an application.
+//!
+//! No HTTP or gRPC servers are started and there is no consensus: all collections are local
+//! to the process. Operations are called directly on [`TableOfContent`], for collection
+//! management there are shortcuts in [`QdrantEmbedded`].
+//!
+//! ```ignore
+//! let qdrant = QdrantEmbedded::open(&storage_config)?;
+//! runtime.block_on(async {
+//!     qdrant.create_collection("docs", create_collection).await?;
+//!     qdrant.toc().update("docs", operation, None, true, None).await?;
+//!     qdrant.toc().search("docs", request, None, None, SearchPriority::default(), None).await
+//! })?;
+//! // Runtimes of the storage are stopped here, outside of the async context
+//! drop(qdrant);
+//! ```
+//!
+//! Requests are not validated as they are by the API, validate them with [`validator::Validate`]
+//! before calling the storage, if they come from untrusted input.
+//!
+//! The storage crate doesn't depend on actix. It still depends on tonic: collections use it to
+//! reach shards on other peers, and the types of the internal API are generated with it.
+
+use std::sync::Arc;
+
+use crate::content_manager::collection_meta_ops::{
+    CollectionMetaOperations, CreateCollection, CreateCollectionOperation,
+    DeleteCollectionOperation,
+};
+use crate::content_manager::errors::StorageError;
+use crate::content_manager::toc::TableOfContent;
+use crate::dispatcher::Dispatcher;
+use crate::runtime::{
+    create_general_purpose_runtime, create_search_runtime, create_update_runtime,
+};
+use crate::types::StorageConfig;
+
+/// Peer id of the embedded storage. There are no other peers, so it only has to be stable.
+const EMBEDDED_PEER_ID: u64 = 0;
+
+/// The storage, running in the current process without servers.
+///
+/// Collections, created by a previous run with the same `storage_path`, are loaded on open.
+/// Holds the runtimes of the storage, so it must be dropped outside of an async context.
+pub struct QdrantEmbedded {
+    dispatcher: Dispatcher,
+}
+
+impl QdrantEmbedded {
+    /// Open the storage in `storage_path` of the config, creating it if it doesn't exist
+    pub fn open(config: &StorageConfig) -> Result<Self, StorageError> {
+        let toc = TableOfContent::new(
+            config,
+            create_search_runtime(config.performance.max_search_threads)?,
+            create_update_runtime()?,
+            create_general_purpose_runtime()?,
+            Default::default(),
+            EMBEDDED_PEER_ID,
+            None,
+        );
+        Ok(Self {
+            dispatcher: Dispatcher::new(Arc::new(toc)),
+        })
+    }
+
+    /// Storage of the collections, which executes searches and updates of points
+    pub fn toc(&self) -> &Arc<TableOfContent> {
+        self.dispatcher.toc()
+    }
+
+    /// Executor of collection management operations, such as changes of aliases
+    pub fn dispatcher(&self) -> &Dispatcher {
+        &self.dispatcher
+    }
+
+    pub async fn create_collection(
+        &self,
+        collection_name: &str,
+        create_collection: CreateCollection,
+    ) -> Result<bool, StorageError> {
+        self.dispatcher
+            .submit_collection_meta_op(
+                CollectionMetaOperations::CreateCollection(CreateCollectionOperation::new(
+                    collection_name.to_string(),
+                    create_collection,
+                )),
+                None,
+            )
+            .await
+    }
+
+    pub async fn delete_collection(&self, collection_name: &str) -> Result<bool, StorageError> {
+        self.dispatcher
+            .submit_collection_meta_op(
+                CollectionMetaOperations::DeleteCollection(DeleteCollectionOperation(
+                    collection_name.to_string(),
+                )),
+                None,
+            )
+            .await
+    }
+}
@@ -14,6 +14,8 @@ use types::ClusterStatus;
 
 pub mod content_manager;
 pub mod dispatcher;
+pub mod embedded;
+pub mod runtime;
 pub mod types;
 
 pub mod serialize_peer_addresses {
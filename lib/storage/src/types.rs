@@ -2,10 +2,14 @@ use std::collections::HashMap;
 
 use chrono::{DateTime, Utc};
 use collection::config::WalConfig;
-use collection::operations::shared_storage_config::SharedStorageConfig;
-use collection::operations::types::NodeType;
+use collection::operations::filter_validation::FilterValidation;
+use collection::operations::shared_storage_config::{
+    DeadReplicaRecoveryConfig, SharedStorageConfig,
+};
+use collection::operations::types::{NodeType, ReadPreference};
 use collection::optimizers_builder::OptimizersConfig;
 use collection::shards::shard::PeerId;
+use collection::wal_archive::WalArchiveConfig;
 use schemars::JsonSchema;
 use segment::common::anonymize::Anonymize;
 use segment::madvise;
@@ -23,12 +27,31 @@ pub struct PerformanceConfig {
     pub max_optimization_threads: usize,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub update_rate_limit: Option<usize>,
+    /// Fraction of approximate searches, which are repeated as exact searches to estimate recall.
+    /// If 0 - recall is not measured.
+    #[serde(default)]
+    pub search_recall_sample_rate: f64,
+    /// Fraction of retrieve and search requests, which are accounted in access counters
+    /// of the returned points. If 0 - access is not tracked.
+    #[serde(default)]
+    pub point_access_sample_rate: f64,
+    /// Max number of search requests executed concurrently. If 0 - auto selection.
+    #[serde(default)]
+    pub max_concurrent_searches: usize,
+    /// Max number of search requests waiting for execution.
+    /// Requests above the limit are rejected as overloaded.
+    #[serde(default = "default_search_queue_size")]
+    pub search_queue_size: usize,
 }
 
 fn default_max_optimization_threads() -> usize {
     1
 }
 
+fn default_search_queue_size() -> usize {
+    1024
+}
+
 /// Global configuration of the storage, loaded on the service launch, default stored in ./config
 #[derive(Clone, Debug, Deserialize, Validate)]
 pub struct StorageConfig {
@@ -54,14 +77,75 @@ pub struct StorageConfig {
     pub node_type: NodeType,
     #[serde(default)]
     pub update_queue_size: Option<usize>,
+    /// Replica selection policy for reads, which can be served by any replica of the shard
+    #[serde(default)]
+    pub read_preference: ReadPreference,
+    /// Validation of filters of read requests, which don't specify it explicitly
+    #[serde(default)]
+    pub filter_validation: FilterValidation,
+    /// Archiving of WAL records before truncation, for point-in-time recovery
+    #[serde(default)]
+    pub wal_archive: Option<WalArchiveConfig>,
+    /// Removal of files in segment directories, which are not used by any segment
+    #[serde(default)]
+    #[validate]
+    pub orphan_files: OrphanFilesConfig,
+    /// Interval between evaluations of collection lifecycle rules, in seconds. If 0 - disabled.
+    #[serde(default = "default_lifecycle_check_interval_sec")]
+    pub lifecycle_check_interval_sec: u64,
+    /// Automatic recovery of replicas of this peer, which are marked as dead
+    #[serde(default)]
+    pub dead_replica_recovery: DeadReplicaRecoveryConfig,
 }
 
 impl StorageConfig {
     pub fn to_shared_storage_config(&self) -> SharedStorageConfig {
-        SharedStorageConfig::new(self.update_queue_size, self.node_type)
+        SharedStorageConfig::new(
+            self.update_queue_size,
+            self.node_type,
+            self.performance.search_recall_sample_rate,
+            self.performance.point_access_sample_rate,
+            self.read_preference,
+            self.filter_validation,
+            self.wal_archive.clone(),
+            self.dead_replica_recovery,
+            self.performance.max_optimization_threads,
+        )
     }
 }
 
+#[derive(Debug, Deserialize, Validate, Clone)]
+pub struct OrphanFilesConfig {
+    /// Interval between background checks of orphaned files, in seconds. If 0 - disabled.
+    #[serde(default = "default_orphan_files_gc_interval_sec")]
+    pub gc_interval_sec: u64,
+    /// Files are removed only if they stay orphaned for this number of seconds since they were found
+    #[serde(default = "default_orphan_files_grace_period_sec")]
+    #[validate(range(min = 1))]
+    pub grace_period_sec: u64,
+}
+
+impl Default for OrphanFilesConfig {
+    fn default() -> Self {
+        OrphanFilesConfig {
+            gc_interval_sec: default_orphan_files_gc_interval_sec(),
+            grace_period_sec: default_orphan_files_grace_period_sec(),
+        }
+    }
+}
+
+fn default_lifecycle_check_interval_sec() -> u64 {
+    3600
+}
+
+fn default_orphan_files_gc_interval_sec() -> u64 {
+    3600
+}
+
+fn default_orphan_files_grace_period_sec() -> u64 {
+    3600
+}
+
 fn default_snapshots_path() -> String {
     "./snapshots".to_string()
 }
@@ -0,0 +1,189 @@
+#![allow(deprecated)]
+
+use std::num::NonZeroU64;
+use std::sync::Arc;
+
+use collection::operations::config_diff::CollectionParamsDiff;
+use collection::operations::types::VectorParams;
+use collection::optimizers_builder::OptimizersConfig;
+use segment::madvise;
+use segment::types::Distance;
+use storage::content_manager::acl::ReadAccess;
+use storage::content_manager::collection_meta_ops::{
+    CollectionMetaOperations, CreateCollection, CreateCollectionOperation, UpdateCollection,
+    UpdateCollectionOperation,
+};
+use storage::content_manager::consensus::operation_sender::OperationSender;
+use storage::content_manager::errors::StorageError;
+use storage::content_manager::toc::TableOfContent;
+use storage::dispatcher::Dispatcher;
+use storage::types::{PerformanceConfig, StorageConfig};
+use tempfile::Builder;
+use tokio::runtime::Runtime;
+
+#[test]
+fn test_read_acl_of_restricted_collection() {
+    let storage_dir = Builder::new().prefix("storage").tempdir().unwrap();
+
+    let config = StorageConfig {
+        storage_path: storage_dir.path().to_str().unwrap().to_string(),
+        snapshots_path: storage_dir
+            .path()
+            .join("snapshots")
+            .to_str()
+            .unwrap()
+            .to_string(),
+        on_disk_payload: false,
+        optimizers: OptimizersConfig {
+            deleted_threshold: 0.5,
+            vacuum_min_vector_number: 100,
+            default_segment_number: 2,
+            max_segment_size: None,
+            memmap_threshold: Some(100),
+            indexing_threshold: 100,
+            flush_interval_sec: 2,
+            max_optimization_threads: 2,
+            target_segment_count: None,
+            max_segment_size_mb: None,
+        },
+        wal: Default::default(),
+        performance: PerformanceConfig {
+            max_search_threads: 1,
+            max_optimization_threads: 1,
+            update_rate_limit: None,
+            search_recall_sample_rate: 0.0,
+            point_access_sample_rate: 0.0,
+            max_concurrent_searches: 0,
+            search_queue_size: 1024,
+        },
+        hnsw_index: Default::default(),
+        quantization: None,
+        mmap_advice: madvise::Advice::Random,
+        node_type: Default::default(),
+        update_queue_size: Default::default(),
+        read_preference: Default::default(),
+        filter_validation: Default::default(),
+        wal_archive: None,
+        orphan_files: Default::default(),
+        lifecycle_check_interval_sec: 0,
+        dead_replica_recovery: Default::default(),
+    };
+
+    let search_runtime = Runtime::new().unwrap();
+    let handle = search_runtime.handle().clone();
+
+    let update_runtime = Runtime::new().unwrap();
+
+    let general_runtime = Runtime::new().unwrap();
+
+    let (propose_sender, _propose_receiver) = std::sync::mpsc::channel();
+    let propose_operation_sender = OperationSender::new(propose_sender);
+
+    let toc = Arc::new(TableOfContent::new(
+        &config,
+        search_runtime,
+        update_runtime,
+        general_runtime,
+        Default::default(),
+        0,
+        Some(propose_operation_sender),
+    ));
+    let dispatcher = Dispatcher::new(toc);
+
+    handle
+        .block_on(
+            dispatcher.submit_collection_meta_op(
+                CollectionMetaOperations::CreateCollection(CreateCollectionOperation::new(
+                    "test".to_string(),
+                    CreateCollection {
+                        vectors: VectorParams {
+                            size: NonZeroU64::new(10).unwrap(),
+                            distance: Distance::Cosine,
+                            hnsw_config: None,
+                            quantization_config: None,
+                            projection: None,
+                        }
+                        .into(),
+                        hnsw_config: None,
+                        wal_config: None,
+                        optimizers_config: None,
+                        shard_number: Some(1),
+                        on_disk_payload: None,
+                        replication_factor: None,
+                        write_consistency_factor: None,
+                        init_from: None,
+                        quantization_config: None,
+                        sparse_vectors: None,
+                    },
+                )),
+                None,
+            ),
+        )
+        .unwrap();
+
+    handle
+        .block_on(dispatcher.submit_collection_meta_op(
+            CollectionMetaOperations::UpdateCollection(UpdateCollectionOperation::new(
+                "test".to_string(),
+                UpdateCollection {
+                    optimizers_config: None,
+                    params: Some(CollectionParamsDiff {
+                        replication_factor: None,
+                        write_consistency_factor: None,
+                        vector_aliases: None,
+                        default_vector: None,
+                        defrag_key: None,
+                        deduplicate_upserts: None,
+                        max_concurrent_searches: None,
+                        acl_key: Some("readers".to_string()),
+                        default_write_ordering: None,
+                        default_read_consistency: None,
+                    }),
+                    vectors: None,
+                    wal_config: None,
+                    quantization_config: None,
+                    lifecycle_config: None,
+                },
+            )),
+            None,
+        ))
+        .unwrap();
+
+    let restricted = ReadAccess::from_claims_header(Some("team-a"));
+    let anonymous = ReadAccess::from_claims_header(None);
+
+    // Point-level filter is applied to reads, which support it
+    let filter = handle
+        .block_on(dispatcher.read_acl_filter("test", &restricted))
+        .unwrap();
+    assert!(filter.is_some());
+    assert!(matches!(
+        handle.block_on(dispatcher.read_acl_filter("test", &anonymous)),
+        Err(StorageError::Forbidden { .. })
+    ));
+
+    // Reads, which can't be filtered by points: point access stats, payload schema, recall,
+    // WAL archive and collection snapshots
+    for access in [&restricted, &anonymous] {
+        assert!(matches!(
+            handle.block_on(dispatcher.get_collection_for_read("test", access)),
+            Err(StorageError::Forbidden { .. })
+        ));
+    }
+    assert!(handle
+        .block_on(dispatcher.get_collection_for_read("test", &ReadAccess::Internal))
+        .is_ok());
+
+    // Full snapshots, debug and dashboard
+    assert!(matches!(
+        handle.block_on(dispatcher.check_unrestricted_read(&restricted)),
+        Err(StorageError::Forbidden { .. })
+    ));
+    assert!(matches!(
+        handle.block_on(dispatcher.check_unrestricted_read(&anonymous)),
+        Err(StorageError::Forbidden { .. })
+    ));
+    assert!(handle
+        .block_on(dispatcher.check_unrestricted_read(&ReadAccess::Internal))
+        .is_ok());
+}
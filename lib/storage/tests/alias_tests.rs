@@ -40,18 +40,30 @@ fn test_alias_operation() {
             indexing_threshold: 100,
             flush_interval_sec: 2,
             max_optimization_threads: 2,
+            target_segment_count: None,
+            max_segment_size_mb: None,
         },
         wal: Default::default(),
         performance: PerformanceConfig {
             max_search_threads: 1,
             max_optimization_threads: 1,
             update_rate_limit: None,
+            search_recall_sample_rate: 0.0,
+            point_access_sample_rate: 0.0,
+            max_concurrent_searches: 0,
+            search_queue_size: 1024,
         },
         hnsw_index: Default::default(),
         quantization: None,
         mmap_advice: madvise::Advice::Random,
         node_type: Default::default(),
         update_queue_size: Default::default(),
+        read_preference: Default::default(),
+        filter_validation: Default::default(),
+        wal_archive: None,
+        orphan_files: Default::default(),
+        lifecycle_check_interval_sec: 0,
+        dead_replica_recovery: Default::default(),
     };
 
     let search_runtime = Runtime::new().unwrap();
@@ -86,6 +98,7 @@ fn test_alias_operation() {
                             distance: Distance::Cosine,
                             hnsw_config: None,
                             quantization_config: None,
+                            projection: None,
                         }
                         .into(),
                         hnsw_config: None,
@@ -97,6 +110,7 @@ fn test_alias_operation() {
                         write_consistency_factor: None,
                         init_from: None,
                         quantization_config: None,
+                        sparse_vectors: None,
                     },
                 )),
                 None,
@@ -110,6 +124,9 @@ fn test_alias_operation() {
                 actions: vec![CreateAlias {
                         collection_name: "test".to_string(),
                         alias_name: "test_alias".to_string(),
+                        description: None,
+                        created_at: None,
+                        created_by: None,
                     }
                     .into()],
             }),
@@ -124,6 +141,9 @@ fn test_alias_operation() {
                         CreateAlias {
                             collection_name: "test".to_string(),
                             alias_name: "test_alias2".to_string(),
+                            description: Some("blue".to_string()),
+                            created_at: None,
+                            created_by: None,
                         }
                         .into(),
                         DeleteAlias {
@@ -144,4 +164,11 @@ fn test_alias_operation() {
     let _ = handle
         .block_on(dispatcher.get_collection("test_alias3"))
         .unwrap();
+
+    // Metadata is kept by the renamed alias
+    let aliases = handle.block_on(dispatcher.list_aliases()).unwrap();
+    assert_eq!(aliases.len(), 1);
+    assert_eq!(aliases[0].alias_name, "test_alias3");
+    assert_eq!(aliases[0].description.as_deref(), Some("blue"));
+    assert!(aliases[0].created_at.is_some());
 }
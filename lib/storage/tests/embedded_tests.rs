@@ -0,0 +1,141 @@
+use std::num::NonZeroU64;
+
+use collection::operations::point_ops::{Batch, PointInsertOperations, PointOperations};
+use collection::operations::types::{SearchRequest, VectorParams};
+use collection::operations::CollectionUpdateOperations;
+use collection::optimizers_builder::OptimizersConfig;
+use segment::madvise;
+use segment::types::Distance;
+use storage::content_manager::collection_meta_ops::CreateCollection;
+use storage::content_manager::search_queue::SearchPriority;
+use storage::embedded::QdrantEmbedded;
+use storage::types::{PerformanceConfig, StorageConfig};
+use tempfile::Builder;
+use tokio::runtime::Runtime;
+
+#[test]
+fn test_embedded_storage() {
+    let storage_dir = Builder::new().prefix("storage").tempdir().unwrap();
+
+    let config = StorageConfig {
+        storage_path: storage_dir.path().to_str().unwrap().to_string(),
+        snapshots_path: storage_dir
+            .path()
+            .join("snapshots")
+            .to_str()
+            .unwrap()
+            .to_string(),
+        on_disk_payload: false,
+        optimizers: OptimizersConfig {
+            deleted_threshold: 0.5,
+            vacuum_min_vector_number: 100,
+            default_segment_number: 2,
+            max_segment_size: None,
+            memmap_threshold: Some(100),
+            indexing_threshold: 100,
+            flush_interval_sec: 2,
+            max_optimization_threads: 2,
+            target_segment_count: None,
+            max_segment_size_mb: None,
+        },
+        wal: Default::default(),
+        performance: PerformanceConfig {
+            max_search_threads: 1,
+            max_optimization_threads: 1,
+            update_rate_limit: None,
+            search_recall_sample_rate: 0.0,
+            point_access_sample_rate: 0.0,
+            max_concurrent_searches: 0,
+            search_queue_size: 1024,
+        },
+        hnsw_index: Default::default(),
+        quantization: None,
+        mmap_advice: madvise::Advice::Random,
+        node_type: Default::default(),
+        update_queue_size: Default::default(),
+        read_preference: Default::default(),
+        filter_validation: Default::default(),
+        wal_archive: None,
+        orphan_files: Default::default(),
+        lifecycle_check_interval_sec: 0,
+        dead_replica_recovery: Default::default(),
+    };
+
+    let runtime = Runtime::new().unwrap();
+
+    let qdrant = QdrantEmbedded::open(&config).unwrap();
+    runtime.block_on(async {
+        let create_collection = CreateCollection {
+            vectors: VectorParams {
+                size: NonZeroU64::new(4).unwrap(),
+                distance: Distance::Dot,
+                hnsw_config: None,
+                quantization_config: None,
+                projection: None,
+            }
+            .into(),
+            hnsw_config: None,
+            wal_config: None,
+            optimizers_config: None,
+            shard_number: Some(1),
+            on_disk_payload: None,
+            replication_factor: None,
+            write_consistency_factor: None,
+            init_from: None,
+            quantization_config: None,
+            sparse_vectors: None,
+        };
+        assert!(qdrant
+            .create_collection("test", create_collection)
+            .await
+            .unwrap());
+
+        let batch = Batch {
+            ids: vec![0.into(), 1.into()],
+            vectors: vec![vec![1.0, 0.0, 0.0, 0.0], vec![0.0, 1.0, 0.0, 0.0]].into(),
+            payloads: None,
+        };
+        qdrant
+            .toc()
+            .update(
+                "test",
+                CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
+                    PointInsertOperations::PointsBatch(batch),
+                )),
+                None,
+                true,
+                None,
+            )
+            .await
+            .unwrap();
+    });
+    drop(qdrant);
+
+    // Collection is loaded again from the same storage
+    let qdrant = QdrantEmbedded::open(&config).unwrap();
+    runtime.block_on(async {
+        let request = SearchRequest {
+            vector: vec![0.0, 1.0, 0.0, 0.0].into(),
+            filter: None,
+            params: None,
+            limit: 1,
+            offset: 0,
+            with_payload: None,
+            with_vector: None,
+            score_threshold: None,
+            with_highlights: None,
+            with_geo_distance: None,
+        };
+        let result = qdrant
+            .toc()
+            .search("test", request, None, None, SearchPriority::default(), None)
+            .await
+            .unwrap();
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].id, 1.into());
+
+        assert!(qdrant.delete_collection("test").await.unwrap());
+        assert!(qdrant.toc().get_collection("test").await.is_err());
+    });
+    drop(qdrant);
+}
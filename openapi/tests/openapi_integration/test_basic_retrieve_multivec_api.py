@@ -182,6 +182,32 @@ def recommendation():
     assert response.ok
 
 
+def test_hybrid_query():
+    for fusion in ["rrf", "weighted_sum", "normalized_sum"]:
+        response = request_with_validation(
+            api='/collections/{collection_name}/points/query',
+            method="POST",
+            path_params={'collection_name': collection_name},
+            body={
+                "prefetch": [
+                    {"vector": {"name": "image", "vector": [0.2, 0.1, 0.9, 0.7]}},
+                    {
+                        "vector": {"name": "text", "vector": [0.05, 0.61, 0.76, 0.74, 0.05, 0.61, 0.76, 0.74]},
+                        "weight": 0.5,
+                    },
+                ],
+                "fusion": fusion,
+                "limit": 3,
+                "with_payload": True,
+            }
+        )
+        assert response.ok
+        result = response.json()['result']
+        assert len(result) == 3
+        assert result[0]['payload'] is not None
+        assert result[0]['score'] >= result[1]['score'] >= result[2]['score']
+
+
 def test_query_nested():
     query_nested()
 
@@ -278,3 +278,63 @@ def test_scroll_with_prefix():
     assert response.ok
     assert len(response.json()['result']) == 3
 
+
+
+def test_search_with_highlights():
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/search',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={
+            "vector": [1., 1., 1., 1.],
+            "limit": 10,
+            "with_payload": False,
+            "with_highlights": True,
+            "filter": {
+                "must": [
+                    {
+                        "key": "title",
+                        "match": {
+                            "text": "ROBO",
+                        }
+                    }
+                ]
+            }
+        }
+    )
+
+    assert response.ok
+    result = response.json()['result']
+    assert len(result) == 3
+
+    for point in result:
+        title = texts[point['id']]
+        assert point.get('payload') is None
+        assert len(point['highlights']) == 1
+        highlight = point['highlights'][0]
+        assert highlight['key'] == "title"
+        assert highlight['index'] == 0
+        assert title[highlight['start']:highlight['end']] == "Robot"
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/search',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={
+            "vector": [1., 1., 1., 1.],
+            "limit": 10,
+            "filter": {
+                "must": [
+                    {
+                        "key": "title",
+                        "match": {
+                            "text": "ROBO",
+                        }
+                    }
+                ]
+            }
+        }
+    )
+
+    assert response.ok
+    assert all('highlights' not in point for point in response.json()['result'])
@@ -0,0 +1,118 @@
+import pytest
+
+from .helpers.collection_setup import basic_collection_setup, drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = 'test_collection_groups'
+
+
+@pytest.fixture(autouse=True)
+def setup():
+    basic_collection_setup(collection_name=collection_name)
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def test_search_groups():
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/search/groups',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={
+            "vector": [0.2, 0.1, 0.9, 0.7],
+            "group_by": "city",
+            "group_size": 2,
+            "limit": 3,
+            "with_payload": True,
+        }
+    )
+    assert response.ok, response.text
+    groups = response.json()['result']['groups']
+    assert sorted(group['id'] for group in groups) == ["Berlin", "London", "Moscow"]
+    for group in groups:
+        assert 1 <= len(group['hits']) <= 2
+        for hit in group['hits']:
+            city = hit['payload']['city']
+            assert group['id'] == city or group['id'] in city
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/search/groups',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={
+            "vector": [0.2, 0.1, 0.9, 0.7],
+            "group_by": "city",
+            "group_size": 1,
+            "limit": 1,
+        }
+    )
+    assert response.ok, response.text
+    groups = response.json()['result']['groups']
+    assert len(groups) == 1
+    assert len(groups[0]['hits']) == 1
+    assert groups[0]['hits'][0]['payload'] is None
+
+
+def test_search_groups_with_lookup():
+    lookup_collection_name = 'test_collection_groups_lookup'
+    basic_collection_setup(collection_name=lookup_collection_name)
+    response = request_with_validation(
+        api='/collections/{collection_name}/points',
+        method="PUT",
+        path_params={'collection_name': lookup_collection_name},
+        query_params={'wait': 'true'},
+        body={
+            "points": [
+                {
+                    "id": 0,
+                    "vector": [0.1, 0.2, 0.3, 0.4],
+                    "payload": {"title": "Zero"}
+                },
+            ]
+        }
+    )
+    assert response.ok, response.text
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/search/groups',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={
+            "vector": [0.2, 0.1, 0.9, 0.7],
+            "group_by": "count",
+            "group_size": 1,
+            "limit": 1,
+            "with_lookup": {
+                "collection": lookup_collection_name,
+                "with_payload": ["title"],
+                "with_vectors": True,
+            },
+        }
+    )
+    assert response.ok, response.text
+    groups = response.json()['result']['groups']
+    assert len(groups) == 1
+    assert groups[0]['id'] == 0
+    assert groups[0]['lookup']['id'] == 0
+    assert groups[0]['lookup']['payload'] == {"title": "Zero"}
+    assert groups[0]['lookup']['vector'] == [0.1, 0.2, 0.3, 0.4]
+
+    # Groups without points in the lookup collection are returned without lookup
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/search/groups',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={
+            "vector": [0.2, 0.1, 0.9, 0.7],
+            "group_by": "city",
+            "group_size": 1,
+            "limit": 3,
+            "with_lookup": lookup_collection_name,
+        }
+    )
+    assert response.ok, response.text
+    groups = response.json()['result']['groups']
+    assert len(groups) == 3
+    assert all('lookup' not in group for group in groups)
+
+    drop_collection(collection_name=lookup_collection_name)
@@ -0,0 +1,136 @@
+use actix_web::rt::time::Instant;
+use actix_web::{get, post, put, web, Responder};
+use actix_web_validator::{Json, Path, Query};
+use collection::operations::backfill_ops::{
+    BackfillBatch, BackfillJobId, BackfillJobInfo, BackfillPointsRequest, BackfillPointsResult,
+    CreateBackfillJob,
+};
+use collection::operations::point_ops::WriteOrdering;
+use collection::operations::types::UpdateResult;
+use serde::Deserialize;
+use storage::content_manager::errors::StorageError;
+use storage::content_manager::toc::TableOfContent;
+use validator::Validate;
+
+use super::update_api::{with_replication_report, UpdateParam};
+use super::CollectionPath;
+use crate::actix::helpers::process_response;
+
+#[derive(Deserialize, Validate)]
+struct BackfillJobPath {
+    job_id: BackfillJobId,
+}
+
+async fn do_create_backfill_job(
+    toc: &TableOfContent,
+    collection_name: &str,
+    request: CreateBackfillJob,
+) -> Result<BackfillJobInfo, StorageError> {
+    let collection = toc.get_collection(collection_name).await?;
+    Ok(collection.create_backfill_job(request).await?)
+}
+
+async fn do_get_backfill_job(
+    toc: &TableOfContent,
+    collection_name: &str,
+    job_id: BackfillJobId,
+) -> Result<BackfillJobInfo, StorageError> {
+    let collection = toc.get_collection(collection_name).await?;
+    Ok(collection.backfill_job_info(job_id).await?)
+}
+
+async fn do_get_backfill_points(
+    toc: &TableOfContent,
+    collection_name: &str,
+    job_id: BackfillJobId,
+    request: BackfillPointsRequest,
+) -> Result<BackfillPointsResult, StorageError> {
+    let collection = toc.get_collection(collection_name).await?;
+    Ok(collection.backfill_points(job_id, request).await?)
+}
+
+async fn do_backfill_update(
+    toc: &TableOfContent,
+    collection_name: &str,
+    job_id: BackfillJobId,
+    batch: BackfillBatch,
+    wait: bool,
+    ordering: Option<WriteOrdering>,
+) -> Result<UpdateResult, StorageError> {
+    let collection = toc.get_collection(collection_name).await?;
+    Ok(collection
+        .backfill_update(job_id, batch, wait, ordering)
+        .await?)
+}
+
+#[post("/collections/{name}/backfill")]
+async fn create_backfill_job(
+    toc: web::Data<TableOfContent>,
+    collection: Path<CollectionPath>,
+    request: Json<CreateBackfillJob>,
+) -> impl Responder {
+    let timing = Instant::now();
+    let response =
+        do_create_backfill_job(toc.get_ref(), &collection.name, request.into_inner()).await;
+    process_response(response, timing)
+}
+
+#[get("/collections/{name}/backfill/{job_id}")]
+async fn get_backfill_job(
+    toc: web::Data<TableOfContent>,
+    collection: Path<CollectionPath>,
+    job: Path<BackfillJobPath>,
+) -> impl Responder {
+    let timing = Instant::now();
+    let response = do_get_backfill_job(toc.get_ref(), &collection.name, job.job_id).await;
+    process_response(response, timing)
+}
+
+#[post("/collections/{name}/backfill/{job_id}/points")]
+async fn get_backfill_points(
+    toc: web::Data<TableOfContent>,
+    collection: Path<CollectionPath>,
+    job: Path<BackfillJobPath>,
+    request: Json<BackfillPointsRequest>,
+) -> impl Responder {
+    let timing = Instant::now();
+    let response = do_get_backfill_points(
+        toc.get_ref(),
+        &collection.name,
+        job.job_id,
+        request.into_inner(),
+    )
+    .await;
+    process_response(response, timing)
+}
+
+#[put("/collections/{name}/backfill/{job_id}/points")]
+async fn backfill_points(
+    toc: web::Data<TableOfContent>,
+    collection: Path<CollectionPath>,
+    job: Path<BackfillJobPath>,
+    batch: Json<BackfillBatch>,
+    params: Query<UpdateParam>,
+) -> impl Responder {
+    let timing = Instant::now();
+    let wait = params.wait.unwrap_or(false);
+
+    let response = do_backfill_update(
+        toc.get_ref(),
+        &collection.name,
+        job.job_id,
+        batch.into_inner(),
+        wait,
+        params.ordering,
+    )
+    .await;
+    process_response(with_replication_report(response, params.verbose), timing)
+}
+
+// Configure services
+pub fn config_backfill_api(cfg: &mut web::ServiceConfig) {
+    cfg.service(create_backfill_job)
+        .service(get_backfill_job)
+        .service(get_backfill_points)
+        .service(backfill_points);
+}
@@ -4,6 +4,7 @@ use actix_web::rt::time::Instant;
 use actix_web::{delete, get, patch, post, put, web, Responder};
 use actix_web_validator::{Json, Path, Query};
 use collection::operations::cluster_ops::ClusterOperations;
+use collection::operations::types::ListAliasesParams;
 use serde::Deserialize;
 use storage::content_manager::collection_meta_ops::{
     ChangeAliasesOperation, CollectionMetaOperations, CreateCollection, CreateCollectionOperation,
@@ -13,6 +14,7 @@ use storage::content_manager::toc::TableOfContent;
 use storage::dispatcher::Dispatcher;
 use validator::Validate;
 
+use super::read_params::Access;
 use super::CollectionPath;
 use crate::actix::helpers::process_response;
 use crate::common::collections::*;
@@ -29,6 +31,18 @@ impl WaitTimeout {
     }
 }
 
+#[derive(Debug, Deserialize, Validate)]
+struct ConfigChangePath {
+    #[validate(length(min = 1, max = 255))]
+    name: String,
+    change_id: u64,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+struct ClusterInfoParam {
+    routing: Option<bool>,
+}
+
 #[get("/collections")]
 async fn get_collections(toc: web::Data<TableOfContent>) -> impl Responder {
     let timing = Instant::now();
@@ -37,9 +51,12 @@ async fn get_collections(toc: web::Data<TableOfContent>) -> impl Responder {
 }
 
 #[get("/aliases")]
-async fn get_aliases(toc: web::Data<TableOfContent>) -> impl Responder {
+async fn get_aliases(
+    toc: web::Data<TableOfContent>,
+    params: Query<ListAliasesParams>,
+) -> impl Responder {
     let timing = Instant::now();
-    let response = do_list_aliases(toc.get_ref()).await;
+    let response = do_list_aliases(toc.get_ref(), &params).await;
     process_response(response, timing)
 }
 
@@ -57,9 +74,10 @@ async fn get_collection(
 async fn get_collection_aliases(
     toc: web::Data<TableOfContent>,
     collection: Path<CollectionPath>,
+    params: Query<ListAliasesParams>,
 ) -> impl Responder {
     let timing = Instant::now();
-    let response = do_list_collection_aliases(toc.get_ref(), &collection.name).await;
+    let response = do_list_collection_aliases(toc.get_ref(), &collection.name, &params).await;
     process_response(response, timing)
 }
 
@@ -89,21 +107,53 @@ async fn update_collection(
     collection: Path<CollectionPath>,
     operation: Json<UpdateCollection>,
     Query(query): Query<WaitTimeout>,
+    Access(access): Access,
 ) -> impl Responder {
     let timing = Instant::now();
     let name = collection.name.clone();
     let response = dispatcher
         .submit_collection_meta_op(
-            CollectionMetaOperations::UpdateCollection(UpdateCollectionOperation::new(
-                name,
-                operation.into_inner(),
-            )),
+            CollectionMetaOperations::UpdateCollection(
+                UpdateCollectionOperation::new(name, operation.into_inner())
+                    .with_author(access.identity()),
+            ),
             query.timeout(),
         )
         .await;
     process_response(response, timing)
 }
 
+#[get("/collections/{name}/config/history")]
+async fn get_config_history(
+    toc: web::Data<TableOfContent>,
+    collection: Path<CollectionPath>,
+) -> impl Responder {
+    let timing = Instant::now();
+    let response = do_get_config_history(toc.get_ref(), &collection.name).await;
+    process_response(response, timing)
+}
+
+#[post("/collections/{name}/config/history/{change_id}/rollback")]
+async fn rollback_config(
+    toc: web::Data<TableOfContent>,
+    dispatcher: web::Data<Dispatcher>,
+    path: Path<ConfigChangePath>,
+    Query(query): Query<WaitTimeout>,
+    Access(access): Access,
+) -> impl Responder {
+    let timing = Instant::now();
+    let response = do_rollback_config(
+        toc.get_ref(),
+        dispatcher.get_ref(),
+        path.name.clone(),
+        path.change_id,
+        access.identity(),
+        query.timeout(),
+    )
+    .await;
+    process_response(response, timing)
+}
+
 #[delete("/collections/{name}")]
 async fn delete_collection(
     dispatcher: web::Data<Dispatcher>,
@@ -127,11 +177,14 @@ async fn update_aliases(
     dispatcher: web::Data<Dispatcher>,
     operation: Json<ChangeAliasesOperation>,
     Query(query): Query<WaitTimeout>,
+    Access(access): Access,
 ) -> impl Responder {
     let timing = Instant::now();
     let response = dispatcher
         .submit_collection_meta_op(
-            CollectionMetaOperations::ChangeAliases(operation.0),
+            CollectionMetaOperations::ChangeAliases(
+                operation.into_inner().with_author(access.identity()),
+            ),
             query.timeout(),
         )
         .await;
@@ -142,9 +195,11 @@ async fn update_aliases(
 async fn get_cluster_info(
     toc: web::Data<TableOfContent>,
     collection: Path<CollectionPath>,
+    params: Query<ClusterInfoParam>,
 ) -> impl Responder {
     let timing = Instant::now();
-    let response = do_get_collection_cluster(toc.get_ref(), &collection.name).await;
+    let with_routing = params.routing.unwrap_or(false);
+    let response = do_get_collection_cluster(toc.get_ref(), &collection.name, with_routing).await;
     process_response(response, timing)
 }
 
@@ -169,18 +224,42 @@ async fn update_collection_cluster(
     process_response(response, timing)
 }
 
+#[post("/collections/{name}/flush")]
+async fn flush_collection(
+    toc: web::Data<TableOfContent>,
+    collection: Path<CollectionPath>,
+) -> impl Responder {
+    let timing = Instant::now();
+    let response = do_flush_collection(toc.get_ref(), &collection.name).await;
+    process_response(response, timing)
+}
+
+#[post("/collections/{name}/wal/truncate")]
+async fn truncate_collection_wal(
+    toc: web::Data<TableOfContent>,
+    collection: Path<CollectionPath>,
+) -> impl Responder {
+    let timing = Instant::now();
+    let response = do_truncate_collection_wal(toc.get_ref(), &collection.name).await;
+    process_response(response, timing)
+}
+
 // Configure services
 pub fn config_collections_api(cfg: &mut web::ServiceConfig) {
     cfg.service(get_collections)
         .service(get_collection)
         .service(create_collection)
         .service(update_collection)
+        .service(get_config_history)
+        .service(rollback_config)
         .service(delete_collection)
         .service(get_aliases)
         .service(get_collection_aliases)
         .service(update_aliases)
         .service(get_cluster_info)
-        .service(update_collection_cluster);
+        .service(update_collection_cluster)
+        .service(flush_collection)
+        .service(truncate_collection_wal);
 }
 
 #[cfg(test)]
@@ -0,0 +1,19 @@
+use actix_web::rt::time::Instant;
+use actix_web::{post, web, Responder};
+
+use crate::actix::helpers::process_response;
+use crate::common::config_reload::ConfigReloader;
+
+#[post("/config/reload")]
+async fn reload_config(config_reloader: web::Data<ConfigReloader>) -> impl Responder {
+    let timing = Instant::now();
+    let response = config_reloader.reload();
+    process_response(response, timing)
+}
+
+// Configure services
+pub fn config_config_reload_api(cfg: &mut web::ServiceConfig, enable_config_reload: bool) {
+    if enable_config_reload {
+        cfg.service(reload_config);
+    }
+}
@@ -1,23 +1,80 @@
 use actix_web::rt::time::Instant;
 use actix_web::{post, web, Responder};
-use actix_web_validator::{Json, Path};
+use actix_web_validator::{Json, Path, Query};
+use collection::operations::cardinality_ops::{
+    CardinalityEstimationRequest, CardinalityEstimationResult,
+};
 use collection::operations::types::CountRequest;
+use storage::content_manager::errors::StorageError;
 use storage::content_manager::toc::TableOfContent;
 
+use super::read_params::{Access, CountParams};
 use super::CollectionPath;
 use crate::actix::helpers::process_response;
-use crate::common::points::do_count_points;
+use crate::common::acl::{restrict_filter, ReadAccess};
+use crate::common::points::{do_count_points, do_wait_for_writes};
 
 #[post("/collections/{name}/points/count")]
 async fn count_points(
     toc: web::Data<TableOfContent>,
     collection: Path<CollectionPath>,
     request: Json<CountRequest>,
+    params: Query<CountParams>,
+    Access(access): Access,
 ) -> impl Responder {
     let timing = Instant::now();
 
-    let response =
-        do_count_points(toc.get_ref(), &collection.name, request.into_inner(), None).await;
+    let response = match do_wait_for_writes(
+        toc.get_ref(),
+        &collection.name,
+        params.consistency_token.as_ref(),
+    )
+    .await
+    {
+        Ok(()) => {
+            do_count_points(
+                toc.get_ref(),
+                &collection.name,
+                request.into_inner(),
+                None,
+                None,
+                params.filter_validation,
+                &access,
+            )
+            .await
+        }
+        Err(err) => Err(err),
+    };
 
     process_response(response, timing)
 }
+
+async fn do_estimate_cardinality(
+    toc: &TableOfContent,
+    collection_name: &str,
+    mut request: CardinalityEstimationRequest,
+    access: &ReadAccess,
+) -> Result<CardinalityEstimationResult, StorageError> {
+    let acl_filter = toc.read_acl_filter(collection_name, access).await?;
+    request.filter = restrict_filter(request.filter, acl_filter.as_ref());
+    let collection = toc.get_collection(collection_name).await?;
+    Ok(collection.estimate_cardinality(request).await?)
+}
+
+#[post("/collections/{name}/points/count/estimate")]
+async fn estimate_count_points(
+    toc: web::Data<TableOfContent>,
+    collection: Path<CollectionPath>,
+    request: Json<CardinalityEstimationRequest>,
+    Access(access): Access,
+) -> impl Responder {
+    let timing = Instant::now();
+    let response = do_estimate_cardinality(
+        toc.get_ref(),
+        &collection.name,
+        request.into_inner(),
+        &access,
+    )
+    .await;
+    process_response(response, timing)
+}
@@ -0,0 +1,40 @@
+use actix_web::rt::time::Instant;
+use actix_web::{get, web, HttpResponse, Responder};
+
+use super::read_params::Access;
+use crate::actix::helpers::process_response;
+
+const INDEX_HTML: &str = include_str!("../dashboard/index.html");
+const DASHBOARD_JS: &str = include_str!("../dashboard/dashboard.js");
+const DASHBOARD_CSS: &str = include_str!("../dashboard/dashboard.css");
+
+/// Dashboard shows data of all collections, so it is not available for callers restricted by ACL
+fn dashboard_file(access: &Access, content_type: &str, body: &'static str) -> HttpResponse {
+    let timing = Instant::now();
+    match access.0.check_unrestricted() {
+        Ok(()) => HttpResponse::Ok().content_type(content_type).body(body),
+        Err(err) => process_response::<()>(Err(err), timing),
+    }
+}
+
+#[get("/dashboard")]
+async fn index(access: Access) -> impl Responder {
+    dashboard_file(&access, "text/html; charset=utf-8", INDEX_HTML)
+}
+
+#[get("/dashboard/dashboard.js")]
+async fn script(access: Access) -> impl Responder {
+    dashboard_file(&access, "text/javascript; charset=utf-8", DASHBOARD_JS)
+}
+
+#[get("/dashboard/dashboard.css")]
+async fn style(access: Access) -> impl Responder {
+    dashboard_file(&access, "text/css; charset=utf-8", DASHBOARD_CSS)
+}
+
+// Configure services
+pub fn config_dashboard_api(cfg: &mut web::ServiceConfig, enable_dashboard: bool) {
+    if enable_dashboard {
+        cfg.service(index).service(script).service(style);
+    }
+}
@@ -0,0 +1,86 @@
+use std::time::Duration;
+
+use actix_web::rt::time::Instant;
+use actix_web::{get, put, web, HttpResponse, Responder};
+use actix_web_validator::{Json, Query};
+use collection::common::fault_injection::{self, FaultInjectionConfig};
+use serde::Deserialize;
+use storage::content_manager::toc::TableOfContent;
+use validator::Validate;
+
+use super::read_params::Access;
+use crate::actix::helpers::process_response;
+use crate::common::memory_usage::do_get_memory_usage;
+use crate::common::profiling::{profile_cpu, ProfileFormat, DEFAULT_PROFILING_FREQUENCY};
+
+const DEFAULT_PROFILING_SECONDS: u64 = 10;
+
+#[derive(Deserialize, Validate)]
+struct ProfileParams {
+    #[validate(range(min = 1, max = 300))]
+    seconds: Option<u64>,
+    #[validate(range(min = 1, max = 1000))]
+    frequency: Option<i32>,
+    format: Option<ProfileFormat>,
+}
+
+#[get("/debug/pprof/profile")]
+async fn profile(params: Query<ProfileParams>, Access(access): Access) -> impl Responder {
+    let timing = Instant::now();
+    if let Err(err) = access.check_unrestricted() {
+        return process_response::<()>(Err(err), timing);
+    }
+    let format = params.format.unwrap_or_default();
+
+    let response = profile_cpu(
+        Duration::from_secs(params.seconds.unwrap_or(DEFAULT_PROFILING_SECONDS)),
+        params.frequency.unwrap_or(DEFAULT_PROFILING_FREQUENCY),
+        format,
+    )
+    .await;
+
+    match response {
+        Ok(profile) => HttpResponse::Ok()
+            .content_type(format.content_type())
+            .body(profile),
+        Err(err) => process_response::<()>(Err(err), timing),
+    }
+}
+
+#[get("/debug/memory")]
+async fn memory_usage(toc: web::Data<TableOfContent>, Access(access): Access) -> impl Responder {
+    let timing = Instant::now();
+    let response = do_get_memory_usage(toc.get_ref(), &access).await;
+    process_response(response, timing)
+}
+
+#[get("/debug/faults")]
+async fn get_faults(Access(access): Access) -> impl Responder {
+    let timing = Instant::now();
+    let response = access
+        .check_unrestricted()
+        .map(|_| fault_injection::faults());
+    process_response(response, timing)
+}
+
+#[put("/debug/faults")]
+async fn set_faults(faults: Json<FaultInjectionConfig>, Access(access): Access) -> impl Responder {
+    let timing = Instant::now();
+    let response = access.check_unrestricted().map(|_| {
+        fault_injection::set_faults(faults.into_inner());
+        true
+    });
+    process_response(response, timing)
+}
+
+// Configure services
+pub fn config_debug_api(cfg: &mut web::ServiceConfig, enable_profiling: bool) {
+    cfg.service(memory_usage);
+    if enable_profiling {
+        cfg.service(profile);
+    }
+    // Only available in builds with the `fault-injection` feature
+    if fault_injection::is_enabled() {
+        cfg.service(get_faults).service(set_faults);
+    }
+}
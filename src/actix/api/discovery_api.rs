@@ -0,0 +1,78 @@
+use actix_web::rt::time::Instant;
+use actix_web::{post, web, Responder};
+use actix_web_validator::{Json, Path, Query};
+use collection::operations::consistency_params::ReadConsistency;
+use collection::operations::filter_validation::FilterValidation;
+use collection::operations::types::DiscoverRequest;
+use segment::types::ScoredPoint;
+use storage::content_manager::errors::StorageError;
+use storage::content_manager::search_queue::SearchPriority;
+use storage::content_manager::toc::TableOfContent;
+
+use super::read_params::{Access, SearchParams};
+use super::CollectionPath;
+use crate::actix::helpers::process_response;
+use crate::common::acl::{restrict_discover_request, ReadAccess};
+use crate::common::points::{check_request_filters, do_wait_for_writes};
+
+async fn do_discover_points(
+    toc: &TableOfContent,
+    collection_name: &str,
+    mut request: DiscoverRequest,
+    read_consistency: Option<ReadConsistency>,
+    priority: SearchPriority,
+    filter_validation: Option<FilterValidation>,
+    access: &ReadAccess,
+) -> Result<Vec<ScoredPoint>, StorageError> {
+    check_request_filters(
+        toc,
+        collection_name,
+        [request.filter.as_ref()],
+        None,
+        filter_validation,
+    )
+    .await?;
+    restrict_discover_request(toc, collection_name, &mut request, access).await?;
+    toc.discover(collection_name, request, read_consistency, priority)
+        .await
+}
+
+#[post("/collections/{name}/points/discover")]
+async fn discover_points(
+    toc: web::Data<TableOfContent>,
+    collection: Path<CollectionPath>,
+    request: Json<DiscoverRequest>,
+    params: Query<SearchParams>,
+    Access(access): Access,
+) -> impl Responder {
+    let timing = Instant::now();
+
+    let response = match do_wait_for_writes(
+        toc.get_ref(),
+        &collection.name,
+        params.consistency_token.as_ref(),
+    )
+    .await
+    {
+        Ok(()) => {
+            do_discover_points(
+                toc.get_ref(),
+                &collection.name,
+                request.into_inner(),
+                params.consistency,
+                params.priority,
+                params.filter_validation,
+                &access,
+            )
+            .await
+        }
+        Err(err) => Err(err),
+    };
+
+    process_response(response, timing)
+}
+
+// Configure services
+pub fn config_discovery_api(cfg: &mut web::ServiceConfig) {
+    cfg.service(discover_points);
+}
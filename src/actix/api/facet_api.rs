@@ -0,0 +1,46 @@
+use actix_web::rt::time::Instant;
+use actix_web::{post, web, Responder};
+use actix_web_validator::{Json, Path};
+use collection::operations::facet_ops::{FacetRequest, FacetResponse};
+use storage::content_manager::errors::StorageError;
+use storage::content_manager::toc::TableOfContent;
+
+use super::read_params::Access;
+use super::CollectionPath;
+use crate::actix::helpers::process_response;
+use crate::common::acl::{restrict_filter, ReadAccess};
+
+async fn do_facet(
+    toc: &TableOfContent,
+    collection_name: &str,
+    mut request: FacetRequest,
+    access: &ReadAccess,
+) -> Result<FacetResponse, StorageError> {
+    let acl_filter = toc.read_acl_filter(collection_name, access).await?;
+    request.filter = restrict_filter(request.filter, acl_filter.as_ref());
+    let collection = toc.get_collection(collection_name).await?;
+    Ok(collection.facet(request).await?)
+}
+
+#[post("/collections/{name}/facet")]
+async fn facet(
+    toc: web::Data<TableOfContent>,
+    collection: Path<CollectionPath>,
+    request: Json<FacetRequest>,
+    Access(access): Access,
+) -> impl Responder {
+    let timing = Instant::now();
+    let response = do_facet(
+        toc.get_ref(),
+        &collection.name,
+        request.into_inner(),
+        &access,
+    )
+    .await;
+    process_response(response, timing)
+}
+
+// Configure services
+pub fn config_facet_api(cfg: &mut web::ServiceConfig) {
+    cfg.service(facet);
+}
@@ -0,0 +1,58 @@
+use actix_web::rt::time::Instant;
+use actix_web::{post, web, Responder};
+use actix_web_validator::{Json, Path};
+use collection::operations::histogram_ops::{HistogramRequest, HistogramResult};
+use serde::Deserialize;
+use storage::content_manager::errors::StorageError;
+use storage::content_manager::toc::TableOfContent;
+use validator::Validate;
+
+use super::read_params::Access;
+use crate::actix::helpers::process_response;
+use crate::common::acl::{restrict_filter, ReadAccess};
+
+#[derive(Deserialize, Validate)]
+struct FieldIndexPath {
+    #[validate(length(min = 1, max = 255))]
+    name: String,
+    #[validate(length(min = 1))]
+    field_name: String,
+}
+
+async fn do_get_field_histogram(
+    toc: &TableOfContent,
+    collection_name: &str,
+    field_name: String,
+    mut request: HistogramRequest,
+    access: &ReadAccess,
+) -> Result<HistogramResult, StorageError> {
+    let acl_filter = toc.read_acl_filter(collection_name, access).await?;
+    request.filter = restrict_filter(request.filter, acl_filter.as_ref());
+    let collection = toc.get_collection(collection_name).await?;
+    Ok(collection.field_histogram(field_name, request).await?)
+}
+
+#[post("/collections/{name}/index/{field_name}/histogram")]
+async fn get_field_histogram(
+    toc: web::Data<TableOfContent>,
+    path: Path<FieldIndexPath>,
+    request: Json<HistogramRequest>,
+    Access(access): Access,
+) -> impl Responder {
+    let timing = Instant::now();
+    let FieldIndexPath { name, field_name } = path.into_inner();
+    let response = do_get_field_histogram(
+        toc.get_ref(),
+        &name,
+        field_name,
+        request.into_inner(),
+        &access,
+    )
+    .await;
+    process_response(response, timing)
+}
+
+// Configure services
+pub fn config_histogram_api(cfg: &mut web::ServiceConfig) {
+    cfg.service(get_field_histogram);
+}
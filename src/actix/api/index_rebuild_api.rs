@@ -0,0 +1,63 @@
+use actix_web::rt::time::Instant;
+use actix_web::{get, post, web, Responder};
+use actix_web_validator::Path;
+use collection::operations::index_rebuild_ops::IndexRebuildInfo;
+use serde::Deserialize;
+use storage::content_manager::errors::StorageError;
+use storage::content_manager::toc::TableOfContent;
+use validator::Validate;
+
+use crate::actix::helpers::process_response;
+
+#[derive(Deserialize, Validate)]
+struct FieldIndexPath {
+    #[validate(length(min = 1, max = 255))]
+    name: String,
+    #[validate(length(min = 1))]
+    field_name: String,
+}
+
+async fn do_rebuild_field_index(
+    toc: &TableOfContent,
+    collection_name: &str,
+    field_name: String,
+) -> Result<IndexRebuildInfo, StorageError> {
+    let collection = toc.get_collection(collection_name).await?;
+    Ok(collection.rebuild_field_index(field_name).await?)
+}
+
+async fn do_get_field_index_rebuild(
+    toc: &TableOfContent,
+    collection_name: &str,
+    field_name: &String,
+) -> Result<IndexRebuildInfo, StorageError> {
+    let collection = toc.get_collection(collection_name).await?;
+    Ok(collection.field_index_rebuild_info(field_name)?)
+}
+
+#[post("/collections/{name}/index/{field_name}/rebuild")]
+async fn rebuild_field_index(
+    toc: web::Data<TableOfContent>,
+    path: Path<FieldIndexPath>,
+) -> impl Responder {
+    let timing = Instant::now();
+    let FieldIndexPath { name, field_name } = path.into_inner();
+    let response = do_rebuild_field_index(toc.get_ref(), &name, field_name).await;
+    process_response(response, timing)
+}
+
+#[get("/collections/{name}/index/{field_name}/rebuild")]
+async fn get_field_index_rebuild(
+    toc: web::Data<TableOfContent>,
+    path: Path<FieldIndexPath>,
+) -> impl Responder {
+    let timing = Instant::now();
+    let response = do_get_field_index_rebuild(toc.get_ref(), &path.name, &path.field_name).await;
+    process_response(response, timing)
+}
+
+// Configure services
+pub fn config_index_rebuild_api(cfg: &mut web::ServiceConfig) {
+    cfg.service(rebuild_field_index)
+        .service(get_field_index_rebuild);
+}
@@ -1,13 +1,32 @@
+pub mod backfill_api;
 pub mod cluster_api;
 pub mod collections_api;
+pub mod config_reload_api;
 pub mod count_api;
+pub mod dashboard_api;
+pub mod debug_api;
+pub mod discovery_api;
+pub mod facet_api;
+pub mod histogram_api;
+pub mod index_rebuild_api;
+pub mod orphan_files_api;
+pub mod payload_schema_api;
+pub mod point_access_api;
+pub mod query_api;
 pub mod read_params;
+pub mod recall_api;
 pub mod recommend_api;
+pub mod reindex_api;
 pub mod retrieve_api;
+pub mod scoring_benchmark_api;
 pub mod search_api;
 pub mod service_api;
+pub mod shard_api;
 pub mod snapshot_api;
+pub mod terms_api;
 pub mod update_api;
+pub mod vacuum_api;
+pub mod wal_archive_api;
 
 use serde::Deserialize;
 use validator::Validate;
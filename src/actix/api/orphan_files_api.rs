@@ -0,0 +1,26 @@
+use actix_web::rt::time::Instant;
+use actix_web::{post, web, Responder};
+use actix_web_validator::{Json, Path};
+use collection::operations::orphan_files_ops::OrphanFilesRequest;
+use storage::content_manager::toc::TableOfContent;
+
+use super::CollectionPath;
+use crate::actix::helpers::process_response;
+
+#[post("/collections/{name}/orphan_files/remove")]
+async fn remove_orphan_files(
+    toc: web::Data<TableOfContent>,
+    collection: Path<CollectionPath>,
+    request: Json<OrphanFilesRequest>,
+) -> impl Responder {
+    let timing = Instant::now();
+    let response = toc
+        .remove_orphaned_files(&collection.name, request.grace_period_sec)
+        .await;
+    process_response(response, timing)
+}
+
+// Configure services
+pub fn config_orphan_files_api(cfg: &mut web::ServiceConfig) {
+    cfg.service(remove_orphan_files);
+}
@@ -0,0 +1,46 @@
+use actix_web::rt::time::Instant;
+use actix_web::{post, web, Responder};
+use actix_web_validator::{Json, Path};
+use collection::operations::payload_schema_ops::{
+    PayloadSchemaInferenceRequest, PayloadSchemaInferenceResult,
+};
+use storage::content_manager::errors::StorageError;
+use storage::content_manager::toc::TableOfContent;
+
+use super::read_params::Access;
+use super::CollectionPath;
+use crate::actix::helpers::process_response;
+use crate::common::acl::ReadAccess;
+
+async fn do_infer_payload_schema(
+    toc: &TableOfContent,
+    collection_name: &str,
+    request: PayloadSchemaInferenceRequest,
+    access: &ReadAccess,
+) -> Result<PayloadSchemaInferenceResult, StorageError> {
+    let collection = toc.get_collection_for_read(collection_name, access).await?;
+    Ok(collection.infer_payload_schema(request).await?)
+}
+
+#[post("/collections/{name}/payload/schema")]
+async fn infer_payload_schema(
+    toc: web::Data<TableOfContent>,
+    collection: Path<CollectionPath>,
+    request: Json<PayloadSchemaInferenceRequest>,
+    Access(access): Access,
+) -> impl Responder {
+    let timing = Instant::now();
+    let response = do_infer_payload_schema(
+        toc.get_ref(),
+        &collection.name,
+        request.into_inner(),
+        &access,
+    )
+    .await;
+    process_response(response, timing)
+}
+
+// Configure services
+pub fn config_payload_schema_api(cfg: &mut web::ServiceConfig) {
+    cfg.service(infer_payload_schema);
+}
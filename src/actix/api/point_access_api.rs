@@ -0,0 +1,44 @@
+use actix_web::rt::time::Instant;
+use actix_web::{post, web, Responder};
+use actix_web_validator::{Json, Path};
+use collection::operations::point_access_ops::{HotPointsReport, HotPointsRequest};
+use storage::content_manager::errors::StorageError;
+use storage::content_manager::toc::TableOfContent;
+
+use super::read_params::Access;
+use super::CollectionPath;
+use crate::actix::helpers::process_response;
+use crate::common::acl::ReadAccess;
+
+async fn do_get_hot_points(
+    toc: &TableOfContent,
+    collection_name: &str,
+    request: HotPointsRequest,
+    access: &ReadAccess,
+) -> Result<HotPointsReport, StorageError> {
+    let collection = toc.get_collection_for_read(collection_name, access).await?;
+    Ok(collection.hot_points(request))
+}
+
+#[post("/collections/{name}/points/hot")]
+async fn get_hot_points(
+    toc: web::Data<TableOfContent>,
+    collection: Path<CollectionPath>,
+    request: Json<HotPointsRequest>,
+    Access(access): Access,
+) -> impl Responder {
+    let timing = Instant::now();
+    let response = do_get_hot_points(
+        toc.get_ref(),
+        &collection.name,
+        request.into_inner(),
+        &access,
+    )
+    .await;
+    process_response(response, timing)
+}
+
+// Configure services
+pub fn config_point_access_api(cfg: &mut web::ServiceConfig) {
+    cfg.service(get_hot_points);
+}
@@ -0,0 +1,82 @@
+use actix_web::rt::time::Instant;
+use actix_web::{post, web, Responder};
+use actix_web_validator::{Json, Path, Query};
+use collection::operations::consistency_params::ReadConsistency;
+use collection::operations::filter_validation::FilterValidation;
+use collection::operations::types::QueryRequest;
+use segment::types::ScoredPoint;
+use storage::content_manager::errors::StorageError;
+use storage::content_manager::search_queue::SearchPriority;
+use storage::content_manager::toc::TableOfContent;
+
+use super::read_params::{Access, SearchParams};
+use super::CollectionPath;
+use crate::actix::helpers::process_response;
+use crate::common::acl::{restrict_query_request, ReadAccess};
+use crate::common::points::{check_request_filters, do_wait_for_writes};
+
+async fn do_query_points(
+    toc: &TableOfContent,
+    collection_name: &str,
+    mut request: QueryRequest,
+    read_consistency: Option<ReadConsistency>,
+    priority: SearchPriority,
+    filter_validation: Option<FilterValidation>,
+    access: &ReadAccess,
+) -> Result<Vec<ScoredPoint>, StorageError> {
+    check_request_filters(
+        toc,
+        collection_name,
+        request
+            .prefetch
+            .iter()
+            .map(|prefetch| prefetch.filter.as_ref())
+            .chain([request.filter.as_ref()]),
+        None,
+        filter_validation,
+    )
+    .await?;
+    restrict_query_request(toc, collection_name, &mut request, access).await?;
+    toc.query(collection_name, request, read_consistency, priority)
+        .await
+}
+
+#[post("/collections/{name}/points/query")]
+async fn query_points(
+    toc: web::Data<TableOfContent>,
+    collection: Path<CollectionPath>,
+    request: Json<QueryRequest>,
+    params: Query<SearchParams>,
+    Access(access): Access,
+) -> impl Responder {
+    let timing = Instant::now();
+
+    let response = match do_wait_for_writes(
+        toc.get_ref(),
+        &collection.name,
+        params.consistency_token.as_ref(),
+    )
+    .await
+    {
+        Ok(()) => {
+            do_query_points(
+                toc.get_ref(),
+                &collection.name,
+                request.into_inner(),
+                params.consistency,
+                params.priority,
+                params.filter_validation,
+                &access,
+            )
+            .await
+        }
+        Err(err) => Err(err),
+    };
+
+    process_response(response, timing)
+}
+
+// Configure services
+pub fn config_query_api(cfg: &mut web::ServiceConfig) {
+    cfg.service(query_points);
+}
@@ -1,13 +1,93 @@
-use collection::operations::consistency_params::ReadConsistency;
+use std::future::{ready, Ready};
+use std::time::Duration;
+
+use actix_web::dev::Payload;
+use actix_web::{FromRequest, HttpRequest};
+use collection::operations::consistency_params::{ConsistencyToken, ReadConsistency};
+use collection::operations::filter_validation::FilterValidation;
 use schemars::JsonSchema;
 use serde::Deserialize;
+use storage::content_manager::search_queue::SearchPriority;
 use validator::Validate;
 
-#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Deserialize, JsonSchema, Validate)]
+use crate::common::acl::{ReadAccess, ACL_CLAIMS_HEADER};
+
+#[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize, JsonSchema, Validate)]
 pub struct ReadParams {
     #[serde(default, deserialize_with = "deserialize_read_consistency")]
     #[validate]
     pub consistency: Option<ReadConsistency>,
+    /// How the filter of the request is checked against payload keys of the collection.
+    /// Default is configured on the node
+    #[serde(default)]
+    pub filter_validation: Option<FilterValidation>,
+    /// Token from the response of an update. The read waits until the replicas apply the update
+    #[serde(default)]
+    pub consistency_token: Option<ConsistencyToken>,
+    /// Max duration of the request in seconds, no timeout if not specified
+    #[serde(default)]
+    #[validate(range(min = 1))]
+    pub timeout: Option<u64>,
+}
+
+impl ReadParams {
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout.map(Duration::from_secs)
+    }
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize, JsonSchema, Validate)]
+pub struct SearchParams {
+    #[serde(default, deserialize_with = "deserialize_read_consistency")]
+    #[validate]
+    pub consistency: Option<ReadConsistency>,
+    /// Priority of the request in the search queue
+    #[serde(default)]
+    pub priority: SearchPriority,
+    /// How the filter of the request is checked against payload keys of the collection.
+    /// Default is configured on the node
+    #[serde(default)]
+    pub filter_validation: Option<FilterValidation>,
+    /// Token from the response of an update. The read waits until the replicas apply the update
+    #[serde(default)]
+    pub consistency_token: Option<ConsistencyToken>,
+    /// Max duration of the request in seconds, no timeout if not specified
+    #[serde(default)]
+    #[validate(range(min = 1))]
+    pub timeout: Option<u64>,
+}
+
+impl SearchParams {
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout.map(Duration::from_secs)
+    }
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize, JsonSchema, Validate)]
+pub struct CountParams {
+    /// How the filter of the request is checked against payload keys of the collection.
+    /// Default is configured on the node
+    #[serde(default)]
+    pub filter_validation: Option<FilterValidation>,
+    /// Token from the response of an update. The read waits until the replicas apply the update
+    #[serde(default)]
+    pub consistency_token: Option<ConsistencyToken>,
+}
+
+/// Claims of the client, set by the authenticating proxy. Malformed header is treated as missing
+pub struct Access(pub ReadAccess);
+
+impl FromRequest for Access {
+    type Error = actix_web::Error;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _payload: &mut Payload) -> Self::Future {
+        let claims = req
+            .headers()
+            .get(ACL_CLAIMS_HEADER)
+            .and_then(|value| value.to_str().ok());
+        ready(Ok(Access(ReadAccess::from_claims_header(claims))))
+    }
 }
 
 fn deserialize_read_consistency<'de, D>(
@@ -62,6 +142,30 @@ mod test {
         }
     }
 
+    #[test]
+    fn deserialize_filter_validation() {
+        let params = deserialize("consistency=all&filter_validation=strict");
+        assert_eq!(params.filter_validation, Some(FilterValidation::Strict));
+        assert_eq!(deserialize("").filter_validation, None);
+    }
+
+    #[test]
+    fn deserialize_consistency_token() {
+        let params = deserialize("consistency_token=0:100.5%2B200.7;1:100.2");
+        assert_eq!(
+            params.consistency_token.unwrap().to_string(),
+            "0:100.5+200.7;1:100.2"
+        );
+        assert!(try_deserialize("consistency_token=malformed").is_err());
+    }
+
+    #[test]
+    fn deserialize_timeout() {
+        let params = deserialize("timeout=10");
+        assert_eq!(params.timeout(), Some(Duration::from_secs(10)));
+        assert_eq!(deserialize("").timeout(), None);
+    }
+
     #[test]
     fn try_deserialize_factor_0() {
         assert!(try_deserialize(&str("0")).is_err());
@@ -90,12 +194,14 @@ mod test {
     fn from_type(r#type: ReadConsistencyType) -> ReadParams {
         ReadParams {
             consistency: Some(ReadConsistency::Type(r#type)),
+            ..Default::default()
         }
     }
 
     fn from_factor(factor: usize) -> ReadParams {
         ReadParams {
             consistency: Some(ReadConsistency::Factor(factor)),
+            ..Default::default()
         }
     }
 }
@@ -0,0 +1,44 @@
+use actix_web::rt::time::Instant;
+use actix_web::{post, web, Responder};
+use actix_web_validator::{Json, Path};
+use collection::operations::recall_ops::{RecallEstimationRequest, RecallEstimationResult};
+use storage::content_manager::errors::StorageError;
+use storage::content_manager::toc::TableOfContent;
+
+use super::read_params::Access;
+use super::CollectionPath;
+use crate::actix::helpers::process_response;
+use crate::common::acl::ReadAccess;
+
+async fn do_estimate_recall(
+    toc: &TableOfContent,
+    collection_name: &str,
+    request: RecallEstimationRequest,
+    access: &ReadAccess,
+) -> Result<RecallEstimationResult, StorageError> {
+    let collection = toc.get_collection_for_read(collection_name, access).await?;
+    Ok(collection.estimate_recall(request).await?)
+}
+
+#[post("/collections/{name}/recall")]
+async fn estimate_recall(
+    toc: web::Data<TableOfContent>,
+    collection: Path<CollectionPath>,
+    request: Json<RecallEstimationRequest>,
+    Access(access): Access,
+) -> impl Responder {
+    let timing = Instant::now();
+    let response = do_estimate_recall(
+        toc.get_ref(),
+        &collection.name,
+        request.into_inner(),
+        &access,
+    )
+    .await;
+    process_response(response, timing)
+}
+
+// Configure services
+pub fn config_recall_api(cfg: &mut web::ServiceConfig) {
+    cfg.service(estimate_recall);
+}
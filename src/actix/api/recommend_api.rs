@@ -1,24 +1,50 @@
+use std::time::Duration;
+
 use actix_web::rt::time::Instant;
 use actix_web::{post, web, Responder};
 use actix_web_validator::{Json, Path, Query};
 use collection::operations::consistency_params::ReadConsistency;
+use collection::operations::filter_validation::FilterValidation;
 use collection::operations::types::{RecommendRequest, RecommendRequestBatch};
 use segment::types::ScoredPoint;
 use storage::content_manager::errors::StorageError;
+use storage::content_manager::search_queue::SearchPriority;
 use storage::content_manager::toc::TableOfContent;
 
-use super::read_params::ReadParams;
+use super::read_params::{Access, SearchParams};
 use super::CollectionPath;
 use crate::actix::helpers::process_response;
+use crate::common::acl::{restrict_recommend_request, ReadAccess};
+use crate::common::points::{check_request_filters, do_wait_for_writes};
 
+#[allow(clippy::too_many_arguments)]
 async fn do_recommend_points(
     toc: &TableOfContent,
     collection_name: &str,
-    request: RecommendRequest,
+    mut request: RecommendRequest,
     read_consistency: Option<ReadConsistency>,
+    priority: SearchPriority,
+    timeout: Option<Duration>,
+    filter_validation: Option<FilterValidation>,
+    access: &ReadAccess,
 ) -> Result<Vec<ScoredPoint>, StorageError> {
-    toc.recommend(collection_name, request, read_consistency)
-        .await
+    check_request_filters(
+        toc,
+        collection_name,
+        [request.filter.as_ref()],
+        None,
+        filter_validation,
+    )
+    .await?;
+    restrict_recommend_request(toc, collection_name, &mut request, access).await?;
+    toc.recommend(
+        collection_name,
+        request,
+        read_consistency,
+        priority,
+        timeout,
+    )
+    .await
 }
 
 #[post("/collections/{name}/points/recommend")]
@@ -26,29 +52,67 @@ async fn recommend_points(
     toc: web::Data<TableOfContent>,
     collection: Path<CollectionPath>,
     request: Json<RecommendRequest>,
-    params: Query<ReadParams>,
+    params: Query<SearchParams>,
+    Access(access): Access,
 ) -> impl Responder {
     let timing = Instant::now();
 
-    let response = do_recommend_points(
+    let response = match do_wait_for_writes(
         toc.get_ref(),
         &collection.name,
-        request.into_inner(),
-        params.consistency,
+        params.consistency_token.as_ref(),
     )
-    .await;
+    .await
+    {
+        Ok(()) => {
+            do_recommend_points(
+                toc.get_ref(),
+                &collection.name,
+                request.into_inner(),
+                params.consistency,
+                params.priority,
+                params.timeout(),
+                params.filter_validation,
+                &access,
+            )
+            .await
+        }
+        Err(err) => Err(err),
+    };
 
     process_response(response, timing)
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn do_recommend_batch_points(
     toc: &TableOfContent,
     collection_name: &str,
-    request: RecommendRequestBatch,
+    mut request: RecommendRequestBatch,
     read_consistency: Option<ReadConsistency>,
+    priority: SearchPriority,
+    timeout: Option<Duration>,
+    filter_validation: Option<FilterValidation>,
+    access: &ReadAccess,
 ) -> Result<Vec<Vec<ScoredPoint>>, StorageError> {
-    toc.recommend_batch(collection_name, request, read_consistency)
-        .await
+    check_request_filters(
+        toc,
+        collection_name,
+        request.searches.iter().map(|search| search.filter.as_ref()),
+        None,
+        filter_validation,
+    )
+    .await?;
+    for search in &mut request.searches {
+        restrict_recommend_request(toc, collection_name, search, access).await?;
+    }
+    toc.recommend_batch(
+        collection_name,
+        request,
+        read_consistency,
+        priority,
+        timeout,
+    )
+    .await
 }
 
 #[post("/collections/{name}/points/recommend/batch")]
@@ -56,17 +120,33 @@ async fn recommend_batch_points(
     toc: web::Data<TableOfContent>,
     collection: Path<CollectionPath>,
     request: Json<RecommendRequestBatch>,
-    params: Query<ReadParams>,
+    params: Query<SearchParams>,
+    Access(access): Access,
 ) -> impl Responder {
     let timing = Instant::now();
 
-    let response = do_recommend_batch_points(
+    let response = match do_wait_for_writes(
         toc.get_ref(),
         &collection.name,
-        request.into_inner(),
-        params.consistency,
+        params.consistency_token.as_ref(),
     )
-    .await;
+    .await
+    {
+        Ok(()) => {
+            do_recommend_batch_points(
+                toc.get_ref(),
+                &collection.name,
+                request.into_inner(),
+                params.consistency,
+                params.priority,
+                params.timeout(),
+                params.filter_validation,
+                &access,
+            )
+            .await
+        }
+        Err(err) => Err(err),
+    };
 
     process_response(response, timing)
 }
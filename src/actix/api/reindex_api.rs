@@ -0,0 +1,69 @@
+use actix_web::rt::time::Instant;
+use actix_web::{delete, get, post, web, Responder};
+use actix_web_validator::{Json, Path};
+use serde::Deserialize;
+use storage::content_manager::reindex::{
+    cancel_reindex, start_reindex, ReindexJobId, ReindexRequest,
+};
+use storage::content_manager::toc::TableOfContent;
+use storage::dispatcher::Dispatcher;
+use validator::Validate;
+
+use super::CollectionPath;
+use crate::actix::helpers::process_response;
+
+#[derive(Deserialize, Validate)]
+struct ReindexJobPath {
+    job_id: ReindexJobId,
+}
+
+#[post("/collections/{name}/reindex")]
+async fn create_reindex_job(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    request: Json<ReindexRequest>,
+) -> impl Responder {
+    let timing = Instant::now();
+    let response = start_reindex(
+        dispatcher.into_inner(),
+        &collection.name,
+        request.into_inner(),
+    )
+    .await;
+    process_response(response, timing)
+}
+
+#[get("/reindex")]
+async fn list_reindex_jobs(toc: web::Data<TableOfContent>) -> impl Responder {
+    let timing = Instant::now();
+    let response = Ok(toc.list_reindex_jobs());
+    process_response(response, timing)
+}
+
+#[get("/reindex/{job_id}")]
+async fn get_reindex_job(
+    toc: web::Data<TableOfContent>,
+    job: Path<ReindexJobPath>,
+) -> impl Responder {
+    let timing = Instant::now();
+    let response = toc.reindex_job(job.job_id);
+    process_response(response, timing)
+}
+
+#[delete("/reindex/{job_id}")]
+async fn cancel_reindex_job(
+    toc: web::Data<TableOfContent>,
+    job: Path<ReindexJobPath>,
+) -> impl Responder {
+    let timing = Instant::now();
+    let response = cancel_reindex(toc.get_ref(), job.job_id);
+    process_response(response, timing)
+}
+
+// Configure services
+pub fn config_reindex_api(cfg: &mut web::ServiceConfig) {
+    cfg.service(create_reindex_job)
+        .service(list_reindex_jobs)
+        .service(get_reindex_job)
+        .service(cancel_reindex_job);
+}
@@ -1,18 +1,26 @@
+use std::time::Duration;
+
 use actix_web::rt::time::Instant;
 use actix_web::{get, post, web, Responder};
 use actix_web_validator::{Json, Path, Query};
 use collection::operations::consistency_params::ReadConsistency;
-use collection::operations::types::{PointRequest, Record, ScrollRequest, ScrollResult};
+use collection::operations::filter_validation::FilterValidation;
+use collection::operations::types::{
+    PointRequest, PointsExistRequest, Record, ScrollRequest, ScrollResult,
+};
 use segment::types::{PointIdType, WithPayloadInterface};
 use serde::Deserialize;
 use storage::content_manager::errors::StorageError;
 use storage::content_manager::toc::TableOfContent;
 use validator::Validate;
 
-use super::read_params::ReadParams;
+use super::read_params::{Access, ReadParams};
 use super::CollectionPath;
 use crate::actix::helpers::process_response;
-use crate::common::points::do_get_points;
+use crate::common::acl::ReadAccess;
+use crate::common::points::{
+    do_check_points_exist, do_get_points, do_scroll_points, do_wait_for_writes,
+};
 
 #[derive(Deserialize, Validate)]
 struct PointPath {
@@ -26,6 +34,7 @@ async fn do_get_point(
     collection_name: &str,
     point_id: PointIdType,
     read_consistency: Option<ReadConsistency>,
+    access: &ReadAccess,
 ) -> Result<Option<Record>, StorageError> {
     let request = PointRequest {
         ids: vec![point_id],
@@ -33,9 +42,17 @@ async fn do_get_point(
         with_vector: true.into(),
     };
 
-    toc.retrieve(collection_name, request, read_consistency, None)
-        .await
-        .map(|points| points.into_iter().next())
+    do_get_points(
+        toc,
+        collection_name,
+        request,
+        read_consistency,
+        None,
+        None,
+        access,
+    )
+    .await
+    .map(|points| points.into_iter().next())
 }
 
 async fn scroll_get_points(
@@ -43,9 +60,22 @@ async fn scroll_get_points(
     collection_name: &str,
     request: ScrollRequest,
     read_consistency: Option<ReadConsistency>,
+    timeout: Option<Duration>,
+    filter_validation: Option<FilterValidation>,
+    access: &ReadAccess,
 ) -> Result<ScrollResult, StorageError> {
-    toc.scroll(collection_name, request, read_consistency, None)
-        .await
+    do_scroll_points(
+        toc,
+        collection_name,
+        request,
+        read_consistency,
+        None,
+        None,
+        timeout,
+        filter_validation,
+        access,
+    )
+    .await
 }
 
 #[get("/collections/{name}/points/{id}")]
@@ -54,6 +84,7 @@ async fn get_point(
     collection: Path<CollectionPath>,
     point: Path<PointPath>,
     params: Query<ReadParams>,
+    Access(access): Access,
 ) -> impl Responder {
     let timing = Instant::now();
 
@@ -70,13 +101,25 @@ async fn get_point(
         }
     };
 
-    let response = do_get_point(
+    let response = match do_wait_for_writes(
         toc.get_ref(),
         &collection.name,
-        point_id,
-        params.consistency,
+        params.consistency_token.as_ref(),
     )
-    .await;
+    .await
+    {
+        Ok(()) => {
+            do_get_point(
+                toc.get_ref(),
+                &collection.name,
+                point_id,
+                params.consistency,
+                &access,
+            )
+            .await
+        }
+        Err(err) => Err(err),
+    };
 
     let response = match response {
         Ok(record) => match record {
@@ -96,15 +139,49 @@ async fn get_points(
     collection: Path<CollectionPath>,
     request: Json<PointRequest>,
     params: Query<ReadParams>,
+    Access(access): Access,
 ) -> impl Responder {
     let timing = Instant::now();
 
-    let response = do_get_points(
+    let response = match do_wait_for_writes(
+        toc.get_ref(),
+        &collection.name,
+        params.consistency_token.as_ref(),
+    )
+    .await
+    {
+        Ok(()) => {
+            do_get_points(
+                toc.get_ref(),
+                &collection.name,
+                request.into_inner(),
+                params.consistency,
+                None,
+                None,
+                &access,
+            )
+            .await
+        }
+        Err(err) => Err(err),
+    };
+    process_response(response, timing)
+}
+
+#[post("/collections/{name}/points/exists")]
+async fn check_points_exist(
+    toc: web::Data<TableOfContent>,
+    collection: Path<CollectionPath>,
+    request: Json<PointsExistRequest>,
+    Access(access): Access,
+) -> impl Responder {
+    let timing = Instant::now();
+
+    let response = do_check_points_exist(
         toc.get_ref(),
         &collection.name,
         request.into_inner(),
-        params.consistency,
         None,
+        &access,
     )
     .await;
     process_response(response, timing)
@@ -116,15 +193,30 @@ async fn scroll_points(
     collection: Path<CollectionPath>,
     request: Json<ScrollRequest>,
     params: Query<ReadParams>,
+    Access(access): Access,
 ) -> impl Responder {
     let timing = Instant::now();
 
-    let response = scroll_get_points(
+    let response = match do_wait_for_writes(
         toc.get_ref(),
         &collection.name,
-        request.into_inner(),
-        params.consistency,
+        params.consistency_token.as_ref(),
     )
-    .await;
+    .await
+    {
+        Ok(()) => {
+            scroll_get_points(
+                toc.get_ref(),
+                &collection.name,
+                request.into_inner(),
+                params.consistency,
+                params.timeout(),
+                params.filter_validation,
+                &access,
+            )
+            .await
+        }
+        Err(err) => Err(err),
+    };
     process_response(response, timing)
 }
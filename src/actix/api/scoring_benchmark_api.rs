@@ -0,0 +1,37 @@
+use actix_web::rt::time::Instant;
+use actix_web::{post, web, Responder};
+use actix_web_validator::{Json, Path};
+use collection::operations::scoring_benchmark_ops::{
+    ScoringBenchmarkRequest, ScoringBenchmarkResult,
+};
+use storage::content_manager::errors::StorageError;
+use storage::content_manager::toc::TableOfContent;
+
+use super::CollectionPath;
+use crate::actix::helpers::process_response;
+
+async fn do_benchmark_scoring(
+    toc: &TableOfContent,
+    collection_name: &str,
+    request: ScoringBenchmarkRequest,
+) -> Result<ScoringBenchmarkResult, StorageError> {
+    let collection = toc.get_collection(collection_name).await?;
+    Ok(collection.benchmark_scoring(request).await?)
+}
+
+#[post("/collections/{name}/benchmark/scoring")]
+async fn benchmark_scoring(
+    toc: web::Data<TableOfContent>,
+    collection: Path<CollectionPath>,
+    request: Json<ScoringBenchmarkRequest>,
+) -> impl Responder {
+    let timing = Instant::now();
+    let response =
+        do_benchmark_scoring(toc.get_ref(), &collection.name, request.into_inner()).await;
+    process_response(response, timing)
+}
+
+// Configure services
+pub fn config_scoring_benchmark_api(cfg: &mut web::ServiceConfig) {
+    cfg.service(benchmark_scoring);
+}
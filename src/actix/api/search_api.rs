@@ -1,31 +1,53 @@
 use actix_web::rt::time::Instant;
 use actix_web::{post, web, Responder};
 use actix_web_validator::{Json, Path, Query};
-use collection::operations::types::{SearchRequest, SearchRequestBatch};
+use collection::operations::types::{
+    ArithmeticSearchRequest, SearchGroupsRequest, SearchRequest, SearchRequestBatch,
+};
 use storage::content_manager::toc::TableOfContent;
 
-use super::read_params::ReadParams;
+use super::read_params::{Access, SearchParams};
 use super::CollectionPath;
 use crate::actix::helpers::process_response;
-use crate::common::points::{do_search_batch_points, do_search_points};
+use crate::common::points::{
+    do_search_arithmetic_points, do_search_batch_points, do_search_point_groups, do_search_points,
+    do_wait_for_writes,
+};
 
 #[post("/collections/{name}/points/search")]
 async fn search_points(
     toc: web::Data<TableOfContent>,
     collection: Path<CollectionPath>,
     request: Json<SearchRequest>,
-    params: Query<ReadParams>,
+    params: Query<SearchParams>,
+    Access(access): Access,
 ) -> impl Responder {
     let timing = Instant::now();
 
-    let response = do_search_points(
+    let response = match do_wait_for_writes(
         toc.get_ref(),
         &collection.name,
-        request.into_inner(),
-        params.consistency,
-        None,
+        params.consistency_token.as_ref(),
     )
-    .await;
+    .await
+    {
+        Ok(()) => {
+            do_search_points(
+                toc.get_ref(),
+                &collection.name,
+                request.into_inner(),
+                params.consistency,
+                None,
+                None,
+                params.priority,
+                params.timeout(),
+                params.filter_validation,
+                &access,
+            )
+            .await
+        }
+        Err(err) => Err(err),
+    };
 
     process_response(response, timing)
 }
@@ -35,23 +57,113 @@ async fn batch_search_points(
     toc: web::Data<TableOfContent>,
     collection: Path<CollectionPath>,
     request: Json<SearchRequestBatch>,
-    params: Query<ReadParams>,
+    params: Query<SearchParams>,
+    Access(access): Access,
 ) -> impl Responder {
     let timing = Instant::now();
 
-    let response = do_search_batch_points(
+    let response = match do_wait_for_writes(
         toc.get_ref(),
         &collection.name,
-        request.into_inner(),
-        params.consistency,
-        None,
+        params.consistency_token.as_ref(),
     )
-    .await;
+    .await
+    {
+        Ok(()) => {
+            do_search_batch_points(
+                toc.get_ref(),
+                &collection.name,
+                request.into_inner(),
+                params.consistency,
+                None,
+                None,
+                params.priority,
+                params.timeout(),
+                params.filter_validation,
+                &access,
+            )
+            .await
+        }
+        Err(err) => Err(err),
+    };
+
+    process_response(response, timing)
+}
+
+#[post("/collections/{name}/points/search/groups")]
+async fn search_point_groups(
+    toc: web::Data<TableOfContent>,
+    collection: Path<CollectionPath>,
+    request: Json<SearchGroupsRequest>,
+    params: Query<SearchParams>,
+    Access(access): Access,
+) -> impl Responder {
+    let timing = Instant::now();
+
+    let response = match do_wait_for_writes(
+        toc.get_ref(),
+        &collection.name,
+        params.consistency_token.as_ref(),
+    )
+    .await
+    {
+        Ok(()) => {
+            do_search_point_groups(
+                toc.get_ref(),
+                &collection.name,
+                request.into_inner(),
+                params.consistency,
+                params.priority,
+                params.filter_validation,
+                &access,
+            )
+            .await
+        }
+        Err(err) => Err(err),
+    };
+
+    process_response(response, timing)
+}
+
+#[post("/collections/{name}/points/search/arithmetic")]
+async fn search_arithmetic_points(
+    toc: web::Data<TableOfContent>,
+    collection: Path<CollectionPath>,
+    request: Json<ArithmeticSearchRequest>,
+    params: Query<SearchParams>,
+    Access(access): Access,
+) -> impl Responder {
+    let timing = Instant::now();
+
+    let response = match do_wait_for_writes(
+        toc.get_ref(),
+        &collection.name,
+        params.consistency_token.as_ref(),
+    )
+    .await
+    {
+        Ok(()) => {
+            do_search_arithmetic_points(
+                toc.get_ref(),
+                &collection.name,
+                request.into_inner(),
+                params.consistency,
+                params.priority,
+                params.filter_validation,
+                &access,
+            )
+            .await
+        }
+        Err(err) => Err(err),
+    };
 
     process_response(response, timing)
 }
 
 // Configure services
 pub fn config_search_api(cfg: &mut web::ServiceConfig) {
-    cfg.service(search_points).service(batch_search_points);
+    cfg.service(search_points)
+        .service(batch_search_points)
+        .service(search_point_groups)
+        .service(search_arithmetic_points);
 }
@@ -13,6 +13,7 @@ use crate::actix::helpers::process_response;
 use crate::common::helpers::LocksOption;
 use crate::common::metrics::MetricsData;
 use crate::common::telemetry::TelemetryCollector;
+use crate::common::telemetry_reporting::{ReportingState, TelemetryReportPreview};
 
 #[derive(Deserialize, Serialize, JsonSchema)]
 pub struct TelemetryParam {
@@ -38,6 +39,17 @@ async fn telemetry(
     process_response(Ok(telemetry_data), timing)
 }
 
+#[get("/telemetry/reporting")]
+async fn telemetry_reporting(
+    telemetry_collector: web::Data<Mutex<TelemetryCollector>>,
+    reporting: web::Data<ReportingState>,
+) -> impl Responder {
+    let timing = Instant::now();
+    let telemetry_collector = telemetry_collector.lock().await;
+    let preview = TelemetryReportPreview::collect(&telemetry_collector, &reporting).await;
+    process_response(Ok(preview), timing)
+}
+
 #[derive(Deserialize, Serialize, JsonSchema)]
 pub struct MetricsParam {
     pub anonymize: Option<bool>,
@@ -90,6 +102,7 @@ async fn get_locks(toc: web::Data<TableOfContent>) -> impl Responder {
 // Configure services
 pub fn config_service_api(cfg: &mut web::ServiceConfig) {
     cfg.service(telemetry)
+        .service(telemetry_reporting)
         .service(metrics)
         .service(put_locks)
         .service(get_locks);
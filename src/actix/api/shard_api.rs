@@ -0,0 +1,237 @@
+use actix_web::rt::time::Instant;
+use actix_web::{post, web, Responder};
+use actix_web_validator::{Json, Path, Query};
+use collection::operations::consistency_params::ConsistencyToken;
+use collection::operations::types::{
+    CountRequest, PointRequest, ScrollRequest, SearchRequest, SearchRequestBatch,
+};
+use collection::shards::shard::ShardId;
+use serde::Deserialize;
+use storage::content_manager::errors::StorageError;
+use storage::content_manager::toc::TableOfContent;
+use validator::Validate;
+
+use super::read_params::{Access, ReadParams, SearchParams};
+use crate::actix::helpers::process_response;
+use crate::common::points::{
+    do_count_points, do_get_points, do_scroll_points, do_search_batch_points, do_search_points,
+    do_wait_for_writes,
+};
+
+#[derive(Deserialize, Validate)]
+struct ShardPath {
+    #[validate(length(min = 1, max = 255))]
+    name: String,
+    shard_id: ShardId,
+}
+
+/// Reads of a single shard silently return empty results for unknown shards,
+/// which is fine for internal requests, but misleading for clients.
+async fn check_shard_exists(
+    toc: &TableOfContent,
+    collection_name: &str,
+    shard_id: ShardId,
+) -> Result<(), StorageError> {
+    let collection = toc.get_collection(collection_name).await?;
+    if !collection.contains_shard(shard_id).await {
+        return Err(StorageError::NotFound {
+            description: format!("Shard {shard_id} of collection {collection_name} not found"),
+        });
+    }
+    Ok(())
+}
+
+/// Check the shard exists and wait for the writes of the consistency token, if the read has one
+async fn prepare_shard_read(
+    toc: &TableOfContent,
+    collection_name: &str,
+    shard_id: ShardId,
+    consistency_token: Option<&ConsistencyToken>,
+) -> Result<(), StorageError> {
+    check_shard_exists(toc, collection_name, shard_id).await?;
+    do_wait_for_writes(toc, collection_name, consistency_token).await
+}
+
+#[post("/collections/{name}/shards/{shard_id}/points")]
+async fn get_shard_points(
+    toc: web::Data<TableOfContent>,
+    path: Path<ShardPath>,
+    request: Json<PointRequest>,
+    params: Query<ReadParams>,
+    Access(access): Access,
+) -> impl Responder {
+    let timing = Instant::now();
+
+    let response = match prepare_shard_read(
+        toc.get_ref(),
+        &path.name,
+        path.shard_id,
+        params.consistency_token.as_ref(),
+    )
+    .await
+    {
+        Ok(()) => {
+            do_get_points(
+                toc.get_ref(),
+                &path.name,
+                request.into_inner(),
+                params.consistency,
+                None,
+                Some(path.shard_id),
+                &access,
+            )
+            .await
+        }
+        Err(err) => Err(err),
+    };
+    process_response(response, timing)
+}
+
+#[post("/collections/{name}/shards/{shard_id}/points/scroll")]
+async fn scroll_shard_points(
+    toc: web::Data<TableOfContent>,
+    path: Path<ShardPath>,
+    request: Json<ScrollRequest>,
+    params: Query<ReadParams>,
+    Access(access): Access,
+) -> impl Responder {
+    let timing = Instant::now();
+
+    let response = match prepare_shard_read(
+        toc.get_ref(),
+        &path.name,
+        path.shard_id,
+        params.consistency_token.as_ref(),
+    )
+    .await
+    {
+        Ok(()) => {
+            do_scroll_points(
+                toc.get_ref(),
+                &path.name,
+                request.into_inner(),
+                params.consistency,
+                None,
+                Some(path.shard_id),
+                params.timeout(),
+                None,
+                &access,
+            )
+            .await
+        }
+        Err(err) => Err(err),
+    };
+    process_response(response, timing)
+}
+
+#[post("/collections/{name}/shards/{shard_id}/points/search")]
+async fn search_shard_points(
+    toc: web::Data<TableOfContent>,
+    path: Path<ShardPath>,
+    request: Json<SearchRequest>,
+    params: Query<SearchParams>,
+    Access(access): Access,
+) -> impl Responder {
+    let timing = Instant::now();
+
+    let response = match prepare_shard_read(
+        toc.get_ref(),
+        &path.name,
+        path.shard_id,
+        params.consistency_token.as_ref(),
+    )
+    .await
+    {
+        Ok(()) => {
+            do_search_points(
+                toc.get_ref(),
+                &path.name,
+                request.into_inner(),
+                params.consistency,
+                None,
+                Some(path.shard_id),
+                params.priority,
+                params.timeout(),
+                None,
+                &access,
+            )
+            .await
+        }
+        Err(err) => Err(err),
+    };
+    process_response(response, timing)
+}
+
+#[post("/collections/{name}/shards/{shard_id}/points/search/batch")]
+async fn batch_search_shard_points(
+    toc: web::Data<TableOfContent>,
+    path: Path<ShardPath>,
+    request: Json<SearchRequestBatch>,
+    params: Query<SearchParams>,
+    Access(access): Access,
+) -> impl Responder {
+    let timing = Instant::now();
+
+    let response = match prepare_shard_read(
+        toc.get_ref(),
+        &path.name,
+        path.shard_id,
+        params.consistency_token.as_ref(),
+    )
+    .await
+    {
+        Ok(()) => {
+            do_search_batch_points(
+                toc.get_ref(),
+                &path.name,
+                request.into_inner(),
+                params.consistency,
+                None,
+                Some(path.shard_id),
+                params.priority,
+                params.timeout(),
+                None,
+                &access,
+            )
+            .await
+        }
+        Err(err) => Err(err),
+    };
+    process_response(response, timing)
+}
+
+#[post("/collections/{name}/shards/{shard_id}/points/count")]
+async fn count_shard_points(
+    toc: web::Data<TableOfContent>,
+    path: Path<ShardPath>,
+    request: Json<CountRequest>,
+    Access(access): Access,
+) -> impl Responder {
+    let timing = Instant::now();
+
+    let response = match check_shard_exists(toc.get_ref(), &path.name, path.shard_id).await {
+        Ok(()) => {
+            do_count_points(
+                toc.get_ref(),
+                &path.name,
+                request.into_inner(),
+                None,
+                Some(path.shard_id),
+                None,
+                &access,
+            )
+            .await
+        }
+        Err(err) => Err(err),
+    };
+    process_response(response, timing)
+}
+
+// Configure services
+pub fn config_shard_api(cfg: &mut web::ServiceConfig) {
+    cfg.service(get_shard_points)
+        .service(scroll_shard_points)
+        .service(search_shard_points)
+        .service(batch_search_shard_points)
+        .service(count_shard_points);
+}
@@ -14,17 +14,19 @@ use storage::content_manager::errors::StorageError;
 use storage::content_manager::snapshots::recover::do_recover_from_snapshot;
 use storage::content_manager::snapshots::{
     do_create_full_snapshot, do_delete_collection_snapshot, do_delete_full_snapshot,
-    do_list_full_snapshots, get_full_snapshot_path,
+    do_inspect_collection_snapshot, do_list_full_snapshots, get_full_snapshot_path,
 };
 use storage::content_manager::toc::TableOfContent;
 use storage::dispatcher::Dispatcher;
 use uuid::Uuid;
 use validator::Validate;
 
+use super::read_params::Access;
 use super::CollectionPath;
 use crate::actix::helpers::{
     accepted_response, collection_into_actix_error, process_response, storage_into_actix_error,
 };
+use crate::common::acl::ReadAccess;
 use crate::common::collections::*;
 
 #[derive(Deserialize, Validate)]
@@ -51,7 +53,14 @@ pub struct SnapshottingForm {
 }
 
 // Actix specific code
-pub async fn do_get_full_snapshot(toc: &TableOfContent, snapshot_name: &str) -> Result<NamedFile> {
+pub async fn do_get_full_snapshot(
+    toc: &TableOfContent,
+    snapshot_name: &str,
+    access: &ReadAccess,
+) -> Result<NamedFile> {
+    toc.check_unrestricted_read(access)
+        .await
+        .map_err(storage_into_actix_error)?;
     let file_name = get_full_snapshot_path(toc, snapshot_name)
         .await
         .map_err(storage_into_actix_error)?;
@@ -88,9 +97,10 @@ pub async fn do_get_snapshot(
     toc: &TableOfContent,
     collection_name: &str,
     snapshot_name: &str,
+    access: &ReadAccess,
 ) -> Result<NamedFile> {
     let collection = toc
-        .get_collection(collection_name)
+        .get_collection_for_read(collection_name, access)
         .await
         .map_err(storage_into_actix_error)?;
 
@@ -103,11 +113,15 @@ pub async fn do_get_snapshot(
 }
 
 #[get("/collections/{name}/snapshots")]
-async fn list_snapshots(toc: web::Data<TableOfContent>, path: web::Path<String>) -> impl Responder {
+async fn list_snapshots(
+    toc: web::Data<TableOfContent>,
+    path: web::Path<String>,
+    Access(access): Access,
+) -> impl Responder {
     let collection_name = path.into_inner();
     let timing = Instant::now();
 
-    let response = do_list_snapshots(&toc, &collection_name).await;
+    let response = do_list_snapshots(&toc, &collection_name, &access).await;
     process_response(response, timing)
 }
 
@@ -116,12 +130,13 @@ async fn create_snapshot(
     dispatcher: web::Data<Dispatcher>,
     path: web::Path<String>,
     params: Query<SnapshottingParam>,
+    Access(access): Access,
 ) -> impl Responder {
     let collection_name = path.into_inner();
     let wait = params.wait.unwrap_or(true);
 
     let timing = Instant::now();
-    let response = do_create_snapshot(dispatcher.get_ref(), &collection_name, wait).await;
+    let response = do_create_snapshot(dispatcher.get_ref(), &collection_name, &access, wait).await;
     match response {
         Err(_) => process_response(response, timing),
         Ok(_) if wait => process_response(response, timing),
@@ -194,14 +209,32 @@ async fn recover_from_snapshot(
 async fn get_snapshot(
     toc: web::Data<TableOfContent>,
     path: web::Path<(String, String)>,
+    Access(access): Access,
+) -> impl Responder {
+    let (collection_name, snapshot_name) = path.into_inner();
+    do_get_snapshot(&toc, &collection_name, &snapshot_name, &access).await
+}
+
+#[get("/collections/{name}/snapshots/{snapshot_name}/inspect")]
+async fn inspect_snapshot(
+    toc: web::Data<TableOfContent>,
+    path: web::Path<(String, String)>,
+    Access(access): Access,
 ) -> impl Responder {
     let (collection_name, snapshot_name) = path.into_inner();
-    do_get_snapshot(&toc, &collection_name, &snapshot_name).await
+    let timing = Instant::now();
+    let response =
+        do_inspect_collection_snapshot(&toc, &collection_name, &snapshot_name, &access).await;
+    process_response(response, timing)
 }
+
 #[get("/snapshots")]
-async fn list_full_snapshots(toc: web::Data<TableOfContent>) -> impl Responder {
+async fn list_full_snapshots(
+    toc: web::Data<TableOfContent>,
+    Access(access): Access,
+) -> impl Responder {
     let timing = Instant::now();
-    let response = do_list_full_snapshots(toc.get_ref()).await;
+    let response = do_list_full_snapshots(toc.get_ref(), &access).await;
     process_response(response, timing)
 }
 
@@ -209,10 +242,11 @@ async fn list_full_snapshots(toc: web::Data<TableOfContent>) -> impl Responder {
 async fn create_full_snapshot(
     dispatcher: web::Data<Dispatcher>,
     params: Query<SnapshottingParam>,
+    Access(access): Access,
 ) -> impl Responder {
     let timing = Instant::now();
     let wait = params.wait.unwrap_or(true);
-    let response = do_create_full_snapshot(dispatcher.get_ref(), wait).await;
+    let response = do_create_full_snapshot(dispatcher.get_ref(), &access, wait).await;
     match response {
         Err(_) => process_response(response, timing),
         Ok(_) if wait => process_response(response, timing),
@@ -224,9 +258,10 @@ async fn create_full_snapshot(
 async fn get_full_snapshot(
     toc: web::Data<TableOfContent>,
     path: web::Path<String>,
+    Access(access): Access,
 ) -> impl Responder {
     let snapshot_name = path.into_inner();
-    do_get_full_snapshot(&toc, &snapshot_name).await
+    do_get_full_snapshot(&toc, &snapshot_name, &access).await
 }
 
 #[delete("/snapshots/{snapshot_name}")]
@@ -272,6 +307,7 @@ pub fn config_snapshots_api(cfg: &mut web::ServiceConfig) {
         .service(upload_snapshot)
         .service(recover_from_snapshot)
         .service(get_snapshot)
+        .service(inspect_snapshot)
         .service(list_full_snapshots)
         .service(create_full_snapshot)
         .service(get_full_snapshot)
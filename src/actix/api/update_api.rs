@@ -3,8 +3,11 @@ use actix_web::{delete, post, put, web, Responder};
 use actix_web_validator::{Json, Path, Query};
 use collection::operations::payload_ops::{DeletePayload, SetPayload};
 use collection::operations::point_ops::{PointInsertOperations, PointsSelector, WriteOrdering};
+use collection::operations::types::UpdateResult;
+use collection::shards::shard::ShardId;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use storage::content_manager::errors::StorageError;
 use storage::content_manager::toc::TableOfContent;
 use validator::Validate;
 
@@ -26,6 +29,33 @@ struct FieldPath {
 pub struct UpdateParam {
     pub wait: Option<bool>,
     pub ordering: Option<WriteOrdering>,
+    /// If true, report the write of the update to the replicas of each shard
+    pub verbose: Option<bool>,
+}
+
+#[derive(Deserialize, Serialize, JsonSchema, Validate)]
+pub struct UpdatePointsParam {
+    pub wait: Option<bool>,
+    pub ordering: Option<WriteOrdering>,
+    /// Shard, which is expected to store all affected points, according to the routing table.
+    /// Request is rejected if the points are stored in other shards.
+    pub shard_key: Option<ShardId>,
+    /// If true, report the write of the update to the replicas of each shard
+    pub verbose: Option<bool>,
+}
+
+/// Report of the replication is verbose, so it is only returned on request
+pub fn with_replication_report(
+    response: Result<UpdateResult, StorageError>,
+    verbose: Option<bool>,
+) -> Result<UpdateResult, StorageError> {
+    if verbose.unwrap_or(false) {
+        return response;
+    }
+    response.map(|result| UpdateResult {
+        replication: None,
+        ..result
+    })
 }
 
 #[put("/collections/{name}/points")]
@@ -33,23 +63,23 @@ async fn upsert_points(
     toc: web::Data<TableOfContent>,
     collection: Path<CollectionPath>,
     operation: Json<PointInsertOperations>,
-    params: Query<UpdateParam>,
+    params: Query<UpdatePointsParam>,
 ) -> impl Responder {
     let timing = Instant::now();
     let operation = operation.into_inner();
     let wait = params.wait.unwrap_or(false);
-    let ordering = params.ordering.unwrap_or_default();
 
     let response = do_upsert_points(
         toc.get_ref(),
         &collection.name,
         operation,
         None,
+        params.shard_key,
         wait,
-        ordering,
+        params.ordering,
     )
     .await;
-    process_response(response, timing)
+    process_response(with_replication_report(response, params.verbose), timing)
 }
 
 #[post("/collections/{name}/points/delete")]
@@ -57,23 +87,23 @@ async fn delete_points(
     toc: web::Data<TableOfContent>,
     collection: Path<CollectionPath>,
     operation: Json<PointsSelector>,
-    params: Query<UpdateParam>,
+    params: Query<UpdatePointsParam>,
 ) -> impl Responder {
     let timing = Instant::now();
     let operation = operation.into_inner();
     let wait = params.wait.unwrap_or(false);
-    let ordering = params.ordering.unwrap_or_default();
 
     let response = do_delete_points(
         toc.get_ref(),
         &collection.name,
         operation,
         None,
+        params.shard_key,
         wait,
-        ordering,
+        params.ordering,
     )
     .await;
-    process_response(response, timing)
+    process_response(with_replication_report(response, params.verbose), timing)
 }
 
 #[post("/collections/{name}/points/payload")]
@@ -86,7 +116,6 @@ async fn set_payload(
     let timing = Instant::now();
     let operation = operation.into_inner();
     let wait = params.wait.unwrap_or(false);
-    let ordering = params.ordering.unwrap_or_default();
 
     let response = do_set_payload(
         toc.get_ref(),
@@ -94,10 +123,10 @@ async fn set_payload(
         operation,
         None,
         wait,
-        ordering,
+        params.ordering,
     )
     .await;
-    process_response(response, timing)
+    process_response(with_replication_report(response, params.verbose), timing)
 }
 
 #[put("/collections/{name}/points/payload")]
@@ -110,7 +139,6 @@ async fn overwrite_payload(
     let timing = Instant::now();
     let operation = operation.into_inner();
     let wait = params.wait.unwrap_or(false);
-    let ordering = params.ordering.unwrap_or_default();
 
     let response = do_overwrite_payload(
         toc.get_ref(),
@@ -118,10 +146,10 @@ async fn overwrite_payload(
         operation,
         None,
         wait,
-        ordering,
+        params.ordering,
     )
     .await;
-    process_response(response, timing)
+    process_response(with_replication_report(response, params.verbose), timing)
 }
 
 #[post("/collections/{name}/points/payload/delete")]
@@ -134,7 +162,6 @@ async fn delete_payload(
     let timing = Instant::now();
     let operation = operation.into_inner();
     let wait = params.wait.unwrap_or(false);
-    let ordering = params.ordering.unwrap_or_default();
 
     let response = do_delete_payload(
         toc.get_ref(),
@@ -142,10 +169,10 @@ async fn delete_payload(
         operation,
         None,
         wait,
-        ordering,
+        params.ordering,
     )
     .await;
-    process_response(response, timing)
+    process_response(with_replication_report(response, params.verbose), timing)
 }
 
 #[post("/collections/{name}/points/payload/clear")]
@@ -158,7 +185,6 @@ async fn clear_payload(
     let timing = Instant::now();
     let operation = operation.into_inner();
     let wait = params.wait.unwrap_or(false);
-    let ordering = params.ordering.unwrap_or_default();
 
     let response = do_clear_payload(
         toc.get_ref(),
@@ -166,10 +192,10 @@ async fn clear_payload(
         operation,
         None,
         wait,
-        ordering,
+        params.ordering,
     )
     .await;
-    process_response(response, timing)
+    process_response(with_replication_report(response, params.verbose), timing)
 }
 
 #[put("/collections/{name}/index")]
@@ -182,7 +208,6 @@ async fn create_field_index(
     let timing = Instant::now();
     let operation = operation.into_inner();
     let wait = params.wait.unwrap_or(false);
-    let ordering = params.ordering.unwrap_or_default();
 
     let response = do_create_index(
         toc.get_ref(),
@@ -190,10 +215,10 @@ async fn create_field_index(
         operation,
         None,
         wait,
-        ordering,
+        params.ordering,
     )
     .await;
-    process_response(response, timing)
+    process_response(with_replication_report(response, params.verbose), timing)
 }
 
 #[delete("/collections/{name}/index/{field_name}")]
@@ -205,7 +230,6 @@ async fn delete_field_index(
 ) -> impl Responder {
     let timing = Instant::now();
     let wait = params.wait.unwrap_or(false);
-    let ordering = params.ordering.unwrap_or_default();
 
     let response = do_delete_index(
         toc.get_ref(),
@@ -213,10 +237,10 @@ async fn delete_field_index(
         field.name.clone(),
         None,
         wait,
-        ordering,
+        params.ordering,
     )
     .await;
-    process_response(response, timing)
+    process_response(with_replication_report(response, params.verbose), timing)
 }
 
 // Configure services
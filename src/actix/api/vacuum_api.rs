@@ -0,0 +1,34 @@
+use actix_web::rt::time::Instant;
+use actix_web::{post, web, Responder};
+use actix_web_validator::{Json, Path};
+use collection::operations::vacuum_ops::{VacuumRequest, VacuumResult};
+use storage::content_manager::errors::StorageError;
+use storage::content_manager::toc::TableOfContent;
+
+use super::CollectionPath;
+use crate::actix::helpers::process_response;
+
+async fn do_vacuum(
+    toc: &TableOfContent,
+    collection_name: &str,
+    request: VacuumRequest,
+) -> Result<VacuumResult, StorageError> {
+    let collection = toc.get_collection(collection_name).await?;
+    Ok(collection.vacuum(request).await?)
+}
+
+#[post("/collections/{name}/vacuum")]
+async fn vacuum(
+    toc: web::Data<TableOfContent>,
+    collection: Path<CollectionPath>,
+    request: Json<VacuumRequest>,
+) -> impl Responder {
+    let timing = Instant::now();
+    let response = do_vacuum(toc.get_ref(), &collection.name, request.into_inner()).await;
+    process_response(response, timing)
+}
+
+// Configure services
+pub fn config_vacuum_api(cfg: &mut web::ServiceConfig) {
+    cfg.service(vacuum);
+}
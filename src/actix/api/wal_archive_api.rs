@@ -0,0 +1,44 @@
+use actix_web::rt::time::Instant;
+use actix_web::{post, web, Responder};
+use actix_web_validator::{Json, Path};
+use collection::operations::wal_replay_ops::{WalReplayRequest, WalReplayResult};
+use storage::content_manager::errors::StorageError;
+use storage::content_manager::toc::TableOfContent;
+
+use super::read_params::Access;
+use super::CollectionPath;
+use crate::actix::helpers::process_response;
+use crate::common::acl::ReadAccess;
+
+async fn do_replay_wal_archive(
+    toc: &TableOfContent,
+    collection_name: &str,
+    request: WalReplayRequest,
+    access: &ReadAccess,
+) -> Result<WalReplayResult, StorageError> {
+    let collection = toc.get_collection_for_read(collection_name, access).await?;
+    Ok(collection.replay_wal_archive(request).await?)
+}
+
+#[post("/collections/{name}/wal_archive/replay")]
+async fn replay_wal_archive(
+    toc: web::Data<TableOfContent>,
+    collection: Path<CollectionPath>,
+    request: Json<WalReplayRequest>,
+    Access(access): Access,
+) -> impl Responder {
+    let timing = Instant::now();
+    let response = do_replay_wal_archive(
+        toc.get_ref(),
+        &collection.name,
+        request.into_inner(),
+        &access,
+    )
+    .await;
+    process_response(response, timing)
+}
+
+// Configure services
+pub fn config_wal_archive_api(cfg: &mut web::ServiceConfig) {
+    cfg.service(replay_wal_archive);
+}
@@ -0,0 +1,134 @@
+"use strict";
+
+const SCROLL_LIMIT = 10;
+
+let nextPageOffset = null;
+
+async function request(method, path, body) {
+    const options = {method, headers: {}};
+    if (body !== undefined) {
+        options.headers["Content-Type"] = "application/json";
+        options.body = typeof body === "string" ? body : JSON.stringify(body);
+    }
+    const response = await fetch(path, options);
+    const text = await response.text();
+    let json;
+    try {
+        json = JSON.parse(text);
+    } catch (e) {
+        json = text;
+    }
+    return {ok: response.ok, status: response.status, json};
+}
+
+function show(element, value, isError) {
+    element.textContent = typeof value === "string" ? value : JSON.stringify(value, null, 2);
+    element.classList.toggle("error", Boolean(isError));
+}
+
+function showResponse(element, response) {
+    show(element, response.json, !response.ok);
+}
+
+function selectTab(name) {
+    for (const button of document.querySelectorAll("nav button")) {
+        button.classList.toggle("active", button.dataset.tab === name);
+    }
+    for (const tab of document.querySelectorAll(".tab")) {
+        tab.classList.toggle("active", tab.id === name);
+    }
+}
+
+async function loadCollections() {
+    const list = document.getElementById("collections-list");
+    const select = document.getElementById("points-collection");
+    const response = await request("GET", "/collections");
+    if (!response.ok) {
+        showResponse(document.getElementById("collection-info"), response);
+        return;
+    }
+
+    list.replaceChildren();
+    select.replaceChildren();
+    for (const {name} of response.json.result.collections) {
+        const info = await request("GET", `/collections/${encodeURIComponent(name)}`);
+        const result = info.ok ? info.json.result : {};
+
+        const row = document.createElement("tr");
+        for (const value of [name, result.status, result.points_count, result.segments_count]) {
+            const cell = document.createElement("td");
+            cell.textContent = value === undefined ? "-" : value;
+            row.appendChild(cell);
+        }
+        const actions = document.createElement("td");
+        const infoButton = document.createElement("button");
+        infoButton.textContent = "Info";
+        infoButton.onclick = () => showResponse(document.getElementById("collection-info"), info);
+        const pointsButton = document.createElement("button");
+        pointsButton.textContent = "Points";
+        pointsButton.onclick = () => {
+            select.value = name;
+            selectTab("points");
+            scrollPoints(null);
+        };
+        actions.append(infoButton, pointsButton);
+        row.appendChild(actions);
+        list.appendChild(row);
+
+        const option = document.createElement("option");
+        option.value = option.textContent = name;
+        select.appendChild(option);
+    }
+}
+
+function collectionPath() {
+    const name = document.getElementById("points-collection").value;
+    return `/collections/${encodeURIComponent(name)}`;
+}
+
+async function getPoint() {
+    const id = document.getElementById("points-id").value.trim();
+    // Point ids are either unsigned integers or UUIDs
+    const pointId = /^\d+$/.test(id) ? id : encodeURIComponent(id);
+    const response = await request("GET", `${collectionPath()}/points/${pointId}`);
+    showResponse(document.getElementById("points-result"), response);
+}
+
+async function scrollPoints(offset) {
+    const body = {limit: SCROLL_LIMIT, with_payload: true, with_vector: true};
+    if (offset !== null) {
+        body.offset = offset;
+    }
+    const response = await request("POST", `${collectionPath()}/points/scroll`, body);
+    showResponse(document.getElementById("points-result"), response);
+    nextPageOffset = response.ok ? response.json.result.next_page_offset : null;
+    document.getElementById("points-next").disabled = nextPageOffset === null;
+}
+
+async function sendConsoleRequest() {
+    const method = document.getElementById("console-method").value;
+    const path = document.getElementById("console-path").value.trim();
+    const body = document.getElementById("console-body").value.trim();
+    const result = document.getElementById("console-result");
+    if (body) {
+        try {
+            JSON.parse(body);
+        } catch (e) {
+            show(result, `Invalid JSON body: ${e.message}`, true);
+            return;
+        }
+    }
+    const response = await request(method, path, body || undefined);
+    showResponse(result, response);
+}
+
+for (const button of document.querySelectorAll("nav button")) {
+    button.onclick = () => selectTab(button.dataset.tab);
+}
+document.getElementById("collections-refresh").onclick = loadCollections;
+document.getElementById("points-get").onclick = getPoint;
+document.getElementById("points-scroll").onclick = () => scrollPoints(null);
+document.getElementById("points-next").onclick = () => scrollPoints(nextPageOffset);
+document.getElementById("console-send").onclick = sendConsoleRequest;
+
+loadCollections();
@@ -19,6 +19,9 @@ pub fn storage_into_actix_error(err: StorageError) -> Error {
         StorageError::ServiceError { .. } => error::ErrorInternalServerError(format!("{err}")),
         StorageError::BadRequest { .. } => error::ErrorBadRequest(format!("{err}")),
         StorageError::Locked { .. } => error::ErrorForbidden(format!("{err}")),
+        StorageError::Overloaded { .. } => error::ErrorTooManyRequests(format!("{err}")),
+        StorageError::Forbidden { .. } => error::ErrorForbidden(format!("{err}")),
+        StorageError::Timeout { .. } => error::ErrorRequestTimeout(format!("{err}")),
     }
 }
 
@@ -58,6 +61,9 @@ where
                 }
                 StorageError::BadRequest { .. } => HttpResponse::BadRequest(),
                 StorageError::Locked { .. } => HttpResponse::Forbidden(),
+                StorageError::Overloaded { .. } => HttpResponse::TooManyRequests(),
+                StorageError::Forbidden { .. } => HttpResponse::Forbidden(),
+                StorageError::Timeout { .. } => HttpResponse::RequestTimeout(),
             };
 
             resp.json(ApiResponse::<()> {
@@ -3,6 +3,7 @@ pub mod actix_telemetry;
 pub mod api;
 #[allow(dead_code)] // May contain functions used in different binaries. Not actually dead
 pub mod helpers;
+pub mod request_id;
 
 use std::fs;
 use std::sync::Arc;
@@ -19,16 +20,37 @@ use openssl::x509::store::X509StoreBuilder;
 use openssl::x509::X509;
 use storage::dispatcher::Dispatcher;
 
+use crate::actix::api::backfill_api::config_backfill_api;
 use crate::actix::api::cluster_api::config_cluster_api;
 use crate::actix::api::collections_api::config_collections_api;
-use crate::actix::api::count_api::count_points;
+use crate::actix::api::config_reload_api::config_config_reload_api;
+use crate::actix::api::count_api::{count_points, estimate_count_points};
+use crate::actix::api::dashboard_api::config_dashboard_api;
+use crate::actix::api::debug_api::config_debug_api;
+use crate::actix::api::discovery_api::config_discovery_api;
+use crate::actix::api::facet_api::config_facet_api;
+use crate::actix::api::histogram_api::config_histogram_api;
+use crate::actix::api::index_rebuild_api::config_index_rebuild_api;
+use crate::actix::api::orphan_files_api::config_orphan_files_api;
+use crate::actix::api::payload_schema_api::config_payload_schema_api;
+use crate::actix::api::point_access_api::config_point_access_api;
+use crate::actix::api::query_api::config_query_api;
+use crate::actix::api::recall_api::config_recall_api;
 use crate::actix::api::recommend_api::config_recommend_api;
-use crate::actix::api::retrieve_api::{get_point, get_points, scroll_points};
+use crate::actix::api::reindex_api::config_reindex_api;
+use crate::actix::api::retrieve_api::{check_points_exist, get_point, get_points, scroll_points};
+use crate::actix::api::scoring_benchmark_api::config_scoring_benchmark_api;
 use crate::actix::api::search_api::config_search_api;
 use crate::actix::api::service_api::config_service_api;
+use crate::actix::api::shard_api::config_shard_api;
 use crate::actix::api::snapshot_api::config_snapshots_api;
+use crate::actix::api::terms_api::config_terms_api;
 use crate::actix::api::update_api::config_update_api;
+use crate::actix::api::vacuum_api::config_vacuum_api;
+use crate::actix::api::wal_archive_api::config_wal_archive_api;
+use crate::common::config_reload::ConfigReloader;
 use crate::common::telemetry::TelemetryCollector;
+use crate::common::telemetry_reporting::ReportingState;
 use crate::settings::{max_web_workers, Settings};
 
 #[get("/")]
@@ -40,10 +62,13 @@ pub async fn index() -> impl Responder {
 pub fn init(
     dispatcher: Arc<Dispatcher>,
     telemetry_collector: Arc<tokio::sync::Mutex<TelemetryCollector>>,
+    telemetry_reporting: Arc<ReportingState>,
+    config_reloader: Arc<ConfigReloader>,
     settings: Settings,
 ) -> std::io::Result<()> {
     actix_web::rt::System::new().block_on(async {
         let toc_data = web::Data::from(dispatcher.toc().clone());
+        let config_reloader_data = web::Data::from(config_reloader);
         let dispatcher_data = web::Data::from(dispatcher);
         let actix_telemetry_collector = telemetry_collector
             .lock()
@@ -51,6 +76,7 @@ pub fn init(
             .actix_telemetry_collector
             .clone();
         let telemetry_collector_data = web::Data::from(telemetry_collector);
+        let telemetry_reporting_data = web::Data::from(telemetry_reporting);
         let mut server = HttpServer::new(move || {
             let cors = Cors::default()
                 .allow_any_origin()
@@ -71,9 +97,12 @@ pub fn init(
                 .wrap(actix_telemetry::ActixTelemetryTransform::new(
                     actix_telemetry_collector.clone(),
                 ))
+                .wrap(request_id::RequestIdTransform)
                 .app_data(dispatcher_data.clone())
                 .app_data(toc_data.clone())
                 .app_data(telemetry_collector_data.clone())
+                .app_data(telemetry_reporting_data.clone())
+                .app_data(config_reloader_data.clone())
                 .app_data(validate_path_config)
                 .app_data(validate_query_config)
                 .app_data(validate_json_config)
@@ -84,13 +113,36 @@ pub fn init(
                 .configure(config_snapshots_api)
                 .configure(config_update_api)
                 .configure(config_cluster_api)
+                .configure(config_backfill_api)
+                .configure(config_reindex_api)
+                .configure(config_recall_api)
+                .configure(config_scoring_benchmark_api)
+                .configure(config_vacuum_api)
+                .configure(config_orphan_files_api)
+                .configure(config_index_rebuild_api)
+                .configure(config_terms_api)
+                .configure(config_histogram_api)
+                .configure(config_facet_api)
+                .configure(config_payload_schema_api)
+                .configure(config_point_access_api)
+                .configure(config_wal_archive_api)
                 .configure(config_service_api)
                 .configure(config_search_api)
                 .configure(config_recommend_api)
+                .configure(config_query_api)
+                .configure(config_discovery_api)
+                .configure(config_shard_api)
+                .configure(|cfg| config_debug_api(cfg, settings.service.enable_profiling))
+                .configure(|cfg| config_dashboard_api(cfg, settings.service.enable_dashboard))
+                .configure(|cfg| {
+                    config_config_reload_api(cfg, settings.service.enable_config_reload)
+                })
                 .service(get_point)
                 .service(get_points)
                 .service(scroll_points)
+                .service(check_points_exist)
                 .service(count_points)
+                .service(estimate_count_points)
         })
         .workers(max_web_workers(&settings));
 
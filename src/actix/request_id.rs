@@ -0,0 +1,77 @@
+use std::future::{ready, Ready};
+
+use actix_web::dev::{Service, ServiceRequest, ServiceResponse, Transform};
+use actix_web::http::header::{HeaderName, HeaderValue};
+use actix_web::Error;
+use futures_util::future::LocalBoxFuture;
+use segment::common::log_context::{with_log_context, LogContext};
+
+const REQUEST_ID_HEADER: &str = "x-request-id";
+
+pub struct RequestIdService<S> {
+    service: S,
+}
+
+pub struct RequestIdTransform;
+
+/// Request id service. It marks log lines, emitted while processing the request,
+/// with the id from `x-request-id` header or with a new random one.
+/// The id is returned in the same header of the response.
+///
+/// More about actix service with similar example
+/// <https://actix.rs/docs/middleware/>
+impl<S, B> Service<ServiceRequest> for RequestIdService<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    actix_web::dev::forward_ready!(service);
+
+    fn call(&self, request: ServiceRequest) -> Self::Future {
+        let request_id = request
+            .headers()
+            .get(REQUEST_ID_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .map(ToOwned::to_owned)
+            .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+        let future = with_log_context(
+            Some(LogContext::request(&request_id)),
+            self.service.call(request),
+        );
+        Box::pin(async move {
+            let mut response = future.await?;
+            if let Ok(value) = HeaderValue::from_str(&request_id) {
+                response
+                    .headers_mut()
+                    .insert(HeaderName::from_static(REQUEST_ID_HEADER), value);
+            }
+            Ok(response)
+        })
+    }
+}
+
+/// Request id transform. It's a builder for an actix service
+///
+/// More about actix transform with similar example
+/// <https://actix.rs/docs/middleware/>
+impl<S, B> Transform<S, ServiceRequest> for RequestIdTransform
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Transform = RequestIdService<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(RequestIdService { service }))
+    }
+}
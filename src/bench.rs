@@ -0,0 +1,465 @@
+use std::fs;
+use std::num::NonZeroU64;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use anyhow::{bail, Context};
+use clap::ValueEnum;
+use collection::operations::point_ops::{Batch, PointInsertOperations, PointOperations};
+use collection::operations::types::{CollectionStatus, SearchRequest, VectorParams};
+use collection::operations::CollectionUpdateOperations;
+use futures::{stream, StreamExt, TryStreamExt};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use rand_distr::{Distribution, Normal};
+use reqwest::header::CONTENT_TYPE;
+use reqwest::Method;
+use segment::data_types::vectors::VectorElementType;
+use segment::types::Distance;
+use serde::{Deserialize, Serialize};
+use storage::content_manager::collection_meta_ops::CreateCollection;
+use storage::content_manager::search_queue::SearchPriority;
+use storage::embedded::QdrantEmbedded;
+use storage::types::StorageConfig;
+use tokio::runtime::Runtime;
+use uuid::Uuid;
+
+use crate::settings::Settings;
+
+/// Number of centers of the `clustered` distribution
+const CLUSTERS: usize = 16;
+
+/// How often the collection status is checked, while waiting for the optimizations
+const STATUS_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+#[derive(clap::Args, Debug)]
+pub struct BenchArgs {
+    /// REST uri of a running instance to benchmark, e.g. `http://localhost:6333`.
+    /// If not specified - an embedded storage in a temporary directory is benchmarked,
+    /// configured the same way as the storage of this peer.
+    #[arg(long, value_name = "URI")]
+    uri: Option<String>,
+    /// Api key of the running instance
+    #[arg(long)]
+    api_key: Option<String>,
+    /// Name of the collection to create.
+    /// WARN: existing collection with this name is deleted.
+    #[arg(long, default_value = "benchmark")]
+    collection: String,
+    /// Dimensionality of the vectors
+    #[arg(long, default_value_t = 128)]
+    dim: usize,
+    /// Number of points to upload
+    #[arg(long, default_value_t = 100_000)]
+    count: usize,
+    /// Distribution of the generated vectors
+    #[arg(long, value_enum, default_value_t = VectorDistribution::Uniform)]
+    distribution: VectorDistribution,
+    /// Number of points in one upsert request
+    #[arg(long, default_value_t = 1000)]
+    batch_size: usize,
+    /// Number of search requests
+    #[arg(long, default_value_t = 1000)]
+    searches: usize,
+    /// Number of results of one search request
+    #[arg(long, default_value_t = 10)]
+    limit: usize,
+    /// Number of requests, running concurrently
+    #[arg(long, default_value_t = 4)]
+    parallel: usize,
+    /// Seed of the generated vectors. The same seed generates the same points and queries
+    #[arg(long, default_value_t = 42)]
+    seed: u64,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum VectorDistribution {
+    /// Components are uniformly distributed in [-1, 1]
+    Uniform,
+    /// Components are normally distributed around zero
+    Normal,
+    /// Vectors are grouped around a few random centers, like embeddings of real data usually are
+    Clustered,
+}
+
+struct VectorGenerator {
+    rng: StdRng,
+    dim: usize,
+    distribution: VectorDistribution,
+    centers: Vec<Vec<VectorElementType>>,
+}
+
+impl VectorGenerator {
+    fn new(dim: usize, distribution: VectorDistribution, seed: u64) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed);
+        // Centers depend only on the seed, so queries fall into the same clusters as points
+        let centers = match distribution {
+            VectorDistribution::Clustered => (0..CLUSTERS)
+                .map(|_| (0..dim).map(|_| rng.gen_range(-1.0..1.0)).collect())
+                .collect(),
+            VectorDistribution::Uniform | VectorDistribution::Normal => vec![],
+        };
+        Self {
+            rng,
+            dim,
+            distribution,
+            centers,
+        }
+    }
+
+    fn with_seed(&self, seed: u64) -> Self {
+        Self {
+            rng: StdRng::seed_from_u64(seed),
+            dim: self.dim,
+            distribution: self.distribution,
+            centers: self.centers.clone(),
+        }
+    }
+
+    fn next_vector(&mut self) -> Vec<VectorElementType> {
+        match self.distribution {
+            VectorDistribution::Uniform => (0..self.dim)
+                .map(|_| self.rng.gen_range(-1.0..1.0))
+                .collect(),
+            VectorDistribution::Normal => {
+                let normal = Normal::new(0.0, 1.0).unwrap();
+                (0..self.dim)
+                    .map(|_| normal.sample(&mut self.rng))
+                    .collect()
+            }
+            VectorDistribution::Clustered => {
+                let noise = Normal::new(0.0, 0.1).unwrap();
+                let center = &self.centers[self.rng.gen_range(0..self.centers.len())];
+                center
+                    .iter()
+                    .map(|value| value + noise.sample(&mut self.rng))
+                    .collect()
+            }
+        }
+    }
+}
+
+/// Running instance, accessed through the REST API
+struct RemoteTarget {
+    client: reqwest::Client,
+    uri: String,
+    api_key: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct CollectionInfoResponse {
+    result: CollectionInfoResult,
+}
+
+#[derive(Deserialize)]
+struct CollectionInfoResult {
+    status: CollectionStatus,
+}
+
+impl RemoteTarget {
+    async fn send(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<&impl Serialize>,
+    ) -> anyhow::Result<Vec<u8>> {
+        let url = format!("{}{path}", self.uri.trim_end_matches('/'));
+        let mut request = self.client.request(method, url);
+        if let Some(api_key) = &self.api_key {
+            request = request.header("api-key", api_key);
+        }
+        if let Some(body) = body {
+            request = request
+                .header(CONTENT_TYPE, "application/json")
+                .body(serde_json::to_vec(body)?);
+        }
+        let response = request.send().await?;
+        let status = response.status();
+        let bytes = response.bytes().await?;
+        if !status.is_success() {
+            bail!(
+                "Request {path} failed with status {status}: {}",
+                String::from_utf8_lossy(&bytes)
+            );
+        }
+        Ok(bytes.to_vec())
+    }
+
+    async fn delete_collection(&self, name: &str) -> anyhow::Result<()> {
+        self.send(Method::DELETE, &format!("/collections/{name}"), None::<&()>)
+            .await?;
+        Ok(())
+    }
+}
+
+/// Instance under the benchmark
+enum Target<'a> {
+    Remote(RemoteTarget),
+    Embedded(&'a QdrantEmbedded),
+}
+
+impl Target<'_> {
+    async fn recreate_collection(
+        &self,
+        name: &str,
+        create: CreateCollection,
+    ) -> anyhow::Result<()> {
+        match self {
+            Target::Remote(remote) => {
+                remote.delete_collection(name).await?;
+                remote
+                    .send(Method::PUT, &format!("/collections/{name}"), Some(&create))
+                    .await?;
+            }
+            Target::Embedded(qdrant) => {
+                qdrant.delete_collection(name).await?;
+                qdrant.create_collection(name, create).await?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn upsert(&self, name: &str, batch: Batch) -> anyhow::Result<()> {
+        let operation = PointInsertOperations::PointsBatch(batch);
+        match self {
+            Target::Remote(remote) => {
+                let path = format!("/collections/{name}/points?wait=true");
+                remote.send(Method::PUT, &path, Some(&operation)).await?;
+            }
+            Target::Embedded(qdrant) => {
+                qdrant
+                    .toc()
+                    .update(
+                        name,
+                        CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
+                            operation,
+                        )),
+                        None,
+                        true,
+                        None,
+                    )
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn search(&self, name: &str, request: SearchRequest) -> anyhow::Result<()> {
+        match self {
+            Target::Remote(remote) => {
+                let path = format!("/collections/{name}/points/search");
+                remote.send(Method::POST, &path, Some(&request)).await?;
+            }
+            Target::Embedded(qdrant) => {
+                qdrant
+                    .toc()
+                    .search(name, request, None, None, SearchPriority::default(), None)
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn status(&self, name: &str) -> anyhow::Result<CollectionStatus> {
+        match self {
+            Target::Remote(remote) => {
+                let response = remote
+                    .send(Method::GET, &format!("/collections/{name}"), None::<&()>)
+                    .await?;
+                let info: CollectionInfoResponse = serde_json::from_slice(&response)?;
+                Ok(info.result.status)
+            }
+            Target::Embedded(qdrant) => {
+                let collection = qdrant.toc().get_collection(name).await?;
+                Ok(collection.info(None).await?.status)
+            }
+        }
+    }
+}
+
+/// Upload synthetic points into a new collection and measure ingest and search throughput.
+///
+/// Either a running instance is benchmarked through its REST API, or an embedded storage,
+/// created in a temporary directory and removed afterwards.
+pub fn run_bench(args: BenchArgs, settings: &Settings) -> anyhow::Result<()> {
+    if args.dim == 0 || args.count == 0 || args.batch_size == 0 || args.parallel == 0 {
+        bail!("dim, count, batch size and parallel must be positive");
+    }
+    let runtime = Runtime::new()?;
+
+    match &args.uri {
+        Some(uri) => {
+            let target = Target::Remote(RemoteTarget {
+                client: reqwest::Client::new(),
+                uri: uri.clone(),
+                api_key: args.api_key.clone(),
+            });
+            runtime.block_on(bench(&target, &args))
+        }
+        None => {
+            let storage_path =
+                std::env::temp_dir().join(format!("qdrant-bench-{}", Uuid::new_v4()));
+            let qdrant = embedded_storage(&settings.storage, &storage_path)?;
+            let result = runtime.block_on(bench(&Target::Embedded(&qdrant), &args));
+            // Runtimes of the storage can't be dropped in async context
+            drop(qdrant);
+            // Collection is removed along with the whole storage
+            fs::remove_dir_all(&storage_path)
+                .with_context(|| format!("Failed to remove storage {}", storage_path.display()))?;
+            result
+        }
+    }
+}
+
+fn embedded_storage(config: &StorageConfig, storage_path: &Path) -> anyhow::Result<QdrantEmbedded> {
+    let mut config = config.clone();
+    config.storage_path = storage_path.to_string_lossy().into_owned();
+    config.snapshots_path = storage_path
+        .join("snapshots")
+        .to_string_lossy()
+        .into_owned();
+    log::info!(
+        "Benchmarking embedded storage in {}",
+        storage_path.display()
+    );
+
+    Ok(QdrantEmbedded::open(&config)?)
+}
+
+async fn bench(target: &Target<'_>, args: &BenchArgs) -> anyhow::Result<()> {
+    let name = args.collection.as_str();
+    let create = CreateCollection {
+        vectors: VectorParams {
+            size: NonZeroU64::new(args.dim as u64).unwrap(),
+            distance: Distance::Cosine,
+            hnsw_config: None,
+            quantization_config: None,
+            projection: None,
+        }
+        .into(),
+        shard_number: None,
+        replication_factor: None,
+        write_consistency_factor: None,
+        on_disk_payload: None,
+        hnsw_config: None,
+        wal_config: None,
+        optimizers_config: None,
+        init_from: None,
+        quantization_config: None,
+        sparse_vectors: None,
+    };
+    target.recreate_collection(name, create).await?;
+
+    let mut generator = VectorGenerator::new(args.dim, args.distribution, args.seed);
+    let mut queries = generator.with_seed(args.seed.wrapping_add(1));
+
+    let timer = Instant::now();
+    stream::iter((0..args.count).step_by(args.batch_size))
+        .map(|offset| {
+            let end = (offset + args.batch_size).min(args.count);
+            let batch = Batch {
+                ids: (offset as u64..end as u64).map(Into::into).collect(),
+                vectors: (offset..end)
+                    .map(|_| generator.next_vector())
+                    .collect::<Vec<_>>()
+                    .into(),
+                payloads: None,
+            };
+            target.upsert(name, batch)
+        })
+        .buffer_unordered(args.parallel)
+        .try_collect::<Vec<_>>()
+        .await?;
+    let upload_time = timer.elapsed();
+    println!(
+        "Upload: {} points in {:.2} s, {:.1} points/s",
+        args.count,
+        upload_time.as_secs_f64(),
+        args.count as f64 / upload_time.as_secs_f64(),
+    );
+
+    let timer = Instant::now();
+    while target.status(name).await? != CollectionStatus::Green {
+        tokio::time::sleep(STATUS_POLL_INTERVAL).await;
+    }
+    println!("Indexing: {:.2} s", timer.elapsed().as_secs_f64());
+
+    let timer = Instant::now();
+    let mut latencies: Vec<Duration> = stream::iter(0..args.searches)
+        .map(|_| {
+            let request = SearchRequest {
+                vector: queries.next_vector().into(),
+                filter: None,
+                params: None,
+                limit: args.limit,
+                offset: 0,
+                with_payload: None,
+                with_vector: None,
+                score_threshold: None,
+                with_highlights: None,
+                with_geo_distance: None,
+            };
+            async move {
+                let timer = Instant::now();
+                target.search(name, request).await?;
+                anyhow::Ok(timer.elapsed())
+            }
+        })
+        .buffer_unordered(args.parallel)
+        .try_collect()
+        .await?;
+    let search_time = timer.elapsed();
+    latencies.sort_unstable();
+    println!(
+        "Search: {} requests in {:.2} s, {:.1} requests/s, latency p50 {:.2} ms, p95 {:.2} ms, p99 {:.2} ms",
+        args.searches,
+        search_time.as_secs_f64(),
+        args.searches as f64 / search_time.as_secs_f64(),
+        percentile_ms(&latencies, 0.5),
+        percentile_ms(&latencies, 0.95),
+        percentile_ms(&latencies, 0.99),
+    );
+
+    match target {
+        Target::Remote(remote) => remote.delete_collection(name).await,
+        Target::Embedded(_) => Ok(()),
+    }
+}
+
+/// Percentile of the sorted latencies in milliseconds
+fn percentile_ms(sorted: &[Duration], percentile: f64) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    let index = ((sorted.len() - 1) as f64 * percentile).round() as usize;
+    sorted[index].as_micros() as f64 / 1000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vector_generator() {
+        for distribution in [
+            VectorDistribution::Uniform,
+            VectorDistribution::Normal,
+            VectorDistribution::Clustered,
+        ] {
+            let mut generator = VectorGenerator::new(16, distribution, 42);
+            let mut same_seed = VectorGenerator::new(16, distribution, 42);
+            let vector = generator.next_vector();
+            assert_eq!(vector.len(), 16);
+            assert_eq!(vector, same_seed.next_vector());
+            assert_ne!(vector, generator.next_vector());
+        }
+    }
+
+    #[test]
+    fn test_percentile() {
+        let latencies: Vec<_> = (1..=100).map(Duration::from_millis).collect();
+        assert_eq!(percentile_ms(&latencies, 0.5), 51.0);
+        assert_eq!(percentile_ms(&latencies, 0.99), 99.0);
+        assert_eq!(percentile_ms(&[], 0.5), 0.0);
+    }
+}
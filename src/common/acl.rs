@@ -0,0 +1,135 @@
+use std::collections::HashSet;
+
+use collection::operations::types::{
+    ArithmeticSearchRequest, CountRequest, DiscoverRequest, LookupLocation, QueryRequest,
+    RecommendExample, RecommendRequest,
+};
+pub use storage::content_manager::acl::{
+    readable_ids_filter, restrict_filter, ReadAccess, ACL_CLAIMS_HEADER,
+};
+use storage::content_manager::errors::StorageError;
+use storage::content_manager::toc::TableOfContent;
+
+/// Check that the caller can read all example points of the recommendation or discovery request,
+/// given by ids.
+///
+/// Examples are looked up by ids, so without this check the vectors of points outside of the
+/// ACL could leak through the scores of the recommended points.
+async fn check_examples<'a>(
+    toc: &TableOfContent,
+    collection_name: &str,
+    lookup_from: Option<&LookupLocation>,
+    examples: impl Iterator<Item = &'a RecommendExample>,
+    access: &ReadAccess,
+) -> Result<(), StorageError> {
+    // Raw vectors are given by the caller, so only examples given by ids are checked
+    let examples: HashSet<_> = examples.filter_map(RecommendExample::as_point_id).collect();
+    if examples.is_empty() {
+        return Ok(());
+    }
+    let lookup_collection = match lookup_from {
+        Some(lookup_from) => lookup_from.collection.as_str(),
+        None => collection_name,
+    };
+    let acl_filter = match toc.read_acl_filter(lookup_collection, access).await? {
+        Some(acl_filter) => acl_filter,
+        None => return Ok(()),
+    };
+    let examples: Vec<_> = examples.into_iter().collect();
+    let readable = toc
+        .count(
+            lookup_collection,
+            CountRequest {
+                filter: readable_ids_filter(&examples, Some(&acl_filter)),
+                exact: true,
+                planner_hint: None,
+                distinct: None,
+            },
+            None,
+        )
+        .await?;
+    if readable.count < examples.len() {
+        return Err(StorageError::NotFound {
+            description: format!(
+                "Not all example points of the request are found in {lookup_collection}"
+            ),
+        });
+    }
+    Ok(())
+}
+
+/// Restrict the recommendation to points, readable with the given access.
+/// Both the examples and the recommended points are checked against the ACL.
+pub async fn restrict_recommend_request(
+    toc: &TableOfContent,
+    collection_name: &str,
+    request: &mut RecommendRequest,
+    access: &ReadAccess,
+) -> Result<(), StorageError> {
+    check_examples(
+        toc,
+        collection_name,
+        request.lookup_from.as_ref(),
+        request.positive.iter().chain(&request.negative),
+        access,
+    )
+    .await?;
+    let acl_filter = toc.read_acl_filter(collection_name, access).await?;
+    request.filter = restrict_filter(request.filter.take(), acl_filter.as_ref());
+    Ok(())
+}
+
+/// Restrict the discovery to points, readable with the given access.
+/// Both the examples and the found points are checked against the ACL.
+pub async fn restrict_discover_request(
+    toc: &TableOfContent,
+    collection_name: &str,
+    request: &mut DiscoverRequest,
+    access: &ReadAccess,
+) -> Result<(), StorageError> {
+    check_examples(
+        toc,
+        collection_name,
+        request.lookup_from.as_ref(),
+        request.examples(),
+        access,
+    )
+    .await?;
+    let acl_filter = toc.read_acl_filter(collection_name, access).await?;
+    request.filter = restrict_filter(request.filter.take(), acl_filter.as_ref());
+    Ok(())
+}
+
+/// Restrict the vector arithmetic search to points, readable with the given access.
+/// Both the examples and the found points are checked against the ACL.
+pub async fn restrict_arithmetic_request(
+    toc: &TableOfContent,
+    collection_name: &str,
+    request: &mut ArithmeticSearchRequest,
+    access: &ReadAccess,
+) -> Result<(), StorageError> {
+    check_examples(
+        toc,
+        collection_name,
+        request.lookup_from.as_ref(),
+        request.terms.iter().map(|term| &term.example),
+        access,
+    )
+    .await?;
+    let acl_filter = toc.read_acl_filter(collection_name, access).await?;
+    request.filter = restrict_filter(request.filter.take(), acl_filter.as_ref());
+    Ok(())
+}
+
+/// Restrict the hybrid query to points, readable with the given access.
+/// Filter of the query applies to all prefetches, so it is enough to restrict it.
+pub async fn restrict_query_request(
+    toc: &TableOfContent,
+    collection_name: &str,
+    request: &mut QueryRequest,
+    access: &ReadAccess,
+) -> Result<(), StorageError> {
+    let acl_filter = toc.read_acl_filter(collection_name, access).await?;
+    request.filter = restrict_filter(request.filter.take(), acl_filter.as_ref());
+    Ok(())
+}
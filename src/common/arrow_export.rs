@@ -0,0 +1,334 @@
+use std::sync::Arc;
+
+use arrow_array::builder::{FixedSizeListBuilder, Float32Builder, StringBuilder};
+use arrow_array::{Array, ArrayRef, RecordBatch};
+use arrow_schema::{ArrowError, DataType, Field, Schema, SchemaRef};
+use collection::operations::id_range_ops::IdRange;
+use collection::operations::types::{Record, ScrollRequest, ScrollResult};
+use segment::data_types::vectors::{VectorStruct, DEFAULT_VECTOR_NAME};
+use segment::types::{Filter, PointIdType, WithPayloadInterface, WithVector};
+use serde::{Deserialize, Serialize};
+use storage::content_manager::errors::StorageError;
+use storage::content_manager::toc::TableOfContent;
+use validator::Validate;
+
+use crate::common::acl::ReadAccess;
+use crate::common::points::do_scroll_points;
+
+const DEFAULT_EXPORT_BATCH_SIZE: usize = 1000;
+
+pub const ID_COLUMN: &str = "id";
+pub const PAYLOAD_COLUMN: &str = "payload";
+/// Name of the column of the default unnamed vector
+pub const DEFAULT_VECTOR_COLUMN: &str = "vector";
+
+/// Columnar export of points, passed as JSON in an Arrow Flight ticket or descriptor command
+#[derive(Debug, Deserialize, Serialize, Validate, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct ExportRequest {
+    /// Name of the collection to export
+    pub collection: String,
+    /// Export only points which satisfy this conditions. Default: all points
+    pub filter: Option<Filter>,
+    /// Names of vectors to export. Default: all vectors
+    pub vectors: Option<Vec<String>>,
+    /// Whether to export payload as JSON strings. Default: true
+    pub with_payload: Option<bool>,
+    /// Number of points in each record batch. Default: 1000
+    #[validate(range(min = 1))]
+    pub batch_size: Option<usize>,
+    /// Export only points in this range of ids. Default: all points
+    pub id_range: Option<IdRange>,
+    /// Number of endpoints in the flight info, each exporting a non-overlapping range of ids,
+    /// so that the export can be read concurrently. Default: 1
+    #[validate(range(min = 1))]
+    pub partitions: Option<usize>,
+}
+
+/// Vector, exported as a column of fixed size lists
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExportedVector {
+    pub name: String,
+    pub dim: usize,
+}
+
+impl ExportedVector {
+    fn column_name(&self) -> &str {
+        if self.name == DEFAULT_VECTOR_NAME {
+            DEFAULT_VECTOR_COLUMN
+        } else {
+            &self.name
+        }
+    }
+}
+
+impl ExportRequest {
+    pub fn batch_size(&self) -> usize {
+        self.batch_size.unwrap_or(DEFAULT_EXPORT_BATCH_SIZE)
+    }
+
+    pub fn with_payload(&self) -> bool {
+        self.with_payload.unwrap_or(true)
+    }
+
+    /// Resolve names and dimensions of the exported vectors from the collection config
+    pub async fn exported_vectors(
+        &self,
+        toc: &TableOfContent,
+    ) -> Result<Vec<ExportedVector>, StorageError> {
+        let collection = toc.get_collection(&self.collection).await?;
+        let params = collection.state().await.config.params;
+        match &self.vectors {
+            None => Ok(params
+                .vectors
+                .params_iter()
+                .map(|(name, vector_params)| ExportedVector {
+                    name: name.to_string(),
+                    dim: vector_params.size.get() as usize,
+                })
+                .collect()),
+            Some(names) => names
+                .iter()
+                .map(|name| {
+                    let name = params.resolve_vector_name(name);
+                    let vector_params = params.get_vector_params(&name)?;
+                    Ok(ExportedVector {
+                        name,
+                        dim: vector_params.size.get() as usize,
+                    })
+                })
+                .collect(),
+        }
+    }
+
+    /// Offset of the first page
+    pub fn start_offset(&self) -> Option<PointIdType> {
+        self.id_range.and_then(|id_range| id_range.start)
+    }
+
+    /// Split the export into requests of non-overlapping ranges of ids, which can be read
+    /// concurrently. Collections with ACL are not split, as boundaries of ranges are point ids.
+    pub async fn partition(
+        &self,
+        toc: &TableOfContent,
+        access: &ReadAccess,
+    ) -> Result<Vec<ExportRequest>, StorageError> {
+        let partitions = self.partitions.unwrap_or(1);
+        if partitions <= 1
+            || self.id_range.is_some()
+            || toc
+                .read_acl_filter(&self.collection, access)
+                .await?
+                .is_some()
+        {
+            return Ok(vec![self.clone()]);
+        }
+        let collection = toc.get_collection(&self.collection).await?;
+        let id_ranges = collection.id_range_partitions(partitions).await?;
+        Ok(id_ranges
+            .into_iter()
+            .map(|id_range| ExportRequest {
+                id_range: Some(id_range),
+                partitions: None,
+                ..self.clone()
+            })
+            .collect())
+    }
+
+    /// Read a page of points, starting from `offset`, with the filter pushed down to segments.
+    /// Points outside of the collection ACL of the caller are skipped
+    pub async fn scroll_page(
+        &self,
+        toc: &TableOfContent,
+        vectors: &[ExportedVector],
+        offset: Option<PointIdType>,
+        access: &ReadAccess,
+    ) -> Result<ScrollResult, StorageError> {
+        let with_vector = if vectors.is_empty() {
+            WithVector::Bool(false)
+        } else {
+            WithVector::Selector(vectors.iter().map(|vector| vector.name.clone()).collect())
+        };
+        let request = ScrollRequest {
+            offset,
+            limit: Some(self.batch_size()),
+            filter: self.filter.clone(),
+            with_payload: Some(WithPayloadInterface::Bool(self.with_payload())),
+            with_vector,
+            ..Default::default()
+        };
+        let mut page = do_scroll_points(
+            toc,
+            &self.collection,
+            request,
+            None,
+            None,
+            None,
+            None,
+            None,
+            access,
+        )
+        .await?;
+        if let Some(end) = self.id_range.and_then(|id_range| id_range.end) {
+            // Points are scrolled in the order of ids
+            page.points.retain(|point| point.id < end);
+            if page.next_page_offset.map_or(false, |offset| offset >= end) {
+                page.next_page_offset = None;
+            }
+        }
+        Ok(page)
+    }
+}
+
+fn vector_builder(dim: usize, capacity: usize) -> FixedSizeListBuilder<Float32Builder> {
+    FixedSizeListBuilder::with_capacity(
+        Float32Builder::with_capacity(capacity * dim),
+        dim as i32,
+        capacity,
+    )
+}
+
+/// Schema of the exported record batches.
+///
+/// Point ids are exported as strings, so numeric and UUID ids share a single column.
+pub fn export_schema(vectors: &[ExportedVector], with_payload: bool) -> SchemaRef {
+    let mut fields = vec![Field::new(ID_COLUMN, DataType::Utf8, false)];
+    for vector in vectors {
+        // Data type of an empty array is used to match the type of the built columns exactly
+        let data_type = vector_builder(vector.dim, 0).finish().data_type().clone();
+        fields.push(Field::new(vector.column_name(), data_type, true));
+    }
+    if with_payload {
+        fields.push(Field::new(PAYLOAD_COLUMN, DataType::Utf8, true));
+    }
+    Arc::new(Schema::new(fields))
+}
+
+fn get_vector<'a>(record: &'a Record, name: &str) -> Option<&'a [f32]> {
+    match record.vector.as_ref()? {
+        VectorStruct::Single(vector) if name == DEFAULT_VECTOR_NAME => Some(vector.as_slice()),
+        VectorStruct::Single(_) => None,
+        VectorStruct::Multi(vectors) => vectors.get(name).map(|vector| vector.as_slice()),
+    }
+}
+
+/// Convert retrieved points into a record batch of the `export_schema`
+pub fn records_to_batch(
+    schema: SchemaRef,
+    vectors: &[ExportedVector],
+    with_payload: bool,
+    records: &[Record],
+) -> Result<RecordBatch, ArrowError> {
+    let mut ids = StringBuilder::with_capacity(records.len(), records.len() * 8);
+    for record in records {
+        ids.append_value(record.id.to_string());
+    }
+    let mut columns: Vec<ArrayRef> = vec![Arc::new(ids.finish())];
+
+    for vector in vectors {
+        let mut builder = vector_builder(vector.dim, records.len());
+        for record in records {
+            match get_vector(record, &vector.name) {
+                Some(values) if values.len() == vector.dim => {
+                    builder.values().append_slice(values);
+                    builder.append(true);
+                }
+                _ => {
+                    // Fixed size list requires `dim` child values even for null entries
+                    builder.values().append_nulls(vector.dim);
+                    builder.append(false);
+                }
+            }
+        }
+        columns.push(Arc::new(builder.finish()));
+    }
+
+    if with_payload {
+        let mut payloads = StringBuilder::new();
+        for record in records {
+            let payload = record
+                .payload
+                .as_ref()
+                .map(serde_json::to_string)
+                .transpose()
+                .map_err(|err| ArrowError::ExternalError(Box::new(err)))?;
+            payloads.append_option(payload);
+        }
+        columns.push(Arc::new(payloads.finish()));
+    }
+
+    RecordBatch::try_new(schema, columns)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use arrow_array::{FixedSizeListArray, StringArray};
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_records_to_batch() {
+        let vectors = vec![
+            ExportedVector {
+                name: "image".to_string(),
+                dim: 2,
+            },
+            ExportedVector {
+                name: "text".to_string(),
+                dim: 3,
+            },
+        ];
+        let records = vec![
+            Record {
+                id: 1.into(),
+                payload: Some(serde_json::from_value(json!({"color": "red"})).unwrap()),
+                vector: Some(VectorStruct::Multi(HashMap::from([
+                    ("image".to_string(), vec![0.1, 0.2]),
+                    ("text".to_string(), vec![0.3, 0.4, 0.5]),
+                ]))),
+                geo_distance: None,
+            },
+            Record {
+                id: 2.into(),
+                payload: None,
+                vector: Some(VectorStruct::Multi(HashMap::from([(
+                    "image".to_string(),
+                    vec![0.6, 0.7],
+                )]))),
+                geo_distance: None,
+            },
+        ];
+
+        let schema = export_schema(&vectors, true);
+        let batch = records_to_batch(schema.clone(), &vectors, true, &records).unwrap();
+        assert_eq!(batch.schema(), schema);
+        assert_eq!(batch.num_rows(), 2);
+        assert_eq!(batch.num_columns(), 4);
+
+        let ids = batch
+            .column(0)
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .unwrap();
+        assert_eq!(ids.value(1), "2");
+
+        let text = batch
+            .column(2)
+            .as_any()
+            .downcast_ref::<FixedSizeListArray>()
+            .unwrap();
+        assert!(text.is_valid(0));
+        assert!(text.is_null(1));
+
+        let payloads = batch
+            .column(3)
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .unwrap();
+        assert_eq!(payloads.value(0), r#"{"color":"red"}"#);
+        assert!(payloads.is_null(1));
+    }
+}
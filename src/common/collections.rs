@@ -5,17 +5,20 @@ use collection::operations::cluster_ops::{
     AbortTransferOperation, ClusterOperations, DropReplicaOperation, MoveShardOperation,
     ReplicateShardOperation,
 };
+use collection::operations::config_history_ops::ConfigChange;
 use collection::operations::snapshot_ops::SnapshotDescription;
 use collection::operations::types::{
-    AliasDescription, CollectionClusterInfo, CollectionInfo, CollectionsAliasesResponse,
+    CollectionClusterInfo, CollectionInfo, CollectionsAliasesResponse, FlushResult,
+    ListAliasesParams, WalTruncateResult,
 };
 use collection::shards::replica_set;
 use collection::shards::shard::ShardId;
 use collection::shards::transfer::shard_transfer::{ShardTransfer, ShardTransferKey};
 use itertools::Itertools;
+use storage::content_manager::acl::ReadAccess;
 use storage::content_manager::collection_meta_ops::ShardTransferOperations::{Abort, Start};
 use storage::content_manager::collection_meta_ops::{
-    CollectionMetaOperations, UpdateCollectionOperation,
+    CollectionMetaOperations, UpdateCollection, UpdateCollectionOperation,
 };
 use storage::content_manager::errors::StorageError;
 use storage::content_manager::toc::TableOfContent;
@@ -44,30 +47,31 @@ pub async fn do_list_collections(toc: &TableOfContent) -> CollectionsResponse {
 pub async fn do_list_collection_aliases(
     toc: &TableOfContent,
     collection_name: &str,
+    params: &ListAliasesParams,
 ) -> Result<CollectionsAliasesResponse, StorageError> {
-    let mut aliases: Vec<AliasDescription> = Default::default();
-    for alias in toc.collection_aliases(collection_name).await? {
-        aliases.push(AliasDescription {
-            alias_name: alias.to_string(),
-            collection_name: collection_name.to_string(),
-        });
-    }
-    Ok(CollectionsAliasesResponse { aliases })
+    let aliases = toc.collection_alias_descriptions(collection_name).await?;
+    Ok(CollectionsAliasesResponse {
+        aliases: params.apply(aliases),
+    })
 }
 
 pub async fn do_list_aliases(
     toc: &TableOfContent,
+    params: &ListAliasesParams,
 ) -> Result<CollectionsAliasesResponse, StorageError> {
     let aliases = toc.list_aliases().await?;
-    Ok(CollectionsAliasesResponse { aliases })
+    Ok(CollectionsAliasesResponse {
+        aliases: params.apply(aliases),
+    })
 }
 
 pub async fn do_list_snapshots(
     toc: &TableOfContent,
     collection_name: &str,
+    access: &ReadAccess,
 ) -> Result<Vec<SnapshotDescription>, StorageError> {
     Ok(toc
-        .get_collection(collection_name)
+        .get_collection_for_read(collection_name, access)
         .await?
         .list_snapshots()
         .await?)
@@ -76,8 +80,13 @@ pub async fn do_list_snapshots(
 pub async fn do_create_snapshot(
     dispatcher: &Dispatcher,
     collection_name: &str,
+    access: &ReadAccess,
     wait: bool,
 ) -> Result<SnapshotDescription, StorageError> {
+    // Snapshot contains all points of the collection, regardless of the ACL filter
+    dispatcher
+        .get_collection_for_read(collection_name, access)
+        .await?;
     let collection = collection_name.to_string();
     let dispatcher = dispatcher.clone();
     let snapshot = tokio::spawn(async move { dispatcher.create_snapshot(&collection).await });
@@ -95,9 +104,59 @@ pub async fn do_create_snapshot(
 pub async fn do_get_collection_cluster(
     toc: &TableOfContent,
     name: &str,
+    with_routing: bool,
 ) -> Result<CollectionClusterInfo, StorageError> {
     let collection = toc.get_collection(name).await?;
-    Ok(collection.cluster_info(toc.this_peer_id).await?)
+    Ok(collection
+        .cluster_info(toc.this_peer_id, with_routing)
+        .await?)
+}
+
+pub async fn do_flush_collection(
+    toc: &TableOfContent,
+    name: &str,
+) -> Result<FlushResult, StorageError> {
+    let collection = toc.get_collection(name).await?;
+    Ok(collection.flush().await?)
+}
+
+pub async fn do_truncate_collection_wal(
+    toc: &TableOfContent,
+    name: &str,
+) -> Result<WalTruncateResult, StorageError> {
+    let collection = toc.get_collection(name).await?;
+    Ok(collection.truncate_wal().await?)
+}
+
+pub async fn do_get_config_history(
+    toc: &TableOfContent,
+    name: &str,
+) -> Result<Vec<ConfigChange>, StorageError> {
+    let collection = toc.get_collection(name).await?;
+    Ok(collection.config_history())
+}
+
+/// Restore the collection config to the state before the given change.
+/// Rollback is recorded in the history as a regular config change.
+pub async fn do_rollback_config(
+    toc: &TableOfContent,
+    dispatcher: &Dispatcher,
+    name: String,
+    change_id: u64,
+    author: Option<String>,
+    wait_timeout: Option<Duration>,
+) -> Result<bool, StorageError> {
+    let collection = toc.get_collection(&name).await?;
+    let change = collection.config_change(change_id)?;
+    let update = UpdateCollection::rollback_to(&collection.config().await, &change.previous)?;
+    dispatcher
+        .submit_collection_meta_op(
+            CollectionMetaOperations::UpdateCollection(
+                UpdateCollectionOperation::new(name, update).with_author(author),
+            ),
+            wait_timeout,
+        )
+        .await
 }
 
 pub async fn do_update_collection_cluster(
@@ -149,6 +208,9 @@ pub async fn do_update_collection_cluster(
             // validate source peer exists
             validate_peer_exists(move_shard.from_peer_id)?;
 
+            // validate shards of the collection can be transferred
+            collection.config().await.check_quantized_only_transfer()?;
+
             // submit operation to consensus
             dispatcher
                 .submit_collection_meta_op(
@@ -182,6 +244,9 @@ pub async fn do_update_collection_cluster(
             // validate source peer exists
             validate_peer_exists(replicate_shard.from_peer_id)?;
 
+            // validate shards of the collection can be transferred
+            collection.config().await.check_quantized_only_transfer()?;
+
             // submit operation to consensus
             dispatcher
                 .submit_collection_meta_op(
@@ -0,0 +1,234 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use parking_lot::Mutex;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use storage::content_manager::errors::StorageError;
+use storage::content_manager::toc::TableOfContent;
+use validator::Validate;
+
+use crate::common::logger::CollectionLogger;
+use crate::common::telemetry_reporting::ReportingState;
+use crate::settings::Settings;
+
+/// Options, which are applied on reload without restart.
+/// Nested options are separated by `.`, all options under the listed ones are included.
+const RELOADABLE_OPTIONS: &[&str] = &[
+    "log_level",
+    "log_format",
+    "collection_log_levels",
+    "telemetry_disabled",
+    "telemetry",
+    "storage.performance.update_rate_limit",
+    "storage.performance.max_optimization_threads",
+];
+
+const LOGGER_OPTIONS: &[&str] = &["log_level", "log_format", "collection_log_levels"];
+
+const TELEMETRY_OPTIONS: &[&str] = &["telemetry_disabled", "telemetry"];
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+pub struct ConfigReloadResult {
+    /// Changed options, which are applied
+    pub applied: Vec<String>,
+    /// Changed options, which are applied only after restart of the service
+    pub restart_required: Vec<String>,
+}
+
+fn is_option_of(path: &str, options: &[&str]) -> bool {
+    options.iter().any(|option| {
+        path == *option
+            || path
+                .strip_prefix(option)
+                .map_or(false, |rest| rest.starts_with('.'))
+    })
+}
+
+/// Flatten nested configuration into values by their paths
+fn flatten_options(prefix: &str, value: &Value, options: &mut BTreeMap<String, Value>) {
+    match value {
+        Value::Object(object) => {
+            for (key, value) in object {
+                let path = if prefix.is_empty() {
+                    key.to_owned()
+                } else {
+                    format!("{prefix}.{key}")
+                };
+                flatten_options(&path, value, options);
+            }
+        }
+        _ => {
+            options.insert(prefix.to_owned(), value.clone());
+        }
+    }
+}
+
+/// Paths of options, which are added, removed or changed
+fn changed_options(old: &BTreeMap<String, Value>, new: &BTreeMap<String, Value>) -> Vec<String> {
+    let mut changed: Vec<_> = new
+        .iter()
+        .filter(|(path, value)| old.get(*path) != Some(*value))
+        .map(|(path, _)| path.clone())
+        .chain(old.keys().filter(|path| !new.contains_key(*path)).cloned())
+        .collect();
+    changed.sort();
+    changed
+}
+
+/// Re-reads the config and applies options, which can be changed without restart
+pub struct ConfigReloader {
+    config_path: Option<String>,
+    /// Options of the running service
+    options: Mutex<BTreeMap<String, Value>>,
+    toc: Arc<TableOfContent>,
+    logger: &'static CollectionLogger,
+    telemetry_reporting: Arc<ReportingState>,
+    /// Reporting is disabled by the command line argument, regardless of the config
+    telemetry_disabled_by_args: bool,
+}
+
+impl ConfigReloader {
+    pub fn new(
+        config_path: Option<String>,
+        raw_settings: &Value,
+        toc: Arc<TableOfContent>,
+        logger: &'static CollectionLogger,
+        telemetry_reporting: Arc<ReportingState>,
+        telemetry_disabled_by_args: bool,
+    ) -> Self {
+        let mut options = BTreeMap::new();
+        flatten_options("", raw_settings, &mut options);
+        ConfigReloader {
+            config_path,
+            options: Mutex::new(options),
+            toc,
+            logger,
+            telemetry_reporting,
+            telemetry_disabled_by_args,
+        }
+    }
+
+    pub fn reload(&self) -> Result<ConfigReloadResult, StorageError> {
+        let (settings, raw_settings) = Settings::load(self.config_path.clone())
+            .map_err(|err| StorageError::bad_input(&format!("Can't read config: {err}")))?;
+        settings
+            .validate()
+            .map_err(|err| StorageError::bad_input(&format!("Invalid config: {err}")))?;
+
+        let mut new_options = BTreeMap::new();
+        flatten_options("", &raw_settings, &mut new_options);
+
+        let mut options = self.options.lock();
+        let (applied, restart_required): (Vec<_>, Vec<_>) = changed_options(&options, &new_options)
+            .into_iter()
+            .partition(|path| is_option_of(path, RELOADABLE_OPTIONS));
+
+        if applied
+            .iter()
+            .any(|path| is_option_of(path, LOGGER_OPTIONS))
+        {
+            self.logger.reload(
+                &settings.log_level,
+                settings.log_format,
+                &settings.collection_log_levels,
+            );
+        }
+        if applied
+            .iter()
+            .any(|path| path == "storage.performance.update_rate_limit")
+        {
+            self.toc
+                .set_update_rate_limit(settings.storage.performance.update_rate_limit);
+        }
+        if applied
+            .iter()
+            .any(|path| path == "storage.performance.max_optimization_threads")
+        {
+            self.toc.set_max_optimization_threads(
+                settings.storage.performance.max_optimization_threads,
+            );
+        }
+        if applied
+            .iter()
+            .any(|path| is_option_of(path, TELEMETRY_OPTIONS))
+        {
+            self.telemetry_reporting
+                .set(settings.telemetry_reporting(self.telemetry_disabled_by_args));
+        }
+
+        // Only applied options are updated,
+        // so options, which require restart, are reported until the service is restarted
+        for path in &applied {
+            match new_options.remove(path) {
+                Some(value) => options.insert(path.clone(), value),
+                None => options.remove(path),
+            };
+        }
+
+        if !applied.is_empty() {
+            log::info!("Config reloaded, applied options: {}", applied.join(", "));
+        }
+        if !restart_required.is_empty() {
+            log::warn!(
+                "Changed options require restart: {}",
+                restart_required.join(", ")
+            );
+        }
+
+        Ok(ConfigReloadResult {
+            applied,
+            restart_required,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_changed_options() {
+        let flatten = |value: Value| {
+            let mut options = BTreeMap::new();
+            flatten_options("", &value, &mut options);
+            options
+        };
+        let old = flatten(json!({
+            "log_level": "INFO",
+            "collection_log_levels": { "a": "WARN" },
+            "storage": {
+                "performance": { "max_search_threads": 0, "max_optimization_threads": 1 },
+            },
+        }));
+        let new = flatten(json!({
+            "log_level": "DEBUG",
+            "collection_log_levels": { "b": "WARN" },
+            "storage": {
+                "performance": { "max_search_threads": 2, "max_optimization_threads": 2 },
+            },
+        }));
+
+        let changed = changed_options(&old, &new);
+        assert_eq!(
+            changed,
+            vec![
+                "collection_log_levels.a",
+                "collection_log_levels.b",
+                "log_level",
+                "storage.performance.max_optimization_threads",
+                "storage.performance.max_search_threads",
+            ]
+        );
+
+        let reloadable: Vec<_> = changed
+            .iter()
+            .filter(|path| is_option_of(path, RELOADABLE_OPTIONS))
+            .collect();
+        assert_eq!(reloadable.len(), 4);
+        assert!(!is_option_of("log_levels", RELOADABLE_OPTIONS));
+    }
+}
@@ -1,28 +1,29 @@
 use std::time::Duration;
 
+use serde_json::Value;
+
+use crate::common::telemetry_reporting::telemetry_url;
+
 pub struct ErrorReporter;
 
 impl ErrorReporter {
-    fn get_url() -> String {
-        if cfg!(debug_assertions) {
-            "https://staging-telemetry.qdrant.io".to_string()
-        } else {
-            "https://telemetry.qdrant.io".to_string()
-        }
+    /// Body of the crash report, exactly as it is sent
+    pub fn report_body(error: &str, reporting_id: &str, backtrace: Option<&str>) -> Value {
+        serde_json::json!({
+            "id": reporting_id,
+            "error": error,
+            "backtrace": backtrace.unwrap_or(""),
+        })
     }
 
     pub fn report(error: &str, reporting_id: &str, backtrace: Option<&str>) {
         let client = reqwest::blocking::Client::new();
 
-        let report = serde_json::json!({
-            "id": reporting_id,
-            "error": error,
-            "backtrace": backtrace.unwrap_or(""),
-        });
+        let report = Self::report_body(error, reporting_id, backtrace);
 
         let data = serde_json::to_string(&report).unwrap();
         let _resp = client
-            .post(Self::get_url())
+            .post(telemetry_url())
             .body(data)
             .header("Content-Type", "application/json")
             .timeout(Duration::from_secs(1))
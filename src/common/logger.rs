@@ -0,0 +1,209 @@
+use std::collections::HashMap;
+use std::io;
+use std::io::Write;
+
+use env_logger::fmt::Formatter;
+use log::{LevelFilter, Log, Metadata, Record};
+use parking_lot::RwLock;
+use segment::common::log_context::LogContext;
+use serde_json::{Map, Value};
+
+use crate::settings::LogFormat;
+
+struct LoggerState {
+    /// Logger with default filters
+    logger: env_logger::Logger,
+    /// Logger for lines, which already passed the log level of the collection
+    unfiltered_logger: env_logger::Logger,
+    collection_levels: HashMap<String, LevelFilter>,
+    format: LogFormat,
+}
+
+impl LoggerState {
+    /// Build loggers for the configuration. Returns descriptions of invalid log levels as well
+    fn new(
+        log_level: &str,
+        log_format: LogFormat,
+        collection_log_levels: &HashMap<String, String>,
+    ) -> (Self, Vec<String>) {
+        let is_info = log_level.to_ascii_uppercase() == "INFO";
+        let mut log_builder = logger_builder(log_format);
+
+        log_builder
+            // Parse user defined log level configuration
+            .parse_filters(log_level)
+            // h2 is very verbose and we have many network operations,
+            // so it is limited to only errors
+            .filter_module("h2", LevelFilter::Error)
+            .filter_module("tower", LevelFilter::Warn);
+
+        if is_info {
+            // Additionally filter verbose modules if no extended logging configuration is provided
+            log_builder
+                .filter_module("wal", LevelFilter::Warn)
+                .filter_module("raft::raft", LevelFilter::Warn);
+        };
+
+        let mut collection_levels = HashMap::new();
+        let mut invalid_levels = Vec::new();
+        for (collection, level) in collection_log_levels {
+            match level.parse() {
+                Ok(level) => {
+                    collection_levels.insert(collection.clone(), level);
+                }
+                Err(_) => invalid_levels.push(format!("{level} of collection {collection}")),
+            }
+        }
+
+        let state = LoggerState {
+            logger: log_builder.build(),
+            unfiltered_logger: logger_builder(log_format)
+                .filter_level(LevelFilter::Trace)
+                .build(),
+            collection_levels,
+            format: log_format,
+        };
+        (state, invalid_levels)
+    }
+
+    fn max_level(&self) -> LevelFilter {
+        self.collection_levels
+            .values()
+            .copied()
+            .fold(self.logger.filter(), LevelFilter::max)
+    }
+
+    fn collection_level(&self, context: &Option<LogContext>) -> Option<LevelFilter> {
+        let collection = context.as_ref()?.collection.as_ref()?;
+        self.collection_levels.get(&**collection).copied()
+    }
+}
+
+/// Logger, which marks log lines with fields of the current log context,
+/// and applies log levels of collections instead of the default filters.
+/// Configuration of the logger can be reloaded without restart.
+pub struct CollectionLogger {
+    state: RwLock<LoggerState>,
+}
+
+impl CollectionLogger {
+    /// Set up the global logger
+    pub fn install(
+        log_level: &str,
+        log_format: LogFormat,
+        collection_log_levels: &HashMap<String, String>,
+    ) -> &'static CollectionLogger {
+        let (state, invalid_levels) =
+            LoggerState::new(log_level, log_format, collection_log_levels);
+        let max_level = state.max_level();
+        let logger: &'static CollectionLogger = Box::leak(Box::new(CollectionLogger {
+            state: RwLock::new(state),
+        }));
+        log::set_logger(logger).expect("Logger is already set");
+        log::set_max_level(max_level);
+        warn_invalid_levels(invalid_levels);
+        logger
+    }
+
+    pub fn reload(
+        &self,
+        log_level: &str,
+        log_format: LogFormat,
+        collection_log_levels: &HashMap<String, String>,
+    ) {
+        let (state, invalid_levels) =
+            LoggerState::new(log_level, log_format, collection_log_levels);
+        log::set_max_level(state.max_level());
+        *self.state.write() = state;
+        warn_invalid_levels(invalid_levels);
+    }
+}
+
+fn warn_invalid_levels(invalid_levels: Vec<String>) {
+    for invalid_level in invalid_levels {
+        log::warn!("Invalid log level {invalid_level} is ignored");
+    }
+}
+
+impl Log for CollectionLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        let state = self.state.read();
+        match state.collection_level(&LogContext::current()) {
+            Some(level) => metadata.level() <= level,
+            None => state.logger.enabled(metadata),
+        }
+    }
+
+    fn log(&self, record: &Record) {
+        let state = self.state.read();
+        let context = LogContext::current();
+        let logger = match state.collection_level(&context) {
+            Some(level) if record.level() <= level => &state.unfiltered_logger,
+            Some(_) => return,
+            None => &state.logger,
+        };
+        match (state.format, context) {
+            // JSON lines get the fields of the context in the formatter
+            (LogFormat::Text, Some(context)) => logger.log(
+                &record
+                    .to_builder()
+                    .args(format_args!("{} {}", text_fields(&context), record.args()))
+                    .build(),
+            ),
+            _ => logger.log(record),
+        }
+    }
+
+    fn flush(&self) {
+        let state = self.state.read();
+        state.logger.flush();
+        state.unfiltered_logger.flush();
+    }
+}
+
+fn text_fields(context: &LogContext) -> String {
+    let mut fields = Vec::new();
+    if let Some(collection) = &context.collection {
+        fields.push(format!("collection={collection}"));
+    }
+    if let Some(shard_id) = context.shard_id {
+        fields.push(format!("shard={shard_id}"));
+    }
+    if let Some(request_id) = &context.request_id {
+        fields.push(format!("request_id={request_id}"));
+    }
+    fields.join(" ")
+}
+
+fn format_json(buf: &mut Formatter, record: &Record) -> io::Result<()> {
+    let mut line = Map::new();
+    line.insert(
+        "timestamp".to_string(),
+        buf.timestamp_millis().to_string().into(),
+    );
+    line.insert("level".to_string(), record.level().as_str().into());
+    line.insert("target".to_string(), record.target().into());
+    if let Some(context) = LogContext::current() {
+        if let Some(collection) = context.collection {
+            line.insert("collection".to_string(), (&*collection).into());
+        }
+        if let Some(shard_id) = context.shard_id {
+            line.insert("shard".to_string(), shard_id.into());
+        }
+        if let Some(request_id) = context.request_id {
+            line.insert("request_id".to_string(), (&*request_id).into());
+        }
+    }
+    line.insert("message".to_string(), record.args().to_string().into());
+    writeln!(buf, "{}", Value::Object(line))
+}
+
+fn logger_builder(format: LogFormat) -> env_logger::Builder {
+    let mut log_builder = env_logger::Builder::new();
+    match format {
+        // Timestamp in millis
+        LogFormat::Text => log_builder.format_timestamp_millis(),
+        LogFormat::Json => log_builder.format(format_json),
+    };
+    log_builder
+}
@@ -0,0 +1,62 @@
+use std::collections::BTreeMap;
+
+use schemars::JsonSchema;
+use segment::types::MemoryUsage;
+use serde::{Deserialize, Serialize};
+use storage::content_manager::acl::ReadAccess;
+use storage::content_manager::errors::StorageError;
+use storage::content_manager::toc::TableOfContent;
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+pub struct MemoryUsageReport {
+    /// Resident set size of the process in bytes, if available on the platform
+    pub rss_bytes: Option<usize>,
+    /// Estimated memory of all collections in bytes.
+    /// The rest of RSS is occupied by memory mapped files, request buffers, allocator caches, etc.
+    pub accounted_bytes: usize,
+    /// Estimated memory of local shards of each collection
+    pub collections: BTreeMap<String, MemoryUsage>,
+}
+
+/// Read resident set size of the current process
+#[cfg(target_os = "linux")]
+fn process_rss_bytes() -> Option<usize> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    let rss_kb = status
+        .lines()
+        .find_map(|line| line.strip_prefix("VmRSS:"))?
+        .trim()
+        .strip_suffix("kB")?
+        .trim()
+        .parse::<usize>()
+        .ok()?;
+    Some(rss_kb * 1024)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn process_rss_bytes() -> Option<usize> {
+    None
+}
+
+pub async fn do_get_memory_usage(
+    toc: &TableOfContent,
+    access: &ReadAccess,
+) -> Result<MemoryUsageReport, StorageError> {
+    access.check_unrestricted()?;
+    let mut collections = BTreeMap::new();
+    for collection_name in toc.all_collections().await {
+        let collection = match toc.get_collection(&collection_name).await {
+            Ok(collection) => collection,
+            // Collection was removed in the meantime
+            Err(StorageError::NotFound { .. }) => continue,
+            Err(err) => return Err(err),
+        };
+        collections.insert(collection_name, collection.memory_usage().await?);
+    }
+
+    Ok(MemoryUsageReport {
+        rss_bytes: process_rss_bytes(),
+        accounted_bytes: collections.values().map(MemoryUsage::total).sum(),
+        collections,
+    })
+}
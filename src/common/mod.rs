@@ -1,13 +1,24 @@
 #[allow(dead_code)] // May contain functions used in different binaries. Not actually dead
+pub mod acl;
+pub mod arrow_export;
+#[allow(dead_code)] // May contain functions used in different binaries. Not actually dead
 pub mod collections;
 #[allow(dead_code)] // May contain functions used in different binaries. Not actually dead
+pub mod config_reload;
+#[allow(dead_code)] // May contain functions used in different binaries. Not actually dead
 pub mod error_reporting;
 #[allow(dead_code)] // May contain functions used in different binaries. Not actually dead
 pub mod helpers;
+#[allow(dead_code)] // May contain functions used in different binaries. Not actually dead
+pub mod logger;
+#[allow(dead_code)] // May contain functions used in different binaries. Not actually dead
+pub mod memory_usage;
 pub mod metrics;
 #[allow(dead_code)] // May contain functions used in different binaries. Not actually dead
 pub mod points;
 #[allow(dead_code)] // May contain functions used in different binaries. Not actually dead
+pub mod profiling;
+#[allow(dead_code)] // May contain functions used in different binaries. Not actually dead
 pub mod telemetry;
 pub mod telemetry_ops;
 #[allow(dead_code)] // May contain functions used in different binaries. Not actually dead
@@ -1,21 +1,31 @@
-use collection::operations::consistency_params::ReadConsistency;
+use std::collections::HashSet;
+use std::time::Duration;
+
+use collection::operations::consistency_params::{ConsistencyToken, ReadConsistency};
+use collection::operations::filter_validation::FilterValidation;
 use collection::operations::payload_ops::{DeletePayload, PayloadOps, SetPayload};
 use collection::operations::point_ops::{
     PointInsertOperations, PointOperations, PointsSelector, WriteOrdering,
 };
 use collection::operations::types::{
-    CountRequest, CountResult, PointRequest, Record, ScrollRequest, ScrollResult, SearchRequest,
-    SearchRequestBatch, UpdateResult,
+    ArithmeticSearchRequest, CountRequest, CountResult, GroupsResult, PointExistence, PointRequest,
+    PointsExistRequest, Record, ScrollRequest, ScrollResult, SearchGroupsRequest, SearchRequest,
+    SearchRequestBatch, UpdateResult, WithLookup,
 };
 use collection::operations::{CollectionUpdateOperations, CreateIndex, FieldIndexOperations};
 use collection::shards::shard::ShardId;
 use schemars::JsonSchema;
-use segment::types::{PayloadFieldSchema, ScoredPoint};
+use segment::types::{Filter, PayloadFieldSchema, ScoredPoint, WithPayloadInterface};
 use serde::{Deserialize, Serialize};
 use storage::content_manager::errors::StorageError;
+use storage::content_manager::search_queue::SearchPriority;
 use storage::content_manager::toc::TableOfContent;
 use validator::Validate;
 
+use crate::common::acl::{
+    readable_ids_filter, restrict_arithmetic_request, restrict_filter, ReadAccess,
+};
+
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Validate)]
 pub struct CreateFieldIndex {
     pub field_name: String,
@@ -23,24 +33,57 @@ pub struct CreateFieldIndex {
     pub field_schema: Option<PayloadFieldSchema>,
 }
 
+/// Apply point operation to the selected shard, if it is a request from another peer,
+/// or to the shards, which store affected points, if it is a request from a client.
+///
+/// `shard_key` - shard, which the client expects to store all affected points
+async fn update_points(
+    toc: &TableOfContent,
+    collection_name: &str,
+    operation: CollectionUpdateOperations,
+    shard_selection: Option<ShardId>,
+    shard_key: Option<ShardId>,
+    wait: bool,
+    ordering: Option<WriteOrdering>,
+) -> Result<UpdateResult, StorageError> {
+    match shard_selection {
+        Some(_) => {
+            toc.update(collection_name, operation, shard_selection, wait, ordering)
+                .await
+        }
+        None => {
+            toc.update_with_shard_key(collection_name, operation, shard_key, wait, ordering)
+                .await
+        }
+    }
+}
+
 pub async fn do_upsert_points(
     toc: &TableOfContent,
     collection_name: &str,
-    operation: PointInsertOperations,
+    mut operation: PointInsertOperations,
     shard_selection: Option<ShardId>,
+    shard_key: Option<ShardId>,
     wait: bool,
-    ordering: WriteOrdering,
+    ordering: Option<WriteOrdering>,
 ) -> Result<UpdateResult, StorageError> {
+    let generated_ids = operation.generate_missing_ids();
     let collection_operation =
         CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(operation));
-    toc.update(
+    let mut result = update_points(
+        toc,
         collection_name,
         collection_operation,
         shard_selection,
+        shard_key,
         wait,
         ordering,
     )
-    .await
+    .await?;
+    if !generated_ids.is_empty() {
+        result.generated_ids = Some(generated_ids);
+    }
+    Ok(result)
 }
 
 pub async fn do_delete_points(
@@ -48,8 +91,9 @@ pub async fn do_delete_points(
     collection_name: &str,
     points: PointsSelector,
     shard_selection: Option<ShardId>,
+    shard_key: Option<ShardId>,
     wait: bool,
-    ordering: WriteOrdering,
+    ordering: Option<WriteOrdering>,
 ) -> Result<UpdateResult, StorageError> {
     let point_operation = match points {
         PointsSelector::PointIdsSelector(points) => {
@@ -60,10 +104,12 @@ pub async fn do_delete_points(
         }
     };
     let collection_operation = CollectionUpdateOperations::PointOperation(point_operation);
-    toc.update(
+    update_points(
+        toc,
         collection_name,
         collection_operation,
         shard_selection,
+        shard_key,
         wait,
         ordering,
     )
@@ -76,7 +122,7 @@ pub async fn do_set_payload(
     operation: SetPayload,
     shard_selection: Option<ShardId>,
     wait: bool,
-    ordering: WriteOrdering,
+    ordering: Option<WriteOrdering>,
 ) -> Result<UpdateResult, StorageError> {
     let collection_operation =
         CollectionUpdateOperations::PayloadOperation(PayloadOps::SetPayload(operation));
@@ -96,7 +142,7 @@ pub async fn do_overwrite_payload(
     operation: SetPayload,
     shard_selection: Option<ShardId>,
     wait: bool,
-    ordering: WriteOrdering,
+    ordering: Option<WriteOrdering>,
 ) -> Result<UpdateResult, StorageError> {
     let collection_operation =
         CollectionUpdateOperations::PayloadOperation(PayloadOps::OverwritePayload(operation));
@@ -116,7 +162,7 @@ pub async fn do_delete_payload(
     operation: DeletePayload,
     shard_selection: Option<ShardId>,
     wait: bool,
-    ordering: WriteOrdering,
+    ordering: Option<WriteOrdering>,
 ) -> Result<UpdateResult, StorageError> {
     let collection_operation =
         CollectionUpdateOperations::PayloadOperation(PayloadOps::DeletePayload(operation));
@@ -136,7 +182,7 @@ pub async fn do_clear_payload(
     points: PointsSelector,
     shard_selection: Option<ShardId>,
     wait: bool,
-    ordering: WriteOrdering,
+    ordering: Option<WriteOrdering>,
 ) -> Result<UpdateResult, StorageError> {
     let points_operation = match points {
         PointsSelector::PointIdsSelector(points) => PayloadOps::ClearPayload {
@@ -164,7 +210,7 @@ pub async fn do_create_index(
     operation: CreateFieldIndex,
     shard_selection: Option<ShardId>,
     wait: bool,
-    ordering: WriteOrdering,
+    ordering: Option<WriteOrdering>,
 ) -> Result<UpdateResult, StorageError> {
     let collection_operation = CollectionUpdateOperations::FieldIndexOperation(
         FieldIndexOperations::CreateIndex(CreateIndex {
@@ -188,7 +234,7 @@ pub async fn do_delete_index(
     index_name: String,
     shard_selection: Option<ShardId>,
     wait: bool,
-    ordering: WriteOrdering,
+    ordering: Option<WriteOrdering>,
 ) -> Result<UpdateResult, StorageError> {
     let collection_operation = CollectionUpdateOperations::FieldIndexOperation(
         FieldIndexOperations::DeleteIndex(index_name),
@@ -203,55 +249,434 @@ pub async fn do_delete_index(
     .await
 }
 
+/// Check filters of a client request against payload keys of the collection.
+/// Requests of other peers, which select the shard, were already checked by the peer,
+/// which received them from the client.
+pub async fn check_request_filters<'a>(
+    toc: &TableOfContent,
+    collection_name: &str,
+    filters: impl IntoIterator<Item = Option<&'a Filter>>,
+    shard_selection: Option<ShardId>,
+    filter_validation: Option<FilterValidation>,
+) -> Result<(), StorageError> {
+    if shard_selection.is_some() {
+        return Ok(());
+    }
+    for filter in filters {
+        toc.check_filter(collection_name, filter, filter_validation)
+            .await?;
+    }
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
 pub async fn do_search_points(
     toc: &TableOfContent,
     collection_name: &str,
-    request: SearchRequest,
+    mut request: SearchRequest,
     read_consistency: Option<ReadConsistency>,
     shard_selection: Option<ShardId>,
+    shard_key: Option<ShardId>,
+    priority: SearchPriority,
+    timeout: Option<Duration>,
+    filter_validation: Option<FilterValidation>,
+    access: &ReadAccess,
 ) -> Result<Vec<ScoredPoint>, StorageError> {
-    toc.search(collection_name, request, read_consistency, shard_selection)
-        .await
+    check_request_filters(
+        toc,
+        collection_name,
+        [request.filter.as_ref()],
+        shard_selection,
+        filter_validation,
+    )
+    .await?;
+    let acl_filter = toc.read_acl_filter(collection_name, access).await?;
+    request.filter = restrict_filter(request.filter, acl_filter.as_ref());
+    match shard_selection {
+        Some(_) => {
+            toc.search(
+                collection_name,
+                request,
+                read_consistency,
+                shard_selection,
+                priority,
+                timeout,
+            )
+            .await
+        }
+        None => {
+            toc.search_with_shard_key(
+                collection_name,
+                request,
+                read_consistency,
+                shard_key,
+                priority,
+                timeout,
+            )
+            .await
+        }
+    }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub async fn do_search_batch_points(
     toc: &TableOfContent,
     collection_name: &str,
-    request: SearchRequestBatch,
+    mut request: SearchRequestBatch,
     read_consistency: Option<ReadConsistency>,
     shard_selection: Option<ShardId>,
+    shard_key: Option<ShardId>,
+    priority: SearchPriority,
+    timeout: Option<Duration>,
+    filter_validation: Option<FilterValidation>,
+    access: &ReadAccess,
 ) -> Result<Vec<Vec<ScoredPoint>>, StorageError> {
-    toc.search_batch(collection_name, request, read_consistency, shard_selection)
+    check_request_filters(
+        toc,
+        collection_name,
+        request.searches.iter().map(|search| search.filter.as_ref()),
+        shard_selection,
+        filter_validation,
+    )
+    .await?;
+    let acl_filter = toc.read_acl_filter(collection_name, access).await?;
+    for search in &mut request.searches {
+        search.filter = restrict_filter(search.filter.take(), acl_filter.as_ref());
+    }
+    match shard_selection {
+        Some(_) => {
+            toc.search_batch(
+                collection_name,
+                request,
+                read_consistency,
+                shard_selection,
+                priority,
+                timeout,
+            )
+            .await
+        }
+        None => {
+            toc.search_batch_with_shard_key(
+                collection_name,
+                request,
+                read_consistency,
+                shard_key,
+                priority,
+                timeout,
+            )
+            .await
+        }
+    }
+}
+
+pub async fn do_search_arithmetic_points(
+    toc: &TableOfContent,
+    collection_name: &str,
+    mut request: ArithmeticSearchRequest,
+    read_consistency: Option<ReadConsistency>,
+    priority: SearchPriority,
+    filter_validation: Option<FilterValidation>,
+    access: &ReadAccess,
+) -> Result<Vec<ScoredPoint>, StorageError> {
+    check_request_filters(
+        toc,
+        collection_name,
+        [request.filter.as_ref()],
+        None,
+        filter_validation,
+    )
+    .await?;
+    restrict_arithmetic_request(toc, collection_name, &mut request, access).await?;
+    toc.search_arithmetic(collection_name, request, read_consistency, priority)
         .await
 }
 
+pub async fn do_search_point_groups(
+    toc: &TableOfContent,
+    collection_name: &str,
+    mut request: SearchGroupsRequest,
+    read_consistency: Option<ReadConsistency>,
+    priority: SearchPriority,
+    filter_validation: Option<FilterValidation>,
+    access: &ReadAccess,
+) -> Result<GroupsResult, StorageError> {
+    check_request_filters(
+        toc,
+        collection_name,
+        [request.filter.as_ref()],
+        None,
+        filter_validation,
+    )
+    .await?;
+    let acl_filter = toc.read_acl_filter(collection_name, access).await?;
+    request.filter = restrict_filter(request.filter, acl_filter.as_ref());
+    let lookup_collection = request
+        .with_lookup
+        .clone()
+        .map(|with_lookup| WithLookup::from(with_lookup).collection_name);
+    let mut result = toc
+        .search_groups(collection_name, request, read_consistency, priority)
+        .await?;
+
+    // Looked up points are read by ids, so points outside of the ACL of the lookup collection
+    // are dropped afterwards, as if they were not found
+    let lookup_collection = match lookup_collection {
+        Some(lookup_collection) => lookup_collection,
+        None => return Ok(result),
+    };
+    let lookup_acl_filter = match toc.read_acl_filter(&lookup_collection, access).await? {
+        Some(lookup_acl_filter) => lookup_acl_filter,
+        None => return Ok(result),
+    };
+    let lookup_ids: Vec<_> = result
+        .groups
+        .iter()
+        .filter_map(|group| group.lookup.as_ref().map(|lookup| lookup.id))
+        .collect();
+    if lookup_ids.is_empty() {
+        return Ok(result);
+    }
+    let scroll_request = ScrollRequest {
+        limit: Some(lookup_ids.len()),
+        filter: readable_ids_filter(&lookup_ids, Some(&lookup_acl_filter)),
+        with_payload: Some(WithPayloadInterface::Bool(false)),
+        with_vector: false.into(),
+        ..Default::default()
+    };
+    let readable_ids: HashSet<_> = toc
+        .scroll(
+            &lookup_collection,
+            scroll_request,
+            read_consistency,
+            None,
+            None,
+        )
+        .await?
+        .points
+        .into_iter()
+        .map(|point| point.id)
+        .collect();
+    for group in &mut result.groups {
+        if let Some(lookup) = &group.lookup {
+            if !readable_ids.contains(&lookup.id) {
+                group.lookup = None;
+            }
+        }
+    }
+    Ok(result)
+}
+
 pub async fn do_count_points(
     toc: &TableOfContent,
     collection_name: &str,
-    request: CountRequest,
+    mut request: CountRequest,
     shard_selection: Option<ShardId>,
+    shard_key: Option<ShardId>,
+    filter_validation: Option<FilterValidation>,
+    access: &ReadAccess,
 ) -> Result<CountResult, StorageError> {
-    toc.count(collection_name, request, shard_selection).await
+    check_request_filters(
+        toc,
+        collection_name,
+        [request.filter.as_ref()],
+        shard_selection,
+        filter_validation,
+    )
+    .await?;
+    let acl_filter = toc.read_acl_filter(collection_name, access).await?;
+    request.filter = restrict_filter(request.filter, acl_filter.as_ref());
+    match shard_selection {
+        Some(_) => toc.count(collection_name, request, shard_selection).await,
+        None => {
+            toc.count_with_shard_key(collection_name, request, shard_key)
+                .await
+        }
+    }
 }
 
+/// Wait until the writes of the consistency token are applied, if the read has one
+pub async fn do_wait_for_writes(
+    toc: &TableOfContent,
+    collection_name: &str,
+    consistency_token: Option<&ConsistencyToken>,
+) -> Result<(), StorageError> {
+    match consistency_token {
+        Some(token) => toc.wait_for_writes(collection_name, token).await,
+        None => Ok(()),
+    }
+}
+
+/// Retrieve points by ids.
+///
+/// If the collection restricts reads with ACL, points are scrolled with the ids and the ACL
+/// in the filter instead, so points outside of the ACL are reported as missing.
 pub async fn do_get_points(
     toc: &TableOfContent,
     collection_name: &str,
     request: PointRequest,
     read_consistency: Option<ReadConsistency>,
     shard_selection: Option<ShardId>,
+    shard_key: Option<ShardId>,
+    access: &ReadAccess,
 ) -> Result<Vec<Record>, StorageError> {
-    toc.retrieve(collection_name, request, read_consistency, shard_selection)
-        .await
+    let acl_filter = match toc.read_acl_filter(collection_name, access).await? {
+        Some(acl_filter) => acl_filter,
+        None => {
+            return match shard_selection {
+                Some(_) => {
+                    toc.retrieve(collection_name, request, read_consistency, shard_selection)
+                        .await
+                }
+                None => {
+                    toc.retrieve_with_shard_key(
+                        collection_name,
+                        request,
+                        read_consistency,
+                        shard_key,
+                    )
+                    .await
+                }
+            }
+        }
+    };
+    if request.ids.is_empty() {
+        return Ok(vec![]);
+    }
+    let scroll_request = ScrollRequest {
+        limit: Some(request.ids.len()),
+        filter: readable_ids_filter(&request.ids, Some(&acl_filter)),
+        with_payload: Some(
+            request
+                .with_payload
+                .unwrap_or(WithPayloadInterface::Bool(true)),
+        ),
+        with_vector: request.with_vector,
+        ..Default::default()
+    };
+    scroll_points(
+        toc,
+        collection_name,
+        scroll_request,
+        read_consistency,
+        shard_selection,
+        shard_key,
+        None,
+    )
+    .await
+    .map(|result| result.points)
 }
 
+/// Check which of the points exist, along with their versions, without reading payload and vectors.
+///
+/// If the collection restricts reads with ACL, points outside of the ACL are reported as missing.
+pub async fn do_check_points_exist(
+    toc: &TableOfContent,
+    collection_name: &str,
+    request: PointsExistRequest,
+    shard_selection: Option<ShardId>,
+    access: &ReadAccess,
+) -> Result<Vec<PointExistence>, StorageError> {
+    let mut versions = toc
+        .point_versions(collection_name, &request.ids, shard_selection)
+        .await?;
+    if let Some(acl_filter) = toc.read_acl_filter(collection_name, access).await? {
+        if !versions.is_empty() {
+            let existing_ids: Vec<_> = versions.keys().copied().collect();
+            let scroll_request = ScrollRequest {
+                limit: Some(existing_ids.len()),
+                filter: readable_ids_filter(&existing_ids, Some(&acl_filter)),
+                with_payload: Some(WithPayloadInterface::Bool(false)),
+                with_vector: false.into(),
+                ..Default::default()
+            };
+            let readable_ids: HashSet<_> = toc
+                .scroll(collection_name, scroll_request, None, shard_selection, None)
+                .await?
+                .points
+                .into_iter()
+                .map(|point| point.id)
+                .collect();
+            versions.retain(|id, _| readable_ids.contains(id));
+        }
+    }
+    Ok(request
+        .ids
+        .into_iter()
+        .map(|id| PointExistence {
+            id,
+            exists: versions.contains_key(&id),
+            version: versions.get(&id).copied(),
+        })
+        .collect())
+}
+
+#[allow(clippy::too_many_arguments)]
 pub async fn do_scroll_points(
+    toc: &TableOfContent,
+    collection_name: &str,
+    mut request: ScrollRequest,
+    read_consistency: Option<ReadConsistency>,
+    shard_selection: Option<ShardId>,
+    shard_key: Option<ShardId>,
+    timeout: Option<Duration>,
+    filter_validation: Option<FilterValidation>,
+    access: &ReadAccess,
+) -> Result<ScrollResult, StorageError> {
+    check_request_filters(
+        toc,
+        collection_name,
+        [request.filter.as_ref()],
+        shard_selection,
+        filter_validation,
+    )
+    .await?;
+    let acl_filter = toc.read_acl_filter(collection_name, access).await?;
+    request.filter = restrict_filter(request.filter, acl_filter.as_ref());
+    scroll_points(
+        toc,
+        collection_name,
+        request,
+        read_consistency,
+        shard_selection,
+        shard_key,
+        timeout,
+    )
+    .await
+}
+
+/// Scroll the selected shard, if it is a request from another peer,
+/// or the shards of the collection, if it is a request from a client.
+///
+/// `shard_key` - shard, which the client expects to store all scrolled points
+async fn scroll_points(
     toc: &TableOfContent,
     collection_name: &str,
     request: ScrollRequest,
     read_consistency: Option<ReadConsistency>,
     shard_selection: Option<ShardId>,
+    shard_key: Option<ShardId>,
+    timeout: Option<Duration>,
 ) -> Result<ScrollResult, StorageError> {
-    toc.scroll(collection_name, request, read_consistency, shard_selection)
-        .await
+    match shard_selection {
+        Some(_) => {
+            toc.scroll(
+                collection_name,
+                request,
+                read_consistency,
+                shard_selection,
+                timeout,
+            )
+            .await
+        }
+        None => {
+            toc.scroll_with_shard_key(
+                collection_name,
+                request,
+                read_consistency,
+                shard_key,
+                timeout,
+            )
+            .await
+        }
+    }
 }
@@ -0,0 +1,98 @@
+use std::time::Duration;
+
+use serde::Deserialize;
+use storage::content_manager::errors::StorageError;
+
+/// Default number of stack samples per second
+pub const DEFAULT_PROFILING_FREQUENCY: i32 = 100;
+
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ProfileFormat {
+    /// Folded stacks, one line per unique stack, as consumed by `flamegraph.pl` or `inferno`
+    #[default]
+    Folded,
+    /// Protobuf encoded profile, as consumed by `go tool pprof`
+    Pprof,
+    /// Rendered flamegraph in SVG format
+    Flamegraph,
+}
+
+impl ProfileFormat {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            ProfileFormat::Folded => "text/plain",
+            ProfileFormat::Pprof => "application/octet-stream",
+            ProfileFormat::Flamegraph => "image/svg+xml",
+        }
+    }
+}
+
+/// Sample stacks of all threads of the process for the given duration.
+/// Only one profiling session may run at a time.
+#[cfg(target_os = "linux")]
+pub async fn profile_cpu(
+    duration: Duration,
+    frequency: i32,
+    format: ProfileFormat,
+) -> Result<Vec<u8>, StorageError> {
+    use pprof::protos::Message;
+
+    let guard = pprof::ProfilerGuardBuilder::default()
+        .frequency(frequency)
+        .blocklist(&["libc", "libgcc", "pthread", "vdso"])
+        .build()
+        .map_err(|err| StorageError::BadRequest {
+            description: format!("Can't start profiling: {err}"),
+        })?;
+
+    tokio::time::sleep(duration).await;
+
+    let report = guard
+        .report()
+        .build()
+        .map_err(|err| StorageError::service_error(format!("Can't build profile: {err}")))?;
+    drop(guard);
+
+    let mut output = Vec::new();
+    match format {
+        ProfileFormat::Folded => {
+            for (frames, count) in &report.data {
+                let mut stack = vec![frames.thread_name_or_id()];
+                stack.extend(
+                    frames
+                        .frames
+                        .iter()
+                        .rev()
+                        .flat_map(|frame| frame.iter().rev().map(|symbol| symbol.to_string())),
+                );
+                output.extend(format!("{} {count}\n", stack.join(";")).into_bytes());
+            }
+        }
+        ProfileFormat::Pprof => {
+            let profile = report.pprof().map_err(|err| {
+                StorageError::service_error(format!("Can't encode profile: {err}"))
+            })?;
+            profile.encode(&mut output).map_err(|err| {
+                StorageError::service_error(format!("Can't encode profile: {err}"))
+            })?;
+        }
+        ProfileFormat::Flamegraph => {
+            report.flamegraph(&mut output).map_err(|err| {
+                StorageError::service_error(format!("Can't render flamegraph: {err}"))
+            })?;
+        }
+    }
+    Ok(output)
+}
+
+#[cfg(not(target_os = "linux"))]
+pub async fn profile_cpu(
+    _duration: Duration,
+    _frequency: i32,
+    _format: ProfileFormat,
+) -> Result<Vec<u8>, StorageError> {
+    Err(StorageError::BadRequest {
+        description: "Profiling is only supported on Linux".to_string(),
+    })
+}
@@ -3,6 +3,7 @@ use std::path::Path;
 use chrono::{DateTime, SubsecRound, Utc};
 use schemars::JsonSchema;
 use segment::common::anonymize::Anonymize;
+use segment::common::memory::get_total_memory;
 use serde::{Deserialize, Serialize};
 
 pub struct AppBuildTelemetryCollector {
@@ -113,7 +114,8 @@ fn get_system_data() -> RunningEnvironmentTelemetry {
         distribution_version,
         is_docker: cfg!(unix) && Path::new("/.dockerenv").exists(),
         cores: sys_info::cpu_num().ok().map(|x| x as usize),
-        ram_size: sys_info::mem_info().ok().map(|x| x.total as usize),
+        // In KiloBytes, as reported by `sys_info`
+        ram_size: get_total_memory().map(|bytes| bytes / 1024),
         disk_size: sys_info::disk_info().ok().map(|x| x.total as usize),
         cpu_flags: cpu_flags.join(","),
     }
@@ -1,14 +1,89 @@
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
 
+use schemars::JsonSchema;
 use segment::common::anonymize::Anonymize;
+use serde::Serialize;
+use serde_json::Value;
 use tokio::sync::Mutex;
 
-use crate::common::telemetry::TelemetryCollector;
+use crate::common::error_reporting::ErrorReporter;
+use crate::common::telemetry::{TelemetryCollector, TelemetryData};
+use crate::settings::TelemetryConfig;
 
 const DETAIL_LEVEL: usize = 5;
 const REPORTING_INTERVAL: Duration = Duration::from_secs(60 * 60); // One hour
 
+pub fn telemetry_url() -> String {
+    if cfg!(debug_assertions) {
+        "https://staging-telemetry.qdrant.io".to_string()
+    } else {
+        "https://telemetry.qdrant.io".to_string()
+    }
+}
+
+/// Categories of telemetry, which are reported at the moment.
+/// Shared by the reporters, so categories can be enabled or disabled on config reload.
+pub struct ReportingState {
+    crash_reports: AtomicBool,
+    usage: AtomicBool,
+}
+
+impl ReportingState {
+    pub fn new(config: TelemetryConfig) -> Self {
+        Self {
+            crash_reports: AtomicBool::new(config.crash_reports),
+            usage: AtomicBool::new(config.usage),
+        }
+    }
+
+    pub fn set(&self, config: TelemetryConfig) {
+        self.crash_reports
+            .store(config.crash_reports, Ordering::Relaxed);
+        self.usage.store(config.usage, Ordering::Relaxed);
+    }
+
+    pub fn get(&self) -> TelemetryConfig {
+        TelemetryConfig {
+            crash_reports: self.crash_reports.load(Ordering::Relaxed),
+            usage: self.usage.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Everything, which would be sent to the developers with the current config
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct TelemetryReportPreview {
+    /// Categories, which are reported with the current config
+    pub enabled: TelemetryConfig,
+    /// Address, which reports are sent to
+    pub url: String,
+    /// Usage statistics, exactly as they would be sent by the next hourly report
+    pub usage: TelemetryData,
+    /// Crash report, sent on panic, with placeholders instead of the panic details
+    pub crash_report: Value,
+}
+
+impl TelemetryReportPreview {
+    pub async fn collect(telemetry: &TelemetryCollector, reporting: &ReportingState) -> Self {
+        Self {
+            enabled: reporting.get(),
+            url: telemetry_url(),
+            usage: usage_report(telemetry).await,
+            crash_report: ErrorReporter::report_body(
+                "<panic message>",
+                &telemetry.reporting_id(),
+                Some("<panic location>"),
+            ),
+        }
+    }
+}
+
+async fn usage_report(telemetry: &TelemetryCollector) -> TelemetryData {
+    telemetry.prepare_data(DETAIL_LEVEL).await.anonymize()
+}
+
 pub struct TelemetryReporter {
     telemetry_url: String,
     telemetry: Arc<Mutex<TelemetryCollector>>,
@@ -16,26 +91,14 @@ pub struct TelemetryReporter {
 
 impl TelemetryReporter {
     fn new(telemetry: Arc<Mutex<TelemetryCollector>>) -> Self {
-        let telemetry_url = if cfg!(debug_assertions) {
-            "https://staging-telemetry.qdrant.io".to_string()
-        } else {
-            "https://telemetry.qdrant.io".to_string()
-        };
-
         Self {
-            telemetry_url,
+            telemetry_url: telemetry_url(),
             telemetry,
         }
     }
 
     async fn report(&self) {
-        let data = self
-            .telemetry
-            .lock()
-            .await
-            .prepare_data(DETAIL_LEVEL)
-            .await
-            .anonymize();
+        let data = usage_report(&*self.telemetry.lock().await).await;
         let client = reqwest::Client::new();
         let data = serde_json::to_string(&data).unwrap();
         let _resp = client
@@ -46,10 +109,14 @@ impl TelemetryReporter {
             .await;
     }
 
-    pub async fn run(telemetry: Arc<Mutex<TelemetryCollector>>) {
+    /// Report usage statistics periodically, while usage reporting is enabled.
+    /// Reporting can be enabled or disabled on config reload.
+    pub async fn run(telemetry: Arc<Mutex<TelemetryCollector>>, reporting: Arc<ReportingState>) {
         let reporter = Self::new(telemetry);
         loop {
-            reporter.report().await;
+            if reporting.get().usage {
+                reporter.report().await;
+            }
             tokio::time::sleep(REPORTING_INTERVAL).await;
         }
     }
@@ -10,6 +10,7 @@ use api::grpc::dynamic_channel_pool::make_grpc_channel;
 use api::grpc::qdrant::raft_client::RaftClient;
 use api::grpc::qdrant::{AllPeers, PeerId as GrpcPeerId, RaftMessage as GrpcRaftMessage};
 use api::grpc::transport_channel_pool::TransportChannelPool;
+use collection::common::fault_injection;
 use collection::shards::channel_service::ChannelService;
 use collection::shards::shard::PeerId;
 use raft::eraftpb::Message as RaftMessage;
@@ -767,6 +768,10 @@ impl Consensus {
         let future = async move {
             let mut send_futures = Vec::new();
             for (message, address) in messages_with_address {
+                if fault_injection::drop_p2p_message() {
+                    log::debug!("Dropped message to peer {} by injected fault", message.to);
+                    continue;
+                }
                 let address = match address {
                     Some(address) => address,
                     None => match who_is(
@@ -927,10 +932,12 @@ mod tests {
     use storage::content_manager::consensus_manager::{ConsensusManager, ConsensusStateRef};
     use storage::content_manager::toc::TableOfContent;
     use storage::dispatcher::Dispatcher;
+    use storage::runtime::{
+        create_general_purpose_runtime, create_search_runtime, create_update_runtime,
+    };
     use tempfile::Builder;
 
     use super::Consensus;
-    use crate::common::helpers::create_general_purpose_runtime;
     use crate::settings::ConsensusConfig;
 
     #[test]
@@ -941,12 +948,9 @@ mod tests {
         settings.storage.storage_path = storage_dir.path().to_str().unwrap().to_string();
         std::env::set_var("RUST_LOG", log::Level::Debug.as_str());
         env_logger::init();
-        let search_runtime =
-            crate::create_search_runtime(settings.storage.performance.max_search_threads)
-                .expect("Can't create search runtime.");
-        let update_runtime =
-            crate::create_update_runtime(settings.storage.performance.max_search_threads)
-                .expect("Can't create update runtime.");
+        let search_runtime = create_search_runtime(settings.storage.performance.max_search_threads)
+            .expect("Can't create search runtime.");
+        let update_runtime = create_update_runtime().expect("Can't create update runtime.");
         let general_runtime =
             create_general_purpose_runtime().expect("Can't create general purpose runtime.");
         let handle = general_runtime.handle().clone();
@@ -1021,6 +1025,7 @@ mod tests {
                                 distance: Distance::Cosine,
                                 hnsw_config: None,
                                 quantization_config: None,
+                                projection: None,
                             }
                             .into(),
                             hnsw_config: None,
@@ -1032,6 +1037,7 @@ mod tests {
                             write_consistency_factor: None,
                             init_from: None,
                             quantization_config: None,
+                            sparse_vectors: None,
                         },
                     )),
                     None,
@@ -2,6 +2,7 @@
 
 #[cfg(feature = "web")]
 mod actix;
+mod bench;
 mod common;
 mod consensus;
 mod greeting;
@@ -12,6 +13,7 @@ mod startup;
 mod tonic;
 
 use std::io::Error;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::thread;
 use std::thread::JoinHandle;
@@ -19,27 +21,32 @@ use std::time::Duration;
 
 use ::tonic::transport::Uri;
 use api::grpc::transport_channel_pool::TransportChannelPool;
-use clap::Parser;
+use clap::{Parser, Subcommand};
+use collection::common::fault_injection;
 use collection::shards::channel_service::ChannelService;
 use consensus::Consensus;
 use slog::Drain;
-use startup::setup_panic_hook;
+use startup::{lock_storage, setup_panic_hook};
 use storage::content_manager::consensus::operation_sender::OperationSender;
 use storage::content_manager::consensus::persistent::Persistent;
 use storage::content_manager::consensus_manager::{ConsensusManager, ConsensusStateRef};
+use storage::content_manager::lifecycle::run_lifecycle_policies;
 use storage::content_manager::toc::TableOfContent;
 use storage::dispatcher::Dispatcher;
+use storage::runtime::{
+    create_general_purpose_runtime, create_search_runtime, create_update_runtime,
+};
 #[cfg(not(target_env = "msvc"))]
 use tikv_jemallocator::Jemalloc;
 
-use crate::common::helpers::{
-    create_general_purpose_runtime, create_search_runtime, create_update_runtime,
-    load_tls_client_config,
-};
+use crate::bench::{run_bench, BenchArgs};
+use crate::common::config_reload::ConfigReloader;
+use crate::common::helpers::load_tls_client_config;
 use crate::common::telemetry::TelemetryCollector;
-use crate::common::telemetry_reporting::TelemetryReporter;
+use crate::common::telemetry_reporting::{ReportingState, TelemetryReporter};
 use crate::greeting::welcome;
 use crate::migrations::single_to_cluster::handle_existing_collections;
+use crate::migrations::storage_migration::{check_storage, migrate_storage};
 use crate::settings::Settings;
 use crate::snapshots::{recover_full_snapshot, recover_snapshots};
 use crate::startup::setup_logger;
@@ -103,23 +110,78 @@ struct Args {
     /// Read more: https://qdrant.tech/documentation/telemetry
     #[arg(long, action, default_value_t = false)]
     disable_telemetry: bool,
+
+    /// Check that the storage is compatible with this version and exit, without starting the peer.
+    /// Exits with non-zero code, if any collection or segment can't be loaded.
+    #[arg(long, action, default_value_t = false)]
+    check_storage: bool,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Upgrade on-disk formats of the storage to the current version, without starting the peer.
+    /// Allows to upgrade over several patch versions of the same minor version at once,
+    /// which can't be loaded by this version directly. Other versions are refused.
+    ///
+    /// The storage is copied into the new location, the original storage is not modified.
+    Migrate {
+        /// Path to the storage of the old version
+        #[arg(long, value_name = "PATH")]
+        from: PathBuf,
+        /// Path to the new storage, must not exist or be empty
+        #[arg(long, value_name = "PATH")]
+        to: PathBuf,
+    },
+    /// Generate a synthetic collection and report ingest and search throughput,
+    /// without starting the peer. Allows to validate the hardware before production loads.
+    ///
+    /// Benchmarks a running instance, if its uri is specified, or an embedded storage otherwise.
+    Bench(BenchArgs),
 }
 
 fn main() -> anyhow::Result<()> {
     let args = Args::parse();
-    let settings = Settings::new(args.config_path).expect("Can't read config.");
+    let (settings, raw_settings) =
+        Settings::load(args.config_path.clone()).expect("Can't read config.");
 
-    let reporting_enabled = !settings.telemetry_disabled && !args.disable_telemetry;
+    // Reporting may be enabled or disabled on config reload
+    let telemetry_reporting = Arc::new(ReportingState::new(
+        settings.telemetry_reporting(args.disable_telemetry),
+    ));
 
     let reporting_id = TelemetryCollector::generate_id();
 
-    setup_logger(&settings.log_level);
-    setup_panic_hook(reporting_enabled, reporting_id.to_string());
+    let logger = setup_logger(
+        &settings.log_level,
+        settings.log_format,
+        &settings.collection_log_levels,
+    );
+    setup_panic_hook(telemetry_reporting.clone(), reporting_id.to_string());
+
+    if fault_injection::is_enabled() {
+        log::warn!("Fault injection is enabled in this build, it must not be used in production");
+    }
+
+    match args.command {
+        Some(Command::Migrate { from, to }) => return migrate_storage(&from, &to),
+        Some(Command::Bench(bench_args)) => return run_bench(bench_args, &settings),
+        None => {}
+    }
+
+    if args.check_storage {
+        return check_storage(Path::new(&settings.storage.storage_path));
+    }
 
     segment::madvise::set_global(settings.storage.mmap_advice);
 
     welcome();
 
+    // Held until the process exits
+    let _storage_lock = lock_storage(Path::new(&settings.storage.storage_path))?;
+
     // Validate as soon as possible, but we must initialize logging first
     settings.validate_and_warn();
 
@@ -155,9 +217,7 @@ fn main() -> anyhow::Result<()> {
     let search_runtime = create_search_runtime(settings.storage.performance.max_search_threads)
         .expect("Can't search create runtime.");
 
-    let update_runtime =
-        create_update_runtime(settings.storage.performance.max_optimization_threads)
-            .expect("Can't optimizer create runtime.");
+    let update_runtime = create_update_runtime().expect("Can't optimizer create runtime.");
 
     let general_runtime =
         create_general_purpose_runtime().expect("Can't optimizer general purpose runtime.");
@@ -195,6 +255,9 @@ fn main() -> anyhow::Result<()> {
         channel_service.id_to_address = persistent_consensus_state.peer_address_by_id.clone();
     }
 
+    // Report all incompatibilities of the storage at once, before loading any collection
+    check_storage(Path::new(&settings.storage.storage_path))?;
+
     // Table of content manages the list of collections.
     // It is a main entry point for the storage.
     let toc = TableOfContent::new(
@@ -312,6 +375,12 @@ fn main() -> anyhow::Result<()> {
 
     let tonic_telemetry_collector = telemetry_collector.tonic_telemetry_collector.clone();
 
+    runtime_handle.spawn(TableOfContent::run_orphan_files_gc(toc_arc.clone()));
+    runtime_handle.spawn(run_lifecycle_policies(
+        dispatcher_arc.clone(),
+        settings.storage.lifecycle_check_interval_sec,
+    ));
+
     //
     // Telemetry reporting
     //
@@ -319,13 +388,21 @@ fn main() -> anyhow::Result<()> {
     let reporting_id = telemetry_collector.reporting_id();
     let telemetry_collector = Arc::new(tokio::sync::Mutex::new(telemetry_collector));
 
-    if reporting_enabled {
-        log::info!("Telemetry reporting enabled, id: {}", reporting_id);
-
-        runtime_handle.spawn(TelemetryReporter::run(telemetry_collector.clone()));
+    let reporting = telemetry_reporting.get();
+    if reporting.crash_reports || reporting.usage {
+        log::info!(
+            "Telemetry reporting enabled, id: {}, crash reports: {}, usage: {}",
+            reporting_id,
+            reporting.crash_reports,
+            reporting.usage,
+        );
     } else {
         log::info!("Telemetry reporting disabled");
     }
+    runtime_handle.spawn(TelemetryReporter::run(
+        telemetry_collector.clone(),
+        telemetry_reporting.clone(),
+    ));
 
     //
     // REST API server
@@ -333,11 +410,27 @@ fn main() -> anyhow::Result<()> {
 
     #[cfg(feature = "web")]
     {
+        let config_reloader = Arc::new(ConfigReloader::new(
+            args.config_path,
+            &raw_settings,
+            toc_arc.clone(),
+            logger,
+            telemetry_reporting.clone(),
+            args.disable_telemetry,
+        ));
         let dispatcher_arc = dispatcher_arc.clone();
         let settings = settings.clone();
         let handle = thread::Builder::new()
             .name("web".to_string())
-            .spawn(move || actix::init(dispatcher_arc.clone(), telemetry_collector, settings))
+            .spawn(move || {
+                actix::init(
+                    dispatcher_arc.clone(),
+                    telemetry_collector,
+                    telemetry_reporting,
+                    config_reloader,
+                    settings,
+                )
+            })
             .unwrap();
         handles.push(handle);
     }
@@ -0,0 +1,100 @@
+use std::fs;
+use std::path::Path;
+
+use anyhow::{bail, Context};
+use collection::collection::Collection;
+use collection::config::CollectionConfig;
+use storage::content_manager::toc::COLLECTIONS_DIR;
+
+/// Copy the storage from `from` into `to` and upgrade on-disk formats of all its collections
+/// to the current version.
+/// The original storage is not modified, so it is still usable by the old version
+/// if the migration fails.
+pub fn migrate_storage(from: &Path, to: &Path) -> anyhow::Result<()> {
+    if !from.join(COLLECTIONS_DIR).is_dir() {
+        bail!("{} is not a storage directory", from.display());
+    }
+    if to.exists() && to.read_dir()?.next().is_some() {
+        bail!("Target directory {} is not empty", to.display());
+    }
+
+    log::info!("Copying storage {} into {}", from.display(), to.display());
+    copy_dir_all(from, to)
+        .with_context(|| format!("Failed to copy storage into {}", to.display()))?;
+
+    for entry in fs::read_dir(to.join(COLLECTIONS_DIR))? {
+        let collection_path = entry?.path();
+        if !CollectionConfig::check(&collection_path) {
+            log::warn!(
+                "Collection config is not found in the collection directory: {:?}, skipping",
+                collection_path
+            );
+            continue;
+        }
+        Collection::migrate_storage(&collection_path).with_context(|| {
+            format!(
+                "Failed to migrate collection at {}",
+                collection_path.display()
+            )
+        })?;
+    }
+
+    log::info!("Storage is migrated into {}", to.display());
+    Ok(())
+}
+
+/// Check all collections of the storage for incompatibilities with the current version,
+/// without modifying them.
+/// All found problems are logged at once, instead of failing on the first incompatible segment.
+pub fn check_storage(storage_path: &Path) -> anyhow::Result<()> {
+    let collections_path = storage_path.join(COLLECTIONS_DIR);
+    if !collections_path.is_dir() {
+        // New storage, nothing to check
+        return Ok(());
+    }
+
+    let mut problems = Vec::new();
+    for entry in fs::read_dir(&collections_path)? {
+        let collection_path = entry?.path();
+        if !CollectionConfig::check(&collection_path) {
+            continue;
+        }
+        let collection_name = collection_path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        problems.extend(
+            Collection::check_storage_compatibility(&collection_path)
+                .into_iter()
+                .map(|problem| format!("Collection {collection_name}: {problem}")),
+        );
+    }
+
+    if problems.is_empty() {
+        log::info!("Storage {} is compatible", storage_path.display());
+        return Ok(());
+    }
+
+    for problem in &problems {
+        log::error!("{problem}");
+    }
+    bail!(
+        "Storage {} is not compatible with the current version, found {} problems",
+        storage_path.display(),
+        problems.len()
+    )
+}
+
+fn copy_dir_all(from: &Path, to: &Path) -> std::io::Result<()> {
+    fs::create_dir_all(to)?;
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        let target = to.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_dir_all(&entry.path(), &target)?;
+        } else {
+            fs::copy(entry.path(), target)?;
+        }
+    }
+    Ok(())
+}
@@ -1,14 +1,42 @@
 use api::grpc::models::CollectionsResponse;
+use collection::common::fault_injection::FaultInjectionConfig;
+use collection::operations::backfill_ops::{
+    BackfillBatch, BackfillJobInfo, BackfillPointsRequest, BackfillPointsResult, CreateBackfillJob,
+};
+use collection::operations::cardinality_ops::{
+    CardinalityEstimationRequest, CardinalityEstimationResult,
+};
 use collection::operations::cluster_ops::ClusterOperations;
+use collection::operations::config_history_ops::ConfigChange;
 use collection::operations::consistency_params::ReadConsistency;
+use collection::operations::facet_ops::{FacetRequest, FacetResponse};
+use collection::operations::filter_validation::FilterValidation;
+use collection::operations::histogram_ops::{HistogramRequest, HistogramResult};
+use collection::operations::index_rebuild_ops::IndexRebuildInfo;
+use collection::operations::orphan_files_ops::{OrphanFilesRequest, OrphanFilesResult};
 use collection::operations::payload_ops::{DeletePayload, SetPayload};
+use collection::operations::payload_schema_ops::{
+    PayloadSchemaInferenceRequest, PayloadSchemaInferenceResult,
+};
+use collection::operations::point_access_ops::{HotPointsReport, HotPointsRequest};
 use collection::operations::point_ops::{PointInsertOperations, PointsSelector, WriteOrdering};
-use collection::operations::snapshot_ops::{SnapshotDescription, SnapshotRecover};
+use collection::operations::recall_ops::{RecallEstimationRequest, RecallEstimationResult};
+use collection::operations::scoring_benchmark_ops::{
+    ScoringBenchmarkRequest, ScoringBenchmarkResult,
+};
+use collection::operations::snapshot_ops::{
+    SnapshotDescription, SnapshotInspection, SnapshotRecover,
+};
+use collection::operations::terms_ops::{TermsRequest, TermsResult};
 use collection::operations::types::{
-    AliasDescription, CollectionClusterInfo, CollectionInfo, CollectionsAliasesResponse,
-    CountRequest, CountResult, PointRequest, RecommendRequest, RecommendRequestBatch, Record,
-    ScrollRequest, ScrollResult, SearchRequest, SearchRequestBatch, UpdateResult,
+    AliasDescription, AliasSortBy, ArithmeticSearchRequest, CollectionClusterInfo, CollectionInfo,
+    CollectionsAliasesResponse, CountRequest, CountResult, DiscoverRequest, FlushResult,
+    GroupsResult, PointExistence, PointRequest, PointsExistRequest, QueryRequest, RecommendRequest,
+    RecommendRequestBatch, Record, ScrollRequest, ScrollResult, SearchGroupsRequest, SearchRequest,
+    SearchRequestBatch, UpdateResult, WalTruncateResult,
 };
+use collection::operations::vacuum_ops::{VacuumRequest, VacuumResult};
+use collection::operations::wal_replay_ops::{WalReplayRequest, WalReplayResult};
 use schemars::gen::SchemaSettings;
 use schemars::JsonSchema;
 use segment::types::ScoredPoint;
@@ -16,11 +44,16 @@ use serde::{Deserialize, Serialize};
 use storage::content_manager::collection_meta_ops::{
     ChangeAliasesOperation, CreateCollection, UpdateCollection,
 };
+use storage::content_manager::reindex::{ReindexJobInfo, ReindexRequest};
+use storage::content_manager::search_queue::SearchPriority;
 use storage::types::ClusterStatus;
 
+use crate::common::config_reload::ConfigReloadResult;
 use crate::common::helpers::LocksOption;
+use crate::common::memory_usage::MemoryUsageReport;
 use crate::common::points::CreateFieldIndex;
 use crate::common::telemetry::TelemetryData;
+use crate::common::telemetry_reporting::TelemetryReportPreview;
 
 mod actix;
 mod common;
@@ -63,6 +96,54 @@ struct AllDefinitions {
     ay: AliasDescription,
     az: WriteOrdering,
     b1: ReadConsistency,
+    b2: CreateBackfillJob,
+    b3: BackfillJobInfo,
+    b4: BackfillPointsRequest,
+    b5: BackfillPointsResult,
+    b6: BackfillBatch,
+    b7: RecallEstimationRequest,
+    b8: RecallEstimationResult,
+    b9: VacuumRequest,
+    ba: VacuumResult,
+    bb: FlushResult,
+    bc: WalTruncateResult,
+    bd: SearchPriority,
+    be: MemoryUsageReport,
+    bf: IndexRebuildInfo,
+    bg: TermsRequest,
+    bh: TermsResult,
+    bi: PayloadSchemaInferenceRequest,
+    bj: PayloadSchemaInferenceResult,
+    bk: FilterValidation,
+    bl: CardinalityEstimationRequest,
+    bm: CardinalityEstimationResult,
+    bn: HotPointsRequest,
+    bo: HotPointsReport,
+    bp: SnapshotInspection,
+    bq: WalReplayRequest,
+    br: WalReplayResult,
+    bs: ConfigReloadResult,
+    bt: OrphanFilesRequest,
+    bu: OrphanFilesResult,
+    bv: ScoringBenchmarkRequest,
+    bw: ScoringBenchmarkResult,
+    bx: TelemetryReportPreview,
+    by: FaultInjectionConfig,
+    bz: PointsExistRequest,
+    ca: PointExistence,
+    cb: ConfigChange,
+    cc: AliasSortBy,
+    cd: FacetRequest,
+    ce: FacetResponse,
+    cd: ReindexRequest,
+    ce: ReindexJobInfo,
+    cf: QueryRequest,
+    cg: SearchGroupsRequest,
+    ch: GroupsResult,
+    ci: HistogramRequest,
+    cj: HistogramResult,
+    ck: DiscoverRequest,
+    cl: ArithmeticSearchRequest,
 }
 
 fn save_schema<T: JsonSchema>() {
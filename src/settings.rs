@@ -1,12 +1,14 @@
-use std::{env, io};
+use std::collections::HashMap;
+use std::{env, fs, io};
 
 use api::grpc::transport_channel_pool::{
     DEFAULT_CONNECT_TIMEOUT, DEFAULT_GRPC_TIMEOUT, DEFAULT_POOL_SIZE,
 };
 use collection::operations::validation;
 use config::{Config, ConfigError, Environment, File};
+use schemars::JsonSchema;
 use segment::common::cpu::get_num_cpus;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use storage::types::StorageConfig;
 use validator::Validate;
 
@@ -24,6 +26,15 @@ pub struct ServiceConfig {
     pub enable_tls: bool,
     #[serde(default)]
     pub verify_https_client_certificate: bool,
+    /// Expose `/debug/pprof/profile` endpoint, which samples the process for profiling
+    #[serde(default)]
+    pub enable_profiling: bool,
+    /// Serve the web dashboard under `/dashboard`
+    #[serde(default = "default_enable_dashboard")]
+    pub enable_dashboard: bool,
+    /// Expose `/config/reload` endpoint, which applies changes of the config without restart
+    #[serde(default)]
+    pub enable_config_reload: bool,
 }
 
 #[derive(Debug, Deserialize, Clone, Default, Validate)]
@@ -93,12 +104,28 @@ pub struct TlsConfig {
     pub ca_cert: String,
 }
 
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum LogFormat {
+    /// Human-readable lines
+    #[default]
+    Text,
+    /// JSON object per line, with fields of the collection, shard and API request
+    Json,
+}
+
 #[derive(Debug, Deserialize, Clone, Validate)]
 pub struct Settings {
     #[serde(default = "default_debug")]
     pub debug: bool,
     #[serde(default = "default_log_level")]
     pub log_level: String,
+    #[serde(default)]
+    pub log_format: LogFormat,
+    /// Log levels of lines, emitted while working on the collection, by collection name.
+    /// Override `log_level` for these collections
+    #[serde(default)]
+    pub collection_log_levels: HashMap<String, String>,
     #[validate]
     pub storage: StorageConfig,
     #[validate]
@@ -106,11 +133,34 @@ pub struct Settings {
     #[serde(default)]
     #[validate]
     pub cluster: ClusterConfig,
+    /// Disables reporting of all telemetry categories
     #[serde(default = "default_telemetry_disabled")]
     pub telemetry_disabled: bool,
+    #[serde(default)]
+    pub telemetry: TelemetryConfig,
     pub tls: Option<TlsConfig>,
 }
 
+/// Categories of telemetry, reported to the developers
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq)]
+pub struct TelemetryConfig {
+    /// Report messages and locations of panics
+    #[serde(default = "default_telemetry_category")]
+    pub crash_reports: bool,
+    /// Report anonymized usage statistics every hour
+    #[serde(default = "default_telemetry_category")]
+    pub usage: bool,
+}
+
+impl Default for TelemetryConfig {
+    fn default() -> Self {
+        TelemetryConfig {
+            crash_reports: default_telemetry_category(),
+            usage: default_telemetry_category(),
+        }
+    }
+}
+
 impl Settings {
     pub fn tls(&self) -> io::Result<&TlsConfig> {
         self.tls
@@ -125,6 +175,20 @@ impl Settings {
         )
     }
 
+    /// Categories of telemetry to report, `telemetry_disabled` and the command line argument
+    /// disable all of them
+    #[allow(dead_code)]
+    pub fn telemetry_reporting(&self, disabled_by_args: bool) -> TelemetryConfig {
+        if self.telemetry_disabled || disabled_by_args {
+            TelemetryConfig {
+                crash_reports: false,
+                usage: false,
+            }
+        } else {
+            self.telemetry
+        }
+    }
+
     #[allow(dead_code)]
     pub fn validate_and_warn(&self) {
         if let Err(ref errs) = self.validate() {
@@ -137,10 +201,18 @@ fn default_telemetry_disabled() -> bool {
     false
 }
 
+fn default_telemetry_category() -> bool {
+    true
+}
+
 fn default_cors() -> bool {
     true
 }
 
+fn default_enable_dashboard() -> bool {
+    true
+}
+
 fn default_debug() -> bool {
     false
 }
@@ -177,10 +249,23 @@ fn default_connection_pool_size() -> usize {
 impl Settings {
     #[allow(dead_code)]
     pub fn new(config_path: Option<String>) -> Result<Self, ConfigError> {
+        // You can deserialize (and thus freeze) the entire configuration as
+        Self::config(config_path)?.try_deserialize()
+    }
+
+    /// Load settings along with the raw configuration, which includes values of all sources
+    #[allow(dead_code)]
+    pub fn load(config_path: Option<String>) -> Result<(Self, serde_json::Value), ConfigError> {
+        let config = Self::config(config_path)?;
+        let raw = config.clone().try_deserialize()?;
+        Ok((config.try_deserialize()?, raw))
+    }
+
+    fn config(config_path: Option<String>) -> Result<Config, ConfigError> {
         let config_path = config_path.unwrap_or_else(|| "config/config".into());
         let env = env::var("RUN_MODE").unwrap_or_else(|_| "development".into());
 
-        let s = Config::builder()
+        let mut builder = Config::builder()
             // Start off by merging in the "default" configuration file
             .add_source(File::with_name(&config_path))
             // Add in the current environment file
@@ -192,12 +277,106 @@ impl Settings {
             .add_source(File::with_name("config/local").required(false))
             // Add in settings from the environment (with a prefix of APP)
             // Eg.. `QDRANT_DEBUG=1 ./target/app` would set the `debug` key
-            .add_source(Environment::with_prefix("QDRANT").separator("__"))
-            .build()?;
+            .add_source(Environment::with_prefix(ENV_PREFIX).separator(ENV_SEPARATOR));
 
-        // You can deserialize (and thus freeze) the entire configuration as
-        s.try_deserialize()
+        // Eg.. `QDRANT__TLS__KEY_FILE=/run/secrets/key` would set the `tls.key` key
+        // to the content of the file
+        for (key, value) in env_file_overrides()? {
+            builder = builder.set_override(key, value)?;
+        }
+
+        // Replace `${VAR}` in string values of all sources
+        let mut values: serde_json::Value = builder.build()?.try_deserialize()?;
+        interpolate_env_value(&mut values)?;
+        Config::try_from(&values)
+    }
+}
+
+const ENV_PREFIX: &str = "QDRANT";
+const ENV_SEPARATOR: &str = "__";
+/// Suffix of variables, which reference a file with the value, e.g. with a mounted secret
+const ENV_FILE_SUFFIX: &str = "_FILE";
+
+/// Read the value from the file, referenced by a variable.
+/// Trailing newlines, which are usually added by editors, are not a part of the value.
+fn read_env_file(variable: &str, path: &str) -> Result<String, ConfigError> {
+    let content = fs::read_to_string(path).map_err(|err| {
+        ConfigError::Message(format!(
+            "Can't read file {path}, referenced by {variable}: {err}"
+        ))
+    })?;
+    Ok(content.trim_end_matches(['\n', '\r']).to_string())
+}
+
+/// Settings from files, referenced by `QDRANT__<KEY>_FILE` variables
+fn env_file_overrides() -> Result<Vec<(String, String)>, ConfigError> {
+    let prefix = format!("{ENV_PREFIX}{ENV_SEPARATOR}");
+    let mut overrides = Vec::new();
+    for (variable, path) in env::vars() {
+        let key = match variable
+            .strip_prefix(&prefix)
+            .and_then(|key| key.strip_suffix(ENV_FILE_SUFFIX))
+        {
+            Some(key) => key,
+            None => continue,
+        };
+        let key = key.to_lowercase().replace(ENV_SEPARATOR, ".");
+        overrides.push((key, read_env_file(&variable, &path)?));
+    }
+    Ok(overrides)
+}
+
+/// Value of the `${VAR}` reference: the variable itself or content of the file from `VAR_FILE`
+fn env_reference_value(variable: &str) -> Result<String, ConfigError> {
+    if let Ok(value) = env::var(variable) {
+        return Ok(value);
+    }
+    let file_variable = format!("{variable}{ENV_FILE_SUFFIX}");
+    match env::var(&file_variable) {
+        Ok(path) => read_env_file(&file_variable, &path),
+        Err(_) => Err(ConfigError::Message(format!(
+            "Environment variable {variable}, referenced in config, is not set"
+        ))),
+    }
+}
+
+/// Replace `${VAR}` references in the string with values of environment variables
+fn interpolate_env(value: &str) -> Result<String, ConfigError> {
+    let mut result = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(start) = rest.find("${") {
+        let end = match rest[start..].find('}') {
+            Some(end) => start + end,
+            None => {
+                return Err(ConfigError::Message(format!(
+                    "Unterminated environment variable reference in config value {value}"
+                )))
+            }
+        };
+        result.push_str(&rest[..start]);
+        result.push_str(&env_reference_value(&rest[start + 2..end])?);
+        rest = &rest[end + 1..];
     }
+    result.push_str(rest);
+    Ok(result)
+}
+
+fn interpolate_env_value(value: &mut serde_json::Value) -> Result<(), ConfigError> {
+    match value {
+        serde_json::Value::String(string) => *string = interpolate_env(string)?,
+        serde_json::Value::Array(array) => {
+            for value in array {
+                interpolate_env_value(value)?;
+            }
+        }
+        serde_json::Value::Object(object) => {
+            for value in object.values_mut() {
+                interpolate_env_value(value)?;
+            }
+        }
+        serde_json::Value::Null | serde_json::Value::Bool(_) | serde_json::Value::Number(_) => {}
+    }
+    Ok(())
 }
 
 /// Returns the number of maximum actix workers.
@@ -230,4 +409,43 @@ mod tests {
         // Validate
         config.validate().unwrap();
     }
+
+    #[test]
+    fn test_interpolate_env() {
+        let dir = tempfile::Builder::new()
+            .prefix("secrets")
+            .tempdir()
+            .unwrap();
+        let secret_path = dir.path().join("secret");
+        fs::write(&secret_path, "from file\n").unwrap();
+
+        env::set_var("TEST_INTERPOLATE_VALUE", "value");
+        env::set_var("TEST_INTERPOLATE_SECRET_FILE", &secret_path);
+
+        assert_eq!(
+            interpolate_env("a ${TEST_INTERPOLATE_VALUE} b ${TEST_INTERPOLATE_SECRET}").unwrap(),
+            "a value b from file",
+        );
+        assert_eq!(interpolate_env("no references").unwrap(), "no references");
+        assert!(interpolate_env("${TEST_INTERPOLATE_MISSING}").is_err());
+        assert!(interpolate_env("${TEST_INTERPOLATE_VALUE").is_err());
+    }
+
+    #[test]
+    fn test_telemetry_reporting() {
+        env::set_var("RUN_MODE", "TEST");
+        let mut settings = Settings::new(None).unwrap();
+        settings.telemetry_disabled = false;
+        settings.telemetry = TelemetryConfig {
+            crash_reports: true,
+            usage: false,
+        };
+
+        assert_eq!(settings.telemetry_reporting(false), settings.telemetry);
+        assert!(!settings.telemetry_reporting(true).crash_reports);
+
+        settings.telemetry_disabled = true;
+        let reporting = settings.telemetry_reporting(false);
+        assert!(!reporting.crash_reports && !reporting.usage);
+    }
 }
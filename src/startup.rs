@@ -1,36 +1,29 @@
 //! Contains a collection of functions that are called at the start of the program.
 
+use std::collections::HashMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
 use std::panic;
+use std::path::Path;
+use std::sync::Arc;
 
-use log::LevelFilter;
+use anyhow::{bail, Context};
+use fs4::FileExt;
 
 use crate::common::error_reporting::ErrorReporter;
+use crate::common::logger::CollectionLogger;
+use crate::common::telemetry_reporting::ReportingState;
+use crate::settings::LogFormat;
 
-pub fn setup_logger(log_level: &str) {
-    let is_info = log_level.to_ascii_uppercase() == "INFO";
-    let mut log_builder = env_logger::Builder::new();
-
-    log_builder
-        // Timestamp in millis
-        .format_timestamp_millis()
-        // Parse user defined log level configuration
-        .parse_filters(log_level)
-        // h2 is very verbose and we have many network operations,
-        // so it is limited to only errors
-        .filter_module("h2", LevelFilter::Error)
-        .filter_module("tower", LevelFilter::Warn);
-
-    if is_info {
-        // Additionally filter verbose modules if no extended logging configuration is provided
-        log_builder
-            .filter_module("wal", LevelFilter::Warn)
-            .filter_module("raft::raft", LevelFilter::Warn);
-    };
-
-    log_builder.init();
+pub fn setup_logger(
+    log_level: &str,
+    log_format: LogFormat,
+    collection_log_levels: &HashMap<String, String>,
+) -> &'static CollectionLogger {
+    CollectionLogger::install(log_level, log_format, collection_log_levels)
 }
 
-pub fn setup_panic_hook(reporting_enabled: bool, reporting_id: String) {
+pub fn setup_panic_hook(reporting: Arc<ReportingState>, reporting_id: String) {
     panic::set_hook(Box::new(move |panic_info| {
         let loc = if let Some(loc) = panic_info.location() {
             format!(" in file {} at line {}", loc.file(), loc.line())
@@ -46,8 +39,64 @@ pub fn setup_panic_hook(reporting_enabled: bool, reporting_id: String) {
         };
         log::error!("Panic occurred{loc}: {message}");
 
-        if reporting_enabled {
+        if reporting.get().crash_reports {
             ErrorReporter::report(message, &reporting_id, Some(&loc));
         }
     }));
 }
+
+const STORAGE_LOCK_FILE: &str = ".lock";
+
+/// Lock the storage directory, so no other process can open the same storage concurrently.
+/// The lock is held until the returned file is closed. The file contains PID of the owner.
+pub fn lock_storage(storage_path: &Path) -> anyhow::Result<File> {
+    fs::create_dir_all(storage_path)
+        .with_context(|| format!("Can't create storage directory {}", storage_path.display()))?;
+    let lock_path = storage_path.join(STORAGE_LOCK_FILE);
+    let mut lock_file = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .open(&lock_path)
+        .with_context(|| format!("Can't open storage lock file {}", lock_path.display()))?;
+
+    if lock_file.try_lock_exclusive().is_err() {
+        let mut owner_pid = String::new();
+        // PID is informational, failure to read it doesn't change the outcome
+        let _ = lock_file.read_to_string(&mut owner_pid);
+        bail!(
+            "Storage {} is already in use by another process with PID {}",
+            storage_path.display(),
+            match owner_pid.trim() {
+                "" => "unknown",
+                pid => pid,
+            }
+        );
+    }
+
+    lock_file.set_len(0)?;
+    lock_file.seek(SeekFrom::Start(0))?;
+    write!(lock_file, "{}", std::process::id())?;
+    lock_file.sync_all()?;
+    Ok(lock_file)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lock_storage() {
+        let dir = tempfile::Builder::new()
+            .prefix("storage")
+            .tempdir()
+            .unwrap();
+
+        let lock = lock_storage(dir.path()).unwrap();
+        let error = lock_storage(dir.path()).unwrap_err().to_string();
+        assert!(error.contains(&std::process::id().to_string()), "{error}");
+
+        drop(lock);
+        lock_storage(dir.path()).unwrap();
+    }
+}
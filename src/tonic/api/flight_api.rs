@@ -0,0 +1,198 @@
+use std::sync::Arc;
+
+use arrow_flight::encode::FlightDataEncoderBuilder;
+use arrow_flight::error::FlightError;
+use arrow_flight::flight_service_server::FlightService;
+use arrow_flight::{
+    Action, ActionType, Criteria, Empty, FlightData, FlightDescriptor, FlightEndpoint, FlightInfo,
+    HandshakeRequest, HandshakeResponse, IpcMessage, PutResult, SchemaAsIpc, SchemaResult, Ticket,
+};
+use arrow_ipc::writer::IpcWriteOptions;
+use arrow_schema::ArrowError;
+use futures::stream::{self, BoxStream};
+use futures::{StreamExt, TryStreamExt};
+use storage::content_manager::conversions::error_to_status;
+use storage::content_manager::toc::TableOfContent;
+use tonic::{Request, Response, Status, Streaming};
+
+use super::{read_access, validate};
+use crate::common::arrow_export::{export_schema, records_to_batch, ExportRequest};
+
+/// Arrow Flight service for columnar export of points.
+///
+/// Both tickets and descriptor commands are JSON encoded `ExportRequest`s, so a client may call
+/// `DoGet` directly without requesting flight info first. Flight info splits the export into
+/// endpoints with non-overlapping ranges of point ids, if `partitions` are requested.
+pub struct FlightExportService {
+    toc: Arc<TableOfContent>,
+}
+
+impl FlightExportService {
+    pub fn new(toc: Arc<TableOfContent>) -> Self {
+        Self { toc }
+    }
+}
+
+fn parse_export_request(bytes: &[u8]) -> Result<ExportRequest, Status> {
+    let request: ExportRequest = serde_json::from_slice(bytes)
+        .map_err(|err| Status::invalid_argument(format!("Invalid export request: {err}")))?;
+    validate(&request)?;
+    Ok(request)
+}
+
+#[tonic::async_trait]
+impl FlightService for FlightExportService {
+    type HandshakeStream = BoxStream<'static, Result<HandshakeResponse, Status>>;
+    type ListFlightsStream = BoxStream<'static, Result<FlightInfo, Status>>;
+    type DoGetStream = BoxStream<'static, Result<FlightData, Status>>;
+    type DoPutStream = BoxStream<'static, Result<PutResult, Status>>;
+    type DoActionStream = BoxStream<'static, Result<arrow_flight::Result, Status>>;
+    type ListActionsStream = BoxStream<'static, Result<ActionType, Status>>;
+    type DoExchangeStream = BoxStream<'static, Result<FlightData, Status>>;
+
+    async fn handshake(
+        &self,
+        _request: Request<Streaming<HandshakeRequest>>,
+    ) -> Result<Response<Self::HandshakeStream>, Status> {
+        Err(Status::unimplemented("Handshake is not required"))
+    }
+
+    async fn list_flights(
+        &self,
+        _request: Request<Criteria>,
+    ) -> Result<Response<Self::ListFlightsStream>, Status> {
+        Err(Status::unimplemented("Listing flights is not supported"))
+    }
+
+    async fn get_flight_info(
+        &self,
+        request: Request<FlightDescriptor>,
+    ) -> Result<Response<FlightInfo>, Status> {
+        let access = read_access(&request);
+        let descriptor = request.into_inner();
+        let export_request = parse_export_request(&descriptor.cmd)?;
+        let vectors = export_request
+            .exported_vectors(&self.toc)
+            .await
+            .map_err(error_to_status)?;
+        let schema = export_schema(&vectors, export_request.with_payload());
+        let IpcMessage(schema) = SchemaAsIpc::new(&schema, &IpcWriteOptions::default())
+            .try_into()
+            .map_err(|err: ArrowError| Status::internal(err.to_string()))?;
+
+        let partitions = export_request
+            .partition(&self.toc, &access)
+            .await
+            .map_err(error_to_status)?;
+        let endpoint = partitions
+            .iter()
+            .map(|partition| {
+                let ticket = serde_json::to_vec(partition)
+                    .map_err(|err| Status::internal(err.to_string()))?;
+                Ok(FlightEndpoint {
+                    ticket: Some(Ticket {
+                        ticket: ticket.into(),
+                    }),
+                    location: vec![],
+                })
+            })
+            .collect::<Result<_, Status>>()?;
+
+        Ok(Response::new(FlightInfo {
+            schema,
+            flight_descriptor: Some(descriptor),
+            endpoint,
+            total_records: -1,
+            total_bytes: -1,
+        }))
+    }
+
+    async fn get_schema(
+        &self,
+        request: Request<FlightDescriptor>,
+    ) -> Result<Response<SchemaResult>, Status> {
+        let export_request = parse_export_request(&request.into_inner().cmd)?;
+        let vectors = export_request
+            .exported_vectors(&self.toc)
+            .await
+            .map_err(error_to_status)?;
+        let schema = export_schema(&vectors, export_request.with_payload());
+        let schema_result =
+            SchemaResult::try_from(SchemaAsIpc::new(&schema, &IpcWriteOptions::default()))
+                .map_err(|err| Status::internal(err.to_string()))?;
+        Ok(Response::new(schema_result))
+    }
+
+    async fn do_get(
+        &self,
+        request: Request<Ticket>,
+    ) -> Result<Response<Self::DoGetStream>, Status> {
+        let access = read_access(&request);
+        let export_request = parse_export_request(&request.into_inner().ticket)?;
+        let vectors = export_request
+            .exported_vectors(&self.toc)
+            .await
+            .map_err(error_to_status)?;
+        let with_payload = export_request.with_payload();
+        let schema = export_schema(&vectors, with_payload);
+
+        // Scroll pages lazily, so that only a single batch is kept in memory
+        let toc = self.toc.clone();
+        let batch_schema = schema.clone();
+        let start_offset = export_request.start_offset();
+        let batches = stream::try_unfold(Some(start_offset), move |offset| {
+            let toc = toc.clone();
+            let export_request = export_request.clone();
+            let vectors = vectors.clone();
+            let schema = batch_schema.clone();
+            let access = access.clone();
+            async move {
+                let offset = match offset {
+                    Some(offset) => offset,
+                    // Previous page was the last one
+                    None => return Ok(None),
+                };
+                let page = export_request
+                    .scroll_page(&toc, &vectors, offset, &access)
+                    .await
+                    .map_err(|err| FlightError::Tonic(error_to_status(err)))?;
+                let batch = records_to_batch(schema, &vectors, with_payload, &page.points)?;
+                Ok::<_, FlightError>(Some((batch, page.next_page_offset.map(Some))))
+            }
+        });
+
+        let flight_data = FlightDataEncoderBuilder::new()
+            .with_schema(schema)
+            .build(batches)
+            .map_err(Status::from);
+        Ok(Response::new(flight_data.boxed()))
+    }
+
+    async fn do_put(
+        &self,
+        _request: Request<Streaming<FlightData>>,
+    ) -> Result<Response<Self::DoPutStream>, Status> {
+        Err(Status::unimplemented("Import is not supported"))
+    }
+
+    async fn do_action(
+        &self,
+        _request: Request<Action>,
+    ) -> Result<Response<Self::DoActionStream>, Status> {
+        Err(Status::unimplemented("Actions are not supported"))
+    }
+
+    async fn list_actions(
+        &self,
+        _request: Request<Empty>,
+    ) -> Result<Response<Self::ListActionsStream>, Status> {
+        Ok(Response::new(stream::empty().boxed()))
+    }
+
+    async fn do_exchange(
+        &self,
+        _request: Request<Streaming<FlightData>>,
+    ) -> Result<Response<Self::DoExchangeStream>, Status> {
+        Err(Status::unimplemented("Exchange is not supported"))
+    }
+}
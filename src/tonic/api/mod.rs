@@ -1,6 +1,7 @@
 pub mod collections_api;
 mod collections_common;
 pub mod collections_internal_api;
+pub mod flight_api;
 pub mod points_api;
 mod points_common;
 pub mod points_internal_api;
@@ -8,9 +9,11 @@ pub mod raft_api;
 pub mod snapshots_api;
 
 use collection::operations::validation;
-use tonic::Status;
+use tonic::{Request, Status};
 use validator::Validate;
 
+use crate::common::acl::{ReadAccess, ACL_CLAIMS_HEADER};
+
 /// Validate the given request and fail on error.
 ///
 /// Returns validation error on failure.
@@ -27,6 +30,15 @@ fn validate_and_log(request: &dyn Validate) {
     }
 }
 
+/// Claims of the client, set by the authenticating proxy. Malformed metadata is treated as missing
+fn read_access<T>(request: &Request<T>) -> ReadAccess {
+    let claims = request
+        .metadata()
+        .get(ACL_CLAIMS_HEADER)
+        .and_then(|value| value.to_str().ok());
+    ReadAccess::from_claims_header(claims)
+}
+
 #[cfg(test)]
 mod tests {
     use validator::Validate;
@@ -1,21 +1,23 @@
 use std::sync::Arc;
+use std::time::Duration;
 
 use api::grpc::qdrant::points_server::Points;
 use api::grpc::qdrant::{
     ClearPayloadPoints, CountPoints, CountResponse, CreateFieldIndexCollection,
-    DeleteFieldIndexCollection, DeletePayloadPoints, DeletePoints, GetPoints, GetResponse,
-    PointsOperationResponse, RecommendBatchPoints, RecommendBatchResponse, RecommendPoints,
-    RecommendResponse, ScrollPoints, ScrollResponse, SearchBatchPoints, SearchBatchResponse,
-    SearchPoints, SearchResponse, SetPayloadPoints, UpsertPoints,
+    DeleteFieldIndexCollection, DeletePayloadPoints, DeletePoints, DiscoverPoints,
+    DiscoverResponse, GetPoints, GetResponse, PointsOperationResponse, QueryPoints, QueryResponse,
+    RecommendBatchPoints, RecommendBatchResponse, RecommendPoints, RecommendResponse, ScrollPoints,
+    ScrollResponse, SearchArithmeticPoints, SearchArithmeticResponse, SearchBatchPoints,
+    SearchBatchResponse, SearchPoints, SearchResponse, SetPayloadPoints, UpsertPoints,
 };
 use storage::content_manager::toc::TableOfContent;
 use tonic::{Request, Response, Status};
 
-use super::validate;
+use super::{read_access, validate};
 use crate::tonic::api::points_common::{
-    clear_payload, count, create_field_index, delete, delete_field_index, delete_payload, get,
-    overwrite_payload, recommend, recommend_batch, scroll, search, search_batch, set_payload,
-    upsert,
+    clear_payload, count, create_field_index, delete, delete_field_index, delete_payload, discover,
+    get, overwrite_payload, query, recommend, recommend_batch, scroll, search, search_arithmetic,
+    search_batch, set_payload, upsert,
 };
 
 pub struct PointsService {
@@ -48,7 +50,8 @@ impl Points for PointsService {
 
     async fn get(&self, request: Request<GetPoints>) -> Result<Response<GetResponse>, Status> {
         validate(request.get_ref())?;
-        get(self.toc.as_ref(), request.into_inner(), None).await
+        let access = read_access(&request);
+        get(self.toc.as_ref(), request.into_inner(), None, &access).await
     }
 
     async fn set_payload(
@@ -104,7 +107,8 @@ impl Points for PointsService {
         request: Request<SearchPoints>,
     ) -> Result<Response<SearchResponse>, Status> {
         validate(request.get_ref())?;
-        search(self.toc.as_ref(), request.into_inner(), None).await
+        let access = read_access(&request);
+        search(self.toc.as_ref(), request.into_inner(), None, &access).await
     }
 
     async fn search_batch(
@@ -112,17 +116,23 @@ impl Points for PointsService {
         request: Request<SearchBatchPoints>,
     ) -> Result<Response<SearchBatchResponse>, Status> {
         validate(request.get_ref())?;
+        let access = read_access(&request);
         let SearchBatchPoints {
             collection_name,
             search_points,
             read_consistency,
+            priority,
+            timeout,
         } = request.into_inner();
         search_batch(
             self.toc.as_ref(),
             collection_name,
             search_points,
             read_consistency,
+            priority,
+            timeout.map(Duration::from_secs),
             None,
+            &access,
         )
         .await
     }
@@ -132,7 +142,8 @@ impl Points for PointsService {
         request: Request<ScrollPoints>,
     ) -> Result<Response<ScrollResponse>, Status> {
         validate(request.get_ref())?;
-        scroll(self.toc.as_ref(), request.into_inner(), None).await
+        let access = read_access(&request);
+        scroll(self.toc.as_ref(), request.into_inner(), None, &access).await
     }
 
     async fn recommend(
@@ -140,7 +151,8 @@ impl Points for PointsService {
         request: Request<RecommendPoints>,
     ) -> Result<Response<RecommendResponse>, Status> {
         validate(request.get_ref())?;
-        recommend(self.toc.as_ref(), request.into_inner()).await
+        let access = read_access(&request);
+        recommend(self.toc.as_ref(), request.into_inner(), &access).await
     }
 
     async fn recommend_batch(
@@ -148,16 +160,22 @@ impl Points for PointsService {
         request: Request<RecommendBatchPoints>,
     ) -> Result<Response<RecommendBatchResponse>, Status> {
         validate(request.get_ref())?;
+        let access = read_access(&request);
         let RecommendBatchPoints {
             collection_name,
             recommend_points,
             read_consistency,
+            priority,
+            timeout,
         } = request.into_inner();
         recommend_batch(
             self.toc.as_ref(),
             collection_name,
             recommend_points,
             read_consistency,
+            priority,
+            timeout.map(Duration::from_secs),
+            &access,
         )
         .await
     }
@@ -167,6 +185,34 @@ impl Points for PointsService {
         request: Request<CountPoints>,
     ) -> Result<Response<CountResponse>, Status> {
         validate(request.get_ref())?;
-        count(self.toc.as_ref(), request.into_inner(), None).await
+        let access = read_access(&request);
+        count(self.toc.as_ref(), request.into_inner(), None, &access).await
+    }
+
+    async fn query(
+        &self,
+        request: Request<QueryPoints>,
+    ) -> Result<Response<QueryResponse>, Status> {
+        validate(request.get_ref())?;
+        let access = read_access(&request);
+        query(self.toc.as_ref(), request.into_inner(), &access).await
+    }
+
+    async fn discover(
+        &self,
+        request: Request<DiscoverPoints>,
+    ) -> Result<Response<DiscoverResponse>, Status> {
+        validate(request.get_ref())?;
+        let access = read_access(&request);
+        discover(self.toc.as_ref(), request.into_inner(), &access).await
+    }
+
+    async fn search_arithmetic(
+        &self,
+        request: Request<SearchArithmeticPoints>,
+    ) -> Result<Response<SearchArithmeticResponse>, Status> {
+        validate(request.get_ref())?;
+        let access = read_access(&request);
+        search_arithmetic(self.toc.as_ref(), request.into_inner(), &access).await
     }
 }
@@ -1,37 +1,45 @@
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 use api::grpc::conversions::proto_to_payloads;
 use api::grpc::qdrant::payload_index_params::IndexParams;
 use api::grpc::qdrant::{
     BatchResult, ClearPayloadPoints, CountPoints, CountResponse, CreateFieldIndexCollection,
-    DeleteFieldIndexCollection, DeletePayloadPoints, DeletePoints, FieldType, GetPoints,
-    GetResponse, PayloadIndexParams, PointsOperationResponse,
-    ReadConsistency as ReadConsistencyGrpc, RecommendBatchResponse, RecommendPoints,
-    RecommendResponse, ScrollPoints, ScrollResponse, SearchBatchResponse, SearchPoints,
-    SearchResponse, SetPayloadPoints, SyncPoints, UpsertPoints,
+    DeleteFieldIndexCollection, DeletePayloadPoints, DeletePoints, DiscoverPoints,
+    DiscoverResponse, FieldType, GetPoints, GetResponse, PayloadIndexParams,
+    PointsOperationResponse, QueryPoints, QueryResponse, ReadConsistency as ReadConsistencyGrpc,
+    RecommendBatchResponse, RecommendPoints, RecommendResponse, ScrollPoints, ScrollResponse,
+    SearchArithmeticPoints, SearchArithmeticResponse, SearchBatchResponse, SearchPoints,
+    SearchResponse, SetPayloadPoints, SyncPoints, UpdatePointVectors, UpsertPoints,
 };
 use collection::operations::consistency_params::ReadConsistency;
-use collection::operations::conversions::write_ordering_from_proto;
+use collection::operations::conversions::{
+    planner_hint_from_proto, recommend_examples_from_proto, recommend_strategy_from_proto,
+    recommend_weights_from_proto, scroll_order_from_proto, write_ordering_from_proto,
+};
 use collection::operations::payload_ops::DeletePayload;
 use collection::operations::point_ops::{
-    PointInsertOperations, PointOperations, PointSyncOperation, PointsSelector,
+    PointInsertOperations, PointOperations, PointSyncOperation, PointVectors, PointsSelector,
 };
 use collection::operations::types::{
-    default_exact_count, PointRequest, RecommendRequestBatch, ScrollRequest, SearchRequest,
-    SearchRequestBatch,
+    default_exact_count, ArithmeticSearchRequest, DiscoverRequest, PointRequest, QueryRequest,
+    RecommendRequestBatch, ScrollRequest, SearchRequest, SearchRequestBatch,
 };
 use collection::operations::CollectionUpdateOperations;
 use collection::shards::shard::ShardId;
 use segment::data_types::vectors::NamedVector;
 use segment::types::{PayloadFieldSchema, PayloadSchemaParams, PayloadSchemaType};
-use storage::content_manager::conversions::error_to_status;
+use storage::content_manager::conversions::{error_to_status, search_priority_from_proto};
 use storage::content_manager::toc::TableOfContent;
 use tonic::{Response, Status};
 
+use crate::common::acl::{
+    restrict_discover_request, restrict_query_request, restrict_recommend_request, ReadAccess,
+};
 use crate::common::points::{
-    do_clear_payload, do_count_points, do_create_index, do_delete_index, do_delete_payload,
-    do_delete_points, do_get_points, do_overwrite_payload, do_scroll_points,
-    do_search_batch_points, do_search_points, do_set_payload, do_upsert_points, CreateFieldIndex,
+    check_request_filters, do_clear_payload, do_count_points, do_create_index, do_delete_index,
+    do_delete_payload, do_delete_points, do_get_points, do_overwrite_payload, do_scroll_points,
+    do_search_arithmetic_points, do_search_batch_points, do_search_points, do_set_payload,
+    do_upsert_points, CreateFieldIndex,
 };
 
 pub fn points_operation_response(
@@ -54,6 +62,7 @@ pub async fn upsert(
         wait,
         points,
         ordering,
+        shard_key,
     } = upsert_points;
     let points = points
         .into_iter()
@@ -66,6 +75,7 @@ pub async fn upsert(
         &collection_name,
         operation,
         shard_selection,
+        shard_key,
         wait.unwrap_or(false),
         write_ordering_from_proto(ordering)?,
     )
@@ -119,6 +129,42 @@ pub async fn sync(
     Ok(Response::new(response))
 }
 
+pub async fn update_vectors(
+    toc: &TableOfContent,
+    update_point_vectors: UpdatePointVectors,
+    shard_selection: Option<ShardId>,
+) -> Result<Response<PointsOperationResponse>, Status> {
+    let UpdatePointVectors {
+        collection_name,
+        wait,
+        points,
+        ordering,
+    } = update_point_vectors;
+
+    let points = points
+        .into_iter()
+        .map(PointVectors::try_from)
+        .collect::<Result<_, _>>()?;
+
+    let timing = Instant::now();
+
+    let collection_operation =
+        CollectionUpdateOperations::PointOperation(PointOperations::UpdateVectors { points });
+    let result = toc
+        .update(
+            &collection_name,
+            collection_operation,
+            shard_selection,
+            wait.unwrap_or(false),
+            write_ordering_from_proto(ordering)?,
+        )
+        .await
+        .map_err(error_to_status)?;
+
+    let response = points_operation_response(timing, result);
+    Ok(Response::new(response))
+}
+
 pub async fn delete(
     toc: &TableOfContent,
     delete_points: DeletePoints,
@@ -129,6 +175,7 @@ pub async fn delete(
         wait,
         points,
         ordering,
+        shard_key,
     } = delete_points;
 
     let points_selector = match points {
@@ -142,6 +189,7 @@ pub async fn delete(
         &collection_name,
         points_selector,
         shard_selection,
+        shard_key,
         wait.unwrap_or(false),
         write_ordering_from_proto(ordering)?,
     )
@@ -360,6 +408,7 @@ pub async fn create_field_index(
             FieldType::Float => Some(PayloadSchemaType::Float.into()),
             FieldType::Geo => Some(PayloadSchemaType::Geo.into()),
             FieldType::Text => Some(PayloadSchemaType::Text.into()),
+            FieldType::Bool => Some(PayloadSchemaType::Bool.into()),
         },
         (None, Some(_)) => return Err(Status::invalid_argument("field type is missing")),
         (None, None) => None,
@@ -418,6 +467,7 @@ pub async fn search(
     toc: &TableOfContent,
     search_points: SearchPoints,
     shard_selection: Option<ShardId>,
+    access: &ReadAccess,
 ) -> Result<Response<SearchResponse>, Status> {
     let SearchPoints {
         collection_name,
@@ -431,6 +481,10 @@ pub async fn search(
         vector_name,
         with_vectors,
         read_consistency,
+        priority,
+        with_highlights,
+        with_geo_distance,
+        timeout,
     } = search_points;
 
     let search_request = SearchRequest {
@@ -449,9 +503,12 @@ pub async fn search(
                 .unwrap_or_default(),
         ),
         score_threshold,
+        with_highlights,
+        with_geo_distance,
     };
 
     let read_consistency = ReadConsistency::try_from_optional(read_consistency)?;
+    let priority = search_priority_from_proto(priority)?;
 
     let timing = Instant::now();
     let scored_points = do_search_points(
@@ -460,6 +517,11 @@ pub async fn search(
         search_request,
         read_consistency,
         shard_selection,
+        None,
+        priority,
+        timeout.map(Duration::from_secs),
+        None,
+        access,
     )
     .await
     .map_err(error_to_status)?;
@@ -475,12 +537,16 @@ pub async fn search(
     Ok(Response::new(response))
 }
 
+#[allow(clippy::too_many_arguments)]
 pub async fn search_batch(
     toc: &TableOfContent,
     collection_name: String,
     search_points: Vec<SearchPoints>,
     read_consistency: Option<ReadConsistencyGrpc>,
+    priority: Option<i32>,
+    timeout: Option<Duration>,
     shard_selection: Option<ShardId>,
+    access: &ReadAccess,
 ) -> Result<Response<SearchBatchResponse>, Status> {
     let searches: Result<Vec<_>, Status> = search_points
         .into_iter()
@@ -492,6 +558,7 @@ pub async fn search_batch(
     };
 
     let read_consistency = ReadConsistency::try_from_optional(read_consistency)?;
+    let priority = search_priority_from_proto(priority)?;
 
     let timing = Instant::now();
     let scored_points = do_search_batch_points(
@@ -500,6 +567,11 @@ pub async fn search_batch(
         search_requests,
         read_consistency,
         shard_selection,
+        None,
+        priority,
+        timeout,
+        None,
+        access,
     )
     .await
     .map_err(error_to_status)?;
@@ -520,6 +592,7 @@ pub async fn search_batch(
 pub async fn recommend(
     toc: &TableOfContent,
     recommend_points: RecommendPoints,
+    access: &ReadAccess,
 ) -> Result<Response<RecommendResponse>, Status> {
     let RecommendPoints {
         collection_name,
@@ -535,17 +608,21 @@ pub async fn recommend(
         with_vectors,
         lookup_from,
         read_consistency,
+        priority,
+        positive_vectors,
+        negative_vectors,
+        strategy,
+        timeout,
+        positive_weights,
+        negative_weights,
     } = recommend_points;
 
-    let request = collection::operations::types::RecommendRequest {
-        positive: positive
-            .into_iter()
-            .map(|p| p.try_into())
-            .collect::<Result<_, _>>()?,
-        negative: negative
-            .into_iter()
-            .map(|p| p.try_into())
-            .collect::<Result<_, _>>()?,
+    let mut request = collection::operations::types::RecommendRequest {
+        positive: recommend_examples_from_proto(positive, positive_vectors)?,
+        negative: recommend_examples_from_proto(negative, negative_vectors)?,
+        positive_weights: recommend_weights_from_proto(positive_weights),
+        negative_weights: recommend_weights_from_proto(negative_weights),
+        strategy: recommend_strategy_from_proto(strategy)?,
         filter: filter.map(|f| f.try_into()).transpose()?,
         params: params.map(|p| p.into()),
         limit: limit as usize,
@@ -562,10 +639,23 @@ pub async fn recommend(
     };
 
     let read_consistency = ReadConsistency::try_from_optional(read_consistency)?;
+    let priority = search_priority_from_proto(priority)?;
 
     let timing = Instant::now();
+    check_request_filters(toc, &collection_name, [request.filter.as_ref()], None, None)
+        .await
+        .map_err(error_to_status)?;
+    restrict_recommend_request(toc, &collection_name, &mut request, access)
+        .await
+        .map_err(error_to_status)?;
     let recommended_points = toc
-        .recommend(&collection_name, request, read_consistency)
+        .recommend(
+            &collection_name,
+            request,
+            read_consistency,
+            priority,
+            timeout.map(Duration::from_secs),
+        )
         .await
         .map_err(error_to_status)?;
 
@@ -585,20 +675,47 @@ pub async fn recommend_batch(
     collection_name: String,
     recommend_points: Vec<RecommendPoints>,
     read_consistency: Option<ReadConsistencyGrpc>,
+    priority: Option<i32>,
+    timeout: Option<Duration>,
+    access: &ReadAccess,
 ) -> Result<Response<RecommendBatchResponse>, Status> {
     let searches: Result<Vec<_>, Status> = recommend_points
         .into_iter()
         .map(|recommend_point| recommend_point.try_into())
         .collect();
-    let recommend_batch = RecommendRequestBatch {
+    let mut recommend_batch = RecommendRequestBatch {
         searches: searches?,
     };
 
     let read_consistency = ReadConsistency::try_from_optional(read_consistency)?;
+    let priority = search_priority_from_proto(priority)?;
 
     let timing = Instant::now();
+    check_request_filters(
+        toc,
+        &collection_name,
+        recommend_batch
+            .searches
+            .iter()
+            .map(|search| search.filter.as_ref()),
+        None,
+        None,
+    )
+    .await
+    .map_err(error_to_status)?;
+    for search in &mut recommend_batch.searches {
+        restrict_recommend_request(toc, &collection_name, search, access)
+            .await
+            .map_err(error_to_status)?;
+    }
     let scored_points = toc
-        .recommend_batch(&collection_name, recommend_batch, read_consistency)
+        .recommend_batch(
+            &collection_name,
+            recommend_batch,
+            read_consistency,
+            priority,
+            timeout,
+        )
         .await
         .map_err(error_to_status)?;
 
@@ -615,10 +732,118 @@ pub async fn recommend_batch(
     Ok(Response::new(response))
 }
 
+pub async fn query(
+    toc: &TableOfContent,
+    query_points: QueryPoints,
+    access: &ReadAccess,
+) -> Result<Response<QueryResponse>, Status> {
+    let collection_name = query_points.collection_name.clone();
+    let read_consistency =
+        ReadConsistency::try_from_optional(query_points.read_consistency.clone())?;
+    let priority = search_priority_from_proto(query_points.priority)?;
+    let mut request: QueryRequest = query_points.try_into()?;
+
+    let timing = Instant::now();
+    check_request_filters(
+        toc,
+        &collection_name,
+        request
+            .prefetch
+            .iter()
+            .map(|prefetch| prefetch.filter.as_ref())
+            .chain([request.filter.as_ref()]),
+        None,
+        None,
+    )
+    .await
+    .map_err(error_to_status)?;
+    restrict_query_request(toc, &collection_name, &mut request, access)
+        .await
+        .map_err(error_to_status)?;
+    let points = toc
+        .query(&collection_name, request, read_consistency, priority)
+        .await
+        .map_err(error_to_status)?;
+
+    let response = QueryResponse {
+        result: points.into_iter().map(|point| point.into()).collect(),
+        time: timing.elapsed().as_secs_f64(),
+    };
+
+    Ok(Response::new(response))
+}
+
+pub async fn discover(
+    toc: &TableOfContent,
+    discover_points: DiscoverPoints,
+    access: &ReadAccess,
+) -> Result<Response<DiscoverResponse>, Status> {
+    let collection_name = discover_points.collection_name.clone();
+    let read_consistency =
+        ReadConsistency::try_from_optional(discover_points.read_consistency.clone())?;
+    let priority = search_priority_from_proto(discover_points.priority)?;
+    let mut request: DiscoverRequest = discover_points.try_into()?;
+
+    let timing = Instant::now();
+    check_request_filters(toc, &collection_name, [request.filter.as_ref()], None, None)
+        .await
+        .map_err(error_to_status)?;
+    restrict_discover_request(toc, &collection_name, &mut request, access)
+        .await
+        .map_err(error_to_status)?;
+    let points = toc
+        .discover(&collection_name, request, read_consistency, priority)
+        .await
+        .map_err(error_to_status)?;
+
+    let response = DiscoverResponse {
+        result: points.into_iter().map(|point| point.into()).collect(),
+        time: timing.elapsed().as_secs_f64(),
+    };
+
+    Ok(Response::new(response))
+}
+
+pub async fn search_arithmetic(
+    toc: &TableOfContent,
+    search_arithmetic_points: SearchArithmeticPoints,
+    access: &ReadAccess,
+) -> Result<Response<SearchArithmeticResponse>, Status> {
+    let collection_name = search_arithmetic_points.collection_name.clone();
+    let read_consistency =
+        ReadConsistency::try_from_optional(search_arithmetic_points.read_consistency.clone())?;
+    let priority = search_priority_from_proto(search_arithmetic_points.priority)?;
+    let request: ArithmeticSearchRequest = search_arithmetic_points.try_into()?;
+
+    let timing = Instant::now();
+    let scored_points = do_search_arithmetic_points(
+        toc,
+        &collection_name,
+        request,
+        read_consistency,
+        priority,
+        None,
+        access,
+    )
+    .await
+    .map_err(error_to_status)?;
+
+    let response = SearchArithmeticResponse {
+        result: scored_points
+            .into_iter()
+            .map(|point| point.into())
+            .collect(),
+        time: timing.elapsed().as_secs_f64(),
+    };
+
+    Ok(Response::new(response))
+}
+
 pub async fn scroll(
     toc: &TableOfContent,
     scroll_points: ScrollPoints,
     shard_selection: Option<ShardId>,
+    access: &ReadAccess,
 ) -> Result<Response<ScrollResponse>, Status> {
     let ScrollPoints {
         collection_name,
@@ -628,6 +853,12 @@ pub async fn scroll(
         with_payload,
         with_vectors,
         read_consistency,
+        order,
+        seed,
+        planner_hint,
+        geo_order,
+        order_by,
+        timeout,
     } = scroll_points;
 
     let scroll_request = ScrollRequest {
@@ -638,6 +869,11 @@ pub async fn scroll(
         with_vector: with_vectors
             .map(|selector| selector.into())
             .unwrap_or_default(),
+        order: scroll_order_from_proto(order)?,
+        seed,
+        planner_hint: planner_hint_from_proto(planner_hint)?,
+        geo_order: geo_order.map(|order| order.try_into()).transpose()?,
+        order_by: order_by.map(|order_by| order_by.try_into()).transpose()?,
     };
 
     let read_consistency = ReadConsistency::try_from_optional(read_consistency)?;
@@ -649,6 +885,10 @@ pub async fn scroll(
         scroll_request,
         read_consistency,
         shard_selection,
+        None,
+        timeout.map(Duration::from_secs),
+        None,
+        access,
     )
     .await
     .map_err(error_to_status)?;
@@ -658,9 +898,10 @@ pub async fn scroll(
         result: scrolled_points
             .points
             .into_iter()
-            .map(|point| point.into())
-            .collect(),
+            .map(|point| point.try_into())
+            .collect::<Result<_, _>>()?,
         time: timing.elapsed().as_secs_f64(),
+        next_page_order_value: scrolled_points.next_page_order_value,
     };
 
     Ok(Response::new(response))
@@ -670,22 +911,35 @@ pub async fn count(
     toc: &TableOfContent,
     count_points: CountPoints,
     shard_selection: Option<ShardId>,
+    access: &ReadAccess,
 ) -> Result<Response<CountResponse>, Status> {
     let CountPoints {
         collection_name,
         filter,
         exact,
+        planner_hint,
+        distinct,
     } = count_points;
 
     let count_request = collection::operations::types::CountRequest {
         filter: filter.map(|f| f.try_into()).transpose()?,
         exact: exact.unwrap_or_else(default_exact_count),
+        planner_hint: planner_hint_from_proto(planner_hint)?,
+        distinct,
     };
 
     let timing = Instant::now();
-    let count_result = do_count_points(toc, &collection_name, count_request, shard_selection)
-        .await
-        .map_err(error_to_status)?;
+    let count_result = do_count_points(
+        toc,
+        &collection_name,
+        count_request,
+        shard_selection,
+        None,
+        None,
+        access,
+    )
+    .await
+    .map_err(error_to_status)?;
 
     let response = CountResponse {
         result: Some(count_result.into()),
@@ -699,6 +953,7 @@ pub async fn get(
     toc: &TableOfContent,
     get_points: GetPoints,
     shard_selection: Option<ShardId>,
+    access: &ReadAccess,
 ) -> Result<Response<GetResponse>, Status> {
     let GetPoints {
         collection_name,
@@ -729,12 +984,17 @@ pub async fn get(
         point_request,
         read_consistency,
         shard_selection,
+        None,
+        access,
     )
     .await
     .map_err(error_to_status)?;
 
     let response = GetResponse {
-        result: records.into_iter().map(|point| point.into()).collect(),
+        result: records
+            .into_iter()
+            .map(|point| point.try_into())
+            .collect::<Result<_, _>>()?,
         time: timing.elapsed().as_secs_f64(),
     };
 
@@ -1,21 +1,28 @@
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use api::grpc::qdrant::points_internal_server::PointsInternal;
 use api::grpc::qdrant::{
     ClearPayloadPointsInternal, CountPointsInternal, CountResponse,
     CreateFieldIndexCollectionInternal, DeleteFieldIndexCollectionInternal,
     DeletePayloadPointsInternal, DeletePointsInternal, GetPointsInternal, GetResponse,
+    IdChunksInternal, IdChunksResponse, PointVersion, PointVersionsInternal, PointVersionsResponse,
     PointsOperationResponse, RecommendPointsInternal, RecommendResponse, ScrollPointsInternal,
     ScrollResponse, SearchBatchPointsInternal, SearchBatchResponse, SearchPointsInternal,
-    SearchResponse, SetPayloadPointsInternal, SyncPointsInternal, UpsertPointsInternal,
+    SearchPriority, SearchResponse, SetPayloadPointsInternal, SyncPointsInternal,
+    UpdateVectorsInternal, UpsertPointsInternal, WaitAppliedInternal, WaitAppliedResponse,
 };
+use segment::types::PointIdType;
+use storage::content_manager::conversions::error_to_status;
 use storage::content_manager::toc::TableOfContent;
 use tonic::{Request, Response, Status};
 
 use super::validate_and_log;
+use crate::common::acl::ReadAccess;
 use crate::tonic::api::points_common::{
     clear_payload, count, create_field_index, delete, delete_field_index, delete_payload, get,
-    overwrite_payload, recommend, scroll, search, search_batch, set_payload, sync, upsert,
+    overwrite_payload, recommend, scroll, search, search_batch, set_payload, sync, update_vectors,
+    upsert,
 };
 
 /// This API is intended for P2P communication within a distributed deployment.
@@ -158,7 +165,16 @@ impl PointsInternal for PointsInternalService {
 
         search_points.read_consistency = None; // *Have* to be `None`!
 
-        search(self.toc.as_ref(), search_points, shard_id).await
+        // The original request was already admitted into the search queue of the first node
+        search_points.priority = Some(SearchPriority::High as i32);
+
+        search(
+            self.toc.as_ref(),
+            search_points,
+            shard_id,
+            &ReadAccess::Internal,
+        )
+        .await
     }
 
     async fn search_batch(
@@ -183,7 +199,10 @@ impl PointsInternal for PointsInternalService {
             collection_name,
             search_points,
             None, // *Have* to be `None`!
+            Some(SearchPriority::High as i32),
+            None,
             shard_id,
+            &ReadAccess::Internal,
         )
         .await
     }
@@ -203,8 +222,9 @@ impl PointsInternal for PointsInternalService {
             .ok_or_else(|| Status::invalid_argument("RecommendPoints is missing"))?;
 
         recommend_points.read_consistency = None; // *Have* to be `None`!
+        recommend_points.priority = Some(SearchPriority::High as i32);
 
-        recommend(self.toc.as_ref(), recommend_points).await
+        recommend(self.toc.as_ref(), recommend_points, &ReadAccess::Internal).await
     }
 
     async fn scroll(
@@ -222,7 +242,13 @@ impl PointsInternal for PointsInternalService {
 
         scroll_points.read_consistency = None; // *Have* to be `None`!
 
-        scroll(self.toc.as_ref(), scroll_points, shard_id).await
+        scroll(
+            self.toc.as_ref(),
+            scroll_points,
+            shard_id,
+            &ReadAccess::Internal,
+        )
+        .await
     }
 
     async fn get(
@@ -240,7 +266,13 @@ impl PointsInternal for PointsInternalService {
 
         get_points.read_consistency = None; // *Have* to be `None`!
 
-        get(self.toc.as_ref(), get_points, shard_id).await
+        get(
+            self.toc.as_ref(),
+            get_points,
+            shard_id,
+            &ReadAccess::Internal,
+        )
+        .await
     }
 
     async fn count(
@@ -255,7 +287,13 @@ impl PointsInternal for PointsInternalService {
 
         let count_points =
             count_points.ok_or_else(|| Status::invalid_argument("CountPoints is missing"))?;
-        count(self.toc.as_ref(), count_points, shard_id).await
+        count(
+            self.toc.as_ref(),
+            count_points,
+            shard_id,
+            &ReadAccess::Internal,
+        )
+        .await
     }
 
     async fn sync(
@@ -287,4 +325,112 @@ impl PointsInternal for PointsInternalService {
 
         overwrite_payload(self.toc.as_ref(), set_payload_points, shard_id).await
     }
+
+    async fn id_chunks(
+        &self,
+        request: Request<IdChunksInternal>,
+    ) -> Result<Response<IdChunksResponse>, Status> {
+        validate_and_log(request.get_ref());
+        let IdChunksInternal {
+            collection_name,
+            chunks,
+            shard_id,
+        } = request.into_inner();
+        let shard_id = shard_id.ok_or_else(|| Status::invalid_argument("shard_id is missing"))?;
+
+        let timing = Instant::now();
+        let collection = self
+            .toc
+            .get_collection(&collection_name)
+            .await
+            .map_err(error_to_status)?;
+        let chunks = collection
+            .local_id_chunks(shard_id, chunks as usize)
+            .await
+            .map_err(|err| error_to_status(err.into()))?;
+        Ok(Response::new(IdChunksResponse {
+            result: chunks.into_iter().map(Into::into).collect(),
+            time: timing.elapsed().as_secs_f64(),
+        }))
+    }
+
+    async fn point_versions(
+        &self,
+        request: Request<PointVersionsInternal>,
+    ) -> Result<Response<PointVersionsResponse>, Status> {
+        validate_and_log(request.get_ref());
+        let PointVersionsInternal {
+            collection_name,
+            ids,
+            shard_id,
+        } = request.into_inner();
+        let shard_id = shard_id.ok_or_else(|| Status::invalid_argument("shard_id is missing"))?;
+        let ids = ids
+            .into_iter()
+            .map(PointIdType::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let timing = Instant::now();
+        let collection = self
+            .toc
+            .get_collection(&collection_name)
+            .await
+            .map_err(error_to_status)?;
+        let versions = collection
+            .local_point_versions(shard_id, &ids)
+            .await
+            .map_err(|err| error_to_status(err.into()))?;
+        Ok(Response::new(PointVersionsResponse {
+            result: versions
+                .into_iter()
+                .map(|(id, version)| PointVersion {
+                    id: Some(id.into()),
+                    version,
+                })
+                .collect(),
+            time: timing.elapsed().as_secs_f64(),
+        }))
+    }
+
+    async fn wait_applied(
+        &self,
+        request: Request<WaitAppliedInternal>,
+    ) -> Result<Response<WaitAppliedResponse>, Status> {
+        validate_and_log(request.get_ref());
+        let WaitAppliedInternal {
+            collection_name,
+            version,
+            timeout,
+            shard_id,
+        } = request.into_inner();
+        let shard_id = shard_id.ok_or_else(|| Status::invalid_argument("shard_id is missing"))?;
+
+        let timing = Instant::now();
+        let collection = self
+            .toc
+            .get_collection(&collection_name)
+            .await
+            .map_err(error_to_status)?;
+        collection
+            .local_wait_applied(shard_id, version, Duration::from_secs(timeout))
+            .await
+            .map_err(|err| error_to_status(err.into()))?;
+        Ok(Response::new(WaitAppliedResponse {
+            time: timing.elapsed().as_secs_f64(),
+        }))
+    }
+
+    async fn update_vectors(
+        &self,
+        request: Request<UpdateVectorsInternal>,
+    ) -> Result<Response<PointsOperationResponse>, Status> {
+        validate_and_log(request.get_ref());
+        let UpdateVectorsInternal {
+            update_vectors: update_point_vectors,
+            shard_id,
+        } = request.into_inner();
+        let update_point_vectors = update_point_vectors
+            .ok_or_else(|| Status::invalid_argument("UpdatePointVectors is missing"))?;
+        update_vectors(self.toc.as_ref(), update_point_vectors, shard_id).await
+    }
 }
@@ -15,7 +15,7 @@ use storage::content_manager::snapshots::{
 use storage::dispatcher::Dispatcher;
 use tonic::{async_trait, Request, Response, Status};
 
-use super::validate;
+use super::{read_access, validate};
 use crate::common::collections::{do_create_snapshot, do_list_snapshots};
 
 pub struct SnapshotsService {
@@ -35,10 +35,11 @@ impl Snapshots for SnapshotsService {
         request: Request<CreateSnapshotRequest>,
     ) -> Result<Response<CreateSnapshotResponse>, Status> {
         validate(request.get_ref())?;
+        let access = read_access(&request);
         let collection_name = request.into_inner().collection_name;
         let timing = Instant::now();
         let dispatcher = self.dispatcher.clone();
-        let response = do_create_snapshot(&dispatcher, &collection_name, true)
+        let response = do_create_snapshot(&dispatcher, &collection_name, &access, true)
             .await
             .map_err(error_to_status)?;
         Ok(Response::new(CreateSnapshotResponse {
@@ -52,10 +53,11 @@ impl Snapshots for SnapshotsService {
         request: Request<ListSnapshotsRequest>,
     ) -> Result<Response<ListSnapshotsResponse>, Status> {
         validate(request.get_ref())?;
+        let access = read_access(&request);
         let collection_name = request.into_inner().collection_name;
 
         let timing = Instant::now();
-        let snapshots = do_list_snapshots(&self.dispatcher, &collection_name)
+        let snapshots = do_list_snapshots(&self.dispatcher, &collection_name, &access)
             .await
             .map_err(error_to_status)?;
         Ok(Response::new(ListSnapshotsResponse {
@@ -88,8 +90,9 @@ impl Snapshots for SnapshotsService {
         request: Request<CreateFullSnapshotRequest>,
     ) -> Result<Response<CreateSnapshotResponse>, Status> {
         validate(request.get_ref())?;
+        let access = read_access(&request);
         let timing = Instant::now();
-        let response = do_create_full_snapshot(&self.dispatcher, true)
+        let response = do_create_full_snapshot(&self.dispatcher, &access, true)
             .await
             .map_err(error_to_status)?;
         Ok(Response::new(CreateSnapshotResponse {
@@ -103,8 +106,9 @@ impl Snapshots for SnapshotsService {
         request: Request<ListFullSnapshotsRequest>,
     ) -> Result<Response<ListSnapshotsResponse>, Status> {
         validate(request.get_ref())?;
+        let access = read_access(&request);
         let timing = Instant::now();
-        let snapshots = do_list_full_snapshots(&self.dispatcher)
+        let snapshots = do_list_full_snapshots(&self.dispatcher, &access)
             .await
             .map_err(error_to_status)?;
         Ok(Response::new(ListSnapshotsResponse {
@@ -13,6 +13,7 @@ use ::api::grpc::qdrant::points_server::PointsServer;
 use ::api::grpc::qdrant::qdrant_server::{Qdrant, QdrantServer};
 use ::api::grpc::qdrant::snapshots_server::SnapshotsServer;
 use ::api::grpc::qdrant::{HealthCheckReply, HealthCheckRequest};
+use arrow_flight::flight_service_server::FlightServiceServer;
 use storage::content_manager::consensus_manager::ConsensusStateRef;
 use storage::content_manager::toc::TableOfContent;
 use storage::dispatcher::Dispatcher;
@@ -27,6 +28,7 @@ use crate::common::telemetry_ops::requests_telemetry::TonicTelemetryCollector;
 use crate::settings::Settings;
 use crate::tonic::api::collections_api::CollectionsService;
 use crate::tonic::api::collections_internal_api::CollectionsInternalService;
+use crate::tonic::api::flight_api::FlightExportService;
 use crate::tonic::api::points_api::PointsService;
 use crate::tonic::api::points_internal_api::PointsInternalService;
 use crate::tonic::api::snapshots_api::SnapshotsService;
@@ -59,6 +61,7 @@ pub fn init(
         let collections_service = CollectionsService::new(dispatcher.clone());
         let points_service = PointsService::new(dispatcher.toc().clone());
         let snapshot_service = SnapshotsService::new(dispatcher.clone());
+        let flight_service = FlightExportService::new(dispatcher.toc().clone());
 
         log::info!("Qdrant gRPC listening on {}", grpc_port);
 
@@ -100,6 +103,8 @@ pub fn init(
                     .accept_compressed(CompressionEncoding::Gzip)
                     .max_decoding_message_size(usize::MAX),
             )
+            // Arrow Flight clients don't negotiate gzip, record batches are sent uncompressed
+            .add_service(FlightServiceServer::new(flight_service))
             .serve_with_shutdown(socket, async {
                 signal::ctrl_c().await.unwrap();
                 log::debug!("Stopping gRPC");